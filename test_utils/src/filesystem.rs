@@ -1,25 +1,83 @@
 use alloc::{
+    borrow::ToOwned,
     boxed::Box,
+    format,
     string::{String, ToString},
     vec::Vec,
 };
 use core::cmp::min;
 
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use spin::Mutex;
 
-use wie_backend::Filesystem;
+use wie_backend::{DirEntry, FileMetadata, Filesystem};
 
-/// In-memory `Filesystem` implementation for tests.
+/// In-memory `Filesystem` implementation for tests. Directories are
+/// implied by file paths and can also be created explicitly; timestamps
+/// are not tracked.
 #[derive(Default)]
 pub struct MemoryFilesystem {
     files: Mutex<HashMap<(String, String), Vec<u8>>>,
+    dirs: Mutex<HashSet<(String, String)>>,
 }
 
 impl MemoryFilesystem {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn is_dir(&self, aid: &str, path: &str) -> bool {
+        if path.is_empty() || self.dirs.lock().contains(&(aid.to_string(), path.to_string())) {
+            return true;
+        }
+
+        let prefix = format!("{path}/");
+        self.files.lock().keys().any(|(a, p)| a == aid && p.starts_with(&prefix))
+    }
+
+    fn children(&self, aid: &str, path: &str) -> Vec<DirEntry> {
+        let prefix = if path.is_empty() { String::new() } else { format!("{path}/") };
+        let mut result: Vec<DirEntry> = Vec::new();
+
+        let files = self
+            .files
+            .lock()
+            .keys()
+            .filter(|(a, _)| a == aid)
+            .map(|(_, p)| (p.clone(), false))
+            .collect::<Vec<_>>();
+        let dirs = self
+            .dirs
+            .lock()
+            .iter()
+            .filter(|(a, _)| a == aid)
+            .map(|(_, p)| (p.clone(), true))
+            .collect::<Vec<_>>();
+        for (entry_path, is_dir) in files.into_iter().chain(dirs) {
+            let Some(rest) = entry_path.strip_prefix(&prefix) else {
+                continue;
+            };
+            let (name, is_dir) = match rest.split_once('/') {
+                Some((name, _)) => (name, true),
+                None => (rest, is_dir),
+            };
+            if !name.is_empty() && !result.iter().any(|e| e.name == name) {
+                result.push(DirEntry {
+                    name: name.to_owned(),
+                    is_dir,
+                });
+            }
+        }
+
+        result
+    }
+
+    fn create_parents(&self, aid: &str, path: &str) {
+        let mut dirs = self.dirs.lock();
+        for (index, _) in path.match_indices('/') {
+            dirs.insert((aid.to_string(), path[..index].to_string()));
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -46,6 +104,8 @@ impl Filesystem for MemoryFilesystem {
     }
 
     async fn write(&self, aid: &str, path: &str, offset: usize, data: &[u8]) -> usize {
+        self.create_parents(aid, path);
+
         let mut files = self.files.lock();
         let file = files.entry((aid.to_string(), path.to_string())).or_default();
         if file.len() < offset + data.len() {
@@ -57,8 +117,74 @@ impl Filesystem for MemoryFilesystem {
     }
 
     async fn truncate(&self, aid: &str, path: &str, len: usize) {
+        self.create_parents(aid, path);
+
         let mut files = self.files.lock();
         let file = files.entry((aid.to_string(), path.to_string())).or_default();
         file.resize(len, 0);
     }
+
+    async fn metadata(&self, aid: &str, path: &str) -> Option<FileMetadata> {
+        if let Some(size) = self.size(aid, path).await {
+            return Some(FileMetadata {
+                is_dir: false,
+                size,
+                created: None,
+                modified: None,
+            });
+        }
+
+        self.is_dir(aid, path).then_some(FileMetadata {
+            is_dir: true,
+            size: 0,
+            created: None,
+            modified: None,
+        })
+    }
+
+    async fn list(&self, aid: &str, path: &str) -> Option<Vec<DirEntry>> {
+        if !self.is_dir(aid, path) {
+            return None;
+        }
+
+        Some(self.children(aid, path))
+    }
+
+    async fn remove(&self, aid: &str, path: &str) -> bool {
+        self.files.lock().remove(&(aid.to_string(), path.to_string())).is_some()
+    }
+
+    async fn rename(&self, aid: &str, from: &str, to: &str) -> bool {
+        if self.is_dir(aid, to) {
+            return false;
+        }
+        let Some(data) = self.files.lock().remove(&(aid.to_string(), from.to_string())) else {
+            return false;
+        };
+
+        self.create_parents(aid, to);
+        self.files.lock().insert((aid.to_string(), to.to_string()), data);
+
+        true
+    }
+
+    async fn create_dir(&self, aid: &str, path: &str) -> bool {
+        let mut ancestors = path.match_indices('/').map(|(index, _)| &path[..index]).chain(core::iter::once(path));
+        if ancestors.any(|ancestor| self.files.lock().contains_key(&(aid.to_string(), ancestor.to_string()))) {
+            return false;
+        }
+
+        self.create_parents(aid, path);
+        self.dirs.lock().insert((aid.to_string(), path.to_string()));
+
+        true
+    }
+
+    async fn remove_dir(&self, aid: &str, path: &str) -> bool {
+        if path.is_empty() || !self.is_dir(aid, path) || !self.children(aid, path).is_empty() {
+            return false;
+        }
+
+        self.dirs.lock().remove(&(aid.to_string(), path.to_string()))
+    }
 }
@@ -18,7 +18,15 @@ where
     T: FnOnce(Jvm) -> F + Send + 'static,
     F: Future<Output = JvmResult<()>> + Send,
 {
-    let mut system = System::new(Box::new(TestPlatform::new()), "", "", DefaultTaskRunner);
+    run_jvm_test_with_platform(TestPlatform::new(), protos, func)
+}
+
+pub fn run_jvm_test_with_platform<T, F>(platform: TestPlatform, protos: Box<[Box<[WieJavaClassProto]>]>, func: T) -> Result<()>
+where
+    T: FnOnce(Jvm) -> F + Send + 'static,
+    F: Future<Output = JvmResult<()>> + Send,
+{
+    let mut system = System::new(Box::new(platform), "", "", DefaultTaskRunner);
 
     let done = Arc::new(AtomicBool::new(false));
     let done_clone = done.clone();
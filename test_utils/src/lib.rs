@@ -3,10 +3,12 @@ extern crate alloc;
 
 mod filesystem;
 mod jvm;
+mod network;
 mod platform;
 
 pub use self::{
     filesystem::MemoryFilesystem,
-    jvm::run_jvm_test,
+    jvm::{run_jvm_test, run_jvm_test_with_platform},
+    network::LoopbackNetwork,
     platform::{TestPlatform, TestPlatformEvent},
 };
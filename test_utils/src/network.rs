@@ -0,0 +1,123 @@
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+
+use hashbrown::HashMap;
+
+use wie_backend::{DatagramSocket, Network, NetworkError, Socket};
+
+const LOCALHOST: [u8; 4] = [127, 0, 0, 1];
+
+type Service = Arc<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
+
+/// In-process `Network` for tests. Only `localhost` resolves, and each port is served by a
+/// closure that answers every write or datagram sent to it.
+#[derive(Default)]
+pub struct LoopbackNetwork {
+    services: HashMap<u16, Service>,
+}
+
+impl LoopbackNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_service<F>(mut self, port: u16, service: F) -> Self
+    where
+        F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static,
+    {
+        self.services.insert(port, Arc::new(service));
+        self
+    }
+
+    /// Serves `port` with a service sending everything back.
+    pub fn with_echo(self, port: u16) -> Self {
+        self.with_service(port, |data| data.to_vec())
+    }
+}
+
+impl Network for LoopbackNetwork {
+    fn resolve(&self, host: &str) -> Result<[u8; 4], NetworkError> {
+        if host == "localhost" {
+            Ok(LOCALHOST)
+        } else {
+            Err(NetworkError::HostNotFound)
+        }
+    }
+
+    fn connect(&self, address: [u8; 4], port: u16) -> Result<Box<dyn Socket>, NetworkError> {
+        match self.services.get(&port) {
+            Some(service) if address == LOCALHOST => Ok(Box::new(LoopbackSocket {
+                service: service.clone(),
+                incoming: Vec::new(),
+            })),
+            _ => Err(NetworkError::ConnectionRefused),
+        }
+    }
+
+    fn bind_datagram(&self) -> Result<Box<dyn DatagramSocket>, NetworkError> {
+        Ok(Box::new(LoopbackDatagramSocket {
+            services: self.services.clone(),
+            incoming: VecDeque::new(),
+        }))
+    }
+}
+
+struct LoopbackSocket {
+    service: Service,
+    incoming: Vec<u8>,
+}
+
+impl Socket for LoopbackSocket {
+    fn poll_connect(&mut self) -> Result<(), NetworkError> {
+        Ok(())
+    }
+
+    fn poll_readable(&mut self) -> Result<(), NetworkError> {
+        if self.incoming.is_empty() {
+            Err(NetworkError::WouldBlock)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, NetworkError> {
+        self.poll_readable()?;
+
+        let length = self.incoming.len().min(buf.len());
+        buf[..length].copy_from_slice(&self.incoming[..length]);
+        self.incoming.drain(..length);
+
+        Ok(length)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<usize, NetworkError> {
+        self.incoming.extend((self.service)(data));
+
+        Ok(data.len())
+    }
+}
+
+struct LoopbackDatagramSocket {
+    services: HashMap<u16, Service>,
+    incoming: VecDeque<(Vec<u8>, u16)>,
+}
+
+impl DatagramSocket for LoopbackDatagramSocket {
+    fn send_to(&mut self, address: [u8; 4], port: u16, data: &[u8]) -> Result<usize, NetworkError> {
+        // datagrams nobody listens to are lost
+        if let Some(service) = self.services.get(&port).filter(|_| address == LOCALHOST) {
+            self.incoming.push_back((service(data), port));
+        }
+
+        Ok(data.len())
+    }
+
+    fn recv_from(&mut self, buf: &mut [u8]) -> Result<(usize, [u8; 4], u16), NetworkError> {
+        let (data, port) = self.incoming.pop_front().ok_or(NetworkError::WouldBlock)?;
+
+        // excess bytes are discarded, like a truncated datagram
+        let length = data.len().min(buf.len());
+        buf[..length].copy_from_slice(&data[..length]);
+
+        Ok((length, LOCALHOST, port))
+    }
+}
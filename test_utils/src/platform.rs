@@ -8,7 +8,10 @@ use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 use hashbrown::HashMap;
 use spin::Mutex;
-use wie_backend::{AudioSink, Database, DatabaseRepository, Filesystem, Instant, Platform, RecordId, Screen, canvas::Image};
+use wie_backend::{
+    AudioSink, DEFAULT_STORAGE_QUOTA, Database, DatabaseRepository, DeviceProfile, Filesystem, Instant, Network, OfflineNetwork, Platform, RecordId,
+    Screen, canvas::Image,
+};
 use wie_util::Result;
 
 use crate::filesystem::MemoryFilesystem;
@@ -25,6 +28,9 @@ pub struct TestPlatform {
     event_handler: Option<Box<dyn Fn(TestPlatformEvent) + Sync + Send>>,
     fs: Arc<MemoryFilesystem>,
     db: Arc<MemoryDatabaseRepository>,
+    storage_quota: u64,
+    device_profile: DeviceProfile,
+    network: Option<Box<dyn Network>>,
 }
 
 impl Default for TestPlatform {
@@ -40,6 +46,9 @@ impl TestPlatform {
             event_handler: None,
             fs: Arc::new(MemoryFilesystem::default()),
             db: Arc::new(MemoryDatabaseRepository::default()),
+            storage_quota: DEFAULT_STORAGE_QUOTA,
+            device_profile: DeviceProfile::default(),
+            network: None,
         }
     }
 
@@ -52,8 +61,29 @@ impl TestPlatform {
             event_handler: Some(Box::new(event_handler)),
             fs: Arc::new(MemoryFilesystem::default()),
             db: Arc::new(MemoryDatabaseRepository::default()),
+            storage_quota: DEFAULT_STORAGE_QUOTA,
+            device_profile: DeviceProfile::default(),
+            network: None,
         }
     }
+
+    pub fn with_storage_quota(mut self, storage_quota: u64) -> Self {
+        self.storage_quota = storage_quota;
+        self
+    }
+
+    pub fn with_device_profile(mut self, device_profile: DeviceProfile) -> Self {
+        self.device_profile = device_profile;
+        self
+    }
+
+    pub fn with_network<T>(mut self, network: T) -> Self
+    where
+        T: Network + 'static,
+    {
+        self.network = Some(Box::new(network));
+        self
+    }
 }
 
 impl Platform for TestPlatform {
@@ -93,6 +123,18 @@ impl Platform for TestPlatform {
     }
 
     fn vibrate(&self, _duration_ms: u64, _intensity: u8) {}
+
+    fn storage_quota(&self, _aid: &str) -> u64 {
+        self.storage_quota
+    }
+
+    fn device_profile(&self) -> DeviceProfile {
+        self.device_profile.clone()
+    }
+
+    fn network(&self) -> &dyn Network {
+        self.network.as_deref().unwrap_or(&OfflineNetwork)
+    }
 }
 
 type DatabaseKey = (String, String);
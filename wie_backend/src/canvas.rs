@@ -1,17 +1,20 @@
 mod lbmp;
+mod sis;
 
 use alloc::{borrow::Cow, boxed::Box, string::ToString, vec, vec::Vec};
 use core::mem::size_of;
 
 use ab_glyph::{Font, FontRef, ScaleFont};
 use bytemuck::{Pod, cast_slice, pod_collect_to_vec};
-use image::ImageReader;
+use image::{AnimationDecoder, ImageReader, RgbaImage, codecs::gif::GifDecoder};
 use num_traits::{Num, Zero};
 
 use wie_util::{Result, WieError};
 
 use self::lbmp::decode_lbmp;
 
+pub use self::sis::{SisFrame, SisImage, SisObject, SisPlacement, decode_sis, is_sis};
+
 lazy_static::lazy_static! {
     static ref FONT: FontRef<'static> = FontRef::try_from_slice(include_bytes!("../../fonts/neodgm.ttf")).unwrap();
 }
@@ -884,6 +887,11 @@ impl Clip {
     }
 }
 
+pub struct ImageFrame {
+    pub image: Box<dyn Image>,
+    pub delay: u32,
+}
+
 pub fn decode_image(data: &[u8]) -> Result<Box<dyn Image>> {
     extern crate std; // XXX
 
@@ -898,15 +906,48 @@ pub fn decode_image(data: &[u8]) -> Result<Box<dyn Image>> {
         .map_err(|x| WieError::FatalError(x.to_string()))?
         .decode()
         .map_err(|x| WieError::FatalError(x.to_string()))?;
-    let rgba = image.into_rgba8();
 
+    Ok(rgba_to_image(image.into_rgba8()))
+}
+
+// decodes every frame of an animated gif, fully composed with the previous frames' disposal applied.
+// other formats give a single frame
+pub fn decode_image_frames(data: &[u8]) -> Result<Vec<ImageFrame>> {
+    extern crate std; // XXX
+
+    use std::io::Cursor;
+
+    if !data.starts_with(b"GIF8") {
+        return Ok(vec![ImageFrame {
+            image: decode_image(data)?,
+            delay: 0,
+        }]);
+    }
+
+    let decoder = GifDecoder::new(Cursor::new(data)).map_err(|x| WieError::FatalError(x.to_string()))?;
+    let frames = decoder.into_frames().collect_frames().map_err(|x| WieError::FatalError(x.to_string()))?;
+
+    Ok(frames
+        .into_iter()
+        .map(|frame| {
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+
+            ImageFrame {
+                delay: numerator / denominator.max(1),
+                image: rgba_to_image(frame.into_buffer()),
+            }
+        })
+        .collect())
+}
+
+fn rgba_to_image(rgba: RgbaImage) -> Box<dyn Image> {
     let data = rgba.pixels().flat_map(|x| [x.0[2], x.0[1], x.0[0], x.0[3]]).collect::<Vec<_>>();
 
-    Ok(Box::new(VecImageBuffer::<ArgbPixel>::from_raw(
+    Box::new(VecImageBuffer::<ArgbPixel>::from_raw(
         rgba.width(),
         rgba.height(),
         pod_collect_to_vec(&data),
-    )) as Box<_>)
+    ))
 }
 
 pub fn string_width(string: &str, pt_size: f32) -> f32 {
@@ -1644,4 +1685,26 @@ mod tests {
         assert!(is_set(&image, 8, 16), "inside clip should be filled");
         assert!(!is_set(&image, 24, 16), "outside clip must not be filled");
     }
+
+    #[test]
+    fn test_decode_image_frames() -> Result<()> {
+        extern crate std;
+
+        use image::{Delay, Frame, Rgba, RgbaImage, codecs::gif::GifEncoder};
+
+        let mut data = Vec::new();
+        {
+            let frames = [Rgba([0xff, 0, 0, 0xff]), Rgba([0, 0, 0xff, 0xff])]
+                .map(|color| Frame::from_parts(RgbaImage::from_pixel(2, 2, color), 0, 0, Delay::from_numer_denom_ms(100, 1)));
+            GifEncoder::new(&mut data).encode_frames(frames).unwrap();
+        }
+
+        let frames = super::decode_image_frames(&data)?;
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames.iter().map(|x| x.delay).collect::<Vec<_>>(), [100, 100]);
+        assert_eq!(frames[0].image.get_pixel(1, 1).r, 0xff);
+        assert_eq!(frames[1].image.get_pixel(1, 1).b, 0xff);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,314 @@
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use core::mem::size_of;
+
+use wie_util::{Result, WieError};
+
+use crate::canvas::{ArgbPixel, Canvas, Clip, Color, Image, ImageBufferCanvas, VecImageBuffer};
+
+// animated image file format for skvm
+//
+// header: "SIS" magic, version u8, width u16, height u16, level u8, object count u8, frame count u8, reserved u8
+// object: width u16, height u16, flags u8 (bit 0 set if a transparency mask follows), pixel rows, mask rows
+// frame: delay u16 in milliseconds, placement count u8, then object id u8, x i16, y i16 for each placement
+//
+// values are little endian, pixels are packed msb first with `level` bits per pixel and every row is padded to a byte.
+// 1 and 2 bit pixels are grayscale levels with 0 as white, 8 bit pixels are rgb332. mask bits are set for opaque pixels.
+//
+// there is no public specification of this format. the layout above is reconstructed and has not yet been checked
+// against SIS files from shipped games, so decoding failures log the header bytes to help collect samples.
+
+const MAGIC: &[u8] = b"SIS";
+const HEADER_SIZE: usize = 12;
+
+pub struct SisObject {
+    pub width: u32,
+    pub height: u32,
+    // argb, with alpha cleared on pixels masked out as transparent
+    pixels: Vec<u32>,
+}
+
+impl SisObject {
+    pub fn image(&self, use_transparency: bool) -> Box<dyn Image> {
+        let pixels = if use_transparency {
+            self.pixels.clone()
+        } else {
+            self.pixels.iter().map(|x| x | 0xff000000).collect()
+        };
+
+        Box::new(VecImageBuffer::<ArgbPixel>::from_raw(self.width, self.height, pixels))
+    }
+}
+
+pub struct SisFrame {
+    pub delay: u32,
+    pub placements: Vec<SisPlacement>,
+}
+
+#[derive(Clone, Copy)]
+pub struct SisPlacement {
+    pub object: usize,
+    pub x: i32,
+    pub y: i32,
+}
+
+pub struct SisImage {
+    pub width: u32,
+    pub height: u32,
+    pub level: u8,
+    pub objects: Vec<SisObject>,
+    pub frames: Vec<SisFrame>,
+}
+
+impl SisImage {
+    // composes the frame's objects over a white background
+    pub fn frame(&self, index: usize) -> Result<Box<dyn Image>> {
+        let frame = self
+            .frames
+            .get(index)
+            .ok_or_else(|| WieError::FatalError(format!("Invalid SIS frame {index}")))?;
+        canvas_size(self.width, self.height, usize::MAX)?;
+
+        let mut canvas = ImageBufferCanvas::new(VecImageBuffer::<ArgbPixel>::new(self.width, self.height));
+        let clip = Clip {
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+        };
+        canvas.fill_rect(
+            0,
+            0,
+            self.width,
+            self.height,
+            Color {
+                a: 0xff,
+                r: 0xff,
+                g: 0xff,
+                b: 0xff,
+            },
+            clip,
+        );
+
+        for placement in &frame.placements {
+            let object = &self.objects[placement.object];
+            canvas.draw(placement.x, placement.y, object.width, object.height, &*object.image(true), 0, 0, clip);
+        }
+
+        Ok(Box::new(canvas.into_inner()) as _)
+    }
+
+    // bytes needed to hold every decoded object and a frame in argb
+    pub fn buffer_size(&self) -> usize {
+        let objects = self.objects.iter().map(|x| x.width as usize * x.height as usize).sum::<usize>();
+
+        (objects + self.width as usize * self.height as usize) * 4
+    }
+}
+
+pub fn is_sis(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn decode_sis(data: &[u8]) -> Result<SisImage> {
+    if !is_sis(data) || data.len() < HEADER_SIZE {
+        return Err(WieError::FatalError("Invalid SIS header".to_string()));
+    }
+
+    let mut reader = Reader { data, offset: 4 };
+
+    let width = reader.read_u16()? as u32;
+    let height = reader.read_u16()? as u32;
+    let level = reader.read_u8()?;
+    let object_count = reader.read_u8()? as usize;
+    let frame_count = reader.read_u8()? as usize;
+    reader.read_u8()?;
+
+    if ![1, 2, 8].contains(&level) {
+        return Err(WieError::Unimplemented(format!("Unsupported SIS level {level}")));
+    }
+    // frames are drawn from the object data, so a canvas needing more pixels than the data has bits is corrupt
+    canvas_size(width, height, (data.len() - HEADER_SIZE) * 8)?;
+
+    let objects = (0..object_count).map(|_| decode_object(&mut reader, level)).collect::<Result<Vec<_>>>()?;
+
+    let frames = (0..frame_count)
+        .map(|_| {
+            let delay = reader.read_u16()? as u32;
+            let placement_count = reader.read_u8()?;
+
+            let placements = (0..placement_count)
+                .map(|_| {
+                    let object = reader.read_u8()? as usize;
+                    let x = reader.read_u16()? as i16 as i32;
+                    let y = reader.read_u16()? as i16 as i32;
+
+                    if object >= objects.len() {
+                        return Err(WieError::FatalError(format!("Invalid SIS object id {object}")));
+                    }
+
+                    Ok(SisPlacement { object, x, y })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(SisFrame { delay, placements })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(SisImage {
+        width,
+        height,
+        level,
+        objects,
+        frames,
+    })
+}
+
+fn decode_object(reader: &mut Reader, level: u8) -> Result<SisObject> {
+    let width = reader.read_u16()? as u32;
+    let height = reader.read_u16()? as u32;
+    let flags = reader.read_u8()?;
+
+    let pixel_stride = (width as usize * level as usize).div_ceil(8);
+    let mask_stride = (width as usize).div_ceil(8);
+
+    let pixel_data = reader.read_bytes(checked_size(pixel_stride, height)?)?;
+    let mask_data = if flags & 1 != 0 {
+        Some(reader.read_bytes(checked_size(mask_stride, height)?)?)
+    } else {
+        None
+    };
+
+    let mut pixels = vec![0; width as usize * height as usize];
+    for y in 0..height as usize {
+        for x in 0..width as usize {
+            let value = read_bits(&pixel_data[y * pixel_stride..], x, level);
+            let opaque = mask_data.is_none_or(|mask| read_bits(&mask[y * mask_stride..], x, 1) != 0);
+
+            let rgb = match level {
+                8 => {
+                    let r = ((value >> 5) & 7) * 255 / 7;
+                    let g = ((value >> 2) & 7) * 255 / 7;
+                    let b = (value & 3) * 255 / 3;
+
+                    (r << 16) | (g << 8) | b
+                }
+                _ => {
+                    let max = (1 << level) - 1;
+                    let gray = (max - value) * 255 / max;
+
+                    (gray << 16) | (gray << 8) | gray
+                }
+            };
+
+            pixels[y * width as usize + x] = if opaque { rgb | 0xff000000 } else { rgb };
+        }
+    }
+
+    Ok(SisObject { width, height, pixels })
+}
+
+fn checked_size(stride: usize, height: u32) -> Result<usize> {
+    stride
+        .checked_mul(height as usize)
+        .ok_or_else(|| WieError::FatalError("SIS object is too large".to_string()))
+}
+
+// pixel count of a `width` x `height` canvas, if it is at most `limit`
+fn canvas_size(width: u32, height: u32, limit: usize) -> Result<usize> {
+    (width as usize)
+        .checked_mul(height as usize)
+        .filter(|&x| x <= limit && x.checked_mul(size_of::<u32>()).is_some())
+        .ok_or_else(|| WieError::FatalError(format!("Invalid SIS canvas size {width}x{height}")))
+}
+
+fn read_bits(row: &[u8], index: usize, bits: u8) -> u32 {
+    let bit = index * bits as usize;
+    let byte = row[bit / 8];
+    let shift = 8 - bits as usize - bit % 8;
+
+    ((byte >> shift) & ((1u16 << bits) - 1) as u8) as u32
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        let end = self.offset.checked_add(length).filter(|&x| x <= self.data.len());
+        let Some(end) = end else {
+            return Err(WieError::FatalError("Unexpected end of SIS data".to_string()));
+        };
+
+        let result = &self.data[self.offset..end];
+        self.offset = end;
+
+        Ok(result)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        let bytes = self.read_bytes(2)?;
+
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use wie_util::Result;
+
+    use super::decode_sis;
+
+    #[test]
+    fn test_decode_sis() -> Result<()> {
+        let mut data = vec![b'S', b'I', b'S', 1, 4, 0, 2, 0, 8, 1, 2, 0];
+        // 2x2 rgb332 object, red and green on the top row, blue and white below, top right pixel masked out
+        data.extend([2, 0, 2, 0, 1, 0xe0, 0x1c, 0x03, 0xff, 0b1000_0000, 0b1100_0000]);
+        // first frame draws the object at the origin, second one at (2, 0)
+        data.extend([100, 0, 1, 0, 0, 0, 0, 0]);
+        data.extend([200, 0, 1, 0, 2, 0, 0, 0]);
+
+        let image = decode_sis(&data)?;
+        assert_eq!((image.width, image.height, image.level), (4, 2, 8));
+        assert_eq!(image.objects.len(), 1);
+        assert_eq!(image.frames.iter().map(|x| x.delay).collect::<vec::Vec<_>>(), [100, 200]);
+
+        let object = image.objects[0].image(true);
+        assert_eq!(object.get_pixel(0, 0).r, 0xff);
+        assert_eq!(object.get_pixel(1, 0).a, 0);
+        assert_eq!(image.objects[0].image(false).get_pixel(1, 0).a, 0xff);
+
+        let frame = image.frame(1).unwrap();
+        assert_eq!(frame.get_pixel(0, 0).r, 0xff);
+        assert_eq!(frame.get_pixel(0, 0).g, 0xff);
+        assert_eq!(frame.get_pixel(3, 0).r, 0xff);
+        assert_eq!(frame.get_pixel(3, 0).g, 0xff);
+        assert_eq!(frame.get_pixel(2, 0).g, 0);
+        assert_eq!(frame.get_pixel(2, 1).b, 0xff);
+        assert!(image.frame(2).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_sis_truncated() {
+        assert!(decode_sis(&[b'S', b'I', b'S', 1, 4, 0, 2, 0, 8, 1, 0, 0, 2, 0]).is_err());
+    }
+
+    #[test]
+    fn test_decode_sis_rejects_oversized_canvas() {
+        // a 65535x65535 canvas with a single 1x1 object
+        let mut data = vec![b'S', b'I', b'S', 1, 0xff, 0xff, 0xff, 0xff, 8, 1, 1, 0];
+        data.extend([1, 0, 1, 0, 0, 0xff]);
+        data.extend([100, 0, 1, 0, 0, 0, 0, 0]);
+
+        assert!(decode_sis(&data).is_err());
+    }
+}
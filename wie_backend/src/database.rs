@@ -3,7 +3,7 @@ use alloc::{boxed::Box, vec::Vec};
 pub type RecordId = u32;
 
 #[async_trait::async_trait]
-pub trait Database: Send {
+pub trait Database: Send + Sync {
     async fn next_id(&self) -> RecordId;
     async fn add(&mut self, data: &[u8]) -> RecordId;
     async fn get(&self, id: RecordId) -> Option<Vec<u8>>;
@@ -14,7 +14,7 @@ pub trait Database: Send {
 }
 
 #[async_trait::async_trait]
-pub trait DatabaseRepository {
+pub trait DatabaseRepository: Sync {
     async fn open(&self, name: &str, app_id: &str) -> Box<dyn Database>;
     async fn exists(&self, name: &str, app_id: &str) -> bool;
     async fn delete(&self, name: &str, app_id: &str) -> bool;
@@ -0,0 +1,134 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+};
+
+use serde::Deserialize;
+
+use wie_util::{Result, WieError};
+
+use crate::input_method::HangulLayout;
+
+/// Traits of the emulated handset, consulted by every platform crate.
+///
+/// Profiles are written in TOML; omitted keys keep the values of [`DeviceProfile::default`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeviceProfile {
+    pub name: String,
+    pub screen_width: u32,
+    pub screen_height: u32,
+    /// Bits per pixel of the display.
+    pub color_depth: u32,
+    /// Hangul layout printed on the keypad.
+    pub keypad: HangulLayout,
+    /// System properties reported to applications, overriding the platform defaults.
+    pub properties: BTreeMap<String, String>,
+    /// Heap available to the application, in bytes.
+    pub heap_size: u32,
+    pub fonts: FontSet,
+    /// Platform version reported to applications, empty if the handset does not report one.
+    pub platform_version: String,
+}
+
+impl DeviceProfile {
+    pub fn from_toml(data: &str) -> Result<Self> {
+        toml::from_str(data).map_err(|x| WieError::FatalError(format!("Invalid device profile: {x}")))
+    }
+
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.get(name).map(|x| x.as_str())
+    }
+
+    /// `1 << color_depth`, saturated to `i32::MAX`.
+    pub fn num_colors(&self) -> i32 {
+        1i32.checked_shl(self.color_depth).filter(|&x| x > 0).unwrap_or(i32::MAX)
+    }
+}
+
+impl Default for DeviceProfile {
+    fn default() -> Self {
+        Self {
+            name: "Emulator".to_string(),
+            screen_width: 240,
+            screen_height: 320,
+            color_depth: 16,
+            keypad: HangulLayout::default(),
+            properties: BTreeMap::new(),
+            heap_size: 0x100000,
+            fonts: FontSet::default(),
+            platform_version: String::new(),
+        }
+    }
+}
+
+/// Pixel heights of the handset's small, medium and large fonts.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FontSet {
+    pub small: u32,
+    pub medium: u32,
+    pub large: u32,
+}
+
+impl Default for FontSet {
+    fn default() -> Self {
+        Self {
+            small: 10,
+            medium: 12,
+            large: 16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_method::HangulLayout;
+
+    use super::DeviceProfile;
+
+    #[test]
+    fn parses_partial_profile() {
+        let profile = DeviceProfile::from_toml(
+            r#"
+            name = "SPH-B5200"
+            screen_width = 176
+            screen_height = 220
+            color_depth = 18
+            keypad = "naragul"
+            platform_version = "2.0.1"
+
+            [properties]
+            PHONEMODEL = "SPH-B5200"
+
+            [fonts]
+            medium = 14
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!((profile.screen_width, profile.screen_height), (176, 220));
+        assert_eq!(profile.keypad, HangulLayout::Naragul);
+        assert_eq!(profile.property("PHONEMODEL"), Some("SPH-B5200"));
+        assert_eq!(profile.num_colors(), 1 << 18);
+        assert_eq!((profile.fonts.small, profile.fonts.medium), (10, 14));
+        assert_eq!(profile.heap_size, DeviceProfile::default().heap_size);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(DeviceProfile::from_toml("screen_depth = 16").is_err());
+        assert!(DeviceProfile::from_toml("keypad = \"qwerty\"").is_err());
+    }
+
+    #[test]
+    fn num_colors_saturates() {
+        let profile = DeviceProfile {
+            color_depth: 32,
+            ..Default::default()
+        };
+
+        assert_eq!(profile.num_colors(), i32::MAX);
+    }
+}
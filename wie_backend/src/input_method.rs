@@ -0,0 +1,905 @@
+use alloc::{format, string::String, vec::Vec};
+
+use serde::Deserialize;
+
+use crate::{KeyCode, time::Instant};
+
+/// Milliseconds after which a multi-tap key stops cycling through its letters.
+pub const MULTI_TAP_TIMEOUT: u64 = 1000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InputMode {
+    Numeric,
+    Uppercase,
+    Lowercase,
+    Korean,
+}
+
+/// Hangul keypad layout used in [`InputMode::Korean`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HangulLayout {
+    /// Samsung's 천지인: vowels are built from `ㅣ`, `ㆍ`, `ㅡ` strokes, consonants are multi-tapped.
+    #[default]
+    Chunjiin,
+    /// LG's 나랏글: `*` adds a stroke and `#` doubles the last jamo.
+    Naragul,
+}
+
+/// Outcome of feeding a key to an [`InputMethod`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InputResult {
+    /// Whether the key was used for text entry. Keys that are not consumed
+    /// (arrows, soft keys, `CLEAR` with nothing composing) are left to the caller.
+    pub consumed: bool,
+    /// Text that left the composition and should be inserted at the caret.
+    pub committed: String,
+}
+
+/// Key event delivered to a text editor, see [`InputMethod::key_event`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KeyPhase {
+    Pressed,
+    Repeated,
+    Released,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct MultiTap {
+    key: KeyCode,
+    index: usize,
+    at: Instant,
+}
+
+/// Keypad input method shared by the text entry APIs of every platform.
+///
+/// The engine only tracks the text being composed; callers own the edited
+/// text and insert [`InputResult::committed`] at their caret, drawing
+/// [`InputMethod::composing`] right after it. In English modes the composing
+/// text is the letter being multi-tapped, in Korean mode it holds the jamo of
+/// the syllable still being assembled. A multi-tap cycle ends after
+/// [`MULTI_TAP_TIMEOUT`]; Hangul composition continues until a non-input key,
+/// a space or a mode change commits it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InputMethod {
+    mode: InputMode,
+    layout: HangulLayout,
+    pending: Vec<char>,
+    tap: Option<MultiTap>,
+}
+
+const ENGLISH_KEYS: [(KeyCode, &str); 10] = [
+    (KeyCode::NUM1, ".,?!1"),
+    (KeyCode::NUM2, "abc2"),
+    (KeyCode::NUM3, "def3"),
+    (KeyCode::NUM4, "ghi4"),
+    (KeyCode::NUM5, "jkl5"),
+    (KeyCode::NUM6, "mno6"),
+    (KeyCode::NUM7, "pqrs7"),
+    (KeyCode::NUM8, "tuv8"),
+    (KeyCode::NUM9, "wxyz9"),
+    (KeyCode::NUM0, " 0"),
+];
+
+const CHUNJIIN_CONSONANTS: [(KeyCode, &str); 7] = [
+    (KeyCode::NUM4, "ㄱㅋㄲ"),
+    (KeyCode::NUM5, "ㄴㄹ"),
+    (KeyCode::NUM6, "ㄷㅌㄸ"),
+    (KeyCode::NUM7, "ㅂㅍㅃ"),
+    (KeyCode::NUM8, "ㅅㅎㅆ"),
+    (KeyCode::NUM9, "ㅈㅊㅉ"),
+    (KeyCode::NUM0, "ㅇㅁ"),
+];
+
+// `ㆍ` and `‥` are the one and two dot strokes of a vowel still being built
+const ARAEA: char = 'ㆍ';
+const DOUBLE_ARAEA: char = '‥';
+
+const CHUNJIIN_VOWELS: [(char, char, char); 22] = [
+    ('ㅣ', 'ㆍ', 'ㅏ'),
+    ('ㅏ', 'ㆍ', 'ㅑ'),
+    ('ㅏ', 'ㅣ', 'ㅐ'),
+    ('ㅑ', 'ㅣ', 'ㅒ'),
+    ('ㆍ', 'ㅣ', 'ㅓ'),
+    ('‥', 'ㅣ', 'ㅕ'),
+    ('ㅓ', 'ㅣ', 'ㅔ'),
+    ('ㅕ', 'ㅣ', 'ㅖ'),
+    ('ㆍ', 'ㆍ', '‥'),
+    ('‥', 'ㆍ', 'ㆍ'),
+    ('ㆍ', 'ㅡ', 'ㅗ'),
+    ('‥', 'ㅡ', 'ㅛ'),
+    ('ㅡ', 'ㆍ', 'ㅜ'),
+    ('ㅜ', 'ㆍ', 'ㅠ'),
+    ('ㅡ', 'ㅣ', 'ㅢ'),
+    ('ㅗ', 'ㅣ', 'ㅚ'),
+    ('ㅚ', 'ㆍ', 'ㅘ'),
+    ('ㅘ', 'ㅣ', 'ㅙ'),
+    ('ㅜ', 'ㅣ', 'ㅟ'),
+    ('ㅠ', 'ㅣ', 'ㅝ'),
+    ('ㅝ', 'ㅣ', 'ㅞ'),
+    ('ㅟ', 'ㆍ', 'ㅝ'),
+];
+
+const NARAGUL_KEYS: [(KeyCode, &str); 10] = [
+    (KeyCode::NUM1, "ㄱ"),
+    (KeyCode::NUM2, "ㄴ"),
+    (KeyCode::NUM3, "ㅏㅓ"),
+    (KeyCode::NUM4, "ㄹ"),
+    (KeyCode::NUM5, "ㅁ"),
+    (KeyCode::NUM6, "ㅗㅜ"),
+    (KeyCode::NUM7, "ㅅ"),
+    (KeyCode::NUM8, "ㅇ"),
+    (KeyCode::NUM9, "ㅣ"),
+    (KeyCode::NUM0, "ㅡ"),
+];
+
+const NARAGUL_STROKES: [(char, char); 21] = [
+    ('ㄱ', 'ㅋ'),
+    ('ㅋ', 'ㄱ'),
+    ('ㄴ', 'ㄷ'),
+    ('ㄷ', 'ㅌ'),
+    ('ㅌ', 'ㄴ'),
+    ('ㅁ', 'ㅂ'),
+    ('ㅂ', 'ㅍ'),
+    ('ㅍ', 'ㅁ'),
+    ('ㅅ', 'ㅈ'),
+    ('ㅈ', 'ㅊ'),
+    ('ㅊ', 'ㅅ'),
+    ('ㅇ', 'ㅎ'),
+    ('ㅎ', 'ㅇ'),
+    ('ㅏ', 'ㅑ'),
+    ('ㅑ', 'ㅏ'),
+    ('ㅓ', 'ㅕ'),
+    ('ㅕ', 'ㅓ'),
+    ('ㅗ', 'ㅛ'),
+    ('ㅛ', 'ㅗ'),
+    ('ㅜ', 'ㅠ'),
+    ('ㅠ', 'ㅜ'),
+];
+
+const NARAGUL_DOUBLES: [(char, char); 10] = [
+    ('ㄱ', 'ㄲ'),
+    ('ㄲ', 'ㄱ'),
+    ('ㄷ', 'ㄸ'),
+    ('ㄸ', 'ㄷ'),
+    ('ㅂ', 'ㅃ'),
+    ('ㅃ', 'ㅂ'),
+    ('ㅅ', 'ㅆ'),
+    ('ㅆ', 'ㅅ'),
+    ('ㅈ', 'ㅉ'),
+    ('ㅉ', 'ㅈ'),
+];
+
+const CHOSEONG: &str = "ㄱㄲㄴㄷㄸㄹㅁㅂㅃㅅㅆㅇㅈㅉㅊㅋㅌㅍㅎ";
+const JUNGSEONG: &str = "ㅏㅐㅑㅒㅓㅔㅕㅖㅗㅘㅙㅚㅛㅜㅝㅞㅟㅠㅡㅢㅣ";
+const JONGSEONG: &str = "ㄱㄲㄳㄴㄵㄶㄷㄹㄺㄻㄼㄽㄾㄿㅀㅁㅂㅄㅅㅆㅇㅈㅊㅋㅌㅍㅎ";
+
+const COMPOUND_FINALS: [(char, char, char); 11] = [
+    ('ㄱ', 'ㅅ', 'ㄳ'),
+    ('ㄴ', 'ㅈ', 'ㄵ'),
+    ('ㄴ', 'ㅎ', 'ㄶ'),
+    ('ㄹ', 'ㄱ', 'ㄺ'),
+    ('ㄹ', 'ㅁ', 'ㄻ'),
+    ('ㄹ', 'ㅂ', 'ㄼ'),
+    ('ㄹ', 'ㅅ', 'ㄽ'),
+    ('ㄹ', 'ㅌ', 'ㄾ'),
+    ('ㄹ', 'ㅍ', 'ㄿ'),
+    ('ㄹ', 'ㅎ', 'ㅀ'),
+    ('ㅂ', 'ㅅ', 'ㅄ'),
+];
+
+const COMPOUND_VOWELS: [(char, char, char); 13] = [
+    ('ㅗ', 'ㅏ', 'ㅘ'),
+    ('ㅗ', 'ㅐ', 'ㅙ'),
+    ('ㅗ', 'ㅣ', 'ㅚ'),
+    ('ㅜ', 'ㅓ', 'ㅝ'),
+    ('ㅜ', 'ㅔ', 'ㅞ'),
+    ('ㅜ', 'ㅣ', 'ㅟ'),
+    ('ㅡ', 'ㅣ', 'ㅢ'),
+    ('ㅏ', 'ㅣ', 'ㅐ'),
+    ('ㅓ', 'ㅣ', 'ㅔ'),
+    ('ㅑ', 'ㅣ', 'ㅒ'),
+    ('ㅕ', 'ㅣ', 'ㅖ'),
+    ('ㅘ', 'ㅣ', 'ㅙ'),
+    ('ㅝ', 'ㅣ', 'ㅞ'),
+];
+
+impl InputMethod {
+    pub fn new(mode: InputMode, layout: HangulLayout) -> Self {
+        Self {
+            mode,
+            layout,
+            pending: Vec::new(),
+            tap: None,
+        }
+    }
+
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    pub fn layout(&self) -> HangulLayout {
+        self.layout
+    }
+
+    /// Switches the mode, returning the text committed from the old one.
+    pub fn set_mode(&mut self, mode: InputMode) -> String {
+        let committed = self.commit();
+        self.mode = mode;
+
+        committed
+    }
+
+    /// Text still being composed, to be drawn at the caret.
+    pub fn composing(&self) -> String {
+        match self.mode {
+            InputMode::Korean => compose(&self.pending).into_iter().map(|(text, _)| text).collect(),
+            _ => self.pending.iter().collect(),
+        }
+    }
+
+    pub fn is_composing(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Ends the composition, returning its text.
+    pub fn commit(&mut self) -> String {
+        let committed = self.composing();
+        self.pending.clear();
+        self.tap = None;
+
+        committed
+    }
+
+    /// Ends a multi-tap cycle that timed out. In English modes this commits
+    /// the letter, in Korean mode the next press of the key starts a new jamo.
+    pub fn tick(&mut self, now: Instant) -> String {
+        let Some(tap) = self.tap else {
+            return String::new();
+        };
+        if now.raw().saturating_sub(tap.at.raw()) < MULTI_TAP_TIMEOUT {
+            return String::new();
+        }
+
+        self.tap = None;
+        match self.mode {
+            InputMode::Uppercase | InputMode::Lowercase => self.commit(),
+            _ => String::new(),
+        }
+    }
+
+    /// Feeds a key press. `room` is the number of characters the caller can
+    /// still insert; presses that would overflow it are swallowed.
+    pub fn key(&mut self, key: KeyCode, now: Instant, room: usize) -> InputResult {
+        let mut committed = self.tick(now);
+
+        let snapshot = self.clone();
+        let Some(mut result) = self.handle(key, now) else {
+            committed.push_str(&self.commit());
+            return InputResult { consumed: false, committed };
+        };
+
+        if result.committed.chars().count() + self.composing().chars().count() > room {
+            *self = snapshot;
+            result.committed.clear();
+        }
+        committed.push_str(&result.committed);
+
+        InputResult {
+            consumed: result.consumed,
+            committed,
+        }
+    }
+
+    /// Feeds a key event from a text editor. Presses and repeats go through
+    /// [`InputMethod::key`], except that holding a keypad key does not cycle
+    /// through its letters; releases end a timed out multi-tap cycle. `None`
+    /// means the editor should ignore the event altogether.
+    pub fn key_event(&mut self, phase: KeyPhase, key: KeyCode, now: Instant, room: usize) -> Option<InputResult> {
+        match phase {
+            KeyPhase::Repeated if keypad_char(key).is_some() => None,
+            KeyPhase::Pressed | KeyPhase::Repeated => Some(self.key(key, now, room)),
+            KeyPhase::Released => Some(InputResult {
+                consumed: false,
+                committed: self.tick(now),
+            }),
+        }
+    }
+
+    fn handle(&mut self, key: KeyCode, now: Instant) -> Option<InputResult> {
+        if key == KeyCode::CLEAR {
+            self.tap = None;
+            return self.pending.pop().map(|_| InputResult {
+                consumed: true,
+                committed: String::new(),
+            });
+        }
+
+        match self.mode {
+            InputMode::Numeric => Some(consumed(keypad_char(key)?.into())),
+            InputMode::Uppercase | InputMode::Lowercase => self.handle_english(key, now),
+            InputMode::Korean => match self.layout {
+                HangulLayout::Chunjiin => self.handle_chunjiin(key, now),
+                HangulLayout::Naragul => self.handle_naragul(key, now),
+            },
+        }
+    }
+
+    fn handle_english(&mut self, key: KeyCode, now: Instant) -> Option<InputResult> {
+        match key {
+            KeyCode::STAR => {
+                let committed = self.set_mode(next_mode(self.mode));
+                Some(consumed(committed))
+            }
+            KeyCode::HASH => {
+                let mut committed = self.commit();
+                committed.push(' ');
+                Some(consumed(committed))
+            }
+            _ => {
+                let letters = lookup(&ENGLISH_KEYS, key)?;
+                let (committed, letter) = self.multi_tap(key, letters, now);
+
+                let letter = if self.mode == InputMode::Uppercase {
+                    letter.to_ascii_uppercase()
+                } else {
+                    letter
+                };
+                self.pending.push(letter);
+
+                Some(consumed(committed))
+            }
+        }
+    }
+
+    fn handle_chunjiin(&mut self, key: KeyCode, now: Instant) -> Option<InputResult> {
+        let stroke = match key {
+            KeyCode::NUM1 => Some('ㅣ'),
+            KeyCode::NUM2 => Some(ARAEA),
+            KeyCode::NUM3 => Some('ㅡ'),
+            _ => None,
+        };
+        if let Some(stroke) = stroke {
+            self.tap = None;
+            let combined = self
+                .pending
+                .last()
+                .and_then(|&last| CHUNJIIN_VOWELS.iter().find(|x| x.0 == last && x.1 == stroke));
+            if let Some(&(_, _, vowel)) = combined {
+                *self.pending.last_mut().unwrap() = vowel;
+                return Some(consumed(String::new()));
+            }
+
+            let committed = self.settle();
+            self.pending.push(stroke);
+            return Some(consumed(committed));
+        }
+
+        match key {
+            KeyCode::STAR => {
+                let committed = self.set_mode(next_mode(self.mode));
+                Some(consumed(committed))
+            }
+            KeyCode::HASH => {
+                let mut committed = self.commit();
+                committed.push(' ');
+                Some(consumed(committed))
+            }
+            _ => {
+                let consonants = lookup(&CHUNJIIN_CONSONANTS, key)?;
+                Some(consumed(self.hangul_multi_tap(key, consonants, now)))
+            }
+        }
+    }
+
+    fn handle_naragul(&mut self, key: KeyCode, now: Instant) -> Option<InputResult> {
+        let table: &[(char, char)] = match key {
+            KeyCode::STAR => &NARAGUL_STROKES,
+            KeyCode::HASH => &NARAGUL_DOUBLES,
+            _ => {
+                let jamo = lookup(&NARAGUL_KEYS, key)?;
+                return Some(consumed(self.hangul_multi_tap(key, jamo, now)));
+            }
+        };
+
+        self.tap = None;
+        let replacement = self.pending.last().and_then(|&last| table.iter().find(|x| x.0 == last));
+        if let Some(&(_, replacement)) = replacement {
+            *self.pending.last_mut().unwrap() = replacement;
+            return Some(consumed(String::new()));
+        }
+
+        if key == KeyCode::HASH {
+            let mut committed = self.commit();
+            committed.push(' ');
+            return Some(consumed(committed));
+        }
+
+        Some(consumed(String::new()))
+    }
+
+    // cycles the last jamo while the same key is tapped, otherwise appends the first one
+    fn hangul_multi_tap(&mut self, key: KeyCode, jamo: &str, now: Instant) -> String {
+        let cycling = self.tap.is_some_and(|x| x.key == key) && !self.pending.is_empty();
+        if cycling {
+            let (_, next) = self.multi_tap(key, jamo, now);
+            *self.pending.last_mut().unwrap() = next;
+            return String::new();
+        }
+
+        let committed = self.settle();
+        let (_, first) = self.multi_tap(key, jamo, now);
+        self.pending.push(first);
+
+        committed
+    }
+
+    // advances the multi-tap cycle of `key`, committing the previous letter when another key starts a new cycle
+    fn multi_tap(&mut self, key: KeyCode, letters: &str, now: Instant) -> (String, char) {
+        let count = letters.chars().count();
+        let (committed, index) = match self.tap {
+            Some(tap) if tap.key == key => (String::new(), (tap.index + 1) % count),
+            _ => {
+                let committed = if self.mode == InputMode::Korean { String::new() } else { self.commit() };
+                (committed, 0)
+            }
+        };
+        if self.mode != InputMode::Korean {
+            self.pending.clear();
+        }
+        self.tap = Some(MultiTap { key, index, at: now });
+
+        (committed, letters.chars().nth(index).unwrap())
+    }
+
+    // commits the syllables that can no longer change, keeping the last one (and a trailing stroke) composing
+    fn settle(&mut self) -> String {
+        let units = compose(&self.pending);
+
+        let keep = match units.last() {
+            Some((text, _)) if text.starts_with([ARAEA, DOUBLE_ARAEA]) => 2,
+            _ => 1,
+        };
+        if units.len() <= keep {
+            return String::new();
+        }
+
+        let settled = &units[..units.len() - keep];
+        let length = settled.iter().map(|(_, length)| length).sum::<usize>();
+        self.pending.drain(..length);
+
+        settled.iter().map(|(text, _)| text.as_str()).collect()
+    }
+
+    /// Serializes the engine so text controls can keep their own instance
+    /// in a guest-visible string field.
+    pub fn save(&self) -> String {
+        let mode = match self.mode {
+            InputMode::Numeric => 'n',
+            InputMode::Uppercase => 'u',
+            InputMode::Lowercase => 'l',
+            InputMode::Korean => 'k',
+        };
+        let layout = match self.layout {
+            HangulLayout::Chunjiin => 'c',
+            HangulLayout::Naragul => 'n',
+        };
+        let tap = match self.tap {
+            Some(tap) => format!("{}{}@{}", keypad_char(tap.key).unwrap(), tap.index, tap.at.raw()),
+            None => String::new(),
+        };
+
+        format!("{mode}{layout}:{tap}:{}", self.pending.iter().collect::<String>())
+    }
+
+    /// Inverse of [`InputMethod::save`].
+    pub fn restore(state: &str) -> Option<Self> {
+        let mut parts = state.splitn(3, ':');
+        let mut header = parts.next()?.chars();
+        let tap = parts.next()?;
+        let pending = parts.next()?;
+
+        let mode = match header.next()? {
+            'n' => InputMode::Numeric,
+            'u' => InputMode::Uppercase,
+            'l' => InputMode::Lowercase,
+            'k' => InputMode::Korean,
+            _ => return None,
+        };
+        let layout = match header.next()? {
+            'c' => HangulLayout::Chunjiin,
+            'n' => HangulLayout::Naragul,
+            _ => return None,
+        };
+
+        let tap = if tap.is_empty() {
+            None
+        } else {
+            let mut chars = tap.chars();
+            let key = keypad_key(chars.next()?)?;
+            let (index, at) = chars.as_str().split_once('@')?;
+            Some(MultiTap {
+                key,
+                index: index.parse().ok()?,
+                at: Instant::from_epoch_millis(at.parse().ok()?),
+            })
+        };
+
+        Some(Self {
+            mode,
+            layout,
+            pending: pending.chars().collect(),
+            tap,
+        })
+    }
+}
+
+fn consumed(committed: String) -> InputResult {
+    InputResult { consumed: true, committed }
+}
+
+fn next_mode(mode: InputMode) -> InputMode {
+    match mode {
+        InputMode::Korean => InputMode::Uppercase,
+        InputMode::Uppercase => InputMode::Lowercase,
+        InputMode::Lowercase => InputMode::Korean,
+        InputMode::Numeric => InputMode::Numeric,
+    }
+}
+
+fn lookup<T: Copy>(table: &[(KeyCode, T)], key: KeyCode) -> Option<T> {
+    table.iter().find(|x| x.0 == key).map(|x| x.1)
+}
+
+/// Character printed on a keypad key.
+pub fn keypad_char(key: KeyCode) -> Option<char> {
+    Some(match key {
+        KeyCode::NUM0 => '0',
+        KeyCode::NUM1 => '1',
+        KeyCode::NUM2 => '2',
+        KeyCode::NUM3 => '3',
+        KeyCode::NUM4 => '4',
+        KeyCode::NUM5 => '5',
+        KeyCode::NUM6 => '6',
+        KeyCode::NUM7 => '7',
+        KeyCode::NUM8 => '8',
+        KeyCode::NUM9 => '9',
+        KeyCode::STAR => '*',
+        KeyCode::HASH => '#',
+        _ => return None,
+    })
+}
+
+fn keypad_key(char: char) -> Option<KeyCode> {
+    Some(match char {
+        '0' => KeyCode::NUM0,
+        '1' => KeyCode::NUM1,
+        '2' => KeyCode::NUM2,
+        '3' => KeyCode::NUM3,
+        '4' => KeyCode::NUM4,
+        '5' => KeyCode::NUM5,
+        '6' => KeyCode::NUM6,
+        '7' => KeyCode::NUM7,
+        '8' => KeyCode::NUM8,
+        '9' => KeyCode::NUM9,
+        '*' => KeyCode::STAR,
+        '#' => KeyCode::HASH,
+        _ => return None,
+    })
+}
+
+#[derive(Default)]
+struct Syllable {
+    initial: Option<char>,
+    medial: Option<char>,
+    finals: Vec<char>,
+    length: usize,
+}
+
+impl Syllable {
+    fn render(&self) -> String {
+        let initial = self.initial.and_then(|x| CHOSEONG.chars().position(|y| y == x));
+        let medial = self.medial.and_then(|x| JUNGSEONG.chars().position(|y| y == x));
+        let finals = match self.finals[..] {
+            [] => Some(None),
+            [x] => JONGSEONG.chars().position(|y| y == x).map(Some),
+            [x, y] => compound(&COMPOUND_FINALS, x, y).and_then(|z| JONGSEONG.chars().position(|w| w == z).map(Some)),
+            _ => None,
+        };
+
+        if let (Some(initial), Some(medial), Some(finals)) = (initial, medial, finals) {
+            let code = 0xac00 + (initial * 21 + medial) * 28 + finals.map(|x| x + 1).unwrap_or(0);
+            return char::from_u32(code as u32).into_iter().collect();
+        }
+
+        self.initial.into_iter().chain(self.medial).chain(self.finals.iter().copied()).collect()
+    }
+}
+
+fn compound(table: &[(char, char, char)], first: char, second: char) -> Option<char> {
+    table.iter().find(|x| x.0 == first && x.1 == second).map(|x| x.2)
+}
+
+fn is_consonant(jamo: char) -> bool {
+    CHOSEONG.contains(jamo)
+}
+
+fn is_vowel(jamo: char) -> bool {
+    JUNGSEONG.contains(jamo)
+}
+
+// assembles jamo into syllables the way a 2-set keyboard does, returning each unit with the number of jamo it used
+fn compose(jamo: &[char]) -> Vec<(String, usize)> {
+    fn flush(units: &mut Vec<(String, usize)>, current: &mut Syllable) {
+        if current.length > 0 {
+            units.push((current.render(), current.length));
+        }
+        *current = Syllable::default();
+    }
+
+    let mut units = Vec::new();
+    let mut current = Syllable::default();
+
+    for &x in jamo {
+        if is_consonant(x) {
+            let accepts_final = current.initial.is_some()
+                && current.medial.is_some()
+                && match current.finals[..] {
+                    [] => JONGSEONG.contains(x),
+                    [first] => compound(&COMPOUND_FINALS, first, x).is_some(),
+                    _ => false,
+                };
+            if accepts_final {
+                current.finals.push(x);
+                current.length += 1;
+            } else {
+                flush(&mut units, &mut current);
+                current.initial = Some(x);
+                current.length = 1;
+            }
+        } else if is_vowel(x) {
+            if let Some(initial) = current.finals.pop() {
+                // the last final consonant becomes the initial of the next syllable
+                current.length -= 1;
+                flush(&mut units, &mut current);
+                current.initial = Some(initial);
+                current.medial = Some(x);
+                current.length = 2;
+            } else if let Some(medial) = current.medial {
+                if let Some(vowel) = compound(&COMPOUND_VOWELS, medial, x) {
+                    current.medial = Some(vowel);
+                    current.length += 1;
+                } else {
+                    flush(&mut units, &mut current);
+                    current.medial = Some(x);
+                    current.length = 1;
+                }
+            } else {
+                current.medial = Some(x);
+                current.length += 1;
+            }
+        } else {
+            flush(&mut units, &mut current);
+            units.push((x.into(), 1));
+        }
+    }
+    flush(&mut units, &mut current);
+
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use crate::{KeyCode, time::Instant};
+
+    use super::{HangulLayout, InputMethod, InputMode, InputResult, KeyPhase, MULTI_TAP_TIMEOUT};
+
+    // types `keys` with `gap` milliseconds between presses, returning committed text and the composition
+    fn type_keys(input_method: &mut InputMethod, keys: &[KeyCode], gap: u64) -> (String, String) {
+        let mut committed = String::new();
+        for (i, &key) in keys.iter().enumerate() {
+            let result = input_method.key(key, Instant::from_epoch_millis(i as u64 * gap), usize::MAX);
+            assert!(result.consumed, "{key:?} was not consumed");
+            committed.push_str(&result.committed);
+        }
+
+        (committed, input_method.composing())
+    }
+
+    fn keys(digits: &str) -> Vec<KeyCode> {
+        digits.chars().map(|x| super::keypad_key(x).unwrap()).collect()
+    }
+
+    #[test]
+    fn numeric_mode_commits_digits_directly() {
+        let mut input_method = InputMethod::new(InputMode::Numeric, HangulLayout::Chunjiin);
+
+        assert_eq!(type_keys(&mut input_method, &keys("1*0#"), 10), ("1*0#".into(), "".into()));
+    }
+
+    #[test]
+    fn english_multi_tap_cycles_and_commits_on_timeout() {
+        let mut input_method = InputMethod::new(InputMode::Lowercase, HangulLayout::Chunjiin);
+
+        // "c" then "a" on the same key after the timeout, then "t"
+        let mut committed = String::new();
+        for (key, at) in [(KeyCode::NUM2, 0), (KeyCode::NUM2, 100), (KeyCode::NUM2, 200)] {
+            committed += &input_method.key(key, Instant::from_epoch_millis(at), usize::MAX).committed;
+        }
+        committed += &input_method
+            .key(KeyCode::NUM2, Instant::from_epoch_millis(200 + MULTI_TAP_TIMEOUT), usize::MAX)
+            .committed;
+        committed += &input_method.key(KeyCode::NUM8, Instant::from_epoch_millis(1300), usize::MAX).committed;
+        assert_eq!(committed, "ca");
+        assert_eq!(input_method.composing(), "t");
+
+        assert_eq!(input_method.tick(Instant::from_epoch_millis(1300 + MULTI_TAP_TIMEOUT)), "t");
+        assert!(!input_method.is_composing());
+    }
+
+    #[test]
+    fn holding_a_keypad_key_does_not_cycle() {
+        let mut input_method = InputMethod::new(InputMode::Lowercase, HangulLayout::Chunjiin);
+        let at = Instant::from_epoch_millis;
+
+        assert!(
+            input_method
+                .key_event(KeyPhase::Pressed, KeyCode::NUM2, at(0), usize::MAX)
+                .unwrap()
+                .consumed
+        );
+        assert_eq!(input_method.key_event(KeyPhase::Repeated, KeyCode::NUM2, at(100), usize::MAX), None);
+        assert_eq!(input_method.key_event(KeyPhase::Repeated, KeyCode::NUM2, at(200), usize::MAX), None);
+        assert_eq!(input_method.composing(), "a");
+
+        // repeats of other keys finish the composition and are left to the editor
+        let result = input_method.key_event(KeyPhase::Repeated, KeyCode::RIGHT, at(300), usize::MAX);
+        assert_eq!(
+            result,
+            Some(InputResult {
+                consumed: false,
+                committed: "a".into()
+            })
+        );
+    }
+
+    #[test]
+    fn release_commits_after_multi_tap_timeout() {
+        let mut input_method = InputMethod::new(InputMode::Lowercase, HangulLayout::Chunjiin);
+        let at = Instant::from_epoch_millis;
+
+        input_method.key_event(KeyPhase::Pressed, KeyCode::NUM3, at(0), usize::MAX);
+        input_method.key_event(KeyPhase::Pressed, KeyCode::NUM3, at(100), usize::MAX);
+
+        let released = input_method.key_event(KeyPhase::Released, KeyCode::NUM3, at(150), usize::MAX).unwrap();
+        assert_eq!(released.committed, "");
+        assert_eq!(input_method.composing(), "e");
+
+        let released = input_method
+            .key_event(KeyPhase::Released, KeyCode::NUM3, at(100 + MULTI_TAP_TIMEOUT), usize::MAX)
+            .unwrap();
+        assert!(!released.consumed);
+        assert_eq!(released.committed, "e");
+        assert!(!input_method.is_composing());
+    }
+
+    #[test]
+    fn star_cycles_between_text_modes() {
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+
+        input_method.key(KeyCode::STAR, Instant::from_epoch_millis(0), usize::MAX);
+        assert_eq!(input_method.mode(), InputMode::Uppercase);
+        assert_eq!(type_keys(&mut input_method, &keys("44"), 10), ("".into(), "H".into()));
+
+        let result = input_method.key(KeyCode::STAR, Instant::from_epoch_millis(100), usize::MAX);
+        assert_eq!(result.committed, "H");
+        assert_eq!(input_method.mode(), InputMode::Lowercase);
+    }
+
+    #[test]
+    fn chunjiin_composes_syllables() {
+        // 한 = ㅎ(88) ㅏ(12) ㄴ(5), 글 = ㄱ(4) ㅡ(3) ㄹ(55)
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        let (committed, composing) = type_keys(&mut input_method, &keys("88125"), 10);
+        assert_eq!(committed, "");
+        assert_eq!(composing, "한");
+
+        let (committed, composing) = type_keys(&mut input_method, &keys("43"), 10);
+        assert_eq!(committed, "한");
+        assert_eq!(composing, "그");
+
+        let (_, composing) = type_keys(&mut input_method, &keys("55"), 10);
+        assert_eq!(composing, "글");
+
+        // the final consonant moves to the next syllable once a vowel follows: 기너
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        assert_eq!(type_keys(&mut input_method, &keys("4152"), 10), ("".into(), "긴ㆍ".into()));
+        assert_eq!(type_keys(&mut input_method, &keys("1"), 10), ("".into(), "기너".into()));
+        assert_eq!(type_keys(&mut input_method, &keys("4"), 10), ("기".into(), "넉".into()));
+    }
+
+    #[test]
+    fn chunjiin_builds_compound_vowels_from_strokes() {
+        // 워 = ㅇ ㅡ ㆍ ㆍ ㅣ, 와 = ㅇ ㆍ ㅡ ㅣ ㆍ
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        assert_eq!(type_keys(&mut input_method, &keys("03221"), 10).1, "워");
+
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        assert_eq!(type_keys(&mut input_method, &keys("02312"), 10).1, "와");
+    }
+
+    #[test]
+    fn chunjiin_repeats_a_consonant_after_the_timeout() {
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        assert_eq!(type_keys(&mut input_method, &keys("44"), 10), ("".into(), "ㅋ".into()));
+
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        assert_eq!(type_keys(&mut input_method, &keys("44"), MULTI_TAP_TIMEOUT), ("".into(), "ㄱㄱ".into()));
+
+        // another key in between also ends the cycle: 각
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        assert_eq!(type_keys(&mut input_method, &keys("4124"), 10), ("".into(), "각".into()));
+    }
+
+    #[test]
+    fn naragul_adds_strokes_and_doubles() {
+        // 한 = ㅇ* ㅏ ㄴ, 빵 = ㅁ* ㅏ ㅇ with ㅂ doubled
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Naragul);
+        let keys = [KeyCode::NUM8, KeyCode::STAR, KeyCode::NUM3, KeyCode::NUM2];
+        assert_eq!(type_keys(&mut input_method, &keys, 10), ("".into(), "한".into()));
+
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Naragul);
+        let keys = [KeyCode::NUM5, KeyCode::STAR, KeyCode::HASH, KeyCode::NUM3, KeyCode::NUM8];
+        assert_eq!(type_keys(&mut input_method, &keys, 10), ("".into(), "빵".into()));
+
+        // 3 toggles ㅏ and ㅓ, the compound vowel forms from ㅜ and ㅓ: 워
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Naragul);
+        let keys = [KeyCode::NUM8, KeyCode::NUM6, KeyCode::NUM6, KeyCode::NUM3, KeyCode::NUM3];
+        assert_eq!(type_keys(&mut input_method, &keys, 10).1, "워");
+    }
+
+    #[test]
+    fn clear_and_navigation_keys() {
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        type_keys(&mut input_method, &keys("4125"), 10);
+
+        let result = input_method.key(KeyCode::CLEAR, Instant::from_epoch_millis(100), usize::MAX);
+        assert!(result.consumed);
+        assert_eq!(input_method.composing(), "가");
+
+        let result = input_method.key(KeyCode::RIGHT, Instant::from_epoch_millis(200), usize::MAX);
+        assert!(!result.consumed);
+        assert_eq!(result.committed, "가");
+
+        let result = input_method.key(KeyCode::CLEAR, Instant::from_epoch_millis(300), usize::MAX);
+        assert!(!result.consumed);
+    }
+
+    #[test]
+    fn room_limits_committed_and_composing_text() {
+        let mut input_method = InputMethod::new(InputMode::Numeric, HangulLayout::Chunjiin);
+        let result = input_method.key(KeyCode::NUM1, Instant::from_epoch_millis(0), 0);
+        assert!(result.consumed);
+        assert_eq!(result.committed, "");
+
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Chunjiin);
+        type_keys(&mut input_method, &keys("412"), 10);
+        // a vowel would split 가ㄴ.. into two syllables, which does not fit in one character
+        let result = input_method.key(KeyCode::NUM5, Instant::from_epoch_millis(100), 1);
+        assert!(result.consumed);
+        assert_eq!(input_method.composing(), "간");
+        input_method.key(KeyCode::NUM1, Instant::from_epoch_millis(200), 1);
+        assert_eq!(input_method.composing(), "간");
+    }
+
+    #[test]
+    fn state_round_trips_through_save() {
+        let mut input_method = InputMethod::new(InputMode::Korean, HangulLayout::Naragul);
+        type_keys(&mut input_method, &[KeyCode::NUM1, KeyCode::NUM3], 10);
+
+        assert_eq!(InputMethod::restore(&input_method.save()), Some(input_method.clone()));
+        assert_eq!(InputMethod::restore("zz::"), None);
+    }
+}
@@ -4,24 +4,34 @@ extern crate alloc;
 mod audio_sink;
 pub mod canvas;
 mod database;
+mod device_profile;
 mod executor;
+mod input_method;
+mod network;
 mod platform;
 mod screen;
 mod system;
 mod task;
 mod task_runner;
 mod time;
+mod trace;
 
 pub use self::{
     audio_sink::{AudioCommand, AudioEventData, AudioHandle, AudioSequence, AudioSink, TimedAudioEvent},
     database::{Database, DatabaseRepository, RecordId},
+    device_profile::{DeviceProfile, FontSet},
     executor::{AsyncCallable, AsyncCallableResult},
-    platform::{Filesystem, Platform},
+    input_method::{HangulLayout, InputMethod, InputMode, InputResult, KeyPhase, MULTI_TAP_TIMEOUT, keypad_char},
+    network::{
+        ConnectionId, Connections, DatagramConnection, DatagramSocket, HttpConnection, HttpResponse, Network, NetworkError, OfflineNetwork, Socket,
+    },
+    platform::{DirEntry, FileMetadata, Filesystem, Platform},
     screen::Screen,
-    system::{Event, FilesystemOverlay, KeyCode, System},
+    system::{DEFAULT_STORAGE_QUOTA, Event, FilesystemOverlay, KeyCode, Storage, System},
     task::YieldFuture,
     task_runner::{DefaultTaskRunner, TaskRunner},
     time::Instant,
+    trace::{TraceCallback, TraceEvent, TraceOptions},
 };
 
 use alloc::{
@@ -37,11 +47,14 @@ use wie_util::{Result, WieError};
 pub trait Emulator {
     fn handle_event(&mut self, event: Event);
     fn tick(&mut self) -> Result<()>;
+    fn aid(&self) -> &str;
 }
 
 pub struct ProfileSample {
     /// Leaf-first call stack: [pc, lr, lr_prev, ...].
     pub stack: Vec<u32>,
+    /// Names for each `stack` entry, as given by the platform's symbol provider.
+    pub symbols: Vec<String>,
     pub count: u64,
 }
 
@@ -53,6 +66,17 @@ pub type ProfileCallback = Box<dyn FnMut(Vec<ProfileSample>) + Send + Sync>;
 pub struct Options {
     pub enable_gdbserver: bool,
     pub profile: Option<ProfileCallback>,
+    pub trace: Option<TraceOptions>,
+    /// Guard guest heap allocations with red zones and report the ones still live at exit.
+    pub heap_debug: bool,
+    /// Patch tables merged with the built-in `data/binary_patches.toml` when a native binary is loaded.
+    pub binary_patches: Vec<BinaryPatchFile>,
+}
+
+pub struct BinaryPatchFile {
+    /// Shown in error messages, usually the path the file was read from.
+    pub name: String,
+    pub contents: String,
 }
 
 pub fn extract_zip(zip: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
@@ -0,0 +1,232 @@
+mod datagram;
+mod http;
+
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+
+use crate::platform::Platform;
+
+pub use self::{
+    datagram::DatagramConnection,
+    http::{HttpConnection, HttpResponse},
+};
+
+pub type ConnectionId = u32;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NetworkError {
+    /// The operation has not finished yet; poll again later.
+    WouldBlock,
+    HostNotFound,
+    ConnectionRefused,
+    /// An established connection failed.
+    ConnectionLost,
+    /// Malformed request or unknown connection.
+    Invalid,
+    /// The platform has no network access.
+    Offline,
+}
+
+/// Host network stack used by the platform network APIs.
+///
+/// Nothing here may block the emulator: operations that cannot complete immediately return
+/// [`NetworkError::WouldBlock`] and are polled again from a scheduled task.
+pub trait Network: Send + Sync {
+    fn is_online(&self) -> bool {
+        true
+    }
+
+    /// Resolves `host` to an IPv4 address. Called again with the same host until it stops returning `WouldBlock`.
+    fn resolve(&self, host: &str) -> Result<[u8; 4], NetworkError>;
+
+    /// Starts a TCP connection; completion is reported through [`Socket::poll_connect`].
+    fn connect(&self, address: [u8; 4], port: u16) -> Result<Box<dyn Socket>, NetworkError>;
+
+    /// Opens a UDP socket on any local port. Platforms without UDP keep the default.
+    fn bind_datagram(&self) -> Result<Box<dyn DatagramSocket>, NetworkError> {
+        Err(NetworkError::Invalid)
+    }
+}
+
+/// Non-blocking TCP stream returned from [`Network::connect`]. Dropping it closes the connection.
+pub trait Socket: Send + Sync {
+    fn poll_connect(&mut self) -> Result<(), NetworkError>;
+
+    /// `Ok` once [`read`](Self::read) would not block, including at end of stream.
+    fn poll_readable(&mut self) -> Result<(), NetworkError>;
+
+    /// Returns `Ok(0)` once the peer has closed the connection.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, NetworkError>;
+
+    fn write(&mut self, data: &[u8]) -> Result<usize, NetworkError>;
+}
+
+/// Non-blocking UDP socket returned from [`Network::bind_datagram`].
+pub trait DatagramSocket: Send + Sync {
+    fn send_to(&mut self, address: [u8; 4], port: u16, data: &[u8]) -> Result<usize, NetworkError>;
+
+    /// Receives one datagram, returning its length and sender.
+    fn recv_from(&mut self, buf: &mut [u8]) -> Result<(usize, [u8; 4], u16), NetworkError>;
+}
+
+/// [`Network`] of platforms without network access.
+pub struct OfflineNetwork;
+
+impl Network for OfflineNetwork {
+    fn is_online(&self) -> bool {
+        false
+    }
+
+    fn resolve(&self, _host: &str) -> Result<[u8; 4], NetworkError> {
+        Err(NetworkError::Offline)
+    }
+
+    fn connect(&self, _address: [u8; 4], _port: u16) -> Result<Box<dyn Socket>, NetworkError> {
+        Err(NetworkError::Offline)
+    }
+
+    fn bind_datagram(&self) -> Result<Box<dyn DatagramSocket>, NetworkError> {
+        Err(NetworkError::Offline)
+    }
+}
+
+enum Connection {
+    Socket(Option<Box<dyn Socket>>),
+    Http(HttpConnection),
+    Datagram(DatagramConnection),
+    DatagramSocket(Box<dyn DatagramSocket>),
+}
+
+/// Sockets and HTTP requests opened by the application, keyed by the ids handed out to it.
+pub struct Connections {
+    platform: Arc<Box<dyn Platform>>,
+    connections: BTreeMap<ConnectionId, Connection>,
+    last_id: ConnectionId,
+}
+
+impl Connections {
+    pub fn new(platform: Arc<Box<dyn Platform>>) -> Self {
+        Self {
+            platform,
+            connections: BTreeMap::new(),
+            last_id: 0,
+        }
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.platform.network().is_online()
+    }
+
+    pub fn resolve(&self, host: &str) -> Result<[u8; 4], NetworkError> {
+        resolve(self.platform.network(), host)
+    }
+
+    /// Allocates a socket that is connected later with [`connect`](Self::connect).
+    pub fn create_socket(&mut self) -> ConnectionId {
+        self.insert(Connection::Socket(None))
+    }
+
+    pub fn connect(&mut self, id: ConnectionId, address: [u8; 4], port: u16) -> Result<(), NetworkError> {
+        let network = self.platform.network();
+        match self.connections.get_mut(&id) {
+            Some(Connection::Socket(socket @ None)) => {
+                *socket = Some(network.connect(address, port)?);
+
+                Ok(())
+            }
+            _ => Err(NetworkError::Invalid),
+        }
+    }
+
+    /// The socket `id`, once [`connect`](Self::connect) has been called on it.
+    pub fn socket(&mut self, id: ConnectionId) -> Option<&mut dyn Socket> {
+        match self.connections.get_mut(&id) {
+            Some(Connection::Socket(Some(socket))) => Some(socket.as_mut()),
+            _ => None,
+        }
+    }
+
+    /// Prepares a request to `url`; `None` if it is not an `http://` url.
+    pub fn open_http(&mut self, url: &str) -> Option<ConnectionId> {
+        let connection = HttpConnection::new(url)?;
+
+        Some(self.insert(Connection::Http(connection)))
+    }
+
+    pub fn http(&mut self, id: ConnectionId) -> Option<&mut HttpConnection> {
+        match self.connections.get_mut(&id) {
+            Some(Connection::Http(connection)) => Some(connection),
+            _ => None,
+        }
+    }
+
+    /// Opens a UDP socket exchanging datagrams with `address`:`port`.
+    pub fn open_datagram(&mut self, address: [u8; 4], port: u16) -> Result<ConnectionId, NetworkError> {
+        let socket = self.platform.network().bind_datagram()?;
+
+        Ok(self.insert(Connection::Datagram(DatagramConnection::new(socket, address, port))))
+    }
+
+    pub fn datagram(&mut self, id: ConnectionId) -> Option<&mut DatagramConnection> {
+        match self.connections.get_mut(&id) {
+            Some(Connection::Datagram(connection)) => Some(connection),
+            _ => None,
+        }
+    }
+
+    /// Opens a UDP socket exchanging datagrams with any peer.
+    pub fn open_datagram_socket(&mut self) -> Result<ConnectionId, NetworkError> {
+        let socket = self.platform.network().bind_datagram()?;
+
+        Ok(self.insert(Connection::DatagramSocket(socket)))
+    }
+
+    pub fn datagram_socket(&mut self, id: ConnectionId) -> Option<&mut dyn DatagramSocket> {
+        match self.connections.get_mut(&id) {
+            Some(Connection::DatagramSocket(socket)) => Some(socket.as_mut()),
+            _ => None,
+        }
+    }
+
+    /// Advances the request `id`; `Ok` once its response has been received.
+    pub fn poll_http(&mut self, id: ConnectionId) -> Result<(), NetworkError> {
+        let network = self.platform.network();
+        match self.connections.get_mut(&id) {
+            Some(Connection::Http(connection)) => connection.poll(network),
+            _ => Err(NetworkError::Invalid),
+        }
+    }
+
+    pub fn close(&mut self, id: ConnectionId) -> bool {
+        self.connections.remove(&id).is_some()
+    }
+
+    fn insert(&mut self, connection: Connection) -> ConnectionId {
+        self.last_id += 1;
+        self.connections.insert(self.last_id, connection);
+
+        self.last_id
+    }
+}
+
+pub(crate) fn resolve(network: &dyn Network, host: &str) -> Result<[u8; 4], NetworkError> {
+    // dotted quads need no lookup
+    let parts = host.split('.').map(|x| x.parse()).collect::<Result<Vec<u8>, _>>();
+    if let Some(address) = parts.ok().and_then(|x| x.try_into().ok()) {
+        return Ok(address);
+    }
+
+    network.resolve(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NetworkError, OfflineNetwork, resolve};
+
+    #[test]
+    fn resolve_parses_dotted_quads() {
+        assert_eq!(resolve(&OfflineNetwork, "10.0.2.255"), Ok([10, 0, 2, 255]));
+        assert_eq!(resolve(&OfflineNetwork, "10.0.2"), Err(NetworkError::Offline));
+        assert_eq!(resolve(&OfflineNetwork, "10.0.2.256"), Err(NetworkError::Offline));
+        assert_eq!(resolve(&OfflineNetwork, "example.com"), Err(NetworkError::Offline));
+    }
+}
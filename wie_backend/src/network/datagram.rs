@@ -0,0 +1,56 @@
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
+
+use super::{DatagramSocket, NetworkError};
+
+// larger than any packet handsets could send
+const MAX_DATAGRAM_SIZE: usize = 0x10000;
+
+/// UDP socket bound to a single peer, queueing datagrams until the application receives them.
+pub struct DatagramConnection {
+    socket: Box<dyn DatagramSocket>,
+    address: [u8; 4],
+    port: u16,
+    received: VecDeque<Vec<u8>>,
+}
+
+impl DatagramConnection {
+    pub(super) fn new(socket: Box<dyn DatagramSocket>, address: [u8; 4], port: u16) -> Self {
+        Self {
+            socket,
+            address,
+            port,
+            received: VecDeque::new(),
+        }
+    }
+
+    pub fn send(&mut self, data: &[u8]) -> Result<(), NetworkError> {
+        self.socket.send_to(self.address, self.port, data).map(|_| ())
+    }
+
+    /// Number of datagrams waiting to be received.
+    pub fn pending(&mut self) -> Result<usize, NetworkError> {
+        self.poll()?;
+
+        Ok(self.received.len())
+    }
+
+    /// Next datagram from the peer; `WouldBlock` until one arrives.
+    pub fn receive(&mut self) -> Result<Vec<u8>, NetworkError> {
+        self.poll()?;
+
+        self.received.pop_front().ok_or(NetworkError::WouldBlock)
+    }
+
+    fn poll(&mut self) -> Result<(), NetworkError> {
+        let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                // datagrams from anyone else are dropped, as with a connected socket
+                Ok((length, address, port)) if (address, port) == (self.address, self.port) => self.received.push_back(buf[..length].to_vec()),
+                Ok(_) => {}
+                Err(NetworkError::WouldBlock) => return Ok(()),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
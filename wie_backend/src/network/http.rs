@@ -0,0 +1,436 @@
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str;
+
+use super::{Network, NetworkError, Socket, resolve};
+
+const READ_CHUNK_SIZE: usize = 1024;
+
+struct HttpUrl {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl HttpUrl {
+    fn parse(url: &str) -> Option<Self> {
+        let scheme_end = url.find("://")?;
+        if !url[..scheme_end].eq_ignore_ascii_case("http") {
+            return None;
+        }
+
+        let rest = &url[scheme_end + 3..];
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(x) if rest[x..].starts_with('/') => (&rest[..x], rest[x..].to_string()),
+            Some(x) => (&rest[..x], format!("/{}", &rest[x..])),
+            None => (rest, "/".to_string()),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().ok()?),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            host: host.to_string(),
+            port,
+            path,
+        })
+    }
+
+    fn authority(&self) -> String {
+        if self.port == 80 {
+            self.host.clone()
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum HttpState {
+    Setup,
+    Connecting,
+    /// Bytes of the request already written.
+    Sending(usize),
+    Receiving,
+    Done,
+    Failed(NetworkError),
+}
+
+pub struct HttpResponse {
+    pub code: u16,
+    pub message: String,
+    headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    pub fn content_length(&self) -> Option<usize> {
+        self.header("Content-Length")?.trim().parse().ok()
+    }
+
+    /// Parses `data`; unless `complete`, `None` until all of the body announced by `Content-Length` is there.
+    fn parse(data: &[u8], complete: bool, has_body: bool) -> Option<Self> {
+        let header_end = data.windows(4).position(|x| x == b"\r\n\r\n")?;
+        let head = str::from_utf8(&data[..header_end]).ok()?;
+        let mut lines = head.split("\r\n");
+
+        let mut status = lines.next()?.splitn(3, ' ');
+        if !status.next()?.starts_with("HTTP/") {
+            return None;
+        }
+        let code = status.next()?.parse().ok()?;
+        let message = status.next().unwrap_or("").to_string();
+
+        let headers = lines
+            .filter_map(|x| x.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect::<Vec<_>>();
+
+        let mut body = if has_body { data[header_end + 4..].to_vec() } else { Vec::new() };
+        let content_length = find_header(&headers, "Content-Length").and_then(|x| x.parse::<usize>().ok());
+        match content_length {
+            Some(length) if has_body && body.len() < length && !complete => return None,
+            Some(length) => body.truncate(length),
+            None if !complete && has_body => return None,
+            None => {}
+        }
+
+        Some(Self {
+            code,
+            message,
+            headers,
+            body,
+        })
+    }
+}
+
+/// HTTP/1.0 request driven by [`poll`](Self::poll), shared by the platform HTTP APIs.
+///
+/// The request is configured first, then sent on the first poll; the body is read out of the buffered response.
+pub struct HttpConnection {
+    url: HttpUrl,
+    method: String,
+    request_headers: Vec<(String, String)>,
+    proxy: Option<(String, u16)>,
+    request_body: Vec<u8>,
+    state: HttpState,
+    socket: Option<Box<dyn Socket>>,
+    request: Vec<u8>,
+    received: Vec<u8>,
+    response: Option<HttpResponse>,
+    read_offset: usize,
+}
+
+impl HttpConnection {
+    pub fn new(url: &str) -> Option<Self> {
+        Some(Self {
+            url: HttpUrl::parse(url)?,
+            method: "GET".to_string(),
+            request_headers: Vec::new(),
+            proxy: None,
+            request_body: Vec::new(),
+            state: HttpState::Setup,
+            socket: None,
+            request: Vec::new(),
+            received: Vec::new(),
+            response: None,
+            read_offset: 0,
+        })
+    }
+
+    /// Whether the request can still be changed.
+    pub fn is_setup(&self) -> bool {
+        self.state == HttpState::Setup
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn set_method(&mut self, method: &str) -> bool {
+        if !self.is_setup() || !["GET", "POST", "HEAD"].iter().any(|x| x.eq_ignore_ascii_case(method)) {
+            return false;
+        }
+        self.method = method.to_ascii_uppercase();
+
+        true
+    }
+
+    pub fn request_property(&self, key: &str) -> Option<&str> {
+        find_header(&self.request_headers, key)
+    }
+
+    pub fn set_request_property(&mut self, key: &str, value: &str) -> bool {
+        if !self.is_setup() {
+            return false;
+        }
+
+        match self.request_headers.iter_mut().find(|(x, _)| x.eq_ignore_ascii_case(key)) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.request_headers.push((key.to_string(), value.to_string())),
+        }
+
+        true
+    }
+
+    pub fn proxy(&self) -> Option<(&str, u16)> {
+        self.proxy.as_ref().map(|(host, port)| (host.as_str(), *port))
+    }
+
+    pub fn set_proxy(&mut self, host: &str, port: u16) -> bool {
+        if !self.is_setup() {
+            return false;
+        }
+        self.proxy = (!host.is_empty()).then(|| (host.to_string(), port));
+
+        true
+    }
+
+    /// Appends to the request body.
+    pub fn write(&mut self, data: &[u8]) -> bool {
+        if !self.is_setup() {
+            return false;
+        }
+        self.request_body.extend_from_slice(data);
+
+        true
+    }
+
+    pub fn response(&self) -> Option<&HttpResponse> {
+        self.response.as_ref()
+    }
+
+    /// Reads the response body, returning `0` at its end or before the response arrived.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        let Some(response) = &self.response else {
+            return 0;
+        };
+
+        let remaining = &response.body[self.read_offset..];
+        let length = remaining.len().min(buf.len());
+        buf[..length].copy_from_slice(&remaining[..length]);
+        self.read_offset += length;
+
+        length
+    }
+
+    /// Advances the request; `Ok` once the response has been received.
+    pub fn poll(&mut self, network: &dyn Network) -> Result<(), NetworkError> {
+        loop {
+            self.state = match self.state {
+                HttpState::Setup => {
+                    let (host, port) = self.proxy.clone().unwrap_or_else(|| (self.url.host.clone(), self.url.port));
+                    match resolve(network, &host).and_then(|x| network.connect(x, port)) {
+                        Ok(socket) => {
+                            self.socket = Some(socket);
+                            self.request = self.request_bytes();
+
+                            HttpState::Connecting
+                        }
+                        Err(NetworkError::WouldBlock) => return Err(NetworkError::WouldBlock),
+                        Err(err) => HttpState::Failed(err),
+                    }
+                }
+                HttpState::Connecting => match self.socket()?.poll_connect() {
+                    Ok(()) => HttpState::Sending(0),
+                    Err(NetworkError::WouldBlock) => return Err(NetworkError::WouldBlock),
+                    Err(err) => HttpState::Failed(err),
+                },
+                HttpState::Sending(offset) if offset == self.request.len() => HttpState::Receiving,
+                HttpState::Sending(offset) => {
+                    let request = &self.request[offset..];
+                    match self.socket.as_mut().ok_or(NetworkError::Invalid)?.write(request) {
+                        Ok(written) => HttpState::Sending(offset + written),
+                        Err(NetworkError::WouldBlock) => return Err(NetworkError::WouldBlock),
+                        Err(err) => HttpState::Failed(err),
+                    }
+                }
+                HttpState::Receiving => {
+                    let mut buf = [0; READ_CHUNK_SIZE];
+                    match self.socket()?.read(&mut buf) {
+                        Ok(0) => self.finish(true),
+                        Ok(read) => {
+                            self.received.extend_from_slice(&buf[..read]);
+                            self.finish(false)
+                        }
+                        Err(NetworkError::WouldBlock) => return Err(NetworkError::WouldBlock),
+                        Err(err) => HttpState::Failed(err),
+                    }
+                }
+                HttpState::Done => return Ok(()),
+                HttpState::Failed(err) => {
+                    self.socket = None;
+
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn socket(&mut self) -> Result<&mut dyn Socket, NetworkError> {
+        Ok(self.socket.as_mut().ok_or(NetworkError::Invalid)?.as_mut())
+    }
+
+    fn finish(&mut self, complete: bool) -> HttpState {
+        match HttpResponse::parse(&self.received, complete, self.method != "HEAD") {
+            Some(response) => {
+                self.response = Some(response);
+                self.socket = None;
+                self.received = Vec::new();
+
+                HttpState::Done
+            }
+            None if complete => HttpState::Failed(NetworkError::ConnectionLost),
+            None => HttpState::Receiving,
+        }
+    }
+
+    fn request_bytes(&self) -> Vec<u8> {
+        // proxies take the absolute url
+        let target = match self.proxy {
+            Some(_) => format!("http://{}{}", self.url.authority(), self.url.path),
+            None => self.url.path.clone(),
+        };
+
+        let mut head = format!("{} {} HTTP/1.0\r\n", self.method, target);
+        if self.request_property("Host").is_none() {
+            head.push_str(&format!("Host: {}\r\n", self.url.authority()));
+        }
+        for (key, value) in &self.request_headers {
+            head.push_str(&format!("{key}: {value}\r\n"));
+        }
+        if !self.request_body.is_empty() && self.request_property("Content-Length").is_none() {
+            head.push_str(&format!("Content-Length: {}\r\n", self.request_body.len()));
+        }
+        head.push_str("\r\n");
+
+        let mut request = head.into_bytes();
+        request.extend_from_slice(&self.request_body);
+
+        request
+    }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+    use spin::Mutex;
+
+    use crate::network::{Network, NetworkError, Socket};
+
+    use super::HttpConnection;
+
+    struct ScriptedSocket {
+        sent: Arc<Mutex<Vec<u8>>>,
+        response: Vec<u8>,
+        polls: usize,
+    }
+
+    impl Socket for ScriptedSocket {
+        fn poll_connect(&mut self) -> Result<(), NetworkError> {
+            self.polls += 1;
+            if self.polls < 2 { Err(NetworkError::WouldBlock) } else { Ok(()) }
+        }
+
+        fn poll_readable(&mut self) -> Result<(), NetworkError> {
+            Ok(())
+        }
+
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, NetworkError> {
+            let length = self.response.len().min(buf.len()).min(16);
+            buf[..length].copy_from_slice(&self.response[..length]);
+            self.response.drain(..length);
+
+            Ok(length)
+        }
+
+        fn write(&mut self, data: &[u8]) -> Result<usize, NetworkError> {
+            self.sent.lock().extend_from_slice(data);
+
+            Ok(data.len())
+        }
+    }
+
+    struct ScriptedNetwork {
+        sent: Arc<Mutex<Vec<u8>>>,
+        response: &'static [u8],
+    }
+
+    impl Network for ScriptedNetwork {
+        fn resolve(&self, host: &str) -> Result<[u8; 4], NetworkError> {
+            assert_eq!(host, "example.com");
+
+            Ok([127, 0, 0, 1])
+        }
+
+        fn connect(&self, address: [u8; 4], port: u16) -> Result<Box<dyn Socket>, NetworkError> {
+            assert_eq!((address, port), ([127, 0, 0, 1], 8080));
+
+            Ok(Box::new(ScriptedSocket {
+                sent: self.sent.clone(),
+                response: self.response.to_vec(),
+                polls: 0,
+            }))
+        }
+    }
+
+    #[test]
+    fn post_request_round_trip() {
+        let network = ScriptedNetwork {
+            sent: Arc::new(Mutex::new(Vec::new())),
+            response: b"HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 5\r\n\r\nhello, trailing",
+        };
+
+        let mut connection = HttpConnection::new("http://example.com:8080/rank?id=1").unwrap();
+        assert!(connection.set_method("post"));
+        assert!(connection.set_request_property("User-Agent", "wie"));
+        assert!(connection.write(b"score=10"));
+
+        assert_eq!(connection.poll(&network), Err(NetworkError::WouldBlock));
+        while connection.poll(&network) == Err(NetworkError::WouldBlock) {}
+        assert!(!connection.write(b"late"));
+
+        assert_eq!(
+            &network.sent.lock()[..],
+            b"POST /rank?id=1 HTTP/1.0\r\nHost: example.com:8080\r\nUser-Agent: wie\r\nContent-Length: 8\r\n\r\nscore=10"
+        );
+
+        let response = connection.response().unwrap();
+        assert_eq!((response.code, response.message.as_str()), (200, "OK"));
+        assert_eq!(response.header("content-type"), Some("text/plain"));
+        assert_eq!(response.content_length(), Some(5));
+
+        let mut buf = [0; 4];
+        assert_eq!(connection.read(&mut buf), 4);
+        assert_eq!(connection.read(&mut buf), 1);
+        assert_eq!(&buf[..1], b"o");
+        assert_eq!(connection.read(&mut buf), 0);
+    }
+
+    #[test]
+    fn rejects_unsupported_urls() {
+        assert!(HttpConnection::new("https://example.com/").is_none());
+        assert!(HttpConnection::new("http://:80/").is_none());
+        assert!(HttpConnection::new("http://example.com:http/").is_none());
+    }
+}
@@ -1,6 +1,14 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, vec::Vec};
 
-use crate::{audio_sink::AudioSink, database::DatabaseRepository, screen::Screen, time::Instant};
+use crate::{
+    audio_sink::AudioSink,
+    database::DatabaseRepository,
+    device_profile::DeviceProfile,
+    network::{Network, OfflineNetwork},
+    screen::Screen,
+    system::DEFAULT_STORAGE_QUOTA,
+    time::Instant,
+};
 
 pub trait Platform: Send + Sync {
     fn screen(&self) -> &dyn Screen;
@@ -12,6 +20,40 @@ pub trait Platform: Send + Sync {
     fn write_stderr(&self, buf: &[u8]);
     fn exit(&self);
     fn vibrate(&self, duration_ms: u64, intensity: u8);
+
+    /// Storage quota in bytes shared by the files and databases of `aid`.
+    fn storage_quota(&self, _aid: &str) -> u64 {
+        DEFAULT_STORAGE_QUOTA
+    }
+
+    /// Profile of the emulated handset, read once when the [`System`](crate::System) is created.
+    fn device_profile(&self) -> DeviceProfile {
+        DeviceProfile::default()
+    }
+
+    /// Host network stack; platforms without one leave applications offline.
+    fn network(&self) -> &dyn Network {
+        &OfflineNetwork
+    }
+}
+
+/// Metadata of a file or directory in a [`Filesystem`].
+#[derive(Clone, Copy)]
+pub struct FileMetadata {
+    pub is_dir: bool,
+    /// Size in bytes; always `0` for directories.
+    pub size: usize,
+    /// `None` when the backend does not track timestamps.
+    pub created: Option<Instant>,
+    pub modified: Option<Instant>,
+}
+
+/// Single entry returned by [`Filesystem::list`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DirEntry {
+    /// Entry name relative to the listed directory, without separators.
+    pub name: String,
+    pub is_dir: bool,
 }
 
 /// Platform filesystem abstraction. Every method is scoped by `aid`;
@@ -51,4 +93,35 @@ pub trait Filesystem: Send + Sync {
     /// - `len > current_size` → zero-fill extend.
     /// - `len < current_size` → tail bytes dropped.
     async fn truncate(&self, aid: &str, path: &str, len: usize);
+
+    /// Metadata of the file or directory at `path`.
+    ///
+    /// - An empty `path` addresses the aid root, which always exists as a
+    ///   directory.
+    /// - Missing entry → `None`.
+    async fn metadata(&self, aid: &str, path: &str) -> Option<FileMetadata>;
+
+    /// Immediate children of the directory at `path` (empty = aid root).
+    ///
+    /// - `path` missing or not a directory → `None`.
+    /// - Order of the returned entries is unspecified.
+    async fn list(&self, aid: &str, path: &str) -> Option<Vec<DirEntry>>;
+
+    /// Delete the file at `path`. Directories are not removed; use
+    /// `remove_dir`. Returns `false` if nothing was deleted.
+    async fn remove(&self, aid: &str, path: &str) -> bool;
+
+    /// Move the file at `from` to `to`, replacing any file already at `to`
+    /// and creating missing intermediate directories. Directories cannot be
+    /// renamed. Returns `false` if `from` is not a file or on failure.
+    async fn rename(&self, aid: &str, from: &str, to: &str) -> bool;
+
+    /// Create the directory at `path` and any missing parents. Returns
+    /// `true` if `path` is a directory afterwards, `false` if a file is in
+    /// the way or on failure.
+    async fn create_dir(&self, aid: &str, path: &str) -> bool;
+
+    /// Remove the directory at `path`. Only empty directories are removed;
+    /// missing, non-empty, or non-directory paths return `false`.
+    async fn remove_dir(&self, aid: &str, path: &str) -> bool;
 }
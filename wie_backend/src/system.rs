@@ -1,6 +1,7 @@
 mod audio;
 mod event_queue;
 mod file_system;
+mod storage;
 
 use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc};
 
@@ -10,17 +11,22 @@ use wie_util::Result;
 
 use crate::{
     AsyncCallable,
+    database::DatabaseRepository,
+    device_profile::DeviceProfile,
     executor::Executor,
+    input_method::{InputMethod, InputMode},
+    network::Connections,
     platform::Platform,
     task::{SleepFuture, YieldFuture},
     task_runner::TaskRunner,
 };
 
-use self::{audio::Audio, event_queue::EventQueue};
+use self::{audio::Audio, event_queue::EventQueue, storage::TrackedDatabaseRepository};
 
 pub use self::{
     event_queue::{Event, KeyCode},
     file_system::FilesystemOverlay,
+    storage::{DEFAULT_STORAGE_QUOTA, Storage},
 };
 
 #[derive(Clone)]
@@ -29,9 +35,14 @@ pub struct System {
     aid: String,
     executor: Executor,
     platform: Arc<Box<dyn Platform>>,
+    device_profile: Arc<DeviceProfile>,
     filesystem: FilesystemOverlay,
+    storage: Storage,
+    database_repository: TrackedDatabaseRepository,
     event_queue: Arc<RwLock<EventQueue>>,
     audio: Arc<RwLock<Audio>>,
+    input_method: Arc<RwLock<InputMethod>>,
+    connections: Arc<RwLock<Connections>>,
     task_runner: Arc<dyn TaskRunner>,
 }
 
@@ -42,15 +53,23 @@ impl System {
     {
         let audio_sink = platform.audio_sink();
         let platform = Arc::new(platform);
+        let storage = Storage::new(platform.clone(), aid, pid);
+        let device_profile = platform.device_profile();
+        let input_method = InputMethod::new(InputMode::Korean, device_profile.keypad);
 
         Self {
             pid: pid.to_owned(),
             aid: aid.to_owned(), // TODO create metadata dictionary or something
             executor: Executor::new(),
-            filesystem: FilesystemOverlay::new(platform.clone(), aid),
+            filesystem: FilesystemOverlay::new(platform.clone(), aid, storage.clone()),
+            database_repository: TrackedDatabaseRepository::new(storage.clone()),
+            storage,
+            connections: Arc::new(RwLock::new(Connections::new(platform.clone()))),
             platform,
+            device_profile: Arc::new(device_profile),
             event_queue: Arc::new(RwLock::new(EventQueue::new())),
             audio: Arc::new(RwLock::new(Audio::new(audio_sink))),
+            input_method: Arc::new(RwLock::new(input_method)),
             task_runner: Arc::new(task_runner),
         }
     }
@@ -87,6 +106,18 @@ impl System {
         &self.filesystem
     }
 
+    /// Storage quota accounting shared by files and databases.
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    /// Databases of the application. Goes through here rather than
+    /// `Platform::database_repository` so record writes count against the
+    /// storage quota.
+    pub fn database_repository(&self) -> &dyn DatabaseRepository {
+        &self.database_repository
+    }
+
     pub fn pid(&self) -> &str {
         &self.pid
     }
@@ -95,6 +126,10 @@ impl System {
         &self.aid
     }
 
+    pub fn device_profile(&self) -> &DeviceProfile {
+        &self.device_profile
+    }
+
     pub fn platform(&self) -> &dyn Platform {
         self.platform.as_ref().as_ref()
     }
@@ -106,4 +141,14 @@ impl System {
     pub fn event_queue(&self) -> RwLockWriteGuard<'_, EventQueue> {
         self.event_queue.write()
     }
+
+    /// Handset-wide input method, used by text APIs that do not keep their own per-control state.
+    pub fn input_method(&self) -> RwLockWriteGuard<'_, InputMethod> {
+        self.input_method.write()
+    }
+
+    /// Sockets and HTTP requests opened by the application.
+    pub fn connections(&self) -> RwLockWriteGuard<'_, Connections> {
+        self.connections.write()
+    }
 }
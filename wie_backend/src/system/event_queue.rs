@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, collections::VecDeque};
+use alloc::{boxed::Box, collections::VecDeque, string::String};
 use core::pin::Pin;
 
 use wie_util::Result;
@@ -38,12 +38,23 @@ pub enum KeyCode {
 impl KeyCode {
     // TODO we can use libraries like strum
     pub fn parse(string: &str) -> KeyCode {
-        match string {
+        Self::from_name(string).unwrap_or_else(|| unimplemented!("Unknown key: {string}"))
+    }
+
+    pub fn from_name(string: &str) -> Option<KeyCode> {
+        Some(match string {
             "UP" => KeyCode::UP,
             "DOWN" => KeyCode::DOWN,
             "LEFT" => KeyCode::LEFT,
             "RIGHT" => KeyCode::RIGHT,
             "OK" => KeyCode::OK,
+            "LEFT_SOFT_KEY" => KeyCode::LEFT_SOFT_KEY,
+            "RIGHT_SOFT_KEY" => KeyCode::RIGHT_SOFT_KEY,
+            "CLR" | "CLEAR" => KeyCode::CLEAR,
+            "CALL" => KeyCode::CALL,
+            "HANGUP" => KeyCode::HANGUP,
+            "VOLUME_UP" => KeyCode::VOLUME_UP,
+            "VOLUME_DOWN" => KeyCode::VOLUME_DOWN,
             "0" => KeyCode::NUM0,
             "1" => KeyCode::NUM1,
             "2" => KeyCode::NUM2,
@@ -56,9 +67,8 @@ impl KeyCode {
             "9" => KeyCode::NUM9,
             "#" => KeyCode::HASH,
             "*" => KeyCode::STAR,
-            "CLR" => KeyCode::CLEAR,
-            _ => unimplemented!("Unknown key: {string}"),
-        }
+            _ => return None,
+        })
     }
 }
 
@@ -69,6 +79,7 @@ pub enum Event {
     Keydown(KeyCode),
     Keyup(KeyCode),
     Keyrepeat(KeyCode),
+    Text(String), // text composed on the host keyboard, entered as is without going through the input method
     Timer { due: Instant, callback: TimerCallback },
     Notify { r#type: i32, param1: i32, param2: i32 }, // wipi notifyEvent
 }
@@ -100,6 +111,11 @@ impl EventQueue {
         self.events.push_back(event);
     }
 
+    // puts back the unhandled part of an event so that it is handled before anything queued after it
+    pub fn push_front(&mut self, event: Event) {
+        self.events.push_front(event);
+    }
+
     pub fn pop(&mut self) -> Option<Event> {
         self.events.pop_front()
     }
@@ -1,29 +1,48 @@
-use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc, vec::Vec};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use core::cmp::min;
 
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use spin::Mutex;
 
-use crate::platform::Platform;
+use crate::platform::{DirEntry, FileMetadata, Platform};
+
+use super::storage::Storage;
+
+/// Platform file recording deleted virtual files, one normalized path per
+/// line. Hidden from guests so they cannot clobber it.
+const DELETED_LIST_PATH: &str = ".wie_deleted";
 
 /// Normalize a guest-supplied path so both overlay layers see the same key.
 ///
 /// - Leading `/` are stripped (archive paths often carry them).
 /// - `.` segments are dropped.
-/// - `..` segments, trailing `/`, backslashes, and empty results all
-///   return `None`.
+/// - `..` segments, trailing `/`, backslashes, empty results, and the
+///   overlay's own bookkeeping file all return `None`.
 fn normalize_guest_path(path: &str) -> Option<String> {
-    if path.contains('\\') {
+    if path.ends_with('/') {
         return None;
     }
 
-    let trimmed = path.trim_start_matches('/');
-    if trimmed.is_empty() || trimmed.ends_with('/') {
+    normalize_guest_dir(path).filter(|x| !x.is_empty())
+}
+
+/// Directory variant of [`normalize_guest_path`]: a trailing `/` is
+/// allowed and an empty result addresses the root.
+fn normalize_guest_dir(path: &str) -> Option<String> {
+    if path.contains('\\') {
         return None;
     }
 
     let mut out = String::new();
-    for seg in trimmed.split('/') {
+    for seg in path.split('/') {
         match seg {
             "" => continue,
             "." => continue,
@@ -37,7 +56,7 @@ fn normalize_guest_path(path: &str) -> Option<String> {
         }
     }
 
-    if out.is_empty() { None } else { Some(out) }
+    if out == DELETED_LIST_PATH { None } else { Some(out) }
 }
 
 /// Unified filesystem view exposed by `System::filesystem()`.
@@ -45,19 +64,26 @@ fn normalize_guest_path(path: &str) -> Option<String> {
 /// Wraps the persistent `Platform::filesystem()` backend and an in-memory
 /// virtual layer holding archive resources. Writes always hit the platform
 /// backend; reads prefer the platform backend and fall back to the virtual
-/// layer. Paths are normalized internally so callers pass raw guest paths.
+/// layer. Deleting a virtual file records it in a persistent list so it
+/// stays deleted across runs. Writes that grow the platform backend are
+/// rejected once the application's storage quota is exhausted. Paths are
+/// normalized internally so callers pass raw guest paths.
 #[derive(Clone)]
 pub struct FilesystemOverlay {
     platform: Arc<Box<dyn Platform>>,
     virtual_files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    deleted_virtual: Arc<Mutex<Option<HashSet<String>>>>,
+    storage: Storage,
     aid: Arc<str>,
 }
 
 impl FilesystemOverlay {
-    pub fn new(platform: Arc<Box<dyn Platform>>, aid: &str) -> Self {
+    pub fn new(platform: Arc<Box<dyn Platform>>, aid: &str, storage: Storage) -> Self {
         Self {
             platform,
             virtual_files: Arc::new(Mutex::new(HashMap::new())),
+            deleted_virtual: Arc::new(Mutex::new(None)),
+            storage,
             aid: Arc::from(aid),
         }
     }
@@ -71,11 +97,111 @@ impl FilesystemOverlay {
         normalize_guest_path(path).is_some()
     }
 
+    async fn deleted_virtual(&self) -> HashSet<String> {
+        if let Some(deleted) = self.deleted_virtual.lock().as_ref() {
+            return deleted.clone();
+        }
+
+        let filesystem = self.platform.filesystem();
+        let size = filesystem.size(&self.aid, DELETED_LIST_PATH).await.unwrap_or(0);
+        let mut buf = vec![0; size];
+        let read = filesystem.read(&self.aid, DELETED_LIST_PATH, 0, size, &mut buf).await.unwrap_or(0);
+
+        let deleted = String::from_utf8_lossy(&buf[..read])
+            .lines()
+            .filter(|x| !x.is_empty())
+            .map(|x| x.to_string())
+            .collect::<HashSet<_>>();
+        *self.deleted_virtual.lock() = Some(deleted.clone());
+
+        deleted
+    }
+
+    async fn set_virtual_deleted(&self, normalized: &str, deleted: bool) {
+        let mut list = self.deleted_virtual().await;
+        let changed = if deleted {
+            list.insert(normalized.to_owned())
+        } else {
+            list.remove(normalized)
+        };
+        if !changed {
+            return;
+        }
+
+        let mut paths = list.iter().collect::<Vec<_>>();
+        paths.sort();
+        let data = paths.into_iter().map(|x| format!("{x}\n")).collect::<String>().into_bytes();
+        let filesystem = self.platform.filesystem();
+        let old_size = self.stored_size(DELETED_LIST_PATH).await;
+        filesystem.truncate(&self.aid, DELETED_LIST_PATH, 0).await;
+        if filesystem.write(&self.aid, DELETED_LIST_PATH, 0, &data).await != data.len() {
+            tracing::warn!(path = normalized, "failed to persist deleted virtual file list");
+        }
+        self.storage.record_resize(old_size, self.stored_size(DELETED_LIST_PATH).await);
+
+        *self.deleted_virtual.lock() = Some(list);
+    }
+
+    /// Whether a live (not deleted) virtual file exists at `normalized`.
+    async fn has_virtual(&self, normalized: &str) -> bool {
+        self.virtual_files.lock().contains_key(normalized) && !self.deleted_virtual().await.contains(normalized)
+    }
+
+    /// Live virtual file paths below directory `normalized` (empty = root).
+    async fn virtual_paths_under(&self, normalized: &str) -> Vec<String> {
+        let prefix = if normalized.is_empty() {
+            String::new()
+        } else {
+            format!("{normalized}/")
+        };
+        let deleted = self.deleted_virtual().await;
+
+        self.virtual_files
+            .lock()
+            .keys()
+            .filter(|x| x.starts_with(&prefix) && !deleted.contains(*x))
+            .map(|x| x[prefix.len()..].to_owned())
+            .collect()
+    }
+
+    /// Bytes the platform backend currently holds for `normalized`.
+    async fn stored_size(&self, normalized: &str) -> u64 {
+        self.platform.filesystem().size(&self.aid, normalized).await.unwrap_or(0) as u64
+    }
+
+    /// Bytes the platform backend will hold for `normalized` once any
+    /// virtual data is materialized.
+    async fn pending_size(&self, normalized: &str) -> usize {
+        if let Some(size) = self.platform.filesystem().size(&self.aid, normalized).await {
+            return size;
+        }
+        if !self.has_virtual(normalized).await {
+            return 0;
+        }
+        self.virtual_files.lock().get(normalized).map(|d| d.len()).unwrap_or(0)
+    }
+
+    /// Checks the storage quota before `normalized` grows to `new_len`
+    /// bytes on the platform backend.
+    async fn has_space_for(&self, normalized: &str, new_len: usize) -> bool {
+        let current = self.platform.filesystem().size(&self.aid, normalized).await.unwrap_or(0);
+        let growth = new_len.saturating_sub(current) as u64;
+        if self.storage.fits(growth).await {
+            return true;
+        }
+
+        tracing::warn!(path = normalized, growth, quota = self.storage.quota(), "storage quota exceeded");
+        false
+    }
+
     async fn materialize_virtual(&self, normalized: &str) -> bool {
         let filesystem = self.platform.filesystem();
         if filesystem.exists(&self.aid, normalized).await {
             return true;
         }
+        if !self.has_virtual(normalized).await {
+            return true;
+        }
 
         let virtual_data = self.virtual_files.lock().get(normalized).cloned();
         let Some(virtual_data) = virtual_data else {
@@ -83,6 +209,7 @@ impl FilesystemOverlay {
         };
 
         let written = filesystem.write(&self.aid, normalized, 0, &virtual_data).await;
+        self.storage.record_resize(0, self.stored_size(normalized).await);
         if written != virtual_data.len() {
             tracing::warn!(
                 path = normalized,
@@ -104,7 +231,7 @@ impl FilesystemOverlay {
         if self.platform.filesystem().exists(&self.aid, &normalized).await {
             return true;
         }
-        self.virtual_files.lock().contains_key(&normalized)
+        self.has_virtual(&normalized).await
     }
 
     pub async fn size(&self, path: &str) -> Option<usize> {
@@ -113,6 +240,9 @@ impl FilesystemOverlay {
         if let Some(size) = self.platform.filesystem().size(&self.aid, &normalized).await {
             return Some(size);
         }
+        if !self.has_virtual(&normalized).await {
+            return None;
+        }
         self.virtual_files.lock().get(&normalized).map(|d| d.len())
     }
 
@@ -123,6 +253,9 @@ impl FilesystemOverlay {
         if plat_fs.exists(&self.aid, &normalized).await {
             return plat_fs.read(&self.aid, &normalized, offset, count, buf).await;
         }
+        if !self.has_virtual(&normalized).await {
+            return None;
+        }
 
         let files = self.virtual_files.lock();
         let data = files.get(&normalized)?;
@@ -138,53 +271,255 @@ impl FilesystemOverlay {
         let Some(normalized) = normalize_guest_path(path) else {
             return 0;
         };
+        let new_len = self.pending_size(&normalized).await.max(offset + data.len());
+        if !self.has_space_for(&normalized, new_len).await {
+            return 0;
+        }
         if !self.materialize_virtual(&normalized).await {
             return 0;
         }
-        self.platform.filesystem().write(&self.aid, &normalized, offset, data).await
+        let old_size = self.stored_size(&normalized).await;
+        let written = self.platform.filesystem().write(&self.aid, &normalized, offset, data).await;
+        self.storage.record_resize(old_size, self.stored_size(&normalized).await);
+        if written == data.len() {
+            self.set_virtual_deleted(&normalized, false).await;
+        }
+
+        written
     }
 
     pub async fn truncate(&self, path: &str, len: usize) -> bool {
         let Some(normalized) = normalize_guest_path(path) else {
             return false;
         };
+        let new_len = self.pending_size(&normalized).await.max(len);
+        if !self.has_space_for(&normalized, new_len).await {
+            return false;
+        }
         if !self.materialize_virtual(&normalized).await {
             return false;
         }
+        let old_size = self.stored_size(&normalized).await;
         let filesystem = self.platform.filesystem();
         filesystem.truncate(&self.aid, &normalized, len).await;
-        filesystem.size(&self.aid, &normalized).await == Some(len)
+        let new_size = filesystem.size(&self.aid, &normalized).await;
+        self.storage.record_resize(old_size, new_size.unwrap_or(0) as u64);
+        if new_size != Some(len) {
+            return false;
+        }
+        self.set_virtual_deleted(&normalized, false).await;
+
+        true
+    }
+
+    /// Metadata of a file or directory; the root (`""` or `/`) is always a
+    /// directory. Virtual entries carry no timestamps.
+    pub async fn metadata(&self, path: &str) -> Option<FileMetadata> {
+        let normalized = normalize_guest_dir(path)?;
+
+        if let Some(metadata) = self.platform.filesystem().metadata(&self.aid, &normalized).await {
+            return Some(metadata);
+        }
+
+        if !normalized.is_empty() && !path.ends_with('/') && self.has_virtual(&normalized).await {
+            let size = self.virtual_files.lock().get(&normalized).map(|d| d.len())?;
+            return Some(FileMetadata {
+                is_dir: false,
+                size,
+                created: None,
+                modified: None,
+            });
+        }
+
+        (!self.virtual_paths_under(&normalized).await.is_empty()).then_some(FileMetadata {
+            is_dir: true,
+            size: 0,
+            created: None,
+            modified: None,
+        })
+    }
+
+    pub async fn is_dir(&self, path: &str) -> bool {
+        self.metadata(path).await.is_some_and(|x| x.is_dir)
+    }
+
+    /// Merged listing of both layers, sorted by name. `None` if `path` is
+    /// not a directory in either layer.
+    pub async fn list(&self, path: &str) -> Option<Vec<DirEntry>> {
+        let normalized = normalize_guest_dir(path)?;
+
+        let platform_entries = self.platform.filesystem().list(&self.aid, &normalized).await;
+        let virtual_paths = self.virtual_paths_under(&normalized).await;
+        if platform_entries.is_none() && virtual_paths.is_empty() {
+            return None;
+        }
+
+        let mut result = platform_entries
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|x| !(normalized.is_empty() && x.name == DELETED_LIST_PATH))
+            .collect::<Vec<_>>();
+        for virtual_path in virtual_paths {
+            let (name, is_dir) = match virtual_path.split_once('/') {
+                Some((name, _)) => (name, true),
+                None => (virtual_path.as_str(), false),
+            };
+            if !result.iter().any(|x| x.name == name) {
+                result.push(DirEntry {
+                    name: name.to_owned(),
+                    is_dir,
+                });
+            }
+        }
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Some(result)
+    }
+
+    /// Delete a file from both layers. Returns `false` if no file existed.
+    pub async fn remove(&self, path: &str) -> bool {
+        let Some(normalized) = normalize_guest_path(path) else {
+            return false;
+        };
+
+        let old_size = self.stored_size(&normalized).await;
+        let removed = self.platform.filesystem().remove(&self.aid, &normalized).await;
+        if removed {
+            self.storage.record_resize(old_size, 0);
+        }
+        if self.has_virtual(&normalized).await {
+            self.set_virtual_deleted(&normalized, true).await;
+            return true;
+        }
+
+        removed
+    }
+
+    /// Move a file, materializing it first if it only exists in the virtual
+    /// layer.
+    pub async fn rename(&self, from: &str, to: &str) -> bool {
+        let (Some(from), Some(to)) = (normalize_guest_path(from), normalize_guest_path(to)) else {
+            return false;
+        };
+        if from == to || !self.exists(&from).await || self.is_dir(&to).await {
+            return false;
+        }
+        if !self.has_space_for(&from, self.pending_size(&from).await).await || !self.materialize_virtual(&from).await {
+            return false;
+        }
+
+        let old_size = self.stored_size(&from).await + self.stored_size(&to).await;
+        if !self.platform.filesystem().rename(&self.aid, &from, &to).await {
+            return false;
+        }
+        self.storage.record_resize(old_size, self.stored_size(&to).await);
+        if self.virtual_files.lock().contains_key(&from) {
+            self.set_virtual_deleted(&from, true).await;
+        }
+        self.set_virtual_deleted(&to, false).await;
+
+        true
+    }
+
+    pub async fn create_dir(&self, path: &str) -> bool {
+        let Some(normalized) = normalize_guest_path(path.trim_end_matches('/')) else {
+            return false;
+        };
+
+        let ancestors = normalized
+            .match_indices('/')
+            .map(|(index, _)| &normalized[..index])
+            .chain(core::iter::once(normalized.as_str()));
+        for ancestor in ancestors {
+            if self.has_virtual(ancestor).await {
+                return false;
+            }
+        }
+
+        self.platform.filesystem().create_dir(&self.aid, &normalized).await
+    }
+
+    /// Remove an empty directory. Directories holding only deleted virtual
+    /// files count as empty.
+    pub async fn remove_dir(&self, path: &str) -> bool {
+        let Some(normalized) = normalize_guest_path(path.trim_end_matches('/')) else {
+            return false;
+        };
+        if !matches!(self.list(&normalized).await, Some(entries) if entries.is_empty()) {
+            return false;
+        }
+
+        self.platform.filesystem().remove_dir(&self.aid, &normalized).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use alloc::{
+        borrow::ToOwned,
         boxed::Box,
+        format,
         string::{String, ToString},
         sync::Arc,
         vec,
         vec::Vec,
     };
 
-    use hashbrown::HashMap;
+    use hashbrown::{HashMap, HashSet};
     use spin::Mutex;
 
     use crate::{
         audio_sink::AudioSink,
-        database::DatabaseRepository,
-        platform::{Filesystem, Platform},
+        database::{Database, DatabaseRepository},
+        platform::{DirEntry, FileMetadata, Filesystem, Platform},
         screen::Screen,
         time::Instant,
     };
 
-    use super::FilesystemOverlay;
+    use super::{super::storage::Storage, FilesystemOverlay};
 
     #[derive(Default)]
     struct StubFilesystem {
         files: Mutex<HashMap<(String, String), Vec<u8>>>,
+        dirs: Mutex<HashSet<(String, String)>>,
         write_limit: Option<usize>,
         fail_truncate: bool,
+        listings: Arc<Mutex<usize>>,
+    }
+    impl StubFilesystem {
+        fn entries(&self, aid: &str, path: &str) -> Vec<DirEntry> {
+            let prefix = if path.is_empty() { String::new() } else { format!("{path}/") };
+            let files = self
+                .files
+                .lock()
+                .keys()
+                .filter(|(a, _)| a == aid)
+                .map(|(_, p)| (p.clone(), false))
+                .collect::<Vec<_>>();
+            let dirs = self
+                .dirs
+                .lock()
+                .iter()
+                .filter(|(a, _)| a == aid)
+                .map(|(_, p)| (p.clone(), true))
+                .collect::<Vec<_>>();
+
+            let mut result: Vec<DirEntry> = Vec::new();
+            for (entry, is_dir) in files.into_iter().chain(dirs) {
+                let Some(rest) = entry.strip_prefix(&prefix) else { continue };
+                let (name, is_dir) = rest.split_once('/').map(|(name, _)| (name, true)).unwrap_or((rest, is_dir));
+                if !result.iter().any(|e| e.name == name) {
+                    result.push(DirEntry {
+                        name: name.to_owned(),
+                        is_dir,
+                    });
+                }
+            }
+            result
+        }
+        fn is_dir(&self, aid: &str, path: &str) -> bool {
+            path.is_empty() || self.dirs.lock().contains(&(aid.to_string(), path.to_string())) || !self.entries(aid, path).is_empty()
+        }
     }
     #[async_trait::async_trait]
     impl Filesystem for StubFilesystem {
@@ -222,10 +557,63 @@ mod tests {
             let file = files.entry((aid.to_string(), path.to_string())).or_default();
             file.resize(len, 0);
         }
+        async fn metadata(&self, aid: &str, path: &str) -> Option<FileMetadata> {
+            let size = self.files.lock().get(&(aid.to_string(), path.to_string())).map(|v| v.len());
+            let is_dir = size.is_none() && self.is_dir(aid, path);
+            (size.is_some() || is_dir).then(|| FileMetadata {
+                is_dir,
+                size: size.unwrap_or(0),
+                created: None,
+                modified: None,
+            })
+        }
+        async fn list(&self, aid: &str, path: &str) -> Option<Vec<DirEntry>> {
+            *self.listings.lock() += 1;
+            self.is_dir(aid, path).then(|| self.entries(aid, path))
+        }
+        async fn remove(&self, aid: &str, path: &str) -> bool {
+            self.files.lock().remove(&(aid.to_string(), path.to_string())).is_some()
+        }
+        async fn rename(&self, aid: &str, from: &str, to: &str) -> bool {
+            let mut files = self.files.lock();
+            let Some(data) = files.remove(&(aid.to_string(), from.to_string())) else {
+                return false;
+            };
+            files.insert((aid.to_string(), to.to_string()), data);
+            true
+        }
+        async fn create_dir(&self, aid: &str, path: &str) -> bool {
+            self.dirs.lock().insert((aid.to_string(), path.to_string()));
+            true
+        }
+        async fn remove_dir(&self, aid: &str, path: &str) -> bool {
+            self.entries(aid, path).is_empty() && self.dirs.lock().remove(&(aid.to_string(), path.to_string()))
+        }
+    }
+
+    struct StubDatabaseRepository {
+        usage: u64,
+    }
+    #[async_trait::async_trait]
+    impl DatabaseRepository for StubDatabaseRepository {
+        async fn open(&self, _name: &str, _app_id: &str) -> Box<dyn Database> {
+            unimplemented!()
+        }
+        async fn exists(&self, _name: &str, _app_id: &str) -> bool {
+            false
+        }
+        async fn delete(&self, _name: &str, _app_id: &str) -> bool {
+            false
+        }
+        async fn usage(&self, _app_id: &str) -> u64 {
+            self.usage
+        }
     }
 
     struct StubPlatform {
         fs: StubFilesystem,
+        database_repository: StubDatabaseRepository,
+        quota: u64,
     }
     impl Platform for StubPlatform {
         fn screen(&self) -> &dyn Screen {
@@ -235,7 +623,7 @@ mod tests {
             Instant::from_epoch_millis(0)
         }
         fn database_repository(&self) -> &dyn DatabaseRepository {
-            unimplemented!()
+            &self.database_repository
         }
         fn filesystem(&self) -> &dyn Filesystem {
             &self.fs
@@ -247,6 +635,9 @@ mod tests {
         fn write_stderr(&self, _buf: &[u8]) {}
         fn exit(&self) {}
         fn vibrate(&self, _duration_ms: u64, _intensity: u8) {}
+        fn storage_quota(&self, _aid: &str) -> u64 {
+            self.quota
+        }
     }
 
     fn setup() -> FilesystemOverlay {
@@ -254,8 +645,17 @@ mod tests {
     }
 
     fn setup_with_filesystem(fs: StubFilesystem) -> FilesystemOverlay {
-        let platform: Arc<Box<dyn Platform>> = Arc::new(Box::new(StubPlatform { fs }));
-        FilesystemOverlay::new(platform, "test-aid")
+        setup_with_quota(fs, 0, u64::MAX)
+    }
+
+    fn setup_with_quota(fs: StubFilesystem, database_usage: u64, quota: u64) -> FilesystemOverlay {
+        let platform: Arc<Box<dyn Platform>> = Arc::new(Box::new(StubPlatform {
+            fs,
+            database_repository: StubDatabaseRepository { usage: database_usage },
+            quota,
+        }));
+        let storage = Storage::new(platform.clone(), "test-aid", "test-pid");
+        FilesystemOverlay::new(platform, "test-aid", storage)
     }
 
     #[futures_test::test]
@@ -365,4 +765,127 @@ mod tests {
         assert!(!failed_truncate_fs.truncate("truncate.dat", 2).await);
         assert_eq!(failed_truncate_fs.size("truncate.dat").await, Some(4));
     }
+
+    #[futures_test::test]
+    async fn list_merges_layers_and_hides_bookkeeping() {
+        let fs = setup();
+        fs.add_virtual("/res/a.png", vec![1]);
+        fs.add_virtual("top.dat", vec![2]);
+        fs.write("res/b.dat", 0, &[3]).await;
+        assert!(fs.create_dir("save/").await);
+        assert!(fs.remove("top.dat").await);
+
+        let names = |entries: Vec<DirEntry>| entries.into_iter().map(|e| (e.name, e.is_dir)).collect::<Vec<_>>();
+        assert_eq!(
+            names(fs.list("/").await.unwrap()),
+            vec![("res".to_string(), true), ("save".to_string(), true)]
+        );
+        assert_eq!(
+            names(fs.list("res").await.unwrap()),
+            vec![("a.png".to_string(), false), ("b.dat".to_string(), false)]
+        );
+        assert!(fs.list("missing").await.is_none());
+        assert!(!fs.is_valid_path(".wie_deleted"));
+    }
+
+    #[futures_test::test]
+    async fn deleted_virtual_file_stays_deleted() {
+        let fs = setup();
+        fs.add_virtual("a/b.dat", vec![1, 2]);
+
+        assert!(fs.remove("a/b.dat").await);
+        assert!(!fs.exists("a/b.dat").await);
+        assert!(fs.metadata("a").await.is_none());
+        assert!(!fs.remove("a/b.dat").await);
+
+        // a fresh overlay over the same platform reloads the deleted list
+        let reopened = FilesystemOverlay::new(fs.platform.clone(), "test-aid", fs.storage.clone());
+        reopened.add_virtual("a/b.dat", vec![1, 2]);
+        assert!(!reopened.exists("a/b.dat").await);
+
+        // writing recreates the file without resurrecting the archive data
+        assert_eq!(reopened.write("a/b.dat", 0, &[9]).await, 1);
+        assert_eq!(reopened.size("a/b.dat").await, Some(1));
+    }
+
+    #[futures_test::test]
+    async fn rename_materializes_virtual_source() {
+        let fs = setup();
+        fs.add_virtual("old.dat", vec![1, 2, 3]);
+
+        assert!(fs.rename("old.dat", "dir/new.dat").await);
+        assert!(!fs.exists("old.dat").await);
+
+        let mut buf = [0u8; 3];
+        assert_eq!(fs.read("dir/new.dat", 0, 3, &mut buf).await, Some(3));
+        assert_eq!(buf, [1, 2, 3]);
+        assert!(!fs.rename("old.dat", "other.dat").await);
+    }
+
+    #[futures_test::test]
+    async fn remove_dir_requires_empty_directory() {
+        let fs = setup();
+        assert!(fs.create_dir("d").await);
+        fs.write("d/f", 0, &[1]).await;
+
+        assert!(!fs.remove_dir("d").await);
+        assert!(fs.remove("d/f").await);
+        assert!(fs.remove_dir("d").await);
+        assert!(!fs.is_dir("d").await);
+        assert!(!fs.remove_dir("/").await);
+
+        fs.add_virtual("v.dat", vec![1]);
+        assert!(!fs.create_dir("v.dat/sub").await);
+    }
+
+    #[futures_test::test]
+    async fn writes_beyond_quota_are_rejected() {
+        let fs = setup_with_quota(StubFilesystem::default(), 4, 10);
+        fs.add_virtual("res.bin", vec![0; 100]);
+
+        assert_eq!(fs.write("a", 0, &[1; 4]).await, 4);
+        assert_eq!(fs.storage.used().await, 8);
+        assert_eq!(fs.storage.available().await, 2);
+
+        assert_eq!(fs.write("a", 4, &[1; 3]).await, 0);
+        assert!(!fs.truncate("b", 3).await);
+        assert!(!fs.rename("res.bin", "copy.bin").await);
+
+        // overwriting in place and shrinking never need more space
+        assert_eq!(fs.write("a", 0, &[2; 4]).await, 4);
+        assert!(fs.truncate("a", 1).await);
+        assert_eq!(fs.write("b", 0, &[3; 5]).await, 5);
+    }
+
+    #[futures_test::test]
+    async fn usage_is_tracked_without_rescanning() {
+        let listings = Arc::new(Mutex::new(0));
+        let fs = setup_with_quota(
+            StubFilesystem {
+                listings: listings.clone(),
+                ..Default::default()
+            },
+            4,
+            100,
+        );
+        fs.add_virtual("res.bin", vec![0; 6]);
+        fs.write("a", 0, &[1; 4]).await;
+        assert_eq!(fs.storage.used().await, 8);
+
+        let scans = *listings.lock();
+
+        fs.write("a", 2, &[2; 6]).await;
+        assert!(fs.truncate("b", 3).await);
+        assert!(fs.rename("b", "a").await);
+        assert!(fs.rename("res.bin", "c").await);
+        assert!(fs.remove("c").await);
+        fs.write("d", 0, &[3; 2]).await;
+
+        assert_eq!(*listings.lock(), scans);
+        let tracked = fs.storage.used().await;
+        assert_eq!(*listings.lock(), scans);
+
+        let rescanned = Storage::new(fs.platform.clone(), "test-aid", "test-pid").used().await;
+        assert_eq!(tracked, rescanned);
+    }
 }
@@ -0,0 +1,207 @@
+use alloc::{boxed::Box, format, string::String, sync::Arc, vec, vec::Vec};
+
+use spin::Mutex;
+
+use crate::{
+    database::{Database, DatabaseRepository, RecordId},
+    platform::Platform,
+};
+
+/// Quota applied when the platform does not configure one; the common
+/// allowance of WIPI-era handsets.
+pub const DEFAULT_STORAGE_QUOTA: u64 = 1024 * 1024;
+
+/// Per-application storage accounting exposed by `System::storage()`.
+///
+/// Files written through the filesystem overlay and the application's
+/// databases share a single quota taken from `Platform::storage_quota`.
+/// Archive resources are part of the installed package and do not count.
+///
+/// Usage is measured once on first use and then kept current by the
+/// filesystem overlay and `System::database_repository()` as they mutate
+/// storage, so quota checks don't rescan everything.
+#[derive(Clone)]
+pub struct Storage {
+    platform: Arc<Box<dyn Platform>>,
+    aid: Arc<str>,
+    pid: Arc<str>,
+    used: Arc<Mutex<Option<u64>>>,
+}
+
+impl Storage {
+    pub fn new(platform: Arc<Box<dyn Platform>>, aid: &str, pid: &str) -> Self {
+        Self {
+            platform,
+            aid: Arc::from(aid),
+            pid: Arc::from(pid),
+            used: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn quota(&self) -> u64 {
+        self.platform.storage_quota(&self.aid)
+    }
+
+    /// Bytes held by persistent files of this application.
+    async fn file_usage(&self) -> u64 {
+        let filesystem = self.platform.filesystem();
+
+        let mut usage = 0;
+        let mut directories = vec![String::new()];
+        while let Some(directory) = directories.pop() {
+            for entry in filesystem.list(&self.aid, &directory).await.unwrap_or_default() {
+                let path = if directory.is_empty() {
+                    entry.name
+                } else {
+                    format!("{directory}/{}", entry.name)
+                };
+
+                if entry.is_dir {
+                    directories.push(path);
+                } else {
+                    usage += filesystem.size(&self.aid, &path).await.unwrap_or(0) as u64;
+                }
+            }
+        }
+
+        usage
+    }
+
+    /// Bytes held by databases of this application. Databases are keyed by
+    /// pid in the `DatabaseRepository`.
+    async fn database_usage(&self) -> u64 {
+        self.platform.database_repository().usage(&self.pid).await
+    }
+
+    pub async fn used(&self) -> u64 {
+        if let Some(used) = *self.used.lock() {
+            return used;
+        }
+
+        let used = self.file_usage().await + self.database_usage().await;
+        *self.used.lock().get_or_insert(used)
+    }
+
+    /// Records that something in storage changed from `old` to `new` bytes.
+    /// Changes before the first measurement are picked up by it instead.
+    pub fn record_resize(&self, old: u64, new: u64) {
+        if let Some(used) = self.used.lock().as_mut() {
+            *used = used.saturating_add(new).saturating_sub(old);
+        }
+    }
+
+    pub async fn available(&self) -> u64 {
+        self.quota().saturating_sub(self.used().await)
+    }
+
+    /// Whether `additional` more bytes fit in the quota. Writes that do not
+    /// grow storage always fit, so applications over quota can still shrink
+    /// or overwrite their data.
+    pub async fn fits(&self, additional: u64) -> bool {
+        additional == 0 || self.used().await.saturating_add(additional) <= self.quota()
+    }
+}
+
+/// `DatabaseRepository` handed out by `System::database_repository()`,
+/// forwarding to the platform while keeping `Storage` usage current.
+#[derive(Clone)]
+pub struct TrackedDatabaseRepository {
+    storage: Storage,
+}
+
+impl TrackedDatabaseRepository {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabaseRepository for TrackedDatabaseRepository {
+    async fn open(&self, name: &str, app_id: &str) -> Box<dyn Database> {
+        let inner = self.storage.platform.database_repository().open(name, app_id).await;
+
+        Box::new(TrackedDatabase {
+            inner,
+            storage: self.storage.clone(),
+        })
+    }
+
+    async fn exists(&self, name: &str, app_id: &str) -> bool {
+        self.storage.platform.database_repository().exists(name, app_id).await
+    }
+
+    async fn delete(&self, name: &str, app_id: &str) -> bool {
+        let repository = self.storage.platform.database_repository();
+
+        let database = repository.open(name, app_id).await;
+        let mut size = 0;
+        for id in database.get_record_ids().await {
+            size += database.get(id).await.map_or(0, |x| x.len() as u64);
+        }
+        drop(database);
+
+        let deleted = repository.delete(name, app_id).await;
+        if deleted {
+            self.storage.record_resize(size, 0);
+        }
+
+        deleted
+    }
+
+    async fn usage(&self, app_id: &str) -> u64 {
+        self.storage.platform.database_repository().usage(app_id).await
+    }
+}
+
+struct TrackedDatabase {
+    inner: Box<dyn Database>,
+    storage: Storage,
+}
+
+impl TrackedDatabase {
+    async fn record_size(&self, id: RecordId) -> u64 {
+        self.inner.get(id).await.map_or(0, |x| x.len() as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl Database for TrackedDatabase {
+    async fn next_id(&self) -> RecordId {
+        self.inner.next_id().await
+    }
+
+    async fn add(&mut self, data: &[u8]) -> RecordId {
+        let id = self.inner.add(data).await;
+        self.storage.record_resize(0, data.len() as u64);
+
+        id
+    }
+
+    async fn get(&self, id: RecordId) -> Option<Vec<u8>> {
+        self.inner.get(id).await
+    }
+
+    async fn set(&mut self, id: RecordId, data: &[u8]) -> bool {
+        let old = self.record_size(id).await;
+        let result = self.inner.set(id, data).await;
+        if result {
+            self.storage.record_resize(old, data.len() as u64);
+        }
+
+        result
+    }
+
+    async fn delete(&mut self, id: RecordId) -> bool {
+        let old = self.record_size(id).await;
+        let result = self.inner.delete(id).await;
+        if result {
+            self.storage.record_resize(old, 0);
+        }
+
+        result
+    }
+
+    async fn get_record_ids(&self) -> Vec<RecordId> {
+        self.inner.get_record_ids().await
+    }
+}
@@ -1,6 +1,6 @@
 use core::ops::{Add, Sub};
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Instant {
     value: u64,
 }
@@ -0,0 +1,161 @@
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::{fmt::Write, ops::Range};
+
+pub enum TraceEvent {
+    /// An instruction executed inside one of the traced address ranges.
+    Instruction {
+        pc: u32,
+        thumb: bool,
+        opcode: u32,
+        disassembly: String,
+        /// Registers the instruction changed, as (name, new value). PC is left out.
+        changed: Vec<(&'static str, u32)>,
+    },
+    /// A guest call into a native SVC handler.
+    SvcCall {
+        category: u32,
+        id: u32,
+        /// Name of the function behind the SVC, as given by the symbol providers.
+        name: String,
+        args: [u32; 4],
+        /// `args` that point to a printable NUL-terminated string.
+        strings: [Option<String>; 4],
+        lr: u32,
+    },
+    SvcReturn {
+        category: u32,
+        id: u32,
+        result: [u32; 2],
+    },
+}
+
+impl TraceEvent {
+    /// Formats the event as a single JSON object, without the trailing newline.
+    pub fn to_json(&self) -> String {
+        let mut result = String::new();
+
+        match self {
+            Self::Instruction {
+                pc,
+                thumb,
+                opcode,
+                disassembly,
+                changed,
+            } => {
+                let width = if *thumb { 4 } else { 8 };
+                write!(
+                    result,
+                    "{{\"type\":\"insn\",\"pc\":\"{pc:#x}\",\"thumb\":{thumb},\"op\":\"{opcode:0width$x}\",\"asm\":\"{}\",\"regs\":{{",
+                    escape(disassembly)
+                )
+                .unwrap();
+                for (i, (name, value)) in changed.iter().enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    write!(result, "{separator}\"{name}\":\"{value:#x}\"").unwrap();
+                }
+                result.push_str("}}");
+            }
+            Self::SvcCall {
+                category,
+                id,
+                name,
+                args,
+                strings,
+                lr,
+            } => {
+                write!(
+                    result,
+                    "{{\"type\":\"svc\",\"category\":{category},\"id\":{id},\"name\":\"{}\",\"args\":[",
+                    escape(name)
+                )
+                .unwrap();
+                for (i, (arg, string)) in args.iter().zip(strings).enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    match string {
+                        Some(string) => write!(result, "{separator}{{\"value\":\"{arg:#x}\",\"string\":\"{}\"}}", escape(string)).unwrap(),
+                        None => write!(result, "{separator}\"{arg:#x}\"").unwrap(),
+                    }
+                }
+                write!(result, "],\"lr\":\"{lr:#x}\"}}").unwrap();
+            }
+            Self::SvcReturn {
+                category,
+                id,
+                result: [r0, r1],
+            } => {
+                write!(
+                    result,
+                    "{{\"type\":\"ret\",\"category\":{category},\"id\":{id},\"r0\":\"{r0:#x}\",\"r1\":\"{r1:#x}\"}}"
+                )
+                .unwrap();
+            }
+        }
+
+        result
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Receives trace events in execution order.
+pub type TraceCallback = Box<dyn FnMut(TraceEvent) + Send + Sync>;
+
+pub struct TraceOptions {
+    /// Instructions are traced only while PC is inside one of these ranges; SVC calls are always logged.
+    pub ranges: Vec<Range<u32>>,
+    pub callback: TraceCallback,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use super::TraceEvent;
+
+    #[test]
+    fn test_json_lines() {
+        let event = TraceEvent::Instruction {
+            pc: 0x1000,
+            thumb: true,
+            opcode: 0x2001,
+            disassembly: "movs r0, #0x1".into(),
+            changed: vec![("r0", 1), ("cpsr", 0x30)],
+        };
+        assert_eq!(
+            event.to_json(),
+            r#"{"type":"insn","pc":"0x1000","thumb":true,"op":"2001","asm":"movs r0, #0x1","regs":{"r0":"0x1","cpsr":"0x30"}}"#
+        );
+
+        let event = TraceEvent::SvcCall {
+            category: 1,
+            id: 2,
+            name: "GetInterface".into(),
+            args: [0x40000000, 0, 0, 0],
+            strings: [Some("a\"b\n".to_string()), None, None, None],
+            lr: 0x1235,
+        };
+        assert_eq!(
+            event.to_json(),
+            r#"{"type":"svc","category":1,"id":2,"name":"GetInterface","args":[{"value":"0x40000000","string":"a\"b\u000a"},"0x0","0x0","0x0"],"lr":"0x1235"}"#
+        );
+
+        let event = TraceEvent::SvcReturn {
+            category: 1,
+            id: 2,
+            result: [3, 0],
+        };
+        assert_eq!(event.to_json(), r#"{"type":"ret","category":1,"id":2,"r0":"0x3","r1":"0x0"}"#);
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{Parser, Subcommand};
+use wie_core_arm::{ArmCore, MemoryPermission};
+
+/// Check binary patch tables against a KTF client.bin or LGT binary.mod
+/// without booting the game.
+///
+/// Tables use the `data/binary_patches.toml` format. The binary is mapped the
+/// way the runtime maps it: client.bin at 0x100000, binary.mod at its ELF
+/// section addresses.
+#[derive(Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the patch entries that apply to a binary and dry-run their installation.
+    Check {
+        /// KTF client.bin or LGT binary.mod.
+        binary: PathBuf,
+        /// Extra patch table, merged with the built-in one as with `wie_cli --patches`. May be repeated.
+        #[arg(long, value_name = "PATH")]
+        patches: Vec<PathBuf>,
+    },
+    /// Search every binary in a folder for a pattern, written as in a patch table `pattern` field.
+    Search {
+        pattern: String,
+        folder: PathBuf,
+        /// Match an ARM-mode pattern on word boundaries instead of a Thumb one.
+        #[arg(long)]
+        arm: bool,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    match args.command {
+        Command::Check { binary, patches } => check(&binary, &patches),
+        Command::Search { pattern, folder, arm } => search(&pattern, &folder, arm),
+    }
+}
+
+fn check(binary: &Path, patches: &[PathBuf]) -> anyhow::Result<()> {
+    let data = fs::read(binary)?;
+    let (mut core, kind, scan_ranges) = load(&data)?;
+    for path in patches {
+        let source = fs::read_to_string(path)?;
+        wie_core_arm::add_binary_patches(&mut core, &path.display().to_string(), &source).map_err(|e| anyhow::anyhow!("{e}"))?;
+    }
+
+    println!("{}: {kind}, md5 {:x}", binary.display(), md5::compute(&data));
+    for (base, size) in &scan_ranges {
+        println!("  scanning [{base:#x}, {:#x})", base + size);
+    }
+    if data.starts_with(b"\x7fELF") {
+        println!("  note: the LGT runtime does not install binary patches, this is a dry run only");
+    }
+
+    let reports = wie_core_arm::check_binary_patches(&mut core, &data, &scan_ranges).map_err(|e| anyhow::anyhow!("{e}"))?;
+    if !reports.iter().any(|x| x.hash.is_some()) {
+        println!("No hash-keyed entry for this binary");
+    }
+    for report in &reports {
+        let key = report.hash.as_deref().unwrap_or("generic");
+        let selected = if report.selected { "" } else { ", not selected" };
+        println!("\n{} ({}, {key}{selected})", report.name, report.origin);
+        for (pc, kind) in &report.hooks {
+            println!("  hook {pc:#x}: {kind}");
+        }
+        for (address, size) in &report.patches {
+            println!("  patch {address:#x}: {size} bytes");
+        }
+        if let Some(error) = &report.error {
+            println!("  error: {error}");
+        }
+    }
+
+    // the entries were only resolved one by one above; merged generic entries can still collide
+    match wie_core_arm::install_binary_patches(&mut core, &data, &scan_ranges) {
+        Ok(count) => println!("\nInstall: {count} patches/hooks"),
+        Err(err) => println!("\nInstall failed: {err}"),
+    }
+
+    Ok(())
+}
+
+fn search(pattern: &str, folder: &Path, arm: bool) -> anyhow::Result<()> {
+    let mut paths = fs::read_dir(folder)?.map(|x| x.map(|x| x.path())).collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|x| x.is_file());
+    paths.sort();
+
+    let mut matched = 0;
+    for path in &paths {
+        let data = fs::read(path)?;
+        let (mut core, _, scan_ranges) = match load(&data) {
+            Ok(x) => x,
+            Err(err) => {
+                eprintln!("{}: skipped, {err}", path.display());
+                continue;
+            }
+        };
+
+        let addresses = wie_core_arm::find_binary_pattern(&mut core, pattern, arm, &scan_ranges).map_err(|e| anyhow::anyhow!("{e}"))?;
+        if addresses.is_empty() {
+            continue;
+        }
+        matched += 1;
+
+        let addresses = addresses.iter().map(|x| format!("{x:#x}")).collect::<Vec<_>>();
+        println!("{} (md5 {:x}): {}", path.display(), md5::compute(&data), addresses.join(", "));
+    }
+    println!("{matched} of {} binaries matched", paths.len());
+
+    Ok(())
+}
+
+/// Map `data` as the runtime would. Returns the core, a description of the
+/// binary, and the ranges `install_binary_patches` scans.
+fn load(data: &[u8]) -> anyhow::Result<(ArmCore, &'static str, Vec<(u32, u32)>)> {
+    let mut core = ArmCore::new(false, None).map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    if data.starts_with(b"\x7fELF") {
+        let (_, code_ranges) = wie_lgt::load_executable(&mut core, data).map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok((core, "LGT binary.mod", code_ranges))
+    } else {
+        core.load(data, wie_ktf::IMAGE_BASE, data.len(), MemoryPermission::ReadWriteExecute)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok((core, "KTF client.bin", vec![(wie_ktf::IMAGE_BASE, data.len() as u32)]))
+    }
+}
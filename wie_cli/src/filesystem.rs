@@ -2,11 +2,12 @@ use std::{
     fs::{self, OpenOptions},
     io::{Read, Seek, SeekFrom, Write},
     path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use directories::ProjectDirs;
 
-use wie_backend::Filesystem;
+use wie_backend::{DirEntry, FileMetadata, Filesystem, Instant};
 
 /// Persistent filesystem backed by `std::fs` under `<base>/<aid>/fs/<path>`.
 /// Any I/O error or rejected path returns the trait's failure value.
@@ -23,6 +24,15 @@ impl CliFilesystem {
     }
 
     fn path_for(&self, aid: &str, path: &str) -> Option<PathBuf> {
+        self.resolve(aid, path, false)
+    }
+
+    /// Like `path_for`, but an empty `path` resolves to the aid root.
+    fn dir_path_for(&self, aid: &str, path: &str) -> Option<PathBuf> {
+        self.resolve(aid, path, true)
+    }
+
+    fn resolve(&self, aid: &str, path: &str, allow_root: bool) -> Option<PathBuf> {
         let sanitized_aid: String = aid.chars().filter(|c| !matches!(c, '/' | '\\' | '\0')).collect();
         if sanitized_aid.is_empty() || sanitized_aid == "." || sanitized_aid == ".." {
             tracing::error!(aid, path, "rejected: invalid aid");
@@ -41,7 +51,7 @@ impl CliFilesystem {
             }
         }
 
-        if normalized.as_os_str().is_empty() {
+        if normalized.as_os_str().is_empty() && !allow_root {
             tracing::error!(aid, path, "rejected: empty normalized path");
             return None;
         }
@@ -50,6 +60,11 @@ impl CliFilesystem {
     }
 }
 
+fn to_instant(time: std::io::Result<SystemTime>) -> Option<Instant> {
+    let duration = time.ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(Instant::from_epoch_millis(duration.as_millis() as u64))
+}
+
 impl Default for CliFilesystem {
     fn default() -> Self {
         Self::new()
@@ -185,4 +200,127 @@ impl Filesystem for CliFilesystem {
             tracing::warn!(aid, path, error = %err, "truncate: set_len failed");
         }
     }
+
+    async fn metadata(&self, aid: &str, path: &str) -> Option<FileMetadata> {
+        let disk_path = self.dir_path_for(aid, path)?;
+        let md = match disk_path.metadata() {
+            Ok(md) => md,
+            // the aid root is created lazily on first write
+            Err(_) if path.is_empty() => {
+                return Some(FileMetadata {
+                    is_dir: true,
+                    size: 0,
+                    created: None,
+                    modified: None,
+                });
+            }
+            Err(_) => return None,
+        };
+
+        Some(FileMetadata {
+            is_dir: md.is_dir(),
+            size: if md.is_file() { md.len() as usize } else { 0 },
+            created: to_instant(md.created()),
+            modified: to_instant(md.modified()),
+        })
+    }
+
+    async fn list(&self, aid: &str, path: &str) -> Option<Vec<DirEntry>> {
+        let disk_path = self.dir_path_for(aid, path)?;
+        let entries = match fs::read_dir(&disk_path) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound && path.is_empty() => return Some(Vec::new()),
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!(aid, path, error = %err, "list: read_dir failed");
+                }
+                return None;
+            }
+        };
+
+        let result = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                let is_dir = entry.file_type().ok()?.is_dir();
+
+                Some(DirEntry { name, is_dir })
+            })
+            .collect();
+
+        Some(result)
+    }
+
+    async fn remove(&self, aid: &str, path: &str) -> bool {
+        let Some(disk_path) = self.path_for(aid, path) else {
+            return false;
+        };
+        if !disk_path.is_file() {
+            return false;
+        }
+
+        match fs::remove_file(&disk_path) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::warn!(aid, path, error = %err, "remove: remove_file failed");
+                false
+            }
+        }
+    }
+
+    async fn rename(&self, aid: &str, from: &str, to: &str) -> bool {
+        let (Some(from_path), Some(to_path)) = (self.path_for(aid, from), self.path_for(aid, to)) else {
+            return false;
+        };
+        if !from_path.is_file() || to_path.is_dir() {
+            return false;
+        }
+
+        if let Some(parent) = to_path.parent()
+            && let Err(err) = fs::create_dir_all(parent)
+        {
+            tracing::warn!(aid, from, to, error = %err, "rename: create parent dir failed");
+            return false;
+        }
+
+        match fs::rename(&from_path, &to_path) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::warn!(aid, from, to, error = %err, "rename: rename failed");
+                false
+            }
+        }
+    }
+
+    async fn create_dir(&self, aid: &str, path: &str) -> bool {
+        let Some(disk_path) = self.path_for(aid, path) else {
+            return false;
+        };
+
+        match fs::create_dir_all(&disk_path) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::warn!(aid, path, error = %err, "create_dir: create_dir_all failed");
+                false
+            }
+        }
+    }
+
+    async fn remove_dir(&self, aid: &str, path: &str) -> bool {
+        let Some(disk_path) = self.path_for(aid, path) else {
+            return false;
+        };
+        if !disk_path.is_dir() {
+            return false;
+        }
+
+        // std::fs::remove_dir refuses non-empty directories on every host
+        match fs::remove_dir(&disk_path) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::debug!(aid, path, error = %err, "remove_dir: remove_dir failed");
+                false
+            }
+        }
+    }
 }
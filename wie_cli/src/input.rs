@@ -0,0 +1,385 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    hash::Hash,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use directories::ProjectDirs;
+use gilrs::{Button, GamepadId, Gilrs};
+use serde::{
+    Deserialize,
+    de::{DeserializeOwned, IntoDeserializer, value::Error as ValueError},
+};
+use winit::keyboard::{KeyCode as WinitKeyCode, PhysicalKey};
+
+use wie_backend::{Event, KeyCode};
+
+const DEFAULT_REPEAT_DELAY_MS: u64 = 100;
+const DEFAULT_REPEAT_INTERVAL_MS: u64 = 100;
+
+const DEFAULT_KEYS: [(WinitKeyCode, KeyCode); 24] = [
+    (WinitKeyCode::Digit1, KeyCode::NUM1),
+    (WinitKeyCode::Digit2, KeyCode::NUM2),
+    (WinitKeyCode::Digit3, KeyCode::NUM3),
+    (WinitKeyCode::KeyQ, KeyCode::NUM4),
+    (WinitKeyCode::KeyW, KeyCode::NUM5),
+    (WinitKeyCode::KeyE, KeyCode::NUM6),
+    (WinitKeyCode::KeyA, KeyCode::NUM7),
+    (WinitKeyCode::KeyS, KeyCode::NUM8),
+    (WinitKeyCode::KeyD, KeyCode::NUM9),
+    (WinitKeyCode::KeyZ, KeyCode::STAR),
+    (WinitKeyCode::KeyX, KeyCode::NUM0),
+    (WinitKeyCode::KeyC, KeyCode::HASH),
+    (WinitKeyCode::Space, KeyCode::OK),
+    (WinitKeyCode::ArrowUp, KeyCode::UP),
+    (WinitKeyCode::ArrowDown, KeyCode::DOWN),
+    (WinitKeyCode::ArrowLeft, KeyCode::LEFT),
+    (WinitKeyCode::ArrowRight, KeyCode::RIGHT),
+    (WinitKeyCode::Backspace, KeyCode::CLEAR),
+    (WinitKeyCode::ShiftLeft, KeyCode::LEFT_SOFT_KEY),
+    (WinitKeyCode::ShiftRight, KeyCode::RIGHT_SOFT_KEY),
+    (WinitKeyCode::Backquote, KeyCode::VOLUME_UP),
+    (WinitKeyCode::Tab, KeyCode::VOLUME_DOWN),
+    (WinitKeyCode::F1, KeyCode::CALL),
+    (WinitKeyCode::F2, KeyCode::HANGUP),
+];
+
+const DEFAULT_BUTTONS: [(Button, KeyCode); 10] = [
+    (Button::DPadUp, KeyCode::UP),
+    (Button::DPadDown, KeyCode::DOWN),
+    (Button::DPadLeft, KeyCode::LEFT),
+    (Button::DPadRight, KeyCode::RIGHT),
+    (Button::South, KeyCode::OK),
+    (Button::East, KeyCode::CLEAR),
+    (Button::LeftTrigger, KeyCode::STAR),
+    (Button::RightTrigger, KeyCode::HASH),
+    (Button::Select, KeyCode::LEFT_SOFT_KEY),
+    (Button::Start, KeyCode::RIGHT_SOFT_KEY),
+];
+
+/// Layout of `keys.toml`. Host keys are winit `KeyCode` names (`KeyQ`, `ArrowUp`, ...), buttons are gilrs
+/// `Button` names (`South`, `DPadUp`, ...) and handset keys use the names accepted by `KeyCode::from_name`.
+/// A top-level `[keys]` or `[gamepad]` table replaces the default layout, `[apps.<aid>]` tables are applied on top.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct KeyConfigFile {
+    repeat_delay: Option<u64>,
+    repeat_interval: Option<u64>,
+    keys: Option<BTreeMap<String, String>>,
+    gamepad: Option<BTreeMap<String, String>>,
+    apps: BTreeMap<String, AppKeyConfigFile>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct AppKeyConfigFile {
+    keys: BTreeMap<String, String>,
+    gamepad: BTreeMap<String, String>,
+}
+
+pub struct KeyBindings {
+    keys: HashMap<WinitKeyCode, KeyCode>,
+    buttons: HashMap<Button, KeyCode>,
+    repeat_delay: Duration,
+    repeat_interval: Duration,
+}
+
+impl KeyBindings {
+    pub fn default_path() -> PathBuf {
+        let base_dir = ProjectDirs::from("net", "dlunch", "wie").unwrap();
+
+        base_dir.config_dir().join("keys.toml")
+    }
+
+    /// Loads bindings for `aid` from `path`, or from the default path if it exists.
+    pub fn load(path: Option<&Path>, aid: &str) -> anyhow::Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_owned(), true),
+            None => (Self::default_path(), false),
+        };
+
+        if !required && !path.exists() {
+            return Self::parse("", aid);
+        }
+
+        let config = fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+
+        Self::parse(&config, aid).with_context(|| format!("invalid key config {}", path.display()))
+    }
+
+    fn parse(config: &str, aid: &str) -> anyhow::Result<Self> {
+        let config: KeyConfigFile = toml::from_str(config)?;
+
+        let mut keys = match &config.keys {
+            Some(keys) => parse_bindings(keys)?,
+            None => DEFAULT_KEYS.into_iter().collect(),
+        };
+        let mut buttons = match &config.gamepad {
+            Some(buttons) => parse_bindings(buttons)?,
+            None => DEFAULT_BUTTONS.into_iter().collect(),
+        };
+
+        if let Some(app) = config.apps.get(aid) {
+            keys.extend(parse_bindings(&app.keys)?);
+            buttons.extend(parse_bindings(&app.gamepad)?);
+        }
+
+        Ok(Self {
+            keys,
+            buttons,
+            repeat_delay: Duration::from_millis(config.repeat_delay.unwrap_or(DEFAULT_REPEAT_DELAY_MS)),
+            repeat_interval: Duration::from_millis(config.repeat_interval.unwrap_or(DEFAULT_REPEAT_INTERVAL_MS)),
+        })
+    }
+
+    fn key(&self, key: PhysicalKey) -> Option<KeyCode> {
+        match key {
+            PhysicalKey::Code(code) => self.keys.get(&code).copied(),
+            PhysicalKey::Unidentified(_) => None,
+        }
+    }
+
+    fn button(&self, button: Button) -> Option<KeyCode> {
+        self.buttons.get(&button).copied()
+    }
+}
+
+fn parse_bindings<T>(bindings: &BTreeMap<String, String>) -> anyhow::Result<HashMap<T, KeyCode>>
+where
+    T: DeserializeOwned + Eq + Hash,
+{
+    bindings
+        .iter()
+        .map(|(host, handset)| {
+            let host = T::deserialize(host.as_str().into_deserializer()).map_err(|x: ValueError| anyhow::anyhow!("unknown host key {host}: {x}"))?;
+            let handset = KeyCode::from_name(handset).ok_or_else(|| anyhow::anyhow!("unknown handset key {handset}"))?;
+
+            Ok((host, handset))
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+enum InputSource {
+    Keyboard(WinitKeyCode),
+    Gamepad(GamepadId, Button),
+}
+
+struct HeldKey {
+    sources: HashSet<InputSource>,
+    next_repeat: Instant,
+}
+
+/// Tracks handset keys held by any number of host inputs, so a key held on both the keyboard and a gamepad
+/// is pressed and released once, and generates key repeats while it is held.
+struct KeyState {
+    held: HashMap<KeyCode, HeldKey>,
+    repeat_delay: Duration,
+    repeat_interval: Duration,
+}
+
+impl KeyState {
+    fn new(repeat_delay: Duration, repeat_interval: Duration) -> Self {
+        Self {
+            held: HashMap::new(),
+            repeat_delay,
+            repeat_interval,
+        }
+    }
+
+    fn press(&mut self, source: InputSource, key: KeyCode, now: Instant) -> Option<Event> {
+        let held = self.held.entry(key).or_insert_with(|| HeldKey {
+            sources: HashSet::new(),
+            next_repeat: now + self.repeat_delay,
+        });
+
+        let first = held.sources.is_empty();
+        held.sources.insert(source);
+
+        first.then_some(Event::Keydown(key))
+    }
+
+    fn release(&mut self, source: InputSource) -> Option<Event> {
+        let (&key, held) = self.held.iter_mut().find(|(_, held)| held.sources.contains(&source))?;
+
+        held.sources.remove(&source);
+        if !held.sources.is_empty() {
+            return None;
+        }
+        self.held.remove(&key);
+
+        Some(Event::Keyup(key))
+    }
+
+    fn repeats(&mut self, now: Instant) -> Vec<Event> {
+        self.held
+            .iter_mut()
+            .filter(|(_, held)| now >= held.next_repeat)
+            .map(|(&key, held)| {
+                held.next_repeat = now + self.repeat_interval;
+
+                Event::Keyrepeat(key)
+            })
+            .collect()
+    }
+}
+
+/// Keyboard and gamepad input, translated to handset key events.
+pub struct Input {
+    bindings: KeyBindings,
+    state: KeyState,
+    gilrs: Option<Gilrs>,
+}
+
+impl Input {
+    pub fn new(bindings: KeyBindings) -> Self {
+        let gilrs = Gilrs::new().inspect_err(|x| tracing::warn!("gamepad input is not available: {x}")).ok();
+
+        Self {
+            state: KeyState::new(bindings.repeat_delay, bindings.repeat_interval),
+            bindings,
+            gilrs,
+        }
+    }
+
+    pub fn key_down(&mut self, key: PhysicalKey) -> Option<Event> {
+        let PhysicalKey::Code(code) = key else {
+            return None;
+        };
+        let keycode = self.bindings.key(key)?;
+
+        self.state.press(InputSource::Keyboard(code), keycode, Instant::now())
+    }
+
+    pub fn key_up(&mut self, key: PhysicalKey) -> Option<Event> {
+        let PhysicalKey::Code(code) = key else {
+            return None;
+        };
+
+        self.state.release(InputSource::Keyboard(code))
+    }
+
+    /// Polls connected gamepads and returns pending key events, including key repeats.
+    pub fn update(&mut self) -> Vec<Event> {
+        let now = Instant::now();
+        let mut events = Vec::new();
+
+        if let Some(gilrs) = &mut self.gilrs {
+            while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
+                match event {
+                    gilrs::EventType::ButtonPressed(button, _) => {
+                        if let Some(keycode) = self.bindings.button(button) {
+                            events.extend(self.state.press(InputSource::Gamepad(id, button), keycode, now));
+                        }
+                    }
+                    gilrs::EventType::ButtonReleased(button, _) => {
+                        events.extend(self.state.release(InputSource::Gamepad(id, button)));
+                    }
+                    gilrs::EventType::Disconnected => {
+                        let sources = self
+                            .state
+                            .held
+                            .values()
+                            .flat_map(|held| held.sources.iter())
+                            .filter(|source| matches!(source, InputSource::Gamepad(gamepad, _) if *gamepad == id))
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        events.extend(sources.into_iter().filter_map(|source| self.state.release(source)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        events.extend(self.state.repeats(now));
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use winit::keyboard::{KeyCode as WinitKeyCode, PhysicalKey};
+
+    use wie_backend::{Event, KeyCode};
+
+    use super::{InputSource, KeyBindings, KeyState};
+
+    #[test]
+    fn empty_config_uses_default_layout() {
+        let bindings = KeyBindings::parse("", "test-aid").unwrap();
+
+        assert_eq!(bindings.key(PhysicalKey::Code(WinitKeyCode::KeyQ)), Some(KeyCode::NUM4));
+        assert_eq!(bindings.button(gilrs::Button::South), Some(KeyCode::OK));
+        assert_eq!(bindings.repeat_delay, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn app_bindings_override_global_layout() {
+        let config = r#"
+            repeat_delay = 400
+            repeat_interval = 50
+
+            [keys]
+            Numpad1 = "1"
+            Enter = "OK"
+
+            [apps.game.keys]
+            Enter = "5"
+            KeyZ = "LEFT_SOFT_KEY"
+        "#;
+
+        let bindings = KeyBindings::parse(config, "game").unwrap();
+        assert_eq!(bindings.key(PhysicalKey::Code(WinitKeyCode::Numpad1)), Some(KeyCode::NUM1));
+        assert_eq!(bindings.key(PhysicalKey::Code(WinitKeyCode::Enter)), Some(KeyCode::NUM5));
+        assert_eq!(bindings.key(PhysicalKey::Code(WinitKeyCode::KeyZ)), Some(KeyCode::LEFT_SOFT_KEY));
+        assert_eq!(bindings.key(PhysicalKey::Code(WinitKeyCode::KeyQ)), None);
+        assert_eq!(bindings.repeat_delay, Duration::from_millis(400));
+        assert_eq!(bindings.repeat_interval, Duration::from_millis(50));
+
+        let bindings = KeyBindings::parse(config, "other").unwrap();
+        assert_eq!(bindings.key(PhysicalKey::Code(WinitKeyCode::Enter)), Some(KeyCode::OK));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(KeyBindings::parse("[keys]\nKeyQ = \"MENU\"", "test-aid").is_err());
+        assert!(KeyBindings::parse("[keys]\nNotAKey = \"OK\"", "test-aid").is_err());
+        assert!(KeyBindings::parse("[gamepad]\nSouth = \"OK\"\nNorth = \"CLR\"", "test-aid").is_ok());
+    }
+
+    #[test]
+    fn key_held_by_several_sources_is_pressed_once() {
+        let mut state = KeyState::new(Duration::from_millis(300), Duration::from_millis(100));
+        let keyboard = InputSource::Keyboard(WinitKeyCode::Space);
+        let other = InputSource::Keyboard(WinitKeyCode::Enter);
+        let now = Instant::now();
+
+        assert!(matches!(state.press(keyboard, KeyCode::OK, now), Some(Event::Keydown(KeyCode::OK))));
+        assert!(state.press(other, KeyCode::OK, now).is_none());
+        assert!(state.release(keyboard).is_none());
+        assert!(matches!(state.release(other), Some(Event::Keyup(KeyCode::OK))));
+        assert!(state.release(other).is_none());
+    }
+
+    #[test]
+    fn repeats_after_delay_at_interval() {
+        let mut state = KeyState::new(Duration::from_millis(300), Duration::from_millis(100));
+        let now = Instant::now();
+        state.press(InputSource::Keyboard(WinitKeyCode::KeyW), KeyCode::NUM5, now);
+
+        assert!(state.repeats(now + Duration::from_millis(200)).is_empty());
+        assert!(matches!(
+            state.repeats(now + Duration::from_millis(300)).as_slice(),
+            [Event::Keyrepeat(KeyCode::NUM5)]
+        ));
+        assert!(state.repeats(now + Duration::from_millis(350)).is_empty());
+        assert_eq!(state.repeats(now + Duration::from_millis(400)).len(), 1);
+    }
+}
@@ -3,24 +3,28 @@ extern crate alloc;
 mod audio_sink;
 mod database;
 mod filesystem;
+mod input;
+mod network;
 mod window;
 
-use core::str;
+use core::{ops::Range, str};
 use std::{
-    collections::{HashMap, hash_map::Entry},
     fs::{self, File},
     io::{LineWriter, Write, stderr},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Mutex, mpsc::Sender, mpsc::channel},
     thread,
     time::{SystemTime, UNIX_EPOCH},
 };
 
+use anyhow::Context;
 use clap::Parser;
+use directories::ProjectDirs;
 use midir::MidiOutput;
-use winit::keyboard::{KeyCode as WinitKeyCode, PhysicalKey};
-
-use wie_backend::{AudioCommand, Emulator, Event, Filesystem, Instant, KeyCode, Options, Platform, ProfileSample, Screen, extract_zip};
+use wie_backend::{
+    AudioCommand, BinaryPatchFile, DEFAULT_STORAGE_QUOTA, DeviceProfile, Emulator, Event, Filesystem, Instant, Network, Options, Platform,
+    ProfileSample, Screen, TraceEvent, TraceOptions, extract_zip,
+};
 use wie_j2me::J2MEEmulator;
 use wie_ktf::KtfEmulator;
 use wie_lgt::LgtEmulator;
@@ -30,6 +34,8 @@ use self::{
     audio_sink::AudioSink,
     database::DatabaseRepository,
     filesystem::CliFilesystem,
+    input::{Input, KeyBindings},
+    network::CliNetwork,
     window::{WindowCallbackEvent, WindowHandle, WindowImpl},
 };
 
@@ -37,11 +43,14 @@ struct WieCliPlatform {
     audio_tx: Sender<AudioCommand>,
     database_repository: DatabaseRepository,
     filesystem: CliFilesystem,
+    network: CliNetwork,
     window: WindowHandle,
+    storage_quota: u64,
+    device_profile: DeviceProfile,
 }
 
 impl WieCliPlatform {
-    fn new(window: WindowHandle, midi_device: Option<usize>) -> Self {
+    fn new(window: WindowHandle, midi_device: Option<usize>, storage_quota: u64, device_profile: DeviceProfile) -> Self {
         let (tx, rx) = channel();
         thread::spawn(move || audio_sink::run(rx, midi_device));
 
@@ -49,7 +58,10 @@ impl WieCliPlatform {
             audio_tx: tx,
             database_repository: DatabaseRepository::new(),
             filesystem: CliFilesystem::new(),
+            network: CliNetwork::new(),
             window,
+            storage_quota,
+            device_profile,
         }
     }
 }
@@ -97,6 +109,18 @@ impl Platform for WieCliPlatform {
     fn vibrate(&self, duration_ms: u64, intensity: u8) {
         tracing::info!("vibrate({duration_ms}ms, {intensity}%) - not supported on this platform");
     }
+
+    fn storage_quota(&self, _aid: &str) -> u64 {
+        self.storage_quota
+    }
+
+    fn device_profile(&self) -> DeviceProfile {
+        self.device_profile.clone()
+    }
+
+    fn network(&self) -> &dyn Network {
+        &self.network
+    }
 }
 
 #[derive(Parser)]
@@ -109,11 +133,32 @@ struct Args {
     /// flushed batch; `flamegraph.pl` aggregates duplicates).
     #[arg(long)]
     profile_out: Option<PathBuf>,
+    /// Write SVC calls, and instructions inside `--trace-range`, to this path as JSON lines.
+    #[arg(long, value_name = "PATH")]
+    trace: Option<PathBuf>,
+    /// Trace every instruction executed in this address range, e.g. `0x100000-0x100400`. May be repeated.
+    #[arg(long, value_name = "START-END", requires = "trace", value_parser = parse_trace_range)]
+    trace_range: Vec<Range<u32>>,
+    /// Guard guest heap allocations with red zones, fail on double or invalid frees, and log allocations still live at exit.
+    #[arg(long)]
+    heap_debug: bool,
+    /// Extra binary patch table in the `data/binary_patches.toml` format, merged with the built-in one. May be repeated.
+    #[arg(long, value_name = "PATH")]
+    patches: Vec<PathBuf>,
     /// Select a MIDI output by zero-based index.
     #[arg(long, value_name = "INDEX")]
     midi_device: Option<usize>,
+    /// Persistent storage available to the application, in bytes.
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_STORAGE_QUOTA)]
+    storage_quota: u64,
+    /// Key bindings and key repeat settings, defaults to `keys.toml` in the config directory.
+    #[arg(long, value_name = "PATH")]
+    key_config: Option<PathBuf>,
+    /// Handset profile to emulate, either a TOML file or the name of one in the `devices` config directory.
+    #[arg(long, value_name = "NAME|PATH")]
+    device: Option<String>,
     /// List available MIDI output devices and exit.
-    #[arg(long, conflicts_with_all = ["filename", "debug", "profile_out", "midi_device"])]
+    #[arg(long, conflicts_with_all = ["filename", "debug", "profile_out", "trace", "trace_range", "heap_debug", "patches", "midi_device", "storage_quota", "key_config", "device"])]
     list_midi_devices: bool,
 }
 
@@ -130,13 +175,61 @@ fn main() -> anyhow::Result<()> {
     }
 
     let profile = args.profile_out.as_ref().map(|path| profile_callback(path)).transpose()?;
+    let trace = args
+        .trace
+        .as_ref()
+        .map(|path| trace_options(path, args.trace_range.clone()))
+        .transpose()?;
+    let binary_patches = args
+        .patches
+        .iter()
+        .map(|path| read_binary_patches(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
     let options = Options {
         enable_gdbserver: args.debug,
         profile,
+        trace,
+        heap_debug: args.heap_debug,
+        binary_patches,
     };
     let filename = args.filename.as_deref().ok_or_else(|| anyhow::anyhow!("filename is required"))?;
+    let device_profile = args.device.as_deref().map(load_device_profile).transpose()?.unwrap_or_default();
+
+    start_with_platform_options(
+        filename,
+        options,
+        args.midi_device,
+        args.storage_quota,
+        args.key_config.as_deref(),
+        device_profile,
+    )
+}
 
-    start_with_midi_device(filename, options, args.midi_device)
+fn read_binary_patches(path: &Path) -> anyhow::Result<BinaryPatchFile> {
+    let contents = fs::read_to_string(path).with_context(|| format!("failed to read binary patches {}", path.display()))?;
+
+    Ok(BinaryPatchFile {
+        name: path.display().to_string(),
+        contents,
+    })
+}
+
+fn load_device_profile(device: &str) -> anyhow::Result<DeviceProfile> {
+    let path = Path::new(device);
+    let path = if path.is_file() {
+        path.to_owned()
+    } else {
+        let Some(base_dir) = ProjectDirs::from("net", "dlunch", "wie") else {
+            tracing::warn!("no config directory to look up device profile {device}, using the default profile");
+            return Ok(DeviceProfile::default());
+        };
+
+        base_dir.config_dir().join("devices").join(format!("{device}.toml"))
+    };
+
+    let data = fs::read_to_string(&path).with_context(|| format!("failed to read device profile {}", path.display()))?;
+
+    Ok(DeviceProfile::from_toml(&data)?)
 }
 
 fn list_midi_devices() -> anyhow::Result<()> {
@@ -153,19 +246,56 @@ fn profile_callback(path: &PathBuf) -> anyhow::Result<wie_backend::ProfileCallba
     Ok(Box::new(move |batch: Vec<ProfileSample>| {
         let mut writer = writer.lock().unwrap();
         for sample in batch {
-            let folded: Vec<String> = sample.stack.iter().rev().map(|pc| format!("0x{pc:x}")).collect();
+            // frames are named by the platform's symbol provider, `;` separates them in the folded format
+            let folded: Vec<String> = sample.symbols.iter().rev().map(|name| name.replace(';', ":")).collect();
             let _ = writeln!(writer, "{} {}", folded.join(";"), sample.count);
         }
     }))
 }
 
+fn parse_trace_range(value: &str) -> Result<Range<u32>, String> {
+    let parse = |x: &str| {
+        let x = x.trim();
+        match x.strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => x.parse(),
+        }
+        .map_err(|err| format!("invalid address {x}: {err}"))
+    };
+
+    let (start, end) = value.split_once('-').ok_or_else(|| "expected START-END".to_string())?;
+    let (start, end) = (parse(start)?, parse(end)?);
+    if start >= end {
+        return Err("range end must be greater than its start".into());
+    }
+
+    Ok(start..end)
+}
+
+fn trace_options(path: &PathBuf, ranges: Vec<Range<u32>>) -> anyhow::Result<TraceOptions> {
+    let mut writer = LineWriter::new(File::create(path)?);
+    Ok(TraceOptions {
+        ranges,
+        callback: Box::new(move |event: TraceEvent| {
+            let _ = writeln!(writer, "{}", event.to_json());
+        }),
+    })
+}
+
 pub fn start(filename: &str, options: Options) -> anyhow::Result<()> {
-    start_with_midi_device(filename, options, None)
+    start_with_platform_options(filename, options, None, DEFAULT_STORAGE_QUOTA, None, DeviceProfile::default())
 }
 
-fn start_with_midi_device(filename: &str, options: Options, midi_device: Option<usize>) -> anyhow::Result<()> {
-    let window = WindowImpl::new(240, 320)?;
-    let platform = Box::new(WieCliPlatform::new(window.handle(), midi_device));
+fn start_with_platform_options(
+    filename: &str,
+    options: Options,
+    midi_device: Option<usize>,
+    storage_quota: u64,
+    key_config: Option<&Path>,
+    device_profile: DeviceProfile,
+) -> anyhow::Result<()> {
+    let window = WindowImpl::new(device_profile.screen_width, device_profile.screen_height)?;
+    let platform = Box::new(WieCliPlatform::new(window.handle(), midi_device, storage_quota, device_profile));
 
     let buf = fs::read(filename)?;
     let mut emulator: Box<dyn Emulator> = if filename.ends_with("zip") {
@@ -220,43 +350,26 @@ fn start_with_midi_device(filename: &str, options: Options, midi_device: Option<
         anyhow::bail!("Unknown file format");
     };
 
-    let mut key_events = HashMap::new();
+    let mut input = Input::new(KeyBindings::load(key_config, emulator.aid())?);
     window.run(move |event| {
         match event {
             WindowCallbackEvent::Update => {
-                let now = SystemTime::now();
-
-                for entry in key_events.iter_mut() {
-                    let (keycode, time) = entry;
-
-                    // TODO const
-                    if now.duration_since(*time).unwrap().as_millis() > 100 {
-                        emulator.handle_event(Event::Keyrepeat(*keycode));
-                        *time = now;
-                    }
+                for event in input.update() {
+                    emulator.handle_event(event);
                 }
 
                 emulator.tick()?
             }
             WindowCallbackEvent::Redraw => emulator.handle_event(Event::Redraw),
             WindowCallbackEvent::Keydown(x) => {
-                if let Some(keycode) = convert_key(x) {
-                    let entry = key_events.entry(keycode);
-                    if let Entry::Vacant(entry) = entry {
-                        emulator.handle_event(Event::Keydown(keycode));
-
-                        let now = SystemTime::now();
-
-                        entry.insert(now);
-                    }
+                if let Some(event) = input.key_down(x) {
+                    emulator.handle_event(event);
                 }
             }
+            WindowCallbackEvent::Text(text) => emulator.handle_event(Event::Text(text)),
             WindowCallbackEvent::Keyup(x) => {
-                if let Some(keycode) = convert_key(x)
-                    && key_events.contains_key(&keycode)
-                {
-                    key_events.remove(&keycode);
-                    emulator.handle_event(Event::Keyup(keycode));
+                if let Some(event) = input.key_up(x) {
+                    emulator.handle_event(event);
                 }
             }
         }
@@ -269,36 +382,6 @@ fn select_midi_output_index(port_count: usize, requested: Option<usize>) -> Opti
     requested.filter(|&index| index < port_count).or_else(|| port_count.checked_sub(1))
 }
 
-fn convert_key(key: PhysicalKey) -> Option<KeyCode> {
-    match key {
-        PhysicalKey::Code(WinitKeyCode::Digit1) => Some(KeyCode::NUM1),
-        PhysicalKey::Code(WinitKeyCode::Digit2) => Some(KeyCode::NUM2),
-        PhysicalKey::Code(WinitKeyCode::Digit3) => Some(KeyCode::NUM3),
-        PhysicalKey::Code(WinitKeyCode::KeyQ) => Some(KeyCode::NUM4),
-        PhysicalKey::Code(WinitKeyCode::KeyW) => Some(KeyCode::NUM5),
-        PhysicalKey::Code(WinitKeyCode::KeyE) => Some(KeyCode::NUM6),
-        PhysicalKey::Code(WinitKeyCode::KeyA) => Some(KeyCode::NUM7),
-        PhysicalKey::Code(WinitKeyCode::KeyS) => Some(KeyCode::NUM8),
-        PhysicalKey::Code(WinitKeyCode::KeyD) => Some(KeyCode::NUM9),
-        PhysicalKey::Code(WinitKeyCode::KeyZ) => Some(KeyCode::STAR),
-        PhysicalKey::Code(WinitKeyCode::KeyX) => Some(KeyCode::NUM0),
-        PhysicalKey::Code(WinitKeyCode::KeyC) => Some(KeyCode::HASH),
-        PhysicalKey::Code(WinitKeyCode::Space) => Some(KeyCode::OK),
-        PhysicalKey::Code(WinitKeyCode::ArrowUp) => Some(KeyCode::UP),
-        PhysicalKey::Code(WinitKeyCode::ArrowDown) => Some(KeyCode::DOWN),
-        PhysicalKey::Code(WinitKeyCode::ArrowLeft) => Some(KeyCode::LEFT),
-        PhysicalKey::Code(WinitKeyCode::ArrowRight) => Some(KeyCode::RIGHT),
-        PhysicalKey::Code(WinitKeyCode::Backspace) => Some(KeyCode::CLEAR),
-        PhysicalKey::Code(WinitKeyCode::ShiftLeft) => Some(KeyCode::LEFT_SOFT_KEY),
-        PhysicalKey::Code(WinitKeyCode::ShiftRight) => Some(KeyCode::RIGHT_SOFT_KEY),
-        PhysicalKey::Code(WinitKeyCode::Backquote) => Some(KeyCode::VOLUME_UP),
-        PhysicalKey::Code(WinitKeyCode::Tab) => Some(KeyCode::VOLUME_DOWN),
-        PhysicalKey::Code(WinitKeyCode::F1) => Some(KeyCode::CALL),
-        PhysicalKey::Code(WinitKeyCode::F2) => Some(KeyCode::HANGUP),
-        _ => None,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use clap::Parser;
@@ -347,4 +430,27 @@ mod tests {
         assert_eq!(args.filename.as_deref(), Some("game.jar"));
         assert_eq!(args.midi_device, Some(1));
     }
+
+    #[test]
+    fn storage_quota_defaults_and_can_be_overridden() {
+        let args = Args::try_parse_from(["wie_cli", "game.jar"]).unwrap();
+        assert_eq!(args.storage_quota, wie_backend::DEFAULT_STORAGE_QUOTA);
+
+        let args = Args::try_parse_from(["wie_cli", "game.jar", "--storage-quota", "4096"]).unwrap();
+        assert_eq!(args.storage_quota, 4096);
+    }
+
+    #[test]
+    fn parses_device() {
+        let args = Args::try_parse_from(["wie_cli", "game.jar", "--device", "sph-b5200"]).unwrap();
+
+        assert_eq!(args.device.as_deref(), Some("sph-b5200"));
+    }
+
+    #[test]
+    fn parses_key_config_path() {
+        let args = Args::try_parse_from(["wie_cli", "game.jar", "--key-config", "keys.toml"]).unwrap();
+
+        assert_eq!(args.key_config.as_deref(), Some(std::path::Path::new("keys.toml")));
+    }
 }
@@ -0,0 +1,246 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use wie_backend::{DatagramSocket, Network, NetworkError, Socket};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Slot<T> = Arc<Mutex<Option<T>>>;
+
+/// [`Network`] over the host's TCP/IP stack. Lookups and connects run on worker threads so they never block the emulator.
+#[derive(Default)]
+pub struct CliNetwork {
+    lookups: Arc<Mutex<HashMap<String, Option<Result<[u8; 4], NetworkError>>>>>,
+}
+
+impl CliNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Network for CliNetwork {
+    fn resolve(&self, host: &str) -> Result<[u8; 4], NetworkError> {
+        let mut lookups = self.lookups.lock().unwrap();
+        match lookups.get(host).copied() {
+            Some(Some(Ok(address))) => return Ok(address),
+            // failures are reported once, the next lookup tries again
+            Some(Some(Err(err))) => {
+                lookups.remove(host);
+                return Err(err);
+            }
+            Some(None) => return Err(NetworkError::WouldBlock),
+            None => {}
+        }
+        lookups.insert(host.to_owned(), None);
+
+        let lookups = self.lookups.clone();
+        let host = host.to_owned();
+        thread::spawn(move || {
+            let address = (host.as_str(), 0)
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut x| x.find_map(|x| if let SocketAddr::V4(x) = x { Some(x.ip().octets()) } else { None }))
+                .ok_or(NetworkError::HostNotFound);
+            if address.is_err() {
+                tracing::warn!("Failed to resolve {host}");
+            }
+
+            lookups.lock().unwrap().insert(host, Some(address));
+        });
+
+        Err(NetworkError::WouldBlock)
+    }
+
+    fn connect(&self, address: [u8; 4], port: u16) -> Result<Box<dyn Socket>, NetworkError> {
+        let slot: Slot<io::Result<TcpStream>> = Arc::new(Mutex::new(None));
+
+        let result = slot.clone();
+        thread::spawn(move || {
+            let stream = TcpStream::connect_timeout(&SocketAddr::from((address, port)), CONNECT_TIMEOUT);
+
+            *result.lock().unwrap() = Some(stream);
+        });
+
+        Ok(Box::new(CliSocket::Connecting(slot)))
+    }
+
+    fn bind_datagram(&self) -> Result<Box<dyn DatagramSocket>, NetworkError> {
+        let socket = UdpSocket::bind(("0.0.0.0", 0)).and_then(|x| x.set_nonblocking(true).map(|_| x));
+
+        Ok(Box::new(CliDatagramSocket(socket.map_err(to_network_error)?)))
+    }
+}
+
+enum CliSocket {
+    Connecting(Slot<io::Result<TcpStream>>),
+    Connected(TcpStream),
+    Failed(NetworkError),
+}
+
+impl CliSocket {
+    fn stream(&mut self) -> Result<&mut TcpStream, NetworkError> {
+        self.poll_connect()?;
+
+        match self {
+            Self::Connected(stream) => Ok(stream),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Socket for CliSocket {
+    fn poll_connect(&mut self) -> Result<(), NetworkError> {
+        match self {
+            Self::Connecting(slot) => {
+                let Some(result) = slot.lock().unwrap().take() else {
+                    return Err(NetworkError::WouldBlock);
+                };
+
+                *self = match result.and_then(|x| x.set_nonblocking(true).map(|_| x)) {
+                    Ok(stream) => {
+                        let _ = stream.set_nodelay(true);
+
+                        Self::Connected(stream)
+                    }
+                    Err(err) => {
+                        tracing::warn!("Failed to connect: {err}");
+
+                        Self::Failed(NetworkError::ConnectionRefused)
+                    }
+                };
+
+                self.poll_connect()
+            }
+            Self::Connected(_) => Ok(()),
+            Self::Failed(err) => Err(*err),
+        }
+    }
+
+    fn poll_readable(&mut self) -> Result<(), NetworkError> {
+        // a zero-length peek is an EOF, which is readable as well
+        self.stream()?.peek(&mut [0; 1]).map(|_| ()).map_err(to_network_error)
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, NetworkError> {
+        self.stream()?.read(buf).map_err(to_network_error)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<usize, NetworkError> {
+        self.stream()?.write(data).map_err(to_network_error)
+    }
+}
+
+struct CliDatagramSocket(UdpSocket);
+
+impl DatagramSocket for CliDatagramSocket {
+    fn send_to(&mut self, address: [u8; 4], port: u16, data: &[u8]) -> Result<usize, NetworkError> {
+        self.0.send_to(data, SocketAddr::from((address, port))).map_err(to_network_error)
+    }
+
+    fn recv_from(&mut self, buf: &mut [u8]) -> Result<(usize, [u8; 4], u16), NetworkError> {
+        match self.0.recv_from(buf).map_err(to_network_error)? {
+            (length, SocketAddr::V4(address)) => Ok((length, address.ip().octets(), address.port())),
+            (_, SocketAddr::V6(_)) => Err(NetworkError::WouldBlock),
+        }
+    }
+}
+
+fn to_network_error(err: io::Error) -> NetworkError {
+    match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => NetworkError::WouldBlock,
+        io::ErrorKind::ConnectionRefused => NetworkError::ConnectionRefused,
+        _ => NetworkError::ConnectionLost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, UdpSocket},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use wie_backend::{Network, NetworkError};
+
+    use super::CliNetwork;
+
+    fn wait<T>(mut f: impl FnMut() -> Result<T, NetworkError>) -> Result<T, NetworkError> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            match f() {
+                Err(NetworkError::WouldBlock) if Instant::now() < deadline => thread::sleep(Duration::from_millis(1)),
+                result => return result,
+            }
+        }
+    }
+
+    #[test]
+    fn exchanges_data_with_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4];
+            stream.read_exact(&mut buf).unwrap();
+            stream.write_all(b"pong").unwrap();
+            buf
+        });
+
+        let network = CliNetwork::new();
+        let mut socket = network.connect([127, 0, 0, 1], port).unwrap();
+        wait(|| socket.poll_connect()).unwrap();
+        assert_eq!(socket.write(b"ping"), Ok(4));
+
+        wait(|| socket.poll_readable()).unwrap();
+        let mut buf = [0; 16];
+        let read = socket.read(&mut buf).unwrap();
+        assert_eq!(&buf[..read], b"pong");
+        assert_eq!(&server.join().unwrap(), b"ping");
+
+        wait(|| socket.poll_readable()).unwrap();
+        assert_eq!(socket.read(&mut buf), Ok(0));
+    }
+
+    #[test]
+    fn reports_refused_connection() {
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+        let network = CliNetwork::new();
+        let mut socket = network.connect([127, 0, 0, 1], port).unwrap();
+        assert_eq!(wait(|| socket.poll_connect()), Err(NetworkError::ConnectionRefused));
+    }
+
+    #[test]
+    fn exchanges_datagrams() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = server.local_addr().unwrap().port();
+
+        let network = CliNetwork::new();
+        let mut socket = network.bind_datagram().unwrap();
+        assert_eq!(socket.send_to([127, 0, 0, 1], port, b"ping"), Ok(4));
+
+        let mut buf = [0; 16];
+        let (read, peer) = server.recv_from(&mut buf).unwrap();
+        assert_eq!(&buf[..read], b"ping");
+        server.send_to(b"pong", peer).unwrap();
+
+        let (read, address, from) = wait(|| socket.recv_from(&mut buf)).unwrap();
+        assert_eq!((&buf[..read], address, from), (&b"pong"[..], [127, 0, 0, 1], port));
+    }
+
+    #[test]
+    fn resolves_localhost() {
+        let network = CliNetwork::new();
+
+        assert_eq!(wait(|| network.resolve("localhost")), Ok([127, 0, 0, 1]));
+    }
+}
@@ -8,9 +8,9 @@ use softbuffer::{Context, Surface};
 use winit::{
     application::ApplicationHandler,
     dpi::{LogicalSize, PhysicalSize},
-    event::{ElementState, KeyEvent, StartCause, WindowEvent},
+    event::{ElementState, Ime, KeyEvent, StartCause, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
-    keyboard::PhysicalKey,
+    keyboard::{KeyCode, PhysicalKey},
     window::{Window as WinitWindow, WindowId},
 };
 
@@ -53,6 +53,7 @@ pub enum WindowCallbackEvent {
     Redraw,
     Keydown(PhysicalKey),
     Keyup(PhysicalKey),
+    Text(String),
 }
 
 pub struct WindowHandle {
@@ -157,6 +158,7 @@ impl WindowImpl {
             surface: None,
             callback: Box::new(callback),
             last_frame: vec![0u32; (width * height) as usize],
+            text_entry: false,
         };
 
         Ok(self.event_loop.run_app(&mut handler)?)
@@ -282,6 +284,8 @@ where
     context: Option<Context<Arc<WinitWindow>>>,
     surface: Option<Surface<Arc<WinitWindow>, Arc<WinitWindow>>>,
     callback: Box<C>,
+    /// Whether typed text goes to the emulated text fields instead of being mapped to keypad keys.
+    text_entry: bool,
 }
 
 impl<C> ApplicationHandlerImpl<C>
@@ -297,6 +301,16 @@ where
         }
     }
 
+    /// Toggles host keyboard text entry, enabling the OS input method while it is on.
+    fn toggle_text_entry(&mut self) {
+        self.text_entry = !self.text_entry;
+        if let Some(window) = &self.window {
+            window.set_ime_allowed(self.text_entry);
+        }
+
+        tracing::info!("Host text entry {}", if self.text_entry { "enabled" } else { "disabled" });
+    }
+
     /// Sets the native/user scale factor.
     /// After calling this you'll need to call [`Self::on_resize`] to update the surface accordingly.
     fn update_scale_factor(&mut self, native: Option<f64>, user: Option<f64>) {
@@ -466,10 +480,18 @@ where
                         physical_key,
                         state,
                         repeat: false,
+                        text,
                         ..
                     },
                 ..
             } => match state {
+                ElementState::Pressed if physical_key == PhysicalKey::Code(KeyCode::F3) => {
+                    self.toggle_text_entry();
+                }
+                // printable text is entered as is, other keys such as arrows and backspace still act as handset keys
+                ElementState::Pressed if self.text_entry && text.as_ref().is_some_and(|x| !x.chars().any(char::is_control)) => {
+                    self.callback(WindowCallbackEvent::Text(text.unwrap().to_string()), event_loop);
+                }
                 ElementState::Pressed => {
                     self.callback(WindowCallbackEvent::Keydown(physical_key), event_loop);
                 }
@@ -477,6 +499,9 @@ where
                     self.callback(WindowCallbackEvent::Keyup(physical_key), event_loop);
                 }
             },
+            WindowEvent::Ime(Ime::Commit(text)) if self.text_entry => {
+                self.callback(WindowCallbackEvent::Text(text), event_loop);
+            }
             WindowEvent::RedrawRequested => {
                 self.callback(WindowCallbackEvent::Redraw, event_loop);
             }
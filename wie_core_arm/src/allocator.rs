@@ -1,15 +1,20 @@
 mod bucket;
+mod debug;
 mod list;
 
-use wie_util::Result;
+use alloc::{format, string::String};
+
+use wie_util::{ByteRead, ByteWrite, Result, WieError};
 
 use crate::{
-    ArmCore,
+    ArmCore, MemoryPermission,
     core::{HEAP_BASE, HEAP_SIZE},
 };
 
+pub(crate) use self::debug::HeapDebug;
 use self::{
     bucket::{BUCKET_MAX, BucketAllocator},
+    debug::{RED_ZONE_SIZE, RED_ZONE_VALUE},
     list::ListAllocator,
 };
 
@@ -17,45 +22,176 @@ pub struct Allocator;
 
 impl Allocator {
     pub fn init(core: &mut ArmCore) -> Result<()> {
-        core.map(HEAP_BASE, HEAP_SIZE)?;
+        core.map(HEAP_BASE, HEAP_SIZE, MemoryPermission::ReadWrite)?;
 
         ListAllocator::init(core, HEAP_BASE, HEAP_SIZE / 2)?;
         BucketAllocator::init(core, HEAP_BASE + HEAP_SIZE / 2, HEAP_SIZE / 2)?;
+        core.inner.lock().heap_free = Self::total_memory();
 
         Ok(())
     }
 
+    /// Surrounds every allocation with red zones and tracks it with the guest caller, so that overruns,
+    /// double frees and invalid frees fail with the offending call site. Must be enabled before the first allocation.
+    pub fn enable_debug(core: &mut ArmCore) {
+        core.inner.lock().heap_debug = Some(HeapDebug::default());
+    }
+
     pub fn alloc(core: &mut ArmCore, size: u32) -> Result<u32> {
-        if size > BUCKET_MAX as _ {
-            ListAllocator::alloc(core, HEAP_BASE, HEAP_SIZE / 2, size)
-        } else {
-            BucketAllocator::alloc(core, HEAP_BASE + HEAP_SIZE / 2, size)
+        if !Self::is_debug(core) {
+            return Self::alloc_block(core, size);
         }
+
+        let (_, caller) = core.read_pc_lr()?;
+        let block_size = size.checked_add(RED_ZONE_SIZE * 2).ok_or(WieError::AllocationFailure)?;
+        let block = Self::alloc_block(core, block_size)?;
+        let address = block + RED_ZONE_SIZE;
+
+        core.write_bytes(block, &[RED_ZONE_VALUE; RED_ZONE_SIZE as usize])?;
+        core.write_bytes(address + size, &[RED_ZONE_VALUE; RED_ZONE_SIZE as usize])?;
+        core.inner.lock().heap_debug.as_mut().unwrap().on_alloc(address, size, caller);
+
+        Ok(address)
     }
 
     pub fn free(core: &mut ArmCore, address: u32, size: u32) -> Result<()> {
-        if size > BUCKET_MAX as _ {
-            ListAllocator::free(core, address)
-        } else {
-            BucketAllocator::free(core, HEAP_BASE + HEAP_SIZE / 2, address, size)
+        if !Self::is_debug(core) {
+            return Self::free_block(core, address, size);
         }
+
+        let (_, caller) = core.read_pc_lr()?;
+        let (allocation, freed_by) = {
+            let inner = core.inner.lock();
+            let heap_debug = inner.heap_debug.as_ref().unwrap();
+
+            (heap_debug.live(address), heap_debug.freed_by(address))
+        };
+
+        let Some(allocation) = allocation else {
+            return Err(WieError::FatalError(match freed_by {
+                Some(freed_by) => format!(
+                    "Double free of {address:#x} by {}, previously freed by {}",
+                    Self::describe_caller(core, caller),
+                    Self::describe_caller(core, freed_by)
+                ),
+                None => format!("Invalid free of {address:#x} by {}", Self::describe_caller(core, caller)),
+            }));
+        };
+
+        if allocation.size != size {
+            tracing::warn!(
+                "Free of {address:#x} by {} with size {size:#x}, allocated with {:#x}",
+                Self::describe_caller(core, caller),
+                allocation.size
+            );
+        }
+
+        let block = address - RED_ZONE_SIZE;
+        for (kind, zone) in [("underflow", block), ("overflow", address + allocation.size)] {
+            let mut data = [0; RED_ZONE_SIZE as usize];
+            core.read_bytes(zone, &mut data)?;
+            if data.iter().any(|&x| x != RED_ZONE_VALUE) {
+                return Err(WieError::FatalError(format!(
+                    "Heap buffer {kind} of {address:#x} ({:#x} bytes allocated by {}), detected on free by {}",
+                    allocation.size,
+                    Self::describe_caller(core, allocation.caller),
+                    Self::describe_caller(core, caller)
+                )));
+            }
+        }
+
+        Self::free_block(core, block, allocation.size + RED_ZONE_SIZE * 2)?;
+        core.inner.lock().heap_debug.as_mut().unwrap().on_free(address, caller);
+
+        Ok(())
+    }
+
+    /// Bytes both allocators can hand out, allocation overhead included.
+    pub fn total_memory() -> u32 {
+        HEAP_SIZE / 2 + BucketAllocator::capacity()
+    }
+
+    /// Bytes of [`Self::total_memory`] not handed out.
+    pub fn free_memory(core: &ArmCore) -> u32 {
+        core.inner.lock().heap_free
+    }
+
+    /// Live allocations grouped by the guest call site, or `None` unless [`Self::enable_debug`] was called.
+    pub fn census(core: &ArmCore) -> Option<String> {
+        let mut inner = core.inner.lock();
+        let inner = &mut *inner;
+
+        let heap_debug = inner.heap_debug.as_ref()?;
+        Some(heap_debug.census(|address| inner.symbols.symbolize(&mut *inner.engine, inner.image_base, address)))
+    }
+
+    /// Describes the heap blocks in use, for the debugger's `monitor heap`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn describe<R: ?Sized + ByteRead>(reader: &R) -> Result<String> {
+        let list = ListAllocator::describe(reader, HEAP_BASE, HEAP_SIZE / 2)?;
+        let bucket = BucketAllocator::describe(reader, HEAP_BASE + HEAP_SIZE / 2)?;
+
+        Ok(list + &bucket)
     }
 
     pub fn is_allocated(core: &ArmCore, address: u32, size: u32) -> Result<bool> {
+        if let Some(heap_debug) = &core.inner.lock().heap_debug {
+            return Ok(heap_debug.live(address).is_some_and(|x| x.size == size));
+        }
+
         if size > BUCKET_MAX as _ {
             ListAllocator::is_allocated(core, HEAP_BASE, HEAP_SIZE / 2, address, size)
         } else {
             BucketAllocator::is_allocated(core, HEAP_BASE + HEAP_SIZE / 2, address, size)
         }
     }
+
+    fn is_debug(core: &ArmCore) -> bool {
+        core.inner.lock().heap_debug.is_some()
+    }
+
+    fn describe_caller(core: &ArmCore, caller: u32) -> String {
+        format!("{} ({caller:#x})", core.symbolize(caller))
+    }
+
+    fn alloc_block(core: &mut ArmCore, size: u32) -> Result<u32> {
+        let (address, block_size) = if size > BUCKET_MAX as _ {
+            let address = ListAllocator::alloc(core, HEAP_BASE, HEAP_SIZE / 2, size)?;
+            (address, ListAllocator::block_size(size).unwrap())
+        } else {
+            let address = BucketAllocator::alloc(core, HEAP_BASE + HEAP_SIZE / 2, size)?;
+            (address, BucketAllocator::slot_size(size))
+        };
+        core.inner.lock().heap_free -= block_size;
+
+        Ok(address)
+    }
+
+    fn free_block(core: &mut ArmCore, address: u32, size: u32) -> Result<()> {
+        let block_size = if size > BUCKET_MAX as _ {
+            ListAllocator::free(core, address)?
+        } else {
+            BucketAllocator::free(core, HEAP_BASE + HEAP_SIZE / 2, address, size)?;
+            BucketAllocator::slot_size(size)
+        };
+        core.inner.lock().heap_free += block_size;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use wie_util::Result;
+    use wie_util::{ByteWrite, Result, WieError};
 
     use crate::{Allocator, ArmCore};
 
+    fn set_lr(core: &mut ArmCore, lr: u32) {
+        let mut context = core.save_context();
+        context.lr = lr;
+        core.restore_context(&context);
+    }
+
     #[test]
     fn allocation_status_tracks_bucket_and_list_allocations() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
@@ -74,4 +210,85 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn free_memory_counts_both_allocators() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        Allocator::init(&mut core)?;
+
+        let total = Allocator::total_memory();
+        assert_eq!(Allocator::free_memory(&core), total);
+
+        let bucket = Allocator::alloc(&mut core, 12)?;
+        assert_eq!(Allocator::free_memory(&core), total - 16);
+
+        let list = Allocator::alloc(&mut core, 1024)?;
+        assert_eq!(Allocator::free_memory(&core), total - 16 - 1032);
+
+        Allocator::free(&mut core, bucket, 12)?;
+        Allocator::free(&mut core, list, 1024)?;
+        assert_eq!(Allocator::free_memory(&core), total);
+
+        Ok(())
+    }
+
+    #[test]
+    fn debug_mode_rejects_sizes_overflowing_with_red_zones() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        Allocator::enable_debug(&mut core);
+        Allocator::init(&mut core)?;
+
+        assert!(matches!(Allocator::alloc(&mut core, u32::MAX - 4), Err(WieError::AllocationFailure)));
+        assert_eq!(Allocator::free_memory(&core), Allocator::total_memory());
+
+        Ok(())
+    }
+
+    #[test]
+    fn debug_mode_detects_overflow_and_bad_frees() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        Allocator::enable_debug(&mut core);
+        Allocator::init(&mut core)?;
+
+        let address = Allocator::alloc(&mut core, 12)?;
+        assert!(Allocator::is_allocated(&core, address, 12)?);
+        Allocator::free(&mut core, address, 12)?;
+        assert!(matches!(Allocator::free(&mut core, address, 12), Err(WieError::FatalError(x)) if x.starts_with("Double free")));
+        assert!(matches!(Allocator::free(&mut core, address + 4, 12), Err(WieError::FatalError(x)) if x.starts_with("Invalid free")));
+
+        let address = Allocator::alloc(&mut core, 1024)?;
+        core.write_bytes(address + 1024, &[0])?;
+        assert!(matches!(Allocator::free(&mut core, address, 1024), Err(WieError::FatalError(x)) if x.starts_with("Heap buffer overflow")));
+
+        let address = Allocator::alloc(&mut core, 12)?;
+        core.write_bytes(address - 1, &[0])?;
+        assert!(matches!(Allocator::free(&mut core, address, 12), Err(WieError::FatalError(x)) if x.starts_with("Heap buffer underflow")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn census_groups_live_allocations_by_caller() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        assert!(Allocator::census(&core).is_none());
+
+        Allocator::enable_debug(&mut core);
+        Allocator::init(&mut core)?;
+
+        set_lr(&mut core, 0x1001);
+        Allocator::alloc(&mut core, 0x10)?;
+        Allocator::alloc(&mut core, 0x20)?;
+        set_lr(&mut core, 0x2001);
+        let freed = Allocator::alloc(&mut core, 0x100)?;
+        Allocator::alloc(&mut core, 0x8)?;
+        Allocator::free(&mut core, freed, 0x100)?;
+
+        let census = Allocator::census(&core).unwrap();
+        assert_eq!(
+            census,
+            "3 live allocations, 0x38 bytes\n0x30 bytes in 2 allocations from 0x1000 (0x1001)\n0x8 bytes in 1 allocations from 0x2000 (0x2001)\n"
+        );
+
+        Ok(())
+    }
 }
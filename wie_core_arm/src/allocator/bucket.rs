@@ -1,4 +1,6 @@
 use alloc::vec;
+#[cfg(not(target_arch = "wasm32"))]
+use alloc::{format, string::String};
 
 use wie_util::{ByteRead, ByteWrite, Result, WieError};
 
@@ -138,6 +140,32 @@ impl BucketAllocator {
         Ok(header[0] & (1 << (slot % 8)) == 0)
     }
 
+    /// Bytes all slots together can hand out.
+    pub fn capacity() -> u32 {
+        BUCKETS.iter().map(|&(slot_size, slot_count)| (slot_size * slot_count) as u32).sum()
+    }
+
+    /// Bytes an allocation of `size` bytes takes from the heap.
+    pub fn slot_size(size: u32) -> u32 {
+        BUCKETS[Self::find_bucket_index(size)].0 as u32
+    }
+
+    /// Summarizes the slots in use per bucket, for the debugger.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn describe<R: ?Sized + ByteRead>(reader: &R, base_address: u32) -> Result<String> {
+        let mut result = String::from("Bucket heap:\n");
+        for (i, &(slot_size, slot_count)) in BUCKETS.iter().enumerate() {
+            let mut header = vec![0u8; header_length(i)];
+            reader.read_bytes(base_address + region_offset(i) as u32, &mut header)?;
+
+            // free slots have their bit set
+            let used = slot_count - header.iter().map(|x| x.count_ones() as usize).sum::<usize>();
+            result += &format!("{slot_size:>4} bytes: {used}/{slot_count} slots in use\n");
+        }
+
+        Ok(result)
+    }
+
     fn find_bucket_index(size: u32) -> usize {
         BUCKETS.iter().position(|&(s, _)| size as usize <= s).unwrap_or(BUCKETS.len() - 1)
     }
@@ -147,7 +175,7 @@ impl BucketAllocator {
 mod tests {
     use wie_util::Result;
 
-    use crate::ArmCore;
+    use crate::{ArmCore, MemoryPermission};
 
     use super::BucketAllocator;
 
@@ -160,7 +188,7 @@ mod tests {
     #[test]
     fn test_allocator() -> Result<()> {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x40000000, 0x8000000)?;
+        core.map(0x40000000, 0x8000000, MemoryPermission::ReadWrite)?;
 
         BucketAllocator::init(&mut core, 0x40000000, 0x8000000)?;
 
@@ -196,7 +224,7 @@ mod tests {
     #[test]
     fn test_init_rejects_undersized_region() {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x40000000, 0x1000000).unwrap();
+        core.map(0x40000000, 0x1000000, MemoryPermission::ReadWrite).unwrap();
 
         // 0x1000000 (16 MB) is far too small for the full bucket layout.
         assert!(BucketAllocator::init(&mut core, 0x40000000, 0x1000000).is_err());
@@ -205,7 +233,7 @@ mod tests {
     #[test]
     fn test_allocator_small_sizes() -> Result<()> {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x40000000, 0x8000000)?;
+        core.map(0x40000000, 0x8000000, MemoryPermission::ReadWrite)?;
 
         BucketAllocator::init(&mut core, 0x40000000, 0x8000000)?;
 
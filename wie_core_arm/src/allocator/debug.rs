@@ -0,0 +1,66 @@
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+
+/// Bytes guarded on each side of an allocation while heap debugging is enabled.
+pub const RED_ZONE_SIZE: u32 = 8;
+pub const RED_ZONE_VALUE: u8 = 0xfd;
+
+#[derive(Clone, Copy)]
+pub struct HeapAllocation {
+    pub size: u32,
+    /// Return address of the guest code that requested the allocation.
+    pub caller: u32,
+}
+
+/// Tracks live guest allocations, keyed by the address returned to the guest.
+#[derive(Default)]
+pub struct HeapDebug {
+    live: BTreeMap<u32, HeapAllocation>,
+    // address => caller of the free, until the address is handed out again
+    freed: BTreeMap<u32, u32>,
+}
+
+impl HeapDebug {
+    pub fn on_alloc(&mut self, address: u32, size: u32, caller: u32) {
+        self.freed.remove(&address);
+        self.live.insert(address, HeapAllocation { size, caller });
+    }
+
+    pub fn on_free(&mut self, address: u32, caller: u32) {
+        self.live.remove(&address);
+        self.freed.insert(address, caller);
+    }
+
+    pub fn live(&self, address: u32) -> Option<HeapAllocation> {
+        self.live.get(&address).copied()
+    }
+
+    /// Caller of the previous free of `address`, if it was freed and not reused since.
+    pub fn freed_by(&self, address: u32) -> Option<u32> {
+        self.freed.get(&address).copied()
+    }
+
+    /// Live allocations grouped by call site, largest first.
+    pub fn census(&self, mut name: impl FnMut(u32) -> String) -> String {
+        let mut sites = BTreeMap::<u32, (u32, u32)>::new();
+        for allocation in self.live.values() {
+            let (count, bytes) = sites.entry(allocation.caller).or_default();
+            *count += 1;
+            *bytes += allocation.size;
+        }
+
+        let mut sites = sites.into_iter().collect::<Vec<_>>();
+        sites.sort_by(|(_, (_, a)), (_, (_, b))| b.cmp(a));
+
+        let total = self.live.values().map(|x| x.size).sum::<u32>();
+        let mut result = format!("{} live allocations, {total:#x} bytes\n", self.live.len());
+        for (caller, (count, bytes)) in sites {
+            result += &format!("{bytes:#x} bytes in {count} allocations from {} ({caller:#x})\n", name(caller));
+        }
+
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+}
@@ -1,8 +1,12 @@
 use alloc::format;
+#[cfg(not(target_arch = "wasm32"))]
+use alloc::string::String;
 use core::mem::size_of;
 
 use bytemuck::{Pod, Zeroable};
 
+#[cfg(not(target_arch = "wasm32"))]
+use wie_util::ByteRead;
 use wie_util::{Result, WieError, read_generic, write_generic};
 
 use crate::core::ArmCore;
@@ -43,8 +47,15 @@ impl ListAllocator {
         Ok(())
     }
 
+    /// Bytes a block for `size` bytes takes from the heap, header and canary included.
+    pub fn block_size(size: u32) -> Option<u32> {
+        size.checked_add(size_of::<ListAllocationHeader>() as u32)?
+            .checked_next_multiple_of(4)?
+            .checked_add(CANARY_SIZE)
+    }
+
     pub fn alloc(core: &mut ArmCore, base_address: u32, base_size: u32, size: u32) -> Result<u32> {
-        let size_to_alloc = (size as usize + size_of::<ListAllocationHeader>()).next_multiple_of(4) as u32 + CANARY_SIZE;
+        let size_to_alloc = Self::block_size(size).ok_or(WieError::AllocationFailure)?;
 
         let address = Self::find_address(core, base_address, base_size, size_to_alloc)?;
 
@@ -67,7 +78,8 @@ impl ListAllocator {
         Ok(address + size_of::<ListAllocationHeader>() as u32)
     }
 
-    pub fn free(core: &mut ArmCore, address: u32) -> Result<()> {
+    /// Returns the size of the freed block.
+    pub fn free(core: &mut ArmCore, address: u32) -> Result<u32> {
         let base_address = address - size_of::<ListAllocationHeader>() as u32;
 
         tracing::trace!("Freeing {address:#x}");
@@ -87,7 +99,7 @@ impl ListAllocator {
         let header = ListAllocationHeader::new(header.size(), false);
         write_generic(core, base_address, header)?;
 
-        Ok(())
+        Ok(header.size())
     }
 
     pub fn is_allocated(core: &ArmCore, base_address: u32, base_size: u32, address: u32, size: u32) -> Result<bool> {
@@ -99,8 +111,39 @@ impl ListAllocator {
         }
 
         let header: ListAllocationHeader = read_generic(core, header_address)?;
-        let allocation_size = (size as usize + size_of::<ListAllocationHeader>()).next_multiple_of(4) as u32 + CANARY_SIZE;
-        Ok(header.in_use() && header.size() == allocation_size)
+        Ok(header.in_use() && Self::block_size(size) == Some(header.size()))
+    }
+
+    /// Lists the blocks in use, for the debugger.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn describe<R: ?Sized + ByteRead>(reader: &R, base_address: u32, base_size: u32) -> Result<String> {
+        const MAX_LISTED: usize = 256;
+
+        let end = base_address + base_size;
+        let mut result = String::new();
+        let (mut used_blocks, mut used_bytes) = (0, 0);
+        let mut cursor = base_address;
+        while cursor < end {
+            let header: ListAllocationHeader = read_generic(reader, cursor)?;
+            if header.size() == 0 {
+                result += &format!("Invalid allocation header at {cursor:#x}\n");
+                break;
+            }
+
+            if header.in_use() {
+                if used_blocks < MAX_LISTED {
+                    result += &format!("{:#x}: {:#x} bytes\n", cursor + size_of::<ListAllocationHeader>() as u32, header.size());
+                }
+                used_blocks += 1;
+                used_bytes += header.size();
+            }
+            cursor += header.size();
+        }
+        if used_blocks > MAX_LISTED {
+            result += &format!("... {} more\n", used_blocks - MAX_LISTED);
+        }
+
+        Ok(format!("List heap: {used_blocks} blocks, {used_bytes:#x} bytes in use\n{result}"))
     }
 
     fn find_address(core: &mut ArmCore, base_address: u32, base_size: u32, size: u32) -> Result<u32> {
@@ -144,14 +187,14 @@ impl ListAllocator {
 mod tests {
     use wie_util::{Result, WieError, write_generic};
 
-    use crate::ArmCore;
+    use crate::{ArmCore, MemoryPermission};
 
     use super::ListAllocator;
 
     #[test]
     fn test_allocator() -> Result<()> {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x40000000, 0x1000)?;
+        core.map(0x40000000, 0x1000, MemoryPermission::ReadWrite)?;
 
         ListAllocator::init(&mut core, 0x40000000, 0x1000)?;
         let address = ListAllocator::alloc(&mut core, 0x40000000, 0x1000, 4)?;
@@ -164,7 +207,7 @@ mod tests {
     #[test]
     fn test_coalesce_adjacent_free_blocks() -> Result<()> {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x40000000, 0x1000)?;
+        core.map(0x40000000, 0x1000, MemoryPermission::ReadWrite)?;
 
         ListAllocator::init(&mut core, 0x40000000, 0x400)?;
         let a = ListAllocator::alloc(&mut core, 0x40000000, 0x400, 0x100)?;
@@ -180,10 +223,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_describe_lists_blocks_in_use() -> Result<()> {
+        let mut core = ArmCore::new(false, None).unwrap();
+        core.map(0x40000000, 0x1000, MemoryPermission::ReadWrite)?;
+
+        ListAllocator::init(&mut core, 0x40000000, 0x400)?;
+        let a = ListAllocator::alloc(&mut core, 0x40000000, 0x400, 0x10)?;
+        let b = ListAllocator::alloc(&mut core, 0x40000000, 0x400, 0x20)?;
+        ListAllocator::free(&mut core, a)?;
+
+        let description = ListAllocator::describe(&core, 0x40000000, 0x400)?;
+        assert!(description.starts_with("List heap: 1 blocks, 0x28 bytes in use\n"));
+        assert!(description.contains(&alloc::format!("{b:#x}: 0x28 bytes")));
+        assert!(!description.contains(&alloc::format!("{a:#x}:")));
+
+        Ok(())
+    }
+
     #[test]
     fn test_double_free_returns_error() -> Result<()> {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x40000000, 0x1000)?;
+        core.map(0x40000000, 0x1000, MemoryPermission::ReadWrite)?;
 
         ListAllocator::init(&mut core, 0x40000000, 0x1000)?;
         let address = ListAllocator::alloc(&mut core, 0x40000000, 0x1000, 4)?;
@@ -199,7 +260,7 @@ mod tests {
     #[test]
     fn test_corrupted_canary_returns_error() -> Result<()> {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x40000000, 0x1000)?;
+        core.map(0x40000000, 0x1000, MemoryPermission::ReadWrite)?;
 
         ListAllocator::init(&mut core, 0x40000000, 0x1000)?;
         let address = ListAllocator::alloc(&mut core, 0x40000000, 0x1000, 4)?;
@@ -1,4 +1,11 @@
-use alloc::{format, vec, vec::Vec};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 
 use wie_util::{ByteRead, Result, WieError};
 
@@ -7,51 +14,144 @@ use crate::ArmCore;
 mod hook;
 mod parser;
 mod patch;
+mod report;
+
+pub use self::report::{BinaryPatchReport, check_binary_patches, find_binary_pattern};
 
 use self::{
     hook::{Hook, PatternHook},
     patch::{PatchSpec, PatternPatchSpec},
 };
 
-/// Match the binary against the embedded patch table and install any matching
-/// patches/hooks. The on-disk MD5 selects a hash-keyed entry first, falling
-/// back to a hash-less generic entry. `scan_ranges` are the `(base, size)` byte
-/// ranges searched for pattern matching (typically the guest `.text` region).
-/// Returns the number of patches + hooks installed.
+/// The hook dispatcher's `SVC #0x80`, little-endian, in Thumb and ARM encoding.
+/// Patches may never produce either.
+const THUMB_SVC_80: [u8; 2] = [0x80, 0xdf];
+const ARM_SVC_80: [u8; 4] = [0x80, 0x00, 0x00, 0xef];
+
+/// Register a user-supplied patch table, in the same format as
+/// `data/binary_patches.toml`, to be merged with the embedded one by
+/// [`install_binary_patches`]. `origin` names the table in diagnostics. The
+/// table is validated here, so malformed files and hash-keyed entries that
+/// collide with an already known entry fail before anything is loaded.
+pub fn add_binary_patches(core: &mut ArmCore, origin: &str, source: &str) -> Result<()> {
+    let table = Arc::new(PatchTable {
+        origin: origin.to_string(),
+        entries: parser::parse_binary_patches(source, origin)?,
+    });
+
+    let mut tables = patch_tables(core);
+    tables.push(table.clone());
+    check_hash_conflicts(&tables)?;
+
+    core.inner.lock().binary_patches.push(table);
+    Ok(())
+}
+
+/// Match the binary against the embedded patch table and any user tables added
+/// with [`add_binary_patches`], and install the matching patches/hooks. The
+/// on-disk MD5 selects a hash-keyed entry first; otherwise every hash-less
+/// generic entry is merged and installed together. `scan_ranges` are the
+/// `(base, size)` byte ranges searched for pattern matching (typically the
+/// guest `.text` region). Returns the number of patches + hooks installed.
 pub fn install_binary_patches(core: &mut ArmCore, data: &[u8], scan_ranges: &[(u32, u32)]) -> Result<usize> {
     let hash = md5::compute(data).0;
-    let entries = parser::binary_patches();
-    if let Some(entry) = entries.iter().find(|e| matches!(e.hash, Some(h) if h == hash)) {
+    let tables = patch_tables(core);
+    let entries = tables.iter().flat_map(|x| &x.entries);
+
+    if let Some(entry) = entries.clone().find(|e| matches!(e.hash, Some(h) if h == hash)) {
         return install_entry(core, entry, scan_ranges, true);
     }
-    if let Some(entry) = entries.iter().find(|e| e.hash.is_none()) {
-        return install_entry(core, entry, scan_ranges, false);
+    if let Some(entry) = merge_generic_entries(entries) {
+        return install_entry(core, &entry, scan_ranges, false);
     }
     Ok(0)
 }
 
+/// A parsed patch table. `origin` is kept for diagnostics.
+pub(crate) struct PatchTable {
+    origin: String,
+    entries: Vec<Entry>,
+}
+
+/// The embedded table followed by the user tables, in the order they were added.
+fn patch_tables(core: &ArmCore) -> Vec<Arc<PatchTable>> {
+    let mut inner = core.inner.lock();
+
+    // the embedded table is parsed on first use, so that it always comes first
+    if inner.binary_patches.is_empty() {
+        inner.binary_patches.push(Arc::new(PatchTable {
+            origin: parser::BUILTIN_ORIGIN.to_string(),
+            entries: parser::binary_patches(),
+        }));
+    }
+
+    inner.binary_patches.clone()
+}
+
+/// Only one hash-keyed entry can be installed for a binary, so two entries
+/// claiming the same hash would make the choice depend on table order.
+fn check_hash_conflicts(tables: &[Arc<PatchTable>]) -> Result<()> {
+    let mut seen = BTreeMap::new();
+    for table in tables {
+        for entry in &table.entries {
+            let Some(hash) = entry.hash else { continue };
+            if let Some((origin, name)) = seen.insert(hash, (&table.origin, &entry.name)) {
+                return Err(WieError::FatalError(format!(
+                    "{}: entry {} has hash {}, which is already used by entry {name} in {origin}",
+                    table.origin,
+                    entry.name,
+                    format_hash(&hash)
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generic entries are combined into a single entry because each install
+/// registers the one binary patch SVC handler; overlapping patches between the
+/// merged entries are rejected by the usual overlap checks.
+fn merge_generic_entries<'a>(entries: impl Iterator<Item = &'a Entry>) -> Option<Entry> {
+    let mut generic = entries.filter(|e| e.hash.is_none());
+    let mut merged = generic.next()?.clone();
+    for entry in generic {
+        merged.name = format!("{} + {}", merged.name, entry.name);
+        merged.hooks.extend_from_slice(&entry.hooks);
+        merged.hook_patterns.extend_from_slice(&entry.hook_patterns);
+        merged.patches.extend_from_slice(&entry.patches);
+        merged.patch_patterns.extend_from_slice(&entry.patch_patterns);
+    }
+    Some(merged)
+}
+
+fn format_hash(hash: &[u8; 16]) -> String {
+    hash.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+#[derive(Clone)]
 struct Entry {
     hash: Option<[u8; 16]>,
-    name: alloc::string::String,
+    name: String,
     hooks: Vec<Hook>,
     hook_patterns: Vec<PatternHook>,
     patches: Vec<PatchSpec>,
     patch_patterns: Vec<PatternPatchSpec>,
 }
 
+#[derive(Clone)]
 enum PatternToken {
     Literal(u8),
     AnyByte,
     Capture(CaptureName),
-    /// Bit-level match for a byte that mixes a fixed opcode with a 3-bit
-    /// register field (Thumb1 low-register encoding). `(byte & mask) == fixed`
-    /// is the literal check; if `capture` is set, `(byte >> shift) & 0b111`
-    /// is read into the named register slot, with cross-byte consistency
-    /// enforced at match time.
+    /// Bit-level match for a byte that mixes a fixed opcode with a register
+    /// field (3 bits for Thumb1 low registers, 4 bits for ARM). `(byte & mask)
+    /// == fixed` is the literal check; if `capture` is set as `(name, shift,
+    /// width)`, the `width` bits at `shift` are read into the named register
+    /// slot, with cross-byte consistency enforced at match time.
     BitMatch {
         mask: u8,
         fixed: u8,
-        capture: Option<(CaptureName, u8)>,
+        capture: Option<(CaptureName, u8, u8)>,
     },
 }
 
@@ -75,10 +175,20 @@ struct PatternMatch {
     src_reg: Option<u8>,
     dst_reg: Option<u8>,
     count_reg: Option<u8>,
-    /// Address of the first `{exit_b}` byte — combined with `exit_b_bytes` to
+    /// Address of the first `{exit_b}` byte — combined with `exit_b` to
     /// compute the branch target.
     exit_b_site: Option<u32>,
-    exit_b_bytes: Option<[u8; 2]>,
+    exit_b: Option<ExitBranch>,
+}
+
+/// Branch instruction captured by `{exit_b}`: two tokens in Thumb patterns,
+/// four in ARM patterns.
+#[derive(Debug, Clone, Copy)]
+enum ExitBranch {
+    /// Thumb `B imm11`.
+    Thumb(u16),
+    /// ARM `B`/`BL imm24` with the AL condition.
+    Arm(u32),
 }
 
 fn install_entry(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)], is_specific: bool) -> Result<usize> {
@@ -104,8 +214,9 @@ fn install_entry(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)],
     Ok(n_patches + hooks.len())
 }
 
-/// Scans on 2-byte boundaries since all Thumb instructions are halfword-aligned.
-fn scan_pattern(core: &mut ArmCore, tokens: &[PatternToken], scan_ranges: &[(u32, u32)]) -> Result<Vec<(u32, PatternMatch)>> {
+/// Scans on `alignment`-byte boundaries: 2 for Thumb instructions (and raw
+/// patches), 4 for ARM instructions.
+fn scan_pattern(core: &mut ArmCore, tokens: &[PatternToken], scan_ranges: &[(u32, u32)], alignment: usize) -> Result<Vec<(u32, PatternMatch)>> {
     let mut results = Vec::new();
     let pat_len = tokens.len();
     if pat_len == 0 {
@@ -120,7 +231,7 @@ fn scan_pattern(core: &mut ArmCore, tokens: &[PatternToken], scan_ranges: &[(u32
         while off + pat_len <= buf.len() {
             if let Some(mut pm) = try_match(tokens, &buf[off..off + pat_len]) {
                 pm.addr = base + off as u32;
-                if pm.exit_b_bytes.is_some() {
+                if pm.exit_b.is_some() {
                     for (ti, t) in tokens.iter().enumerate() {
                         if matches!(t, PatternToken::Capture(CaptureName::ExitB)) {
                             pm.exit_b_site = Some(base + (off + ti) as u32);
@@ -130,7 +241,7 @@ fn scan_pattern(core: &mut ArmCore, tokens: &[PatternToken], scan_ranges: &[(u32
                 }
                 results.push((pm.addr, pm));
             }
-            off += 2;
+            off += alignment;
         }
     }
 
@@ -152,7 +263,7 @@ fn try_match(tokens: &[PatternToken], bytes: &[u8]) -> Option<PatternMatch> {
         dst_reg: None,
         count_reg: None,
         exit_b_site: None,
-        exit_b_bytes: None,
+        exit_b: None,
     };
     let mut i = 0;
     while i < tokens.len() {
@@ -171,8 +282,8 @@ fn try_match(tokens: &[PatternToken], bytes: &[u8]) -> Option<PatternMatch> {
                 if b & mask != *fixed {
                     return None;
                 }
-                if let Some((name, shift)) = capture {
-                    let value = (b >> shift) & 0b111;
+                if let Some((name, shift, width)) = capture {
+                    let value = (b >> shift) & ((1 << width) - 1);
                     let slot = match name {
                         CaptureName::SrcReg => &mut m.src_reg,
                         CaptureName::DstReg => &mut m.dst_reg,
@@ -202,24 +313,37 @@ fn try_match(tokens: &[PatternToken], bytes: &[u8]) -> Option<PatternMatch> {
                     i += 1;
                 }
                 CaptureName::ExitB => {
-                    if i + 1 >= bytes.len() || i + 1 >= tokens.len() {
-                        return None;
-                    }
-                    let hi = bytes[i + 1];
-                    if !matches!(tokens[i + 1], PatternToken::Capture(CaptureName::ExitB)) {
-                        return None;
-                    }
-                    // Reject matches where the captured bytes aren't a Thumb
-                    // unconditional `B imm11` (`11100 iiiiiiiiiii`, encoded as
-                    // 0xE0xx-0xE7xx little-endian). Without this, a permissive
-                    // pattern can land on arbitrary instructions and decode
-                    // them as if they were branches, sending the dispatcher
-                    // to a garbage `exit_pc`.
-                    if hi & 0xf8 != 0xe0 {
-                        return None;
-                    }
-                    m.exit_b_bytes = Some([b, hi]);
-                    i += 2;
+                    // The parser guarantees the run is exactly 2 (Thumb) or 4 (ARM) tokens long.
+                    let run = tokens[i..]
+                        .iter()
+                        .take_while(|t| matches!(t, PatternToken::Capture(CaptureName::ExitB)))
+                        .count();
+                    // Reject matches where the captured bytes aren't an
+                    // unconditional branch: Thumb `B imm11` (`11100
+                    // iiiiiiiiiii`, 0xE0xx-0xE7xx little-endian) or ARM
+                    // `B`/`BL imm24` with the AL condition (top byte 0xEA/0xEB).
+                    // Without this, a permissive pattern can land on arbitrary
+                    // instructions and decode them as if they were branches,
+                    // sending the dispatcher to a garbage `exit_pc`.
+                    let branch = match run {
+                        2 => {
+                            let raw = u16::from_le_bytes([b, bytes[i + 1]]);
+                            if raw & 0xf800 != 0xe000 {
+                                return None;
+                            }
+                            ExitBranch::Thumb(raw)
+                        }
+                        4 => {
+                            let raw = u32::from_le_bytes([b, bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+                            if raw >> 25 != 0b1110_101 {
+                                return None;
+                            }
+                            ExitBranch::Arm(raw)
+                        }
+                        _ => return None,
+                    };
+                    m.exit_b = Some(branch);
+                    i += run;
                 }
                 CaptureName::SrcReg | CaptureName::DstReg | CaptureName::CountReg => {
                     // Register captures only land in `BitMatch`, not whole-byte `Capture`.
@@ -267,12 +391,12 @@ mod tests {
             PatternToken::BitMatch {
                 mask: 0b1100_0111,
                 fixed: 0b0000_0011,
-                capture: Some((CaptureName::SrcReg, 3)),
+                capture: Some((CaptureName::SrcReg, 3, 3)),
             },
             PatternToken::BitMatch {
                 mask: 0b1111_1000,
                 fixed: 0b0011_0000,
-                capture: Some((CaptureName::SrcReg, 0)),
+                capture: Some((CaptureName::SrcReg, 0, 3)),
             },
         ];
         // 0x0B = 0b00001011 → src bits 5..3 = 001 → R1
@@ -295,6 +419,25 @@ mod tests {
         assert!(try_match(&tokens, &[0xe8, 0xe7]).is_some());
     }
 
+    #[test]
+    fn pattern_scan_accepts_arm_b_and_bl_exit_b_only() {
+        let tokens = [
+            PatternToken::Capture(CaptureName::ExitB),
+            PatternToken::Capture(CaptureName::ExitB),
+            PatternToken::Capture(CaptureName::ExitB),
+            PatternToken::Capture(CaptureName::ExitB),
+        ];
+        // `b #+8`, `bl #+8`
+        assert!(matches!(
+            try_match(&tokens, &[0x00, 0x00, 0x00, 0xea]).unwrap().exit_b,
+            Some(ExitBranch::Arm(0xea00_0000))
+        ));
+        assert!(try_match(&tokens, &[0x00, 0x00, 0x00, 0xeb]).is_some());
+        // `bne #+8` is conditional, `subs r2, r2, #1` is not a branch at all.
+        assert!(try_match(&tokens, &[0x00, 0x00, 0x00, 0x1a]).is_none());
+        assert!(try_match(&tokens, &[0x01, 0x20, 0x52, 0xe2]).is_none());
+    }
+
     mod install_entry_tests {
         use alloc::{vec, vec::Vec};
 
@@ -389,6 +532,7 @@ mod tests {
             entry.hook_patterns.push(crate::binary_patches::hook::PatternHook {
                 tokens: vec![PatternToken::Literal(0x70), PatternToken::Literal(0xb5)],
                 kind_template: crate::binary_patches::hook::PatternHookKind::Memcpy,
+                arm: false,
             });
             entry.patch_patterns.push(PatternPatchSpec {
                 tokens: vec![PatternToken::Literal(0x70), PatternToken::Literal(0xb5)],
@@ -465,4 +609,90 @@ mod tests {
             Ok(())
         }
     }
+
+    mod user_table_tests {
+        use alloc::format;
+
+        use wie_util::ByteWrite;
+
+        use super::*;
+
+        const DATA: [u8; 8] = [0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80];
+
+        #[test]
+        fn add_binary_patches_reports_origin_of_invalid_tables() -> Result<()> {
+            let mut core = ArmCore::new(false, None)?;
+
+            let err = add_binary_patches(&mut core, "broken.toml", "[[entry]]\nname = \"x\"\nhash = \"1234\"\n").unwrap_err();
+            assert!(format!("{err}").contains("broken.toml: entry x: hash must be 32 hex chars"), "{err}");
+            assert!(core.inner.lock().binary_patches.is_empty());
+            Ok(())
+        }
+
+        #[test]
+        fn add_binary_patches_rejects_duplicate_hash() -> Result<()> {
+            let mut core = ArmCore::new(false, None)?;
+            let table = |name: &str| {
+                format!("[[entry]]\nname = \"{name}\"\nhash = \"00112233445566778899aabbccddeeff\"\n[[entry.patch]]\npc = 0x2000\nbytes = \"00\"\n")
+            };
+
+            add_binary_patches(&mut core, "first.toml", &table("first"))?;
+            let err = add_binary_patches(&mut core, "second.toml", &table("second")).unwrap_err();
+            let message = format!("{err}");
+            assert!(
+                message.contains("second.toml: entry second has hash 00112233445566778899aabbccddeeff"),
+                "{message}"
+            );
+            assert!(message.contains("entry first in first.toml"), "{message}");
+            assert_eq!(core.inner.lock().binary_patches.len(), 1);
+            Ok(())
+        }
+
+        #[test]
+        fn install_binary_patches_prefers_user_hash_entry() -> Result<()> {
+            let mut core = ArmCore::new(false, None)?;
+            core.map(0x2000, 0x100)?;
+            core.write_bytes(0x2000, &DATA)?;
+
+            let table = format!(
+                "[[entry]]\nname = \"user\"\nhash = \"{}\"\n[[entry.patch]]\npc = 0x2002\nbytes = \"aa bb\"\nexpect = \"30 40\"\n",
+                format_hash(&md5::compute(DATA).0)
+            );
+            add_binary_patches(&mut core, "user.toml", &table)?;
+
+            let n = install_binary_patches(&mut core, &DATA, &[(0x2000, DATA.len() as u32)])?;
+            assert_eq!(n, 1);
+
+            let mut buf = [0u8; 4];
+            core.read_bytes(0x2000, &mut buf)?;
+            assert_eq!(buf, [0x10, 0x20, 0xaa, 0xbb]);
+            Ok(())
+        }
+
+        #[test]
+        fn install_binary_patches_merges_generic_entries() -> Result<()> {
+            let mut core = ArmCore::new(false, None)?;
+            core.map(0x2000, 0x100)?;
+            core.write_bytes(0x2000, &DATA)?;
+
+            add_binary_patches(
+                &mut core,
+                "a.toml",
+                "[[entry]]\nname = \"a\"\n[[entry.patch]]\npattern = \"10 20\"\nbytes = \"01 02\"\n",
+            )?;
+            add_binary_patches(
+                &mut core,
+                "b.toml",
+                "[[entry]]\nname = \"b\"\n[[entry.patch]]\npattern = \"50 60\"\nbytes = \"05 06\"\n",
+            )?;
+
+            let n = install_binary_patches(&mut core, &DATA, &[(0x2000, DATA.len() as u32)])?;
+            assert_eq!(n, 2);
+
+            let mut buf = [0u8; 8];
+            core.read_bytes(0x2000, &mut buf)?;
+            assert_eq!(buf, [0x01, 0x02, 0x30, 0x40, 0x05, 0x06, 0x70, 0x80]);
+            Ok(())
+        }
+    }
 }
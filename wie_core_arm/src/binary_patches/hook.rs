@@ -2,18 +2,35 @@ use alloc::{collections::BTreeMap, format, string::ToString, sync::Arc, vec::Vec
 
 use wie_util::{ByteWrite, Result, WieError, read_generic};
 
-use super::{Entry, PatternToken, scan_pattern};
+use super::{Entry, ExitBranch, PatternToken, scan_pattern};
 use crate::{ArmCore, engine::ArmRegister, function::JumpTo, stdlib};
 
 const BINARY_PATCH_SVC: u32 = 0x80;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Hook {
-    /// LSB=1 (Thumb) is required; the engine doesn't service ARM-mode SVCs.
+    /// LSB=1 selects a Thumb hook (2-byte SVC); LSB=0 an ARM hook (4-byte SVC),
+    /// which must then be word-aligned.
     pub pc: u32,
     pub kind: HookKind,
 }
 
+impl Hook {
+    pub fn is_thumb(&self) -> bool {
+        self.pc & 1 == 1
+    }
+
+    /// Address of the patched SVC instruction.
+    pub fn svc_address(&self) -> u32 {
+        self.pc & !1
+    }
+
+    /// Size of the patched SVC instruction.
+    pub fn svc_size(&self) -> u32 {
+        if self.is_thumb() { 2 } else { 4 }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum HookKind {
     /// ABI: dst=r0, src=r1, len=r2; returns via LR.
@@ -38,10 +55,15 @@ pub enum HookKind {
 
 #[derive(Debug, Clone, Copy)]
 pub struct InlineCopy {
+    /// Frame pointer the offsets are measured from: R7 in Thumb code, FP (R11) in ARM code.
+    pub frame: ArmRegister,
     pub dst_offset: i32,
     pub src_offset: i32,
     pub len_offset: i32,
     pub exit_pc: u32,
+    /// Set when the loop exits through an ARM `BL`: LR is pointed past the
+    /// `BL` before jumping to `exit_pc`, as the call would have done.
+    pub exit_lr: Option<u32>,
     /// Set when the loop's outer code re-reads the stack slots after the body;
     /// the dispatcher then writes back `dst+len`, `src+len`, `len=0`.
     pub spill_back: bool,
@@ -57,11 +79,15 @@ pub struct RegInlineCopy {
 }
 
 /// Scanned across the install-time memory range; each match becomes a `Hook`.
+#[derive(Clone)]
 pub struct PatternHook {
     pub tokens: Vec<PatternToken>,
     pub kind_template: PatternHookKind,
+    /// Matches ARM code: scanned on 4-byte boundaries, hooked with an ARM SVC.
+    pub arm: bool,
 }
 
+#[derive(Clone)]
 pub enum PatternHookKind {
     Memcpy,
     Memset,
@@ -69,7 +95,8 @@ pub enum PatternHookKind {
     Strlen,
     InlineCopy {
         /// `None` => filled from the matching `{dst}` / `{src}` / `{len}`
-        /// capture (Thumb1 `SUBS Rn, #imm8` byte, negated to a stack offset).
+        /// capture (the imm8 byte of Thumb1 `SUBS Rn, #imm8` or ARM
+        /// `SUB Rd, FP, #imm8`, negated to a stack offset).
         /// `Some(v)` => pinned by TOML when the pattern omits the capture.
         dst_offset: Option<i32>,
         src_offset: Option<i32>,
@@ -81,32 +108,31 @@ pub enum PatternHookKind {
     /// Pattern-matched register-resident copy loop. The `src`/`dst`/`count`
     /// registers are read from the pattern's bit-level captures and `exit_pc`
     /// is derived at install time from `match_addr + pattern.len()` (Thumb bit
-    /// set).
+    /// set for Thumb patterns).
     RegInlineCopy {
         count_offset: i32,
     },
 }
 
 /// Expand static + pattern hooks into a single `Vec<Hook>` whose PCs are final
-/// and Thumb-valid. All pattern matching happens here; downstream consumers
+/// and mode-valid. All pattern matching happens here; downstream consumers
 /// (overlap check, `apply_hooks`) only see PC + kind, never raw tokens.
 ///
-/// Static hook PCs come from TOML, so we validate the Thumb bit up front —
+/// Static hook PCs come from TOML, so we validate their alignment up front —
 /// `install_entry` runs `apply_patches` between resolve and apply, and a
 /// fatal-after-write would leave guest memory partially modified.
 pub fn resolve_hooks(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)]) -> Result<Vec<Hook>> {
     for hook in &entry.hooks {
-        if hook.pc & 1 == 0 {
-            return Err(WieError::FatalError(format!(
-                "entry {}: hook PC {:#x} targets ARM mode; only Thumb (LSB=1) is supported",
-                entry.name, hook.pc
-            )));
+        validate_pc(&entry.name, "hook PC", hook.pc)?;
+        if let HookKind::InlineCopy(spec) = hook.kind {
+            validate_pc(&entry.name, "hook exit_pc", spec.exit_pc)?;
         }
     }
     let mut installed: Vec<Hook> = entry.hooks.clone();
 
     for pattern in &entry.hook_patterns {
-        let matches = scan_pattern(core, &pattern.tokens, scan_ranges)?;
+        let alignment = if pattern.arm { 4 } else { 2 };
+        let matches = scan_pattern(core, &pattern.tokens, scan_ranges, alignment)?;
         for (match_addr, pm) in matches {
             let kind = match &pattern.kind_template {
                 PatternHookKind::Memcpy => HookKind::Memcpy,
@@ -129,22 +155,24 @@ pub fn resolve_hooks(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32
                     let len = len_offset
                         .or_else(|| pm.len.map(capture_to_offset))
                         .ok_or_else(|| WieError::FatalError(format!("pattern match at {match_addr:#x} missing len")))?;
-                    let exit = if let Some(v) = exit_pc {
-                        *v
+                    let (exit, exit_lr) = if let Some(v) = exit_pc {
+                        (*v, None)
                     } else {
                         let site = pm
                             .exit_b_site
                             .ok_or_else(|| WieError::FatalError("pattern missing exit_b site".to_string()))?;
-                        let bytes = pm
-                            .exit_b_bytes
+                        let branch = pm
+                            .exit_b
                             .ok_or_else(|| WieError::FatalError("pattern missing exit_b bytes".to_string()))?;
-                        decode_exit_b(site, bytes)
+                        decode_exit_b(site, branch)
                     };
                     HookKind::InlineCopy(InlineCopy {
+                        frame: if pattern.arm { ArmRegister::FP } else { ArmRegister::R7 },
                         dst_offset: dst,
                         src_offset: src,
                         len_offset: len,
                         exit_pc: exit,
+                        exit_lr,
                         spill_back: *spill_back,
                     })
                 }
@@ -161,7 +189,7 @@ pub fn resolve_hooks(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32
                         pm.count_reg
                             .ok_or_else(|| WieError::FatalError(format!("pattern match at {match_addr:#x} missing count register capture")))?,
                     );
-                    let exit_pc = match_addr.wrapping_add(pattern.tokens.len() as u32) | 1;
+                    let exit_pc = match_addr.wrapping_add(pattern.tokens.len() as u32) | (!pattern.arm as u32);
                     HookKind::RegInlineCopy(RegInlineCopy {
                         src,
                         dst,
@@ -171,7 +199,7 @@ pub fn resolve_hooks(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32
                     })
                 }
             };
-            let pc = match_addr | 1;
+            let pc = if pattern.arm { match_addr } else { match_addr | 1 };
             if installed.iter().any(|h| h.pc == pc) {
                 tracing::warn!("Hook at {pc:#x} already registered; skipping duplicate match");
                 continue;
@@ -183,6 +211,17 @@ pub fn resolve_hooks(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32
     Ok(installed)
 }
 
+/// Thumb PCs have LSB=1; ARM PCs must be word-aligned.
+fn validate_pc(entry_name: &str, what: &str, pc: u32) -> Result<()> {
+    if pc & 0b11 == 0b10 {
+        return Err(WieError::FatalError(format!(
+            "entry {entry_name}: {what} {pc:#x} targets ARM mode but is not word-aligned (set LSB=1 for Thumb)"
+        )));
+    }
+
+    Ok(())
+}
+
 /// Patch the SVC instruction at every hook PC and register the dispatcher.
 /// `hooks` must already be the fully expanded list from `resolve_hooks`. The
 /// dispatcher is registered even when `hooks` is empty so that any later SVC
@@ -191,11 +230,15 @@ pub fn resolve_hooks(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32
 pub fn apply_hooks(core: &mut ArmCore, entry_name: &str, hooks: &[Hook]) -> Result<()> {
     let mut registry = BTreeMap::new();
     for hook in hooks {
-        debug_assert!(hook.pc & 1 == 1, "resolve_hooks must reject ARM-mode PCs");
+        debug_assert!(hook.pc & 0b11 != 0b10, "resolve_hooks must reject misaligned ARM-mode PCs");
         registry.insert(hook.pc, hook.kind);
-        let patch_addr = hook.pc & !1;
-        let instruction: u16 = 0xdf00 | (BINARY_PATCH_SVC as u16 & 0xff);
-        core.write_bytes(patch_addr, &instruction.to_le_bytes())?;
+        if hook.is_thumb() {
+            let instruction: u16 = 0xdf00 | (BINARY_PATCH_SVC as u16 & 0xff);
+            core.write_bytes(hook.svc_address(), &instruction.to_le_bytes())?;
+        } else {
+            let instruction: u32 = 0xef00_0000 | BINARY_PATCH_SVC;
+            core.write_bytes(hook.svc_address(), &instruction.to_le_bytes())?;
+        }
         tracing::info!("Hook installed at {:#x}: {:?}", hook.pc, hook.kind);
     }
     if !hooks.is_empty() {
@@ -205,8 +248,9 @@ pub fn apply_hooks(core: &mut ArmCore, entry_name: &str, hooks: &[Hook]) -> Resu
     Ok(())
 }
 
-/// Negate the unsigned `SUBS Rn, #imm8` immediate: the captured byte is the
-/// distance below R7, so the resulting offset is `-imm8`.
+/// Negate the unsigned `SUBS Rn, #imm8` (or ARM `SUB Rd, FP, #imm8`)
+/// immediate: the captured byte is the distance below the frame pointer, so
+/// the resulting offset is `-imm8`.
 fn capture_to_offset(byte: u8) -> i32 {
     -(byte as i32)
 }
@@ -221,28 +265,48 @@ fn arm_register_from_index(index: u8) -> ArmRegister {
         5 => ArmRegister::R5,
         6 => ArmRegister::R6,
         7 => ArmRegister::R7,
-        _ => unreachable!("BitMatch only captures 3 bits, value must be 0..=7"),
+        8 => ArmRegister::R8,
+        9 => ArmRegister::SB,
+        10 => ArmRegister::SL,
+        11 => ArmRegister::FP,
+        12 => ArmRegister::IP,
+        13 => ArmRegister::SP,
+        14 => ArmRegister::LR,
+        15 => ArmRegister::PC,
+        _ => unreachable!("BitMatch captures at most 4 bits, value must be 0..=15"),
     }
 }
 
-/// Decode a Thumb `B imm11` (`11100 iiiiiiiiiii`) at `b_site`. Returns the
-/// target PC with the Thumb bit set.
-fn decode_exit_b(b_site: u32, bytes: [u8; 2]) -> u32 {
-    let raw = u16::from_le_bytes(bytes);
-    let imm11 = (raw & 0x07ff) as i32;
-    let offset = if imm11 & 0x400 != 0 { imm11 - 0x800 } else { imm11 };
-    let target = (b_site.wrapping_add(4) as i64).wrapping_add((offset * 2) as i64) as u32;
-    target | 1
+/// Decode the exit branch at `b_site`. Returns the target PC — with the Thumb
+/// bit set for a Thumb `B imm11` (`11100 iiiiiiiiiii`), without it for an ARM
+/// `B`/`BL imm24` — and, for `BL`, the link address it would have set.
+fn decode_exit_b(b_site: u32, branch: ExitBranch) -> (u32, Option<u32>) {
+    match branch {
+        ExitBranch::Thumb(raw) => {
+            let imm11 = (raw & 0x07ff) as i32;
+            let offset = if imm11 & 0x400 != 0 { imm11 - 0x800 } else { imm11 };
+            let target = (b_site.wrapping_add(4) as i64).wrapping_add((offset * 2) as i64) as u32;
+            (target | 1, None)
+        }
+        ExitBranch::Arm(raw) => {
+            // sign-extend imm24, then scale to bytes
+            let offset = ((raw << 8) as i32) >> 6;
+            let target = b_site.wrapping_add(8).wrapping_add(offset as u32);
+            let link = (raw & (1 << 24) != 0).then(|| b_site.wrapping_add(4));
+            (target, link)
+        }
+    }
 }
 
 type Registry = Arc<BTreeMap<u32, HookKind>>;
 
 async fn handle_binary_patch_svc(core: &mut ArmCore, registry: &mut Registry) -> Result<JumpTo> {
     let (pc, lr) = core.read_pc_lr()?;
-    // PC on entry is the address right after the patched 2-byte SVC. Drop any
-    // Thumb bit, step back over the SVC, then re-set the Thumb bit because
-    // hook PCs are stored that way.
-    let hook_pc = (pc.wrapping_sub(2)) | 1;
+    let thumb = core.inner.lock().engine.reg_read(ArmRegister::Cpsr) & 0x20 != 0;
+    // PC on entry is the address right after the patched SVC. Step back over
+    // the 2-byte Thumb SVC and re-set the Thumb bit because Thumb hook PCs are
+    // stored that way; ARM hook PCs sit 4 bytes back.
+    let hook_pc = if thumb { pc.wrapping_sub(2) | 1 } else { pc.wrapping_sub(4) };
     let kind = registry
         .get(&hook_pc)
         .copied()
@@ -292,10 +356,10 @@ async fn handle_binary_patch_svc(core: &mut ArmCore, registry: &mut Registry) ->
             Ok(JumpTo(lr))
         }
         HookKind::InlineCopy(spec) => {
-            let r7 = core.inner.lock().engine.reg_read(ArmRegister::R7);
-            let dst_slot = r7.wrapping_add(spec.dst_offset as u32);
-            let src_slot = r7.wrapping_add(spec.src_offset as u32);
-            let len_slot = r7.wrapping_add(spec.len_offset as u32);
+            let frame = core.inner.lock().engine.reg_read(spec.frame);
+            let dst_slot = frame.wrapping_add(spec.dst_offset as u32);
+            let src_slot = frame.wrapping_add(spec.src_offset as u32);
+            let len_slot = frame.wrapping_add(spec.len_offset as u32);
             let dst: u32 = read_generic(core, dst_slot)?;
             let src: u32 = read_generic(core, src_slot)?;
             let len: u32 = read_generic(core, len_slot)?;
@@ -309,6 +373,9 @@ async fn handle_binary_patch_svc(core: &mut ArmCore, registry: &mut Registry) ->
                 core.write_bytes(src_slot, &src.wrapping_add(len).to_le_bytes())?;
                 core.write_bytes(len_slot, &0u32.to_le_bytes())?;
             }
+            if let Some(exit_lr) = spec.exit_lr {
+                core.inner.lock().engine.reg_write(ArmRegister::LR, exit_lr);
+            }
             Ok(JumpTo(spec.exit_pc))
         }
         HookKind::RegInlineCopy(spec) => {
@@ -342,7 +409,10 @@ mod tests {
     use wie_util::ByteRead;
 
     use super::*;
-    use crate::function::{RegisteredFunction, RegisteredFunctionHolder};
+    use crate::{
+        MemoryPermission,
+        function::{RegisteredFunction, RegisteredFunctionHolder},
+    };
 
     fn registry_with(pc: u32, kind: HookKind) -> Registry {
         let mut map = BTreeMap::new();
@@ -350,11 +420,12 @@ mod tests {
         Arc::new(map)
     }
 
-    /// Set PC to where it would be on entry to the SVC handler for a hook at
-    /// `hook_pc` (svc_addr + 2, i.e. just past the patched 2-byte SVC).
+    /// Set PC to where it would be on entry to the SVC handler for a Thumb hook
+    /// at `hook_pc` (svc_addr + 2, i.e. just past the patched 2-byte SVC), in
+    /// Thumb state.
     fn set_post_svc_pc(core: &mut ArmCore, hook_pc: u32) {
         let mut inner = core.inner.lock();
-        inner.engine.reg_write(ArmRegister::PC, (hook_pc & !1).wrapping_add(2));
+        inner.engine.reg_write(ArmRegister::PC, (hook_pc & !1).wrapping_add(2) | 1);
     }
 
     fn entry_with_static(name: &str, hooks: Vec<Hook>) -> Entry {
@@ -369,20 +440,41 @@ mod tests {
     }
 
     #[test]
-    fn resolve_hooks_rejects_arm_mode_pc() -> Result<()> {
+    fn resolve_hooks_rejects_misaligned_arm_mode_pc() -> Result<()> {
         let entry = entry_with_static(
             "arm-mode",
             vec![Hook {
-                pc: 0x2000, // LSB=0 => ARM mode
+                pc: 0x2002, // LSB=0 => ARM mode, but not word-aligned
                 kind: HookKind::Memcpy,
             }],
         );
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x1000)?;
+        core.map(0x2000, 0x1000, MemoryPermission::ReadWriteExecute)?;
 
         let err = resolve_hooks(&mut core, &entry, &[]).unwrap_err();
         let msg = alloc::format!("{err}");
-        assert!(msg.contains("ARM mode"), "unexpected error: {msg}");
+        assert!(msg.contains("not word-aligned"), "unexpected error: {msg}");
+        Ok(())
+    }
+
+    #[test]
+    fn apply_hooks_writes_arm_svc_instruction() -> Result<()> {
+        let entry = entry_with_static(
+            "arm-patch",
+            vec![Hook {
+                pc: 0x2004, // ARM
+                kind: HookKind::Memcpy,
+            }],
+        );
+        let mut core = ArmCore::new(false, None)?;
+        core.map(0x2000, 0x1000, MemoryPermission::ReadWriteExecute)?;
+
+        let hooks = resolve_hooks(&mut core, &entry, &[])?;
+        apply_hooks(&mut core, &entry.name, &hooks)?;
+
+        let mut buf = [0u8; 4];
+        core.read_bytes(0x2004, &mut buf)?;
+        assert_eq!(buf, [BINARY_PATCH_SVC as u8, 0x00, 0x00, 0xef]);
         Ok(())
     }
 
@@ -396,7 +488,7 @@ mod tests {
             }],
         );
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x1000)?;
+        core.map(0x2000, 0x1000, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x2000, &[0xaa, 0xbb])?;
 
         let hooks = resolve_hooks(&mut core, &entry, &[])?;
@@ -411,7 +503,7 @@ mod tests {
     #[futures_test::test]
     async fn memcpy_dispatch_copies_bytes_and_returns_via_lr() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x10000, 0x1000)?;
+        core.map(0x10000, 0x1000, MemoryPermission::ReadWriteExecute)?;
 
         let src = 0x10000u32;
         let dst = 0x10400u32;
@@ -441,7 +533,7 @@ mod tests {
     #[futures_test::test]
     async fn memset_dispatch_fills_bytes() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x10000, 0x1000)?;
+        core.map(0x10000, 0x1000, MemoryPermission::ReadWriteExecute)?;
 
         let dst = 0x10000u32;
         let len = 16u32;
@@ -467,7 +559,7 @@ mod tests {
     #[futures_test::test]
     async fn strcpy_dispatch_copies_null_terminated_string_and_returns_via_lr() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x10000, 0x1000)?;
+        core.map(0x10000, 0x1000, MemoryPermission::ReadWriteExecute)?;
 
         let src = 0x10000u32;
         let dst = 0x10400u32;
@@ -503,7 +595,7 @@ mod tests {
     #[futures_test::test]
     async fn strlen_dispatch_returns_length_in_r0() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x10000, 0x1000)?;
+        core.map(0x10000, 0x1000, MemoryPermission::ReadWriteExecute)?;
 
         let str_ptr = 0x10100u32;
         let s = b"abcdef\0";
@@ -529,7 +621,7 @@ mod tests {
     #[futures_test::test]
     async fn inline_copy_dispatch_reads_frame_copies_and_jumps_to_exit() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x10000, 0x2000)?;
+        core.map(0x10000, 0x2000, MemoryPermission::ReadWriteExecute)?;
 
         let src = 0x10000u32;
         let dst = 0x10800u32;
@@ -549,10 +641,12 @@ mod tests {
         set_post_svc_pc(&mut core, hook_pc);
 
         let spec = InlineCopy {
+            frame: ArmRegister::R7,
             dst_offset: 0,
             src_offset: 4,
             len_offset: 8,
             exit_pc: 0x10401,
+            exit_lr: None,
             spill_back: true,
         };
         let registry = registry_with(hook_pc, HookKind::InlineCopy(spec));
@@ -577,8 +671,8 @@ mod tests {
     #[futures_test::test]
     async fn install_then_execute_hits_dispatcher_end_to_end() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x20000, 0x2000)?;
-        core.map(0x30000, 0x1000)?;
+        core.map(0x20000, 0x2000, MemoryPermission::ReadWriteExecute)?;
+        core.map(0x30000, 0x1000, MemoryPermission::ReadWriteExecute)?;
 
         let src = 0x30000u32;
         let dst = 0x30200u32;
@@ -645,10 +739,140 @@ mod tests {
         Ok(())
     }
 
+    #[futures_test::test]
+    async fn arm_mode_hook_executes_svc_and_returns_via_lr() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        core.map(0x20000, 0x2000, MemoryPermission::ReadWriteExecute)?;
+        core.map(0x30000, 0x1000, MemoryPermission::ReadWriteExecute)?;
+
+        let str_ptr = 0x30000u32;
+        core.write_bytes(str_ptr, b"arm mode\0")?;
+
+        let hook_pc = 0x20000u32;
+        let entry = entry_with_static(
+            "e2e-arm",
+            vec![Hook {
+                pc: hook_pc,
+                kind: HookKind::Strlen,
+            }],
+        );
+        let hooks = resolve_hooks(&mut core, &entry, &[])?;
+        apply_hooks(&mut core, &entry.name, &hooks)?;
+
+        let return_addr = 0x40000u32; // ARM caller
+        {
+            let mut inner = core.inner.lock();
+            inner.engine.reg_write(ArmRegister::R0, str_ptr);
+            inner.engine.reg_write(ArmRegister::LR, return_addr);
+            inner.engine.reg_write(ArmRegister::PC, hook_pc);
+
+            let cpsr = inner.engine.reg_read(ArmRegister::Cpsr);
+            inner.engine.reg_write(ArmRegister::Cpsr, (cpsr & !0x3f) | 0x1f);
+            inner.engine.reg_write(ArmRegister::SP, 0x20f00);
+        }
+
+        let result = {
+            let mut inner = core.inner.lock();
+            inner.engine.run(0, 10)?
+        };
+        match result {
+            crate::engine::EngineRunResult::Svc { category, lr, spsr } => {
+                assert_eq!(category, BINARY_PATCH_SVC);
+                assert_eq!(lr, hook_pc + 4);
+                assert_eq!(spsr & 0x20, 0);
+                let mut inner = core.inner.lock();
+                inner.engine.reg_write(ArmRegister::Cpsr, spsr);
+                inner.engine.reg_write(ArmRegister::PC, lr);
+            }
+            _ => panic!("expected Svc"),
+        }
+
+        let registry = registry_with(hook_pc, HookKind::Strlen);
+        RegisteredFunctionHolder::new(handle_binary_patch_svc, &registry).call(&mut core).await?;
+
+        let inner = core.inner.lock();
+        assert_eq!(inner.engine.reg_read(ArmRegister::R0), 8);
+        assert_eq!(inner.engine.reg_read(ArmRegister::PC), return_addr);
+        assert_eq!(inner.engine.reg_read(ArmRegister::Cpsr) & 0x20, 0);
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn arm_inline_copy_exit_bl_sets_link_register() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        core.map(0x10000, 0x2000, MemoryPermission::ReadWriteExecute)?;
+
+        let src = 0x10000u32;
+        let dst = 0x10800u32;
+        core.write_bytes(src, &[1, 2, 3])?;
+
+        let frame = 0x11000u32;
+        core.write_bytes(frame - 4, &dst.to_le_bytes())?;
+        core.write_bytes(frame - 8, &src.to_le_bytes())?;
+        core.write_bytes(frame - 12, &3u32.to_le_bytes())?;
+
+        let hook_pc = 0x10200u32;
+        {
+            let mut inner = core.inner.lock();
+            inner.engine.reg_write(ArmRegister::FP, frame);
+            inner.engine.reg_write(ArmRegister::PC, hook_pc + 4);
+            let cpsr = inner.engine.reg_read(ArmRegister::Cpsr);
+            inner.engine.reg_write(ArmRegister::Cpsr, cpsr & !0x20);
+        }
+
+        let spec = InlineCopy {
+            frame: ArmRegister::FP,
+            dst_offset: -4,
+            src_offset: -8,
+            len_offset: -12,
+            exit_pc: 0x10400,
+            exit_lr: Some(0x10224),
+            spill_back: false,
+        };
+        let registry = registry_with(hook_pc, HookKind::InlineCopy(spec));
+        RegisteredFunctionHolder::new(handle_binary_patch_svc, &registry).call(&mut core).await?;
+
+        let mut out = [0u8; 3];
+        core.read_bytes(dst, &mut out)?;
+        assert_eq!(out, [1, 2, 3]);
+
+        let inner = core.inner.lock();
+        assert_eq!(inner.engine.reg_read(ArmRegister::PC), 0x10400);
+        assert_eq!(inner.engine.reg_read(ArmRegister::LR), 0x10224);
+        Ok(())
+    }
+
+    #[test]
+    fn arm_pattern_hook_scans_word_boundaries() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        core.map(0x50000, 0x100, MemoryPermission::ReadWriteExecute)?;
+        // halfword-aligned copy of the pattern must be skipped, word-aligned one hooked
+        core.write_bytes(0x50012, &[0x01, 0x40, 0x2d, 0xe9])?;
+        core.write_bytes(0x50020, &[0x01, 0x40, 0x2d, 0xe9])?;
+
+        let entry = Entry {
+            hash: None,
+            name: "scan-arm".into(),
+            hooks: vec![],
+            hook_patterns: vec![PatternHook {
+                tokens: [0x01, 0x40, 0x2d, 0xe9].into_iter().map(PatternToken::Literal).collect(),
+                kind_template: PatternHookKind::Memcpy,
+                arm: true,
+            }],
+            patches: vec![],
+            patch_patterns: vec![],
+        };
+
+        let hooks = resolve_hooks(&mut core, &entry, &[(0x50000, 0x100)])?;
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].pc, 0x50020);
+        Ok(())
+    }
+
     #[test]
     fn pattern_scan_matches_single_hit() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x50000, 0x200)?;
+        core.map(0x50000, 0x200, MemoryPermission::ReadWriteExecute)?;
 
         let pat_bytes = [0xaa, 0xbb, 0xcc, 0xdd];
         let match_addr = 0x50020u32;
@@ -666,6 +890,7 @@ mod tests {
                     PatternToken::Literal(0xdd),
                 ],
                 kind_template: PatternHookKind::Memcpy,
+                arm: false,
             }],
             patches: vec![],
             patch_patterns: vec![],
@@ -693,19 +918,29 @@ mod tests {
 
     #[test]
     fn pattern_scan_exit_b_computes_forward_branch_target() {
-        let bytes = [0x02, 0xe0];
         let site = 0x100u32;
-        let exit = decode_exit_b(site, bytes);
-        assert_eq!(exit, (site + 4 + 4) | 1);
+        let exit = decode_exit_b(site, ExitBranch::Thumb(0xe002));
+        assert_eq!(exit, ((site + 4 + 4) | 1, None));
 
-        let neg = decode_exit_b(site, [0xfe, 0xe7]);
-        assert_eq!(neg, site | 1);
+        let neg = decode_exit_b(site, ExitBranch::Thumb(0xe7fe));
+        assert_eq!(neg, (site | 1, None));
+    }
+
+    #[test]
+    fn pattern_scan_exit_b_decodes_arm_b_and_bl() {
+        let site = 0x100u32;
+        // `b #+0x10`
+        assert_eq!(decode_exit_b(site, ExitBranch::Arm(0xea00_0002)), (site + 8 + 8, None));
+        // `b .` — branch to self
+        assert_eq!(decode_exit_b(site, ExitBranch::Arm(0xeaff_fffe)), (site, None));
+        // `bl` also reports where the call would have returned to
+        assert_eq!(decode_exit_b(site, ExitBranch::Arm(0xebff_fffe)), (site, Some(site + 4)));
     }
 
     #[test]
     fn pattern_duplicate_pc_warns_once_and_skips() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x60000, 0x100)?;
+        core.map(0x60000, 0x100, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x60010, &[0x11, 0x22, 0x33, 0x44])?;
 
         let entry = Entry {
@@ -716,10 +951,12 @@ mod tests {
                 PatternHook {
                     tokens: vec![PatternToken::Literal(0x11), PatternToken::Literal(0x22)],
                     kind_template: PatternHookKind::Memcpy,
+                    arm: false,
                 },
                 PatternHook {
                     tokens: vec![PatternToken::Literal(0x11), PatternToken::Literal(0x22)],
                     kind_template: PatternHookKind::Memcpy,
+                    arm: false,
                 },
             ],
             patches: vec![],
@@ -1,18 +1,35 @@
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec::Vec};
 
 use serde::Deserialize;
 
+use wie_util::{Result, WieError};
+
 use super::{
-    CaptureName, Entry, PatternToken,
+    ARM_SVC_80, CaptureName, Entry, PatternToken, THUMB_SVC_80,
     hook::{Hook, HookKind, InlineCopy, PatternHook, PatternHookKind},
     patch::{PatchSpec, PatternPatchSpec},
 };
+use crate::engine::ArmRegister;
 
 const BINARY_PATCHES_TOML: &str = include_str!("../../../data/binary_patches.toml");
 
+pub const BUILTIN_ORIGIN: &str = "data/binary_patches.toml";
+
 pub fn binary_patches() -> Vec<Entry> {
-    let doc: RawDoc = toml::from_str(BINARY_PATCHES_TOML).expect("parse data/binary_patches.toml");
-    doc.entry.into_iter().map(RawEntry::into_entry).collect()
+    parse_binary_patches(BINARY_PATCHES_TOML, BUILTIN_ORIGIN).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse a patch table in the `data/binary_patches.toml` format. `origin` names the source in error messages.
+pub fn parse_binary_patches(source: &str, origin: &str) -> Result<Vec<Entry>> {
+    let doc: RawDoc = toml::from_str(source).map_err(|e| WieError::FatalError(format!("{origin}: {e}")))?;
+    doc.entry
+        .into_iter()
+        .map(RawEntry::into_entry)
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| match e {
+            WieError::FatalError(message) => WieError::FatalError(format!("{origin}: {message}")),
+            e => e,
+        })
 }
 
 #[derive(Deserialize)]
@@ -38,6 +55,7 @@ struct RawHook {
     kind: KindTag,
     pc: Option<u32>,
     pattern: Option<String>,
+    mode: Option<ModeTag>,
     dst_offset: Option<i32>,
     src_offset: Option<i32>,
     len_offset: Option<i32>,
@@ -56,6 +74,13 @@ struct RawPatch {
     offset: Option<u32>,
 }
 
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ModeTag {
+    Thumb,
+    Arm,
+}
+
 #[derive(Deserialize, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 enum KindTag {
@@ -68,45 +93,55 @@ enum KindTag {
 }
 
 impl RawEntry {
-    fn into_entry(self) -> Entry {
+    fn into_entry(self) -> Result<Entry> {
         let name = self.name;
-        let hash = self.hash.as_deref().map(|s| parse_hash(s, &name));
+        let hash = self.hash.as_deref().map(|s| parse_hash(s, &name)).transpose()?;
         let mut hooks = Vec::new();
         let mut hook_patterns = Vec::new();
         for raw in self.hook {
             match (raw.pc, raw.pattern.as_deref()) {
-                (Some(pc), None) => hooks.push(Hook {
-                    pc,
-                    kind: pc_kind(&raw, &name),
-                }),
+                (Some(pc), None) => {
+                    if raw.mode.is_some() {
+                        return Err(WieError::FatalError(format!(
+                            "entry {name}: pc-based hook selects its mode with the pc LSB, `mode` is only valid with `pattern`"
+                        )));
+                    }
+                    hooks.push(Hook {
+                        pc,
+                        kind: pc_kind(&raw, pc & 1 == 0, &name)?,
+                    })
+                }
                 (None, Some(pat)) => {
-                    let tokens = parse_pattern(pat, &name);
-                    let kind_template = pattern_template(&raw, &tokens, &name);
-                    hook_patterns.push(PatternHook { tokens, kind_template });
+                    let arm = raw.mode == Some(ModeTag::Arm);
+                    let tokens = parse_pattern(pat, arm, &name)?;
+                    let kind_template = pattern_template(&raw, &tokens, &name)?;
+                    hook_patterns.push(PatternHook { tokens, kind_template, arm });
                 }
-                (Some(_), Some(_)) => panic!("entry {name}: hook cannot specify both `pc` and `pattern`"),
-                (None, None) => panic!("entry {name}: hook must specify either `pc` or `pattern`"),
+                (Some(_), Some(_)) => return Err(WieError::FatalError(format!("entry {name}: hook cannot specify both `pc` and `pattern`"))),
+                (None, None) => return Err(WieError::FatalError(format!("entry {name}: hook must specify either `pc` or `pattern`"))),
             }
         }
         if hash.is_none() && !hooks.is_empty() {
-            panic!("entry {name}: hash is required when pc-based hooks are present (a pc only makes sense for a specific binary)");
+            return Err(WieError::FatalError(format!(
+                "entry {name}: hash is required when pc-based hooks are present (a pc only makes sense for a specific binary)"
+            )));
         }
         let mut patches = Vec::new();
         let mut patch_patterns = Vec::new();
         for raw in self.patch {
-            match into_patch_kind(raw, hash.is_some(), &name) {
+            match into_patch_kind(raw, hash.is_some(), &name)? {
                 ParsedPatch::Pc(spec) => patches.push(spec),
                 ParsedPatch::Pattern(spec) => patch_patterns.push(spec),
             }
         }
-        Entry {
+        Ok(Entry {
             hash,
             name,
             hooks,
             hook_patterns,
             patches,
             patch_patterns,
-        }
+        })
     }
 }
 
@@ -115,47 +150,67 @@ enum ParsedPatch {
     Pattern(PatternPatchSpec),
 }
 
-fn into_patch_kind(raw: RawPatch, has_hash: bool, entry_name: &str) -> ParsedPatch {
-    let bytes = parse_hex_bytes(&raw.bytes, entry_name, "bytes");
+fn into_patch_kind(raw: RawPatch, has_hash: bool, entry_name: &str) -> Result<ParsedPatch> {
+    let bytes = parse_hex_bytes(&raw.bytes, entry_name, "bytes")?;
     if bytes.is_empty() {
-        panic!("entry {entry_name}: patch `bytes` must be at least 1 byte");
-    }
-    reject_svc_pattern(&bytes, entry_name);
-    let expect = raw.expect.as_deref().map(|s| {
-        let v = parse_hex_bytes(s, entry_name, "expect");
-        if v.len() != bytes.len() {
-            panic!(
-                "entry {entry_name}: patch `expect` length ({}) must equal `bytes` length ({})",
-                v.len(),
-                bytes.len()
-            );
+        return Err(WieError::FatalError(format!("entry {entry_name}: patch `bytes` must be at least 1 byte")));
+    }
+    reject_svc_pattern(&bytes, entry_name)?;
+    let expect = raw
+        .expect
+        .as_deref()
+        .map(|s| {
+            let v = parse_hex_bytes(s, entry_name, "expect")?;
+            if v.len() != bytes.len() {
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: patch `expect` length ({}) must equal `bytes` length ({})",
+                    v.len(),
+                    bytes.len()
+                )));
+            }
+            Ok(v)
+        })
+        .transpose()?;
+
+    Ok(match (raw.pc, raw.pattern.as_deref()) {
+        (Some(_), Some(_)) => {
+            return Err(WieError::FatalError(format!(
+                "entry {entry_name}: patch cannot specify both `pc` and `pattern`"
+            )));
+        }
+        (None, None) => {
+            return Err(WieError::FatalError(format!(
+                "entry {entry_name}: patch must specify either `pc` or `pattern`"
+            )));
         }
-        v
-    });
-
-    match (raw.pc, raw.pattern.as_deref()) {
-        (Some(_), Some(_)) => panic!("entry {entry_name}: patch cannot specify both `pc` and `pattern`"),
-        (None, None) => panic!("entry {entry_name}: patch must specify either `pc` or `pattern`"),
         (Some(pc), None) => {
             if !has_hash {
-                panic!("entry {entry_name}: patch `pc` requires entry `hash` (a pc only makes sense for a specific binary)");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: patch `pc` requires entry `hash` (a pc only makes sense for a specific binary)"
+                )));
             }
             if raw.offset.is_some() {
-                panic!("entry {entry_name}: patch `offset` is meaningless with `pc` (only valid for `pattern`)");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: patch `offset` is meaningless with `pc` (only valid for `pattern`)"
+                )));
             }
             ParsedPatch::Pc(PatchSpec { pc, bytes, expect })
         }
         (None, Some(pat)) => {
-            let tokens = parse_pattern(pat, entry_name);
-            reject_patch_capture_tokens(&tokens, entry_name);
+            let tokens = parse_pattern(pat, false, entry_name)?;
+            reject_patch_capture_tokens(&tokens, entry_name)?;
             let offset = raw.offset.unwrap_or(0);
             let pat_len = tokens.len() as u32;
             let bytes_len = bytes.len() as u32;
-            let end = offset
-                .checked_add(bytes_len)
-                .unwrap_or_else(|| panic!("entry {entry_name}: patch `offset` ({offset}) + `bytes` length ({bytes_len}) overflows u32"));
+            let end = offset.checked_add(bytes_len).ok_or_else(|| {
+                WieError::FatalError(format!(
+                    "entry {entry_name}: patch `offset` ({offset}) + `bytes` length ({bytes_len}) overflows u32"
+                ))
+            })?;
             if end > pat_len {
-                panic!("entry {entry_name}: patch `offset` ({offset}) + `bytes` length ({bytes_len}) exceeds pattern length ({pat_len})");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: patch `offset` ({offset}) + `bytes` length ({bytes_len}) exceeds pattern length ({pat_len})"
+                )));
             }
             ParsedPatch::Pattern(PatternPatchSpec {
                 tokens,
@@ -164,80 +219,97 @@ fn into_patch_kind(raw: RawPatch, has_hash: bool, entry_name: &str) -> ParsedPat
                 offset,
             })
         }
-    }
+    })
 }
 
-fn parse_hex_bytes(s: &str, entry_name: &str, field: &str) -> Vec<u8> {
+fn parse_hex_bytes(s: &str, entry_name: &str, field: &str) -> Result<Vec<u8>> {
     let mut out = Vec::new();
     for tok in s.split_whitespace() {
         if tok.len() != 2 || !tok.chars().all(|c| c.is_ascii_hexdigit()) {
-            panic!("entry {entry_name}: patch `{field}` token `{tok}` is not a 2-char hex byte");
+            return Err(WieError::FatalError(format!(
+                "entry {entry_name}: patch `{field}` token `{tok}` is not a 2-char hex byte"
+            )));
         }
         out.push(u8::from_str_radix(tok, 16).unwrap());
     }
-    out
+    Ok(out)
 }
 
-/// Reject `bytes` that, written as a 16-bit little-endian halfword, would
-/// produce `SVC #0x80` — the same opcode the hook dispatcher patches in.
+/// Reject `bytes` that contain `SVC #0x80` in Thumb (`80 df`) or ARM
+/// (`80 00 00 ef`) encoding — the same opcode the hook dispatcher patches in.
 /// Allowing it would let a patch silently install an unregistered hook PC and
 /// crash with a misleading "fired at unregistered PC" fatal at runtime.
-fn reject_svc_pattern(bytes: &[u8], entry_name: &str) {
-    for w in bytes.windows(2) {
-        if w == [0x80, 0xdf] {
-            panic!(
-                "entry {entry_name}: patch `bytes` may not contain the SVC #0x80 instruction (`80 df` LE) — that would shadow the hook dispatcher"
-            );
-        }
-    }
+fn reject_svc_pattern(bytes: &[u8], entry_name: &str) -> Result<()> {
+    if bytes.windows(2).any(|w| w == THUMB_SVC_80) {
+        return Err(WieError::FatalError(format!(
+            "entry {entry_name}: patch `bytes` may not contain the SVC #0x80 instruction (`80 df` LE) — that would shadow the hook dispatcher"
+        )));
+    }
+    if bytes.windows(4).any(|w| w == ARM_SVC_80) {
+        return Err(WieError::FatalError(format!(
+            "entry {entry_name}: patch `bytes` may not contain the ARM SVC #0x80 instruction (`80 00 00 ef` LE) — that would shadow the hook dispatcher"
+        )));
+    }
+    Ok(())
 }
 
 /// Patches do not get to consume capture results, so capture tokens in a patch
 /// pattern have no place to land. Reject them at parse time to avoid a dead
 /// match pattern that silently behaves like `??`.
-fn reject_patch_capture_tokens(tokens: &[PatternToken], entry_name: &str) {
+fn reject_patch_capture_tokens(tokens: &[PatternToken], entry_name: &str) -> Result<()> {
     for t in tokens {
         match t {
             PatternToken::Capture(_) => {
-                panic!("entry {entry_name}: patch pattern may not contain `{{...}}` capture tokens (use `??` for wildcards)");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: patch pattern may not contain `{{...}}` capture tokens (use `??` for wildcards)"
+                )));
             }
             PatternToken::BitMatch { capture: Some(_), .. } => {
-                panic!("entry {entry_name}: patch pattern may not contain register-capture bits in BitMatch (use `?` for wildcards)");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: patch pattern may not contain register-capture bits in BitMatch (use `?` for wildcards)"
+                )));
             }
             _ => {}
         }
     }
+    Ok(())
 }
 
-fn pc_kind(raw: &RawHook, entry_name: &str) -> HookKind {
-    match raw.kind {
+fn pc_kind(raw: &RawHook, arm: bool, entry_name: &str) -> Result<HookKind> {
+    Ok(match raw.kind {
         KindTag::Memcpy => HookKind::Memcpy,
         KindTag::Memset => HookKind::Memset,
         KindTag::Strcpy => HookKind::Strcpy,
         KindTag::Strlen => HookKind::Strlen,
         KindTag::InlineCopy => HookKind::InlineCopy(InlineCopy {
+            frame: if arm { ArmRegister::FP } else { ArmRegister::R7 },
             dst_offset: raw
                 .dst_offset
-                .unwrap_or_else(|| panic!("entry {entry_name}: pc-based inline_copy requires dst_offset")),
+                .ok_or_else(|| WieError::FatalError(format!("entry {entry_name}: pc-based inline_copy requires dst_offset")))?,
             src_offset: raw
                 .src_offset
-                .unwrap_or_else(|| panic!("entry {entry_name}: pc-based inline_copy requires src_offset")),
+                .ok_or_else(|| WieError::FatalError(format!("entry {entry_name}: pc-based inline_copy requires src_offset")))?,
             len_offset: raw
                 .len_offset
-                .unwrap_or_else(|| panic!("entry {entry_name}: pc-based inline_copy requires len_offset")),
+                .ok_or_else(|| WieError::FatalError(format!("entry {entry_name}: pc-based inline_copy requires len_offset")))?,
             exit_pc: raw
                 .exit_pc
-                .unwrap_or_else(|| panic!("entry {entry_name}: pc-based inline_copy requires exit_pc")),
+                .ok_or_else(|| WieError::FatalError(format!("entry {entry_name}: pc-based inline_copy requires exit_pc")))?,
+            exit_lr: None,
             spill_back: raw
                 .spill_back
-                .unwrap_or_else(|| panic!("entry {entry_name}: inline_copy requires spill_back")),
+                .ok_or_else(|| WieError::FatalError(format!("entry {entry_name}: inline_copy requires spill_back")))?,
         }),
-        KindTag::RegInlineCopy => panic!("entry {entry_name}: reg_inline_copy must be pattern-based, not pc-based"),
-    }
+        KindTag::RegInlineCopy => {
+            return Err(WieError::FatalError(format!(
+                "entry {entry_name}: reg_inline_copy must be pattern-based, not pc-based"
+            )));
+        }
+    })
 }
 
-fn pattern_template(raw: &RawHook, tokens: &[PatternToken], entry_name: &str) -> PatternHookKind {
-    match raw.kind {
+fn pattern_template(raw: &RawHook, tokens: &[PatternToken], entry_name: &str) -> Result<PatternHookKind> {
+    Ok(match raw.kind {
         KindTag::Memcpy => PatternHookKind::Memcpy,
         KindTag::Memset => PatternHookKind::Memset,
         KindTag::Strcpy => PatternHookKind::Strcpy,
@@ -245,65 +317,83 @@ fn pattern_template(raw: &RawHook, tokens: &[PatternToken], entry_name: &str) ->
         KindTag::RegInlineCopy => {
             let need = |cap: CaptureName, label: &str| {
                 if !tokens.iter().any(|t| match t {
-                    PatternToken::BitMatch { capture: Some((c, _)), .. } => *c == cap,
+                    PatternToken::BitMatch {
+                        capture: Some((c, _, _)), ..
+                    } => *c == cap,
                     _ => false,
                 }) {
-                    panic!("entry {entry_name}: reg_inline_copy pattern must capture {label} register");
+                    return Err(WieError::FatalError(format!(
+                        "entry {entry_name}: reg_inline_copy pattern must capture {label} register"
+                    )));
                 }
+                Ok(())
             };
-            need(CaptureName::SrcReg, "src");
-            need(CaptureName::DstReg, "dst");
-            need(CaptureName::CountReg, "count");
+            need(CaptureName::SrcReg, "src")?;
+            need(CaptureName::DstReg, "dst")?;
+            need(CaptureName::CountReg, "count")?;
             PatternHookKind::RegInlineCopy {
                 count_offset: raw
                     .count_offset
-                    .unwrap_or_else(|| panic!("entry {entry_name}: reg_inline_copy requires count_offset")),
+                    .ok_or_else(|| WieError::FatalError(format!("entry {entry_name}: reg_inline_copy requires count_offset")))?,
             }
         }
         KindTag::InlineCopy => {
             let exit_cap = tokens.iter().any(|t| matches!(t, PatternToken::Capture(CaptureName::ExitB)));
             if !exit_cap && raw.exit_pc.is_none() {
-                panic!("entry {entry_name}: inline_copy pattern needs either {{exit_b}} capture or exit_pc");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: inline_copy pattern needs either {{exit_b}} capture or exit_pc"
+                )));
             }
             if exit_cap && raw.exit_pc.is_some() {
-                panic!("entry {entry_name}: inline_copy pattern cannot specify both {{exit_b}} and exit_pc");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: inline_copy pattern cannot specify both {{exit_b}} and exit_pc"
+                )));
             }
             PatternHookKind::InlineCopy {
-                dst_offset: resolve_offset("dst_offset", tokens, CaptureName::Dst, raw.dst_offset, entry_name),
-                src_offset: resolve_offset("src_offset", tokens, CaptureName::Src, raw.src_offset, entry_name),
-                len_offset: resolve_offset("len_offset", tokens, CaptureName::Len, raw.len_offset, entry_name),
+                dst_offset: resolve_offset("dst_offset", tokens, CaptureName::Dst, raw.dst_offset, entry_name)?,
+                src_offset: resolve_offset("src_offset", tokens, CaptureName::Src, raw.src_offset, entry_name)?,
+                len_offset: resolve_offset("len_offset", tokens, CaptureName::Len, raw.len_offset, entry_name)?,
                 exit_pc: raw.exit_pc,
                 spill_back: raw
                     .spill_back
-                    .unwrap_or_else(|| panic!("entry {entry_name}: inline_copy requires spill_back")),
+                    .ok_or_else(|| WieError::FatalError(format!("entry {entry_name}: inline_copy requires spill_back")))?,
             }
         }
-    }
+    })
 }
 
-fn resolve_offset(field: &str, tokens: &[PatternToken], cap: CaptureName, fixed: Option<i32>, entry_name: &str) -> Option<i32> {
+fn resolve_offset(field: &str, tokens: &[PatternToken], cap: CaptureName, fixed: Option<i32>, entry_name: &str) -> Result<Option<i32>> {
     let has_cap = tokens.iter().any(|t| matches!(t, PatternToken::Capture(c) if *c == cap));
     match (has_cap, fixed) {
-        (true, None) => None,
-        (false, Some(_)) => fixed,
-        (true, Some(_)) => panic!("entry {entry_name}: {field} cannot be set when a corresponding capture is in the pattern"),
-        (false, None) => panic!("entry {entry_name}: {field} required when no matching capture is in the pattern"),
+        (true, None) => Ok(None),
+        (false, Some(_)) => Ok(fixed),
+        (true, Some(_)) => Err(WieError::FatalError(format!(
+            "entry {entry_name}: {field} cannot be set when a corresponding capture is in the pattern"
+        ))),
+        (false, None) => Err(WieError::FatalError(format!(
+            "entry {entry_name}: {field} required when no matching capture is in the pattern"
+        ))),
     }
 }
 
-fn parse_hash(s: &str, entry_name: &str) -> [u8; 16] {
+fn parse_hash(s: &str, entry_name: &str) -> Result<[u8; 16]> {
     if s.len() != 32 {
-        panic!("entry {entry_name}: hash must be 32 hex chars (got {} chars: `{s}`)", s.len());
+        return Err(WieError::FatalError(format!(
+            "entry {entry_name}: hash must be 32 hex chars (got {} chars: `{s}`)",
+            s.len()
+        )));
     }
     let mut out = [0u8; 16];
     for i in 0..16 {
         out[i] = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16)
-            .unwrap_or_else(|_| panic!("entry {entry_name}: hash contains non-hex byte at offset {i}: `{s}`"));
+            .map_err(|_| WieError::FatalError(format!("entry {entry_name}: hash contains non-hex byte at offset {i}: `{s}`")))?;
     }
-    out
+    Ok(out)
 }
 
-fn parse_pattern(pattern: &str, entry_name: &str) -> Vec<PatternToken> {
+/// `arm` selects ARM-mode token shapes: `{exit_b}` spans 4 tokens and register
+/// placeholders span 4 bits.
+pub fn parse_pattern(pattern: &str, arm: bool, entry_name: &str) -> Result<Vec<PatternToken>> {
     let mut tokens = Vec::new();
     for raw in pattern.split_whitespace() {
         let token = if raw == "??" {
@@ -311,56 +401,75 @@ fn parse_pattern(pattern: &str, entry_name: &str) -> Vec<PatternToken> {
         } else if raw.len() == 10
             && let Some(bits) = raw.strip_prefix("0b")
         {
-            parse_bit_match(bits, entry_name)
+            parse_bit_match(bits, arm, entry_name)?
         } else if let Some(rest) = raw.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
             let cap = match rest {
                 "dst" => CaptureName::Dst,
                 "src" => CaptureName::Src,
                 "len" => CaptureName::Len,
                 "exit_b" => CaptureName::ExitB,
-                _ => panic!("entry {entry_name}: unknown capture name {{{rest}}} (allowed: dst, src, len, exit_b)"),
+                _ => {
+                    return Err(WieError::FatalError(format!(
+                        "entry {entry_name}: unknown capture name {{{rest}}} (allowed: dst, src, len, exit_b)"
+                    )));
+                }
             };
             PatternToken::Capture(cap)
         } else if raw.len() == 2 && raw.chars().all(|c| c.is_ascii_hexdigit()) {
             PatternToken::Literal(u8::from_str_radix(raw, 16).unwrap())
         } else {
-            panic!("entry {entry_name}: invalid pattern token `{raw}`");
+            return Err(WieError::FatalError(format!("entry {entry_name}: invalid pattern token `{raw}`")));
         };
         tokens.push(token);
     }
-    validate_exit_b(&tokens, entry_name);
-    tokens
+    validate_exit_b(&tokens, if arm { 4 } else { 2 }, entry_name)?;
+    Ok(tokens)
 }
 
-fn validate_exit_b(tokens: &[PatternToken], entry_name: &str) {
+/// `{exit_b}` covers one branch instruction: `width` consecutive tokens.
+fn validate_exit_b(tokens: &[PatternToken], width: usize, entry_name: &str) -> Result<()> {
+    let (count, label) = if width == 4 { ("four", "ARM") } else { ("two", "Thumb") };
     let mut pair_seen = false;
     let mut i = 0;
     while i < tokens.len() {
         if matches!(tokens[i], PatternToken::Capture(CaptureName::ExitB)) {
-            let next_is_exit = matches!(tokens.get(i + 1), Some(PatternToken::Capture(CaptureName::ExitB)));
-            if !next_is_exit {
-                panic!("entry {entry_name}: {{exit_b}} must appear as two consecutive tokens");
+            let run = tokens[i..]
+                .iter()
+                .take_while(|t| matches!(t, PatternToken::Capture(CaptureName::ExitB)))
+                .count();
+            if run < width {
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: {{exit_b}} must appear as {count} consecutive tokens in {label} patterns"
+                )));
             }
-            if matches!(tokens.get(i + 2), Some(PatternToken::Capture(CaptureName::ExitB))) {
-                panic!("entry {entry_name}: {{exit_b}} appears more than twice consecutively");
+            if run > width {
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: {{exit_b}} appears more than {count} times consecutively"
+                )));
             }
             if pair_seen {
-                panic!("entry {entry_name}: pattern may contain at most one {{exit_b}} pair");
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: pattern may contain at most one {{exit_b}} branch"
+                )));
             }
             pair_seen = true;
-            i += 2;
+            i += width;
         } else {
             i += 1;
         }
     }
+    Ok(())
 }
 
 /// Parse an 8-character byte specification of `0`/`1` literals, `?` wildcards,
-/// and `s`/`d`/`c` register placeholders (3 consecutive of the same letter).
+/// and `s`/`d`/`c` register placeholders (3 consecutive of the same letter,
+/// or 4 in ARM patterns).
 /// e.g. `00sss011` → mask=0b11000111, fixed=0b00000011, capture (src @ shift 3).
-fn parse_bit_match(bits: &str, entry_name: &str) -> PatternToken {
+fn parse_bit_match(bits: &str, arm: bool, entry_name: &str) -> Result<PatternToken> {
     if bits.len() != 8 {
-        panic!("entry {entry_name}: bit pattern `0b{bits}` must be 8 characters");
+        return Err(WieError::FatalError(format!(
+            "entry {entry_name}: bit pattern `0b{bits}` must be 8 characters"
+        )));
     }
     let mut mask: u8 = 0;
     let mut fixed: u8 = 0;
@@ -387,20 +496,32 @@ fn parse_bit_match(bits: &str, entry_name: &str) -> PatternToken {
                         *lowest = bit; // iterating high→low, so the latest write is the lowest bit
                         *count += 1;
                     }
-                    Some(_) => panic!("entry {entry_name}: bit pattern `0b{bits}` mixes multiple register placeholders"),
+                    Some(_) => {
+                        return Err(WieError::FatalError(format!(
+                            "entry {entry_name}: bit pattern `0b{bits}` mixes multiple register placeholders"
+                        )));
+                    }
                     None => capture = Some((name, bit, 1)),
                 }
             }
-            _ => panic!("entry {entry_name}: invalid char {ch:?} in bit pattern `0b{bits}` (allowed: 0,1,?,s,d,c)"),
+            _ => {
+                return Err(WieError::FatalError(format!(
+                    "entry {entry_name}: invalid char {ch:?} in bit pattern `0b{bits}` (allowed: 0,1,?,s,d,c)"
+                )));
+            }
         }
     }
-    let capture = capture.map(|(name, lowest, count)| {
-        if count != 3 {
-            panic!("entry {entry_name}: bit pattern `0b{bits}` register placeholder must span exactly 3 bits");
+    let width = if arm { 4 } else { 3 };
+    let capture = match capture {
+        Some((_, _, count)) if count != width => {
+            return Err(WieError::FatalError(format!(
+                "entry {entry_name}: bit pattern `0b{bits}` register placeholder must span exactly {width} bits"
+            )));
         }
-        (name, lowest)
-    });
-    PatternToken::BitMatch { mask, fixed, capture }
+        Some((name, lowest, _)) => Some((name, lowest, width)),
+        None => None,
+    };
+    Ok(PatternToken::BitMatch { mask, fixed, capture })
 }
 
 #[cfg(test)]
@@ -410,8 +531,7 @@ mod tests {
     use super::*;
 
     fn parse_doc(toml_text: &str) -> Vec<Entry> {
-        let doc: RawDoc = toml::from_str(toml_text).expect("parse test toml");
-        doc.entry.into_iter().map(RawEntry::into_entry).collect()
+        parse_binary_patches(toml_text, "test toml").unwrap_or_else(|e| panic!("{e}"))
     }
 
     #[test]
@@ -674,6 +794,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn arm_mode_hook_pattern_uses_arm_token_shapes() {
+        let entries = parse_doc(
+            r#"
+            [[entry]]
+            name = "arm"
+
+            [[entry.hook]]
+            kind = "inline_copy"
+            mode = "arm"
+            pattern = "{dst} 00 4b e2 0bdddd0000 ?? {exit_b} {exit_b} {exit_b} {exit_b}"
+            src_offset = -8
+            len_offset = -12
+            spill_back = false
+            "#,
+        );
+        let hook = &entries[0].hook_patterns[0];
+        assert!(hook.arm);
+        assert!(matches!(
+            hook.tokens[4],
+            PatternToken::BitMatch {
+                mask: 0x0f,
+                fixed: 0,
+                capture: Some((CaptureName::DstReg, 4, 4))
+            }
+        ));
+        assert_eq!(hook.tokens.len(), 10);
+    }
+
+    #[test]
+    fn arm_mode_pc_hook_uses_fp_frame() {
+        let entries = parse_doc(
+            r#"
+            [[entry]]
+            hash = "00000000000000000000000000000000"
+            name = "arm-pc"
+
+            [[entry.hook]]
+            kind = "inline_copy"
+            pc = 0x1000
+            dst_offset = -4
+            src_offset = -8
+            len_offset = -12
+            exit_pc = 0x1040
+            spill_back = true
+            "#,
+        );
+        let hook = &entries[0].hooks[0];
+        assert!(!hook.is_thumb());
+        assert!(matches!(hook.kind, HookKind::InlineCopy(InlineCopy { frame: ArmRegister::FP, .. })));
+    }
+
+    #[test]
+    #[should_panic(expected = "four consecutive tokens in ARM patterns")]
+    fn arm_mode_thumb_sized_exit_b_panics() {
+        parse_doc(
+            r#"
+            [[entry]]
+            name = "x"
+            [[entry.hook]]
+            kind = "inline_copy"
+            mode = "arm"
+            pattern = "{dst} {src} {len} {exit_b} {exit_b}"
+            spill_back = false
+            "#,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`mode` is only valid with `pattern`")]
+    fn pc_hook_with_mode_panics() {
+        parse_doc(
+            r#"
+            [[entry]]
+            hash = "00000000000000000000000000000000"
+            name = "x"
+            [[entry.hook]]
+            kind = "memcpy"
+            pc = 0x1000
+            mode = "arm"
+            "#,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ARM SVC #0x80")]
+    fn bytes_containing_arm_svc_80_panics() {
+        parse_doc(
+            r#"
+            [[entry]]
+            hash = "00000000000000000000000000000000"
+            name = "x"
+            [[entry.patch]]
+            pc = 0x100
+            bytes = "80 00 00 ef"
+            "#,
+        );
+    }
+
     #[test]
     #[should_panic(expected = "unknown field")]
     fn patch_with_unknown_field_panics() {
@@ -2,15 +2,17 @@ use alloc::{format, string::String, vec::Vec};
 
 use wie_util::{ByteRead, ByteWrite, Result, WieError};
 
-use super::{Entry, PatternToken, hook::Hook, scan_pattern};
+use super::{ARM_SVC_80, Entry, PatternToken, THUMB_SVC_80, hook::Hook, scan_pattern};
 use crate::ArmCore;
 
+#[derive(Clone)]
 pub struct PatchSpec {
     pub pc: u32,
     pub bytes: Vec<u8>,
     pub expect: Option<Vec<u8>>,
 }
 
+#[derive(Clone)]
 pub struct PatternPatchSpec {
     pub tokens: Vec<PatternToken>,
     pub bytes: Vec<u8>,
@@ -35,6 +37,15 @@ pub fn install_patches(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u
     Ok(patches.len())
 }
 
+/// Everything `install_patches` checks, without writing: returns the
+/// `(address, length)` of each patch that would be applied.
+pub fn check_patches(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)], hooks: &[Hook]) -> Result<Vec<(u32, usize)>> {
+    let patches = resolve_patches(core, entry, scan_ranges)?;
+    validate_overlap(&patches, hooks, &entry.name)?;
+    verify_patches(core, &entry.name, &patches)?;
+    Ok(patches.iter().map(|x| (x.addr, x.bytes.len())).collect())
+}
+
 fn resolve_patches(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)]) -> Result<Vec<Patch>> {
     let mut out: Vec<Patch> = entry
         .patches
@@ -47,7 +58,7 @@ fn resolve_patches(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)]
         .collect();
 
     for (idx, pp) in entry.patch_patterns.iter().enumerate() {
-        let matches = scan_pattern(core, &pp.tokens, scan_ranges)?;
+        let matches = scan_pattern(core, &pp.tokens, scan_ranges, 2)?;
         if matches.is_empty() {
             tracing::warn!("Patch pattern #{idx} in entry {}: no matches", entry.name);
             continue;
@@ -75,6 +86,18 @@ fn resolve_patches(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)]
 /// pre-write phases observe guest memory but never mutate it, so any failure
 /// leaves memory untouched even when other patches would have succeeded.
 fn apply_patches(core: &mut ArmCore, entry_name: &str, patches: &[Patch]) -> Result<()> {
+    verify_patches(core, entry_name, patches)?;
+    for patch in patches {
+        core.write_bytes(patch.addr, &patch.bytes)?;
+        tracing::info!("Patch applied at {:#x} ({} bytes)", patch.addr, patch.bytes.len());
+    }
+    if !patches.is_empty() {
+        tracing::info!("Applied {} patches for {entry_name}", patches.len());
+    }
+    Ok(())
+}
+
+fn verify_patches(core: &mut ArmCore, entry_name: &str, patches: &[Patch]) -> Result<()> {
     for patch in patches {
         let mut current = alloc::vec![0u8; patch.bytes.len()];
         core.read_bytes(patch.addr, &mut current)?;
@@ -99,39 +122,43 @@ fn apply_patches(core: &mut ArmCore, entry_name: &str, patches: &[Patch]) -> Res
         }
         reject_emergent_svc(core, entry_name, patch)?;
     }
-    for patch in patches {
-        core.write_bytes(patch.addr, &patch.bytes)?;
-        tracing::info!("Patch applied at {:#x} ({} bytes)", patch.addr, patch.bytes.len());
-    }
-    if !patches.is_empty() {
-        tracing::info!("Applied {} patches for {entry_name}", patches.len());
-    }
     Ok(())
 }
 
 /// Read the `[addr - 1, addr + bytes.len() + 1)` window, splice in the patch
-/// bytes, and reject if the resulting halfwords would form `SVC #0x80`. This
+/// bytes, and reject if the resulting halfwords would form `SVC #0x80`; then do
+/// the same for the words around the patch and the ARM `SVC #0x80`. This
 /// catches cases where the patch boundary plus an unmodified neighbor synthesizes
 /// the dispatcher opcode, which `reject_svc_pattern` (parser-side, payload-only)
 /// can't see.
 fn reject_emergent_svc(core: &mut ArmCore, entry_name: &str, patch: &Patch) -> Result<()> {
     let pad_start = patch.addr.saturating_sub(1);
-    let body_offset = (patch.addr - pad_start) as usize;
-    let window_len = body_offset + patch.bytes.len() + 1;
-    let mut window = alloc::vec![0u8; window_len];
-    core.read_bytes(pad_start, &mut window)?;
-    window[body_offset..body_offset + patch.bytes.len()].copy_from_slice(&patch.bytes);
-    for w in window.windows(2) {
-        if w == [0x80, 0xdf] {
-            return Err(WieError::FatalError(format!(
-                "entry {entry_name}: patch at {:#x} would synthesize SVC #0x80 with a neighboring byte",
-                patch.addr
-            )));
-        }
+    let window = patched_window(core, patch, pad_start, patch.addr + patch.bytes.len() as u32 + 1)?;
+    let thumb = window.windows(2).any(|w| w == THUMB_SVC_80);
+
+    // ARM instructions are word-aligned, so only whole words can form the opcode
+    let word_start = patch.addr & !3;
+    let window = patched_window(core, patch, word_start, (patch.addr + patch.bytes.len() as u32).next_multiple_of(4))?;
+    let arm = window.chunks(4).any(|w| w == ARM_SVC_80);
+
+    if thumb || arm {
+        return Err(WieError::FatalError(format!(
+            "entry {entry_name}: patch at {:#x} would synthesize SVC #0x80 with a neighboring byte",
+            patch.addr
+        )));
     }
     Ok(())
 }
 
+/// Guest bytes in `[start, end)` as they would read after `patch` is applied.
+fn patched_window(core: &mut ArmCore, patch: &Patch, start: u32, end: u32) -> Result<Vec<u8>> {
+    let mut window = alloc::vec![0u8; (end - start) as usize];
+    core.read_bytes(start, &mut window)?;
+    let body_offset = (patch.addr - start) as usize;
+    window[body_offset..body_offset + patch.bytes.len()].copy_from_slice(&patch.bytes);
+    Ok(window)
+}
+
 enum Region {
     Patch(u32),
     Hook(u32),
@@ -148,7 +175,7 @@ impl Region {
 
 /// Reject any byte-region collision between patches and hook SVC sites before
 /// we write anything. Patches are `[addr, addr + bytes.len())`; hook SVC sites
-/// are the 2 bytes at `pc & !1`.
+/// are the 2 (Thumb) or 4 (ARM) bytes at `pc & !1`.
 fn validate_overlap(patches: &[Patch], hooks: &[Hook], entry_name: &str) -> Result<()> {
     let mut regions: Vec<(u32, u32, Region)> = Vec::new();
     for p in patches {
@@ -156,8 +183,8 @@ fn validate_overlap(patches: &[Patch], hooks: &[Hook], entry_name: &str) -> Resu
         regions.push((p.addr, p.addr.saturating_add(p.bytes.len() as u32), Region::Patch(p.addr)));
     }
     for h in hooks {
-        let base = h.pc & !1;
-        regions.push((base, base.saturating_add(2), Region::Hook(h.pc)));
+        let base = h.svc_address();
+        regions.push((base, base.saturating_add(h.svc_size()), Region::Hook(h.pc)));
     }
     regions.sort_by_key(|r| r.0);
     for w in regions.windows(2) {
@@ -190,9 +217,12 @@ mod tests {
     use alloc::{vec, vec::Vec};
 
     use super::*;
-    use crate::binary_patches::{
-        PatternToken,
-        hook::{Hook, HookKind},
+    use crate::{
+        MemoryPermission,
+        binary_patches::{
+            PatternToken,
+            hook::{Hook, HookKind},
+        },
     };
 
     fn empty_entry(name: &str) -> Entry {
@@ -226,7 +256,7 @@ mod tests {
     #[test]
     fn resolve_patches_pattern_multi_match_applies_offset() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x40000, 0x100)?;
+        core.map(0x40000, 0x100, MemoryPermission::ReadWriteExecute)?;
         // Lay the same 4-byte pattern at two locations
         core.write_bytes(0x40000, &[0xaa, 0xbb, 0xcc, 0xdd])?;
         core.write_bytes(0x40020, &[0xaa, 0xbb, 0xcc, 0xdd])?;
@@ -253,7 +283,7 @@ mod tests {
     fn resolve_patches_pattern_offset_overflow_is_fatal() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
         // match_addr (0x1000_0000) + offset (0xffff_ffff) overflows u32.
-        core.map(0x1000_0000, 0x10)?;
+        core.map(0x1000_0000, 0x10, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x1000_0000, &[0xaa, 0xbb])?;
 
         let mut entry = empty_entry("overflow");
@@ -273,7 +303,7 @@ mod tests {
     #[test]
     fn resolve_patches_pattern_zero_match_warns_and_skips() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x40000, 0x40)?;
+        core.map(0x40000, 0x40, MemoryPermission::ReadWriteExecute)?;
         // Memory is zeros; nothing matches.
         let mut entry = empty_entry("zero-match");
         entry.patch_patterns.push(PatternPatchSpec {
@@ -290,7 +320,7 @@ mod tests {
     #[test]
     fn apply_patches_happy_path_pc() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x100)?;
+        core.map(0x2000, 0x100, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x2000, &[0xaa, 0xbb, 0xcc, 0xdd])?;
 
         let patches = vec![Patch {
@@ -309,7 +339,7 @@ mod tests {
     #[test]
     fn apply_patches_expect_mismatch_does_not_write() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x100)?;
+        core.map(0x2000, 0x100, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x2000, &[0xaa, 0xbb, 0xcc, 0x00])?;
 
         let patches = vec![Patch {
@@ -331,7 +361,7 @@ mod tests {
         // Two patches: the first would succeed, the second's expect mismatches.
         // Two-phase apply must leave both regions untouched.
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x100)?;
+        core.map(0x2000, 0x100, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x2000, &[0xaa, 0xbb])?;
         core.write_bytes(0x2010, &[0x99, 0x88])?; // mismatches second patch's expect
 
@@ -365,7 +395,7 @@ mod tests {
         // Guest byte at addr+1 is 0xdf; a 1-byte patch writing 0x80 at addr
         // would synthesize SVC #0x80 (`80 df` LE) with the unmodified neighbor.
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x100)?;
+        core.map(0x2000, 0x100, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x2000, &[0x00, 0xdf])?;
 
         let patches = vec![Patch {
@@ -388,7 +418,7 @@ mod tests {
         // Guest byte at addr-1 is 0x80; a patch writing 0xdf as its first byte
         // synthesizes SVC #0x80 with the unmodified preceding byte.
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x100)?;
+        core.map(0x2000, 0x100, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x2000, &[0x80, 0x00, 0x00])?;
 
         let patches = vec![Patch {
@@ -404,7 +434,7 @@ mod tests {
     #[test]
     fn apply_patches_without_expect_writes_with_warn() -> Result<()> {
         let mut core = ArmCore::new(false, None)?;
-        core.map(0x2000, 0x100)?;
+        core.map(0x2000, 0x100, MemoryPermission::ReadWriteExecute)?;
         core.write_bytes(0x2000, &[0x11, 0x22])?;
 
         let patches = vec![Patch {
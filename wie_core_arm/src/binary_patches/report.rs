@@ -0,0 +1,137 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use wie_util::Result;
+
+use super::{Entry, format_hash, hook, parser, patch, patch_tables, scan_pattern};
+use crate::ArmCore;
+
+/// What one patch table entry does to a binary, resolved without writing guest memory.
+pub struct BinaryPatchReport {
+    pub origin: String,
+    pub name: String,
+    /// MD5 of the targeted binary, `None` for generic entries.
+    pub hash: Option<String>,
+    /// Whether `install_binary_patches` picks this entry for the binary, either
+    /// alone or merged with the other generic entries.
+    pub selected: bool,
+    /// `(pc, kind)` of each hook; the pc LSB selects Thumb.
+    pub hooks: Vec<(u32, String)>,
+    /// `(address, length)` of each patch.
+    pub patches: Vec<(u32, usize)>,
+    /// Why installing the entry on its own would fail.
+    pub error: Option<String>,
+}
+
+/// Dry-run every entry of the embedded and user patch tables that applies to
+/// `data`: hash-keyed entries for its MD5 and all generic entries. The binary
+/// must already be loaded so that `scan_ranges` can be searched.
+pub fn check_binary_patches(core: &mut ArmCore, data: &[u8], scan_ranges: &[(u32, u32)]) -> Result<Vec<BinaryPatchReport>> {
+    let hash = md5::compute(data).0;
+    let tables = patch_tables(core);
+    let has_specific = tables.iter().flat_map(|x| &x.entries).any(|x| x.hash == Some(hash));
+
+    let mut reports = Vec::new();
+    for table in &tables {
+        for entry in &table.entries {
+            if matches!(entry.hash, Some(x) if x != hash) {
+                continue;
+            }
+
+            let mut report = BinaryPatchReport {
+                origin: table.origin.clone(),
+                name: entry.name.clone(),
+                hash: entry.hash.as_ref().map(format_hash),
+                selected: entry.hash.is_some() || !has_specific,
+                hooks: Vec::new(),
+                patches: Vec::new(),
+                error: None,
+            };
+            if let Err(err) = check_entry(core, entry, scan_ranges, &mut report) {
+                report.error = Some(err.to_string());
+            }
+            reports.push(report);
+        }
+    }
+
+    Ok(reports)
+}
+
+fn check_entry(core: &mut ArmCore, entry: &Entry, scan_ranges: &[(u32, u32)], report: &mut BinaryPatchReport) -> Result<()> {
+    let hooks = hook::resolve_hooks(core, entry, scan_ranges)?;
+    report.hooks = hooks.iter().map(|x| (x.pc, format!("{:?}", x.kind))).collect();
+    report.patches = patch::check_patches(core, entry, scan_ranges, &hooks)?;
+
+    if entry.hash.is_some() && report.hooks.is_empty() && report.patches.is_empty() {
+        report.error = Some(format!(
+            "entry {}: hash matched but produced 0 patches/hooks (table likely out of date)",
+            entry.name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Addresses in `scan_ranges` where `pattern`, written as in a patch table
+/// `pattern` field, matches. `arm` selects ARM-mode tokens and word alignment.
+pub fn find_binary_pattern(core: &mut ArmCore, pattern: &str, arm: bool, scan_ranges: &[(u32, u32)]) -> Result<Vec<u32>> {
+    let tokens = parser::parse_pattern(pattern, arm, "search")?;
+    let matches = scan_pattern(core, &tokens, scan_ranges, if arm { 4 } else { 2 })?;
+
+    Ok(matches.into_iter().map(|(address, _)| address).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use wie_util::{ByteRead, ByteWrite, Result};
+
+    use crate::{
+        ArmCore,
+        binary_patches::{add_binary_patches, format_hash},
+    };
+
+    use super::{check_binary_patches, find_binary_pattern};
+
+    const DATA: [u8; 8] = [0x10, 0x20, 0x30, 0x40, 0x10, 0x20, 0x70, 0x80];
+
+    #[test]
+    fn check_reports_expect_mismatch_without_writing() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        core.map(0x2000, 0x100)?;
+        core.write_bytes(0x2000, &DATA)?;
+
+        let hash = format_hash(&md5::compute(DATA).0);
+        let table = format!("[[entry]]\nname = \"user\"\nhash = \"{hash}\"\n[[entry.patch]]\npc = 0x2002\nbytes = \"aa bb\"\nexpect = \"31 41\"\n");
+        add_binary_patches(&mut core, "user.toml", &table)?;
+
+        let reports = check_binary_patches(&mut core, &DATA, &[(0x2000, DATA.len() as u32)])?;
+        let report = reports.iter().find(|x| x.name == "user").unwrap();
+        assert!(report.selected);
+        assert_eq!(report.hash.as_deref(), Some(hash.as_str()));
+        let error = report.error.as_deref().unwrap();
+        assert!(error.contains("patch at 0x2002 expected 31 41 but found 30 40"), "{error}");
+        assert!(reports.iter().filter(|x| x.hash.is_none()).all(|x| !x.selected));
+
+        let mut buf = [0u8; 8];
+        core.read_bytes(0x2000, &mut buf)?;
+        assert_eq!(buf, DATA);
+        Ok(())
+    }
+
+    #[test]
+    fn find_binary_pattern_respects_alignment() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        core.map(0x2000, 0x100)?;
+        core.write_bytes(0x2000, &DATA)?;
+
+        assert_eq!(find_binary_pattern(&mut core, "10 20", false, &[(0x2000, 8)])?, [0x2000, 0x2004]);
+        assert!(find_binary_pattern(&mut core, "20 ??", false, &[(0x2000, 8)])?.is_empty());
+        assert_eq!(find_binary_pattern(&mut core, "10 20 ?? ??", true, &[(0x2000, 8)])?, [0x2000, 0x2004]);
+        Ok(())
+    }
+}
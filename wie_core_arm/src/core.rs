@@ -3,14 +3,17 @@ use core::mem::size_of;
 
 use spin::Mutex;
 
-use wie_backend::{ProfileCallback, ProfileSample, YieldFuture};
-use wie_util::{ByteRead, ByteWrite, Result, WieError, read_generic};
+use wie_backend::{ProfileCallback, ProfileSample, TraceCallback, TraceEvent, TraceOptions, YieldFuture};
+use wie_util::{ByteRead, ByteWrite, GuestFault, Result, WieError, read_generic};
 
 use crate::{
     EmulatedFunction, ResultWriter, ThreadId,
+    allocator::HeapDebug,
+    binary_patches::PatchTable,
     context::ArmCoreContext,
     engine::{Arm32CpuEngine, ArmEngine, ArmRegister, EngineRunResult, MemoryPermission},
     function::{RegisteredFunction, RegisteredFunctionHolder},
+    symbols::{SymbolProvider, Symbols},
     thread::ThreadState,
     thread_wrapper::ArmCoreThreadWrapper,
 };
@@ -23,7 +26,7 @@ use crate::{
 const GLOBAL_DATA_BASE: u32 = 0x7fff0000;
 const FUNCTIONS_BASE: u32 = 0x71000000;
 const FUNCTIONS_SIZE: usize = 0x10000;
-const SVC_STUB_SIZE: u32 = 16;
+pub(crate) const SVC_STUB_SIZE: u32 = 16;
 pub const RUN_FUNCTION_LR: u32 = 0x7f000000;
 pub const HEAP_BASE: u32 = 0x40000000;
 pub const HEAP_SIZE: u32 = 0x10000000;
@@ -40,6 +43,9 @@ struct ProfileState {
     callback: ProfileCallback,
 }
 
+/// SVC arguments are shown as strings up to this length.
+const TRACE_MAX_STRING: usize = 64;
+
 pub(crate) struct ArmCoreInner {
     pub(crate) engine: Box<dyn ArmEngine>,
     last_thread_id: ThreadId,
@@ -47,23 +53,67 @@ pub(crate) struct ArmCoreInner {
     svc_handlers: BTreeMap<u32, Arc<Box<dyn RegisteredFunction>>>,
     next_stub_address: u32,
     profile: Option<ProfileState>,
+    trace: Option<TraceCallback>,
+    current_thread_id: Option<ThreadId>,
+    pub(crate) image_base: Option<u32>,
+    pub(crate) symbols: Symbols,
+    pub(crate) heap_debug: Option<HeapDebug>,
+    // bytes of the heap not handed out, kept by `Allocator`
+    pub(crate) heap_free: u32,
+    /// Parsed patch tables: the embedded one, once needed, followed by the user tables.
+    pub(crate) binary_patches: Vec<Arc<PatchTable>>,
 }
 
 impl Drop for ArmCoreInner {
     fn drop(&mut self) {
         if let Some(mut profile) = self.profile.take() {
-            let batch = drain_samples(&mut profile.samples);
+            let batch = drain_samples(&mut profile.samples, &self.symbols, &mut *self.engine, self.image_base);
             if !batch.is_empty() {
                 (profile.callback)(batch);
             }
         }
+
+        if let Some(heap_debug) = &self.heap_debug
+            && !heap_debug.is_empty()
+        {
+            let census = heap_debug.census(|address| self.symbols.symbolize(&mut *self.engine, self.image_base, address));
+            tracing::warn!("Guest heap allocations still live at exit:\n{census}");
+        }
+    }
+}
+
+// reads a printable NUL-terminated string at `address`, if there is one
+fn read_trace_string(engine: &mut dyn ArmEngine, address: u32) -> Option<String> {
+    if address < 0x1000 || !engine.is_mapped(address, 1) {
+        return None;
     }
+
+    let mut buf = [0; TRACE_MAX_STRING];
+    let size = (0..TRACE_MAX_STRING)
+        .take_while(|&x| engine.is_mapped(address.wrapping_add(x as u32), 1))
+        .count();
+    let size = engine.mem_read(address, size, &mut buf[..size]).ok()?;
+    let length = buf[..size].iter().position(|&x| x == 0)?;
+    if length < 2 || !buf[..length].iter().all(|x| x.is_ascii_graphic() || *x == b' ') {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&buf[..length]).into_owned())
 }
 
-fn drain_samples(samples: &mut BTreeMap<Vec<u32>, u64>) -> Vec<ProfileSample> {
+fn drain_samples(
+    samples: &mut BTreeMap<Vec<u32>, u64>,
+    symbols: &Symbols,
+    engine: &mut dyn ArmEngine,
+    image_base: Option<u32>,
+) -> Vec<ProfileSample> {
     core::mem::take(samples)
         .into_iter()
-        .map(|(stack, count)| ProfileSample { stack, count })
+        .map(|(stack, count)| ProfileSample {
+            symbols: stack.iter().map(|&x| symbols.symbolize(engine, image_base, x)).collect(),
+            stack,
+            count,
+        })
         .collect()
 }
 
@@ -101,6 +151,13 @@ impl ArmCore {
             svc_handlers: BTreeMap::new(),
             next_stub_address: FUNCTIONS_BASE,
             profile,
+            trace: None,
+            current_thread_id: None,
+            image_base: None,
+            symbols: Symbols::default(),
+            heap_debug: None,
+            heap_free: 0,
+            binary_patches: Vec::new(),
         };
 
         let result = Self {
@@ -117,6 +174,59 @@ impl ArmCore {
         Ok(result)
     }
 
+    /// Starts logging SVC calls and the instructions executed in `trace.ranges`.
+    pub fn set_trace(&mut self, trace: TraceOptions) {
+        let mut inner = self.inner.lock();
+
+        inner.engine.set_trace_ranges(trace.ranges);
+        inner.trace = Some(trace.callback);
+    }
+
+    fn flush_trace(&self) {
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        if let Some(callback) = &mut inner.trace {
+            for event in inner.engine.take_trace() {
+                callback(event);
+            }
+        }
+    }
+
+    fn trace_svc_call(&self, category: u32) {
+        let mut inner = self.inner.lock();
+        if inner.trace.is_none() {
+            return;
+        }
+
+        let inner = &mut *inner;
+        let id = inner.engine.reg_read(ArmRegister::IP);
+        let args = [ArmRegister::R0, ArmRegister::R1, ArmRegister::R2, ArmRegister::R3].map(|x| inner.engine.reg_read(x));
+        let strings = args.map(|x| read_trace_string(&mut *inner.engine, x));
+        let event = TraceEvent::SvcCall {
+            category,
+            id,
+            name: inner.symbols.svc_name(&mut *inner.engine, category, id),
+            args,
+            strings,
+            lr: inner.engine.reg_read(ArmRegister::LR),
+        };
+        (inner.trace.as_mut().unwrap())(event);
+    }
+
+    fn trace_svc_return(&self, category: u32, id: u32) {
+        let mut inner = self.inner.lock();
+        if inner.trace.is_none() {
+            return;
+        }
+
+        let event = TraceEvent::SvcReturn {
+            category,
+            id,
+            result: [inner.engine.reg_read(ArmRegister::R0), inner.engine.reg_read(ArmRegister::R1)],
+        };
+        (inner.trace.as_mut().unwrap())(event);
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn debug_inner(&self) -> Option<Arc<DebugInner>> {
         let inner = self.inner.lock();
@@ -128,14 +238,15 @@ impl ArmCore {
             .map(|engine| engine.debug_inner())
     }
 
-    pub fn load(&mut self, data: &[u8], address: u32, map_size: usize) -> Result<()> {
+    pub fn load(&mut self, data: &[u8], address: u32, map_size: usize, permission: MemoryPermission) -> Result<()> {
         let mut inner = self.inner.lock();
 
-        inner
-            .engine
-            .mem_map(address, map_size.next_multiple_of(0x1000), MemoryPermission::ReadWriteExecute);
+        inner.engine.mem_map(address, map_size.next_multiple_of(0x1000), permission);
         inner.engine.mem_write(address, data)?;
 
+        // the lowest loaded address is used to symbolize call stacks of guest faults
+        inner.image_base = Some(inner.image_base.map_or(address, |x| x.min(address)));
+
         Ok(())
     }
 
@@ -235,12 +346,13 @@ impl ArmCore {
             }
             r7 = prev_r7;
         }
+        let inner = &mut *inner;
         let profile = inner.profile.as_mut().unwrap();
         *profile.samples.entry(stack).or_insert(0) += 1;
         profile.counter = profile.counter.wrapping_add(1);
         if profile.counter >= PROFILE_FLUSH_INTERVAL {
             profile.counter = 0;
-            let batch = drain_samples(&mut profile.samples);
+            let batch = drain_samples(&mut profile.samples, &inner.symbols, &mut *inner.engine, inner.image_base);
             (profile.callback)(batch);
         }
     }
@@ -286,7 +398,13 @@ impl ArmCore {
         loop {
             let result = {
                 let mut inner = self.inner.lock();
-                inner.engine.run(RUN_FUNCTION_LR, 10_000)?
+                inner.engine.run(RUN_FUNCTION_LR, 10_000)
+            };
+            self.flush_trace();
+            let result = match result {
+                Ok(result) => result,
+                Err(WieError::GuestFault(fault)) => return Err(self.describe_guest_fault(fault)),
+                Err(err) => return Err(err),
             };
 
             self.sample_profile();
@@ -311,8 +429,14 @@ impl ArmCore {
                             .ok_or_else(|| WieError::FatalError(format!("Unknown SVC handler category: {category}")))?
                     };
 
+                    // the svc stub passes the function id in ip
+                    let id = self.inner.lock().engine.reg_read(ArmRegister::IP);
+                    self.trace_svc_call(category);
+
                     let mut self1 = self.clone();
                     function.call(&mut self1).await?;
+
+                    self.trace_svc_return(category, id);
                 }
             }
         }
@@ -330,19 +454,48 @@ impl ArmCore {
         R: ResultWriter<R> + Sync + Send + 'static,
         P: Sync + Send + 'static,
     {
-        let mut inner = self.inner.lock();
+        {
+            let mut inner = self.inner.lock();
+
+            if inner.svc_handlers.contains_key(&category) {
+                return Err(WieError::FatalError(format!("SVC handler already registered for {category}")));
+            }
 
-        if inner.svc_handlers.contains_key(&category) {
-            return Err(WieError::FatalError(format!("SVC handler already registered for {category}")));
+            inner
+                .svc_handlers
+                .insert(category, Arc::new(Box::new(RegisteredFunctionHolder::new(handler, context))));
         }
 
-        inner
-            .svc_handlers
-            .insert(category, Arc::new(Box::new(RegisteredFunctionHolder::new(handler, context))));
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(debug) = self.debug_inner() {
+            debug.on_svc_handler_registered(category, core::any::type_name::<F>());
+        }
 
         Ok(())
     }
 
+    /// Records a guest java class for symbol providers and the debugger's `monitor classes`.
+    pub fn note_loaded_class(&self, address: u32, name: &str) {
+        self.inner.lock().symbols.on_class_loaded(address, name);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(debug) = self.debug_inner() {
+            debug.on_class_loaded(address, name);
+        }
+    }
+
+    pub fn register_symbol_provider(&mut self, provider: Arc<dyn SymbolProvider>) {
+        self.inner.lock().symbols.add_provider(provider);
+    }
+
+    /// Names `address` for profiles, e.g. `Class.method+0x10` or `image+0x1234`.
+    pub fn symbolize(&self, address: u32) -> String {
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+
+        inner.symbols.symbolize(&mut *inner.engine, inner.image_base, address)
+    }
+
     pub fn make_svc_stub(&mut self, category: u32, id: impl Into<u32>) -> Result<u32> {
         let mut inner = self.inner.lock();
         let id = id.into();
@@ -356,6 +509,7 @@ impl ArmCore {
             return Err(WieError::FatalError("SVC stub space exhausted".into()));
         }
         inner.next_stub_address += SVC_STUB_SIZE;
+        inner.symbols.add_stub(address, category, id);
 
         let stub = [
             0x10,
@@ -381,12 +535,23 @@ impl ArmCore {
         Ok(address + 1)
     }
 
-    pub fn map(&mut self, address: u32, size: u32) -> Result<()> {
+    pub fn map(&mut self, address: u32, size: u32, permission: MemoryPermission) -> Result<()> {
         tracing::trace!("Map address: {address:#x}, size: {size:#x}");
 
         let mut inner = self.inner.lock();
 
-        inner.engine.mem_map(address, size as usize, MemoryPermission::ReadWrite);
+        inner.engine.mem_map(address, size as usize, permission);
+
+        Ok(())
+    }
+
+    /// Changes the permissions of mapped memory, e.g. to seal an image once it has relocated itself.
+    pub fn protect(&mut self, address: u32, size: u32, permission: MemoryPermission) -> Result<()> {
+        tracing::trace!("Protect address: {address:#x}, size: {size:#x}");
+
+        let mut inner = self.inner.lock();
+
+        inner.engine.mem_protect(address, size as usize, permission);
 
         Ok(())
     }
@@ -541,6 +706,21 @@ impl ArmCore {
             || (FUNCTIONS_BASE..FUNCTIONS_BASE + FUNCTIONS_SIZE as u32).contains(&address)
     }
 
+    // adds what only the core knows to a fault reported by the engine
+    fn describe_guest_fault(&self, mut fault: Box<GuestFault>) -> WieError {
+        let (thread_id, image_base) = {
+            let inner = self.inner.lock();
+            (inner.current_thread_id, inner.image_base.unwrap_or(0))
+        };
+
+        fault.thread_id = thread_id;
+        fault.call_stack = self.dump_call_stack(image_base).unwrap_or_default();
+
+        tracing::error!("Guest fault: {fault}");
+
+        WieError::GuestFault(fault)
+    }
+
     fn dump_regs(&self) -> String {
         let inner = self.inner.lock();
 
@@ -656,6 +836,7 @@ impl ThreadContextGuard {
     pub fn new(mut core: ArmCore, thread_id: ThreadId) -> Self {
         let context = core.inner.lock().threads.get(&thread_id).unwrap().context.clone(); // TODO we might not need clone
         core.restore_context(&context);
+        core.inner.lock().current_thread_id = Some(thread_id);
 
         #[cfg(not(target_arch = "wasm32"))]
         if let Some(debug) = core.debug_inner() {
@@ -672,6 +853,7 @@ impl Drop for ThreadContextGuard {
 
         let mut inner = self.core.inner.lock();
         inner.threads.get_mut(&self.thread_id).unwrap().context = context;
+        inner.current_thread_id = None;
         drop(inner);
 
         #[cfg(not(target_arch = "wasm32"))]
@@ -683,7 +865,10 @@ impl Drop for ThreadContextGuard {
 
 #[cfg(test)]
 mod tests {
+    use wie_util::MemoryAccessKind;
+
     use super::*;
+    use crate::Allocator;
 
     async fn test_svc_handler(_core: &mut ArmCore, seen_id: &mut Option<u32>, id: crate::SvcId) -> Result<()> {
         *seen_id = Some(id.0);
@@ -694,7 +879,7 @@ mod tests {
     #[test]
     fn test_thumb_svc_stub_dispatch() {
         let mut core = ArmCore::new(false, None).unwrap();
-        core.map(0x1000, 0x1000).unwrap();
+        core.map(0x1000, 0x1000, MemoryPermission::ReadWrite).unwrap();
 
         let mut context = core.save_context();
         context.sp = 0x2000;
@@ -724,4 +909,114 @@ mod tests {
             EngineRunResult::CountExhausted => panic!("expected SVC, got count exhausted"),
         }
     }
+    struct TestSymbols;
+
+    impl SymbolProvider for TestSymbols {
+        fn symbolize(&self, _: &dyn ByteRead, address: u32) -> Option<String> {
+            (0x1100..0x1200).contains(&address).then(|| format!("Test.run+{:#x}", address - 0x1100))
+        }
+
+        fn svc_name(&self, _: &dyn ByteRead, category: u32, id: u32) -> Option<String> {
+            (category == 1).then(|| format!("handler{id}"))
+        }
+    }
+
+    #[test]
+    fn test_symbolize() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        core.load(&[0; 0x10], 0x1000, 0x1000, MemoryPermission::ReadExecute)?;
+        core.register_svc_handler(1, test_svc_handler, &None)?;
+        core.register_svc_handler(2, test_svc_handler, &None)?;
+        let stub = core.make_svc_stub(1, 3u32)?;
+        let unnamed_stub = core.make_svc_stub(2, 4u32)?;
+
+        assert_eq!(core.symbolize(0x1000), "image+0x0");
+        core.register_symbol_provider(Arc::new(TestSymbols));
+
+        assert_eq!(core.symbolize(stub + 8), "handler3");
+        assert_eq!(core.symbolize(unnamed_stub), "svc2:0x4");
+        assert_eq!(core.symbolize(0x1105), "Test.run+0x4");
+        assert_eq!(core.symbolize(0x1204), "image+0x204");
+        assert_eq!(core.symbolize(HEAP_BASE + 0x10), "heap+0x10");
+        assert_eq!(core.symbolize(0x10), "0x10");
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn test_sealed_text_and_heap_fault() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        Allocator::init(&mut core)?;
+
+        let mut context = core.save_context();
+        context.sp = Allocator::alloc(&mut core, 0x100)? + 0x100;
+        core.restore_context(&context);
+
+        // movs r1, #0; str r1, [r0]; bx lr
+        let code = [0x00, 0x21, 0x01, 0x60, 0x70, 0x47];
+        core.load(&code, 0x1000, 0x1000, MemoryPermission::ReadWriteExecute)?;
+        core.protect(0x1000, 0x1000, MemoryPermission::ReadExecute)?;
+
+        let Err(WieError::GuestFault(fault)) = core.run_function::<u32>(0x1001, &[0x1000]).await else {
+            panic!("writing text should fault")
+        };
+        assert_eq!((fault.address, fault.access, fault.pc), (0x1000, MemoryAccessKind::Write, 0x1002));
+
+        let ptr_code = Allocator::alloc(&mut core, code.len() as u32)?;
+        core.write_bytes(ptr_code, &code)?;
+
+        let Err(WieError::GuestFault(fault)) = core.run_function::<u32>(ptr_code + 1, &[ptr_code]).await else {
+            panic!("executing heap should fault")
+        };
+        assert_eq!((fault.address, fault.access), (ptr_code, MemoryAccessKind::Execute));
+
+        Ok(())
+    }
+
+    async fn test_trace_handler(_core: &mut ArmCore, _: &mut (), id: crate::SvcId) -> Result<u32> {
+        Ok(id.0 + 1)
+    }
+
+    #[futures_test::test]
+    async fn test_trace_svc_and_instructions() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        core.map(0x1000, 0x1000, MemoryPermission::ReadWrite)?;
+        core.write_bytes(0x1800, b"hello\0")?;
+
+        let mut context = core.save_context();
+        context.sp = 0x2000;
+        core.restore_context(&context);
+
+        core.register_svc_handler(1, test_trace_handler, &())?;
+        core.register_symbol_provider(Arc::new(TestSymbols));
+        let stub = core.make_svc_stub(1, 7u32)?;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events1 = events.clone();
+        core.set_trace(TraceOptions {
+            ranges: vec![FUNCTIONS_BASE..FUNCTIONS_BASE + 4],
+            callback: Box::new(move |event| events1.lock().push(event.to_json())),
+        });
+
+        let result: u32 = core.run_function(stub, &[0x1800, 2]).await?;
+        assert_eq!(result, 8);
+
+        let events = events.lock();
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[0],
+            r#"{"type":"insn","pc":"0x71000000","thumb":true,"op":"b410","asm":"push {r4}","regs":{"sp":"0x1ffc"}}"#
+        );
+        assert_eq!(
+            events[1],
+            r#"{"type":"insn","pc":"0x71000002","thumb":true,"op":"4c02","asm":"ldr r4, [pc, #0x8] ; 0x7100000c","regs":{"r4":"0x7"}}"#
+        );
+        assert_eq!(
+            events[2],
+            r#"{"type":"svc","category":1,"id":7,"name":"handler7","args":[{"value":"0x1800","string":"hello"},"0x2","0x0","0x0"],"lr":"0x7f000000"}"#
+        );
+        assert_eq!(events[3], r#"{"type":"ret","category":1,"id":7,"r0":"0x8","r1":"0x0"}"#);
+
+        Ok(())
+    }
 }
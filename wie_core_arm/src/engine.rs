@@ -1,11 +1,18 @@
 mod arm32_cpu;
 #[cfg(not(target_arch = "wasm32"))]
 mod debugged_arm32_cpu;
+mod disassembler;
 
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use wie_backend::TraceEvent;
 use wie_util::{AsAny, Result};
 
 pub use arm32_cpu::Arm32CpuEngine;
 #[cfg(not(target_arch = "wasm32"))]
+pub(crate) use arm32_cpu::{WatchKind, Watchpoint};
+#[cfg(not(target_arch = "wasm32"))]
 pub use debugged_arm32_cpu::DebuggedArm32CpuEngine;
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) use debugged_arm32_cpu::{DebugBreakpointKind, DebugInner, DebugSignal, DebugStopReason};
@@ -21,9 +28,14 @@ pub trait ArmEngine: Send + AsAny {
     fn reg_write(&mut self, reg: ArmRegister, value: u32);
     fn reg_read(&self, reg: ArmRegister) -> u32;
     fn mem_map(&mut self, address: u32, size: usize, permission: MemoryPermission);
+    /// Replaces the permissions of mapped memory, unlike `mem_map` which only adds to them.
+    fn mem_protect(&mut self, address: u32, size: usize, permission: MemoryPermission);
     fn mem_write(&mut self, address: u32, data: &[u8]) -> Result<()>;
     fn mem_read(&mut self, address: u32, size: usize, result: &mut [u8]) -> Result<usize>;
     fn is_mapped(&self, address: u32, size: usize) -> bool;
+    /// Records every instruction executed inside `ranges`, replacing the previous ranges.
+    fn set_trace_ranges(&mut self, ranges: Vec<Range<u32>>);
+    fn take_trace(&mut self) -> Vec<TraceEvent>;
 }
 
 #[allow(clippy::enum_variant_names)]
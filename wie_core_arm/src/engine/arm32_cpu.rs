@@ -1,15 +1,52 @@
-use alloc::{boxed::Box, format, vec};
-use core::cell::RefCell;
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use core::{cell::RefCell, ops::Range};
 
 use arm32_cpu::{Cpu, Memory, Mode, reg};
 
-use wie_util::{Result, WieError};
+use wie_backend::TraceEvent;
+use wie_util::{GuestFault, MemoryAccessKind, Result, WieError};
+
+use crate::engine::{
+    ArmEngine, ArmRegister, EngineRunResult, MemoryPermission,
+    disassembler::{REGISTERS, disassemble_arm, disassemble_thumb},
+};
+
+// only the debugger sets watchpoints
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum WatchKind {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Watchpoint {
+    pub address: u32,
+    pub len: u32,
+    pub kind: WatchKind,
+}
 
-use crate::engine::{ArmEngine, ArmRegister, EngineRunResult, MemoryPermission};
+impl Watchpoint {
+    fn matches(&self, address: u32, size: u32, write: bool) -> bool {
+        let kind_matches = match self.kind {
+            WatchKind::Read => !write,
+            WatchKind::Write => write,
+            WatchKind::ReadWrite => true,
+        };
+
+        kind_matches && address < self.address.saturating_add(self.len) && self.address < address.saturating_add(size)
+    }
+}
 
 pub struct Arm32CpuEngine {
     cpu: Cpu,
     mem: EmulatedMemory,
+    watchpoints: Vec<Watchpoint>,
+    watch_hit: Option<Watchpoint>,
+    trace_ranges: Vec<Range<u32>>,
+    trace: Vec<TraceEvent>,
 }
 
 impl Arm32CpuEngine {
@@ -17,7 +54,117 @@ impl Arm32CpuEngine {
         Self {
             cpu: Cpu::new(),
             mem: EmulatedMemory::new(),
+            watchpoints: Vec::new(),
+            watch_hit: None,
+            trace_ranges: Vec::new(),
+            trace: Vec::new(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        if !self.watchpoints.contains(&watchpoint) {
+            self.watchpoints.push(watchpoint);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn remove_watchpoint(&mut self, watchpoint: Watchpoint) -> bool {
+        let count = self.watchpoints.len();
+        self.watchpoints.retain(|x| *x != watchpoint);
+
+        self.watchpoints.len() != count
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Returns the watchpoint the last `run` stopped at, if any.
+    pub(crate) fn take_watch_hit(&mut self) -> Option<Watchpoint> {
+        self.watch_hit.take()
+    }
+
+    fn is_traced(&self, pc: u32) -> bool {
+        self.trace_ranges.iter().any(|x| x.contains(&pc))
+    }
+
+    fn trace_registers(&self) -> [u32; 16] {
+        let mut result = [0; 16];
+        for (i, value) in result.iter_mut().enumerate().take(15) {
+            *value = self.cpu.reg_get(Mode::User, i as u8);
         }
+        // pc is implied by the next event, cpsr takes its slot
+        result[15] = self.cpu.reg_get(Mode::User, reg::CPSR);
+
+        result
+    }
+
+    // executes a single instruction and records it
+    fn run_traced(&mut self, pc: u32) -> Result<()> {
+        let thumb = self.cpu.reg_get(Mode::User, reg::CPSR) & (1 << 5) != 0;
+        let mut buf = [0; 4];
+        let (opcode, disassembly) = if thumb {
+            self.mem.read_range(pc, 2, &mut buf[..2])?;
+            // the second half of a `bl` pair may lie on an unmapped page
+            let _ = self.mem.read_range(pc + 2, 2, &mut buf[2..]);
+            let instruction = u16::from_le_bytes([buf[0], buf[1]]);
+
+            (
+                instruction as u32,
+                disassemble_thumb(pc, instruction, u16::from_le_bytes([buf[2], buf[3]])),
+            )
+        } else {
+            self.mem.read_range(pc, 4, &mut buf)?;
+            let instruction = u32::from_le_bytes(buf);
+
+            (instruction, disassemble_arm(pc, instruction))
+        };
+
+        let before = self.trace_registers();
+        self.step(pc)?;
+        let after = self.trace_registers();
+
+        let changed = before
+            .iter()
+            .zip(after.iter())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(i, (_, after))| (if i == 15 { "cpsr" } else { REGISTERS[i] }, *after))
+            .collect();
+        self.trace.push(TraceEvent::Instruction {
+            pc,
+            thumb,
+            opcode,
+            disassembly,
+            changed,
+        });
+
+        Ok(())
+    }
+
+    // executes the instruction at `pc`
+    fn step(&mut self, pc: u32) -> Result<()> {
+        if !self.mem.has_permission(pc, PERMISSION_EXECUTE) {
+            return Err(guest_fault(pc, MemoryAccessKind::Execute, pc));
+        }
+
+        let mut arm32cpu_memory = self.mem.as_arm32cpu_memory().with_watchpoints(&self.watchpoints);
+        arm32cpu_memory.fetch_address = pc;
+        if !(self.cpu.step(&mut arm32cpu_memory)) {
+            return Err(WieError::FatalError("Undefined instruction".into()));
+        }
+
+        if let Some((address, access)) = arm32cpu_memory.memory_error() {
+            // leave pc at the faulting instruction
+            self.cpu.reg_set(Mode::User, reg::PC, pc);
+
+            return Err(guest_fault(address, access, pc));
+        }
+
+        // watchpoints stop after the accessing instruction, like hardware ones
+        if let Some(watchpoint) = arm32cpu_memory.watch_hit {
+            self.watch_hit = Some(watchpoint);
+        }
+
+        Ok(())
     }
 
     fn is_svc_exception(&self) -> bool {
@@ -28,17 +175,31 @@ impl Arm32CpuEngine {
         let lr = self.cpu.reg_get(Mode::Supervisor, reg::LR);
         let spsr = self.cpu.reg_get(Mode::Supervisor, reg::SPSR);
 
-        let svc_address = lr.checked_sub(2).ok_or(WieError::InvalidMemoryAccess(lr))?;
-        let mut svc_bytes = [0u8; 2];
-        self.mem.read_range(svc_address, 2, &mut svc_bytes)?;
-        let instruction = u16::from_le_bytes(svc_bytes);
-        if instruction & 0xff00 != 0xdf00 {
-            return Err(WieError::FatalError(format!(
-                "Invalid Thumb SVC instruction {instruction:#06x} at {svc_address:#x}"
-            )));
-        }
+        let category = if spsr & (1 << 5) != 0 {
+            let svc_address = lr.checked_sub(2).ok_or(WieError::InvalidMemoryAccess(lr))?;
+            let mut svc_bytes = [0u8; 2];
+            self.mem.read_range(svc_address, 2, &mut svc_bytes)?;
+            let instruction = u16::from_le_bytes(svc_bytes);
+            if instruction & 0xff00 != 0xdf00 {
+                return Err(WieError::FatalError(format!(
+                    "Invalid Thumb SVC instruction {instruction:#06x} at {svc_address:#x}"
+                )));
+            }
 
-        let category = instruction as u32 & 0xff;
+            instruction as u32 & 0xff
+        } else {
+            let svc_address = lr.checked_sub(4).ok_or(WieError::InvalidMemoryAccess(lr))?;
+            let mut svc_bytes = [0u8; 4];
+            self.mem.read_range(svc_address, 4, &mut svc_bytes)?;
+            let instruction = u32::from_le_bytes(svc_bytes);
+            if instruction & 0x0f00_0000 != 0x0f00_0000 {
+                return Err(WieError::FatalError(format!(
+                    "Invalid ARM SVC instruction {instruction:#010x} at {svc_address:#x}"
+                )));
+            }
+
+            instruction & 0x00ff_ffff
+        };
 
         Ok(EngineRunResult::Svc { category, lr, spsr })
     }
@@ -54,7 +215,7 @@ impl ArmEngine for Arm32CpuEngine {
             }
 
             if pc < 0x1000 {
-                return Err(WieError::InvalidMemoryAccess(pc));
+                return Err(guest_fault(pc, MemoryAccessKind::Execute, pc));
             }
 
             if pc == end {
@@ -65,15 +226,15 @@ impl ArmEngine for Arm32CpuEngine {
                 return Ok(EngineRunResult::CountExhausted);
             }
 
-            let mut arm32cpu_memory = self.mem.as_arm32cpu_memory();
-
-            if !(self.cpu.step(&mut arm32cpu_memory)) {
-                return Err(WieError::FatalError("Undefined instruction".into()));
+            if self.is_traced(pc) {
+                self.run_traced(pc)?;
+            } else {
+                self.step(pc)?;
             }
             count -= 1;
 
-            if let Some(x) = arm32cpu_memory.memory_error() {
-                return Err(WieError::InvalidMemoryAccess(x));
+            if self.watch_hit.is_some() {
+                return Ok(EngineRunResult::CountExhausted);
             }
         }
     }
@@ -94,8 +255,12 @@ impl ArmEngine for Arm32CpuEngine {
         self.cpu.reg_get(Mode::User, reg.into_armv4t())
     }
 
-    fn mem_map(&mut self, address: u32, size: usize, _permission: MemoryPermission) {
-        self.mem.map(address, size);
+    fn mem_map(&mut self, address: u32, size: usize, permission: MemoryPermission) {
+        self.mem.map(address, size, permission as u8);
+    }
+
+    fn mem_protect(&mut self, address: u32, size: usize, permission: MemoryPermission) {
+        self.mem.protect(address, size, permission as u8);
     }
 
     fn mem_write(&mut self, address: u32, data: &[u8]) -> Result<()> {
@@ -109,6 +274,14 @@ impl ArmEngine for Arm32CpuEngine {
     fn is_mapped(&self, address: u32, size: usize) -> bool {
         self.mem.is_mapped(address, size)
     }
+
+    fn set_trace_ranges(&mut self, ranges: Vec<Range<u32>>) {
+        self.trace_ranges = ranges;
+    }
+
+    fn take_trace(&mut self) -> Vec<TraceEvent> {
+        core::mem::take(&mut self.trace)
+    }
 }
 
 impl ArmRegister {
@@ -135,12 +308,41 @@ impl ArmRegister {
     }
 }
 
+// thread id and call stack are filled in by the core, which knows about them
+fn guest_fault(address: u32, access: MemoryAccessKind, pc: u32) -> WieError {
+    WieError::GuestFault(Box::new(GuestFault {
+        address,
+        access,
+        pc,
+        thread_id: None,
+        call_stack: String::new(),
+    }))
+}
+
 const TOTAL_MEMORY: u64 = 0x100000000;
 const PAGE_SIZE: usize = 0x10000;
 const PAGE_MASK: u32 = (PAGE_SIZE - 1) as _;
+// permissions are tracked in smaller units than pages, so that sections of an image can differ
+const PERMISSION_GRANULE: usize = 0x1000;
+
+// same bits as `MemoryPermission`
+const PERMISSION_READ: u8 = 4;
+const PERMISSION_WRITE: u8 = 2;
+const PERMISSION_EXECUTE: u8 = 1;
+
+struct Page {
+    data: [u8; PAGE_SIZE],
+    permissions: [u8; PAGE_SIZE / PERMISSION_GRANULE],
+}
+
+impl Page {
+    fn permission(&self, address: u32) -> u8 {
+        self.permissions[(address & PAGE_MASK) as usize / PERMISSION_GRANULE]
+    }
+}
 
 struct EmulatedMemory {
-    pages: Box<[Option<Box<[u8; PAGE_SIZE]>>]>,
+    pages: Box<[Option<Box<Page>>]>,
 }
 
 impl EmulatedMemory {
@@ -154,18 +356,45 @@ impl EmulatedMemory {
         Arm32CpuMemory::new(self)
     }
 
-    fn map(&mut self, address: u32, size: usize) {
-        let page_start = address & !PAGE_MASK;
-        let page_end = (address + size as u32 + PAGE_MASK) & !PAGE_MASK;
+    // mapping only adds permissions; a granule shared by two mappings gets both
+    fn map(&mut self, address: u32, size: usize, permission: u8) {
+        let granule_mask = PERMISSION_GRANULE as u32 - 1;
+        let granule_start = address & !granule_mask;
+        let granule_end = (address + size as u32 + granule_mask) & !granule_mask;
+
+        for granule in (granule_start..granule_end).step_by(PERMISSION_GRANULE) {
+            let page = self.pages[granule as usize / PAGE_SIZE].get_or_insert_with(|| {
+                Box::new(Page {
+                    data: [0; PAGE_SIZE],
+                    permissions: [0; PAGE_SIZE / PERMISSION_GRANULE],
+                })
+            });
+            page.permissions[(granule & PAGE_MASK) as usize / PERMISSION_GRANULE] |= permission;
+        }
+    }
 
-        for page in (page_start..page_end).step_by(PAGE_SIZE) {
-            let page_data = &mut self.pages[page as usize / PAGE_SIZE];
-            if page_data.is_none() {
-                *page_data = Some(Box::new([0; PAGE_SIZE]));
+    // granules that aren't mapped stay unmapped
+    fn protect(&mut self, address: u32, size: usize, permission: u8) {
+        let granule_mask = PERMISSION_GRANULE as u32 - 1;
+        let granule_start = address & !granule_mask;
+        let granule_end = (address + size as u32 + granule_mask) & !granule_mask;
+
+        for granule in (granule_start..granule_end).step_by(PERMISSION_GRANULE) {
+            if let Some(page) = self.pages[granule as usize / PAGE_SIZE].as_mut() {
+                let granule_permission = &mut page.permissions[(granule & PAGE_MASK) as usize / PERMISSION_GRANULE];
+                if *granule_permission != 0 {
+                    *granule_permission = permission;
+                }
             }
         }
     }
 
+    fn has_permission(&self, address: u32, permission: u8) -> bool {
+        self.pages[address as usize / PAGE_SIZE]
+            .as_ref()
+            .is_some_and(|x| x.permission(address) & permission != 0)
+    }
+
     fn read_range(&self, address: u32, size: usize, result: &mut [u8]) -> Result<usize> {
         let mut remaining_size = size;
         let mut current_address = address;
@@ -178,7 +407,7 @@ impl EmulatedMemory {
             let offset = (current_address - page_address) as usize;
             let available_bytes = (PAGE_SIZE - offset).min(remaining_size);
 
-            result[size - remaining_size..size - remaining_size + available_bytes].copy_from_slice(&page_data[offset..offset + available_bytes]);
+            result[size - remaining_size..size - remaining_size + available_bytes].copy_from_slice(&page_data.data[offset..offset + available_bytes]);
             remaining_size -= available_bytes;
             current_address += available_bytes as u32;
         }
@@ -198,7 +427,7 @@ impl EmulatedMemory {
             let offset = (current_address - page_address) as usize;
             let available_bytes = (PAGE_SIZE - offset).min(data.len() - data_index);
 
-            page_data[offset..offset + available_bytes].copy_from_slice(&data[data_index..data_index + available_bytes]);
+            page_data.data[offset..offset + available_bytes].copy_from_slice(&data[data_index..data_index + available_bytes]);
             data_index += available_bytes;
             current_address += available_bytes as u32;
         }
@@ -207,26 +436,23 @@ impl EmulatedMemory {
     }
 
     fn is_mapped(&self, address: u32, size: usize) -> bool {
-        let page_start = address & !PAGE_MASK;
-        let page_end = (address + size as u32 + PAGE_MASK) & !PAGE_MASK;
+        let granule_mask = PERMISSION_GRANULE as u32 - 1;
+        let granule_start = address & !granule_mask;
+        let granule_end = (address as u64 + size.max(1) as u64 + granule_mask as u64) & !(granule_mask as u64);
 
-        if self.pages[page_start as usize / PAGE_SIZE].is_none() {
-            return false;
-        }
-
-        for page in (page_start..page_end).step_by(PAGE_SIZE) {
-            if self.pages[page as usize / PAGE_SIZE].is_none() {
-                return false;
-            }
-        }
-
-        true
+        (granule_start as u64..granule_end)
+            .step_by(PERMISSION_GRANULE)
+            .all(|granule| self.has_permission(granule as u32, PERMISSION_READ))
     }
 }
 
 struct Arm32CpuMemory<'a> {
     emulated_memory: &'a mut EmulatedMemory,
-    memory_error: RefCell<Option<u32>>,
+    memory_error: RefCell<Option<(u32, MemoryAccessKind)>>,
+    watchpoints: &'a [Watchpoint],
+    watch_hit: Option<Watchpoint>,
+    // reads of the instruction being executed are fetches, which don't trigger watchpoints
+    fetch_address: u32,
 }
 
 impl<'a> Arm32CpuMemory<'a> {
@@ -234,31 +460,56 @@ impl<'a> Arm32CpuMemory<'a> {
         Self {
             emulated_memory,
             memory_error: RefCell::new(None),
+            watchpoints: &[],
+            watch_hit: None,
+            fetch_address: 0,
         }
     }
 
-    fn memory_error(&self) -> Option<u32> {
+    fn with_watchpoints(self, watchpoints: &'a [Watchpoint]) -> Self {
+        Self { watchpoints, ..self }
+    }
+
+    fn check_watchpoints(&mut self, addr: u32, size: u32, write: bool) {
+        if self.watchpoints.is_empty() || (!write && (self.fetch_address..self.fetch_address.saturating_add(4)).contains(&addr)) {
+            return;
+        }
+
+        if let Some(watchpoint) = self.watchpoints.iter().find(|x| x.matches(addr, size, write)) {
+            self.watch_hit.get_or_insert(*watchpoint);
+        }
+    }
+
+    fn memory_error(&self) -> Option<(u32, MemoryAccessKind)> {
         *self.memory_error.borrow()
     }
 
-    fn get_page(&mut self, addr: u32) -> Option<&mut [u8; PAGE_SIZE]> {
+    fn get_page(&mut self, addr: u32, access: MemoryAccessKind) -> Option<&mut [u8; PAGE_SIZE]> {
+        let permission = match access {
+            MemoryAccessKind::Read => PERMISSION_READ,
+            MemoryAccessKind::Write => PERMISSION_WRITE,
+            MemoryAccessKind::Execute => PERMISSION_EXECUTE,
+        };
+
         let page_address = addr & !PAGE_MASK;
         let page_data = self.emulated_memory.pages[page_address as usize / PAGE_SIZE].as_mut();
 
-        if let Some(x) = page_data {
-            Some(x)
-        } else {
-            *self.memory_error.borrow_mut() = Some(addr);
-            None
+        match page_data {
+            Some(x) if x.permission(addr) & permission != 0 => Some(&mut x.data),
+            _ => {
+                *self.memory_error.borrow_mut() = Some((addr, access));
+                None
+            }
         }
     }
 }
 
 impl Memory for Arm32CpuMemory<'_> {
     fn r8(&mut self, addr: u32) -> u8 {
+        self.check_watchpoints(addr, 1, false);
         let offset = addr & PAGE_MASK;
 
-        let page = self.get_page(addr);
+        let page = self.get_page(addr, MemoryAccessKind::Read);
         if page.is_none() {
             return 0;
         }
@@ -269,9 +520,10 @@ impl Memory for Arm32CpuMemory<'_> {
     }
 
     fn r16(&mut self, addr: u32) -> u16 {
+        self.check_watchpoints(addr, 2, false);
         let offset = addr & PAGE_MASK;
 
-        let page = self.get_page(addr);
+        let page = self.get_page(addr, MemoryAccessKind::Read);
         if page.is_none() {
             return 0;
         }
@@ -282,9 +534,10 @@ impl Memory for Arm32CpuMemory<'_> {
     }
 
     fn r32(&mut self, addr: u32) -> u32 {
+        self.check_watchpoints(addr, 4, false);
         let offset = addr & PAGE_MASK;
 
-        let page = self.get_page(addr);
+        let page = self.get_page(addr, MemoryAccessKind::Read);
         if page.is_none() {
             return 0;
         }
@@ -297,9 +550,10 @@ impl Memory for Arm32CpuMemory<'_> {
     }
 
     fn w8(&mut self, addr: u32, val: u8) {
+        self.check_watchpoints(addr, 1, true);
         let offset = addr & PAGE_MASK;
 
-        let page = self.get_page(addr);
+        let page = self.get_page(addr, MemoryAccessKind::Write);
         if page.is_none() {
             return;
         }
@@ -310,9 +564,10 @@ impl Memory for Arm32CpuMemory<'_> {
     }
 
     fn w16(&mut self, addr: u32, val: u16) {
+        self.check_watchpoints(addr, 2, true);
         let offset = addr & PAGE_MASK;
 
-        let page = self.get_page(addr);
+        let page = self.get_page(addr, MemoryAccessKind::Write);
         if page.is_none() {
             return;
         }
@@ -324,9 +579,10 @@ impl Memory for Arm32CpuMemory<'_> {
     }
 
     fn w32(&mut self, addr: u32, val: u32) {
+        self.check_watchpoints(addr, 4, true);
         let offset = addr & PAGE_MASK;
 
-        let page = self.get_page(addr);
+        let page = self.get_page(addr, MemoryAccessKind::Write);
         if page.is_none() {
             return;
         }
@@ -347,20 +603,24 @@ mod tests {
 
     use arm32_cpu::Memory;
 
-    use super::EmulatedMemory;
+    use wie_util::{MemoryAccessKind, WieError};
+
+    use crate::engine::{ArmEngine, ArmRegister, EngineRunResult, MemoryPermission};
+
+    use super::{Arm32CpuEngine, EmulatedMemory, PERMISSION_READ, PERMISSION_WRITE, WatchKind, Watchpoint};
 
     #[test]
     fn page_table_is_heap_allocated() {
-        assert_eq!(size_of::<EmulatedMemory>(), size_of::<Box<[Option<Box<[u8; super::PAGE_SIZE]>>]>>());
+        assert_eq!(size_of::<EmulatedMemory>(), size_of::<Box<[Option<Box<super::Page>>]>>());
     }
 
     #[test]
     fn test_memory_basic() {
         let mut memory = EmulatedMemory::new();
 
-        memory.map(0x10000, 0x1000);
-        memory.map(0x11000, 0x1000);
-        memory.map(0x20000, 0x10000);
+        memory.map(0x10000, 0x1000, PERMISSION_READ | PERMISSION_WRITE);
+        memory.map(0x11000, 0x1000, PERMISSION_READ | PERMISSION_WRITE);
+        memory.map(0x20000, 0x10000, PERMISSION_READ | PERMISSION_WRITE);
 
         memory.write_range(0x10000, &[123; 0x1000]).unwrap();
 
@@ -401,7 +661,7 @@ mod tests {
     fn test_memory_unmapped_read() {
         let mut memory = EmulatedMemory::new();
 
-        memory.map(0x10000, 0x10000);
+        memory.map(0x10000, 0x10000, PERMISSION_READ | PERMISSION_WRITE);
 
         let mut buf = [0; 0x1000];
         assert!(memory.read_range(0x1f500, 0x1000, &mut buf).is_err());
@@ -411,8 +671,95 @@ mod tests {
     fn test_memory_unmapped_write() {
         let mut memory = EmulatedMemory::new();
 
-        memory.map(0x10000, 0x10000);
+        memory.map(0x10000, 0x10000, PERMISSION_READ | PERMISSION_WRITE);
 
         assert!(memory.write_range(0x1f500, &[12; 0x1000]).is_err());
     }
+
+    #[test]
+    fn test_permissions_fault_at_the_instruction() {
+        let mut engine = Arm32CpuEngine::new();
+        engine.mem_map(0x10000, 0x1000, MemoryPermission::ReadExecute);
+        engine.mem_map(0x11000, 0x1000, MemoryPermission::ReadWrite);
+
+        // movs r0, #1; str r0, [r1]
+        engine.mem_write(0x10000, &[0x01, 0x20, 0x08, 0x60]).unwrap();
+        engine.mem_write(0x11000, &[0x01, 0x20]).unwrap();
+
+        // writing into code
+        engine.reg_write(ArmRegister::R1, 0x10000);
+        engine.reg_write(ArmRegister::PC, 0x10001);
+        let Err(WieError::GuestFault(fault)) = engine.run(0, 100) else {
+            panic!()
+        };
+        assert_eq!((fault.address, fault.access, fault.pc), (0x10000, MemoryAccessKind::Write, 0x10002));
+        assert_eq!(engine.reg_read(ArmRegister::PC), 0x10002);
+
+        let mut buf = [0; 2];
+        engine.mem_read(0x10000, 2, &mut buf).unwrap();
+        assert_eq!(buf, [0x01, 0x20]);
+
+        // writing outside of the mappings
+        engine.reg_write(ArmRegister::R1, 0x12000);
+        engine.reg_write(ArmRegister::PC, 0x10003);
+        let Err(WieError::GuestFault(fault)) = engine.run(0, 100) else {
+            panic!()
+        };
+        assert_eq!((fault.address, fault.access), (0x12000, MemoryAccessKind::Write));
+        assert!(!engine.is_mapped(0x12000, 4));
+
+        // executing data
+        engine.reg_write(ArmRegister::PC, 0x11001);
+        let Err(WieError::GuestFault(fault)) = engine.run(0, 100) else {
+            panic!()
+        };
+        assert_eq!((fault.address, fault.access, fault.pc), (0x11000, MemoryAccessKind::Execute, 0x11000));
+    }
+
+    #[test]
+    fn test_watchpoints_stop_after_the_access() {
+        let mut engine = Arm32CpuEngine::new();
+        engine.mem_map(0x10000, 0x1000, MemoryPermission::ReadExecute);
+        engine.mem_map(0x11000, 0x1000, MemoryPermission::ReadWrite);
+
+        // movs r0, #1; str r0, [r1]; ldr r2, [r1]; b .
+        engine.mem_write(0x10000, &[0x01, 0x20, 0x08, 0x60, 0x0a, 0x68, 0xfe, 0xe7]).unwrap();
+        engine.reg_write(ArmRegister::R1, 0x11004);
+        engine.reg_write(ArmRegister::PC, 0x10001);
+
+        let read = Watchpoint {
+            address: 0x11004,
+            len: 4,
+            kind: WatchKind::Read,
+        };
+        let write = Watchpoint {
+            kind: WatchKind::Write,
+            ..read
+        };
+        engine.add_watchpoint(read);
+        engine.add_watchpoint(write);
+
+        assert!(matches!(engine.run(0, 100).unwrap(), EngineRunResult::CountExhausted));
+        assert_eq!(engine.take_watch_hit(), Some(write));
+        assert_eq!(engine.reg_read(ArmRegister::PC), 0x10004);
+
+        let mut buf = [0; 4];
+        engine.mem_read(0x11004, 4, &mut buf).unwrap();
+        assert_eq!(buf, [1, 0, 0, 0]);
+
+        assert!(matches!(engine.run(0, 100).unwrap(), EngineRunResult::CountExhausted));
+        assert_eq!(engine.take_watch_hit(), Some(read));
+        assert_eq!(engine.reg_read(ArmRegister::R2), 1);
+
+        // instruction fetches don't count as reads
+        assert!(engine.remove_watchpoint(write));
+        assert!(engine.remove_watchpoint(read));
+        engine.add_watchpoint(Watchpoint {
+            address: 0x10006,
+            len: 2,
+            ..read
+        });
+        assert!(matches!(engine.run(0, 100).unwrap(), EngineRunResult::CountExhausted));
+        assert_eq!(engine.take_watch_hit(), None);
+    }
 }
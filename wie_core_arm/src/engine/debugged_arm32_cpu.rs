@@ -1,13 +1,19 @@
-use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
-use core::time::Duration;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{ops::Range, time::Duration};
 
 use crossbeam::channel;
 use spin::Mutex;
-use wie_util::WieError;
+use wie_backend::TraceEvent;
+use wie_util::{ByteRead, WieError};
 
 use crate::{ThreadId, context::ArmCoreContext};
 
-use super::{Arm32CpuEngine, ArmEngine, ArmRegister, EngineRunResult, MemoryPermission};
+use super::{Arm32CpuEngine, ArmEngine, ArmRegister, EngineRunResult, MemoryPermission, Watchpoint};
 
 #[derive(Copy, Clone)]
 enum ResumeMode {
@@ -34,6 +40,7 @@ pub(crate) enum DebugSignal {
 pub(crate) enum DebugStopReason {
     Signal(DebugSignal),
     SwBreak(ThreadId),
+    Watch(ThreadId, Watchpoint),
 }
 
 #[derive(Copy, Clone)]
@@ -118,6 +125,9 @@ pub(crate) struct DebugInner {
     resume_rx: channel::Receiver<ResumeMode>,
     interrupt_pending: Mutex<bool>,
     breakpoints: Mutex<BTreeMap<u32, SoftwareBreakpoint>>,
+    // kept here for monitor commands, as the core is locked while the target is stopped
+    svc_handlers: Mutex<BTreeMap<u32, String>>,
+    loaded_classes: Mutex<BTreeMap<u32, String>>,
     active_threads: Mutex<Vec<ThreadId>>,
     current_thread: Mutex<Option<ThreadId>>,
     thread_ready_tx: channel::Sender<()>,
@@ -138,6 +148,8 @@ impl DebugInner {
             resume_rx,
             interrupt_pending: Mutex::new(false),
             breakpoints: Mutex::new(BTreeMap::new()),
+            svc_handlers: Mutex::new(BTreeMap::new()),
+            loaded_classes: Mutex::new(BTreeMap::new()),
             active_threads: Mutex::new(Vec::new()),
             current_thread: Mutex::new(None),
             thread_ready_tx,
@@ -280,6 +292,30 @@ impl DebugInner {
         Ok(())
     }
 
+    pub(crate) fn add_watchpoint(&self, watchpoint: Watchpoint) {
+        self.cpu.lock().add_watchpoint(watchpoint);
+    }
+
+    pub(crate) fn remove_watchpoint(&self, watchpoint: Watchpoint) -> bool {
+        self.cpu.lock().remove_watchpoint(watchpoint)
+    }
+
+    pub(crate) fn on_svc_handler_registered(&self, category: u32, name: &str) {
+        self.svc_handlers.lock().insert(category, name.to_string());
+    }
+
+    pub(crate) fn svc_handlers(&self) -> BTreeMap<u32, String> {
+        self.svc_handlers.lock().clone()
+    }
+
+    pub(crate) fn on_class_loaded(&self, address: u32, name: &str) {
+        self.loaded_classes.lock().insert(address, name.to_string());
+    }
+
+    pub(crate) fn loaded_classes(&self) -> BTreeMap<u32, String> {
+        self.loaded_classes.lock().clone()
+    }
+
     pub(crate) fn active_threads(&self) -> Vec<ThreadId> {
         self.active_threads.lock().clone()
     }
@@ -372,6 +408,7 @@ impl DebugInner {
         match error {
             WieError::AllocationFailure => DebugStopReason::Signal(DebugSignal::Kill),
             WieError::InvalidMemoryAccess(_) => DebugStopReason::Signal(DebugSignal::Segv),
+            WieError::GuestFault(_) => DebugStopReason::Signal(DebugSignal::Segv),
             WieError::Unimplemented(_) => DebugStopReason::Signal(DebugSignal::Sys),
             WieError::JavaException(_) => DebugStopReason::Signal(DebugSignal::Trap),
             WieError::JavaExceptionUnwind { .. } => DebugStopReason::Signal(DebugSignal::Trap),
@@ -380,6 +417,12 @@ impl DebugInner {
     }
 }
 
+impl ByteRead for DebugInner {
+    fn read_bytes(&self, address: u32, result: &mut [u8]) -> wie_util::Result<usize> {
+        self.read_memory(address, result)
+    }
+}
+
 pub struct DebuggedArm32CpuEngine {
     debug: Arc<DebugInner>,
     run_state: RunState,
@@ -428,6 +471,14 @@ impl DebuggedArm32CpuEngine {
             return Err(error);
         }
 
+        let watch_hit = self.debug.cpu.lock().take_watch_hit();
+        if let Some(watchpoint) = watch_hit
+            && result.is_ok()
+        {
+            self.stop(DebugStopReason::Watch(self.stop_thread_id(), watchpoint));
+            return Ok(());
+        }
+
         match result {
             Ok(EngineRunResult::Svc { .. }) => {}
             Ok(_) if matches!(resume_mode, ResumeMode::Continue) => {}
@@ -482,6 +533,14 @@ impl ArmEngine for DebuggedArm32CpuEngine {
 
             let result = self.debug.cpu.lock().run(end, run_count);
 
+            let watch_hit = self.debug.cpu.lock().take_watch_hit();
+            if let Some(watchpoint) = watch_hit
+                && result.is_ok()
+            {
+                self.stop(DebugStopReason::Watch(self.stop_thread_id(), watchpoint));
+                continue;
+            }
+
             match result {
                 Ok(result @ EngineRunResult::Svc { .. }) => return Ok(result),
                 Ok(result) => match resume_mode {
@@ -505,6 +564,10 @@ impl ArmEngine for DebuggedArm32CpuEngine {
         self.debug.cpu.lock().mem_map(address, size, permission)
     }
 
+    fn mem_protect(&mut self, address: u32, size: usize, permission: MemoryPermission) {
+        self.debug.cpu.lock().mem_protect(address, size, permission)
+    }
+
     fn mem_write(&mut self, address: u32, data: &[u8]) -> wie_util::Result<()> {
         self.debug.cpu.lock().mem_write(address, data)
     }
@@ -516,6 +579,14 @@ impl ArmEngine for DebuggedArm32CpuEngine {
     fn is_mapped(&self, address: u32, size: usize) -> bool {
         self.debug.cpu.lock().is_mapped(address, size)
     }
+
+    fn set_trace_ranges(&mut self, ranges: Vec<Range<u32>>) {
+        self.debug.cpu.lock().set_trace_ranges(ranges)
+    }
+
+    fn take_trace(&mut self) -> Vec<TraceEvent> {
+        self.debug.cpu.lock().take_trace()
+    }
 }
 
 #[cfg(test)]
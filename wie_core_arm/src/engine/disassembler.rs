@@ -0,0 +1,288 @@
+use alloc::{format, string::String, vec::Vec};
+
+// a small ARMv4T disassembler for instruction traces. it covers what the guest binaries use;
+// anything else is printed as a raw word.
+
+pub const REGISTERS: [&str; 16] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "sb", "sl", "fp", "ip", "sp", "lr", "pc",
+];
+const CONDITIONS: [&str; 16] = [
+    "eq", "ne", "cs", "cc", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le", "", "nv",
+];
+const SHIFTS: [&str; 4] = ["lsl", "lsr", "asr", "ror"];
+
+fn reg(index: u32) -> &'static str {
+    REGISTERS[(index & 0xf) as usize]
+}
+
+fn reg_list(mask: u32, extra: Option<&str>) -> String {
+    let mut regs = (0..16).filter(|x| mask & (1 << x) != 0).map(reg).collect::<Vec<_>>();
+    if let Some(extra) = extra {
+        regs.push(extra);
+    }
+
+    format!("{{{}}}", regs.join(", "))
+}
+
+/// Disassembles the thumb instruction at `pc`. `next` is the following halfword, used to decode `bl` pairs.
+pub fn disassemble_thumb(pc: u32, instruction: u16, next: u16) -> String {
+    let ins = instruction as u32;
+    let low3 = |shift: u32| reg((ins >> shift) & 7);
+
+    match ins >> 11 {
+        0b00000..=0b00010 => {
+            let op = ["lsls", "lsrs", "asrs"][(ins >> 11) as usize];
+            format!("{op} {}, {}, #{:#x}", low3(0), low3(3), (ins >> 6) & 0x1f)
+        }
+        0b00011 => {
+            let op = if ins & (1 << 9) != 0 { "subs" } else { "adds" };
+            if ins & (1 << 10) != 0 {
+                format!("{op} {}, {}, #{:#x}", low3(0), low3(3), (ins >> 6) & 7)
+            } else {
+                format!("{op} {}, {}, {}", low3(0), low3(3), low3(6))
+            }
+        }
+        0b00100..=0b00111 => {
+            let op = ["movs", "cmp", "adds", "subs"][((ins >> 11) & 3) as usize];
+            format!("{op} {}, #{:#x}", low3(8), ins & 0xff)
+        }
+        0b01000 if ins & (1 << 10) == 0 => {
+            let op = [
+                "ands", "eors", "lsls", "lsrs", "asrs", "adcs", "sbcs", "rors", "tst", "negs", "cmp", "cmn", "orrs", "muls", "bics", "mvns",
+            ][((ins >> 6) & 0xf) as usize];
+            format!("{op} {}, {}", low3(0), low3(3))
+        }
+        0b01000 => {
+            let rd = (ins & 7) | ((ins >> 4) & 8);
+            let rm = (ins >> 3) & 0xf;
+            match (ins >> 8) & 3 {
+                0 => format!("add {}, {}", reg(rd), reg(rm)),
+                1 => format!("cmp {}, {}", reg(rd), reg(rm)),
+                2 => format!("mov {}, {}", reg(rd), reg(rm)),
+                _ if ins & (1 << 7) != 0 => format!("blx {}", reg(rm)),
+                _ => format!("bx {}", reg(rm)),
+            }
+        }
+        0b01001 => {
+            let target = (pc.wrapping_add(4) & !3).wrapping_add((ins & 0xff) << 2);
+            format!("ldr {}, [pc, #{:#x}] ; {target:#x}", low3(8), (ins & 0xff) << 2)
+        }
+        0b01010 | 0b01011 => {
+            let op = ["str", "strh", "strb", "ldrsb", "ldr", "ldrh", "ldrb", "ldrsh"][((ins >> 9) & 7) as usize];
+            format!("{op} {}, [{}, {}]", low3(0), low3(3), low3(6))
+        }
+        0b01100..=0b10001 => {
+            let (op, scale) = match ins >> 11 {
+                0b01100 => ("str", 4),
+                0b01101 => ("ldr", 4),
+                0b01110 => ("strb", 1),
+                0b01111 => ("ldrb", 1),
+                0b10000 => ("strh", 2),
+                _ => ("ldrh", 2),
+            };
+            format!("{op} {}, [{}, #{:#x}]", low3(0), low3(3), ((ins >> 6) & 0x1f) * scale)
+        }
+        0b10010 | 0b10011 => {
+            let op = if ins & (1 << 11) != 0 { "ldr" } else { "str" };
+            format!("{op} {}, [sp, #{:#x}]", low3(8), (ins & 0xff) << 2)
+        }
+        0b10100 => format!("add {}, pc, #{:#x}", low3(8), (ins & 0xff) << 2),
+        0b10101 => format!("add {}, sp, #{:#x}", low3(8), (ins & 0xff) << 2),
+        0b10110 | 0b10111 => match (ins >> 8) & 0xf {
+            0b0000 => {
+                let op = if ins & (1 << 7) != 0 { "sub" } else { "add" };
+                format!("{op} sp, #{:#x}", (ins & 0x7f) << 2)
+            }
+            0b0100 | 0b0101 => format!("push {}", reg_list(ins & 0xff, (ins & (1 << 8) != 0).then_some("lr"))),
+            0b1100 | 0b1101 => format!("pop {}", reg_list(ins & 0xff, (ins & (1 << 8) != 0).then_some("pc"))),
+            0b1110 => format!("bkpt #{:#x}", ins & 0xff),
+            _ => format!(".hword {ins:#06x}"),
+        },
+        0b11000 => format!("stmia {}!, {}", low3(8), reg_list(ins & 0xff, None)),
+        0b11001 => format!("ldmia {}!, {}", low3(8), reg_list(ins & 0xff, None)),
+        0b11010 | 0b11011 => match (ins >> 8) & 0xf {
+            0xf => format!("svc #{:#x}", ins & 0xff),
+            0xe => format!(".hword {ins:#06x}"),
+            cond => {
+                let offset = ((ins & 0xff) as i8 as i32) << 1;
+                format!("b{} {:#x}", CONDITIONS[cond as usize], pc.wrapping_add(4).wrapping_add(offset as u32))
+            }
+        },
+        0b11100 => {
+            let offset = (((ins & 0x7ff) << 21) as i32) >> 20;
+            format!("b {:#x}", pc.wrapping_add(4).wrapping_add(offset as u32))
+        }
+        0b11110 => {
+            let high = (((ins & 0x7ff) << 21) as i32) >> 9;
+            let next = next as u32;
+            if next >> 11 == 0b11111 || next >> 11 == 0b11101 {
+                let target = pc.wrapping_add(4).wrapping_add(high as u32).wrapping_add((next & 0x7ff) << 1);
+                if next >> 11 == 0b11101 {
+                    format!("blx {:#x}", target & !3)
+                } else {
+                    format!("bl {target:#x}")
+                }
+            } else {
+                format!("bl.prefix #{high:#x}")
+            }
+        }
+        0b11111 => format!("bl.suffix #{:#x}", (ins & 0x7ff) << 1),
+        _ => format!(".hword {ins:#06x}"),
+    }
+}
+
+fn arm_operand2(ins: u32) -> String {
+    if ins & (1 << 25) != 0 {
+        let rotate = ((ins >> 8) & 0xf) * 2;
+        return format!("#{:#x}", (ins & 0xff).rotate_right(rotate));
+    }
+
+    let rm = reg(ins & 0xf);
+    let shift = SHIFTS[((ins >> 5) & 3) as usize];
+    if ins & (1 << 4) != 0 {
+        format!("{rm}, {shift} {}", reg(ins >> 8))
+    } else {
+        match (ins >> 7) & 0x1f {
+            0 if (ins >> 5) & 3 == 0 => String::from(rm),
+            0 if (ins >> 5) & 3 == 3 => format!("{rm}, rrx"),
+            0 => format!("{rm}, {shift} #32"),
+            amount => format!("{rm}, {shift} #{amount}"),
+        }
+    }
+}
+
+/// Disassembles the arm instruction at `pc`.
+pub fn disassemble_arm(pc: u32, ins: u32) -> String {
+    let cond = ins >> 28;
+    if cond == 0xf {
+        return format!(".word {ins:#010x}");
+    }
+    let cc = CONDITIONS[cond as usize];
+    let rd = reg(ins >> 12);
+    let rn = reg(ins >> 16);
+    let writeback = if ins & (1 << 21) != 0 { "!" } else { "" };
+
+    if ins & 0x0ffffff0 == 0x012fff10 {
+        return format!("bx{cc} {}", reg(ins));
+    }
+    if ins & 0x0e000000 == 0x0a000000 {
+        let op = if ins & (1 << 24) != 0 { "bl" } else { "b" };
+        let offset = (((ins & 0xffffff) << 8) as i32) >> 6;
+        return format!("{op}{cc} {:#x}", pc.wrapping_add(8).wrapping_add(offset as u32));
+    }
+    if ins & 0x0f000000 == 0x0f000000 {
+        return format!("svc{cc} #{:#x}", ins & 0xffffff);
+    }
+    if ins & 0x0fb00ff0 == 0x01000090 {
+        let op = if ins & (1 << 22) != 0 { "swpb" } else { "swp" };
+        return format!("{op}{cc} {rd}, {}, [{rn}]", reg(ins));
+    }
+    if ins & 0x0fc000f0 == 0x00000090 {
+        let s = if ins & (1 << 20) != 0 { "s" } else { "" };
+        let (rd, rn) = (reg(ins >> 16), reg(ins >> 12));
+        return if ins & (1 << 21) != 0 {
+            format!("mla{cc}{s} {rd}, {}, {}, {rn}", reg(ins), reg(ins >> 8))
+        } else {
+            format!("mul{cc}{s} {rd}, {}, {}", reg(ins), reg(ins >> 8))
+        };
+    }
+    if ins & 0x0f8000f0 == 0x00800090 {
+        let op = ["umull", "umlal", "smull", "smlal"][((ins >> 21) & 3) as usize];
+        let s = if ins & (1 << 20) != 0 { "s" } else { "" };
+        return format!("{op}{cc}{s} {rd}, {}, {}, {}", reg(ins >> 16), reg(ins), reg(ins >> 8));
+    }
+    if ins & 0x0e000090 == 0x00000090 && ins & 0x60 != 0 {
+        let op = match ((ins >> 20) & 1, (ins >> 5) & 3) {
+            (0, _) => "strh",
+            (_, 1) => "ldrh",
+            (_, 2) => "ldrsb",
+            _ => "ldrsh",
+        };
+        let sign = if ins & (1 << 23) != 0 { "" } else { "-" };
+        let offset = if ins & (1 << 22) != 0 {
+            format!("#{sign}{:#x}", ((ins >> 4) & 0xf0) | (ins & 0xf))
+        } else {
+            format!("{sign}{}", reg(ins))
+        };
+        return if ins & (1 << 24) != 0 {
+            format!("{op}{cc} {rd}, [{rn}, {offset}]{writeback}")
+        } else {
+            format!("{op}{cc} {rd}, [{rn}], {offset}")
+        };
+    }
+    if ins & 0x0fbf0fff == 0x010f0000 {
+        let psr = if ins & (1 << 22) != 0 { "spsr" } else { "cpsr" };
+        return format!("mrs{cc} {rd}, {psr}");
+    }
+    if ins & 0x0db0f000 == 0x0120f000 {
+        let psr = if ins & (1 << 22) != 0 { "spsr" } else { "cpsr" };
+        return format!("msr{cc} {psr}, {}", arm_operand2(ins));
+    }
+    if ins & 0x0c000000 == 0 {
+        let opcode = (ins >> 21) & 0xf;
+        let op = [
+            "and", "eor", "sub", "rsb", "add", "adc", "sbc", "rsc", "tst", "teq", "cmp", "cmn", "orr", "mov", "bic", "mvn",
+        ][opcode as usize];
+        let s = if ins & (1 << 20) != 0 && !(8..12).contains(&opcode) { "s" } else { "" };
+        return match opcode {
+            8..=11 => format!("{op}{cc} {rn}, {}", arm_operand2(ins)),
+            13 | 15 => format!("{op}{cc}{s} {rd}, {}", arm_operand2(ins)),
+            _ => format!("{op}{cc}{s} {rd}, {rn}, {}", arm_operand2(ins)),
+        };
+    }
+    if ins & 0x0c000000 == 0x04000000 && ins & 0x02000010 != 0x02000010 {
+        let op = match ((ins >> 20) & 1, (ins >> 22) & 1) {
+            (0, 0) => "str",
+            (0, _) => "strb",
+            (_, 0) => "ldr",
+            _ => "ldrb",
+        };
+        let sign = if ins & (1 << 23) != 0 { "" } else { "-" };
+        let offset = if ins & (1 << 25) != 0 {
+            format!("{sign}{}", arm_operand2(ins & !(1 << 25)))
+        } else {
+            format!("#{sign}{:#x}", ins & 0xfff)
+        };
+        return if ins & (1 << 24) != 0 {
+            format!("{op}{cc} {rd}, [{rn}, {offset}]{writeback}")
+        } else {
+            format!("{op}{cc} {rd}, [{rn}], {offset}")
+        };
+    }
+    if ins & 0x0e000000 == 0x08000000 {
+        let op = if ins & (1 << 20) != 0 { "ldm" } else { "stm" };
+        let mode = ["da", "ia", "db", "ib"][((ins >> 23) & 3) as usize];
+        let user = if ins & (1 << 22) != 0 { "^" } else { "" };
+        return format!("{op}{mode}{cc} {rn}{writeback}, {}{user}", reg_list(ins & 0xffff, None));
+    }
+
+    format!(".word {ins:#010x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{disassemble_arm, disassemble_thumb};
+
+    #[test]
+    fn test_thumb() {
+        assert_eq!(disassemble_thumb(0x1000, 0x2001, 0), "movs r0, #0x1");
+        assert_eq!(disassemble_thumb(0x1000, 0xb510, 0), "push {r4, lr}");
+        assert_eq!(disassemble_thumb(0x1000, 0x4c02, 0), "ldr r4, [pc, #0x8] ; 0x100c");
+        assert_eq!(disassemble_thumb(0x1000, 0x4770, 0), "bx lr");
+        assert_eq!(disassemble_thumb(0x1000, 0xdf05, 0), "svc #0x5");
+        assert_eq!(disassemble_thumb(0x1000, 0xe7fe, 0), "b 0x1000");
+        assert_eq!(disassemble_thumb(0x1000, 0xd1fc, 0), "bne 0xffc");
+        assert_eq!(disassemble_thumb(0x1000, 0xf000, 0xf802), "bl 0x1008");
+        assert_eq!(disassemble_thumb(0x1000, 0x6848, 0), "ldr r0, [r1, #0x4]");
+    }
+
+    #[test]
+    fn test_arm() {
+        assert_eq!(disassemble_arm(0x1000, 0xe3a00001), "mov r0, #0x1");
+        assert_eq!(disassemble_arm(0x1000, 0xe12fff1e), "bx lr");
+        assert_eq!(disassemble_arm(0x1000, 0xebfffffe), "bl 0x1000");
+        assert_eq!(disassemble_arm(0x1000, 0xe92d4010), "stmdb sp!, {r4, lr}");
+        assert_eq!(disassemble_arm(0x1000, 0xe5910004), "ldr r0, [r1, #0x4]");
+        assert_eq!(disassemble_arm(0x1000, 0xe0810002), "add r0, r1, r2");
+    }
+}
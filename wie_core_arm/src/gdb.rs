@@ -1,6 +1,10 @@
 extern crate std; // we need thread
 
-use alloc::{format, sync::Arc};
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+};
 use std::{
     io,
     net::{TcpListener, TcpStream},
@@ -23,16 +27,17 @@ use gdbstub::{
             BaseOps,
             multithread::{MultiThreadBase, MultiThreadResume, MultiThreadResumeOps, MultiThreadSingleStep, MultiThreadSingleStepOps},
         },
-        ext::breakpoints::{Breakpoints, BreakpointsOps, SwBreakpoint, SwBreakpointOps},
+        ext::breakpoints::{Breakpoints, BreakpointsOps, HwWatchpoint, HwWatchpointOps, SwBreakpoint, SwBreakpointOps, WatchKind},
         ext::extended_mode::{Args, AttachKind, CurrentActivePid, CurrentActivePidOps, ExtendedMode, ExtendedModeOps, ShouldTerminate},
+        ext::monitor_cmd::{ConsoleOutput, MonitorCmd, MonitorCmdOps},
     },
 };
 use gdbstub_arch::arm::{ArmBreakpointKind, Armv4t, reg::ArmCoreRegs};
 
 use crate::{
-    ArmCore,
+    Allocator, ArmCore,
     context::ArmCoreContext,
-    engine::{DebugBreakpointKind, DebugInner, DebugSignal, DebugStopReason},
+    engine::{self, DebugBreakpointKind, DebugInner, DebugSignal, DebugStopReason, Watchpoint},
 };
 
 type GdbTargetError = &'static str;
@@ -51,9 +56,33 @@ fn to_gdb_stop_reason(reason: DebugStopReason) -> MultiThreadStopReason<u32> {
     match reason {
         DebugStopReason::Signal(signal) => MultiThreadStopReason::Signal(to_gdb_signal(signal)),
         DebugStopReason::SwBreak(thread_id) => MultiThreadStopReason::SwBreak(Tid::try_from(thread_id).unwrap()),
+        DebugStopReason::Watch(thread_id, watchpoint) => MultiThreadStopReason::Watch {
+            tid: Tid::try_from(thread_id).unwrap(),
+            kind: match watchpoint.kind {
+                engine::WatchKind::Read => WatchKind::Read,
+                engine::WatchKind::Write => WatchKind::Write,
+                engine::WatchKind::ReadWrite => WatchKind::ReadWrite,
+            },
+            addr: watchpoint.address,
+        },
     }
 }
 
+fn to_watchpoint(address: u32, len: u32, kind: WatchKind) -> Watchpoint {
+    let kind = match kind {
+        WatchKind::Read => engine::WatchKind::Read,
+        WatchKind::Write => engine::WatchKind::Write,
+        WatchKind::ReadWrite => engine::WatchKind::ReadWrite,
+    };
+
+    Watchpoint { address, len, kind }
+}
+
+const MONITOR_HELP: &str = "Commands:
+  classes  list guest java classes
+  svc      list registered SVC handlers
+  heap     list heap blocks in use";
+
 fn context_to_regs(ctx: &ArmCoreContext, regs: &mut ArmCoreRegs) {
     regs.r = [
         ctx.r0, ctx.r1, ctx.r2, ctx.r3, ctx.r4, ctx.r5, ctx.r6, ctx.r7, ctx.r8, ctx.sb, ctx.sl, ctx.fp, ctx.ip,
@@ -195,6 +224,11 @@ impl Target for GdbTarget {
     fn support_extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
         Some(self)
     }
+
+    #[inline(always)]
+    fn support_monitor_cmd(&mut self) -> Option<MonitorCmdOps<'_, Self>> {
+        Some(self)
+    }
 }
 
 impl MultiThreadBase for GdbTarget {
@@ -288,6 +322,10 @@ impl Breakpoints for GdbTarget {
     fn support_sw_breakpoint(&mut self) -> Option<SwBreakpointOps<'_, Self>> {
         Some(self)
     }
+
+    fn support_hw_watchpoint(&mut self) -> Option<HwWatchpointOps<'_, Self>> {
+        Some(self)
+    }
 }
 
 impl SwBreakpoint for GdbTarget {
@@ -310,6 +348,43 @@ impl SwBreakpoint for GdbTarget {
     }
 }
 
+impl HwWatchpoint for GdbTarget {
+    fn add_hw_watchpoint(&mut self, addr: u32, len: u32, kind: WatchKind) -> TargetResult<bool, Self> {
+        self.debug.add_watchpoint(to_watchpoint(addr, len, kind));
+
+        Ok(true)
+    }
+
+    fn remove_hw_watchpoint(&mut self, addr: u32, len: u32, kind: WatchKind) -> TargetResult<bool, Self> {
+        Ok(self.debug.remove_watchpoint(to_watchpoint(addr, len, kind)))
+    }
+}
+
+impl MonitorCmd for GdbTarget {
+    fn handle_monitor_cmd(&mut self, cmd: &[u8], mut out: ConsoleOutput<'_>) -> Result<(), Self::Error> {
+        let output: String = match String::from_utf8_lossy(cmd).trim() {
+            "classes" => self
+                .debug
+                .loaded_classes()
+                .into_iter()
+                .map(|(address, name)| format!("{address:#x}: {name}\n"))
+                .collect(),
+            "svc" => self
+                .debug
+                .svc_handlers()
+                .into_iter()
+                .map(|(category, name)| format!("{category:#x}: {name}\n"))
+                .collect(),
+            "heap" => Allocator::describe(&*self.debug).unwrap_or_else(|err| format!("Failed to read heap: {err}")),
+            _ => MONITOR_HELP.to_string(),
+        };
+
+        gdbstub::output!(out, "{output}");
+
+        Ok(())
+    }
+}
+
 impl ExtendedMode for GdbTarget {
     fn run(&mut self, _filename: Option<&[u8]>, _args: Args<'_, '_>) -> TargetResult<Pid, Self> {
         if self.debug.active_threads().is_empty() {
@@ -8,6 +8,7 @@ mod core;
 mod engine;
 mod function;
 pub mod stdlib;
+mod symbols;
 mod thread;
 mod thread_wrapper;
 
@@ -18,8 +19,10 @@ pub type ThreadId = usize;
 
 pub use self::{
     allocator::Allocator,
-    binary_patches::install_binary_patches,
+    binary_patches::{BinaryPatchReport, add_binary_patches, check_binary_patches, find_binary_pattern, install_binary_patches},
     context::ArmCoreContext,
     core::{ArmCore, RUN_FUNCTION_LR, RunFunctionResult},
+    engine::MemoryPermission,
     function::{EmulatedFunction, EmulatedFunctionParam, JumpTo, RegisteredFunction, RegisteredFunctionHolder, ResultWriter, SvcId},
+    symbols::{MethodSymbols, SymbolProvider},
 };
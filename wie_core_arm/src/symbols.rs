@@ -0,0 +1,155 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::cell::RefCell;
+
+use wie_util::{ByteRead, Result};
+
+use crate::{
+    core::{HEAP_BASE, HEAP_SIZE, SVC_STUB_SIZE},
+    engine::ArmEngine,
+};
+
+/// Names guest addresses in profiles. Each platform registers one with [`crate::ArmCore::register_symbol_provider`].
+///
+/// Providers run while the core is locked, so they may only read guest memory through `memory`.
+pub trait SymbolProvider: Send + Sync {
+    /// Called for every class passed to [`crate::ArmCore::note_loaded_class`].
+    fn on_class_loaded(&self, _address: u32, _name: &str) {}
+    /// Names `address` if it is inside code the provider knows about.
+    fn symbolize(&self, memory: &dyn ByteRead, address: u32) -> Option<String>;
+    /// Names the function behind the SVC stub for (`category`, `id`).
+    fn svc_name(&self, memory: &dyn ByteRead, category: u32, id: u32) -> Option<String>;
+}
+
+/// Java method bodies found in guest class metadata, for [`SymbolProvider::symbolize`].
+///
+/// The metadata doesn't record method sizes, so a method extends up to the next known address, either another
+/// method or a metadata record. Addresses past the last known one aren't attributed to any method.
+#[derive(Default)]
+pub struct MethodSymbols {
+    // method body address => `Class.method`
+    methods: BTreeMap<u32, String>,
+    boundaries: BTreeSet<u32>,
+}
+
+impl MethodSymbols {
+    pub fn add_method(&mut self, address: u32, name: String) {
+        self.boundaries.insert(address);
+        self.methods.insert(address, name);
+    }
+
+    /// Marks `address` as not being code, e.g. a class or method record.
+    pub fn add_boundary(&mut self, address: u32) {
+        self.boundaries.insert(address);
+    }
+
+    pub fn symbolize(&self, address: u32) -> Option<String> {
+        let (&start, name) = self.methods.range(..=address).next_back()?;
+        let &end = self.boundaries.range(start + 1..).next()?;
+        if address >= end {
+            return None;
+        }
+
+        Some(match address - start {
+            0 => name.clone(),
+            offset => format!("{name}+{offset:#x}"),
+        })
+    }
+}
+
+struct EngineMemory<'a>(RefCell<&'a mut dyn ArmEngine>);
+
+impl ByteRead for EngineMemory<'_> {
+    fn read_bytes(&self, address: u32, result: &mut [u8]) -> Result<usize> {
+        self.0.borrow_mut().mem_read(address, result.len(), result)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Symbols {
+    providers: Vec<Arc<dyn SymbolProvider>>,
+    stubs: BTreeMap<u32, (u32, u32)>,
+}
+
+impl Symbols {
+    pub fn add_provider(&mut self, provider: Arc<dyn SymbolProvider>) {
+        self.providers.push(provider);
+    }
+
+    pub fn add_stub(&mut self, address: u32, category: u32, id: u32) {
+        self.stubs.insert(address, (category, id));
+    }
+
+    pub fn on_class_loaded(&self, address: u32, name: &str) {
+        for provider in &self.providers {
+            provider.on_class_loaded(address, name);
+        }
+    }
+
+    pub fn svc_name(&self, engine: &mut dyn ArmEngine, category: u32, id: u32) -> String {
+        self.name_svc(&EngineMemory(RefCell::new(engine)), category, id)
+    }
+
+    fn name_svc(&self, memory: &dyn ByteRead, category: u32, id: u32) -> String {
+        self.providers
+            .iter()
+            .find_map(|x| x.svc_name(memory, category, id))
+            .unwrap_or_else(|| format!("svc{category}:{id:#x}"))
+    }
+
+    // falls back to `image+offset` and `heap+offset` for addresses no provider knows
+    pub fn symbolize(&self, engine: &mut dyn ArmEngine, image_base: Option<u32>, address: u32) -> String {
+        let address = address & !1; // thumb return addresses
+        let memory = EngineMemory(RefCell::new(engine));
+
+        if let Some((&stub, &(category, id))) = self.stubs.range(..=address).next_back()
+            && address - stub < SVC_STUB_SIZE
+        {
+            return self.name_svc(&memory, category, id);
+        }
+
+        if let Some(name) = self.providers.iter().find_map(|x| x.symbolize(&memory, address)) {
+            return name;
+        }
+
+        if (HEAP_BASE..HEAP_BASE + HEAP_SIZE).contains(&address) {
+            format!("heap+{:#x}", address - HEAP_BASE)
+        } else if let Some(image_base) = image_base
+            && address >= image_base
+        {
+            format!("image+{:#x}", address - image_base)
+        } else {
+            format!("{address:#x}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::MethodSymbols;
+
+    #[test]
+    fn methods_end_at_the_next_known_address() {
+        let mut symbols = MethodSymbols::default();
+        symbols.add_method(0x1000, "A.a".to_string());
+        symbols.add_method(0x1100, "A.b".to_string());
+        symbols.add_boundary(0x1180);
+
+        assert_eq!(symbols.symbolize(0x1000).as_deref(), Some("A.a"));
+        assert_eq!(symbols.symbolize(0x10fe).as_deref(), Some("A.a+0xfe"));
+        assert_eq!(symbols.symbolize(0x1104).as_deref(), Some("A.b+0x4"));
+        assert_eq!(symbols.symbolize(0x1180), None);
+        assert_eq!(symbols.symbolize(0xfff), None);
+
+        // the last method has no known end
+        symbols.add_method(0x1200, "A.c".to_string());
+        assert_eq!(symbols.symbolize(0x1200), None);
+    }
+}
@@ -76,7 +76,7 @@ impl J2MEEmulator {
             .collect::<Vec<_>>();
         let properties = properties.iter().map(|(k, v)| (k.as_ref(), v.as_ref())).collect::<Vec<_>>();
 
-        let protos = [wie_midp::get_protos().into()];
+        let protos = [wie_midp::get_protos().into(), wie_m3g::get_protos().into()];
         let jvm = JvmSupport::new_jvm(system, Some(&jar_filename), Box::new(protos), &properties, RustJavaJvmImplementation).await?;
 
         let main_class_name = if let Some(x) = main_class_name {
@@ -144,6 +144,10 @@ impl Emulator for J2MEEmulator {
         self.system.event_queue().push(event)
     }
 
+    fn aid(&self) -> &str {
+        self.system.aid()
+    }
+
     fn tick(&mut self) -> Result<()> {
         self.system.tick()
     }
@@ -45,6 +45,8 @@ impl JvmSupport {
             WIE_RUSTJAR.to_string()
         };
 
+        // device profile properties come last so they override the platform defaults
+        let device_properties = system.device_profile().properties.iter().map(|(k, v)| (k.as_str(), v.as_str()));
         let properties = [
             ("file.encoding", "EUC-KR"),
             ("java.class.path", &class_path),
@@ -53,6 +55,7 @@ impl JvmSupport {
         .iter()
         .chain(properties.iter())
         .copied()
+        .chain(device_properties)
         .collect();
         let jvm = Jvm::new(
             java_runtime::get_bootstrap_class_loader(Box::new(runtime.clone())),
@@ -218,23 +218,29 @@ where
         self.file_table.lock().files.remove(&fd.id());
     }
 
-    async fn unlink(&self, _path: &str) -> IOResult<()> {
-        Err(IOError::Unsupported)
+    async fn unlink(&self, path: &str) -> IOResult<()> {
+        if self.system.filesystem().remove(path).await {
+            Ok(())
+        } else {
+            Err(IOError::NotFound)
+        }
     }
 
     async fn metadata(&self, path: &str) -> IOResult<FileStat> {
-        if path.is_empty() || path.ends_with("/") {
-            return Ok(FileStat {
-                size: 0,
-                r#type: FileType::Directory,
-            });
-        }
-
-        let size = self.system.filesystem().size(path).await.ok_or(IOError::NotFound)?;
+        let Some(metadata) = self.system.filesystem().metadata(path).await else {
+            // classpath lookups probe directory paths that have no backing entry
+            if path.is_empty() || path.ends_with("/") {
+                return Ok(FileStat {
+                    size: 0,
+                    r#type: FileType::Directory,
+                });
+            }
+            return Err(IOError::NotFound);
+        };
 
         Ok(FileStat {
-            size: size as _,
-            r#type: FileType::File,
+            size: metadata.size as _,
+            r#type: if metadata.is_dir { FileType::Directory } else { FileType::File },
         })
     }
 
@@ -3,7 +3,7 @@ use alloc::{format, string::ToString, sync::Arc, vec, vec::Vec};
 use spin::Mutex;
 
 use wie_backend::extract_zip;
-use wie_core_arm::{Allocator, ArmCore};
+use wie_core_arm::{Allocator, ArmCore, MemoryPermission};
 use wie_util::{ByteRead, Result, WieError};
 
 use crate::{
@@ -38,7 +38,7 @@ pub async fn dump_image(zip: &[u8]) -> Result<Vec<u8>> {
     let mut core_inner = core.clone();
     let buf_slot_clone = buf_slot.clone();
     core.run_in_thread(async move || {
-        core_inner.load(&data, IMAGE_BASE, data.len() + bss_size as usize)?;
+        core_inner.load(&data, IMAGE_BASE, data.len() + bss_size as usize, MemoryPermission::ReadWriteExecute)?;
 
         let _ = core_inner.run_function::<u32>(IMAGE_BASE + 1, &[bss_size]).await?;
 
@@ -56,3 +56,22 @@ pub async fn dump_image(zip: &[u8]) -> Result<Vec<u8>> {
 
     Ok(buf_slot.lock().take().expect("buf populated by closure"))
 }
+
+#[cfg(test)]
+mod tests {
+    use wie_backend::extract_zip;
+
+    use crate::adf::{find_client_bin, parse_bss_size};
+
+    use super::dump_image;
+
+    #[futures_test::test]
+    async fn dump_helloworld_runs_entrypoint() {
+        let zip = include_bytes!("../../test_data/helloworld_ktf.zip");
+        let image = dump_image(zip).await.unwrap();
+
+        let files = extract_zip(zip).unwrap();
+        let (client_bin_name, data) = find_client_bin(&files["00000000.jar"]).unwrap();
+        assert_eq!(image.len(), data.len() + parse_bss_size(&client_bin_name).unwrap() as usize);
+    }
+}
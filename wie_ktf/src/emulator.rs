@@ -1,19 +1,19 @@
 use core::{mem::size_of, pin::Pin, task::Poll};
 
-use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String, vec, vec::Vec};
+use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String, sync::Arc, vec, vec::Vec};
 
 use bytemuck::Zeroable;
 use futures::future::poll_fn;
 use jvm::{ClassInstance, Result as JvmResult, runtime::JavaLangString};
 
 use wie_backend::{Emulator, Event, Options, Platform, System, TaskRunner};
-use wie_core_arm::{Allocator, ArmCore};
+use wie_core_arm::{Allocator, ArmCore, add_binary_patches};
 use wie_jvm_support::JvmSupport;
 use wie_util::{Result, WieError, write_generic};
 
 use crate::{
     adf::{KtfAdf, find_client_bin},
-    runtime::{KtfJvmSupport, KtfJvmThreadContext},
+    runtime::{KtfJvmSupport, KtfJvmThreadContext, KtfSymbolProvider},
 };
 
 pub const IMAGE_BASE: u32 = 0x100000;
@@ -106,6 +106,10 @@ impl KtfEmulator {
         mut options: Options,
     ) -> Result<Self> {
         let mut core = ArmCore::new(options.enable_gdbserver, options.profile.take())?;
+        if let Some(trace) = options.trace.take() {
+            core.set_trace(trace);
+        }
+        core.register_symbol_provider(Arc::new(KtfSymbolProvider::new()));
         let system = System::new(platform, pid, aid, KtfTaskRunner { core: core.clone() });
 
         for (path, data) in files {
@@ -113,8 +117,15 @@ impl KtfEmulator {
             system.filesystem().add_virtual(path, data.clone());
         }
 
+        if options.heap_debug {
+            Allocator::enable_debug(&mut core);
+        }
         Allocator::init(&mut core)?;
 
+        for file in &options.binary_patches {
+            add_binary_patches(&mut core, &file.name, &file.contents)?;
+        }
+
         let mut core_clone = core.clone();
         let mut system_clone = system.clone();
         let jar_filename_clone = jar_filename.to_owned();
@@ -167,6 +178,10 @@ impl Emulator for KtfEmulator {
         self.system.event_queue().push(event)
     }
 
+    fn aid(&self) -> &str {
+        self.system.aid()
+    }
+
     fn tick(&mut self) -> Result<()> {
         self.system.tick().map_err(|x| {
             let reg_stack = self.core.dump_reg_stack(IMAGE_BASE);
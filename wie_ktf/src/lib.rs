@@ -7,4 +7,4 @@ mod emulator;
 mod runtime;
 
 pub use dump::dump_image;
-pub use emulator::KtfEmulator;
+pub use emulator::{IMAGE_BASE, KtfEmulator};
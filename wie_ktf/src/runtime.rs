@@ -1,6 +1,7 @@
 mod init;
 mod java;
 mod svc_ids;
+mod symbols;
 mod wipi_c;
 
 const SVC_CATEGORY_INIT: u32 = 1;
@@ -8,4 +9,7 @@ const SVC_CATEGORY_JAVA_INTERFACE: u32 = 2;
 const SVC_CATEGORY_WIPIC: u32 = 3;
 const SVC_CATEGORY_JAVA: u32 = 4;
 
-pub use self::java::jvm_support::{KtfJvmSupport, KtfJvmThreadContext};
+pub use self::{
+    java::jvm_support::{KtfJvmSupport, KtfJvmThreadContext},
+    symbols::KtfSymbolProvider,
+};
@@ -3,7 +3,7 @@ use core::mem::size_of;
 use jvm::Jvm;
 
 use wie_backend::System;
-use wie_core_arm::{Allocator, ArmCore, EmulatedFunction, ResultWriter, SvcId};
+use wie_core_arm::{Allocator, ArmCore, EmulatedFunction, MemoryPermission, ResultWriter, SvcId};
 use wie_util::{Result, WieError, read_generic, read_null_terminated_string_bytes, write_generic};
 
 use wipi_types::ktf::{ExeInterface, ExeInterfaceFunctions, InitParam0, InitParam3, InitParam4, WipiExe};
@@ -48,19 +48,10 @@ pub async fn load_native(
 ) -> Result<ExeInterfaceFunctions> {
     let bss_size = parse_bss_size(filename)?;
 
-    core.load(data, IMAGE_BASE, data.len() + bss_size as usize)?;
-
-    // Patterns target instruction encodings, which the guest self-rebase at
-    // IMAGE_BASE+1 doesn't rewrite — so installing here is sound and skips a
-    // re-scan after relocation. Hash-matched entries take priority over
-    // hash-less generic ones; only one entry is installed because each install
-    // claims fresh SVC categories from a fixed base and they would collide.
-    //
-    // The scan range covers the whole loaded image because KTF binaries don't
-    // expose a code/metadata boundary at this point. Safety relies on the
-    // patterns being long enough (and `{exit_b}` strict enough) that a
-    // metadata-region collision is implausible; tighten patterns rather than
-    // narrow the range if that ever becomes false.
+    // KTF images don't record where their code ends, so the whole image stays writable and executable
+    core.load(data, IMAGE_BASE, data.len() + bss_size as usize, MemoryPermission::ReadWriteExecute)?;
+
+    // patterns target instruction encodings, which the self-rebase doesn't touch
     wie_core_arm::install_binary_patches(core, data, &[(IMAGE_BASE, data.len() as u32)])?;
 
     register_wipic_svc_handler(core, system, jvm)?;
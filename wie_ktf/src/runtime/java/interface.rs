@@ -8,7 +8,7 @@ use alloc::{
 use core::mem::size_of;
 
 use java_runtime::classes::java::util::Vector;
-use jvm::{ClassInstanceRef, Jvm, runtime::JavaLangString};
+use jvm::{ClassInstanceRef, JavaType, Jvm, runtime::JavaLangString};
 use wipi_types::ktf::java::WIPIJBInterface;
 
 use wie_core_arm::{Allocator, ArmCore, EmulatedFunction, ResultWriter, SvcId};
@@ -199,6 +199,7 @@ async fn register_class(core: &mut ArmCore, jvm: &mut Jvm, ptr_class: u32) -> Re
     if jvm.has_class(&class_name) {
         return Ok(());
     }
+    core.note_loaded_class(ptr_class, &class_name);
 
     let ktf_class_loader = jvm
         .get_static_field("net/wie/KtfClassLoader", "instance", "Lnet/wie/KtfClassLoader;")
@@ -272,6 +273,7 @@ async fn get_field(core: &mut ArmCore, _: &mut (), ptr_class: u32, field_name: u
     }
 }
 
+// no available sample calls jb_unk4 or jb_unk5, so their behavior is unknown
 async fn jb_unk4(_: &mut ArmCore, _: &mut (), a0: u32, a1: u32) -> Result<u32> {
     tracing::warn!("stub jb_unk4({a0:#x}, {a1:#x})");
 
@@ -388,17 +390,21 @@ pub async fn java_array_new(core: &mut ArmCore, jvm: &mut Jvm, element_type: u32
 }
 
 pub async fn java_check_type(core: &mut ArmCore, jvm: &mut Jvm, ptr_class: u32, ptr_instance: u32, unk: u32) -> Result<u32> {
-    tracing::warn!("stub java_check_type({ptr_class:#x}, {ptr_instance:#x}, {unk:#x})");
+    tracing::trace!("java_check_type({ptr_class:#x}, {ptr_instance:#x}, {unk:#x})");
 
-    let instance = JavaClassInstance::from_raw(ptr_instance, core);
-
-    // TODO is it correct?
-    if instance.class()?.name()?.starts_with('[') || unk != 0 {
+    // TODO the meaning of `unk` is unknown, calls with it set are passed as before
+    if unk != 0 {
         return Ok(1);
     }
 
-    let class = JavaClassDefinition::from_raw(ptr_class, core);
-    let result = jvm.is_instance(&instance, &class.name()?);
+    if ptr_instance == 0 {
+        return Ok(0);
+    }
+
+    let instance_class_name = JavaClassInstance::from_raw(ptr_instance, core).class()?.name()?;
+    let class_name = KtfJvmSupport::class_from_raw(core, ptr_class).name()?;
+
+    let result = jvm.is_type_assignable(&JavaType::from_class_name(&instance_class_name), &JavaType::from_class_name(&class_name));
 
     Ok(if result { 1 } else { 0 })
 }
@@ -29,7 +29,6 @@ use wipi_types::ktf::InitParam2;
 use self::{
     array_class_instance::JavaArrayClassInstance,
     classes::net::wie::{ClassLoaderContext, KtfClassLoader},
-    name::JavaFullName,
 };
 use super::interface::register_java_interface_svc_handler;
 
@@ -38,6 +37,7 @@ pub use self::{
     class_definition::JavaClassDefinition,
     class_instance::JavaClassInstance,
     method::{JavaMethod, JavaMethodResult},
+    name::JavaFullName,
     vtable::JavaVtable,
 };
 
@@ -245,6 +245,8 @@ mod test {
     use wie_core_arm::{Allocator, ArmCore};
     use wie_util::{Result, WieError, write_generic};
 
+    use crate::runtime::java::interface::java_check_type;
+
     use super::{JavaArrayClassInstance, JavaClassDefinition, JavaMethod, KtfJvmSupport, KtfJvmThreadContext};
 
     use test_utils::TestPlatform;
@@ -480,4 +482,69 @@ mod test {
 
         Ok(())
     }
+
+    async fn class_raw(jvm: &Jvm, name: &str) -> u32 {
+        let class = jvm.resolve_class(name).await.unwrap();
+
+        KtfJvmSupport::class_definition_raw(&*class.definition).unwrap()
+    }
+
+    #[test]
+    fn test_java_check_type() -> Result<()> {
+        let mut system = System::new(Box::new(TestPlatform::new()), "", "", DefaultTaskRunner);
+
+        let done = Arc::new(AtomicBool::new(false));
+
+        let done_clone = done.clone();
+        let mut system_clone = system.clone();
+        system.spawn(async move || {
+            let (mut jvm, mut core) = init_jvm(&mut system_clone).await?;
+
+            let string = JavaLangString::from_rust_string(&jvm, "test").await.unwrap();
+            let ptr_string = KtfJvmSupport::class_instance_raw(&string);
+            let socket = jvm.instantiate_class("net/wie/WIPISocket").await.unwrap();
+            let ptr_socket = KtfJvmSupport::class_instance_raw(&socket);
+            let array = jvm.instantiate_array("Ljava/lang/String;", 1).await.unwrap();
+            let ptr_array = KtfJvmSupport::class_instance_raw(&array);
+
+            let object = class_raw(&jvm, "java/lang/Object").await;
+            let integer = class_raw(&jvm, "java/lang/Integer").await;
+            let socket_interface = class_raw(&jvm, "org/kwis/msf/io/Socket").await;
+            let object_array = class_raw(&jvm, "[Ljava/lang/Object;").await;
+            let integer_array = class_raw(&jvm, "[Ljava/lang/Integer;").await;
+
+            let cases = [
+                (ptr_string, object, 1),
+                (ptr_string, class_raw(&jvm, "java/lang/String").await, 1),
+                (ptr_string, integer, 0),
+                (ptr_socket, socket_interface, 1),
+                (ptr_string, socket_interface, 0),
+                (ptr_array, object, 1),
+                (ptr_array, object_array, 1),
+                (ptr_array, integer_array, 0),
+                (ptr_string, object_array, 0),
+                (0, object, 0),
+            ];
+            for (ptr_instance, ptr_class, expected) in cases {
+                let result = java_check_type(&mut core, &mut jvm, ptr_class, ptr_instance, 0).await?;
+                assert_eq!(result, expected, "{ptr_instance:#x} instanceof {ptr_class:#x}");
+            }
+
+            // calls with the unknown flag set are passed unchecked
+            assert_eq!(java_check_type(&mut core, &mut jvm, integer, ptr_string, 1).await?, 1);
+
+            done_clone.store(true, Ordering::Relaxed);
+
+            Ok(())
+        });
+
+        loop {
+            system.tick()?;
+            if done.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -138,6 +138,7 @@ impl KtfClassLoader {
 
         if ptr_raw != 0 {
             let class = JavaClassDefinition::from_raw(ptr_raw, &context.core);
+            context.core.note_loaded_class(ptr_raw, &name);
             jvm.register_class(Box::new(class), Some(this.into())).await?;
 
             Ok(jvm.resolve_class(&name).await?.java_class().into())
@@ -0,0 +1,110 @@
+use alloc::{format, string::String, vec::Vec};
+
+use spin::Mutex;
+use wipi_types::ktf::java::{JavaClass as RawJavaClass, JavaClassDescriptor as RawJavaClassDescriptor, JavaMethodDefinition as RawJavaMethod};
+
+use wie_core_arm::{MethodSymbols, SvcId, SymbolProvider};
+use wie_util::{ByteRead, Result, read_generic, read_null_terminated_string_bytes, read_null_terminated_table};
+
+use super::{
+    SVC_CATEGORY_INIT, SVC_CATEGORY_JAVA, SVC_CATEGORY_JAVA_INTERFACE, SVC_CATEGORY_WIPIC,
+    java::jvm_support::JavaFullName,
+    svc_ids::{InitSvcId, JavaSvcId, WIPICDatabaseMethodId, WIPICGraphicsMethodId, WIPICKernelMethodId, WIPICTableId},
+};
+
+#[derive(Default)]
+struct State {
+    // classes whose methods weren't read yet; they are read on the first lookup, when the class is fully set up
+    pending_classes: Vec<u32>,
+    methods: MethodSymbols,
+}
+
+/// Names AOT-compiled java methods in the client binary and KTF SVC stubs.
+#[derive(Default)]
+pub struct KtfSymbolProvider {
+    state: Mutex<State>,
+}
+
+impl KtfSymbolProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn class_name(memory: &dyn ByteRead, ptr_class: u32) -> Result<String> {
+        let class: RawJavaClass = read_generic(memory, ptr_class)?;
+        let descriptor: RawJavaClassDescriptor = read_generic(memory, class.ptr_descriptor)?;
+
+        Ok(String::from_utf8_lossy(&read_null_terminated_string_bytes(memory, descriptor.ptr_name)?).into_owned())
+    }
+
+    fn method_name(memory: &dyn ByteRead, ptr_method: u32) -> Result<String> {
+        let method: RawJavaMethod = read_generic(memory, ptr_method)?;
+        let name = JavaFullName::from_ptr(memory, method.ptr_name)?;
+
+        Ok(format!("{}.{}", Self::class_name(memory, method.ptr_class)?, name.name))
+    }
+
+    fn read_methods(memory: &dyn ByteRead, ptr_class: u32, methods: &mut MethodSymbols) -> Result<()> {
+        let class: RawJavaClass = read_generic(memory, ptr_class)?;
+        methods.add_boundary(ptr_class);
+        methods.add_boundary(class.ptr_descriptor);
+
+        let descriptor: RawJavaClassDescriptor = read_generic(memory, class.ptr_descriptor)?;
+        methods.add_boundary(descriptor.ptr_name);
+        if descriptor.ptr_methods == 0 {
+            return Ok(());
+        }
+        methods.add_boundary(descriptor.ptr_methods);
+
+        for ptr_method in read_null_terminated_table(memory, descriptor.ptr_methods)? {
+            let method: RawJavaMethod = read_generic(memory, ptr_method)?;
+            methods.add_boundary(ptr_method);
+            methods.add_boundary(method.ptr_name);
+            if method.ptr_class == ptr_class && method.fn_body != 0 {
+                methods.add_method(method.fn_body & !1, Self::method_name(memory, ptr_method)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn wipic_name(id: u32) -> Option<String> {
+        let table = WIPICTableId::try_from(id >> 16).ok()?;
+        let function_id = id as u16;
+
+        Some(match table {
+            WIPICTableId::Kernel => format!("MC_knl{:?}", WIPICKernelMethodId::try_from(function_id).ok()?),
+            WIPICTableId::Graphics => format!("MC_grp{:?}", WIPICGraphicsMethodId::try_from(function_id).ok()?),
+            WIPICTableId::Database => format!("MC_db{:?}", WIPICDatabaseMethodId::try_from(function_id).ok()?),
+            _ => format!("WIPIC{table:?}#{function_id}"),
+        })
+    }
+}
+
+impl SymbolProvider for KtfSymbolProvider {
+    fn on_class_loaded(&self, address: u32, _name: &str) {
+        self.state.lock().pending_classes.push(address);
+    }
+
+    fn symbolize(&self, memory: &dyn ByteRead, address: u32) -> Option<String> {
+        let mut state = self.state.lock();
+        let state = &mut *state;
+        for ptr_class in state.pending_classes.drain(..) {
+            if let Err(err) = Self::read_methods(memory, ptr_class, &mut state.methods) {
+                tracing::warn!("Failed to read methods of class {ptr_class:#x}: {err}");
+            }
+        }
+
+        state.methods.symbolize(address)
+    }
+
+    fn svc_name(&self, memory: &dyn ByteRead, category: u32, id: u32) -> Option<String> {
+        match category {
+            SVC_CATEGORY_INIT => InitSvcId::try_from(SvcId(id)).ok().map(|x| format!("{x:?}")),
+            SVC_CATEGORY_JAVA_INTERFACE => JavaSvcId::try_from(SvcId(id)).ok().map(|x| format!("{x:?}")),
+            SVC_CATEGORY_WIPIC => Self::wipic_name(id),
+            SVC_CATEGORY_JAVA => Self::method_name(memory, id).ok(),
+            _ => None,
+        }
+    }
+}
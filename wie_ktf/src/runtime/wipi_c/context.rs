@@ -52,6 +52,14 @@ impl WIPICContext for KtfWIPICContext {
         Ok(())
     }
 
+    fn total_memory(&self) -> Result<WIPICWord> {
+        Ok(Allocator::total_memory())
+    }
+
+    fn free_memory(&self) -> Result<WIPICWord> {
+        Ok(Allocator::free_memory(&self.core))
+    }
+
     fn data_ptr(&self, memory: WIPICIndirectPtr) -> Result<WIPICWord> {
         let base: WIPICWord = read_generic(&self.core, memory.0)?;
 
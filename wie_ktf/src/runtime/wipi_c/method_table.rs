@@ -9,7 +9,7 @@ use wie_core_arm::ArmCore;
 use wie_util::{Result, WieError};
 use wie_wipi_c::{
     MethodImpl, WIPICContext, WIPICMethodBody,
-    api::{database, graphics, kernel, media, misc, net, uic, util},
+    api::{database, graphics, input_method, kernel, media, misc, net, uic, util},
 };
 
 use crate::runtime::{
@@ -221,47 +221,47 @@ pub fn get_uic_method_table() -> Vec<WIPICMethodBody> {
         uic::get_class.into_body(),
         uic::create.into_body(),
         uic::destroy.into_body(),
-        gen_stub(4, "MC_uicRepaint"),
-        gen_stub(5, "MC_uicPaint"),
-        gen_stub(6, "MC_uicGetClassName"),
-        gen_stub(7, "MC_uicIsInstance"),
-        gen_stub(8, "MC_uicHandleEvent"),
-        gen_stub(9, "MC_uicConfigure"),
-        gen_stub(10, "MC_uicGetGeometry"),
-        gen_stub(11, "MC_uicSetEnable"),
-        gen_stub(12, "MC_uicSetCallback"),
-        gen_stub(13, "MC_uicSetEventHandler"),
-        gen_stub(14, "MC_uicSetFont"),
-        gen_stub(15, "MC_uicGetFont"),
-        gen_stub(16, "MC_uicSetFgColor"),
-        gen_stub(17, "MC_uicSetBgColor"),
-        gen_stub(18, "MC_uicSetLabel"),
-        gen_stub(19, "MC_uicGetLabel"),
-        gen_stub(20, "MC_uicSetLabelAlignment"),
-        gen_stub(21, "MC_uicSetTimeMask"),
-        gen_stub(22, "MC_uicSetTime"),
-        gen_stub(23, "MC_uicSetTimeLong"),
-        gen_stub(24, "MC_uicGetTime"),
-        gen_stub(25, "MC_uicAddMenuItem"),
+        uic::repaint.into_body(),
+        uic::paint.into_body(),
+        uic::get_class_name.into_body(),
+        uic::is_instance.into_body(),
+        uic::handle_event.into_body(),
+        uic::configure.into_body(),
+        uic::get_geometry.into_body(),
+        uic::set_enable.into_body(),
+        uic::set_callback.into_body(),
+        uic::set_event_handler.into_body(),
+        uic::set_font.into_body(),
+        uic::get_font.into_body(),
+        uic::set_fg_color.into_body(),
+        uic::set_bg_color.into_body(),
+        uic::set_label.into_body(),
+        uic::get_label.into_body(),
+        uic::set_label_alignment.into_body(),
+        uic::set_time_mask.into_body(),
+        uic::set_time.into_body(),
+        uic::set_time_long.into_body(),
+        uic::get_time.into_body(),
+        uic::add_menu_item.into_body(),
         uic::get_menu_item.into_body(),
-        gen_stub(27, "MC_uicRemoveMenuItem"),
-        gen_stub(28, "MC_uicSetActiveMenuItem"),
-        gen_stub(29, "MC_uicGetActiveMenuItem"),
-        gen_stub(30, "MC_uicInsertText"),
-        gen_stub(31, "MC_uicDeleteText"),
-        gen_stub(32, "MC_uicGetMaxTextSize"),
-        gen_stub(33, "MC_uicSetMaxTextSize"),
-        gen_stub(34, "MC_uicGetTextSize"),
-        gen_stub(35, "MC_uicGetText"),
-        gen_stub(36, "MC_uicAddListItem"),
-        gen_stub(37, "MC_uicGetListItem"),
-        gen_stub(38, "MC_uicRemoveListItem"),
-        gen_stub(39, "MC_uicSetActiveListItem"),
-        gen_stub(40, "MC_uicGetActiveListItem"),
-        gen_stub(41, "OEMC_uicGetCursorPosition"),
-        gen_stub(42, "OEMC_uicSetCursorPosition"),
-        gen_stub(43, "OEMC_uicSetLineGap"),
-        gen_stub(44, "OEMC_uicGetLineGap"),
+        uic::remove_menu_item.into_body(),
+        uic::set_active_menu_item.into_body(),
+        uic::get_active_menu_item.into_body(),
+        uic::insert_text.into_body(),
+        uic::delete_text.into_body(),
+        uic::get_max_text_size.into_body(),
+        uic::set_max_text_size.into_body(),
+        uic::get_text_size.into_body(),
+        uic::get_text.into_body(),
+        uic::add_list_item.into_body(),
+        uic::get_list_item.into_body(),
+        uic::remove_list_item.into_body(),
+        uic::set_active_list_item.into_body(),
+        uic::get_active_list_item.into_body(),
+        uic::get_cursor_position.into_body(),
+        uic::set_cursor_position.into_body(),
+        uic::set_line_gap.into_body(),
+        uic::get_line_gap.into_body(),
     ]
 }
 
@@ -301,34 +301,34 @@ pub fn get_net_method_table() -> Vec<WIPICMethodBody> {
     vec![
         net::connect.into_body(),
         net::close.into_body(),
-        gen_stub(2, "MC_netSocket"),
-        gen_stub(3, "MC_netSocketConnect"),
-        gen_stub(4, "MC_netSocketWrite"),
-        gen_stub(5, "MC_netSocketRead"),
+        net::socket.into_body(),
+        net::socket_connect.into_body(),
+        net::socket_write.into_body(),
+        net::socket_read.into_body(),
         net::socket_close.into_body(),
-        gen_stub(7, "MC_netSocketBind"),
-        gen_stub(8, "MC_netGetMaxPacketLength"),
-        gen_stub(9, "MC_netSocketSendTo"),
-        gen_stub(10, "MC_netSocketRcvFrom"),
-        gen_stub(11, "MC_netGetHostAddr"),
-        gen_stub(12, "MC_netSocketAccept"),
-        gen_stub(13, "MC_netSetReadCB"),
-        gen_stub(14, "MC_netSetWriteCB"),
-        gen_stub(15, "MC_netHttpOpen"),
-        gen_stub(16, "MC_netHttpConnect"),
-        gen_stub(17, "MC_netHttpSetRequestMethod"),
-        gen_stub(18, "MC_netHttpGetRequestMethod"),
-        gen_stub(19, "MC_netHttpSetRequestProperty"),
-        gen_stub(20, "MC_netHttpGetRequestProperty"),
-        gen_stub(21, "MC_netHttpSetProxy"),
-        gen_stub(22, "MC_netHttpGetProxy"),
-        gen_stub(23, "MC_netHttpGetResponseCode"),
-        gen_stub(24, "MC_netHttpGetResponseMessage"),
-        gen_stub(25, "MC_netHttpGetHeaderField"),
-        gen_stub(26, "MC_netHttpGetLength"),
-        gen_stub(27, "MC_netHttpGetType"),
-        gen_stub(28, "MC_netHttpGetEncoding"),
-        gen_stub(29, "MC_netHttpClose"),
+        net::socket_bind.into_body(),
+        net::get_max_packet_length.into_body(),
+        net::socket_send_to.into_body(),
+        net::socket_rcv_from.into_body(),
+        net::get_host_addr.into_body(),
+        net::socket_accept.into_body(),
+        net::set_read_cb.into_body(),
+        net::set_write_cb.into_body(),
+        net::http_open.into_body(),
+        net::http_connect.into_body(),
+        net::http_set_request_method.into_body(),
+        net::http_get_request_method.into_body(),
+        net::http_set_request_property.into_body(),
+        net::http_get_request_property.into_body(),
+        net::http_set_proxy.into_body(),
+        net::http_get_proxy.into_body(),
+        net::http_get_response_code.into_body(),
+        net::http_get_response_message.into_body(),
+        net::http_get_header_field.into_body(),
+        net::http_get_length.into_body(),
+        net::http_get_type.into_body(),
+        net::http_get_encoding.into_body(),
+        net::http_close.into_body(),
     ]
 }
 
@@ -468,11 +468,11 @@ pub fn get_method_body(table_id: WIPICTableId, function_id: u16) -> Option<WIPIC
             WIPICGraphicsMethodId::DecodeNextImage => Some(gen_stub(34, "MC_grpDecodeNextImage")),
             WIPICGraphicsMethodId::EncodeImage => Some(gen_stub(35, "MC_grpEncodeImage")),
             WIPICGraphicsMethodId::PostEvent => Some(graphics::post_event.into_body()),
-            WIPICGraphicsMethodId::HandleInput => Some(gen_stub(37, "MC_imHandleInput")),
-            WIPICGraphicsMethodId::SetCurrentMode => Some(gen_stub(38, "MC_imSetCurrentMode")),
-            WIPICGraphicsMethodId::GetCurrentMode => Some(gen_stub(39, "MC_imGetCurrentMode")),
-            WIPICGraphicsMethodId::GetSupportModeCount => Some(gen_stub(40, "MC_imGetSupportModeCount")),
-            WIPICGraphicsMethodId::GetSupportedModes => Some(gen_stub(41, "MC_imGetSupportedModes")),
+            WIPICGraphicsMethodId::HandleInput => Some(input_method::handle_input.into_body()),
+            WIPICGraphicsMethodId::SetCurrentMode => Some(input_method::set_current_mode.into_body()),
+            WIPICGraphicsMethodId::GetCurrentMode => Some(input_method::get_current_mode.into_body()),
+            WIPICGraphicsMethodId::GetSupportModeCount => Some(input_method::get_support_mode_count.into_body()),
+            WIPICGraphicsMethodId::GetSupportedModes => Some(input_method::get_supported_modes.into_body()),
             WIPICGraphicsMethodId::FillPolygon => Some(gen_stub(42, "MC_grpFillPolygon")),
             WIPICGraphicsMethodId::DrawPolygon => Some(gen_stub(43, "MC_grpDrawPolygon")),
             WIPICGraphicsMethodId::ShowAnnunciator => Some(gen_stub(44, "OEMC_grpShowAnnunciator")),
@@ -36,6 +36,9 @@ pub fn test_helloworld() -> Result<()> {
         Options {
             enable_gdbserver: false,
             profile: None,
+            trace: None,
+            heap_debug: false,
+            binary_patches: Vec::new(),
         },
     )?;
 
@@ -0,0 +1,771 @@
+mod writer;
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::mem::size_of;
+
+use elf::{ElfBytes, abi::STT_FUNC, abi::STT_OBJECT, endian::AnyEndian};
+use spin::Mutex;
+use wipi_types::lgt::{
+    CletFunctions, InitParam1, InitParam2, InitStruct,
+    java::{
+        LgtJavaClass as RawJavaClass, LgtJavaClassDescriptor as RawJavaClassDescriptor, LgtJavaClassLink as RawJavaClassLink,
+        LgtJavaClassMethod as RawJavaMethod,
+    },
+};
+
+use wie_backend::extract_zip;
+use wie_core_arm::{Allocator, ArmCore, EmulatedFunction, JumpTo, ResultWriter, SvcId, SymbolProvider, stdlib};
+use wie_util::{ByteRead, Result, WieError, read_generic, read_null_terminated_string_bytes, write_generic, write_null_terminated_string_bytes};
+
+use crate::{
+    emulator::LgtAppInfo,
+    runtime::{
+        LgtSymbolProvider, SVC_CATEGORY_INIT, SVC_CATEGORY_JAVA_SYSTEM, SVC_CATEGORY_STDLIB, SVC_CATEGORY_WIPIC,
+        init::{import_svc, load_executable},
+        svc_ids::{InitSvcId, JavaSystemSvcId, StdlibSvcId, WIPICSvcId},
+    },
+};
+
+const SVC_CATEGORY_DUMP_INIT: u32 = 1;
+const SVC_CATEGORY_DUMP_IMPORT: u32 = 2;
+
+const DUMP_JAR_PATH: &str = "app.jar";
+
+/// An addressed `binary.mod` section as it is after the bootstrap.
+pub struct LgtImageSection {
+    pub name: String,
+    pub address: u32,
+    /// ELF `sh_flags` of the original section.
+    pub flags: u32,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum LgtImageSymbolKind {
+    Function,
+    Object,
+}
+
+pub struct LgtImageSymbol {
+    /// Functions keep the Thumb bit.
+    pub address: u32,
+    pub size: u32,
+    pub name: String,
+    pub kind: LgtImageSymbolKind,
+}
+
+/// A bootstrapped `binary.mod` with names recovered from the LGT metadata.
+pub struct LgtImage {
+    pub entrypoint: u32,
+    /// ELF `e_flags` of the original binary.
+    pub elf_flags: u32,
+    pub sections: Vec<LgtImageSection>,
+    pub symbols: Vec<LgtImageSymbol>,
+}
+
+impl LgtImage {
+    /// ELF32 executable with one section and load segment per addressed section, plus a symbol table.
+    pub fn to_elf(&self) -> Vec<u8> {
+        writer::write_elf(self)
+    }
+
+    /// `address name` lines sorted by address, for tools that import plain symbol lists.
+    pub fn to_symbol_file(&self) -> String {
+        let mut symbols = self.symbols.iter().collect::<Vec<_>>();
+        symbols.sort_by_key(|x| x.address & !1);
+
+        symbols.iter().map(|x| format!("{:#010x} {}\n", x.address & !1, x.name)).collect()
+    }
+}
+
+#[derive(Default)]
+struct Bootstrap {
+    ptr_jar_path: u32,
+    // svc stub => (import table, function index)
+    imports: BTreeMap<u32, (u32, u32)>,
+    generated_classes: u32,
+    // (ptr_link, generated class record of a public class), with the member and output tables of the link call
+    links: Vec<(Vec<(u32, Option<u32>)>, [u32; 10])>,
+    ptr_clet_functions: u32,
+}
+
+type BootstrapState = Arc<Mutex<Bootstrap>>;
+
+/// Bootstrap the `binary.mod` of an LGT archive, jar or bare `binary.mod`
+/// far enough to resolve imports and link tables, and name what was found.
+///
+/// Java runtime imports only record their arguments; class initializers and
+/// the application itself are not run. A fault in generated code ends that
+/// bootstrap stage with a warning and keeps what was recorded until then.
+pub async fn dump_image(data: &[u8]) -> Result<LgtImage> {
+    let binary_mod = find_binary_mod(data)?;
+
+    let mut core = ArmCore::new(false, None)?;
+    Allocator::init(&mut core)?;
+
+    let image_slot: Arc<Mutex<Option<LgtImage>>> = Arc::new(Mutex::new(None));
+
+    let mut core_inner = core.clone();
+    let image_slot_clone = image_slot.clone();
+    core.run_in_thread(async move || {
+        let (entrypoint, _) = load_executable(&mut core_inner, &binary_mod)?;
+        let state = bootstrap(&mut core_inner, entrypoint).await?;
+
+        let image = collect_image(&core_inner, &binary_mod, &state.lock())?;
+        image_slot_clone.lock().replace(image);
+        Ok(())
+    })?
+    .await?;
+
+    Ok(image_slot.lock().take().expect("image populated by closure"))
+}
+
+fn find_binary_mod(data: &[u8]) -> Result<Vec<u8>> {
+    if data.starts_with(b"\x7fELF") {
+        return Ok(data.to_vec());
+    }
+
+    let files = extract_zip(data)?;
+    if let Some(app_info) = files.get("app_info") {
+        let app_info = LgtAppInfo::parse(app_info);
+        let jar_filename = format!("{}.jar", app_info.aid);
+        let jar = files
+            .get(&jar_filename)
+            .ok_or_else(|| WieError::FatalError(format!("Missing {jar_filename} in LGT archive")))?;
+
+        return find_binary_mod(jar);
+    }
+
+    files
+        .get("binary.mod")
+        .cloned()
+        .ok_or_else(|| WieError::FatalError("Missing binary.mod in LGT jar".to_string()))
+}
+
+async fn bootstrap(core: &mut ArmCore, entrypoint: u32) -> Result<BootstrapState> {
+    let state = BootstrapState::default();
+    core.register_svc_handler(SVC_CATEGORY_DUMP_INIT, handle_init_svc, &state)?;
+    core.register_svc_handler(SVC_CATEGORY_DUMP_IMPORT, handle_import_svc, &state)?;
+
+    let ptr_jar_path = Allocator::alloc(core, (DUMP_JAR_PATH.len() + 1) as u32)?;
+    write_null_terminated_string_bytes(core, ptr_jar_path, DUMP_JAR_PATH.as_bytes())?;
+    state.lock().ptr_jar_path = ptr_jar_path;
+
+    let ptr_init_param_1 = Allocator::alloc(core, size_of::<InitParam1>() as u32)?;
+    let ptr_init_param_2 = Allocator::alloc(core, size_of::<InitParam2>() as u32)?;
+    let init_param_1 = InitParam1 {
+        unk1: [0; 512],
+        unk2: [0; 20],
+        ptr_init_struct: 0,
+    };
+    write_generic(core, ptr_init_param_1, init_param_1)?;
+    let init_param_2 = InitParam2 {
+        fn_get_import_table: core.make_svc_stub(SVC_CATEGORY_DUMP_INIT, 0u32)?,
+        fn_get_import_function: core.make_svc_stub(SVC_CATEGORY_DUMP_INIT, 1u32)?,
+        fn_unk3: 0,
+        fn_unk4: 0,
+    };
+    write_generic(core, ptr_init_param_2, init_param_2)?;
+
+    run_stage(core, "entrypoint", entrypoint + 1, &[ptr_init_param_1, ptr_init_param_2, 0]).await;
+
+    let init_param_1: InitParam1 = read_generic(core, ptr_init_param_1)?;
+    if init_param_1.ptr_init_struct == 0 {
+        tracing::warn!("Entrypoint did not publish an initialization descriptor");
+        return Ok(state);
+    }
+    let init_struct: InitStruct = read_generic(core, init_param_1.ptr_init_struct)?;
+    run_stage(core, "initializer", init_struct.fn_init, &[]).await;
+
+    // the runtime calls the member linker once a class is ready, which never happens here;
+    // the class record stands in for its class object so that the public link names the class
+    let generated_classes = state.lock().generated_classes;
+    for ptr_class in generated_class_records(core, generated_classes)? {
+        let class: RawJavaClass = read_generic(core, ptr_class)?;
+        let descriptor: RawJavaClassDescriptor = read_generic(core, class.ptr_descriptor)?;
+        if descriptor.fn_link_members != 0 {
+            let stage = format!("member linker of {}", read_string(core, descriptor.ptr_name)?);
+            run_stage(core, &stage, descriptor.fn_link_members, &[ptr_class]).await;
+        }
+    }
+
+    Ok(state)
+}
+
+async fn run_stage(core: &mut ArmCore, stage: &str, address: u32, params: &[u32]) {
+    let context = core.save_context();
+    if let Err(err) = core.run_function::<()>(address, params).await {
+        tracing::warn!("{stage} at {address:#x} failed, keeping what was recorded so far: {err}");
+        core.restore_context(&context);
+    }
+}
+
+async fn handle_init_svc(core: &mut ArmCore, state: &mut BootstrapState, id: SvcId) -> Result<JumpTo> {
+    let (_, lr) = core.read_pc_lr()?;
+    let import_table = core.read_param(0)?;
+    // stub ids follow the order of the resolver block: import table, then import function
+    let result = match id.0 {
+        0 => import_table,
+        _ => {
+            let function_index = core.read_param(1)?;
+            let stub = core.make_svc_stub(SVC_CATEGORY_DUMP_IMPORT, (import_table << 16) | function_index)?;
+            state.lock().imports.insert(stub, (import_table, function_index));
+
+            stub
+        }
+    };
+    result.write(core, lr)?;
+
+    Ok(JumpTo(lr))
+}
+
+// an import resolved the way the runtime resolves it
+enum Import {
+    Init(InitSvcId),
+    JavaSystem(JavaSystemSvcId),
+    Wipic(WIPICSvcId),
+    Stdlib(StdlibSvcId),
+}
+
+fn resolve_import(import_table: u32, function_index: u32) -> Option<Import> {
+    let (category, id) = import_svc(import_table, function_index)?;
+    let id = SvcId(id);
+
+    Some(match category {
+        SVC_CATEGORY_INIT => Import::Init(id.try_into().ok()?),
+        SVC_CATEGORY_JAVA_SYSTEM => Import::JavaSystem(id.try_into().ok()?),
+        SVC_CATEGORY_WIPIC => Import::Wipic(id.try_into().ok()?),
+        SVC_CATEGORY_STDLIB => Import::Stdlib(id.try_into().ok()?),
+        _ => return None,
+    })
+}
+
+async fn handle_import_svc(core: &mut ArmCore, state: &mut BootstrapState, id: SvcId) -> Result<JumpTo> {
+    let (_, lr) = core.read_pc_lr()?;
+    let (import_table, function_index) = (id.0 >> 16, id.0 & 0xffff);
+
+    match resolve_import(import_table, function_index) {
+        Some(Import::Init(InitSvcId::GetApplicationJarPath)) => {
+            let path_output = core.read_param(2)?;
+            let ptr_jar_path = state.lock().ptr_jar_path;
+            write_generic(core, path_output, ptr_jar_path)?;
+            0u32.write(core, lr)
+        }
+        Some(Import::JavaSystem(JavaSystemSvcId::CreateRuntimeContext)) => {
+            let generated_classes = core.read_param(0)?;
+            state.lock().generated_classes = generated_classes;
+            generated_classes.write(core, lr)
+        }
+        // resolving a class returns its class object; the class record stands in for it
+        Some(Import::JavaSystem(JavaSystemSvcId::ResolveClass)) => core.read_param(0)?.write(core, lr),
+        Some(Import::JavaSystem(JavaSystemSvcId::LinkPublicClass)) => {
+            let ptr_link = core.read_param(0)?;
+            let ptr_class = core.read_param(1)?;
+            let tables = read_params(core, 2)?;
+            state.lock().links.push((vec![(ptr_link, Some(ptr_class))], tables));
+            0u32.write(core, lr)
+        }
+        Some(Import::JavaSystem(JavaSystemSvcId::LinkImportedClasses)) => {
+            let imported_classes = core.read_param(0)?;
+            let tables = read_params(core, 1)?;
+            let count: u32 = read_generic(core, imported_classes)?;
+            let links = (0..count)
+                .map(|index| {
+                    (
+                        imported_classes + size_of::<u32>() as u32 + index * size_of::<RawJavaClassLink>() as u32,
+                        None,
+                    )
+                })
+                .collect();
+            state.lock().links.push((links, tables));
+            0u32.write(core, lr)
+        }
+        Some(Import::JavaSystem(JavaSystemSvcId::StartApplication)) => {
+            tracing::info!("Bootstrap reached the application start");
+            0u32.write(core, lr)
+        }
+        Some(Import::Wipic(WIPICSvcId::CletRegister)) => {
+            state.lock().ptr_clet_functions = core.read_param(0)?;
+            0u32.write(core, lr)
+        }
+        // generated initializers may copy their static data with these
+        Some(Import::Stdlib(StdlibSvcId::Memcpy)) => EmulatedFunction::call(&stdlib::memcpy, core, &mut ()).await?.write(core, lr),
+        Some(Import::Stdlib(StdlibSvcId::Memset)) => EmulatedFunction::call(&stdlib::memset, core, &mut ()).await?.write(core, lr),
+        Some(Import::Stdlib(StdlibSvcId::Strcpy)) => EmulatedFunction::call(&stdlib::strcpy, core, &mut ()).await?.write(core, lr),
+        Some(Import::Stdlib(StdlibSvcId::Strlen)) => EmulatedFunction::call(&stdlib::strlen, core, &mut ()).await?.write(core, lr),
+        _ => {
+            tracing::debug!("Ignoring {} during dump", import_name(core, import_table, function_index));
+            0u32.write(core, lr)
+        }
+    }?;
+
+    Ok(JumpTo(lr))
+}
+
+// the ten member and output tables passed to the link imports, starting at parameter `first`
+fn read_params(core: &ArmCore, first: usize) -> Result<[u32; 10]> {
+    let mut result = [0; 10];
+    for (index, value) in result.iter_mut().enumerate() {
+        *value = core.read_param(first + index)?;
+    }
+
+    Ok(result)
+}
+
+fn import_name(memory: &dyn ByteRead, import_table: u32, function_index: u32) -> String {
+    import_svc(import_table, function_index)
+        .and_then(|(category, id)| LgtSymbolProvider::new().svc_name(memory, category, id))
+        .unwrap_or_else(|| format!("import_{import_table:x}_{function_index:x}"))
+}
+
+fn read_string(memory: &dyn ByteRead, address: u32) -> Result<String> {
+    Ok(String::from_utf8_lossy(&read_null_terminated_string_bytes(memory, address)?).into_owned())
+}
+
+fn generated_class_records(memory: &dyn ByteRead, generated_classes: u32) -> Result<Vec<u32>> {
+    let mut result = Vec::new();
+    if generated_classes == 0 {
+        return Ok(result);
+    }
+
+    let last_bucket: u32 = read_generic(memory, generated_classes)?;
+    for bucket in 0..=last_bucket {
+        let mut ptr_class = read_generic(memory, generated_classes + size_of::<u32>() as u32 + bucket * size_of::<u32>() as u32)?;
+        while ptr_class != 0 {
+            result.push(ptr_class);
+
+            let class: RawJavaClass = read_generic(memory, ptr_class)?;
+            let descriptor: RawJavaClassDescriptor = read_generic(memory, class.ptr_descriptor)?;
+            ptr_class = descriptor.ptr_next_class;
+        }
+    }
+
+    Ok(result)
+}
+
+fn collect_image(core: &ArmCore, binary_mod: &[u8], state: &Bootstrap) -> Result<LgtImage> {
+    let elf = ElfBytes::<AnyEndian>::minimal_parse(binary_mod).map_err(|x| WieError::FatalError(format!("Failed to parse ELF binary.mod: {x}")))?;
+
+    let mut sections = Vec::new();
+    if let (Some(shdrs), Some(strtab)) = elf
+        .section_headers_with_strtab()
+        .map_err(|x| WieError::FatalError(format!("Failed to read ELF section headers: {x}")))?
+    {
+        for shdr in shdrs.iter().filter(|x| x.sh_addr != 0) {
+            let name = strtab
+                .get(shdr.sh_name as usize)
+                .map_err(|x| WieError::FatalError(format!("Invalid ELF section name index {}: {x}", shdr.sh_name)))?;
+            let mut data = vec![0; shdr.sh_size as usize];
+            core.read_bytes(shdr.sh_addr as u32, &mut data)?;
+
+            sections.push(LgtImageSection {
+                name: name.to_string(),
+                address: shdr.sh_addr as u32,
+                flags: shdr.sh_flags as u32,
+                data,
+            });
+        }
+    }
+
+    let mut symbols = Vec::new();
+    if let Some((symtab, strtab)) = elf
+        .symbol_table()
+        .map_err(|x| WieError::FatalError(format!("Failed to read ELF symbol table: {x}")))?
+    {
+        for symbol in symtab.iter().filter(|x| x.st_name != 0 && x.st_shndx != 0) {
+            let kind = match symbol.st_symtype() {
+                STT_FUNC => LgtImageSymbolKind::Function,
+                STT_OBJECT => LgtImageSymbolKind::Object,
+                _ => continue,
+            };
+            let name = strtab
+                .get(symbol.st_name as usize)
+                .map_err(|x| WieError::FatalError(format!("Invalid ELF symbol name index {}: {x}", symbol.st_name)))?;
+            symbols.push(symbol_of(kind, symbol.st_value as u32, symbol.st_size as u32, name.to_string()));
+        }
+    }
+
+    symbols.push(symbol_of(
+        LgtImageSymbolKind::Function,
+        elf.ehdr.e_entry as u32 + 1,
+        0,
+        "entrypoint".into(),
+    ));
+    collect_bootstrap_symbols(core, state, &sections, &mut symbols);
+
+    Ok(LgtImage {
+        entrypoint: elf.ehdr.e_entry as u32,
+        elf_flags: elf.ehdr.e_flags,
+        sections,
+        symbols,
+    })
+}
+
+fn symbol_of(kind: LgtImageSymbolKind, address: u32, size: u32, name: String) -> LgtImageSymbol {
+    LgtImageSymbol { address, size, name, kind }
+}
+
+fn collect_bootstrap_symbols(core: &ArmCore, state: &Bootstrap, sections: &[LgtImageSection], symbols: &mut Vec<LgtImageSymbol>) {
+    // resolved imports are patched into their thunks; any word holding a stub we handed out is an import slot
+    for section in sections {
+        for (offset, word) in section.data.chunks_exact(4).enumerate() {
+            let value = u32::from_le_bytes(word.try_into().unwrap());
+            if let Some((import_table, function_index)) = state.imports.get(&value) {
+                let name = format!("import:{}", import_name(core, *import_table, *function_index));
+                symbols.push(symbol_of(LgtImageSymbolKind::Object, section.address + offset as u32 * 4, 4, name));
+            }
+        }
+    }
+
+    if state.ptr_clet_functions != 0 {
+        match read_generic::<CletFunctions, _>(core, state.ptr_clet_functions) {
+            Ok(functions) => {
+                symbols.push(symbol_of(
+                    LgtImageSymbolKind::Object,
+                    state.ptr_clet_functions,
+                    size_of::<CletFunctions>() as u32,
+                    "clet:functions".into(),
+                ));
+                for (address, name) in [
+                    (functions.start_clet, "startClet"),
+                    (functions.pause_clet, "pauseClet"),
+                    (functions.resume_clet, "resumeClet"),
+                    (functions.destroy_clet, "destroyClet"),
+                    (functions.paint_clet, "paintClet"),
+                    (functions.handle_clet_event, "handleCletEvent"),
+                ] {
+                    if address != 0 {
+                        symbols.push(symbol_of(LgtImageSymbolKind::Function, address, 0, format!("clet:{name}")));
+                    }
+                }
+            }
+            Err(err) => tracing::warn!("Failed to read clet functions at {:#x}: {err}", state.ptr_clet_functions),
+        }
+    }
+
+    match generated_class_records(core, state.generated_classes) {
+        Ok(classes) => {
+            for ptr_class in classes {
+                if let Err(err) = class_symbols(core, ptr_class, symbols) {
+                    tracing::warn!("Failed to read generated class {ptr_class:#x}: {err}");
+                }
+            }
+        }
+        Err(err) => tracing::warn!("Failed to read generated classes at {:#x}: {err}", state.generated_classes),
+    }
+
+    for (links, tables) in &state.links {
+        for (ptr_link, ptr_class) in links {
+            if let Err(err) = link_symbols(core, *ptr_link, *ptr_class, tables, symbols) {
+                tracing::warn!("Failed to read class link {ptr_link:#x}: {err}");
+            }
+        }
+    }
+}
+
+fn class_symbols(memory: &dyn ByteRead, ptr_class: u32, symbols: &mut Vec<LgtImageSymbol>) -> Result<()> {
+    let class: RawJavaClass = read_generic(memory, ptr_class)?;
+    let descriptor: RawJavaClassDescriptor = read_generic(memory, class.ptr_descriptor)?;
+    let name = read_string(memory, descriptor.ptr_name)?;
+
+    symbols.push(symbol_of(
+        LgtImageSymbolKind::Object,
+        ptr_class,
+        size_of::<RawJavaClass>() as u32,
+        format!("class:{name}"),
+    ));
+    symbols.push(symbol_of(
+        LgtImageSymbolKind::Object,
+        class.ptr_descriptor,
+        size_of::<RawJavaClassDescriptor>() as u32,
+        format!("descriptor:{name}"),
+    ));
+    for (address, kind) in [
+        (descriptor.ptr_vtable, "vtable"),
+        (descriptor.ptr_methods, "methods"),
+        (descriptor.ptr_fields, "fields"),
+    ] {
+        if address != 0 {
+            symbols.push(symbol_of(LgtImageSymbolKind::Object, address, 0, format!("{kind}:{name}")));
+        }
+    }
+    for (address, kind) in [
+        (descriptor.fn_link_members, "link_members"),
+        (descriptor.fn_get_initialized_class, "get_initialized_class"),
+        (descriptor.fn_get_class, "get_class"),
+    ] {
+        if address != 0 {
+            symbols.push(symbol_of(LgtImageSymbolKind::Function, address, 0, format!("{kind}:{name}")));
+        }
+    }
+
+    if descriptor.ptr_methods == 0 {
+        return Ok(());
+    }
+    let count: u32 = read_generic(memory, descriptor.ptr_methods)?;
+    for index in 0..count {
+        let method: RawJavaMethod = read_generic(
+            memory,
+            descriptor.ptr_methods + size_of::<u32>() as u32 + index * size_of::<RawJavaMethod>() as u32,
+        )?;
+        if method.ptr_method != 0 {
+            let method_name = read_string(memory, method.ptr_name)?;
+            let method_descriptor = read_string(memory, method.ptr_descriptor)?;
+            symbols.push(symbol_of(
+                LgtImageSymbolKind::Function,
+                method.ptr_method,
+                0,
+                format!("{name}.{method_name}{method_descriptor}"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// names the output slots the link imports patch, so that generated code reading them shows what it accesses
+fn link_symbols(memory: &dyn ByteRead, ptr_link: u32, ptr_class: Option<u32>, tables: &[u32; 10], symbols: &mut Vec<LgtImageSymbol>) -> Result<()> {
+    let link: RawJavaClassLink = read_generic(memory, ptr_link)?;
+    let class_name = match ptr_class {
+        Some(ptr_class) => {
+            let class: RawJavaClass = read_generic(memory, ptr_class)?;
+            let descriptor: RawJavaClassDescriptor = read_generic(memory, class.ptr_descriptor)?;
+            read_string(memory, descriptor.ptr_name)?
+        }
+        None => read_string(memory, link.ptr_name)?,
+    };
+    let [
+        instance_field_imports,
+        static_field_imports,
+        virtual_method_imports,
+        interface_method_imports,
+        non_virtual_method_imports,
+        instance_field_word_indices,
+        static_field_word_indices,
+        virtual_method_indices,
+        interface_method_indices,
+        non_virtual_method_targets,
+    ] = *tables;
+
+    let member = |table: u32, index: u16, with_descriptor: bool| -> Result<String> {
+        let ptr_name: u32 = read_generic(memory, table + index as u32 * 2 * size_of::<u32>() as u32)?;
+        let name = read_string(memory, ptr_name)?;
+        if !with_descriptor {
+            return Ok(format!("{class_name}.{name}"));
+        }
+        let ptr_descriptor: u32 = read_generic(memory, table + (index as u32 * 2 + 1) * size_of::<u32>() as u32)?;
+
+        Ok(format!("{class_name}.{name}{}", read_string(memory, ptr_descriptor)?))
+    };
+
+    for (kind, imports, outputs, offset, count, with_descriptor) in [
+        (
+            "ifield",
+            instance_field_imports,
+            instance_field_word_indices,
+            link.instance_field_offset,
+            link.instance_field_count,
+            false,
+        ),
+        (
+            "sfield",
+            static_field_imports,
+            static_field_word_indices,
+            link.static_field_offset,
+            link.static_field_count,
+            false,
+        ),
+        (
+            "vmethod",
+            virtual_method_imports,
+            virtual_method_indices,
+            link.virtual_method_offset,
+            link.virtual_method_count,
+            true,
+        ),
+        (
+            "imethod",
+            interface_method_imports,
+            interface_method_indices,
+            link.interface_method_offset,
+            link.interface_method_count,
+            true,
+        ),
+    ] {
+        for index in offset..offset + count {
+            let address = outputs + index as u32 * size_of::<u16>() as u32;
+            symbols.push(symbol_of(
+                LgtImageSymbolKind::Object,
+                address,
+                2,
+                format!("{kind}:{}", member(imports, index, with_descriptor)?),
+            ));
+        }
+    }
+
+    for local_index in 0..link.non_virtual_method_count {
+        let index = link.non_virtual_method_offset + local_index;
+        let name = match local_index {
+            0 => format!("{class_name}.<get_initialized_class>"),
+            1 => format!("{class_name}.<get_class>"),
+            _ => member(non_virtual_method_imports, index, true)?,
+        };
+        let address = non_virtual_method_targets + index as u32 * size_of::<u32>() as u32;
+        symbols.push(symbol_of(LgtImageSymbolKind::Object, address, 4, format!("call:{name}")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::mem::size_of;
+
+    use elf::{ElfBytes, endian::AnyEndian};
+    use wipi_types::lgt::java::{
+        LgtJavaClass as RawJavaClass, LgtJavaClassDescriptor as RawJavaClassDescriptor, LgtJavaClassMethod as RawJavaMethod,
+    };
+
+    use wie_core_arm::{Allocator, ArmCore};
+    use wie_util::{Result, write_generic, write_null_terminated_string_bytes};
+
+    use super::{LgtImageSymbolKind, class_symbols, dump_image, generated_class_records};
+
+    #[futures_test::test]
+    async fn dump_helloworld_keeps_sections_and_names() {
+        let image = dump_image(include_bytes!("../../test_data/helloworld_lgt.zip")).await.unwrap();
+
+        assert!(image.sections.iter().any(|x| x.name == ".text" && x.address == 0x20124));
+        assert!(
+            image
+                .symbols
+                .iter()
+                .any(|x| x.name == "entrypoint" && x.address == 0x20145 && x.kind == LgtImageSymbolKind::Function)
+        );
+
+        let elf = image.to_elf();
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&elf).unwrap();
+        assert_eq!(file.ehdr.e_entry, 0x20144);
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        assert!(
+            symtab
+                .iter()
+                .any(|x| strtab.get(x.st_name as usize).is_ok_and(|x| x == "_start") && x.st_value == 0x20145)
+        );
+
+        assert!(
+            image
+                .symbols
+                .iter()
+                .any(|x| x.name == "clet:startClet" && x.kind == LgtImageSymbolKind::Function)
+        );
+
+        let symbol_file = image.to_symbol_file();
+        assert!(symbol_file.contains("0x00020144 entrypoint\n"), "{symbol_file}");
+    }
+
+    fn write_string(core: &mut ArmCore, value: &str) -> Result<u32> {
+        let address = Allocator::alloc(core, value.len() as u32 + 1)?;
+        write_null_terminated_string_bytes(core, address, value.as_bytes())?;
+
+        Ok(address)
+    }
+
+    // helloworld is a plain clet without generated classes, so the class table is laid out by hand
+    #[test]
+    fn generated_classes_name_their_records_and_aot_methods() -> Result<()> {
+        let mut core = ArmCore::new(false, None)?;
+        Allocator::init(&mut core)?;
+
+        let ptr_method_name = write_string(&mut core, "startApp")?;
+        let ptr_method_descriptor = write_string(&mut core, "([Ljava/lang/String;)V")?;
+        let ptr_methods = Allocator::alloc(&mut core, (size_of::<u32>() + size_of::<RawJavaMethod>()) as u32)?;
+        write_generic(&mut core, ptr_methods, 1u32)?;
+        write_generic(
+            &mut core,
+            ptr_methods + size_of::<u32>() as u32,
+            RawJavaMethod {
+                ptr_class: 0,
+                ptr_name: ptr_method_name,
+                ptr_descriptor: ptr_method_descriptor,
+                access_flags: 0,
+                argument_word_count: 2,
+                unk3: 0,
+                ptr_method: 0x20201,
+                unk4: 0,
+            },
+        )?;
+
+        let ptr_name = write_string(&mut core, "HelloWorld")?;
+        let ptr_descriptor = Allocator::alloc(&mut core, size_of::<RawJavaClassDescriptor>() as u32)?;
+        write_generic(
+            &mut core,
+            ptr_descriptor,
+            RawJavaClassDescriptor {
+                access_flags: 0,
+                ptr_next_class: 0,
+                ptr_name,
+                ptr_vtable: 0,
+                ptr_super_class: 0,
+                ptr_interface_references: 0,
+                instance_field_word_count: 0,
+                link_state: 0,
+                unk7: 0,
+                ptr_instance_reference_bitmap: 0,
+                flags: 0,
+                unk10: 0,
+                vtable_count: 0,
+                ptr_interface_names: 0,
+                fn_link_members: 0,
+                fn_get_initialized_class: 0,
+                fn_get_class: 0,
+                ptr_methods,
+                ptr_fields: 0,
+                ptr_class_fields: 0,
+                unk14: 0,
+                static_field_word_count: 0,
+            },
+        )?;
+        let ptr_class = Allocator::alloc(&mut core, size_of::<RawJavaClass>() as u32)?;
+        write_generic(
+            &mut core,
+            ptr_class,
+            RawJavaClass {
+                unk1: 0,
+                unk2: 0,
+                ptr_descriptor,
+            },
+        )?;
+
+        // one bucket holding the class
+        let generated_classes = Allocator::alloc(&mut core, 2 * size_of::<u32>() as u32)?;
+        write_generic(&mut core, generated_classes, 0u32)?;
+        write_generic(&mut core, generated_classes + size_of::<u32>() as u32, ptr_class)?;
+
+        let classes = generated_class_records(&core, generated_classes)?;
+        assert_eq!(classes, [ptr_class]);
+
+        let mut symbols = Vec::new();
+        class_symbols(&core, ptr_class, &mut symbols)?;
+        assert!(
+            symbols
+                .iter()
+                .any(|x| x.name == "class:HelloWorld" && x.address == ptr_class && x.kind == LgtImageSymbolKind::Object)
+        );
+        assert!(
+            symbols
+                .iter()
+                .any(|x| x.name == "HelloWorld.startApp([Ljava/lang/String;)V" && x.address == 0x20201 && x.kind == LgtImageSymbolKind::Function)
+        );
+
+        Ok(())
+    }
+}
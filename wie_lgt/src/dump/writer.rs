@@ -0,0 +1,283 @@
+use alloc::{vec, vec::Vec};
+
+use elf::abi::{
+    EM_ARM, ET_EXEC, PF_R, PF_W, PF_X, PT_LOAD, SHF_EXECINSTR, SHF_WRITE, SHN_ABS, SHT_PROGBITS, SHT_STRTAB, SHT_SYMTAB, STB_GLOBAL, STB_LOCAL,
+    STT_FUNC, STT_NOTYPE, STT_OBJECT,
+};
+
+use super::{LgtImage, LgtImageSymbolKind};
+
+const EHDR_SIZE: usize = 52;
+const PHDR_SIZE: usize = 32;
+const SHDR_SIZE: usize = 40;
+const SYM_SIZE: usize = 16;
+
+struct SectionHeader {
+    name: u32,
+    kind: u32,
+    flags: u32,
+    address: u32,
+    offset: u32,
+    size: u32,
+    link: u32,
+    info: u32,
+    entry_size: u32,
+}
+
+struct StringTable {
+    data: Vec<u8>,
+}
+
+impl StringTable {
+    fn new() -> Self {
+        Self { data: vec![0] }
+    }
+
+    fn add(&mut self, value: &str) -> u32 {
+        let offset = self.data.len() as u32;
+        self.data.extend_from_slice(value.as_bytes());
+        self.data.push(0);
+
+        offset
+    }
+}
+
+fn push_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn align(buf: &mut Vec<u8>) {
+    buf.resize(buf.len().next_multiple_of(4), 0);
+}
+
+fn push_symbol(buf: &mut Vec<u8>, name: u32, value: u32, size: u32, bind: u8, kind: u8, section: u16) {
+    push_u32(buf, name);
+    push_u32(buf, value);
+    push_u32(buf, size);
+    buf.push((bind << 4) | kind);
+    buf.push(0);
+    push_u16(buf, section);
+}
+
+pub fn write_elf(image: &LgtImage) -> Vec<u8> {
+    let mut shstrtab = StringTable::new();
+    let mut strtab = StringTable::new();
+
+    // section index of an address, 1-based as index 0 is the null section
+    let section_of = |address: u32| {
+        image
+            .sections
+            .iter()
+            .position(|x| (x.address..x.address + x.data.len() as u32).contains(&address))
+            .map(|x| x as u16 + 1)
+            .unwrap_or(SHN_ABS)
+    };
+
+    // ARM mapping symbols go first as they are local; they tell disassemblers which functions are Thumb
+    let mut symtab = vec![0; SYM_SIZE];
+    let mapping_names = [strtab.add("$a"), strtab.add("$t")];
+    for symbol in image.symbols.iter().filter(|x| x.kind == LgtImageSymbolKind::Function) {
+        let address = symbol.address & !1;
+        push_symbol(
+            &mut symtab,
+            mapping_names[(symbol.address & 1) as usize],
+            address,
+            0,
+            STB_LOCAL,
+            STT_NOTYPE,
+            section_of(address),
+        );
+    }
+    let first_global = (symtab.len() / SYM_SIZE) as u32;
+    for symbol in &image.symbols {
+        let kind = match symbol.kind {
+            LgtImageSymbolKind::Function => STT_FUNC,
+            LgtImageSymbolKind::Object => STT_OBJECT,
+        };
+        let name = strtab.add(&symbol.name);
+        push_symbol(
+            &mut symtab,
+            name,
+            symbol.address,
+            symbol.size,
+            STB_GLOBAL,
+            kind,
+            section_of(symbol.address & !1),
+        );
+    }
+
+    let mut data = vec![0; EHDR_SIZE + PHDR_SIZE * image.sections.len()];
+    let mut headers = vec![SectionHeader {
+        name: 0,
+        kind: 0,
+        flags: 0,
+        address: 0,
+        offset: 0,
+        size: 0,
+        link: 0,
+        info: 0,
+        entry_size: 0,
+    }];
+
+    let mut program_headers = Vec::new();
+    for section in &image.sections {
+        align(&mut data);
+        let offset = data.len() as u32;
+        data.extend_from_slice(&section.data);
+
+        let mut flags = PF_R;
+        if section.flags & SHF_WRITE as u32 != 0 {
+            flags |= PF_W;
+        }
+        if section.flags & SHF_EXECINSTR as u32 != 0 {
+            flags |= PF_X;
+        }
+        program_headers.push((offset, section.address, section.data.len() as u32, flags));
+        headers.push(SectionHeader {
+            name: shstrtab.add(&section.name),
+            kind: SHT_PROGBITS,
+            flags: section.flags,
+            address: section.address,
+            offset,
+            size: section.data.len() as u32,
+            link: 0,
+            info: 0,
+            entry_size: 0,
+        });
+    }
+
+    let strtab_index = headers.len() as u32 + 1;
+    for (name, kind, contents, link, info, entry_size) in [
+        (".symtab", SHT_SYMTAB, &symtab, strtab_index, first_global, SYM_SIZE as u32),
+        (".strtab", SHT_STRTAB, &strtab.data, 0, 0, 0),
+    ] {
+        align(&mut data);
+        headers.push(SectionHeader {
+            name: shstrtab.add(name),
+            kind,
+            flags: 0,
+            address: 0,
+            offset: data.len() as u32,
+            size: contents.len() as u32,
+            link,
+            info,
+            entry_size,
+        });
+        data.extend_from_slice(contents);
+    }
+
+    let shstrtab_name = shstrtab.add(".shstrtab");
+    headers.push(SectionHeader {
+        name: shstrtab_name,
+        kind: SHT_STRTAB,
+        flags: 0,
+        address: 0,
+        offset: data.len() as u32,
+        size: shstrtab.data.len() as u32,
+        link: 0,
+        info: 0,
+        entry_size: 0,
+    });
+    data.extend_from_slice(&shstrtab.data);
+
+    align(&mut data);
+    let section_headers_offset = data.len() as u32;
+    for header in &headers {
+        for value in [
+            header.name,
+            header.kind,
+            header.flags,
+            header.address,
+            header.offset,
+            header.size,
+            header.link,
+            header.info,
+            4,
+            header.entry_size,
+        ] {
+            push_u32(&mut data, value);
+        }
+    }
+
+    let mut ehdr = Vec::with_capacity(EHDR_SIZE + PHDR_SIZE * program_headers.len());
+    ehdr.extend_from_slice(b"\x7fELF\x01\x01\x01");
+    ehdr.resize(16, 0);
+    push_u16(&mut ehdr, ET_EXEC);
+    push_u16(&mut ehdr, EM_ARM);
+    push_u32(&mut ehdr, 1);
+    push_u32(&mut ehdr, image.entrypoint);
+    push_u32(&mut ehdr, EHDR_SIZE as u32);
+    push_u32(&mut ehdr, section_headers_offset);
+    push_u32(&mut ehdr, image.elf_flags);
+    push_u16(&mut ehdr, EHDR_SIZE as u16);
+    push_u16(&mut ehdr, PHDR_SIZE as u16);
+    push_u16(&mut ehdr, program_headers.len() as u16);
+    push_u16(&mut ehdr, SHDR_SIZE as u16);
+    push_u16(&mut ehdr, headers.len() as u16);
+    push_u16(&mut ehdr, headers.len() as u16 - 1);
+
+    for (offset, address, size, flags) in program_headers {
+        for value in [PT_LOAD, offset, address, address, size, size, flags, 4] {
+            push_u32(&mut ehdr, value);
+        }
+    }
+
+    data[..ehdr.len()].copy_from_slice(&ehdr);
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    use elf::{ElfBytes, abi::STT_FUNC, endian::AnyEndian};
+
+    use crate::dump::{LgtImage, LgtImageSection, LgtImageSymbol, LgtImageSymbolKind};
+
+    use super::write_elf;
+
+    #[test]
+    fn written_elf_round_trips() {
+        let image = LgtImage {
+            entrypoint: 0x1000,
+            elf_flags: 0x5000000,
+            sections: vec![LgtImageSection {
+                name: ".text".to_string(),
+                address: 0x1000,
+                flags: 6,
+                data: vec![0x70, 0x47, 0x00, 0xbf, 0x12],
+            }],
+            symbols: vec![LgtImageSymbol {
+                address: 0x1003,
+                size: 2,
+                name: "Foo.bar()V".to_string(),
+                kind: LgtImageSymbolKind::Function,
+            }],
+        };
+
+        let data = write_elf(&image);
+        let file = ElfBytes::<AnyEndian>::minimal_parse(&data).unwrap();
+        assert_eq!(file.ehdr.e_entry, 0x1000);
+        assert_eq!(file.ehdr.e_flags, 0x5000000);
+
+        let text = file.section_header_by_name(".text").unwrap().unwrap();
+        assert_eq!(text.sh_addr, 0x1000);
+        assert_eq!(file.section_data(&text).unwrap().0, [0x70, 0x47, 0x00, 0xbf, 0x12]);
+        assert_eq!(file.segments().unwrap().iter().count(), 1);
+
+        let (symtab, strtab) = file.symbol_table().unwrap().unwrap();
+        let symbols = symtab
+            .iter()
+            .map(|x| (strtab.get(x.st_name as usize).unwrap().to_string(), x))
+            .collect::<Vec<_>>();
+        let (_, mapping) = symbols.iter().find(|(name, _)| name == "$t").unwrap();
+        assert_eq!(mapping.st_value, 0x1002);
+        let (_, function) = symbols.iter().find(|(name, _)| name == "Foo.bar()V").unwrap();
+        assert_eq!((function.st_value, function.st_symtype(), function.st_shndx), (0x1003, STT_FUNC, 1));
+    }
+}
@@ -1,6 +1,6 @@
 use core::pin::Pin;
 
-use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String, vec::Vec};
+use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String, sync::Arc, vec::Vec};
 
 use jvm::{
     JavaError,
@@ -12,7 +12,7 @@ use wie_core_arm::{Allocator, ArmCore};
 use wie_jvm_support::JvmSupport;
 use wie_util::{Result, WieError};
 
-use crate::runtime::{LgtJvmSupport, init::load_native};
+use crate::runtime::{LgtJvmSupport, LgtSymbolProvider, init::load_native};
 
 struct LgtTaskRunner {
     core: ArmCore,
@@ -88,6 +88,10 @@ impl LgtEmulator {
         mut options: Options,
     ) -> Result<Self> {
         let mut core = ArmCore::new(options.enable_gdbserver, options.profile.take())?;
+        if let Some(trace) = options.trace.take() {
+            core.set_trace(trace);
+        }
+        core.register_symbol_provider(Arc::new(LgtSymbolProvider::new()));
         let system = System::new(platform, pid, aid, LgtTaskRunner { core: core.clone() });
 
         for (filename, data) in files {
@@ -95,8 +99,18 @@ impl LgtEmulator {
             system.filesystem().add_virtual(filename, data.clone())
         }
 
+        if options.heap_debug {
+            Allocator::enable_debug(&mut core);
+        }
         Allocator::init(&mut core)?;
 
+        if !options.binary_patches.is_empty() {
+            tracing::warn!(
+                "Binary patches are only applied to KTF binaries, ignoring {} patch files",
+                options.binary_patches.len()
+            );
+        }
+
         let main_class_name = main_class_name.map(|x| x.replace('.', "/"));
 
         let mut core_clone = core.clone();
@@ -140,6 +154,10 @@ impl Emulator for LgtEmulator {
         self.system.event_queue().push(event)
     }
 
+    fn aid(&self) -> &str {
+        self.system.aid()
+    }
+
     fn tick(&mut self) -> Result<()> {
         self.system.tick().map_err(|x| {
             let reg_stack = self.core.dump_reg_stack(0x1000); // TODO: hardcode
@@ -152,10 +170,10 @@ impl Emulator for LgtEmulator {
 }
 
 // almost similar to KtfAdf.. can we merge these?
-struct LgtAppInfo {
-    aid: String,
-    pid: String,
-    mclass: String,
+pub(crate) struct LgtAppInfo {
+    pub aid: String,
+    pub pid: String,
+    pub mclass: String,
 }
 
 impl LgtAppInfo {
@@ -1,7 +1,10 @@
 #![no_std]
 extern crate alloc;
 
+mod dump;
 mod emulator;
 mod runtime;
 
+pub use dump::{LgtImage, LgtImageSection, LgtImageSymbol, LgtImageSymbolKind, dump_image};
 pub use emulator::LgtEmulator;
+pub use runtime::init::load_executable;
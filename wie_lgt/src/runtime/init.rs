@@ -1,18 +1,22 @@
-use alloc::format;
+use alloc::{format, vec::Vec};
 use core::mem::size_of;
 
-use elf::{ElfBytes, endian::AnyEndian};
+use elf::{
+    ElfBytes,
+    abi::{SHF_EXECINSTR, SHF_WRITE},
+    endian::AnyEndian,
+};
 
 use jvm::Jvm;
 use wipi_types::lgt::{InitParam1, InitParam2, InitStruct};
 
 use wie_backend::System;
-use wie_core_arm::{Allocator, ArmCore, EmulatedFunction, JumpTo, ResultWriter, SvcId};
+use wie_core_arm::{Allocator, ArmCore, EmulatedFunction, JumpTo, MemoryPermission, ResultWriter, SvcId};
 use wie_util::{Result, WieError, read_generic, write_generic, write_null_terminated_string_bytes};
 
 use super::{
     SVC_CATEGORY_INIT, SVC_CATEGORY_JAVA_SYSTEM, SVC_CATEGORY_STDLIB, SVC_CATEGORY_WIPIC,
-    java::{get_java_interface_method, register_java_system_svc_handler},
+    java::{get_java_interface_method, java_interface_svc_id, register_java_system_svc_handler},
     stdlib::register_stdlib_svc_handler,
     svc_ids::{InitSvcId, JavaSystemSvcId},
     wipi_c::register_wipic_svc_handler,
@@ -39,7 +43,7 @@ async fn handle_init_svc(core: &mut ArmCore, ptr_jar_path: &mut u32, id: SvcId)
 }
 
 pub async fn load_native(core: &mut ArmCore, system: &mut System, jvm: &Jvm, jar_path: &str, data: &[u8]) -> Result<()> {
-    let entrypoint = load_executable(core, data)?;
+    let (entrypoint, _) = load_executable(core, data)?;
     register_wipic_svc_handler(core, system, jvm)?;
     register_stdlib_svc_handler(core, system)?;
     let ptr_jar_path_value = Allocator::alloc(core, (jar_path.len() + 1) as u32)?;
@@ -95,31 +99,35 @@ async fn get_import_table(_core: &mut ArmCore, _: &mut (), import_table: u32) ->
 async fn get_import_function(core: &mut ArmCore, import_table: u32, function_index: u32) -> Result<u32> {
     tracing::debug!("get_import_function({import_table:#x}, {function_index})");
 
-    if import_table == 0x1fb {
-        return core.make_svc_stub(SVC_CATEGORY_WIPIC, function_index);
-    } else if import_table == 0x64 {
+    if import_table == 0x64 {
         return get_java_interface_method(core, function_index);
-    } else if import_table == 1 && function_index == 0x32 {
-        return core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::PendingException);
-    } else if import_table == 1 {
-        return core.make_svc_stub(SVC_CATEGORY_STDLIB, function_index);
     }
 
-    Ok(match (import_table, function_index) {
-        (0x1f8, 0x16) => core.make_svc_stub(SVC_CATEGORY_INIT, InitSvcId::Unk0)?,
-        (0x1f8, 0x17) => core.make_svc_stub(SVC_CATEGORY_INIT, InitSvcId::GetApplicationJarPath)?,
-        (0x1fc, 0x03) => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk1)?,
-        (0x1ff, 0x03) => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk2)?,
-        (0x201, 0x03) => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk3)?,
-        _ => {
-            return Err(WieError::FatalError(format!(
-                "Unknown import function: {import_table:#x}, {function_index:#x}"
-            )));
-        }
+    let (category, id) = import_svc(import_table, function_index)
+        .ok_or_else(|| WieError::FatalError(format!("Unknown import function: {import_table:#x}, {function_index:#x}")))?;
+
+    core.make_svc_stub(category, id)
+}
+
+/// SVC `(category, id)` implementing function `function_index` of import table `import_table`.
+pub fn import_svc(import_table: u32, function_index: u32) -> Option<(u32, u32)> {
+    Some(match (import_table, function_index) {
+        (0x1fb, _) => (SVC_CATEGORY_WIPIC, function_index),
+        (0x64, _) => (SVC_CATEGORY_JAVA_SYSTEM, java_interface_svc_id(function_index)?.into()),
+        (1, 0x32) => (SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::PendingException.into()),
+        (1, _) => (SVC_CATEGORY_STDLIB, function_index),
+        (0x1f8, 0x16) => (SVC_CATEGORY_INIT, InitSvcId::Unk0.into()),
+        (0x1f8, 0x17) => (SVC_CATEGORY_INIT, InitSvcId::GetApplicationJarPath.into()),
+        (0x1fc, 0x03) => (SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk1.into()),
+        (0x1ff, 0x03) => (SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk2.into()),
+        (0x201, 0x03) => (SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk3.into()),
+        _ => return None,
     })
 }
 
-fn load_executable(core: &mut ArmCore, data: &[u8]) -> Result<u32> {
+/// Map the sections of `binary.mod` at their ELF addresses. Returns the
+/// entrypoint and the `(address, size)` of each executable section.
+pub fn load_executable(core: &mut ArmCore, data: &[u8]) -> Result<(u32, Vec<(u32, u32)>)> {
     let elf = ElfBytes::<AnyEndian>::minimal_parse(data).map_err(|x| WieError::FatalError(format!("Failed to parse ELF binary.mod: {x}")))?;
 
     if elf.ehdr.e_machine != elf::abi::EM_ARM {
@@ -138,6 +146,7 @@ fn load_executable(core: &mut ArmCore, data: &[u8]) -> Result<u32> {
     let shdrs = shdrs_opt.ok_or_else(|| WieError::FatalError("ELF is missing section headers".into()))?;
     let strtab = strtab_opt.ok_or_else(|| WieError::FatalError("ELF is missing section name string table".into()))?;
 
+    let mut code_ranges = Vec::new();
     for shdr in shdrs {
         let section_name = strtab
             .get(shdr.sh_name as usize)
@@ -151,13 +160,21 @@ fn load_executable(core: &mut ArmCore, data: &[u8]) -> Result<u32> {
                 .map_err(|x| WieError::FatalError(format!("Failed to read ELF section {section_name}: {x}")))?
                 .0;
 
-            core.load(data, shdr.sh_addr as u32, shdr.sh_size as usize)?;
+            let permission = match (shdr.sh_flags & SHF_EXECINSTR as u64 != 0, shdr.sh_flags & SHF_WRITE as u64 != 0) {
+                (true, false) => MemoryPermission::ReadExecute,
+                (true, true) => MemoryPermission::ReadWriteExecute,
+                (false, _) => MemoryPermission::ReadWrite,
+            };
+            core.load(data, shdr.sh_addr as u32, shdr.sh_size as usize, permission)?;
+            if shdr.sh_flags & SHF_EXECINSTR as u64 != 0 {
+                code_ranges.push((shdr.sh_addr as u32, shdr.sh_size as u32));
+            }
         }
     }
 
     tracing::debug!("Entrypoint: {:#x}", elf.ehdr.e_entry);
 
-    Ok(elf.ehdr.e_entry as u32)
+    Ok((elf.ehdr.e_entry as u32, code_ranges))
 }
 
 async fn unk0(_core: &mut ArmCore, _: &mut (), a0: u32, a1: u32, a2: u32, a3: u32) -> Result<()> {
@@ -16,7 +16,7 @@ mod exception;
 mod interface;
 mod jvm_support;
 
-pub use interface::{get_java_interface_method, register_java_system_svc_handler};
+pub use interface::{get_java_interface_method, java_interface_svc_id, register_java_system_svc_handler};
 pub use jvm_support::LgtJvmSupport;
 
 pub type JavaSvcFunctions = Arc<Mutex<BTreeMap<u32, Arc<Box<dyn RegisteredFunction>>>>>;
@@ -22,39 +22,46 @@ use crate::runtime::{
 };
 
 pub fn get_java_interface_method(core: &mut ArmCore, function_index: u32) -> Result<u32> {
-    Ok(match function_index {
-        0x03 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::InterfaceUnk0)?,
-        0x06 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::DestroyRuntimeContext)?,
-        0x07 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::CreateRuntimeContext)?,
-        0x09 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::StringLiteral)?,
-        0x0a => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::GetInterfaceDispatchTable)?,
-        0x0b => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::RegisterClass)?,
-        0x0c => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::ResolveClass)?,
-        0x0d => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::InitializeClass)?,
-        0x0e => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::GetArrayType)?,
-        0x0f => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Instantiate)?,
-        0x10 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::InstantiateArray)?,
-        0x11 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::InstantiateMultiArray)?,
-        0x12 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::IsClassAssignable)?,
-        0x13 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::LinkPublicClass)?,
-        0x14 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::LinkImportedClasses)?,
-        0x1f => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::PushExceptionFrame)?,
-        0x20 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::PopExceptionFrame)?,
-        0x21 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::ThrowException)?,
-        0x22 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::RaiseNullPointerException)?,
-        0x23 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::RaiseArrayIndexException)?,
-        0x25 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::RaiseArithmeticException)?,
-        0x54 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk54)?,
-        0x55 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::Unk55)?,
-        0x56 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::MonitorEnter)?,
-        0x57 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::MonitorExit)?,
-        0x61 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::StoreReferenceArray)?,
-        0x82 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::SetJarPath)?,
-        0x83 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::StartApplication)?,
-        0xe1 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::GetStringClass)?,
-        0xe2 => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::GetStringArrayClass)?,
-        0xfa => core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, JavaSystemSvcId::StoreReferenceArrayUnchecked)?,
-        _ => return Err(WieError::FatalError(format!("Unknown lgt java import: {function_index:#x}"))),
+    let id = java_interface_svc_id(function_index).ok_or_else(|| WieError::FatalError(format!("Unknown lgt java import: {function_index:#x}")))?;
+
+    core.make_svc_stub(SVC_CATEGORY_JAVA_SYSTEM, id)
+}
+
+/// SVC id implementing function `function_index` of the java import table.
+pub fn java_interface_svc_id(function_index: u32) -> Option<JavaSystemSvcId> {
+    Some(match function_index {
+        0x03 => JavaSystemSvcId::InterfaceUnk0,
+        0x06 => JavaSystemSvcId::DestroyRuntimeContext,
+        0x07 => JavaSystemSvcId::CreateRuntimeContext,
+        0x09 => JavaSystemSvcId::StringLiteral,
+        0x0a => JavaSystemSvcId::GetInterfaceDispatchTable,
+        0x0b => JavaSystemSvcId::RegisterClass,
+        0x0c => JavaSystemSvcId::ResolveClass,
+        0x0d => JavaSystemSvcId::InitializeClass,
+        0x0e => JavaSystemSvcId::GetArrayType,
+        0x0f => JavaSystemSvcId::Instantiate,
+        0x10 => JavaSystemSvcId::InstantiateArray,
+        0x11 => JavaSystemSvcId::InstantiateMultiArray,
+        0x12 => JavaSystemSvcId::IsClassAssignable,
+        0x13 => JavaSystemSvcId::LinkPublicClass,
+        0x14 => JavaSystemSvcId::LinkImportedClasses,
+        0x1f => JavaSystemSvcId::PushExceptionFrame,
+        0x20 => JavaSystemSvcId::PopExceptionFrame,
+        0x21 => JavaSystemSvcId::ThrowException,
+        0x22 => JavaSystemSvcId::RaiseNullPointerException,
+        0x23 => JavaSystemSvcId::RaiseArrayIndexException,
+        0x25 => JavaSystemSvcId::RaiseArithmeticException,
+        0x54 => JavaSystemSvcId::Unk54,
+        0x55 => JavaSystemSvcId::Unk55,
+        0x56 => JavaSystemSvcId::MonitorEnter,
+        0x57 => JavaSystemSvcId::MonitorExit,
+        0x61 => JavaSystemSvcId::StoreReferenceArray,
+        0x82 => JavaSystemSvcId::SetJarPath,
+        0x83 => JavaSystemSvcId::StartApplication,
+        0xe1 => JavaSystemSvcId::GetStringClass,
+        0xe2 => JavaSystemSvcId::GetStringArrayClass,
+        0xfa => JavaSystemSvcId::StoreReferenceArrayUnchecked,
+        _ => return None,
     })
 }
 
@@ -421,6 +421,7 @@ impl JavaClassDefinition {
 
         let mut descriptor = self.descriptor()?;
         let class_name = ClassDefinition::name(self);
+        core.note_loaded_class(self.ptr_raw, &class_name);
         let interface_names = if descriptor.ptr_interface_names != 0 {
             ClassDefinition::interface_names(self)
         } else if descriptor.ptr_interface_references != 0 {
@@ -263,7 +263,7 @@ mod tests {
 
     use test_utils::TestPlatform;
     use wie_backend::{DefaultTaskRunner, System};
-    use wie_core_arm::{Allocator, ArmCore};
+    use wie_core_arm::{Allocator, ArmCore, MemoryPermission};
     use wie_jvm_support::{JvmImplementation, JvmSupport};
     use wie_util::{Result, write_generic, write_null_terminated_string_bytes};
 
@@ -295,7 +295,9 @@ mod tests {
             let implementation = LgtJvmImplementation::new(&mut core)?;
             let jvm = JvmSupport::new_jvm(&system_clone, None, Box::new(protos), &[], implementation.clone()).await?;
 
-            let ptr_aot = Allocator::alloc(&mut core, 6)?;
+            // the heap isn't executable, so the guest code gets a mapping of its own
+            let ptr_aot = 0x10000;
+            core.map(ptr_aot, 0x1000, MemoryPermission::ReadExecute)?;
             write_generic(&mut core, ptr_aot, 0x1808u16)?; // adds r0, r1, r0
             write_generic(&mut core, ptr_aot + 2, 0x0011u16)?; // movs r1, r2
             write_generic(&mut core, ptr_aot + 4, 0x4770u16)?; // bx lr
@@ -357,7 +359,7 @@ mod tests {
             let method = class.method("wide", "(JD)J", true).unwrap();
             let target = method.as_any().downcast_ref::<JavaMethod>().unwrap().target()?;
 
-            let ptr_wrapper = Allocator::alloc(&mut core, 12)?;
+            let ptr_wrapper = ptr_aot + 0x10;
             write_generic(&mut core, ptr_wrapper, 0xb500u16)?; // push {lr}
             write_generic(&mut core, ptr_wrapper + 2, 0x4c01u16)?; // ldr r4, [pc, #4]
             write_generic(&mut core, ptr_wrapper + 4, 0x47a0u16)?; // blx r4
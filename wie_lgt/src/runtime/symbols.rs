@@ -0,0 +1,104 @@
+use alloc::{format, string::String, vec::Vec};
+use core::mem::size_of;
+
+use spin::Mutex;
+use wipi_types::lgt::java::{LgtJavaClass as RawJavaClass, LgtJavaClassDescriptor as RawJavaClassDescriptor, LgtJavaClassMethod as RawJavaMethod};
+
+use wie_core_arm::{MethodSymbols, SvcId, SymbolProvider};
+use wie_util::{ByteRead, Result, read_generic, read_null_terminated_string_bytes};
+
+use super::{
+    SVC_CATEGORY_INIT, SVC_CATEGORY_JAVA, SVC_CATEGORY_JAVA_SYSTEM, SVC_CATEGORY_MISSING_JAVA_VTABLE_ENTRY, SVC_CATEGORY_STDLIB, SVC_CATEGORY_WIPIC,
+    svc_ids::{InitSvcId, JavaSystemSvcId, StdlibSvcId, WIPICSvcId},
+};
+
+#[derive(Default)]
+struct State {
+    // classes whose methods weren't read yet; they are read on the first lookup, when the class is fully set up
+    pending_classes: Vec<u32>,
+    methods: MethodSymbols,
+}
+
+/// Names AOT-compiled java methods and LGT SVC stubs.
+#[derive(Default)]
+pub struct LgtSymbolProvider {
+    state: Mutex<State>,
+}
+
+impl LgtSymbolProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read_string(memory: &dyn ByteRead, address: u32) -> Result<String> {
+        Ok(String::from_utf8_lossy(&read_null_terminated_string_bytes(memory, address)?).into_owned())
+    }
+
+    fn method_name(memory: &dyn ByteRead, ptr_method: u32) -> Result<String> {
+        let method: RawJavaMethod = read_generic(memory, ptr_method)?;
+        let class: RawJavaClass = read_generic(memory, method.ptr_class)?;
+        let descriptor: RawJavaClassDescriptor = read_generic(memory, class.ptr_descriptor)?;
+
+        Ok(format!(
+            "{}.{}",
+            Self::read_string(memory, descriptor.ptr_name)?,
+            Self::read_string(memory, method.ptr_name)?
+        ))
+    }
+
+    fn read_methods(memory: &dyn ByteRead, ptr_class: u32, methods: &mut MethodSymbols) -> Result<()> {
+        let class: RawJavaClass = read_generic(memory, ptr_class)?;
+        methods.add_boundary(ptr_class);
+        methods.add_boundary(class.ptr_descriptor);
+
+        let descriptor: RawJavaClassDescriptor = read_generic(memory, class.ptr_descriptor)?;
+        methods.add_boundary(descriptor.ptr_name);
+        if descriptor.ptr_methods == 0 {
+            return Ok(());
+        }
+        methods.add_boundary(descriptor.ptr_methods);
+
+        let count: u32 = read_generic(memory, descriptor.ptr_methods)?;
+        for index in 0..count as usize {
+            let ptr_method = descriptor.ptr_methods + size_of::<u32>() as u32 + (index * size_of::<RawJavaMethod>()) as u32;
+            let method: RawJavaMethod = read_generic(memory, ptr_method)?;
+            methods.add_boundary(ptr_method);
+            methods.add_boundary(method.ptr_name);
+            if method.ptr_method != 0 {
+                methods.add_method(method.ptr_method & !1, Self::method_name(memory, ptr_method)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SymbolProvider for LgtSymbolProvider {
+    fn on_class_loaded(&self, address: u32, _name: &str) {
+        self.state.lock().pending_classes.push(address);
+    }
+
+    fn symbolize(&self, memory: &dyn ByteRead, address: u32) -> Option<String> {
+        let mut state = self.state.lock();
+        let state = &mut *state;
+        for ptr_class in state.pending_classes.drain(..) {
+            if let Err(err) = Self::read_methods(memory, ptr_class, &mut state.methods) {
+                tracing::warn!("Failed to read methods of class {ptr_class:#x}: {err}");
+            }
+        }
+
+        state.methods.symbolize(address)
+    }
+
+    fn svc_name(&self, memory: &dyn ByteRead, category: u32, id: u32) -> Option<String> {
+        match category {
+            SVC_CATEGORY_INIT => InitSvcId::try_from(SvcId(id)).ok().map(|x| format!("{x:?}")),
+            SVC_CATEGORY_JAVA_SYSTEM => JavaSystemSvcId::try_from(SvcId(id)).ok().map(|x| format!("{x:?}")),
+            SVC_CATEGORY_WIPIC => WIPICSvcId::try_from(SvcId(id)).ok().map(|x| format!("MC_{x:?}")),
+            SVC_CATEGORY_STDLIB => StdlibSvcId::try_from(SvcId(id)).ok().map(|x| format!("{x:?}")),
+            SVC_CATEGORY_JAVA => Self::method_name(memory, id).ok(),
+            SVC_CATEGORY_MISSING_JAVA_VTABLE_ENTRY => Some(format!("<missing vtable entry {id}>")),
+            _ => None,
+        }
+    }
+}
@@ -51,6 +51,14 @@ impl WIPICContext for LgtWIPICContext {
         Ok(())
     }
 
+    fn total_memory(&self) -> Result<WIPICWord> {
+        Ok(Allocator::total_memory())
+    }
+
+    fn free_memory(&self) -> Result<WIPICWord> {
+        Ok(Allocator::free_memory(&self.core))
+    }
+
     fn data_ptr(&self, memory: WIPICIndirectPtr) -> Result<WIPICWord> {
         Ok(memory.0)
     }
@@ -0,0 +1,32 @@
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+
+/// Dump an LGT game's binary.mod for static analysis.
+///
+/// Loads binary.mod at its ELF section addresses, runs the native bootstrap
+/// far enough to resolve imports and link tables, and writes an ELF with
+/// symbols named from the LGT metadata: AOT-compiled methods, class records,
+/// import slots and member link slots.
+#[derive(Parser)]
+struct Args {
+    /// LGT game zip archive, application jar, or a bare binary.mod.
+    input: PathBuf,
+    /// Output file.
+    output: PathBuf,
+    /// Write a plain `address name` symbol list instead of an ELF.
+    #[arg(long)]
+    symbols: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let data = fs::read(&args.input)?;
+    let image = futures::executor::block_on(wie_lgt::dump_image(&data)).map_err(|e| anyhow::anyhow!("{e}"))?;
+    if args.symbols {
+        fs::write(&args.output, image.to_symbol_file())?;
+    } else {
+        fs::write(&args.output, image.to_elf())?;
+    }
+    Ok(())
+}
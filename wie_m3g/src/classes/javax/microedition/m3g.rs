@@ -0,0 +1,31 @@
+mod appearance;
+mod background;
+mod camera;
+mod compositing_mode;
+mod fog;
+mod graphics_3d;
+mod group;
+mod image_2d;
+mod index_buffer;
+mod light;
+mod loader;
+mod material;
+mod mesh;
+mod node;
+mod object_3d;
+mod polygon_mode;
+mod sprite_3d;
+mod texture_2d;
+mod transform;
+mod transformable;
+mod triangle_strip_array;
+mod vertex_array;
+mod vertex_buffer;
+mod world;
+
+pub use {
+    appearance::Appearance, background::Background, camera::Camera, compositing_mode::CompositingMode, fog::Fog, graphics_3d::Graphics3D,
+    group::Group, image_2d::Image2D, index_buffer::IndexBuffer, light::Light, loader::Loader, material::Material, mesh::Mesh, node::Node,
+    object_3d::Object3D, polygon_mode::PolygonMode, sprite_3d::Sprite3D, texture_2d::Texture2D, transform::Transform, transformable::Transformable,
+    triangle_strip_array::TriangleStripArray, vertex_array::VertexArray, vertex_buffer::VertexBuffer, world::World,
+};
@@ -0,0 +1,259 @@
+use alloc::{format, vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::{CompositingMode, Fog, Material, PolygonMode, Texture2D},
+    renderer::Appearance as RendererAppearance,
+};
+
+const MAX_TEXTURE_UNITS: i32 = 2;
+
+// class javax.microedition.m3g.Appearance
+pub struct Appearance;
+
+impl Appearance {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Appearance",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setLayer", "(I)V", Self::set_layer, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getLayer", "()I", Self::get_layer, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setFog", "(Ljavax/microedition/m3g/Fog;)V", Self::set_fog, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getFog", "()Ljavax/microedition/m3g/Fog;", Self::get_fog, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setPolygonMode",
+                    "(Ljavax/microedition/m3g/PolygonMode;)V",
+                    Self::set_polygon_mode,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getPolygonMode",
+                    "()Ljavax/microedition/m3g/PolygonMode;",
+                    Self::get_polygon_mode,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setCompositingMode",
+                    "(Ljavax/microedition/m3g/CompositingMode;)V",
+                    Self::set_compositing_mode,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getCompositingMode",
+                    "()Ljavax/microedition/m3g/CompositingMode;",
+                    Self::get_compositing_mode,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setTexture",
+                    "(ILjavax/microedition/m3g/Texture2D;)V",
+                    Self::set_texture,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getTexture",
+                    "(I)Ljavax/microedition/m3g/Texture2D;",
+                    Self::get_texture,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setMaterial",
+                    "(Ljavax/microedition/m3g/Material;)V",
+                    Self::set_material,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getMaterial",
+                    "()Ljavax/microedition/m3g/Material;",
+                    Self::get_material,
+                    MethodAccessFlags::PUBLIC,
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("layer", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("fog", "Ljavax/microedition/m3g/Fog;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("polygonMode", "Ljavax/microedition/m3g/PolygonMode;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("compositingMode", "Ljavax/microedition/m3g/CompositingMode;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("material", "Ljavax/microedition/m3g/Material;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("texture0", "Ljavax/microedition/m3g/Texture2D;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("texture1", "Ljavax/microedition/m3g/Texture2D;", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Appearance::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        Ok(())
+    }
+
+    async fn set_layer(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, layer: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Appearance::setLayer({this:?}, {layer})");
+
+        if !(-63..=63).contains(&layer) {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "layer out of range").await);
+        }
+
+        jvm.put_field(&mut this, "layer", "I", layer).await
+    }
+
+    async fn get_layer(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Appearance::getLayer({this:?})");
+
+        jvm.get_field(&this, "layer", "I").await
+    }
+
+    async fn set_fog(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, fog: ClassInstanceRef<Fog>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Appearance::setFog({this:?}, {fog:?})");
+
+        jvm.put_field(&mut this, "fog", "Ljavax/microedition/m3g/Fog;", fog).await
+    }
+
+    async fn get_fog(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Fog>> {
+        tracing::debug!("javax.microedition.m3g.Appearance::getFog({this:?})");
+
+        jvm.get_field(&this, "fog", "Ljavax/microedition/m3g/Fog;").await
+    }
+
+    async fn set_polygon_mode(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        polygon_mode: ClassInstanceRef<PolygonMode>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Appearance::setPolygonMode({this:?}, {polygon_mode:?})");
+
+        jvm.put_field(&mut this, "polygonMode", "Ljavax/microedition/m3g/PolygonMode;", polygon_mode)
+            .await
+    }
+
+    async fn get_polygon_mode(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<PolygonMode>> {
+        tracing::debug!("javax.microedition.m3g.Appearance::getPolygonMode({this:?})");
+
+        jvm.get_field(&this, "polygonMode", "Ljavax/microedition/m3g/PolygonMode;").await
+    }
+
+    async fn set_compositing_mode(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        compositing_mode: ClassInstanceRef<CompositingMode>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Appearance::setCompositingMode({this:?}, {compositing_mode:?})");
+
+        jvm.put_field(&mut this, "compositingMode", "Ljavax/microedition/m3g/CompositingMode;", compositing_mode)
+            .await
+    }
+
+    async fn get_compositing_mode(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+    ) -> JvmResult<ClassInstanceRef<CompositingMode>> {
+        tracing::debug!("javax.microedition.m3g.Appearance::getCompositingMode({this:?})");
+
+        jvm.get_field(&this, "compositingMode", "Ljavax/microedition/m3g/CompositingMode;").await
+    }
+
+    async fn set_texture(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        index: i32,
+        texture: ClassInstanceRef<Texture2D>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Appearance::setTexture({this:?}, {index}, {texture:?})");
+
+        if !(0..MAX_TEXTURE_UNITS).contains(&index) {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid texture unit").await);
+        }
+
+        jvm.put_field(&mut this, &format!("texture{index}"), "Ljavax/microedition/m3g/Texture2D;", texture)
+            .await
+    }
+
+    async fn get_texture(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        index: i32,
+    ) -> JvmResult<ClassInstanceRef<Texture2D>> {
+        tracing::debug!("javax.microedition.m3g.Appearance::getTexture({this:?}, {index})");
+
+        if !(0..MAX_TEXTURE_UNITS).contains(&index) {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid texture unit").await);
+        }
+
+        jvm.get_field(&this, &format!("texture{index}"), "Ljavax/microedition/m3g/Texture2D;")
+            .await
+    }
+
+    async fn set_material(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        material: ClassInstanceRef<Material>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Appearance::setMaterial({this:?}, {material:?})");
+
+        jvm.put_field(&mut this, "material", "Ljavax/microedition/m3g/Material;", material).await
+    }
+
+    async fn get_material(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Material>> {
+        tracing::debug!("javax.microedition.m3g.Appearance::getMaterial({this:?})");
+
+        jvm.get_field(&this, "material", "Ljavax/microedition/m3g/Material;").await
+    }
+
+    pub(crate) async fn appearance(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<RendererAppearance> {
+        let mut result = RendererAppearance {
+            layer: jvm.get_field(this, "layer", "I").await?,
+            ..Default::default()
+        };
+
+        let polygon_mode: ClassInstanceRef<PolygonMode> = jvm.get_field(this, "polygonMode", "Ljavax/microedition/m3g/PolygonMode;").await?;
+        if !polygon_mode.is_null() {
+            result.polygon_mode = PolygonMode::polygon_mode(jvm, &polygon_mode).await?;
+        }
+
+        let compositing_mode: ClassInstanceRef<CompositingMode> =
+            jvm.get_field(this, "compositingMode", "Ljavax/microedition/m3g/CompositingMode;").await?;
+        if !compositing_mode.is_null() {
+            result.compositing_mode = CompositingMode::compositing_mode(jvm, &compositing_mode).await?;
+        }
+
+        let material: ClassInstanceRef<Material> = jvm.get_field(this, "material", "Ljavax/microedition/m3g/Material;").await?;
+        if !material.is_null() {
+            result.material = Some(Material::material(jvm, &material).await?);
+        }
+
+        let fog: ClassInstanceRef<Fog> = jvm.get_field(this, "fog", "Ljavax/microedition/m3g/Fog;").await?;
+        if !fog.is_null() {
+            result.fog = Some(Fog::fog(jvm, &fog).await?);
+        }
+
+        // the renderer addresses textures by unit, so stop at the first empty one
+        for index in 0..MAX_TEXTURE_UNITS {
+            let texture: ClassInstanceRef<Texture2D> = jvm
+                .get_field(this, &format!("texture{index}"), "Ljavax/microedition/m3g/Texture2D;")
+                .await?;
+            if texture.is_null() {
+                break;
+            }
+            result.textures.push(Texture2D::texture(jvm, &texture).await?);
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,245 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::Image2D,
+    renderer::{Background as RendererBackground, BackgroundImage, BackgroundImageMode, from_argb},
+};
+
+const BORDER: i32 = 32;
+const REPEAT: i32 = 33;
+
+// class javax.microedition.m3g.Background
+pub struct Background;
+
+impl Background {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Background",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setColor", "(I)V", Self::set_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getColor", "()I", Self::get_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setImage",
+                    "(Ljavax/microedition/m3g/Image2D;)V",
+                    Self::set_image,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getImage",
+                    "()Ljavax/microedition/m3g/Image2D;",
+                    Self::get_image,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("setImageMode", "(II)V", Self::set_image_mode, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getImageModeX", "()I", Self::get_image_mode_x, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getImageModeY", "()I", Self::get_image_mode_y, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setCrop", "(IIII)V", Self::set_crop, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropX", "()I", Self::get_crop_x, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropY", "()I", Self::get_crop_y, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropWidth", "()I", Self::get_crop_width, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropHeight", "()I", Self::get_crop_height, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setColorClearEnable", "(Z)V", Self::set_color_clear_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isColorClearEnabled", "()Z", Self::is_color_clear_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setDepthClearEnable", "(Z)V", Self::set_depth_clear_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isDepthClearEnabled", "()Z", Self::is_depth_clear_enabled, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("color", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("image", "Ljavax/microedition/m3g/Image2D;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("imageModeX", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("imageModeY", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropX", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropY", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropWidth", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropHeight", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("colorClearEnabled", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthClearEnabled", "Z", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Background::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "imageModeX", "I", BORDER).await?;
+        jvm.put_field(&mut this, "imageModeY", "I", BORDER).await?;
+        jvm.put_field(&mut this, "colorClearEnabled", "Z", true).await?;
+        jvm.put_field(&mut this, "depthClearEnabled", "Z", true).await?;
+
+        Ok(())
+    }
+
+    async fn set_color(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, argb: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Background::setColor({this:?}, {argb:#x})");
+
+        jvm.put_field(&mut this, "color", "I", argb).await
+    }
+
+    async fn get_color(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Background::getColor({this:?})");
+
+        jvm.get_field(&this, "color", "I").await
+    }
+
+    async fn set_image(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, image: ClassInstanceRef<Image2D>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Background::setImage({this:?}, {image:?})");
+
+        let (width, height): (i32, i32) = if image.is_null() {
+            (0, 0)
+        } else {
+            (jvm.get_field(&image, "width", "I").await?, jvm.get_field(&image, "height", "I").await?)
+        };
+
+        jvm.put_field(&mut this, "image", "Ljavax/microedition/m3g/Image2D;", image).await?;
+        jvm.put_field(&mut this, "cropX", "I", 0).await?;
+        jvm.put_field(&mut this, "cropY", "I", 0).await?;
+        jvm.put_field(&mut this, "cropWidth", "I", width).await?;
+        jvm.put_field(&mut this, "cropHeight", "I", height).await
+    }
+
+    async fn get_image(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Image2D>> {
+        tracing::debug!("javax.microedition.m3g.Background::getImage({this:?})");
+
+        jvm.get_field(&this, "image", "Ljavax/microedition/m3g/Image2D;").await
+    }
+
+    async fn set_image_mode(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode_x: i32, mode_y: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Background::setImageMode({this:?}, {mode_x}, {mode_y})");
+
+        if !(BORDER..=REPEAT).contains(&mode_x) || !(BORDER..=REPEAT).contains(&mode_y) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid image mode").await);
+        }
+
+        jvm.put_field(&mut this, "imageModeX", "I", mode_x).await?;
+        jvm.put_field(&mut this, "imageModeY", "I", mode_y).await
+    }
+
+    async fn get_image_mode_x(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Background::getImageModeX({this:?})");
+
+        jvm.get_field(&this, "imageModeX", "I").await
+    }
+
+    async fn get_image_mode_y(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Background::getImageModeY({this:?})");
+
+        jvm.get_field(&this, "imageModeY", "I").await
+    }
+
+    async fn set_crop(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Background::setCrop({this:?}, {x}, {y}, {width}, {height})");
+
+        if width < 0 || height < 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "crop size < 0").await);
+        }
+
+        jvm.put_field(&mut this, "cropX", "I", x).await?;
+        jvm.put_field(&mut this, "cropY", "I", y).await?;
+        jvm.put_field(&mut this, "cropWidth", "I", width).await?;
+        jvm.put_field(&mut this, "cropHeight", "I", height).await
+    }
+
+    async fn get_crop_x(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Background::getCropX({this:?})");
+
+        jvm.get_field(&this, "cropX", "I").await
+    }
+
+    async fn get_crop_y(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Background::getCropY({this:?})");
+
+        jvm.get_field(&this, "cropY", "I").await
+    }
+
+    async fn get_crop_width(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Background::getCropWidth({this:?})");
+
+        jvm.get_field(&this, "cropWidth", "I").await
+    }
+
+    async fn get_crop_height(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Background::getCropHeight({this:?})");
+
+        jvm.get_field(&this, "cropHeight", "I").await
+    }
+
+    async fn set_color_clear_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Background::setColorClearEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "colorClearEnabled", "Z", enable).await
+    }
+
+    async fn is_color_clear_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Background::isColorClearEnabled({this:?})");
+
+        jvm.get_field(&this, "colorClearEnabled", "Z").await
+    }
+
+    async fn set_depth_clear_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Background::setDepthClearEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "depthClearEnabled", "Z", enable).await
+    }
+
+    async fn is_depth_clear_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Background::isDepthClearEnabled({this:?})");
+
+        jvm.get_field(&this, "depthClearEnabled", "Z").await
+    }
+
+    pub(crate) async fn background(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<RendererBackground> {
+        let color: i32 = jvm.get_field(this, "color", "I").await?;
+        let image: ClassInstanceRef<Image2D> = jvm.get_field(this, "image", "Ljavax/microedition/m3g/Image2D;").await?;
+
+        let image = if image.is_null() {
+            None
+        } else {
+            let mode = |mode| {
+                if mode == REPEAT {
+                    BackgroundImageMode::Repeat
+                } else {
+                    BackgroundImageMode::Border
+                }
+            };
+
+            Some(BackgroundImage {
+                image: Image2D::texture_image(jvm, &image).await?,
+                mode_x: mode(jvm.get_field(this, "imageModeX", "I").await?),
+                mode_y: mode(jvm.get_field(this, "imageModeY", "I").await?),
+                crop: (
+                    jvm.get_field(this, "cropX", "I").await?,
+                    jvm.get_field(this, "cropY", "I").await?,
+                    jvm.get_field(this, "cropWidth", "I").await?,
+                    jvm.get_field(this, "cropHeight", "I").await?,
+                ),
+            })
+        };
+
+        Ok(RendererBackground {
+            color: from_argb(color as u32),
+            color_clear: jvm.get_field(this, "colorClearEnabled", "Z").await?,
+            depth_clear: jvm.get_field(this, "depthClearEnabled", "Z").await?,
+            image,
+        })
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{classes::javax::microedition::m3g::Transform, renderer::Matrix4};
+
+const GENERIC: i32 = 48;
+const PARALLEL: i32 = 49;
+const PERSPECTIVE: i32 = 50;
+
+// class javax.microedition.m3g.Camera
+pub struct Camera;
+
+impl Camera {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Camera",
+            parent_class: Some("javax/microedition/m3g/Node"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setParallel", "(FFFF)V", Self::set_parallel, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setPerspective", "(FFFF)V", Self::set_perspective, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setGeneric",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    Self::set_generic,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getProjection",
+                    "(Ljavax/microedition/m3g/Transform;)I",
+                    Self::get_projection_transform,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getProjection", "([F)I", Self::get_projection_params, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("projectionType", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("params", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("projection", "[F", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Camera::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Node", "<init>", "()V", ()).await?;
+
+        let params = jvm.instantiate_array("F", 4).await?;
+        jvm.put_field(&mut this, "params", "[F", params).await?;
+        let projection = jvm.instantiate_array("F", 16).await?;
+        jvm.put_field(&mut this, "projection", "[F", projection).await?;
+
+        Self::set_projection(jvm, &mut this, GENERIC, [0.0; 4], &Matrix4::IDENTITY).await
+    }
+
+    async fn set_parallel(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        height: f32,
+        aspect_ratio: f32,
+        near: f32,
+        far: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Camera::setParallel({this:?}, {height}, {aspect_ratio}, {near}, {far})");
+
+        if height <= 0.0 || aspect_ratio <= 0.0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "height or aspectRatio <= 0").await);
+        }
+
+        let projection = Matrix4::parallel(height, aspect_ratio, near, far);
+        Self::set_projection(jvm, &mut this, PARALLEL, [height, aspect_ratio, near, far], &projection).await
+    }
+
+    async fn set_perspective(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        fovy: f32,
+        aspect_ratio: f32,
+        near: f32,
+        far: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Camera::setPerspective({this:?}, {fovy}, {aspect_ratio}, {near}, {far})");
+
+        if aspect_ratio <= 0.0 || near <= 0.0 || far <= 0.0 || fovy <= 0.0 || fovy >= 180.0 {
+            return Err(jvm
+                .exception("java/lang/IllegalArgumentException", "invalid perspective parameters")
+                .await);
+        }
+
+        let projection = Matrix4::perspective(fovy, aspect_ratio, near, far);
+        Self::set_projection(jvm, &mut this, PERSPECTIVE, [fovy, aspect_ratio, near, far], &projection).await
+    }
+
+    async fn set_generic(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Camera::setGeneric({this:?}, {transform:?})");
+
+        if transform.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "transform is null").await);
+        }
+
+        let projection = Transform::matrix(jvm, &transform).await?;
+        Self::set_projection(jvm, &mut this, GENERIC, [0.0; 4], &projection).await
+    }
+
+    async fn get_projection_transform(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Camera::getProjection({this:?}, {transform:?})");
+
+        if !transform.is_null() {
+            let projection = Self::projection(jvm, &this).await?;
+            Transform::set_matrix(jvm, &transform, &projection).await?;
+        }
+
+        jvm.get_field(&this, "projectionType", "I").await
+    }
+
+    async fn get_projection_params(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        mut params: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Camera::getProjection({this:?}, {params:?})");
+
+        let projection_type: i32 = jvm.get_field(&this, "projectionType", "I").await?;
+        if !params.is_null() && projection_type != GENERIC {
+            if jvm.array_length(&params).await? < 4 {
+                return Err(jvm.exception("java/lang/IllegalArgumentException", "params.length < 4").await);
+            }
+
+            let values = jvm.get_field(&this, "params", "[F").await?;
+            let values: Vec<f32> = jvm.load_array(&values, 0, 4).await?;
+            jvm.store_array(&mut params, 0, values).await?;
+        }
+
+        Ok(projection_type)
+    }
+
+    async fn set_projection(
+        jvm: &Jvm,
+        this: &mut ClassInstanceRef<Self>,
+        projection_type: i32,
+        params: [f32; 4],
+        projection: &Matrix4,
+    ) -> JvmResult<()> {
+        jvm.put_field(this, "projectionType", "I", projection_type).await?;
+
+        let mut params_array = jvm.get_field(this, "params", "[F").await?;
+        jvm.store_array(&mut params_array, 0, params.to_vec()).await?;
+
+        let mut projection_array = jvm.get_field(this, "projection", "[F").await?;
+        jvm.store_array(&mut projection_array, 0, projection.0.to_vec()).await
+    }
+
+    pub(crate) async fn projection<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<Matrix4> {
+        let projection = jvm.get_field(this, "projection", "[F").await?;
+        let values: Vec<f32> = jvm.load_array(&projection, 0, 16).await?;
+
+        Ok(Matrix4(values.try_into().unwrap()))
+    }
+}
@@ -0,0 +1,188 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::renderer::{Blending, CompositingMode as RendererCompositingMode};
+
+const ALPHA: i32 = 64;
+const ALPHA_ADD: i32 = 65;
+const MODULATE: i32 = 66;
+const MODULATE_X2: i32 = 67;
+const REPLACE: i32 = 68;
+
+// class javax.microedition.m3g.CompositingMode
+pub struct CompositingMode;
+
+impl CompositingMode {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/CompositingMode",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setBlending", "(I)V", Self::set_blending, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getBlending", "()I", Self::get_blending, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setAlphaThreshold", "(F)V", Self::set_alpha_threshold, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getAlphaThreshold", "()F", Self::get_alpha_threshold, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setAlphaWriteEnable", "(Z)V", Self::set_alpha_write_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isAlphaWriteEnabled", "()Z", Self::is_alpha_write_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setColorWriteEnable", "(Z)V", Self::set_color_write_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isColorWriteEnabled", "()Z", Self::is_color_write_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setDepthWriteEnable", "(Z)V", Self::set_depth_write_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isDepthWriteEnabled", "()Z", Self::is_depth_write_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setDepthTestEnable", "(Z)V", Self::set_depth_test_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isDepthTestEnabled", "()Z", Self::is_depth_test_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setDepthOffset", "(FF)V", Self::set_depth_offset, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getDepthOffsetFactor", "()F", Self::get_depth_offset_factor, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getDepthOffsetUnits", "()F", Self::get_depth_offset_units, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("blending", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("alphaThreshold", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("alphaWrite", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("colorWrite", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthWrite", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthTest", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthOffsetFactor", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthOffsetUnits", "F", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "blending", "I", REPLACE).await?;
+        jvm.put_field(&mut this, "alphaWrite", "Z", true).await?;
+        jvm.put_field(&mut this, "colorWrite", "Z", true).await?;
+        jvm.put_field(&mut this, "depthWrite", "Z", true).await?;
+        jvm.put_field(&mut this, "depthTest", "Z", true).await?;
+
+        Ok(())
+    }
+
+    async fn set_blending(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::setBlending({this:?}, {mode})");
+
+        if !(ALPHA..=REPLACE).contains(&mode) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid blending mode").await);
+        }
+
+        jvm.put_field(&mut this, "blending", "I", mode).await
+    }
+
+    async fn get_blending(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::getBlending({this:?})");
+
+        jvm.get_field(&this, "blending", "I").await
+    }
+
+    async fn set_alpha_threshold(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, threshold: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::setAlphaThreshold({this:?}, {threshold})");
+
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "threshold out of range").await);
+        }
+
+        jvm.put_field(&mut this, "alphaThreshold", "F", threshold).await
+    }
+
+    async fn get_alpha_threshold(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::getAlphaThreshold({this:?})");
+
+        jvm.get_field(&this, "alphaThreshold", "F").await
+    }
+
+    async fn set_alpha_write_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::setAlphaWriteEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "alphaWrite", "Z", enable).await
+    }
+
+    async fn is_alpha_write_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::isAlphaWriteEnabled({this:?})");
+
+        jvm.get_field(&this, "alphaWrite", "Z").await
+    }
+
+    async fn set_color_write_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::setColorWriteEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "colorWrite", "Z", enable).await
+    }
+
+    async fn is_color_write_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::isColorWriteEnabled({this:?})");
+
+        jvm.get_field(&this, "colorWrite", "Z").await
+    }
+
+    async fn set_depth_write_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::setDepthWriteEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "depthWrite", "Z", enable).await
+    }
+
+    async fn is_depth_write_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::isDepthWriteEnabled({this:?})");
+
+        jvm.get_field(&this, "depthWrite", "Z").await
+    }
+
+    async fn set_depth_test_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::setDepthTestEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "depthTest", "Z", enable).await
+    }
+
+    async fn is_depth_test_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::isDepthTestEnabled({this:?})");
+
+        jvm.get_field(&this, "depthTest", "Z").await
+    }
+
+    async fn set_depth_offset(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, factor: f32, units: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::setDepthOffset({this:?}, {factor}, {units})");
+
+        jvm.put_field(&mut this, "depthOffsetFactor", "F", factor).await?;
+        jvm.put_field(&mut this, "depthOffsetUnits", "F", units).await
+    }
+
+    async fn get_depth_offset_factor(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::getDepthOffsetFactor({this:?})");
+
+        jvm.get_field(&this, "depthOffsetFactor", "F").await
+    }
+
+    async fn get_depth_offset_units(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.CompositingMode::getDepthOffsetUnits({this:?})");
+
+        jvm.get_field(&this, "depthOffsetUnits", "F").await
+    }
+
+    pub(crate) async fn compositing_mode(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<RendererCompositingMode> {
+        let blending: i32 = jvm.get_field(this, "blending", "I").await?;
+
+        Ok(RendererCompositingMode {
+            blending: match blending {
+                ALPHA => Blending::Alpha,
+                ALPHA_ADD => Blending::AlphaAdd,
+                MODULATE => Blending::Modulate,
+                MODULATE_X2 => Blending::ModulateX2,
+                _ => Blending::Replace,
+            },
+            alpha_threshold: jvm.get_field(this, "alphaThreshold", "F").await?,
+            depth_test: jvm.get_field(this, "depthTest", "Z").await?,
+            depth_write: jvm.get_field(this, "depthWrite", "Z").await?,
+            color_write: jvm.get_field(this, "colorWrite", "Z").await?,
+        })
+    }
+}
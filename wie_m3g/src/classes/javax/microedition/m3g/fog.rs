@@ -0,0 +1,142 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::renderer::{Fog as RendererFog, FogMode, from_argb};
+
+const EXPONENTIAL: i32 = 80;
+const LINEAR: i32 = 81;
+
+// class javax.microedition.m3g.Fog
+pub struct Fog;
+
+impl Fog {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Fog",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setMode", "(I)V", Self::set_mode, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMode", "()I", Self::get_mode, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setLinear", "(FF)V", Self::set_linear, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getNearDistance", "()F", Self::get_near_distance, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getFarDistance", "()F", Self::get_far_distance, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setDensity", "(F)V", Self::set_density, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getDensity", "()F", Self::get_density, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setColor", "(I)V", Self::set_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getColor", "()I", Self::get_color, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("mode", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("color", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("density", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("near", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("far", "F", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Fog::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "mode", "I", LINEAR).await?;
+        jvm.put_field(&mut this, "density", "F", 1.0f32).await?;
+        jvm.put_field(&mut this, "near", "F", 0.0f32).await?;
+        jvm.put_field(&mut this, "far", "F", 1.0f32).await?;
+
+        Ok(())
+    }
+
+    async fn set_mode(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Fog::setMode({this:?}, {mode})");
+
+        if mode != EXPONENTIAL && mode != LINEAR {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid fog mode").await);
+        }
+
+        jvm.put_field(&mut this, "mode", "I", mode).await
+    }
+
+    async fn get_mode(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Fog::getMode({this:?})");
+
+        jvm.get_field(&this, "mode", "I").await
+    }
+
+    async fn set_linear(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, near: f32, far: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Fog::setLinear({this:?}, {near}, {far})");
+
+        jvm.put_field(&mut this, "near", "F", near).await?;
+        jvm.put_field(&mut this, "far", "F", far).await
+    }
+
+    async fn get_near_distance(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Fog::getNearDistance({this:?})");
+
+        jvm.get_field(&this, "near", "F").await
+    }
+
+    async fn get_far_distance(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Fog::getFarDistance({this:?})");
+
+        jvm.get_field(&this, "far", "F").await
+    }
+
+    async fn set_density(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, density: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Fog::setDensity({this:?}, {density})");
+
+        if density < 0.0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "density must not be negative").await);
+        }
+
+        jvm.put_field(&mut this, "density", "F", density).await
+    }
+
+    async fn get_density(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Fog::getDensity({this:?})");
+
+        jvm.get_field(&this, "density", "F").await
+    }
+
+    async fn set_color(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, rgb: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Fog::setColor({this:?}, {rgb:#x})");
+
+        jvm.put_field(&mut this, "color", "I", rgb & 0xffffff).await
+    }
+
+    async fn get_color(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Fog::getColor({this:?})");
+
+        jvm.get_field(&this, "color", "I").await
+    }
+
+    pub(crate) async fn fog(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<RendererFog> {
+        let mode: i32 = jvm.get_field(this, "mode", "I").await?;
+        let color: i32 = jvm.get_field(this, "color", "I").await?;
+
+        let mode = if mode == EXPONENTIAL {
+            FogMode::Exponential {
+                density: jvm.get_field(this, "density", "F").await?,
+            }
+        } else {
+            FogMode::Linear {
+                near: jvm.get_field(this, "near", "F").await?,
+                far: jvm.get_field(this, "far", "F").await?,
+            }
+        };
+
+        Ok(RendererFog {
+            mode,
+            color: from_argb(0xff000000 | color as u32),
+        })
+    }
+}
@@ -0,0 +1,1073 @@
+use alloc::{boxed::Box, rc::Rc, vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::{lang::Object, util::Vector};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
+
+use wie_backend::canvas::{ArgbPixel, Canvas, Clip, ImageBufferCanvas, VecImageBuffer};
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+use wie_midp::classes::javax::microedition::lcdui::{Graphics, Image};
+
+use crate::{
+    classes::javax::microedition::m3g::{
+        Appearance, Background, Camera, Group, Image2D, IndexBuffer, Light, Mesh, Node, Sprite3D, Transform, Transformable, VertexBuffer, World,
+    },
+    renderer::{
+        Appearance as RendererAppearance, Background as RendererBackground, Light as RendererLight, Matrix4, RenderTarget, Renderer, Sprite,
+        VertexData, Viewport,
+    },
+};
+
+const MAX_LIGHTS: i32 = 8;
+const MAX_VIEWPORT_DIMENSION: i32 = 2048;
+
+enum Drawable {
+    Mesh {
+        vertices: Rc<VertexData>,
+        triangles: Vec<[u32; 3]>,
+        appearance: RendererAppearance,
+    },
+    Sprite(Sprite),
+}
+
+struct RenderItem {
+    layer: i32,
+    model: Matrix4,
+    alpha_factor: f32,
+    drawable: Drawable,
+}
+
+// class javax.microedition.m3g.Graphics3D
+pub struct Graphics3D;
+
+impl Graphics3D {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Graphics3D",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PRIVATE),
+                JavaMethodProto::new(
+                    "getInstance",
+                    "()Ljavax/microedition/m3g/Graphics3D;",
+                    Self::get_instance,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new(
+                    "getProperties",
+                    "()Ljava/util/Hashtable;",
+                    Self::get_properties,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("bindTarget", "(Ljava/lang/Object;)V", Self::bind_target, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "bindTarget",
+                    "(Ljava/lang/Object;ZI)V",
+                    Self::bind_target_with_hints,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("releaseTarget", "()V", Self::release_target, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getTarget", "()Ljava/lang/Object;", Self::get_target, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isDepthBufferEnabled", "()Z", Self::is_depth_buffer_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getHints", "()I", Self::get_hints, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setViewport", "(IIII)V", Self::set_viewport, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getViewportX", "()I", Self::get_viewport_x, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getViewportY", "()I", Self::get_viewport_y, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getViewportWidth", "()I", Self::get_viewport_width, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getViewportHeight", "()I", Self::get_viewport_height, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setDepthRange", "(FF)V", Self::set_depth_range, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getDepthRangeNear", "()F", Self::get_depth_range_near, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getDepthRangeFar", "()F", Self::get_depth_range_far, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("clear", "(Ljavax/microedition/m3g/Background;)V", Self::clear, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "render",
+                    "(Ljavax/microedition/m3g/World;)V",
+                    Self::render_world,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "render",
+                    "(Ljavax/microedition/m3g/Node;Ljavax/microedition/m3g/Transform;)V",
+                    Self::render_node,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "render",
+                    "(Ljavax/microedition/m3g/VertexBuffer;Ljavax/microedition/m3g/IndexBuffer;Ljavax/microedition/m3g/Appearance;Ljavax/microedition/m3g/Transform;)V",
+                    Self::render_vertices,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "render",
+                    "(Ljavax/microedition/m3g/VertexBuffer;Ljavax/microedition/m3g/IndexBuffer;Ljavax/microedition/m3g/Appearance;Ljavax/microedition/m3g/Transform;I)V",
+                    Self::render_vertices_with_scope,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setCamera",
+                    "(Ljavax/microedition/m3g/Camera;Ljavax/microedition/m3g/Transform;)V",
+                    Self::set_camera,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getCamera",
+                    "(Ljavax/microedition/m3g/Transform;)Ljavax/microedition/m3g/Camera;",
+                    Self::get_camera,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "addLight",
+                    "(Ljavax/microedition/m3g/Light;Ljavax/microedition/m3g/Transform;)I",
+                    Self::add_light,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setLight",
+                    "(ILjavax/microedition/m3g/Light;Ljavax/microedition/m3g/Transform;)V",
+                    Self::set_light,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getLight",
+                    "(ILjavax/microedition/m3g/Transform;)Ljavax/microedition/m3g/Light;",
+                    Self::get_light,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("resetLights", "()V", Self::reset_lights, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getLightCount", "()I", Self::get_light_count, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new(
+                    "instance",
+                    "Ljavax/microedition/m3g/Graphics3D;",
+                    FieldAccessFlags::PRIVATE | FieldAccessFlags::STATIC,
+                ),
+                JavaFieldProto::new("target", "Ljava/lang/Object;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthBufferEnabled", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("hints", "I", FieldAccessFlags::PRIVATE),
+                // translation of the bound Graphics, viewport coordinates are relative to it
+                JavaFieldProto::new("originX", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("originY", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("viewportX", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("viewportY", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("viewportWidth", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("viewportHeight", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthRangeNear", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthRangeFar", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("depthBuffer", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("camera", "Ljavax/microedition/m3g/Camera;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cameraTransform", "Ljavax/microedition/m3g/Transform;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("lights", "Ljava/util/Vector;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("lightTransforms", "Ljava/util/Vector;", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        let lights = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        let light_transforms = jvm.new_class("java/util/Vector", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "depthRangeNear", "F", 0.0f32).await?;
+        jvm.put_field(&mut this, "depthRangeFar", "F", 1.0f32).await?;
+        jvm.put_field(&mut this, "lights", "Ljava/util/Vector;", lights).await?;
+        jvm.put_field(&mut this, "lightTransforms", "Ljava/util/Vector;", light_transforms)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_instance(jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<ClassInstanceRef<Self>> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getInstance()");
+
+        let instance: ClassInstanceRef<Self> = jvm
+            .get_static_field("javax/microedition/m3g/Graphics3D", "instance", "Ljavax/microedition/m3g/Graphics3D;")
+            .await?;
+        if !instance.is_null() {
+            return Ok(instance);
+        }
+
+        let instance: ClassInstanceRef<Self> = jvm.new_class("javax/microedition/m3g/Graphics3D", "()V", ()).await?.into();
+        jvm.put_static_field(
+            "javax/microedition/m3g/Graphics3D",
+            "instance",
+            "Ljavax/microedition/m3g/Graphics3D;",
+            instance.clone(),
+        )
+        .await?;
+
+        Ok(instance)
+    }
+
+    async fn get_properties(jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<ClassInstanceRef<Object>> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getProperties()");
+
+        let properties = jvm.new_class("java/util/Hashtable", "()V", ()).await?;
+
+        let values = [
+            ("maxLights", MAX_LIGHTS),
+            ("maxViewportWidth", MAX_VIEWPORT_DIMENSION),
+            ("maxViewportHeight", MAX_VIEWPORT_DIMENSION),
+            ("maxViewportDimension", MAX_VIEWPORT_DIMENSION),
+            ("maxTextureDimension", 1024),
+            ("maxSpriteCropDimension", 1024),
+            ("maxTransformsPerVertex", 1),
+            ("numTextureUnits", 2),
+        ];
+        for (key, value) in values {
+            let key = JavaLangString::from_rust_string(jvm, key).await?;
+            let value = jvm.new_class("java/lang/Integer", "(I)V", (value,)).await?;
+
+            let _: ClassInstanceRef<Object> = jvm
+                .invoke_virtual(
+                    &properties,
+                    "java/util/Hashtable",
+                    "put",
+                    "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+                    (key, value),
+                )
+                .await?;
+        }
+
+        Ok(properties.into())
+    }
+
+    async fn bind_target(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, target: ClassInstanceRef<Object>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::bindTarget({this:?}, {target:?})");
+
+        Self::bind_target_with_hints(jvm, context, this, target, true, 0).await
+    }
+
+    async fn bind_target_with_hints(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        target: ClassInstanceRef<Object>,
+        depth_buffer: bool,
+        hints: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::bindTarget({this:?}, {target:?}, {depth_buffer}, {hints})");
+
+        if target.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "target is null").await);
+        }
+
+        let bound: ClassInstanceRef<Object> = jvm.get_field(&this, "target", "Ljava/lang/Object;").await?;
+        if !bound.is_null() {
+            return Err(jvm.exception("java/lang/IllegalStateException", "target already bound").await);
+        }
+
+        let (width, height, origin, viewport) = if jvm.is_instance(&**target, "javax/microedition/lcdui/Graphics") {
+            let graphics: ClassInstanceRef<Graphics> = target.clone().instance.into();
+
+            let width: i32 = jvm.get_field(&graphics, "width", "I").await?;
+            let height: i32 = jvm.get_field(&graphics, "height", "I").await?;
+            let translate_x: i32 = jvm.get_field(&graphics, "translateX", "I").await?;
+            let translate_y: i32 = jvm.get_field(&graphics, "translateY", "I").await?;
+            let clip = Graphics::clip(jvm, &graphics).await?;
+
+            (
+                width,
+                height,
+                (translate_x, translate_y),
+                (clip.x - translate_x, clip.y - translate_y, clip.width as i32, clip.height as i32),
+            )
+        } else if jvm.is_instance(&**target, "javax/microedition/m3g/Image2D") {
+            let image: ClassInstanceRef<Image2D> = target.clone().instance.into();
+
+            let mutable: bool = jvm.get_field(&image, "mutable", "Z").await?;
+            if !mutable {
+                return Err(jvm.exception("java/lang/IllegalArgumentException", "image is immutable").await);
+            }
+
+            let width: i32 = jvm.get_field(&image, "width", "I").await?;
+            let height: i32 = jvm.get_field(&image, "height", "I").await?;
+
+            (width, height, (0, 0), (0, 0, width, height))
+        } else {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "unsupported target").await);
+        };
+
+        if width > MAX_VIEWPORT_DIMENSION || height > MAX_VIEWPORT_DIMENSION {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "target is too large").await);
+        }
+
+        if depth_buffer {
+            let mut depth = jvm.instantiate_array("F", (width * height) as _).await?;
+            jvm.store_array(&mut depth, 0, vec![1.0f32; (width * height) as usize]).await?;
+            jvm.put_field(&mut this, "depthBuffer", "[F", depth).await?;
+        }
+
+        jvm.put_field(&mut this, "target", "Ljava/lang/Object;", target).await?;
+        jvm.put_field(&mut this, "depthBufferEnabled", "Z", depth_buffer).await?;
+        jvm.put_field(&mut this, "hints", "I", hints).await?;
+        jvm.put_field(&mut this, "originX", "I", origin.0).await?;
+        jvm.put_field(&mut this, "originY", "I", origin.1).await?;
+
+        Self::store_viewport(jvm, &mut this, viewport).await
+    }
+
+    async fn release_target(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::releaseTarget({this:?})");
+
+        // rendering writes through to the target, there is nothing to flush
+        jvm.put_field(&mut this, "target", "Ljava/lang/Object;", None).await?;
+        jvm.put_field(&mut this, "depthBuffer", "[F", None).await
+    }
+
+    async fn get_target(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Object>> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getTarget({this:?})");
+
+        jvm.get_field(&this, "target", "Ljava/lang/Object;").await
+    }
+
+    async fn is_depth_buffer_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::isDepthBufferEnabled({this:?})");
+
+        jvm.get_field(&this, "depthBufferEnabled", "Z").await
+    }
+
+    async fn get_hints(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getHints({this:?})");
+
+        jvm.get_field(&this, "hints", "I").await
+    }
+
+    async fn set_viewport(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::setViewport({this:?}, {x}, {y}, {width}, {height})");
+
+        if width <= 0 || height <= 0 || width > MAX_VIEWPORT_DIMENSION || height > MAX_VIEWPORT_DIMENSION {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid viewport size").await);
+        }
+
+        Self::store_viewport(jvm, &mut this, (x, y, width, height)).await
+    }
+
+    async fn get_viewport_x(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getViewportX({this:?})");
+
+        jvm.get_field(&this, "viewportX", "I").await
+    }
+
+    async fn get_viewport_y(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getViewportY({this:?})");
+
+        jvm.get_field(&this, "viewportY", "I").await
+    }
+
+    async fn get_viewport_width(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getViewportWidth({this:?})");
+
+        jvm.get_field(&this, "viewportWidth", "I").await
+    }
+
+    async fn get_viewport_height(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getViewportHeight({this:?})");
+
+        jvm.get_field(&this, "viewportHeight", "I").await
+    }
+
+    async fn set_depth_range(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, near: f32, far: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::setDepthRange({this:?}, {near}, {far})");
+
+        if !(0.0..=1.0).contains(&near) || !(0.0..=1.0).contains(&far) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "depth range out of [0, 1]").await);
+        }
+
+        jvm.put_field(&mut this, "depthRangeNear", "F", near).await?;
+        jvm.put_field(&mut this, "depthRangeFar", "F", far).await
+    }
+
+    async fn get_depth_range_near(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getDepthRangeNear({this:?})");
+
+        jvm.get_field(&this, "depthRangeNear", "F").await
+    }
+
+    async fn get_depth_range_far(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getDepthRangeFar({this:?})");
+
+        jvm.get_field(&this, "depthRangeFar", "F").await
+    }
+
+    async fn clear(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, background: ClassInstanceRef<Background>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::clear({this:?}, {background:?})");
+
+        let background = if background.is_null() {
+            RendererBackground::default()
+        } else {
+            Background::background(jvm, &background).await?
+        };
+
+        let renderer = Self::renderer(jvm, &this).await?;
+
+        Self::draw(jvm, &this, &renderer, Some(&background), Vec::new()).await
+    }
+
+    async fn render_world(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, world: ClassInstanceRef<World>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::render({this:?}, {world:?})");
+
+        if world.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "world is null").await);
+        }
+
+        let camera: ClassInstanceRef<Camera> = jvm.get_field(&world, "activeCamera", "Ljavax/microedition/m3g/Camera;").await?;
+        if camera.is_null() {
+            return Err(jvm.exception("java/lang/IllegalStateException", "world has no active camera").await);
+        }
+
+        let (camera_transform, camera_root) = Node::world_transform(jvm, &camera.clone().instance.into()).await?;
+        if !camera_root.equals(&**world)? {
+            return Err(jvm
+                .exception("java/lang/IllegalStateException", "active camera is not in the world")
+                .await);
+        }
+
+        let background: ClassInstanceRef<Background> = jvm.get_field(&world, "background", "Ljavax/microedition/m3g/Background;").await?;
+        let background = if background.is_null() {
+            RendererBackground::default()
+        } else {
+            Background::background(jvm, &background).await?
+        };
+
+        let world_transform = Transformable::composite_transform(jvm, &world).await?;
+
+        let mut items = Vec::new();
+        let mut lights = Vec::new();
+        Self::collect(jvm, world.instance.into(), world_transform, &mut items, Some(&mut lights)).await?;
+
+        let mut renderer = Self::renderer(jvm, &this).await?;
+        renderer.set_camera(Camera::projection(jvm, &camera).await?, &camera_transform);
+        renderer.lights = lights;
+
+        Self::draw(jvm, &this, &renderer, Some(&background), items).await
+    }
+
+    async fn render_node(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        node: ClassInstanceRef<Node>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::render({this:?}, {node:?}, {transform:?})");
+
+        if node.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "node is null").await);
+        }
+        let renderable = [
+            "javax/microedition/m3g/Mesh",
+            "javax/microedition/m3g/Sprite3D",
+            "javax/microedition/m3g/Group",
+        ]
+        .into_iter()
+        .any(|class| jvm.is_instance(&**node, class));
+        if !renderable || jvm.is_instance(&**node, "javax/microedition/m3g/World") {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "node is not renderable").await);
+        }
+
+        // the given transform replaces the node's own one
+        let model = Self::transform_or_identity(jvm, &transform).await?;
+
+        let mut items = Vec::new();
+        Self::collect(jvm, node, model, &mut items, None).await?;
+
+        let renderer = Self::camera_renderer(jvm, &this).await?;
+
+        Self::draw(jvm, &this, &renderer, None, items).await
+    }
+
+    async fn render_vertices(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        vertices: ClassInstanceRef<VertexBuffer>,
+        triangles: ClassInstanceRef<IndexBuffer>,
+        appearance: ClassInstanceRef<Appearance>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::render({this:?}, {vertices:?}, {triangles:?}, {appearance:?}, {transform:?})");
+
+        Self::render_vertices_with_scope(jvm, context, this, vertices, triangles, appearance, transform, -1).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn render_vertices_with_scope(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        vertices: ClassInstanceRef<VertexBuffer>,
+        triangles: ClassInstanceRef<IndexBuffer>,
+        appearance: ClassInstanceRef<Appearance>,
+        transform: ClassInstanceRef<Transform>,
+        scope: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::render({this:?}, {vertices:?}, {triangles:?}, {appearance:?}, {transform:?}, {scope})");
+
+        if vertices.is_null() || triangles.is_null() || appearance.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "argument is null").await);
+        }
+
+        let appearance = Appearance::appearance(jvm, &appearance).await?;
+        let item = RenderItem {
+            layer: appearance.layer,
+            model: Self::transform_or_identity(jvm, &transform).await?,
+            alpha_factor: 1.0,
+            drawable: Drawable::Mesh {
+                vertices: Rc::new(VertexBuffer::vertex_data(jvm, &vertices).await?),
+                triangles: IndexBuffer::triangles(jvm, &triangles).await?,
+                appearance,
+            },
+        };
+
+        let renderer = Self::camera_renderer(jvm, &this).await?;
+
+        Self::draw(jvm, &this, &renderer, None, vec![item]).await
+    }
+
+    async fn set_camera(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        camera: ClassInstanceRef<Camera>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::setCamera({this:?}, {camera:?}, {transform:?})");
+
+        let transform = Self::copy_transform(jvm, &transform).await?;
+
+        jvm.put_field(&mut this, "camera", "Ljavax/microedition/m3g/Camera;", camera).await?;
+        jvm.put_field(&mut this, "cameraTransform", "Ljavax/microedition/m3g/Transform;", transform)
+            .await
+    }
+
+    async fn get_camera(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<ClassInstanceRef<Camera>> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getCamera({this:?}, {transform:?})");
+
+        let camera: ClassInstanceRef<Camera> = jvm.get_field(&this, "camera", "Ljavax/microedition/m3g/Camera;").await?;
+        if !camera.is_null() && !transform.is_null() {
+            let camera_transform: ClassInstanceRef<Transform> = jvm.get_field(&this, "cameraTransform", "Ljavax/microedition/m3g/Transform;").await?;
+            let matrix = Self::transform_or_identity(jvm, &camera_transform).await?;
+            Transform::set_matrix(jvm, &transform, &matrix).await?;
+        }
+
+        Ok(camera)
+    }
+
+    async fn add_light(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        light: ClassInstanceRef<Light>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::addLight({this:?}, {light:?}, {transform:?})");
+
+        if light.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "light is null").await);
+        }
+
+        let lights: ClassInstanceRef<Vector> = jvm.get_field(&this, "lights", "Ljava/util/Vector;").await?;
+        let light_transforms: ClassInstanceRef<Vector> = jvm.get_field(&this, "lightTransforms", "Ljava/util/Vector;").await?;
+        let transform = Self::copy_transform(jvm, &transform).await?;
+
+        let _: () = jvm
+            .invoke_virtual(&lights, "java/util/Vector", "addElement", "(Ljava/lang/Object;)V", (light,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&light_transforms, "java/util/Vector", "addElement", "(Ljava/lang/Object;)V", (transform,))
+            .await?;
+
+        let size: i32 = jvm.invoke_virtual(&lights, "java/util/Vector", "size", "()I", ()).await?;
+
+        Ok(size - 1)
+    }
+
+    async fn set_light(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        index: i32,
+        light: ClassInstanceRef<Light>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::setLight({this:?}, {index}, {light:?}, {transform:?})");
+
+        let lights: ClassInstanceRef<Vector> = jvm.get_field(&this, "lights", "Ljava/util/Vector;").await?;
+        let light_transforms: ClassInstanceRef<Vector> = jvm.get_field(&this, "lightTransforms", "Ljava/util/Vector;").await?;
+        Self::check_light_index(jvm, &lights, index).await?;
+
+        let transform = Self::copy_transform(jvm, &transform).await?;
+
+        let _: () = jvm
+            .invoke_virtual(&lights, "java/util/Vector", "setElementAt", "(Ljava/lang/Object;I)V", (light, index))
+            .await?;
+        jvm.invoke_virtual(
+            &light_transforms,
+            "java/util/Vector",
+            "setElementAt",
+            "(Ljava/lang/Object;I)V",
+            (transform, index),
+        )
+        .await
+    }
+
+    async fn get_light(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        index: i32,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<ClassInstanceRef<Light>> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getLight({this:?}, {index}, {transform:?})");
+
+        let lights: ClassInstanceRef<Vector> = jvm.get_field(&this, "lights", "Ljava/util/Vector;").await?;
+        let light_transforms: ClassInstanceRef<Vector> = jvm.get_field(&this, "lightTransforms", "Ljava/util/Vector;").await?;
+        Self::check_light_index(jvm, &lights, index).await?;
+
+        if !transform.is_null() {
+            let light_transform: ClassInstanceRef<Transform> = jvm
+                .invoke_virtual(&light_transforms, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+                .await?;
+            let matrix = Self::transform_or_identity(jvm, &light_transform).await?;
+            Transform::set_matrix(jvm, &transform, &matrix).await?;
+        }
+
+        jvm.invoke_virtual(&lights, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+            .await
+    }
+
+    async fn reset_lights(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::resetLights({this:?})");
+
+        let lights: ClassInstanceRef<Vector> = jvm.get_field(&this, "lights", "Ljava/util/Vector;").await?;
+        let light_transforms: ClassInstanceRef<Vector> = jvm.get_field(&this, "lightTransforms", "Ljava/util/Vector;").await?;
+
+        let _: () = jvm.invoke_virtual(&lights, "java/util/Vector", "removeAllElements", "()V", ()).await?;
+        jvm.invoke_virtual(&light_transforms, "java/util/Vector", "removeAllElements", "()V", ())
+            .await
+    }
+
+    async fn get_light_count(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Graphics3D::getLightCount({this:?})");
+
+        let lights: ClassInstanceRef<Vector> = jvm.get_field(&this, "lights", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&lights, "java/util/Vector", "size", "()I", ()).await
+    }
+
+    async fn check_light_index(jvm: &Jvm, lights: &ClassInstanceRef<Vector>, index: i32) -> JvmResult<()> {
+        let size: i32 = jvm.invoke_virtual(lights, "java/util/Vector", "size", "()I", ()).await?;
+        if index < 0 || index >= size {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid light index").await);
+        }
+
+        Ok(())
+    }
+
+    async fn store_viewport(jvm: &Jvm, this: &mut ClassInstanceRef<Self>, (x, y, width, height): (i32, i32, i32, i32)) -> JvmResult<()> {
+        jvm.put_field(this, "viewportX", "I", x).await?;
+        jvm.put_field(this, "viewportY", "I", y).await?;
+        jvm.put_field(this, "viewportWidth", "I", width).await?;
+        jvm.put_field(this, "viewportHeight", "I", height).await
+    }
+
+    /// Transforms passed to `setCamera` and `addLight` are copied, later changes by the caller don't affect them.
+    async fn copy_transform(jvm: &Jvm, transform: &ClassInstanceRef<Transform>) -> JvmResult<ClassInstanceRef<Transform>> {
+        if transform.is_null() {
+            return Ok(None.into());
+        }
+
+        Ok(jvm
+            .new_class(
+                "javax/microedition/m3g/Transform",
+                "(Ljavax/microedition/m3g/Transform;)V",
+                (transform.clone(),),
+            )
+            .await?
+            .into())
+    }
+
+    async fn transform_or_identity(jvm: &Jvm, transform: &ClassInstanceRef<Transform>) -> JvmResult<Matrix4> {
+        if transform.is_null() {
+            Ok(Matrix4::IDENTITY)
+        } else {
+            Transform::matrix(jvm, transform).await
+        }
+    }
+
+    async fn renderer(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Renderer> {
+        let origin_x: i32 = jvm.get_field(this, "originX", "I").await?;
+        let origin_y: i32 = jvm.get_field(this, "originY", "I").await?;
+        let x: i32 = jvm.get_field(this, "viewportX", "I").await?;
+        let y: i32 = jvm.get_field(this, "viewportY", "I").await?;
+
+        let mut renderer = Renderer::new(Viewport {
+            x: origin_x + x,
+            y: origin_y + y,
+            width: jvm.get_field(this, "viewportWidth", "I").await?,
+            height: jvm.get_field(this, "viewportHeight", "I").await?,
+        });
+        renderer.depth_range = (
+            jvm.get_field(this, "depthRangeNear", "F").await?,
+            jvm.get_field(this, "depthRangeFar", "F").await?,
+        );
+
+        Ok(renderer)
+    }
+
+    /// Renderer for immediate mode, using the camera and lights set on `Graphics3D`.
+    async fn camera_renderer(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Renderer> {
+        let camera: ClassInstanceRef<Camera> = jvm.get_field(this, "camera", "Ljavax/microedition/m3g/Camera;").await?;
+        if camera.is_null() {
+            return Err(jvm.exception("java/lang/IllegalStateException", "no camera set").await);
+        }
+        let camera_transform: ClassInstanceRef<Transform> = jvm.get_field(this, "cameraTransform", "Ljavax/microedition/m3g/Transform;").await?;
+
+        let mut renderer = Self::renderer(jvm, this).await?;
+        renderer.set_camera(
+            Camera::projection(jvm, &camera).await?,
+            &Self::transform_or_identity(jvm, &camera_transform).await?,
+        );
+
+        let lights: ClassInstanceRef<Vector> = jvm.get_field(this, "lights", "Ljava/util/Vector;").await?;
+        let light_transforms: ClassInstanceRef<Vector> = jvm.get_field(this, "lightTransforms", "Ljava/util/Vector;").await?;
+        let size: i32 = jvm.invoke_virtual(&lights, "java/util/Vector", "size", "()I", ()).await?;
+        for index in 0..size {
+            let light: ClassInstanceRef<Light> = jvm
+                .invoke_virtual(&lights, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+                .await?;
+            let transform: ClassInstanceRef<Transform> = jvm
+                .invoke_virtual(&light_transforms, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+                .await?;
+            if light.is_null() {
+                continue;
+            }
+
+            let transform = Self::transform_or_identity(jvm, &transform).await?;
+            renderer.lights.push(Light::light(jvm, &light, transform).await?);
+        }
+
+        Ok(renderer)
+    }
+
+    /// Walks the scene graph below `root`, collecting what to draw and, for world rendering, the lights.
+    async fn collect(
+        jvm: &Jvm,
+        root: ClassInstanceRef<Node>,
+        root_transform: Matrix4,
+        items: &mut Vec<RenderItem>,
+        mut lights: Option<&mut Vec<RendererLight>>,
+    ) -> JvmResult<()> {
+        let mut stack = vec![(root, root_transform, 1.0f32)];
+
+        while let Some((node, transform, alpha_factor)) = stack.pop() {
+            let rendering_enabled: bool = jvm.get_field(&node, "renderingEnabled", "Z").await?;
+            if !rendering_enabled {
+                continue;
+            }
+            let node_alpha_factor: f32 = jvm.get_field(&node, "alphaFactor", "F").await?;
+            let alpha_factor = alpha_factor * node_alpha_factor;
+
+            if jvm.is_instance(&**node, "javax/microedition/m3g/Group") {
+                // pushed in reverse so children are visited in order
+                for child in Group::children(jvm, &node).await?.into_iter().rev() {
+                    let child_transform = transform.multiply(&Transformable::composite_transform(jvm, &child).await?);
+                    stack.push((child, child_transform, alpha_factor));
+                }
+            } else if jvm.is_instance(&**node, "javax/microedition/m3g/Mesh") {
+                let (vertices, submeshes) = Mesh::submeshes(jvm, &node).await?;
+                let vertices = Rc::new(vertices);
+
+                for (triangles, appearance) in submeshes {
+                    items.push(RenderItem {
+                        layer: appearance.layer,
+                        model: transform,
+                        alpha_factor,
+                        drawable: Drawable::Mesh {
+                            vertices: vertices.clone(),
+                            triangles,
+                            appearance,
+                        },
+                    });
+                }
+            } else if jvm.is_instance(&**node, "javax/microedition/m3g/Sprite3D") {
+                if let Some((layer, sprite)) = Sprite3D::sprite(jvm, &node).await? {
+                    items.push(RenderItem {
+                        layer,
+                        model: transform,
+                        alpha_factor,
+                        drawable: Drawable::Sprite(sprite),
+                    });
+                }
+            } else if jvm.is_instance(&**node, "javax/microedition/m3g/Light")
+                && let Some(lights) = lights.as_mut()
+            {
+                lights.push(Light::light(jvm, &node, transform).await?);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn draw(
+        jvm: &Jvm,
+        this: &ClassInstanceRef<Self>,
+        renderer: &Renderer,
+        background: Option<&RendererBackground>,
+        mut items: Vec<RenderItem>,
+    ) -> JvmResult<()> {
+        let target: ClassInstanceRef<Object> = jvm.get_field(this, "target", "Ljava/lang/Object;").await?;
+        if target.is_null() {
+            return Err(jvm.exception("java/lang/IllegalStateException", "no target bound").await);
+        }
+
+        let depth_buffer_enabled: bool = jvm.get_field(this, "depthBufferEnabled", "Z").await?;
+
+        // lower layers first, the sort is stable so scene graph order is kept within a layer
+        items.sort_by_key(|item| item.layer);
+
+        let (mut canvas, clip, image) = if jvm.is_instance(&**target, "javax/microedition/lcdui/Graphics") {
+            let mut graphics: ClassInstanceRef<Graphics> = target.instance.into();
+            let image = Graphics::image(jvm, &mut graphics).await?;
+
+            (Image::canvas(jvm, &image).await?, Graphics::clip(jvm, &graphics).await?, None)
+        } else {
+            let image: ClassInstanceRef<Image2D> = target.instance.into();
+            let width: i32 = jvm.get_field(&image, "width", "I").await?;
+            let height: i32 = jvm.get_field(&image, "height", "I").await?;
+
+            let pixels = jvm.get_field(&image, "pixels", "[B").await?;
+            let mut data = vec![0; (width * height * 4) as usize];
+            jvm.array_raw_buffer(&pixels).await?.read(0, &mut data)?;
+
+            let argb = data
+                .chunks_exact(4)
+                .map(|x| u32::from_be_bytes([x[3], x[0], x[1], x[2]]))
+                .collect::<Vec<_>>();
+            let canvas = ImageBufferCanvas::new(VecImageBuffer::<ArgbPixel>::from_raw(width as _, height as _, argb));
+            let clip = Clip {
+                x: 0,
+                y: 0,
+                width: width as _,
+                height: height as _,
+            };
+
+            (Box::new(canvas) as Box<dyn Canvas>, clip, Some(image))
+        };
+
+        let width = canvas.image().width();
+        let height = canvas.image().height();
+        let length = (width * height) as usize;
+
+        let mut depth_array = jvm.get_field(this, "depthBuffer", "[F").await?;
+        let mut depth = if depth_buffer_enabled {
+            jvm.load_array(&depth_array, 0, length).await?
+        } else {
+            vec![1.0f32; length]
+        };
+
+        let mut render_target = RenderTarget {
+            canvas: &mut *canvas,
+            depth: &mut depth,
+            width,
+            height,
+            clip,
+        };
+
+        if let Some(background) = background {
+            renderer.clear(&mut render_target, background);
+        }
+
+        for item in &mut items {
+            match &mut item.drawable {
+                Drawable::Mesh {
+                    vertices,
+                    triangles,
+                    appearance,
+                } => {
+                    if !depth_buffer_enabled {
+                        appearance.compositing_mode.depth_test = false;
+                        appearance.compositing_mode.depth_write = false;
+                    }
+                    renderer.draw_mesh(&mut render_target, &item.model, vertices, triangles, appearance, item.alpha_factor);
+                }
+                Drawable::Sprite(sprite) => {
+                    if !depth_buffer_enabled {
+                        sprite.compositing_mode.depth_test = false;
+                        sprite.compositing_mode.depth_write = false;
+                    }
+                    renderer.draw_sprite(&mut render_target, &item.model, sprite, item.alpha_factor);
+                }
+            }
+        }
+
+        if depth_buffer_enabled {
+            jvm.store_array(&mut depth_array, 0, depth).await?;
+        }
+
+        if let Some(image) = image {
+            let pixels = canvas
+                .image()
+                .colors()
+                .into_iter()
+                .map(|color| [color.r, color.g, color.b, color.a])
+                .collect::<Vec<_>>();
+            Image2D::store_pixels(jvm, &image, &pixels).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec};
+
+    use jvm::ClassInstanceRef;
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{classes::javax::microedition::m3g::Image2D, get_protos};
+
+    #[test]
+    fn test_render_triangle_into_image() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let image = jvm.new_class("javax/microedition/m3g/Image2D", "(III)V", (99, 4, 4)).await?;
+
+            let graphics_3d: ClassInstanceRef<()> = jvm
+                .invoke_static(
+                    "javax/microedition/m3g/Graphics3D",
+                    "getInstance",
+                    "()Ljavax/microedition/m3g/Graphics3D;",
+                    (),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics_3d,
+                    "javax/microedition/m3g/Graphics3D",
+                    "bindTarget",
+                    "(Ljava/lang/Object;)V",
+                    (image.clone(),),
+                )
+                .await?;
+
+            let background = jvm.new_class("javax/microedition/m3g/Background", "()V", ()).await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &background,
+                    "javax/microedition/m3g/Background",
+                    "setColor",
+                    "(I)V",
+                    (0xff0000ff_u32 as i32,),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics_3d,
+                    "javax/microedition/m3g/Graphics3D",
+                    "clear",
+                    "(Ljavax/microedition/m3g/Background;)V",
+                    (background,),
+                )
+                .await?;
+
+            // a quad covering the left half in a parallel projection of the unit cube
+            let positions = jvm.new_class("javax/microedition/m3g/VertexArray", "(III)V", (4, 3, 1)).await?;
+            let mut values = jvm.instantiate_array("B", 12).await?;
+            jvm.store_array(&mut values, 0, vec![-1i8, -1, 0, 0, -1, 0, -1, 1, 0, 0, 1, 0]).await?;
+            let _: () = jvm
+                .invoke_virtual(&positions, "javax/microedition/m3g/VertexArray", "set", "(II[B)V", (0, 4, values))
+                .await?;
+
+            let vertex_buffer = jvm.new_class("javax/microedition/m3g/VertexBuffer", "()V", ()).await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &vertex_buffer,
+                    "javax/microedition/m3g/VertexBuffer",
+                    "setPositions",
+                    "(Ljavax/microedition/m3g/VertexArray;F[F)V",
+                    (positions, 1.0f32, None),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &vertex_buffer,
+                    "javax/microedition/m3g/VertexBuffer",
+                    "setDefaultColor",
+                    "(I)V",
+                    (0xff00ff00_u32 as i32,),
+                )
+                .await?;
+
+            let mut strip_lengths = jvm.instantiate_array("I", 1).await?;
+            jvm.store_array(&mut strip_lengths, 0, vec![4]).await?;
+            let index_buffer = jvm
+                .new_class("javax/microedition/m3g/TriangleStripArray", "(I[I)V", (0, strip_lengths))
+                .await?;
+
+            let appearance = jvm.new_class("javax/microedition/m3g/Appearance", "()V", ()).await?;
+
+            let camera = jvm.new_class("javax/microedition/m3g/Camera", "()V", ()).await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &camera,
+                    "javax/microedition/m3g/Camera",
+                    "setParallel",
+                    "(FFFF)V",
+                    (2.0f32, 1.0f32, -1.0f32, 1.0f32),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics_3d,
+                    "javax/microedition/m3g/Graphics3D",
+                    "setCamera",
+                    "(Ljavax/microedition/m3g/Camera;Ljavax/microedition/m3g/Transform;)V",
+                    (camera, None),
+                )
+                .await?;
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics_3d,
+                    "javax/microedition/m3g/Graphics3D",
+                    "render",
+                    "(Ljavax/microedition/m3g/VertexBuffer;Ljavax/microedition/m3g/IndexBuffer;Ljavax/microedition/m3g/Appearance;Ljavax/microedition/m3g/Transform;)V",
+                    (vertex_buffer, index_buffer, appearance, None),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&graphics_3d, "javax/microedition/m3g/Graphics3D", "releaseTarget", "()V", ())
+                .await?;
+
+            let image: ClassInstanceRef<Image2D> = image.into();
+            let texture = Image2D::texture_image(&jvm, &image).await?;
+            let row = texture.texels[4..8].iter().map(|x| [x[0], x[1], x[2]]).collect::<alloc::vec::Vec<_>>();
+            assert_eq!(row, vec![[0.0, 1.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0, 1.0]]);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,222 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::util::Vector;
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::javax::microedition::m3g::Node;
+
+// class javax.microedition.m3g.Group
+pub struct Group;
+
+impl Group {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Group",
+            parent_class: Some("javax/microedition/m3g/Node"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("addChild", "(Ljavax/microedition/m3g/Node;)V", Self::add_child, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "removeChild",
+                    "(Ljavax/microedition/m3g/Node;)V",
+                    Self::remove_child,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getChildCount", "()I", Self::get_child_count, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getChild", "(I)Ljavax/microedition/m3g/Node;", Self::get_child, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![JavaFieldProto::new("children", "Ljava/util/Vector;", FieldAccessFlags::PRIVATE)],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Group::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Node", "<init>", "()V", ()).await?;
+
+        let children = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(&mut this, "children", "Ljava/util/Vector;", children).await?;
+
+        Ok(())
+    }
+
+    async fn add_child(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, child: ClassInstanceRef<Node>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Group::addChild({this:?}, {child:?})");
+
+        if child.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "child is null").await);
+        }
+        if child.equals(&**this)? || jvm.is_instance(&**child, "javax/microedition/m3g/World") {
+            return Err(jvm
+                .exception("java/lang/IllegalArgumentException", "child can't be added to this group")
+                .await);
+        }
+
+        let parent: ClassInstanceRef<Node> = jvm.get_field(&child, "parent", "Ljavax/microedition/m3g/Node;").await?;
+        if !parent.is_null() {
+            if parent.equals(&**this)? {
+                return Ok(());
+            }
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "child already has a parent").await);
+        }
+
+        let children: ClassInstanceRef<Vector> = jvm.get_field(&this, "children", "Ljava/util/Vector;").await?;
+        let _: () = jvm
+            .invoke_virtual(&children, "java/util/Vector", "addElement", "(Ljava/lang/Object;)V", (child.clone(),))
+            .await?;
+
+        jvm.invoke_virtual(
+            &child,
+            "javax/microedition/m3g/Node",
+            "setParent",
+            "(Ljavax/microedition/m3g/Node;)V",
+            (this,),
+        )
+        .await
+    }
+
+    async fn remove_child(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, child: ClassInstanceRef<Node>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Group::removeChild({this:?}, {child:?})");
+
+        if child.is_null() {
+            return Ok(());
+        }
+
+        let children: ClassInstanceRef<Vector> = jvm.get_field(&this, "children", "Ljava/util/Vector;").await?;
+        let removed: bool = jvm
+            .invoke_virtual(&children, "java/util/Vector", "removeElement", "(Ljava/lang/Object;)Z", (child.clone(),))
+            .await?;
+
+        if removed {
+            let parent: ClassInstanceRef<Node> = None.into();
+            let _: () = jvm
+                .invoke_virtual(
+                    &child,
+                    "javax/microedition/m3g/Node",
+                    "setParent",
+                    "(Ljavax/microedition/m3g/Node;)V",
+                    (parent,),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_child_count(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Group::getChildCount({this:?})");
+
+        let children: ClassInstanceRef<Vector> = jvm.get_field(&this, "children", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&children, "java/util/Vector", "size", "()I", ()).await
+    }
+
+    async fn get_child(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, index: i32) -> JvmResult<ClassInstanceRef<Node>> {
+        tracing::debug!("javax.microedition.m3g.Group::getChild({this:?}, {index})");
+
+        let children: ClassInstanceRef<Vector> = jvm.get_field(&this, "children", "Ljava/util/Vector;").await?;
+        let size: i32 = jvm.invoke_virtual(&children, "java/util/Vector", "size", "()I", ()).await?;
+        if index < 0 || index >= size {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "child index out of range").await);
+        }
+
+        jvm.invoke_virtual(&children, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+            .await
+    }
+
+    pub(crate) async fn children<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<Vec<ClassInstanceRef<Node>>> {
+        let children: ClassInstanceRef<Vector> = jvm.get_field(this, "children", "Ljava/util/Vector;").await?;
+        let size: i32 = jvm.invoke_virtual(&children, "java/util/Vector", "size", "()I", ()).await?;
+
+        let mut result = Vec::with_capacity(size as _);
+        for index in 0..size {
+            let child: ClassInstanceRef<Node> = jvm
+                .invoke_virtual(&children, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+                .await?;
+            result.push(child);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+
+    use jvm::ClassInstanceRef;
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{classes::javax::microedition::m3g::Node, get_protos};
+
+    #[test]
+    fn test_add_and_remove_child() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let group = jvm.new_class("javax/microedition/m3g/Group", "()V", ()).await?;
+            let child = jvm.new_class("javax/microedition/m3g/Group", "()V", ()).await?;
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &group,
+                    "javax/microedition/m3g/Group",
+                    "addChild",
+                    "(Ljavax/microedition/m3g/Node;)V",
+                    (child.clone(),),
+                )
+                .await?;
+
+            let count: i32 = jvm
+                .invoke_virtual(&group, "javax/microedition/m3g/Group", "getChildCount", "()I", ())
+                .await?;
+            assert_eq!(count, 1);
+
+            let parent: ClassInstanceRef<Node> = jvm
+                .invoke_virtual(&child, "javax/microedition/m3g/Node", "getParent", "()Ljavax/microedition/m3g/Node;", ())
+                .await?;
+            assert!(parent.equals(&*group)?);
+
+            // a node can only have a single parent
+            let other = jvm.new_class("javax/microedition/m3g/Group", "()V", ()).await?;
+            let result: jvm::Result<()> = jvm
+                .invoke_virtual(
+                    &other,
+                    "javax/microedition/m3g/Group",
+                    "addChild",
+                    "(Ljavax/microedition/m3g/Node;)V",
+                    (child.clone(),),
+                )
+                .await;
+            assert!(result.is_err());
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &group,
+                    "javax/microedition/m3g/Group",
+                    "removeChild",
+                    "(Ljavax/microedition/m3g/Node;)V",
+                    (child.clone(),),
+                )
+                .await?;
+
+            let count: i32 = jvm
+                .invoke_virtual(&group, "javax/microedition/m3g/Group", "getChildCount", "()I", ())
+                .await?;
+            assert_eq!(count, 0);
+
+            let parent: ClassInstanceRef<Node> = jvm
+                .invoke_virtual(&child, "javax/microedition/m3g/Node", "getParent", "()Ljavax/microedition/m3g/Node;", ())
+                .await?;
+            assert!(parent.is_null());
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,329 @@
+use alloc::{vec, vec::Vec};
+
+use bytemuck::cast_vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::lang::Object;
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+use wie_midp::classes::javax::microedition::lcdui::Image;
+
+use crate::renderer::TextureImage;
+
+pub(crate) const ALPHA: i32 = 96;
+pub(crate) const LUMINANCE: i32 = 97;
+pub(crate) const LUMINANCE_ALPHA: i32 = 98;
+pub(crate) const RGB: i32 = 99;
+pub(crate) const RGBA: i32 = 100;
+
+// class javax.microedition.m3g.Image2D
+pub struct Image2D;
+
+#[allow(clippy::too_many_arguments)]
+impl Image2D {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Image2D",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(III)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(ILjava/lang/Object;)V", Self::init_with_image, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(III[B)V", Self::init_with_pixels, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(III[B[B)V", Self::init_with_palette, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("set", "(IIII[B)V", Self::set, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getFormat", "()I", Self::get_format, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getWidth", "()I", Self::get_width, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getHeight", "()I", Self::get_height, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isMutable", "()Z", Self::is_mutable, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("format", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("width", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("height", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("mutable", "Z", FieldAccessFlags::PRIVATE),
+                // always RGBA, 4 bytes per pixel
+                JavaFieldProto::new("pixels", "[B", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, format: i32, width: i32, height: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Image2D::<init>({this:?}, {format}, {width}, {height})");
+
+        // mutable images start out as opaque white
+        let pixels = vec![0xff; (width.max(0) * height.max(0) * 4) as usize];
+        Self::initialize(jvm, &mut this, format, width, height, true, pixels).await
+    }
+
+    async fn init_with_image(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        format: i32,
+        image: ClassInstanceRef<Object>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Image2D::<init>({this:?}, {format}, {image:?})");
+
+        if image.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "image is null").await);
+        }
+        if !jvm.is_instance(&**image, "javax/microedition/lcdui/Image") {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "unsupported image type").await);
+        }
+
+        let image: ClassInstanceRef<Image> = image.instance.into();
+        let image = Image::image(jvm, &image).await?;
+
+        let pixels = image
+            .colors()
+            .into_iter()
+            .flat_map(|color| Self::convert_pixel(format, [color.r, color.g, color.b, color.a]))
+            .collect();
+
+        Self::initialize(jvm, &mut this, format, image.width() as _, image.height() as _, false, pixels).await
+    }
+
+    async fn init_with_pixels(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        format: i32,
+        width: i32,
+        height: i32,
+        image: ClassInstanceRef<Array<i8>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Image2D::<init>({this:?}, {format}, {width}, {height}, {image:?})");
+
+        if image.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "image is null").await);
+        }
+
+        let Some(components) = Self::components(format) else {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid image format").await);
+        };
+        let length = (width.max(0) * height.max(0)) as usize * components;
+        if jvm.array_length(&image).await? < length {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "image data is too short").await);
+        }
+
+        let data: Vec<i8> = jvm.load_array(&image, 0, length).await?;
+        let pixels = Self::to_rgba(format, &cast_vec(data));
+
+        Self::initialize(jvm, &mut this, format, width, height, false, pixels).await
+    }
+
+    async fn init_with_palette(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        format: i32,
+        width: i32,
+        height: i32,
+        image: ClassInstanceRef<Array<i8>>,
+        palette: ClassInstanceRef<Array<i8>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Image2D::<init>({this:?}, {format}, {width}, {height}, {image:?}, {palette:?})");
+
+        if image.is_null() || palette.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "image or palette is null").await);
+        }
+
+        if Self::components(format).is_none() {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid image format").await);
+        }
+
+        let pixel_count = (width.max(0) * height.max(0)) as usize;
+        if jvm.array_length(&image).await? < pixel_count {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "image data is too short").await);
+        }
+
+        let indices: Vec<i8> = jvm.load_array(&image, 0, pixel_count).await?;
+        let palette_length = jvm.array_length(&palette).await?;
+        let palette: Vec<i8> = jvm.load_array(&palette, 0, palette_length).await?;
+        let palette = Self::to_rgba(format, &cast_vec(palette));
+
+        let mut pixels = Vec::with_capacity(pixel_count * 4);
+        for index in indices {
+            let offset = (index as u8 as usize) * 4;
+            if offset + 4 > palette.len() {
+                return Err(jvm.exception("java/lang/IllegalArgumentException", "palette index out of range").await);
+            }
+            pixels.extend_from_slice(&palette[offset..offset + 4]);
+        }
+
+        Self::initialize(jvm, &mut this, format, width, height, false, pixels).await
+    }
+
+    async fn set(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        image: ClassInstanceRef<Array<i8>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Image2D::set({this:?}, {x}, {y}, {width}, {height}, {image:?})");
+
+        if image.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "image is null").await);
+        }
+
+        let mutable: bool = jvm.get_field(&this, "mutable", "Z").await?;
+        if !mutable {
+            return Err(jvm.exception("java/lang/IllegalStateException", "image is immutable").await);
+        }
+
+        let format: i32 = jvm.get_field(&this, "format", "I").await?;
+        let image_width: i32 = jvm.get_field(&this, "width", "I").await?;
+        let image_height: i32 = jvm.get_field(&this, "height", "I").await?;
+        if x < 0 || y < 0 || width <= 0 || height <= 0 || x + width > image_width || y + height > image_height {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "region out of bounds").await);
+        }
+
+        let components = Self::components(format).unwrap();
+        let length = (width * height) as usize * components;
+        if jvm.array_length(&image).await? < length {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "image data is too short").await);
+        }
+
+        let data: Vec<i8> = jvm.load_array(&image, 0, length).await?;
+        let rgba = Self::to_rgba(format, &cast_vec(data));
+
+        let mut pixels = jvm.get_field(&this, "pixels", "[B").await?;
+        let mut buffer = jvm.array_raw_buffer_mut(&mut pixels).await?;
+        for row in 0..height as usize {
+            let offset = (((y as usize + row) * image_width as usize) + x as usize) * 4;
+            let source = row * width as usize * 4;
+            buffer.write(offset, &rgba[source..source + width as usize * 4])?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_format(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Image2D::getFormat({this:?})");
+
+        jvm.get_field(&this, "format", "I").await
+    }
+
+    async fn get_width(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Image2D::getWidth({this:?})");
+
+        jvm.get_field(&this, "width", "I").await
+    }
+
+    async fn get_height(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Image2D::getHeight({this:?})");
+
+        jvm.get_field(&this, "height", "I").await
+    }
+
+    async fn is_mutable(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Image2D::isMutable({this:?})");
+
+        jvm.get_field(&this, "mutable", "Z").await
+    }
+
+    async fn initialize(
+        jvm: &Jvm,
+        this: &mut ClassInstanceRef<Self>,
+        format: i32,
+        width: i32,
+        height: i32,
+        mutable: bool,
+        pixels: Vec<u8>,
+    ) -> JvmResult<()> {
+        if Self::components(format).is_none() {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid image format").await);
+        }
+        if width <= 0 || height <= 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid image size").await);
+        }
+
+        let _: () = jvm.invoke_special(this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        let mut pixel_array = jvm.instantiate_array("B", pixels.len()).await?;
+        jvm.array_raw_buffer_mut(&mut pixel_array).await?.write(0, &pixels)?;
+
+        jvm.put_field(this, "format", "I", format).await?;
+        jvm.put_field(this, "width", "I", width).await?;
+        jvm.put_field(this, "height", "I", height).await?;
+        jvm.put_field(this, "mutable", "Z", mutable).await?;
+        jvm.put_field(this, "pixels", "[B", pixel_array).await?;
+
+        Ok(())
+    }
+
+    fn components(format: i32) -> Option<usize> {
+        match format {
+            ALPHA | LUMINANCE => Some(1),
+            LUMINANCE_ALPHA => Some(2),
+            RGB => Some(3),
+            RGBA => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Expands pixel data in the given format to RGBA.
+    pub(crate) fn to_rgba(format: i32, data: &[u8]) -> Vec<u8> {
+        match format {
+            ALPHA => data.iter().flat_map(|&a| [0xff, 0xff, 0xff, a]).collect(),
+            LUMINANCE => data.iter().flat_map(|&l| [l, l, l, 0xff]).collect(),
+            LUMINANCE_ALPHA => data.chunks_exact(2).flat_map(|x| [x[0], x[0], x[0], x[1]]).collect(),
+            RGB => data.chunks_exact(3).flat_map(|x| [x[0], x[1], x[2], 0xff]).collect(),
+            _ => data.to_vec(),
+        }
+    }
+
+    /// Drops the components the format doesn't store, the way the reference implementation does.
+    fn convert_pixel(format: i32, [r, g, b, a]: [u8; 4]) -> [u8; 4] {
+        let luminance = ((r as u32 * 77 + g as u32 * 150 + b as u32 * 29) >> 8) as u8;
+
+        match format {
+            ALPHA => [0xff, 0xff, 0xff, a],
+            LUMINANCE => [luminance, luminance, luminance, 0xff],
+            LUMINANCE_ALPHA => [luminance, luminance, luminance, a],
+            RGB => [r, g, b, 0xff],
+            _ => [r, g, b, a],
+        }
+    }
+
+    /// Writes back RGBA pixels rendered into a mutable image, dropping what the format doesn't store.
+    pub(crate) async fn store_pixels(jvm: &Jvm, this: &ClassInstanceRef<Self>, pixels: &[[u8; 4]]) -> JvmResult<()> {
+        let format: i32 = jvm.get_field(this, "format", "I").await?;
+
+        let data = pixels.iter().flat_map(|&pixel| Self::convert_pixel(format, pixel)).collect::<Vec<_>>();
+
+        let mut pixel_array = jvm.get_field(this, "pixels", "[B").await?;
+        jvm.array_raw_buffer_mut(&mut pixel_array).await?.write(0, &data)?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn texture_image<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<TextureImage> {
+        let format: i32 = jvm.get_field(this, "format", "I").await?;
+        let width: i32 = jvm.get_field(this, "width", "I").await?;
+        let height: i32 = jvm.get_field(this, "height", "I").await?;
+
+        let pixels = jvm.get_field(this, "pixels", "[B").await?;
+        let mut data = vec![0; (width * height * 4) as usize];
+        jvm.array_raw_buffer(&pixels).await?.read(0, &mut data)?;
+
+        Ok(TextureImage {
+            width: width as _,
+            height: height as _,
+            texels: data
+                .chunks_exact(4)
+                .map(|x| [x[0] as f32 / 255.0, x[1] as f32 / 255.0, x[2] as f32 / 255.0, x[3] as f32 / 255.0])
+                .collect(),
+            has_color: format != ALPHA,
+            has_alpha: matches!(format, ALPHA | LUMINANCE_ALPHA | RGBA),
+        })
+    }
+}
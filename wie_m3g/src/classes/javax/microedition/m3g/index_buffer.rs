@@ -0,0 +1,123 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+// class javax.microedition.m3g.IndexBuffer
+pub struct IndexBuffer;
+
+impl IndexBuffer {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/IndexBuffer",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::empty()),
+                JavaMethodProto::new("getIndexCount", "()I", Self::get_index_count, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getIndices", "([I)V", Self::get_indices, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("indices", "[I", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("stripLengths", "[I", FieldAccessFlags::PROTECTED),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.IndexBuffer::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        Ok(())
+    }
+
+    async fn get_index_count(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.IndexBuffer::getIndexCount({this:?})");
+
+        Ok(Self::triangles(jvm, &this).await?.len() as i32 * 3)
+    }
+
+    async fn get_indices(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        mut indices: ClassInstanceRef<Array<i32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.IndexBuffer::getIndices({this:?}, {indices:?})");
+
+        if indices.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "indices is null").await);
+        }
+
+        let triangles = Self::triangles(jvm, &this).await?;
+        if jvm.array_length(&indices).await? < triangles.len() * 3 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "indices is too short").await);
+        }
+
+        let values = triangles.into_iter().flatten().map(|x| x as i32).collect::<Vec<_>>();
+        jvm.store_array(&mut indices, 0, values).await
+    }
+
+    /// Unpacks the strips into separate triangles, flipping every other one so they all share the strip's winding.
+    pub(crate) async fn triangles<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<Vec<[u32; 3]>> {
+        let indices = jvm.get_field(this, "indices", "[I").await?;
+        let strip_lengths = jvm.get_field(this, "stripLengths", "[I").await?;
+
+        let length = jvm.array_length(&indices).await?;
+        let indices: Vec<i32> = jvm.load_array(&indices, 0, length).await?;
+        let strip_count = jvm.array_length(&strip_lengths).await?;
+        let strip_lengths: Vec<i32> = jvm.load_array(&strip_lengths, 0, strip_count).await?;
+
+        Ok(unpack_strips(&indices, &strip_lengths))
+    }
+}
+
+fn unpack_strips(indices: &[i32], strip_lengths: &[i32]) -> Vec<[u32; 3]> {
+    let mut result = Vec::new();
+
+    let mut offset = 0;
+    for &strip_length in strip_lengths {
+        let strip = &indices[offset..offset + strip_length as usize];
+        for (i, window) in strip.windows(3).enumerate() {
+            let triangle = if i % 2 == 0 {
+                [window[0], window[1], window[2]]
+            } else {
+                [window[1], window[0], window[2]]
+            };
+
+            // degenerate triangles are used to stitch strips together
+            if triangle[0] != triangle[1] && triangle[1] != triangle[2] && triangle[0] != triangle[2] {
+                result.push(triangle.map(|x| x as u32));
+            }
+        }
+        offset += strip_length as usize;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::unpack_strips;
+
+    #[test]
+    fn test_unpack_strips() {
+        let triangles = unpack_strips(&[0, 1, 2, 3, 4, 5, 6], &[4, 3]);
+
+        assert_eq!(triangles, vec![[0, 1, 2], [2, 1, 3], [4, 5, 6]]);
+    }
+
+    #[test]
+    fn test_unpack_strips_degenerate() {
+        let triangles = unpack_strips(&[0, 1, 2, 2, 3, 4, 5], &[7]);
+
+        assert_eq!(triangles, vec![[0, 1, 2], [3, 2, 4], [3, 4, 5]]);
+    }
+}
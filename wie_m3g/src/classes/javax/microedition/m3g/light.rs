@@ -0,0 +1,211 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::renderer::{Light as RendererLight, LightMode, Matrix4, from_argb};
+
+const AMBIENT: i32 = 128;
+const DIRECTIONAL: i32 = 129;
+const OMNI: i32 = 130;
+const SPOT: i32 = 131;
+
+// class javax.microedition.m3g.Light
+pub struct Light;
+
+impl Light {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Light",
+            parent_class: Some("javax/microedition/m3g/Node"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setMode", "(I)V", Self::set_mode, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMode", "()I", Self::get_mode, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setColor", "(I)V", Self::set_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getColor", "()I", Self::get_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setIntensity", "(F)V", Self::set_intensity, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getIntensity", "()F", Self::get_intensity, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setAttenuation", "(FFF)V", Self::set_attenuation, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getConstantAttenuation", "()F", Self::get_constant_attenuation, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getLinearAttenuation", "()F", Self::get_linear_attenuation, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getQuadraticAttenuation",
+                    "()F",
+                    Self::get_quadratic_attenuation,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("setSpotAngle", "(F)V", Self::set_spot_angle, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getSpotAngle", "()F", Self::get_spot_angle, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setSpotExponent", "(F)V", Self::set_spot_exponent, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getSpotExponent", "()F", Self::get_spot_exponent, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("mode", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("color", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("intensity", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("constantAttenuation", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("linearAttenuation", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("quadraticAttenuation", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("spotAngle", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("spotExponent", "F", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Light::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Node", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "mode", "I", DIRECTIONAL).await?;
+        jvm.put_field(&mut this, "color", "I", 0xffffff).await?;
+        jvm.put_field(&mut this, "intensity", "F", 1.0f32).await?;
+        jvm.put_field(&mut this, "constantAttenuation", "F", 1.0f32).await?;
+        jvm.put_field(&mut this, "linearAttenuation", "F", 0.0f32).await?;
+        jvm.put_field(&mut this, "quadraticAttenuation", "F", 0.0f32).await?;
+        jvm.put_field(&mut this, "spotAngle", "F", 45.0f32).await?;
+        jvm.put_field(&mut this, "spotExponent", "F", 0.0f32).await?;
+
+        Ok(())
+    }
+
+    async fn set_mode(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Light::setMode({this:?}, {mode})");
+
+        if !(AMBIENT..=SPOT).contains(&mode) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid light mode").await);
+        }
+
+        jvm.put_field(&mut this, "mode", "I", mode).await
+    }
+
+    async fn get_mode(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Light::getMode({this:?})");
+
+        jvm.get_field(&this, "mode", "I").await
+    }
+
+    async fn set_color(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, rgb: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Light::setColor({this:?}, {rgb:#x})");
+
+        jvm.put_field(&mut this, "color", "I", rgb & 0xffffff).await
+    }
+
+    async fn get_color(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Light::getColor({this:?})");
+
+        jvm.get_field(&this, "color", "I").await
+    }
+
+    async fn set_intensity(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, intensity: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Light::setIntensity({this:?}, {intensity})");
+
+        jvm.put_field(&mut this, "intensity", "F", intensity).await
+    }
+
+    async fn get_intensity(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Light::getIntensity({this:?})");
+
+        jvm.get_field(&this, "intensity", "F").await
+    }
+
+    async fn set_attenuation(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        constant: f32,
+        linear: f32,
+        quadratic: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Light::setAttenuation({this:?}, {constant}, {linear}, {quadratic})");
+
+        if constant < 0.0 || linear < 0.0 || quadratic < 0.0 || (constant == 0.0 && linear == 0.0 && quadratic == 0.0) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid attenuation").await);
+        }
+
+        jvm.put_field(&mut this, "constantAttenuation", "F", constant).await?;
+        jvm.put_field(&mut this, "linearAttenuation", "F", linear).await?;
+        jvm.put_field(&mut this, "quadraticAttenuation", "F", quadratic).await
+    }
+
+    async fn get_constant_attenuation(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Light::getConstantAttenuation({this:?})");
+
+        jvm.get_field(&this, "constantAttenuation", "F").await
+    }
+
+    async fn get_linear_attenuation(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Light::getLinearAttenuation({this:?})");
+
+        jvm.get_field(&this, "linearAttenuation", "F").await
+    }
+
+    async fn get_quadratic_attenuation(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Light::getQuadraticAttenuation({this:?})");
+
+        jvm.get_field(&this, "quadraticAttenuation", "F").await
+    }
+
+    async fn set_spot_angle(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, angle: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Light::setSpotAngle({this:?}, {angle})");
+
+        if !(0.0..=90.0).contains(&angle) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "spot angle out of range").await);
+        }
+
+        jvm.put_field(&mut this, "spotAngle", "F", angle).await
+    }
+
+    async fn get_spot_angle(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Light::getSpotAngle({this:?})");
+
+        jvm.get_field(&this, "spotAngle", "F").await
+    }
+
+    async fn set_spot_exponent(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, exponent: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Light::setSpotExponent({this:?}, {exponent})");
+
+        if !(0.0..=128.0).contains(&exponent) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "spot exponent out of range").await);
+        }
+
+        jvm.put_field(&mut this, "spotExponent", "F", exponent).await
+    }
+
+    async fn get_spot_exponent(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Light::getSpotExponent({this:?})");
+
+        jvm.get_field(&this, "spotExponent", "F").await
+    }
+
+    pub(crate) async fn light<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, transform: Matrix4) -> JvmResult<RendererLight> {
+        let mode: i32 = jvm.get_field(this, "mode", "I").await?;
+        let color: i32 = jvm.get_field(this, "color", "I").await?;
+        let [r, g, b, _] = from_argb(color as u32);
+
+        Ok(RendererLight {
+            mode: match mode {
+                AMBIENT => LightMode::Ambient,
+                OMNI => LightMode::Omni,
+                SPOT => LightMode::Spot,
+                _ => LightMode::Directional,
+            },
+            color: [r, g, b],
+            intensity: jvm.get_field(this, "intensity", "F").await?,
+            attenuation: [
+                jvm.get_field(this, "constantAttenuation", "F").await?,
+                jvm.get_field(this, "linearAttenuation", "F").await?,
+                jvm.get_field(this, "quadraticAttenuation", "F").await?,
+            ],
+            spot_angle: jvm.get_field(this, "spotAngle", "F").await?,
+            spot_exponent: jvm.get_field(this, "spotExponent", "F").await?,
+            transform,
+        })
+    }
+}
@@ -0,0 +1,1143 @@
+use alloc::{format, vec, vec::Vec};
+
+use java_class_proto::JavaMethodProto;
+use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::lang::{Object, String};
+use jvm::{
+    Array, ClassInstanceRef, Jvm, Result as JvmResult,
+    runtime::{JavaIoInputStream, JavaLangClassLoader, JavaLangString},
+};
+
+use wie_backend::canvas::decode_image;
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::{Object3D, image_2d},
+    loader::{self, Indices, NodeData, Object, Object3DData, ObjectIndex, Projection, TransformableData},
+};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+// class javax.microedition.m3g.Loader
+pub struct Loader;
+
+impl Loader {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Loader",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PRIVATE),
+                JavaMethodProto::new(
+                    "load",
+                    "(Ljava/lang/String;)[Ljavax/microedition/m3g/Object3D;",
+                    Self::load_from_name,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new(
+                    "load",
+                    "([BI)[Ljavax/microedition/m3g/Object3D;",
+                    Self::load_from_data,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+            ],
+            fields: vec![],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Loader::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        Ok(())
+    }
+
+    async fn load_from_name(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        name: ClassInstanceRef<String>,
+    ) -> JvmResult<ClassInstanceRef<Array<ClassInstanceRef<Object3D>>>> {
+        tracing::debug!("javax.microedition.m3g.Loader::load({name:?})");
+
+        if name.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "name is null").await);
+        }
+
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
+
+        let class_loader = JavaLangClassLoader::get_system_class_loader(jvm).await?;
+        let stream = JavaLangClassLoader::get_resource_as_stream(jvm, &class_loader, &name).await?;
+        let Some(stream) = stream else {
+            let exception = jvm.exception("java/io/IOException", &format!("Resource not found: {name}")).await;
+            return Err(exception);
+        };
+
+        let data = JavaIoInputStream::read_until_end(jvm, &stream).await?;
+
+        Self::load(jvm, &data).await
+    }
+
+    async fn load_from_data(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        data: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+    ) -> JvmResult<ClassInstanceRef<Array<ClassInstanceRef<Object3D>>>> {
+        tracing::debug!("javax.microedition.m3g.Loader::load({data:?}, {offset})");
+
+        if data.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
+        }
+
+        let length = jvm.array_length(&data).await?;
+        if offset < 0 || offset as usize >= length {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "offset out of bounds").await);
+        }
+
+        let mut buffer = vec![0; length - offset as usize];
+        jvm.array_raw_buffer(&data).await?.read(offset as _, &mut buffer)?;
+
+        Self::load(jvm, &buffer).await
+    }
+
+    async fn load(jvm: &Jvm, data: &[u8]) -> JvmResult<ClassInstanceRef<Array<ClassInstanceRef<Object3D>>>> {
+        let roots = if loader::is_m3g(data) {
+            let objects = match loader::parse(data) {
+                Ok(x) => x,
+                Err(x) => return Err(jvm.exception("java/io/IOException", &format!("{x}")).await),
+            };
+
+            let mut instances = Vec::with_capacity(objects.len());
+            for object in &objects {
+                let instance = Self::create(jvm, &instances, object).await?;
+                instances.push(instance);
+            }
+
+            loader::roots(&objects)
+                .into_iter()
+                .map(|index| instances[index as usize - 1].clone())
+                .collect::<Vec<_>>()
+        } else if data.starts_with(&PNG_SIGNATURE) {
+            vec![Self::create_image_from_png(jvm, data).await?]
+        } else {
+            return Err(jvm.exception("java/io/IOException", "Unrecognized file format").await);
+        };
+
+        let mut result = jvm.instantiate_array("Ljavax/microedition/m3g/Object3D;", roots.len()).await?;
+        jvm.store_array(&mut result, 0, roots).await?;
+
+        Ok(result.into())
+    }
+
+    async fn create_image_from_png(jvm: &Jvm, data: &[u8]) -> JvmResult<ClassInstanceRef<Object3D>> {
+        let image = match decode_image(data) {
+            Ok(x) => x,
+            Err(x) => return Err(jvm.exception("java/io/IOException", &format!("{x}")).await),
+        };
+
+        let pixels = image
+            .colors()
+            .into_iter()
+            .flat_map(|color| [color.r as i8, color.g as i8, color.b as i8, color.a as i8])
+            .collect::<Vec<_>>();
+
+        let mut array = jvm.instantiate_array("B", pixels.len()).await?;
+        jvm.store_array(&mut array, 0, pixels).await?;
+
+        Ok(jvm
+            .new_class(
+                "javax/microedition/m3g/Image2D",
+                "(III[B)V",
+                (image_2d::RGBA, image.width() as i32, image.height() as i32, array),
+            )
+            .await?
+            .into())
+    }
+
+    async fn create(jvm: &Jvm, instances: &[ClassInstanceRef<Object3D>], object: &Object) -> JvmResult<ClassInstanceRef<Object3D>> {
+        let reference = async |index: ObjectIndex| -> JvmResult<ClassInstanceRef<Object3D>> {
+            // objects may only refer to the ones before them
+            match index {
+                0 => Ok(None.into()),
+                x if x as usize <= instances.len() => Ok(instances[x as usize - 1].clone()),
+                _ => Err(jvm.exception("java/io/IOException", "Invalid object reference").await),
+            }
+        };
+
+        let instance: ClassInstanceRef<Object3D> = match object {
+            Object::Header | Object::Unsupported(_) => return Ok(None.into()),
+            Object::Appearance {
+                object,
+                layer,
+                compositing_mode,
+                fog,
+                polygon_mode,
+                material,
+                textures,
+            } => {
+                let instance = Self::new_object(jvm, "javax/microedition/m3g/Appearance", object).await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Appearance", "setLayer", "(I)V", (*layer as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Appearance",
+                        "setCompositingMode",
+                        "(Ljavax/microedition/m3g/CompositingMode;)V",
+                        (reference(*compositing_mode).await?,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Appearance",
+                        "setFog",
+                        "(Ljavax/microedition/m3g/Fog;)V",
+                        (reference(*fog).await?,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Appearance",
+                        "setPolygonMode",
+                        "(Ljavax/microedition/m3g/PolygonMode;)V",
+                        (reference(*polygon_mode).await?,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Appearance",
+                        "setMaterial",
+                        "(Ljavax/microedition/m3g/Material;)V",
+                        (reference(*material).await?,),
+                    )
+                    .await?;
+                for (unit, texture) in textures.iter().enumerate() {
+                    let _: () = jvm
+                        .invoke_virtual(
+                            &instance,
+                            "javax/microedition/m3g/Appearance",
+                            "setTexture",
+                            "(ILjavax/microedition/m3g/Texture2D;)V",
+                            (unit as i32, reference(*texture).await?),
+                        )
+                        .await?;
+                }
+
+                instance
+            }
+            Object::Background {
+                object,
+                color,
+                image,
+                image_mode_x,
+                image_mode_y,
+                crop,
+                depth_clear_enabled,
+                color_clear_enabled,
+            } => {
+                let instance = Self::new_object(jvm, "javax/microedition/m3g/Background", object).await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Background", "setColor", "(I)V", (*color as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Background",
+                        "setImage",
+                        "(Ljavax/microedition/m3g/Image2D;)V",
+                        (reference(*image).await?,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Background",
+                        "setImageMode",
+                        "(II)V",
+                        (*image_mode_x as i32, *image_mode_y as i32),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Background",
+                        "setCrop",
+                        "(IIII)V",
+                        (crop[0], crop[1], crop[2], crop[3]),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Background",
+                        "setDepthClearEnable",
+                        "(Z)V",
+                        (*depth_clear_enabled,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Background",
+                        "setColorClearEnable",
+                        "(Z)V",
+                        (*color_clear_enabled,),
+                    )
+                    .await?;
+
+                instance
+            }
+            Object::Camera { node, projection } => {
+                let instance = Self::new_node(jvm, "javax/microedition/m3g/Camera", node).await?;
+                match projection {
+                    Projection::Generic(matrix) => {
+                        let transform = Self::new_transform(jvm, matrix).await?;
+                        let _: () = jvm
+                            .invoke_virtual(
+                                &instance,
+                                "javax/microedition/m3g/Camera",
+                                "setGeneric",
+                                "(Ljavax/microedition/m3g/Transform;)V",
+                                (transform,),
+                            )
+                            .await?;
+                    }
+                    Projection::Parallel {
+                        kind,
+                        fovy,
+                        aspect_ratio,
+                        near,
+                        far,
+                    } => {
+                        let method = if *kind == 49 { "setParallel" } else { "setPerspective" };
+                        let _: () = jvm
+                            .invoke_virtual(
+                                &instance,
+                                "javax/microedition/m3g/Camera",
+                                method,
+                                "(FFFF)V",
+                                (*fovy, *aspect_ratio, *near, *far),
+                            )
+                            .await?;
+                    }
+                }
+
+                instance
+            }
+            Object::CompositingMode {
+                object,
+                depth_test_enabled,
+                depth_write_enabled,
+                color_write_enabled,
+                alpha_write_enabled,
+                blending,
+                alpha_threshold,
+                depth_offset_factor,
+                depth_offset_units,
+            } => {
+                let instance = Self::new_object(jvm, "javax/microedition/m3g/CompositingMode", object).await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/CompositingMode",
+                        "setDepthTestEnable",
+                        "(Z)V",
+                        (*depth_test_enabled,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/CompositingMode",
+                        "setDepthWriteEnable",
+                        "(Z)V",
+                        (*depth_write_enabled,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/CompositingMode",
+                        "setColorWriteEnable",
+                        "(Z)V",
+                        (*color_write_enabled,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/CompositingMode",
+                        "setAlphaWriteEnable",
+                        "(Z)V",
+                        (*alpha_write_enabled,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/CompositingMode",
+                        "setBlending",
+                        "(I)V",
+                        (*blending as i32,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/CompositingMode",
+                        "setAlphaThreshold",
+                        "(F)V",
+                        (*alpha_threshold as f32 / 255.0,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/CompositingMode",
+                        "setDepthOffset",
+                        "(FF)V",
+                        (*depth_offset_factor, *depth_offset_units),
+                    )
+                    .await?;
+
+                instance
+            }
+            Object::Fog {
+                object,
+                color,
+                mode,
+                density,
+                near,
+                far,
+            } => {
+                let instance = Self::new_object(jvm, "javax/microedition/m3g/Fog", object).await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Fog", "setColor", "(I)V", (*color as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Fog", "setMode", "(I)V", (*mode as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Fog", "setDensity", "(F)V", (*density,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Fog", "setLinear", "(FF)V", (*near, *far))
+                    .await?;
+
+                instance
+            }
+            Object::PolygonMode {
+                object,
+                culling,
+                shading,
+                winding,
+                two_sided_lighting_enabled,
+                local_camera_lighting_enabled,
+                perspective_correction_enabled,
+            } => {
+                let instance = Self::new_object(jvm, "javax/microedition/m3g/PolygonMode", object).await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/PolygonMode", "setCulling", "(I)V", (*culling as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/PolygonMode", "setShading", "(I)V", (*shading as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/PolygonMode", "setWinding", "(I)V", (*winding as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/PolygonMode",
+                        "setTwoSidedLightingEnable",
+                        "(Z)V",
+                        (*two_sided_lighting_enabled,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/PolygonMode",
+                        "setLocalCameraLightingEnable",
+                        "(Z)V",
+                        (*local_camera_lighting_enabled,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/PolygonMode",
+                        "setPerspectiveCorrectionEnable",
+                        "(Z)V",
+                        (*perspective_correction_enabled,),
+                    )
+                    .await?;
+
+                instance
+            }
+            Object::Group { node, children } => {
+                let instance = Self::new_node(jvm, "javax/microedition/m3g/Group", node).await?;
+                for child in children {
+                    let _: () = jvm
+                        .invoke_virtual(
+                            &instance,
+                            "javax/microedition/m3g/Group",
+                            "addChild",
+                            "(Ljavax/microedition/m3g/Node;)V",
+                            (reference(*child).await?,),
+                        )
+                        .await?;
+                }
+
+                instance
+            }
+            Object::Image2D {
+                object,
+                format,
+                mutable,
+                width,
+                height,
+                palette,
+                pixels,
+            } => {
+                let (width, height, format) = (*width as i32, *height as i32, *format as i32);
+                let instance = if *mutable {
+                    jvm.new_class("javax/microedition/m3g/Image2D", "(III)V", (format, width, height)).await?
+                } else if palette.is_empty() {
+                    let pixels = Self::byte_array(jvm, pixels).await?;
+                    jvm.new_class("javax/microedition/m3g/Image2D", "(III[B)V", (format, width, height, pixels))
+                        .await?
+                } else {
+                    let pixels = Self::byte_array(jvm, pixels).await?;
+                    let palette = Self::byte_array(jvm, palette).await?;
+                    jvm.new_class("javax/microedition/m3g/Image2D", "(III[B[B)V", (format, width, height, pixels, palette))
+                        .await?
+                };
+                let instance = instance.into();
+                Self::apply_object(jvm, &instance, object).await?;
+
+                instance
+            }
+            Object::TriangleStripArray {
+                object,
+                indices,
+                strip_lengths,
+            } => {
+                let mut strip_lengths_array = jvm.instantiate_array("I", strip_lengths.len()).await?;
+                jvm.store_array(&mut strip_lengths_array, 0, strip_lengths.iter().map(|&x| x as i32).collect::<Vec<_>>())
+                    .await?;
+
+                let instance = match indices {
+                    Indices::Implicit(first_index) => {
+                        jvm.new_class(
+                            "javax/microedition/m3g/TriangleStripArray",
+                            "(I[I)V",
+                            (*first_index as i32, strip_lengths_array),
+                        )
+                        .await?
+                    }
+                    Indices::Explicit(indices) => {
+                        let mut indices_array = jvm.instantiate_array("I", indices.len()).await?;
+                        jvm.store_array(&mut indices_array, 0, indices.iter().map(|&x| x as i32).collect::<Vec<_>>())
+                            .await?;
+
+                        jvm.new_class(
+                            "javax/microedition/m3g/TriangleStripArray",
+                            "([I[I)V",
+                            (indices_array, strip_lengths_array),
+                        )
+                        .await?
+                    }
+                };
+                let instance = instance.into();
+                Self::apply_object(jvm, &instance, object).await?;
+
+                instance
+            }
+            Object::Light {
+                node,
+                attenuation,
+                color,
+                mode,
+                intensity,
+                spot_angle,
+                spot_exponent,
+            } => {
+                let instance = Self::new_node(jvm, "javax/microedition/m3g/Light", node).await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Light",
+                        "setAttenuation",
+                        "(FFF)V",
+                        (attenuation[0], attenuation[1], attenuation[2]),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Light", "setColor", "(I)V", (*color as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Light", "setMode", "(I)V", (*mode as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Light", "setIntensity", "(F)V", (*intensity,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Light", "setSpotAngle", "(F)V", (*spot_angle,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Light", "setSpotExponent", "(F)V", (*spot_exponent,))
+                    .await?;
+
+                instance
+            }
+            Object::Material {
+                object,
+                ambient_color,
+                diffuse_color,
+                emissive_color,
+                specular_color,
+                shininess,
+                vertex_color_tracking_enabled,
+            } => {
+                let instance = Self::new_object(jvm, "javax/microedition/m3g/Material", object).await?;
+                for (target, color) in [
+                    (1024, ambient_color),
+                    (2048, diffuse_color),
+                    (4096, emissive_color),
+                    (8192, specular_color),
+                ] {
+                    let _: () = jvm
+                        .invoke_virtual(&instance, "javax/microedition/m3g/Material", "setColor", "(II)V", (target, *color as i32))
+                        .await?;
+                }
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Material", "setShininess", "(F)V", (*shininess,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Material",
+                        "setVertexColorTrackingEnable",
+                        "(Z)V",
+                        (*vertex_color_tracking_enabled,),
+                    )
+                    .await?;
+
+                instance
+            }
+            Object::Mesh {
+                node,
+                vertex_buffer,
+                submeshes,
+            } => {
+                let mut index_buffers = Vec::with_capacity(submeshes.len());
+                let mut appearances = Vec::with_capacity(submeshes.len());
+                for (index_buffer, appearance) in submeshes {
+                    index_buffers.push(reference(*index_buffer).await?);
+                    appearances.push(reference(*appearance).await?);
+                }
+
+                let mut index_buffers_array = jvm.instantiate_array("Ljavax/microedition/m3g/IndexBuffer;", submeshes.len()).await?;
+                jvm.store_array(&mut index_buffers_array, 0, index_buffers).await?;
+                let mut appearances_array = jvm.instantiate_array("Ljavax/microedition/m3g/Appearance;", submeshes.len()).await?;
+                jvm.store_array(&mut appearances_array, 0, appearances).await?;
+
+                let instance = jvm
+                    .new_class(
+                        "javax/microedition/m3g/Mesh",
+                        "(Ljavax/microedition/m3g/VertexBuffer;[Ljavax/microedition/m3g/IndexBuffer;[Ljavax/microedition/m3g/Appearance;)V",
+                        (reference(*vertex_buffer).await?, index_buffers_array, appearances_array),
+                    )
+                    .await?
+                    .into();
+                Self::apply_node(jvm, &instance, node).await?;
+
+                instance
+            }
+            Object::Texture2D {
+                transformable,
+                image,
+                blend_color,
+                blending,
+                wrapping_s,
+                wrapping_t,
+                level_filter,
+                image_filter,
+            } => {
+                let instance = jvm
+                    .new_class(
+                        "javax/microedition/m3g/Texture2D",
+                        "(Ljavax/microedition/m3g/Image2D;)V",
+                        (reference(*image).await?,),
+                    )
+                    .await?
+                    .into();
+                Self::apply_transformable(jvm, &instance, transformable).await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Texture2D",
+                        "setBlendColor",
+                        "(I)V",
+                        (*blend_color as i32,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(&instance, "javax/microedition/m3g/Texture2D", "setBlending", "(I)V", (*blending as i32,))
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Texture2D",
+                        "setWrapping",
+                        "(II)V",
+                        (*wrapping_s as i32, *wrapping_t as i32),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Texture2D",
+                        "setFiltering",
+                        "(II)V",
+                        (*level_filter as i32, *image_filter as i32),
+                    )
+                    .await?;
+
+                instance
+            }
+            Object::Sprite3D {
+                node,
+                image,
+                appearance,
+                scaled,
+                crop,
+            } => {
+                let instance = jvm
+                    .new_class(
+                        "javax/microedition/m3g/Sprite3D",
+                        "(ZLjavax/microedition/m3g/Image2D;Ljavax/microedition/m3g/Appearance;)V",
+                        (*scaled, reference(*image).await?, reference(*appearance).await?),
+                    )
+                    .await?
+                    .into();
+                Self::apply_node(jvm, &instance, node).await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/Sprite3D",
+                        "setCrop",
+                        "(IIII)V",
+                        (crop[0], crop[1], crop[2], crop[3]),
+                    )
+                    .await?;
+
+                instance
+            }
+            Object::VertexArray {
+                object,
+                component_size,
+                component_count,
+                vertex_count,
+                values,
+            } => {
+                let instance = jvm
+                    .new_class(
+                        "javax/microedition/m3g/VertexArray",
+                        "(III)V",
+                        (*vertex_count as i32, *component_count as i32, *component_size as i32),
+                    )
+                    .await?
+                    .into();
+                Self::apply_object(jvm, &instance, object).await?;
+
+                if *component_size == 1 {
+                    let mut array = jvm.instantiate_array("B", values.len()).await?;
+                    jvm.store_array(&mut array, 0, values.iter().map(|&x| x as i8).collect::<Vec<_>>())
+                        .await?;
+                    let _: () = jvm
+                        .invoke_virtual(
+                            &instance,
+                            "javax/microedition/m3g/VertexArray",
+                            "set",
+                            "(II[B)V",
+                            (0, *vertex_count as i32, array),
+                        )
+                        .await?;
+                } else {
+                    let mut array = jvm.instantiate_array("S", values.len()).await?;
+                    jvm.store_array(&mut array, 0, values.clone()).await?;
+                    let _: () = jvm
+                        .invoke_virtual(
+                            &instance,
+                            "javax/microedition/m3g/VertexArray",
+                            "set",
+                            "(II[S)V",
+                            (0, *vertex_count as i32, array),
+                        )
+                        .await?;
+                }
+
+                instance
+            }
+            Object::VertexBuffer {
+                object,
+                default_color,
+                positions,
+                position_bias,
+                position_scale,
+                normals,
+                colors,
+                tex_coords,
+            } => {
+                let instance = Self::new_object(jvm, "javax/microedition/m3g/VertexBuffer", object).await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/VertexBuffer",
+                        "setDefaultColor",
+                        "(I)V",
+                        (*default_color as i32,),
+                    )
+                    .await?;
+
+                let positions = reference(*positions).await?;
+                if !positions.is_null() {
+                    let bias = Self::float_array(jvm, position_bias).await?;
+                    let _: () = jvm
+                        .invoke_virtual(
+                            &instance,
+                            "javax/microedition/m3g/VertexBuffer",
+                            "setPositions",
+                            "(Ljavax/microedition/m3g/VertexArray;F[F)V",
+                            (positions, *position_scale, bias),
+                        )
+                        .await?;
+                }
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/VertexBuffer",
+                        "setNormals",
+                        "(Ljavax/microedition/m3g/VertexArray;)V",
+                        (reference(*normals).await?,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/VertexBuffer",
+                        "setColors",
+                        "(Ljavax/microedition/m3g/VertexArray;)V",
+                        (reference(*colors).await?,),
+                    )
+                    .await?;
+                for (unit, tex_coords) in tex_coords.iter().enumerate() {
+                    let array = reference(tex_coords.array).await?;
+                    if array.is_null() {
+                        continue;
+                    }
+
+                    let bias = Self::float_array(jvm, &tex_coords.bias).await?;
+                    let _: () = jvm
+                        .invoke_virtual(
+                            &instance,
+                            "javax/microedition/m3g/VertexBuffer",
+                            "setTexCoords",
+                            "(ILjavax/microedition/m3g/VertexArray;F[F)V",
+                            (unit as i32, array, tex_coords.scale, bias),
+                        )
+                        .await?;
+                }
+
+                instance
+            }
+            Object::World {
+                node,
+                children,
+                active_camera,
+                background,
+            } => {
+                let instance = Self::new_node(jvm, "javax/microedition/m3g/World", node).await?;
+                for child in children {
+                    let _: () = jvm
+                        .invoke_virtual(
+                            &instance,
+                            "javax/microedition/m3g/World",
+                            "addChild",
+                            "(Ljavax/microedition/m3g/Node;)V",
+                            (reference(*child).await?,),
+                        )
+                        .await?;
+                }
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/World",
+                        "setActiveCamera",
+                        "(Ljavax/microedition/m3g/Camera;)V",
+                        (reference(*active_camera).await?,),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &instance,
+                        "javax/microedition/m3g/World",
+                        "setBackground",
+                        "(Ljavax/microedition/m3g/Background;)V",
+                        (reference(*background).await?,),
+                    )
+                    .await?;
+
+                instance
+            }
+        };
+
+        Ok(instance)
+    }
+
+    async fn new_object(jvm: &Jvm, class_name: &str, object: &Object3DData) -> JvmResult<ClassInstanceRef<Object3D>> {
+        let instance = jvm.new_class(class_name, "()V", ()).await?.into();
+        Self::apply_object(jvm, &instance, object).await?;
+
+        Ok(instance)
+    }
+
+    async fn new_node(jvm: &Jvm, class_name: &str, node: &NodeData) -> JvmResult<ClassInstanceRef<Object3D>> {
+        let instance = jvm.new_class(class_name, "()V", ()).await?.into();
+        Self::apply_node(jvm, &instance, node).await?;
+
+        Ok(instance)
+    }
+
+    async fn new_transform(jvm: &Jvm, matrix: &[f32; 16]) -> JvmResult<ClassInstanceRef<Object>> {
+        let transform = jvm.new_class("javax/microedition/m3g/Transform", "()V", ()).await?;
+        let matrix = Self::float_array(jvm, matrix).await?;
+        let _: () = jvm
+            .invoke_virtual(&transform, "javax/microedition/m3g/Transform", "set", "([F)V", (matrix,))
+            .await?;
+
+        Ok(transform.into())
+    }
+
+    async fn apply_object(jvm: &Jvm, instance: &ClassInstanceRef<Object3D>, object: &Object3DData) -> JvmResult<()> {
+        let _: () = jvm
+            .invoke_virtual(instance, "javax/microedition/m3g/Object3D", "setUserID", "(I)V", (object.user_id,))
+            .await?;
+
+        // user parameters are exposed as a Hashtable from parameter id to its bytes
+        if !object.user_parameters.is_empty() {
+            let parameters = jvm.new_class("java/util/Hashtable", "()V", ()).await?;
+            for (id, value) in &object.user_parameters {
+                let key = jvm.new_class("java/lang/Integer", "(I)V", (*id,)).await?;
+                let value = Self::byte_array(jvm, value).await?;
+
+                let _: ClassInstanceRef<Object> = jvm
+                    .invoke_virtual(
+                        &parameters,
+                        "java/util/Hashtable",
+                        "put",
+                        "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+                        (key, value),
+                    )
+                    .await?;
+            }
+
+            let _: () = jvm
+                .invoke_virtual(
+                    instance,
+                    "javax/microedition/m3g/Object3D",
+                    "setUserObject",
+                    "(Ljava/lang/Object;)V",
+                    (parameters,),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn apply_transformable(jvm: &Jvm, instance: &ClassInstanceRef<Object3D>, transformable: &TransformableData) -> JvmResult<()> {
+        Self::apply_object(jvm, instance, &transformable.object).await?;
+
+        if let Some(component) = &transformable.component_transform {
+            let [tx, ty, tz] = component.translation;
+            let [sx, sy, sz] = component.scale;
+            let [ax, ay, az] = component.orientation_axis;
+
+            let _: () = jvm
+                .invoke_virtual(instance, "javax/microedition/m3g/Transformable", "setTranslation", "(FFF)V", (tx, ty, tz))
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(instance, "javax/microedition/m3g/Transformable", "setScale", "(FFF)V", (sx, sy, sz))
+                .await?;
+            if component.orientation_angle != 0.0 {
+                let _: () = jvm
+                    .invoke_virtual(
+                        instance,
+                        "javax/microedition/m3g/Transformable",
+                        "setOrientation",
+                        "(FFFF)V",
+                        (component.orientation_angle, ax, ay, az),
+                    )
+                    .await?;
+            }
+        }
+        if let Some(matrix) = &transformable.general_transform {
+            let transform = Self::new_transform(jvm, matrix).await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    instance,
+                    "javax/microedition/m3g/Transformable",
+                    "setTransform",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    (transform,),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn apply_node(jvm: &Jvm, instance: &ClassInstanceRef<Object3D>, node: &NodeData) -> JvmResult<()> {
+        Self::apply_transformable(jvm, instance, &node.transformable).await?;
+
+        let _: () = jvm
+            .invoke_virtual(
+                instance,
+                "javax/microedition/m3g/Node",
+                "setRenderingEnable",
+                "(Z)V",
+                (node.rendering_enabled,),
+            )
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                instance,
+                "javax/microedition/m3g/Node",
+                "setPickingEnable",
+                "(Z)V",
+                (node.picking_enabled,),
+            )
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                instance,
+                "javax/microedition/m3g/Node",
+                "setAlphaFactor",
+                "(F)V",
+                (node.alpha_factor as f32 / 255.0,),
+            )
+            .await?;
+        jvm.invoke_virtual(instance, "javax/microedition/m3g/Node", "setScope", "(I)V", (node.scope,))
+            .await
+    }
+
+    async fn byte_array(jvm: &Jvm, data: &[u8]) -> JvmResult<ClassInstanceRef<Array<i8>>> {
+        let mut array = jvm.instantiate_array("B", data.len()).await?;
+        jvm.array_raw_buffer_mut(&mut array).await?.write(0, data)?;
+
+        Ok(array.into())
+    }
+
+    async fn float_array(jvm: &Jvm, data: &[f32]) -> JvmResult<ClassInstanceRef<Array<f32>>> {
+        let mut array = jvm.instantiate_array("F", data.len()).await?;
+        jvm.store_array(&mut array, 0, data.to_vec()).await?;
+
+        Ok(array.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec, vec::Vec};
+
+    use jvm::ClassInstanceRef;
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{
+        get_protos,
+        loader::{
+            FILE_IDENTIFIER,
+            tests::{header, section},
+        },
+    };
+
+    #[test]
+    fn test_load_image() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let mut object = Vec::new();
+            object.extend_from_slice(&7i32.to_le_bytes());
+            object.extend_from_slice(&0u32.to_le_bytes());
+            object.extend_from_slice(&0u32.to_le_bytes());
+            object.extend_from_slice(&[99, 0]);
+            object.extend_from_slice(&1u32.to_le_bytes());
+            object.extend_from_slice(&1u32.to_le_bytes());
+            object.extend_from_slice(&0u32.to_le_bytes());
+            object.extend_from_slice(&3u32.to_le_bytes());
+            object.extend_from_slice(&[0x12, 0x34, 0x56]);
+
+            let mut file = FILE_IDENTIFIER.to_vec();
+            file.extend(section(0, &[header(), (10, object)]));
+            let file = file.into_iter().map(|x| x as i8).collect::<Vec<_>>();
+
+            let mut data = jvm.instantiate_array("B", file.len()).await?;
+            jvm.store_array(&mut data, 0, file).await?;
+
+            let objects: ClassInstanceRef<jvm::Array<ClassInstanceRef<()>>> = jvm
+                .invoke_static(
+                    "javax/microedition/m3g/Loader",
+                    "load",
+                    "([BI)[Ljavax/microedition/m3g/Object3D;",
+                    (data, 0),
+                )
+                .await?;
+            assert_eq!(jvm.array_length(&objects).await?, 1);
+
+            let objects: Vec<ClassInstanceRef<()>> = jvm.load_array(&objects, 0, 1).await?;
+            assert!(jvm.is_instance(&**objects[0], "javax/microedition/m3g/Image2D"));
+
+            let user_id: i32 = jvm
+                .invoke_virtual(&objects[0], "javax/microedition/m3g/Object3D", "getUserID", "()I", ())
+                .await?;
+            assert_eq!(user_id, 7);
+
+            let width: i32 = jvm
+                .invoke_virtual(&objects[0], "javax/microedition/m3g/Image2D", "getWidth", "()I", ())
+                .await?;
+            assert_eq!(width, 1);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_load_invalid() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let mut data = jvm.instantiate_array("B", 4).await?;
+            jvm.store_array(&mut data, 0, vec![1i8, 2, 3, 4]).await?;
+
+            let result: jvm::Result<ClassInstanceRef<()>> = jvm
+                .invoke_static(
+                    "javax/microedition/m3g/Loader",
+                    "load",
+                    "([BI)[Ljavax/microedition/m3g/Object3D;",
+                    (data, 0),
+                )
+                .await;
+            assert!(result.is_err());
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::renderer::{Material as RendererMaterial, from_argb};
+
+const AMBIENT: i32 = 1024;
+const DIFFUSE: i32 = 2048;
+const EMISSIVE: i32 = 4096;
+const SPECULAR: i32 = 8192;
+
+// class javax.microedition.m3g.Material
+pub struct Material;
+
+impl Material {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Material",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setColor", "(II)V", Self::set_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getColor", "(I)I", Self::get_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setShininess", "(F)V", Self::set_shininess, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getShininess", "()F", Self::get_shininess, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setVertexColorTrackingEnable",
+                    "(Z)V",
+                    Self::set_vertex_color_tracking_enable,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "isVertexColorTrackingEnabled",
+                    "()Z",
+                    Self::is_vertex_color_tracking_enabled,
+                    MethodAccessFlags::PUBLIC,
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("ambientColor", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("diffuseColor", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("emissiveColor", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("specularColor", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("shininess", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("vertexColorTracking", "Z", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Material::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "ambientColor", "I", 0x00333333).await?;
+        jvm.put_field(&mut this, "diffuseColor", "I", 0xffcccccc_u32 as i32).await?;
+
+        Ok(())
+    }
+
+    async fn set_color(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, target: i32, argb: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Material::setColor({this:?}, {target}, {argb:#x})");
+
+        if target == 0 || target & !(AMBIENT | DIFFUSE | EMISSIVE | SPECULAR) != 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid color target").await);
+        }
+
+        // only the diffuse color carries alpha
+        if target & AMBIENT != 0 {
+            jvm.put_field(&mut this, "ambientColor", "I", argb & 0xffffff).await?;
+        }
+        if target & DIFFUSE != 0 {
+            jvm.put_field(&mut this, "diffuseColor", "I", argb).await?;
+        }
+        if target & EMISSIVE != 0 {
+            jvm.put_field(&mut this, "emissiveColor", "I", argb & 0xffffff).await?;
+        }
+        if target & SPECULAR != 0 {
+            jvm.put_field(&mut this, "specularColor", "I", argb & 0xffffff).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_color(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, target: i32) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Material::getColor({this:?}, {target})");
+
+        let name = match target {
+            AMBIENT => "ambientColor",
+            DIFFUSE => "diffuseColor",
+            EMISSIVE => "emissiveColor",
+            SPECULAR => "specularColor",
+            _ => return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid color target").await),
+        };
+
+        jvm.get_field(&this, name, "I").await
+    }
+
+    async fn set_shininess(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, shininess: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Material::setShininess({this:?}, {shininess})");
+
+        if !(0.0..=128.0).contains(&shininess) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "shininess out of range").await);
+        }
+
+        jvm.put_field(&mut this, "shininess", "F", shininess).await
+    }
+
+    async fn get_shininess(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Material::getShininess({this:?})");
+
+        jvm.get_field(&this, "shininess", "F").await
+    }
+
+    async fn set_vertex_color_tracking_enable(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        enable: bool,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Material::setVertexColorTrackingEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "vertexColorTracking", "Z", enable).await
+    }
+
+    async fn is_vertex_color_tracking_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Material::isVertexColorTrackingEnabled({this:?})");
+
+        jvm.get_field(&this, "vertexColorTracking", "Z").await
+    }
+
+    pub(crate) async fn material(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<RendererMaterial> {
+        let ambient: i32 = jvm.get_field(this, "ambientColor", "I").await?;
+        let diffuse: i32 = jvm.get_field(this, "diffuseColor", "I").await?;
+        let emissive: i32 = jvm.get_field(this, "emissiveColor", "I").await?;
+        let specular: i32 = jvm.get_field(this, "specularColor", "I").await?;
+
+        Ok(RendererMaterial {
+            ambient: from_argb(0xff000000 | ambient as u32),
+            diffuse: from_argb(diffuse as u32),
+            emissive: from_argb(0xff000000 | emissive as u32),
+            specular: from_argb(0xff000000 | specular as u32),
+            shininess: jvm.get_field(this, "shininess", "F").await?,
+            vertex_color_tracking: jvm.get_field(this, "vertexColorTracking", "Z").await?,
+        })
+    }
+}
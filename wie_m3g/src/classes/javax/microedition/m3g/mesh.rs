@@ -0,0 +1,235 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::{Appearance, IndexBuffer, VertexBuffer},
+    renderer::{Appearance as RendererAppearance, VertexData},
+};
+
+// class javax.microedition.m3g.Mesh
+pub struct Mesh;
+
+impl Mesh {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Mesh",
+            parent_class: Some("javax/microedition/m3g/Node"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljavax/microedition/m3g/VertexBuffer;Ljavax/microedition/m3g/IndexBuffer;Ljavax/microedition/m3g/Appearance;)V",
+                    Self::init,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljavax/microedition/m3g/VertexBuffer;[Ljavax/microedition/m3g/IndexBuffer;[Ljavax/microedition/m3g/Appearance;)V",
+                    Self::init_with_submeshes,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getVertexBuffer",
+                    "()Ljavax/microedition/m3g/VertexBuffer;",
+                    Self::get_vertex_buffer,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getIndexBuffer",
+                    "(I)Ljavax/microedition/m3g/IndexBuffer;",
+                    Self::get_index_buffer,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getAppearance",
+                    "(I)Ljavax/microedition/m3g/Appearance;",
+                    Self::get_appearance,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setAppearance",
+                    "(ILjavax/microedition/m3g/Appearance;)V",
+                    Self::set_appearance,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getSubmeshCount", "()I", Self::get_submesh_count, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("vertexBuffer", "Ljavax/microedition/m3g/VertexBuffer;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("indexBuffers", "[Ljavax/microedition/m3g/IndexBuffer;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("appearances", "[Ljavax/microedition/m3g/Appearance;", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        vertex_buffer: ClassInstanceRef<VertexBuffer>,
+        index_buffer: ClassInstanceRef<IndexBuffer>,
+        appearance: ClassInstanceRef<Appearance>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Mesh::<init>({this:?}, {vertex_buffer:?}, {index_buffer:?}, {appearance:?})");
+
+        if index_buffer.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "indexBuffer is null").await);
+        }
+
+        Self::initialize(jvm, this, vertex_buffer, vec![index_buffer], vec![appearance]).await
+    }
+
+    async fn init_with_submeshes(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        vertex_buffer: ClassInstanceRef<VertexBuffer>,
+        index_buffers: ClassInstanceRef<Array<ClassInstanceRef<IndexBuffer>>>,
+        appearances: ClassInstanceRef<Array<ClassInstanceRef<Appearance>>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Mesh::<init>({this:?}, {vertex_buffer:?}, {index_buffers:?}, {appearances:?})");
+
+        if index_buffers.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "indexBuffers is null").await);
+        }
+
+        let count = jvm.array_length(&index_buffers).await?;
+        let index_buffers: Vec<ClassInstanceRef<IndexBuffer>> = jvm.load_array(&index_buffers, 0, count).await?;
+        if count == 0 || index_buffers.iter().any(|x| x.is_null()) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid index buffers").await);
+        }
+
+        let appearances: Vec<ClassInstanceRef<Appearance>> = if appearances.is_null() {
+            (0..count).map(|_| None.into()).collect()
+        } else {
+            if jvm.array_length(&appearances).await? < count {
+                return Err(jvm.exception("java/lang/IllegalArgumentException", "appearances is too short").await);
+            }
+            jvm.load_array(&appearances, 0, count).await?
+        };
+
+        Self::initialize(jvm, this, vertex_buffer, index_buffers, appearances).await
+    }
+
+    async fn get_vertex_buffer(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<VertexBuffer>> {
+        tracing::debug!("javax.microedition.m3g.Mesh::getVertexBuffer({this:?})");
+
+        jvm.get_field(&this, "vertexBuffer", "Ljavax/microedition/m3g/VertexBuffer;").await
+    }
+
+    async fn get_index_buffer(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        index: i32,
+    ) -> JvmResult<ClassInstanceRef<IndexBuffer>> {
+        tracing::debug!("javax.microedition.m3g.Mesh::getIndexBuffer({this:?}, {index})");
+
+        let index_buffers = jvm.get_field(&this, "indexBuffers", "[Ljavax/microedition/m3g/IndexBuffer;").await?;
+        let index = Self::check_index(jvm, &index_buffers, index).await?;
+
+        Ok(jvm.load_array(&index_buffers, index, 1).await?.remove(0))
+    }
+
+    async fn get_appearance(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        index: i32,
+    ) -> JvmResult<ClassInstanceRef<Appearance>> {
+        tracing::debug!("javax.microedition.m3g.Mesh::getAppearance({this:?}, {index})");
+
+        let appearances = jvm.get_field(&this, "appearances", "[Ljavax/microedition/m3g/Appearance;").await?;
+        let index = Self::check_index(jvm, &appearances, index).await?;
+
+        Ok(jvm.load_array(&appearances, index, 1).await?.remove(0))
+    }
+
+    async fn set_appearance(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        index: i32,
+        appearance: ClassInstanceRef<Appearance>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Mesh::setAppearance({this:?}, {index}, {appearance:?})");
+
+        let mut appearances = jvm.get_field(&this, "appearances", "[Ljavax/microedition/m3g/Appearance;").await?;
+        let index = Self::check_index(jvm, &appearances, index).await?;
+
+        jvm.store_array(&mut appearances, index, vec![appearance]).await
+    }
+
+    async fn get_submesh_count(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Mesh::getSubmeshCount({this:?})");
+
+        let index_buffers = jvm.get_field(&this, "indexBuffers", "[Ljavax/microedition/m3g/IndexBuffer;").await?;
+
+        Ok(jvm.array_length(&index_buffers).await? as _)
+    }
+
+    async fn initialize(
+        jvm: &Jvm,
+        mut this: ClassInstanceRef<Self>,
+        vertex_buffer: ClassInstanceRef<VertexBuffer>,
+        index_buffers: Vec<ClassInstanceRef<IndexBuffer>>,
+        appearances: Vec<ClassInstanceRef<Appearance>>,
+    ) -> JvmResult<()> {
+        if vertex_buffer.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "vertexBuffer is null").await);
+        }
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Node", "<init>", "()V", ()).await?;
+
+        let mut index_buffers_array = jvm.instantiate_array("Ljavax/microedition/m3g/IndexBuffer;", index_buffers.len()).await?;
+        jvm.store_array(&mut index_buffers_array, 0, index_buffers).await?;
+        let mut appearances_array = jvm.instantiate_array("Ljavax/microedition/m3g/Appearance;", appearances.len()).await?;
+        jvm.store_array(&mut appearances_array, 0, appearances).await?;
+
+        jvm.put_field(&mut this, "vertexBuffer", "Ljavax/microedition/m3g/VertexBuffer;", vertex_buffer)
+            .await?;
+        jvm.put_field(&mut this, "indexBuffers", "[Ljavax/microedition/m3g/IndexBuffer;", index_buffers_array)
+            .await?;
+        jvm.put_field(&mut this, "appearances", "[Ljavax/microedition/m3g/Appearance;", appearances_array)
+            .await
+    }
+
+    async fn check_index<T>(jvm: &Jvm, array: &ClassInstanceRef<Array<T>>, index: i32) -> JvmResult<usize> {
+        if index < 0 || index as usize >= jvm.array_length(array).await? {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid submesh index").await);
+        }
+
+        Ok(index as _)
+    }
+
+    /// Returns the vertex data along with the triangles and appearance of each submesh; submeshes without an appearance are skipped.
+    pub(crate) async fn submeshes<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<(VertexData, Vec<(Vec<[u32; 3]>, RendererAppearance)>)> {
+        let vertex_buffer: ClassInstanceRef<VertexBuffer> = jvm.get_field(this, "vertexBuffer", "Ljavax/microedition/m3g/VertexBuffer;").await?;
+        let vertex_data = VertexBuffer::vertex_data(jvm, &vertex_buffer).await?;
+
+        let index_buffers = jvm.get_field(this, "indexBuffers", "[Ljavax/microedition/m3g/IndexBuffer;").await?;
+        let appearances = jvm.get_field(this, "appearances", "[Ljavax/microedition/m3g/Appearance;").await?;
+
+        let count = jvm.array_length(&index_buffers).await?;
+        let index_buffers: Vec<ClassInstanceRef<IndexBuffer>> = jvm.load_array(&index_buffers, 0, count).await?;
+        let appearances: Vec<ClassInstanceRef<Appearance>> = jvm.load_array(&appearances, 0, count).await?;
+
+        let mut submeshes = Vec::with_capacity(count);
+        for (index_buffer, appearance) in index_buffers.into_iter().zip(appearances) {
+            if appearance.is_null() {
+                continue;
+            }
+
+            let triangles = IndexBuffer::triangles(jvm, &index_buffer).await?;
+            submeshes.push((triangles, Appearance::appearance(jvm, &appearance).await?));
+        }
+
+        Ok((vertex_data, submeshes))
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::{Transform, Transformable},
+    renderer::Matrix4,
+};
+
+// class javax.microedition.m3g.Node
+pub struct Node;
+
+impl Node {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Node",
+            parent_class: Some("javax/microedition/m3g/Transformable"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::empty()),
+                JavaMethodProto::new("setRenderingEnable", "(Z)V", Self::set_rendering_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isRenderingEnabled", "()Z", Self::is_rendering_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setPickingEnable", "(Z)V", Self::set_picking_enable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isPickingEnabled", "()Z", Self::is_picking_enabled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setAlphaFactor", "(F)V", Self::set_alpha_factor, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getAlphaFactor", "()F", Self::get_alpha_factor, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setScope", "(I)V", Self::set_scope, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getScope", "()I", Self::get_scope, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getParent",
+                    "()Ljavax/microedition/m3g/Node;",
+                    Self::get_parent,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getTransformTo",
+                    "(Ljavax/microedition/m3g/Node;Ljavax/microedition/m3g/Transform;)Z",
+                    Self::get_transform_to,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("align", "(Ljavax/microedition/m3g/Node;)V", Self::align, MethodAccessFlags::PUBLIC),
+                // wie private methods...
+                JavaMethodProto::new(
+                    "setParent",
+                    "(Ljavax/microedition/m3g/Node;)V",
+                    Self::set_parent,
+                    MethodAccessFlags::empty(),
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("parent", "Ljavax/microedition/m3g/Node;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("renderingEnabled", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("pickingEnabled", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("alphaFactor", "F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("scope", "I", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Node::<init>({this:?})");
+
+        let _: () = jvm
+            .invoke_special(&this, "javax/microedition/m3g/Transformable", "<init>", "()V", ())
+            .await?;
+
+        jvm.put_field(&mut this, "renderingEnabled", "Z", true).await?;
+        jvm.put_field(&mut this, "pickingEnabled", "Z", true).await?;
+        jvm.put_field(&mut this, "alphaFactor", "F", 1.0f32).await?;
+        jvm.put_field(&mut this, "scope", "I", -1).await?;
+
+        Ok(())
+    }
+
+    async fn set_rendering_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Node::setRenderingEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "renderingEnabled", "Z", enable).await
+    }
+
+    async fn is_rendering_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Node::isRenderingEnabled({this:?})");
+
+        jvm.get_field(&this, "renderingEnabled", "Z").await
+    }
+
+    async fn set_picking_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Node::setPickingEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "pickingEnabled", "Z", enable).await
+    }
+
+    async fn is_picking_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Node::isPickingEnabled({this:?})");
+
+        jvm.get_field(&this, "pickingEnabled", "Z").await
+    }
+
+    async fn set_alpha_factor(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, alpha_factor: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Node::setAlphaFactor({this:?}, {alpha_factor})");
+
+        if !(0.0..=1.0).contains(&alpha_factor) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "alphaFactor out of range").await);
+        }
+
+        jvm.put_field(&mut this, "alphaFactor", "F", alpha_factor).await
+    }
+
+    async fn get_alpha_factor(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<f32> {
+        tracing::debug!("javax.microedition.m3g.Node::getAlphaFactor({this:?})");
+
+        jvm.get_field(&this, "alphaFactor", "F").await
+    }
+
+    async fn set_scope(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, scope: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Node::setScope({this:?}, {scope})");
+
+        jvm.put_field(&mut this, "scope", "I", scope).await
+    }
+
+    async fn get_scope(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Node::getScope({this:?})");
+
+        jvm.get_field(&this, "scope", "I").await
+    }
+
+    async fn get_parent(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Node>> {
+        tracing::debug!("javax.microedition.m3g.Node::getParent({this:?})");
+
+        jvm.get_field(&this, "parent", "Ljavax/microedition/m3g/Node;").await
+    }
+
+    async fn set_parent(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, parent: ClassInstanceRef<Node>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Node::setParent({this:?}, {parent:?})");
+
+        jvm.put_field(&mut this, "parent", "Ljavax/microedition/m3g/Node;", parent).await
+    }
+
+    async fn get_transform_to(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        target: ClassInstanceRef<Node>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Node::getTransformTo({this:?}, {target:?}, {transform:?})");
+
+        if target.is_null() || transform.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "target or transform is null").await);
+        }
+
+        let (this_to_world, this_root) = Self::world_transform(jvm, &this).await?;
+        let (target_to_world, target_root) = Self::world_transform(jvm, &target).await?;
+        if !this_root.equals(&**target_root)? {
+            return Ok(false);
+        }
+
+        let Some(world_to_target) = target_to_world.invert() else {
+            return Err(jvm.exception("java/lang/ArithmeticException", "target transform is not invertible").await);
+        };
+
+        Transform::set_matrix(jvm, &transform, &world_to_target.multiply(&this_to_world)).await?;
+
+        Ok(true)
+    }
+
+    async fn align(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, reference: ClassInstanceRef<Node>) -> JvmResult<()> {
+        tracing::warn!("stub javax.microedition.m3g.Node::align({this:?}, {reference:?})");
+
+        Ok(())
+    }
+
+    /// Returns the node-to-root transform along with the root of the scene graph containing the node.
+    pub(crate) async fn world_transform(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<(Matrix4, ClassInstanceRef<Node>)> {
+        let mut transform = Matrix4::IDENTITY;
+        let mut current = this.clone();
+
+        loop {
+            transform = Transformable::composite_transform(jvm, &current).await?.multiply(&transform);
+
+            let parent: ClassInstanceRef<Node> = jvm.get_field(&current, "parent", "Ljavax/microedition/m3g/Node;").await?;
+            if parent.is_null() {
+                return Ok((transform, current));
+            }
+            current = parent;
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::lang::Object;
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+// class javax.microedition.m3g.Object3D
+pub struct Object3D;
+
+impl Object3D {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Object3D",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::empty()),
+                JavaMethodProto::new("getUserID", "()I", Self::get_user_id, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setUserID", "(I)V", Self::set_user_id, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getUserObject", "()Ljava/lang/Object;", Self::get_user_object, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setUserObject", "(Ljava/lang/Object;)V", Self::set_user_object, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("animate", "(I)I", Self::animate, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("find", "(I)Ljavax/microedition/m3g/Object3D;", Self::find, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getReferences",
+                    "([Ljavax/microedition/m3g/Object3D;)I",
+                    Self::get_references,
+                    MethodAccessFlags::PUBLIC,
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("userID", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("userObject", "Ljava/lang/Object;", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Object3D::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        Ok(())
+    }
+
+    async fn get_user_id(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Object3D::getUserID({this:?})");
+
+        jvm.get_field(&this, "userID", "I").await
+    }
+
+    async fn set_user_id(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, user_id: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Object3D::setUserID({this:?}, {user_id})");
+
+        jvm.put_field(&mut this, "userID", "I", user_id).await
+    }
+
+    async fn get_user_object(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Object>> {
+        tracing::debug!("javax.microedition.m3g.Object3D::getUserObject({this:?})");
+
+        jvm.get_field(&this, "userObject", "Ljava/lang/Object;").await
+    }
+
+    async fn set_user_object(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        user_object: ClassInstanceRef<Object>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Object3D::setUserObject({this:?}, {user_object:?})");
+
+        jvm.put_field(&mut this, "userObject", "Ljava/lang/Object;", user_object).await
+    }
+
+    async fn animate(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, time: i32) -> JvmResult<i32> {
+        tracing::warn!("stub javax.microedition.m3g.Object3D::animate({this:?}, {time})");
+
+        // no animation tracks are evaluated yet; 0 tells the caller nothing is pending
+        Ok(0)
+    }
+
+    async fn find(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, user_id: i32) -> JvmResult<ClassInstanceRef<Self>> {
+        tracing::debug!("javax.microedition.m3g.Object3D::find({this:?}, {user_id})");
+
+        let own_id: i32 = jvm.get_field(&this, "userID", "I").await?;
+        if own_id == user_id {
+            return Ok(this);
+        }
+
+        if jvm.is_instance(&**this, "javax/microedition/m3g/Group") {
+            let child_count: i32 = jvm
+                .invoke_virtual(&this, "javax/microedition/m3g/Group", "getChildCount", "()I", ())
+                .await?;
+            for index in 0..child_count {
+                let child: ClassInstanceRef<Self> = jvm
+                    .invoke_virtual(
+                        &this,
+                        "javax/microedition/m3g/Group",
+                        "getChild",
+                        "(I)Ljavax/microedition/m3g/Node;",
+                        (index,),
+                    )
+                    .await?;
+                let found: ClassInstanceRef<Self> = jvm
+                    .invoke_virtual(
+                        &child,
+                        "javax/microedition/m3g/Object3D",
+                        "find",
+                        "(I)Ljavax/microedition/m3g/Object3D;",
+                        (user_id,),
+                    )
+                    .await?;
+                if !found.is_null() {
+                    return Ok(found);
+                }
+            }
+        }
+
+        Ok(None.into())
+    }
+
+    async fn get_references(
+        _jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        references: ClassInstanceRef<Array<Object3D>>,
+    ) -> JvmResult<i32> {
+        tracing::warn!("stub javax.microedition.m3g.Object3D::getReferences({this:?}, {references:?})");
+
+        Ok(0)
+    }
+}
@@ -0,0 +1,210 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::renderer::{Culling, PolygonMode as RendererPolygonMode, Shading, Winding};
+
+const CULL_BACK: i32 = 160;
+const CULL_FRONT: i32 = 161;
+const CULL_NONE: i32 = 162;
+const SHADE_FLAT: i32 = 164;
+const SHADE_SMOOTH: i32 = 165;
+const WINDING_CCW: i32 = 168;
+const WINDING_CW: i32 = 169;
+
+// class javax.microedition.m3g.PolygonMode
+pub struct PolygonMode;
+
+impl PolygonMode {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/PolygonMode",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setCulling", "(I)V", Self::set_culling, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCulling", "()I", Self::get_culling, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setWinding", "(I)V", Self::set_winding, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getWinding", "()I", Self::get_winding, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setShading", "(I)V", Self::set_shading, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getShading", "()I", Self::get_shading, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setTwoSidedLightingEnable",
+                    "(Z)V",
+                    Self::set_two_sided_lighting_enable,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "isTwoSidedLightingEnabled",
+                    "()Z",
+                    Self::is_two_sided_lighting_enabled,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setLocalCameraLightingEnable",
+                    "(Z)V",
+                    Self::set_local_camera_lighting_enable,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "isLocalCameraLightingEnabled",
+                    "()Z",
+                    Self::is_local_camera_lighting_enabled,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setPerspectiveCorrectionEnable",
+                    "(Z)V",
+                    Self::set_perspective_correction_enable,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "isPerspectiveCorrectionEnabled",
+                    "()Z",
+                    Self::is_perspective_correction_enabled,
+                    MethodAccessFlags::PUBLIC,
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("culling", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("winding", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("shading", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("twoSidedLighting", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("localCameraLighting", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("perspectiveCorrection", "Z", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "culling", "I", CULL_BACK).await?;
+        jvm.put_field(&mut this, "winding", "I", WINDING_CCW).await?;
+        jvm.put_field(&mut this, "shading", "I", SHADE_SMOOTH).await?;
+
+        Ok(())
+    }
+
+    async fn set_culling(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::setCulling({this:?}, {mode})");
+
+        if !(CULL_BACK..=CULL_NONE).contains(&mode) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid culling mode").await);
+        }
+
+        jvm.put_field(&mut this, "culling", "I", mode).await
+    }
+
+    async fn get_culling(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::getCulling({this:?})");
+
+        jvm.get_field(&this, "culling", "I").await
+    }
+
+    async fn set_winding(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::setWinding({this:?}, {mode})");
+
+        if mode != WINDING_CCW && mode != WINDING_CW {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid winding mode").await);
+        }
+
+        jvm.put_field(&mut this, "winding", "I", mode).await
+    }
+
+    async fn get_winding(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::getWinding({this:?})");
+
+        jvm.get_field(&this, "winding", "I").await
+    }
+
+    async fn set_shading(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::setShading({this:?}, {mode})");
+
+        if mode != SHADE_FLAT && mode != SHADE_SMOOTH {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid shading mode").await);
+        }
+
+        jvm.put_field(&mut this, "shading", "I", mode).await
+    }
+
+    async fn get_shading(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::getShading({this:?})");
+
+        jvm.get_field(&this, "shading", "I").await
+    }
+
+    async fn set_two_sided_lighting_enable(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, enable: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::setTwoSidedLightingEnable({this:?}, {enable})");
+
+        jvm.put_field(&mut this, "twoSidedLighting", "Z", enable).await
+    }
+
+    async fn is_two_sided_lighting_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::isTwoSidedLightingEnabled({this:?})");
+
+        jvm.get_field(&this, "twoSidedLighting", "Z").await
+    }
+
+    async fn set_local_camera_lighting_enable(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        enable: bool,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::setLocalCameraLightingEnable({this:?}, {enable})");
+
+        // hint only, lighting is always computed with a local viewer
+        jvm.put_field(&mut this, "localCameraLighting", "Z", enable).await
+    }
+
+    async fn is_local_camera_lighting_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::isLocalCameraLightingEnabled({this:?})");
+
+        jvm.get_field(&this, "localCameraLighting", "Z").await
+    }
+
+    async fn set_perspective_correction_enable(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        enable: bool,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::setPerspectiveCorrectionEnable({this:?}, {enable})");
+
+        // hint only, interpolation is always perspective correct
+        jvm.put_field(&mut this, "perspectiveCorrection", "Z", enable).await
+    }
+
+    async fn is_perspective_correction_enabled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.PolygonMode::isPerspectiveCorrectionEnabled({this:?})");
+
+        jvm.get_field(&this, "perspectiveCorrection", "Z").await
+    }
+
+    pub(crate) async fn polygon_mode(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<RendererPolygonMode> {
+        let culling: i32 = jvm.get_field(this, "culling", "I").await?;
+        let winding: i32 = jvm.get_field(this, "winding", "I").await?;
+        let shading: i32 = jvm.get_field(this, "shading", "I").await?;
+        let two_sided_lighting: bool = jvm.get_field(this, "twoSidedLighting", "Z").await?;
+
+        Ok(RendererPolygonMode {
+            culling: match culling {
+                CULL_FRONT => Culling::Front,
+                CULL_NONE => Culling::None,
+                _ => Culling::Back,
+            },
+            shading: if shading == SHADE_FLAT { Shading::Flat } else { Shading::Smooth },
+            winding: if winding == WINDING_CW { Winding::Cw } else { Winding::Ccw },
+            two_sided_lighting,
+        })
+    }
+}
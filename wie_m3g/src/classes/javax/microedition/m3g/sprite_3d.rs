@@ -0,0 +1,208 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::{Appearance, Image2D},
+    renderer::Sprite,
+};
+
+// class javax.microedition.m3g.Sprite3D
+pub struct Sprite3D;
+
+impl Sprite3D {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Sprite3D",
+            parent_class: Some("javax/microedition/m3g/Node"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new(
+                    "<init>",
+                    "(ZLjavax/microedition/m3g/Image2D;Ljavax/microedition/m3g/Appearance;)V",
+                    Self::init,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("isScaled", "()Z", Self::is_scaled, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setAppearance",
+                    "(Ljavax/microedition/m3g/Appearance;)V",
+                    Self::set_appearance,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getAppearance",
+                    "()Ljavax/microedition/m3g/Appearance;",
+                    Self::get_appearance,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setImage",
+                    "(Ljavax/microedition/m3g/Image2D;)V",
+                    Self::set_image,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getImage",
+                    "()Ljavax/microedition/m3g/Image2D;",
+                    Self::get_image,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("setCrop", "(IIII)V", Self::set_crop, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropX", "()I", Self::get_crop_x, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropY", "()I", Self::get_crop_y, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropWidth", "()I", Self::get_crop_width, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCropHeight", "()I", Self::get_crop_height, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("scaled", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("image", "Ljavax/microedition/m3g/Image2D;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("appearance", "Ljavax/microedition/m3g/Appearance;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropX", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropY", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropWidth", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("cropHeight", "I", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        scaled: bool,
+        image: ClassInstanceRef<Image2D>,
+        appearance: ClassInstanceRef<Appearance>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::<init>({this:?}, {scaled}, {image:?}, {appearance:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Node", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "scaled", "Z", scaled).await?;
+        jvm.put_field(&mut this, "appearance", "Ljavax/microedition/m3g/Appearance;", appearance)
+            .await?;
+
+        Self::set_image(jvm, context, this, image).await
+    }
+
+    async fn is_scaled(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::isScaled({this:?})");
+
+        jvm.get_field(&this, "scaled", "Z").await
+    }
+
+    async fn set_appearance(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        appearance: ClassInstanceRef<Appearance>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::setAppearance({this:?}, {appearance:?})");
+
+        jvm.put_field(&mut this, "appearance", "Ljavax/microedition/m3g/Appearance;", appearance)
+            .await
+    }
+
+    async fn get_appearance(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Appearance>> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::getAppearance({this:?})");
+
+        jvm.get_field(&this, "appearance", "Ljavax/microedition/m3g/Appearance;").await
+    }
+
+    async fn set_image(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, image: ClassInstanceRef<Image2D>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::setImage({this:?}, {image:?})");
+
+        if image.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "image is null").await);
+        }
+
+        let width: i32 = jvm.get_field(&image, "width", "I").await?;
+        let height: i32 = jvm.get_field(&image, "height", "I").await?;
+
+        jvm.put_field(&mut this, "image", "Ljavax/microedition/m3g/Image2D;", image).await?;
+        jvm.put_field(&mut this, "cropX", "I", 0).await?;
+        jvm.put_field(&mut this, "cropY", "I", 0).await?;
+        jvm.put_field(&mut this, "cropWidth", "I", width).await?;
+        jvm.put_field(&mut this, "cropHeight", "I", height).await
+    }
+
+    async fn get_image(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Image2D>> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::getImage({this:?})");
+
+        jvm.get_field(&this, "image", "Ljavax/microedition/m3g/Image2D;").await
+    }
+
+    async fn set_crop(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::setCrop({this:?}, {x}, {y}, {width}, {height})");
+
+        // negative sizes flip the sprite
+        jvm.put_field(&mut this, "cropX", "I", x).await?;
+        jvm.put_field(&mut this, "cropY", "I", y).await?;
+        jvm.put_field(&mut this, "cropWidth", "I", width).await?;
+        jvm.put_field(&mut this, "cropHeight", "I", height).await
+    }
+
+    async fn get_crop_x(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::getCropX({this:?})");
+
+        jvm.get_field(&this, "cropX", "I").await
+    }
+
+    async fn get_crop_y(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::getCropY({this:?})");
+
+        jvm.get_field(&this, "cropY", "I").await
+    }
+
+    async fn get_crop_width(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::getCropWidth({this:?})");
+
+        jvm.get_field(&this, "cropWidth", "I").await
+    }
+
+    async fn get_crop_height(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Sprite3D::getCropHeight({this:?})");
+
+        jvm.get_field(&this, "cropHeight", "I").await
+    }
+
+    /// Returns the sprite with its layer, or `None` if it has no appearance and therefore isn't rendered.
+    pub(crate) async fn sprite<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<Option<(i32, Sprite)>> {
+        let appearance: ClassInstanceRef<Appearance> = jvm.get_field(this, "appearance", "Ljavax/microedition/m3g/Appearance;").await?;
+        if appearance.is_null() {
+            return Ok(None);
+        }
+        let appearance = Appearance::appearance(jvm, &appearance).await?;
+
+        let image: ClassInstanceRef<Image2D> = jvm.get_field(this, "image", "Ljavax/microedition/m3g/Image2D;").await?;
+
+        Ok(Some((
+            appearance.layer,
+            Sprite {
+                image: Image2D::texture_image(jvm, &image).await?,
+                scaled: jvm.get_field(this, "scaled", "Z").await?,
+                crop: (
+                    jvm.get_field(this, "cropX", "I").await?,
+                    jvm.get_field(this, "cropY", "I").await?,
+                    jvm.get_field(this, "cropWidth", "I").await?,
+                    jvm.get_field(this, "cropHeight", "I").await?,
+                ),
+                compositing_mode: appearance.compositing_mode,
+                fog: appearance.fog,
+            },
+        )))
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::{Image2D, Transformable},
+    renderer::{Texture, TextureFilter, TextureFunction, TextureWrap, from_argb},
+};
+
+const FILTER_BASE_LEVEL: i32 = 208;
+const FILTER_LINEAR: i32 = 209;
+const FILTER_NEAREST: i32 = 210;
+const FUNC_ADD: i32 = 224;
+const FUNC_BLEND: i32 = 225;
+const FUNC_DECAL: i32 = 226;
+const FUNC_MODULATE: i32 = 227;
+const FUNC_REPLACE: i32 = 228;
+const WRAP_CLAMP: i32 = 240;
+const WRAP_REPEAT: i32 = 241;
+
+// class javax.microedition.m3g.Texture2D
+pub struct Texture2D;
+
+impl Texture2D {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Texture2D",
+            parent_class: Some("javax/microedition/m3g/Transformable"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljavax/microedition/m3g/Image2D;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setImage",
+                    "(Ljavax/microedition/m3g/Image2D;)V",
+                    Self::set_image,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getImage",
+                    "()Ljavax/microedition/m3g/Image2D;",
+                    Self::get_image,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("setFiltering", "(II)V", Self::set_filtering, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getLevelFilter", "()I", Self::get_level_filter, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getImageFilter", "()I", Self::get_image_filter, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setWrapping", "(II)V", Self::set_wrapping, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getWrappingS", "()I", Self::get_wrapping_s, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getWrappingT", "()I", Self::get_wrapping_t, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setBlending", "(I)V", Self::set_blending, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getBlending", "()I", Self::get_blending, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setBlendColor", "(I)V", Self::set_blend_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getBlendColor", "()I", Self::get_blend_color, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("image", "Ljavax/microedition/m3g/Image2D;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("levelFilter", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("imageFilter", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("wrappingS", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("wrappingT", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("blending", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("blendColor", "I", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, image: ClassInstanceRef<Image2D>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::<init>({this:?}, {image:?})");
+
+        let _: () = jvm
+            .invoke_special(&this, "javax/microedition/m3g/Transformable", "<init>", "()V", ())
+            .await?;
+
+        jvm.put_field(&mut this, "levelFilter", "I", FILTER_BASE_LEVEL).await?;
+        jvm.put_field(&mut this, "imageFilter", "I", FILTER_NEAREST).await?;
+        jvm.put_field(&mut this, "wrappingS", "I", WRAP_REPEAT).await?;
+        jvm.put_field(&mut this, "wrappingT", "I", WRAP_REPEAT).await?;
+        jvm.put_field(&mut this, "blending", "I", FUNC_MODULATE).await?;
+        jvm.put_field(&mut this, "blendColor", "I", 0).await?;
+
+        Self::set_image(jvm, context, this, image).await
+    }
+
+    async fn set_image(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, image: ClassInstanceRef<Image2D>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::setImage({this:?}, {image:?})");
+
+        if image.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "image is null").await);
+        }
+
+        let width: i32 = jvm.get_field(&image, "width", "I").await?;
+        let height: i32 = jvm.get_field(&image, "height", "I").await?;
+        if !(width as u32).is_power_of_two() || !(height as u32).is_power_of_two() {
+            return Err(jvm
+                .exception("java/lang/IllegalArgumentException", "texture dimensions must be powers of two")
+                .await);
+        }
+
+        jvm.put_field(&mut this, "image", "Ljavax/microedition/m3g/Image2D;", image).await
+    }
+
+    async fn get_image(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Image2D>> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::getImage({this:?})");
+
+        jvm.get_field(&this, "image", "Ljavax/microedition/m3g/Image2D;").await
+    }
+
+    async fn set_filtering(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        level_filter: i32,
+        image_filter: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::setFiltering({this:?}, {level_filter}, {image_filter})");
+
+        if !(FILTER_BASE_LEVEL..=FILTER_NEAREST).contains(&level_filter) || !(FILTER_BASE_LEVEL..=FILTER_NEAREST).contains(&image_filter) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid filter").await);
+        }
+
+        jvm.put_field(&mut this, "levelFilter", "I", level_filter).await?;
+        jvm.put_field(&mut this, "imageFilter", "I", image_filter).await
+    }
+
+    async fn get_level_filter(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::getLevelFilter({this:?})");
+
+        jvm.get_field(&this, "levelFilter", "I").await
+    }
+
+    async fn get_image_filter(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::getImageFilter({this:?})");
+
+        jvm.get_field(&this, "imageFilter", "I").await
+    }
+
+    async fn set_wrapping(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, wrap_s: i32, wrap_t: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::setWrapping({this:?}, {wrap_s}, {wrap_t})");
+
+        let valid = WRAP_CLAMP..=WRAP_REPEAT;
+        if !valid.contains(&wrap_s) || !valid.contains(&wrap_t) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid wrapping mode").await);
+        }
+
+        jvm.put_field(&mut this, "wrappingS", "I", wrap_s).await?;
+        jvm.put_field(&mut this, "wrappingT", "I", wrap_t).await
+    }
+
+    async fn get_wrapping_s(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::getWrappingS({this:?})");
+
+        jvm.get_field(&this, "wrappingS", "I").await
+    }
+
+    async fn get_wrapping_t(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::getWrappingT({this:?})");
+
+        jvm.get_field(&this, "wrappingT", "I").await
+    }
+
+    async fn set_blending(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, function: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::setBlending({this:?}, {function})");
+
+        if !(FUNC_ADD..=FUNC_REPLACE).contains(&function) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid blending function").await);
+        }
+
+        jvm.put_field(&mut this, "blending", "I", function).await
+    }
+
+    async fn get_blending(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::getBlending({this:?})");
+
+        jvm.get_field(&this, "blending", "I").await
+    }
+
+    async fn set_blend_color(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, rgb: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::setBlendColor({this:?}, {rgb:#x})");
+
+        jvm.put_field(&mut this, "blendColor", "I", rgb & 0xffffff).await
+    }
+
+    async fn get_blend_color(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.Texture2D::getBlendColor({this:?})");
+
+        jvm.get_field(&this, "blendColor", "I").await
+    }
+
+    pub(crate) async fn texture(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Texture> {
+        let image: ClassInstanceRef<Image2D> = jvm.get_field(this, "image", "Ljavax/microedition/m3g/Image2D;").await?;
+
+        let function: i32 = jvm.get_field(this, "blending", "I").await?;
+        let blend_color: i32 = jvm.get_field(this, "blendColor", "I").await?;
+        let wrap_s: i32 = jvm.get_field(this, "wrappingS", "I").await?;
+        let wrap_t: i32 = jvm.get_field(this, "wrappingT", "I").await?;
+        let image_filter: i32 = jvm.get_field(this, "imageFilter", "I").await?;
+
+        let wrap = |mode| if mode == WRAP_CLAMP { TextureWrap::Clamp } else { TextureWrap::Repeat };
+
+        Ok(Texture {
+            image: Image2D::texture_image(jvm, &image).await?,
+            function: match function {
+                FUNC_ADD => TextureFunction::Add,
+                FUNC_BLEND => TextureFunction::Blend,
+                FUNC_DECAL => TextureFunction::Decal,
+                FUNC_REPLACE => TextureFunction::Replace,
+                _ => TextureFunction::Modulate,
+            },
+            blend_color: from_argb(0xff000000 | blend_color as u32),
+            wrap_s: wrap(wrap_s),
+            wrap_t: wrap(wrap_t),
+            filter: if image_filter == FILTER_LINEAR {
+                TextureFilter::Linear
+            } else {
+                TextureFilter::Nearest
+            },
+            transform: Transformable::composite_transform(jvm, this).await?,
+        })
+    }
+}
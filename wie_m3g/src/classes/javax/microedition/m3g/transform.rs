@@ -0,0 +1,311 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::renderer::{Matrix4, quaternion_from_axis_angle};
+
+// class javax.microedition.m3g.Transform
+pub struct Transform;
+
+impl Transform {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Transform",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    Self::init_with_transform,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("setIdentity", "()V", Self::set_identity, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("set", "(Ljavax/microedition/m3g/Transform;)V", Self::set, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("set", "([F)V", Self::set_from_array, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("get", "([F)V", Self::get, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("invert", "()V", Self::invert, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("transpose", "()V", Self::transpose, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "postMultiply",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    Self::post_multiply,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("postScale", "(FFF)V", Self::post_scale, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("postRotate", "(FFFF)V", Self::post_rotate, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("postRotateQuat", "(FFFF)V", Self::post_rotate_quat, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("postTranslate", "(FFF)V", Self::post_translate, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("transform", "([F)V", Self::transform, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![JavaFieldProto::new("matrix", "[F", FieldAccessFlags::PRIVATE)],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        let matrix = jvm.instantiate_array("F", 16).await?;
+        jvm.put_field(&mut this, "matrix", "[F", matrix).await?;
+
+        Self::set_matrix(jvm, &this, &Matrix4::IDENTITY).await
+    }
+
+    async fn init_with_transform(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::<init>({this:?}, {transform:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Transform", "<init>", "()V", ()).await?;
+
+        let matrix = Self::matrix(jvm, &transform).await?;
+        Self::set_matrix(jvm, &this, &matrix).await
+    }
+
+    async fn set_identity(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::setIdentity({this:?})");
+
+        Self::set_matrix(jvm, &this, &Matrix4::IDENTITY).await
+    }
+
+    async fn set(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, transform: ClassInstanceRef<Transform>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::set({this:?}, {transform:?})");
+
+        if transform.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "transform is null").await);
+        }
+
+        let matrix = Self::matrix(jvm, &transform).await?;
+        Self::set_matrix(jvm, &this, &matrix).await
+    }
+
+    async fn set_from_array(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        values: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::set({this:?}, {values:?})");
+
+        if values.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "matrix is null").await);
+        }
+        if jvm.array_length(&values).await? < 16 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "matrix.length < 16").await);
+        }
+
+        let values: Vec<f32> = jvm.load_array(&values, 0, 16).await?;
+        Self::set_matrix(jvm, &this, &Matrix4(values.try_into().unwrap())).await
+    }
+
+    async fn get(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, mut values: ClassInstanceRef<Array<f32>>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::get({this:?}, {values:?})");
+
+        if values.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "matrix is null").await);
+        }
+        if jvm.array_length(&values).await? < 16 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "matrix.length < 16").await);
+        }
+
+        let matrix = Self::matrix(jvm, &this).await?;
+        jvm.store_array(&mut values, 0, matrix.0.to_vec()).await
+    }
+
+    async fn invert(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::invert({this:?})");
+
+        let Some(inverse) = Self::matrix(jvm, &this).await?.invert() else {
+            return Err(jvm.exception("java/lang/ArithmeticException", "transform is not invertible").await);
+        };
+
+        Self::set_matrix(jvm, &this, &inverse).await
+    }
+
+    async fn transpose(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::transpose({this:?})");
+
+        let matrix = Self::matrix(jvm, &this).await?;
+        Self::set_matrix(jvm, &this, &matrix.transpose()).await
+    }
+
+    async fn post_multiply(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::postMultiply({this:?}, {transform:?})");
+
+        if transform.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "transform is null").await);
+        }
+
+        let other = Self::matrix(jvm, &transform).await?;
+        Self::post_multiply_matrix(jvm, &this, &other).await
+    }
+
+    async fn post_scale(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, sx: f32, sy: f32, sz: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::postScale({this:?}, {sx}, {sy}, {sz})");
+
+        Self::post_multiply_matrix(jvm, &this, &Matrix4::scale(sx, sy, sz)).await
+    }
+
+    async fn post_rotate(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        angle: f32,
+        ax: f32,
+        ay: f32,
+        az: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::postRotate({this:?}, {angle}, {ax}, {ay}, {az})");
+
+        if angle != 0.0 && ax == 0.0 && ay == 0.0 && az == 0.0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "rotation axis is zero").await);
+        }
+
+        let rotation = Matrix4::from_quaternion(quaternion_from_axis_angle(angle, ax, ay, az));
+        Self::post_multiply_matrix(jvm, &this, &rotation).await
+    }
+
+    async fn post_rotate_quat(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        qx: f32,
+        qy: f32,
+        qz: f32,
+        qw: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::postRotateQuat({this:?}, {qx}, {qy}, {qz}, {qw})");
+
+        if qx == 0.0 && qy == 0.0 && qz == 0.0 && qw == 0.0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "quaternion is zero").await);
+        }
+
+        let rotation = Matrix4::from_quaternion(crate::renderer::normalize_quaternion([qx, qy, qz, qw]));
+        Self::post_multiply_matrix(jvm, &this, &rotation).await
+    }
+
+    async fn post_translate(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, tx: f32, ty: f32, tz: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::postTranslate({this:?}, {tx}, {ty}, {tz})");
+
+        Self::post_multiply_matrix(jvm, &this, &Matrix4::translation(tx, ty, tz)).await
+    }
+
+    async fn transform(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        mut vectors: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transform::transform({this:?}, {vectors:?})");
+
+        if vectors.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "vectors is null").await);
+        }
+
+        let length = jvm.array_length(&vectors).await?;
+        if length % 4 != 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "vectors.length % 4 != 0").await);
+        }
+
+        let matrix = Self::matrix(jvm, &this).await?;
+        let values: Vec<f32> = jvm.load_array(&vectors, 0, length).await?;
+        let result = values
+            .chunks_exact(4)
+            .flat_map(|v| matrix.transform([v[0], v[1], v[2], v[3]]))
+            .collect::<Vec<_>>();
+
+        jvm.store_array(&mut vectors, 0, result).await
+    }
+
+    async fn post_multiply_matrix(jvm: &Jvm, this: &ClassInstanceRef<Self>, other: &Matrix4) -> JvmResult<()> {
+        let matrix = Self::matrix(jvm, this).await?;
+
+        Self::set_matrix(jvm, this, &matrix.multiply(other)).await
+    }
+
+    pub(crate) async fn matrix(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Matrix4> {
+        let matrix = jvm.get_field(this, "matrix", "[F").await?;
+        let values: Vec<f32> = jvm.load_array(&matrix, 0, 16).await?;
+
+        Ok(Matrix4(values.try_into().unwrap()))
+    }
+
+    pub(crate) async fn set_matrix(jvm: &Jvm, this: &ClassInstanceRef<Self>, matrix: &Matrix4) -> JvmResult<()> {
+        let mut array = jvm.get_field(this, "matrix", "[F").await?;
+
+        jvm.store_array(&mut array, 0, matrix.0.to_vec()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec, vec::Vec};
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::get_protos;
+
+    #[test]
+    fn test_post_operations_compose_right_to_left() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let transform = jvm.new_class("javax/microedition/m3g/Transform", "()V", ()).await?;
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &transform,
+                    "javax/microedition/m3g/Transform",
+                    "postTranslate",
+                    "(FFF)V",
+                    (1.0f32, 2.0f32, 3.0f32),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &transform,
+                    "javax/microedition/m3g/Transform",
+                    "postScale",
+                    "(FFF)V",
+                    (2.0f32, 2.0f32, 2.0f32),
+                )
+                .await?;
+
+            let mut vectors = jvm.instantiate_array("F", 4).await?;
+            jvm.store_array(&mut vectors, 0, vec![1.0f32, 1.0, 1.0, 1.0]).await?;
+            let _: () = jvm
+                .invoke_virtual(&transform, "javax/microedition/m3g/Transform", "transform", "([F)V", (vectors.clone(),))
+                .await?;
+
+            let result: Vec<f32> = jvm.load_array(&vectors, 0, 4).await?;
+            assert_eq!(result, vec![3.0, 4.0, 5.0, 1.0]);
+
+            let _: () = jvm
+                .invoke_virtual(&transform, "javax/microedition/m3g/Transform", "invert", "()V", ())
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&transform, "javax/microedition/m3g/Transform", "transform", "([F)V", (vectors.clone(),))
+                .await?;
+
+            let result: Vec<f32> = jvm.load_array(&vectors, 0, 4).await?;
+            assert_eq!(result, vec![1.0, 1.0, 1.0, 1.0]);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,378 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::Transform,
+    renderer::{Matrix4, multiply_quaternion, normalize_quaternion, quaternion_from_axis_angle, quaternion_to_axis_angle},
+};
+
+// class javax.microedition.m3g.Transformable
+pub struct Transformable;
+
+impl Transformable {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/Transformable",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::empty()),
+                JavaMethodProto::new("setTranslation", "(FFF)V", Self::set_translation, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("translate", "(FFF)V", Self::translate, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getTranslation", "([F)V", Self::get_translation, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setOrientation", "(FFFF)V", Self::set_orientation, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("preRotate", "(FFFF)V", Self::pre_rotate, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("postRotate", "(FFFF)V", Self::post_rotate, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getOrientation", "([F)V", Self::get_orientation, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setScale", "(FFF)V", Self::set_scale, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("scale", "(FFF)V", Self::scale, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getScale", "([F)V", Self::get_scale, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setTransform",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    Self::set_transform,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getTransform",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    Self::get_transform,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getCompositeTransform",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    Self::get_composite_transform,
+                    MethodAccessFlags::PUBLIC,
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("translation", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("orientation", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("scale", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("transform", "[F", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        for (name, length) in [("translation", 3), ("orientation", 4), ("scale", 3), ("transform", 16)] {
+            let array = jvm.instantiate_array("F", length).await?;
+            jvm.put_field(&mut this, name, "[F", array).await?;
+        }
+
+        Self::store(jvm, &this, "orientation", &[0.0, 0.0, 0.0, 1.0]).await?;
+        Self::store(jvm, &this, "scale", &[1.0, 1.0, 1.0]).await?;
+        Self::store(jvm, &this, "transform", &Matrix4::IDENTITY.0).await
+    }
+
+    async fn set_translation(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, tx: f32, ty: f32, tz: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::setTranslation({this:?}, {tx}, {ty}, {tz})");
+
+        Self::store(jvm, &this, "translation", &[tx, ty, tz]).await
+    }
+
+    async fn translate(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, tx: f32, ty: f32, tz: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::translate({this:?}, {tx}, {ty}, {tz})");
+
+        let translation = Self::load(jvm, &this, "translation", 3).await?;
+        Self::store(
+            jvm,
+            &this,
+            "translation",
+            &[translation[0] + tx, translation[1] + ty, translation[2] + tz],
+        )
+        .await
+    }
+
+    async fn get_translation(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        xyz: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::getTranslation({this:?}, {xyz:?})");
+
+        let translation = Self::load(jvm, &this, "translation", 3).await?;
+        Self::copy_out(jvm, xyz, translation).await
+    }
+
+    async fn set_orientation(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        angle: f32,
+        ax: f32,
+        ay: f32,
+        az: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::setOrientation({this:?}, {angle}, {ax}, {ay}, {az})");
+
+        let rotation = Self::rotation(jvm, angle, ax, ay, az).await?;
+        Self::store(jvm, &this, "orientation", &rotation).await
+    }
+
+    async fn pre_rotate(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        angle: f32,
+        ax: f32,
+        ay: f32,
+        az: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::preRotate({this:?}, {angle}, {ax}, {ay}, {az})");
+
+        let rotation = Self::rotation(jvm, angle, ax, ay, az).await?;
+        let orientation = Self::orientation(jvm, &this).await?;
+
+        Self::store(
+            jvm,
+            &this,
+            "orientation",
+            &normalize_quaternion(multiply_quaternion(rotation, orientation)),
+        )
+        .await
+    }
+
+    async fn post_rotate(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        angle: f32,
+        ax: f32,
+        ay: f32,
+        az: f32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::postRotate({this:?}, {angle}, {ax}, {ay}, {az})");
+
+        let rotation = Self::rotation(jvm, angle, ax, ay, az).await?;
+        let orientation = Self::orientation(jvm, &this).await?;
+
+        Self::store(
+            jvm,
+            &this,
+            "orientation",
+            &normalize_quaternion(multiply_quaternion(orientation, rotation)),
+        )
+        .await
+    }
+
+    async fn get_orientation(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        angle_axis: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::getOrientation({this:?}, {angle_axis:?})");
+
+        let orientation = Self::orientation(jvm, &this).await?;
+        Self::copy_out(jvm, angle_axis, quaternion_to_axis_angle(orientation).to_vec()).await
+    }
+
+    async fn set_scale(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, sx: f32, sy: f32, sz: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::setScale({this:?}, {sx}, {sy}, {sz})");
+
+        Self::store(jvm, &this, "scale", &[sx, sy, sz]).await
+    }
+
+    async fn scale(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, sx: f32, sy: f32, sz: f32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::scale({this:?}, {sx}, {sy}, {sz})");
+
+        let scale = Self::load(jvm, &this, "scale", 3).await?;
+        Self::store(jvm, &this, "scale", &[scale[0] * sx, scale[1] * sy, scale[2] * sz]).await
+    }
+
+    async fn get_scale(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, xyz: ClassInstanceRef<Array<f32>>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::getScale({this:?}, {xyz:?})");
+
+        let scale = Self::load(jvm, &this, "scale", 3).await?;
+        Self::copy_out(jvm, xyz, scale).await
+    }
+
+    async fn set_transform(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::setTransform({this:?}, {transform:?})");
+
+        let matrix = if transform.is_null() {
+            Matrix4::IDENTITY
+        } else {
+            Transform::matrix(jvm, &transform).await?
+        };
+
+        // M3G requires the bottom row of a node transform to be (0, 0, 0, 1)
+        if jvm.is_instance(&**this, "javax/microedition/m3g/Node") && matrix.0[12..] != [0.0, 0.0, 0.0, 1.0] {
+            return Err(jvm
+                .exception("java/lang/IllegalArgumentException", "bottom row of node transform is not (0, 0, 0, 1)")
+                .await);
+        }
+
+        Self::store(jvm, &this, "transform", &matrix.0).await
+    }
+
+    async fn get_transform(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::getTransform({this:?}, {transform:?})");
+
+        if transform.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "transform is null").await);
+        }
+
+        let matrix = Self::load(jvm, &this, "transform", 16).await?;
+        Transform::set_matrix(jvm, &transform, &Matrix4(matrix.try_into().unwrap())).await
+    }
+
+    async fn get_composite_transform(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        transform: ClassInstanceRef<Transform>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.Transformable::getCompositeTransform({this:?}, {transform:?})");
+
+        if transform.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "transform is null").await);
+        }
+
+        let matrix = Self::composite_transform(jvm, &this).await?;
+        Transform::set_matrix(jvm, &transform, &matrix).await
+    }
+
+    /// The composite transform `C = T R S M` of this object.
+    pub(crate) async fn composite_transform<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<Matrix4> {
+        let translation = Self::load(jvm, this, "translation", 3).await?;
+        let orientation = Self::orientation(jvm, this).await?;
+        let scale = Self::load(jvm, this, "scale", 3).await?;
+        let transform = Self::load(jvm, this, "transform", 16).await?;
+
+        Ok(Matrix4::translation(translation[0], translation[1], translation[2])
+            .multiply(&Matrix4::from_quaternion(orientation))
+            .multiply(&Matrix4::scale(scale[0], scale[1], scale[2]))
+            .multiply(&Matrix4(transform.try_into().unwrap())))
+    }
+
+    async fn orientation<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<[f32; 4]> {
+        let orientation = Self::load(jvm, this, "orientation", 4).await?;
+
+        Ok(orientation.try_into().unwrap())
+    }
+
+    async fn rotation(jvm: &Jvm, angle: f32, ax: f32, ay: f32, az: f32) -> JvmResult<[f32; 4]> {
+        if angle != 0.0 && ax == 0.0 && ay == 0.0 && az == 0.0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "rotation axis is zero").await);
+        }
+
+        Ok(quaternion_from_axis_angle(angle, ax, ay, az))
+    }
+
+    async fn copy_out(jvm: &Jvm, mut array: ClassInstanceRef<Array<f32>>, values: Vec<f32>) -> JvmResult<()> {
+        if array.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "array is null").await);
+        }
+        if jvm.array_length(&array).await? < values.len() {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "array is too short").await);
+        }
+
+        jvm.store_array(&mut array, 0, values).await
+    }
+
+    async fn load<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, name: &str, length: usize) -> JvmResult<Vec<f32>> {
+        let array = jvm.get_field(this, name, "[F").await?;
+
+        jvm.load_array(&array, 0, length).await
+    }
+
+    async fn store<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, name: &str, values: &[f32]) -> JvmResult<()> {
+        let mut array = jvm.get_field(this, name, "[F").await?;
+
+        jvm.store_array(&mut array, 0, values.to_vec()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec, vec::Vec};
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::get_protos;
+
+    #[test]
+    fn test_composite_transform() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let group = jvm.new_class("javax/microedition/m3g/Group", "()V", ()).await?;
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &group,
+                    "javax/microedition/m3g/Transformable",
+                    "setTranslation",
+                    "(FFF)V",
+                    (1.0f32, 0.0f32, 0.0f32),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &group,
+                    "javax/microedition/m3g/Transformable",
+                    "setOrientation",
+                    "(FFFF)V",
+                    (90.0f32, 0.0f32, 0.0f32, 1.0f32),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &group,
+                    "javax/microedition/m3g/Transformable",
+                    "setScale",
+                    "(FFF)V",
+                    (2.0f32, 2.0f32, 2.0f32),
+                )
+                .await?;
+
+            let transform = jvm.new_class("javax/microedition/m3g/Transform", "()V", ()).await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &group,
+                    "javax/microedition/m3g/Transformable",
+                    "getCompositeTransform",
+                    "(Ljavax/microedition/m3g/Transform;)V",
+                    (transform.clone(),),
+                )
+                .await?;
+
+            let mut vectors = jvm.instantiate_array("F", 4).await?;
+            jvm.store_array(&mut vectors, 0, vec![1.0f32, 0.0, 0.0, 1.0]).await?;
+            let _: () = jvm
+                .invoke_virtual(&transform, "javax/microedition/m3g/Transform", "transform", "([F)V", (vectors.clone(),))
+                .await?;
+
+            // (1, 0, 0) scaled by 2, rotated onto +Y and moved by +1 on X
+            let result: Vec<f32> = jvm.load_array(&vectors, 0, 4).await?;
+            assert!((result[0] - 1.0).abs() < 1e-5);
+            assert!((result[1] - 2.0).abs() < 1e-5);
+            assert!(result[2].abs() < 1e-5);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::JavaMethodProto;
+use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+// class javax.microedition.m3g.TriangleStripArray
+pub struct TriangleStripArray;
+
+impl TriangleStripArray {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/TriangleStripArray",
+            parent_class: Some("javax/microedition/m3g/IndexBuffer"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(I[I)V", Self::init_implicit, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "([I[I)V", Self::init_explicit, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init_implicit(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        first_index: i32,
+        strip_lengths: ClassInstanceRef<Array<i32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.TriangleStripArray::<init>({this:?}, {first_index}, {strip_lengths:?})");
+
+        let strip_lengths = Self::load_strip_lengths(jvm, &strip_lengths).await?;
+        let count: i32 = strip_lengths.iter().sum();
+        if !(0..=65535).contains(&first_index) || first_index + count > 65536 {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "index out of range").await);
+        }
+
+        Self::initialize(jvm, this, (first_index..first_index + count).collect(), strip_lengths).await
+    }
+
+    async fn init_explicit(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        indices: ClassInstanceRef<Array<i32>>,
+        strip_lengths: ClassInstanceRef<Array<i32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.TriangleStripArray::<init>({this:?}, {indices:?}, {strip_lengths:?})");
+
+        if indices.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "indices is null").await);
+        }
+
+        let strip_lengths = Self::load_strip_lengths(jvm, &strip_lengths).await?;
+        let count = strip_lengths.iter().sum::<i32>() as usize;
+        if jvm.array_length(&indices).await? < count {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "indices is too short").await);
+        }
+
+        let indices: Vec<i32> = jvm.load_array(&indices, 0, count).await?;
+        if indices.iter().any(|x| !(0..=65535).contains(x)) {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "index out of range").await);
+        }
+
+        Self::initialize(jvm, this, indices, strip_lengths).await
+    }
+
+    async fn load_strip_lengths(jvm: &Jvm, strip_lengths: &ClassInstanceRef<Array<i32>>) -> JvmResult<Vec<i32>> {
+        if strip_lengths.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "stripLengths is null").await);
+        }
+
+        let length = jvm.array_length(strip_lengths).await?;
+        let strip_lengths: Vec<i32> = jvm.load_array(strip_lengths, 0, length).await?;
+        if strip_lengths.is_empty() || strip_lengths.iter().any(|&x| x < 3) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid strip lengths").await);
+        }
+
+        Ok(strip_lengths)
+    }
+
+    async fn initialize(jvm: &Jvm, mut this: ClassInstanceRef<Self>, indices: Vec<i32>, strip_lengths: Vec<i32>) -> JvmResult<()> {
+        let _: () = jvm
+            .invoke_special(&this, "javax/microedition/m3g/IndexBuffer", "<init>", "()V", ())
+            .await?;
+
+        let mut indices_array = jvm.instantiate_array("I", indices.len()).await?;
+        jvm.store_array(&mut indices_array, 0, indices).await?;
+        let mut strip_lengths_array = jvm.instantiate_array("I", strip_lengths.len()).await?;
+        jvm.store_array(&mut strip_lengths_array, 0, strip_lengths).await?;
+
+        jvm.put_field(&mut this, "indices", "[I", indices_array).await?;
+        jvm.put_field(&mut this, "stripLengths", "[I", strip_lengths_array).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec};
+
+    use jvm::ClassInstanceRef;
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{classes::javax::microedition::m3g::IndexBuffer, get_protos};
+
+    #[test]
+    fn test_implicit_strip() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let mut strip_lengths = jvm.instantiate_array("I", 2).await?;
+            jvm.store_array(&mut strip_lengths, 0, vec![4, 3]).await?;
+
+            let array = jvm
+                .new_class("javax/microedition/m3g/TriangleStripArray", "(I[I)V", (10, strip_lengths))
+                .await?;
+
+            let index_count: i32 = jvm
+                .invoke_virtual(&array, "javax/microedition/m3g/IndexBuffer", "getIndexCount", "()I", ())
+                .await?;
+            assert_eq!(index_count, 9);
+
+            let array: ClassInstanceRef<IndexBuffer> = array.into();
+            let triangles = IndexBuffer::triangles(&jvm, &array).await?;
+            assert_eq!(triangles, vec![[10, 11, 12], [12, 11, 13], [14, 15, 16]]);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+// class javax.microedition.m3g.VertexArray
+pub struct VertexArray;
+
+impl VertexArray {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/VertexArray",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(III)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("set", "(II[S)V", Self::set_shorts, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("set", "(II[B)V", Self::set_bytes, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("get", "(II[S)V", Self::get_shorts, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("get", "(II[B)V", Self::get_bytes, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getVertexCount", "()I", Self::get_vertex_count, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getComponentCount", "()I", Self::get_component_count, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getComponentType", "()I", Self::get_component_type, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("vertexCount", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("componentCount", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("componentSize", "I", FieldAccessFlags::PRIVATE),
+                // byte components are sign extended
+                JavaFieldProto::new("values", "[S", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        vertex_count: i32,
+        component_count: i32,
+        component_size: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::<init>({this:?}, {vertex_count}, {component_count}, {component_size})");
+
+        if !(1..=65535).contains(&vertex_count) || !(2..=4).contains(&component_count) || !(1..=2).contains(&component_size) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid vertex array format").await);
+        }
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        let values = jvm.instantiate_array("S", (vertex_count * component_count) as _).await?;
+
+        jvm.put_field(&mut this, "vertexCount", "I", vertex_count).await?;
+        jvm.put_field(&mut this, "componentCount", "I", component_count).await?;
+        jvm.put_field(&mut this, "componentSize", "I", component_size).await?;
+        jvm.put_field(&mut this, "values", "[S", values).await?;
+
+        Ok(())
+    }
+
+    async fn set_shorts(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        first_vertex: i32,
+        num_vertices: i32,
+        values: ClassInstanceRef<Array<i16>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::set({this:?}, {first_vertex}, {num_vertices}, {values:?})");
+
+        let (offset, length) = Self::range(jvm, &this, 2, first_vertex, num_vertices, &values).await?;
+        let values: Vec<i16> = jvm.load_array(&values, 0, length).await?;
+
+        let mut array = jvm.get_field(&this, "values", "[S").await?;
+        jvm.store_array(&mut array, offset, values).await
+    }
+
+    async fn set_bytes(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        first_vertex: i32,
+        num_vertices: i32,
+        values: ClassInstanceRef<Array<i8>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::set({this:?}, {first_vertex}, {num_vertices}, {values:?})");
+
+        let (offset, length) = Self::range(jvm, &this, 1, first_vertex, num_vertices, &values).await?;
+        let values: Vec<i8> = jvm.load_array(&values, 0, length).await?;
+
+        let mut array = jvm.get_field(&this, "values", "[S").await?;
+        jvm.store_array(&mut array, offset, values.into_iter().map(|x| x as i16).collect::<Vec<_>>())
+            .await
+    }
+
+    async fn get_shorts(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        first_vertex: i32,
+        num_vertices: i32,
+        mut values: ClassInstanceRef<Array<i16>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::get({this:?}, {first_vertex}, {num_vertices}, {values:?})");
+
+        let (offset, length) = Self::range(jvm, &this, 2, first_vertex, num_vertices, &values).await?;
+
+        let array = jvm.get_field(&this, "values", "[S").await?;
+        let data: Vec<i16> = jvm.load_array(&array, offset, length).await?;
+        jvm.store_array(&mut values, 0, data).await
+    }
+
+    async fn get_bytes(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        first_vertex: i32,
+        num_vertices: i32,
+        mut values: ClassInstanceRef<Array<i8>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::get({this:?}, {first_vertex}, {num_vertices}, {values:?})");
+
+        let (offset, length) = Self::range(jvm, &this, 1, first_vertex, num_vertices, &values).await?;
+
+        let array = jvm.get_field(&this, "values", "[S").await?;
+        let data: Vec<i16> = jvm.load_array(&array, offset, length).await?;
+        jvm.store_array(&mut values, 0, data.into_iter().map(|x| x as i8).collect::<Vec<_>>())
+            .await
+    }
+
+    async fn get_vertex_count(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::getVertexCount({this:?})");
+
+        jvm.get_field(&this, "vertexCount", "I").await
+    }
+
+    async fn get_component_count(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::getComponentCount({this:?})");
+
+        jvm.get_field(&this, "componentCount", "I").await
+    }
+
+    async fn get_component_type(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.VertexArray::getComponentType({this:?})");
+
+        jvm.get_field(&this, "componentSize", "I").await
+    }
+
+    /// Validates a `set`/`get` call and returns the value offset and count it covers.
+    async fn range<T>(
+        jvm: &Jvm,
+        this: &ClassInstanceRef<Self>,
+        component_size: i32,
+        first_vertex: i32,
+        num_vertices: i32,
+        values: &ClassInstanceRef<Array<T>>,
+    ) -> JvmResult<(usize, usize)> {
+        if values.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "values is null").await);
+        }
+
+        let own_component_size: i32 = jvm.get_field(this, "componentSize", "I").await?;
+        if own_component_size != component_size {
+            return Err(jvm.exception("java/lang/IllegalStateException", "component type mismatch").await);
+        }
+
+        let vertex_count: i32 = jvm.get_field(this, "vertexCount", "I").await?;
+        let component_count: i32 = jvm.get_field(this, "componentCount", "I").await?;
+        if num_vertices < 0 || first_vertex < 0 || first_vertex + num_vertices > vertex_count {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "vertex range out of bounds").await);
+        }
+
+        let length = (num_vertices * component_count) as usize;
+        if jvm.array_length(values).await? < length {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "values is too short").await);
+        }
+
+        Ok(((first_vertex * component_count) as usize, length))
+    }
+
+    /// Returns the component count along with all component values.
+    pub(crate) async fn values(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<(usize, Vec<i16>)> {
+        let vertex_count: i32 = jvm.get_field(this, "vertexCount", "I").await?;
+        let component_count: i32 = jvm.get_field(this, "componentCount", "I").await?;
+
+        let array = jvm.get_field(this, "values", "[S").await?;
+        let values = jvm.load_array(&array, 0, (vertex_count * component_count) as _).await?;
+
+        Ok((component_count as _, values))
+    }
+}
@@ -0,0 +1,408 @@
+use alloc::{format, vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::{
+    classes::javax::microedition::m3g::VertexArray,
+    renderer::{VertexData, from_argb},
+};
+
+const MAX_TEXTURE_UNITS: i32 = 2;
+
+// class javax.microedition.m3g.VertexBuffer
+pub struct VertexBuffer;
+
+impl VertexBuffer {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/VertexBuffer",
+            parent_class: Some("javax/microedition/m3g/Object3D"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getVertexCount", "()I", Self::get_vertex_count, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setPositions",
+                    "(Ljavax/microedition/m3g/VertexArray;F[F)V",
+                    Self::set_positions,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getPositions",
+                    "([F)Ljavax/microedition/m3g/VertexArray;",
+                    Self::get_positions,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setNormals",
+                    "(Ljavax/microedition/m3g/VertexArray;)V",
+                    Self::set_normals,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getNormals",
+                    "()Ljavax/microedition/m3g/VertexArray;",
+                    Self::get_normals,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setColors",
+                    "(Ljavax/microedition/m3g/VertexArray;)V",
+                    Self::set_colors,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getColors",
+                    "()Ljavax/microedition/m3g/VertexArray;",
+                    Self::get_colors,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setTexCoords",
+                    "(ILjavax/microedition/m3g/VertexArray;F[F)V",
+                    Self::set_tex_coords,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getTexCoords",
+                    "(I[F)Ljavax/microedition/m3g/VertexArray;",
+                    Self::get_tex_coords,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("setDefaultColor", "(I)V", Self::set_default_color, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getDefaultColor", "()I", Self::get_default_color, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("positions", "Ljavax/microedition/m3g/VertexArray;", FieldAccessFlags::PRIVATE),
+                // scale followed by the x, y, z bias
+                JavaFieldProto::new("positionScaleBias", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("normals", "Ljavax/microedition/m3g/VertexArray;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("colors", "Ljavax/microedition/m3g/VertexArray;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("texCoords0", "Ljavax/microedition/m3g/VertexArray;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("texCoords1", "Ljavax/microedition/m3g/VertexArray;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("texCoordScaleBias0", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("texCoordScaleBias1", "[F", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("defaultColor", "I", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/m3g/Object3D", "<init>", "()V", ()).await?;
+
+        for name in ["positionScaleBias", "texCoordScaleBias0", "texCoordScaleBias1"] {
+            let mut array = jvm.instantiate_array("F", 4).await?;
+            jvm.store_array(&mut array, 0, vec![1.0f32, 0.0, 0.0, 0.0]).await?;
+            jvm.put_field(&mut this, name, "[F", array).await?;
+        }
+
+        jvm.put_field(&mut this, "defaultColor", "I", 0xffffffffu32 as i32).await?;
+
+        Ok(())
+    }
+
+    async fn get_vertex_count(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::getVertexCount({this:?})");
+
+        Self::vertex_count(jvm, &this).await
+    }
+
+    async fn set_positions(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        positions: ClassInstanceRef<VertexArray>,
+        scale: f32,
+        bias: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::setPositions({this:?}, {positions:?}, {scale}, {bias:?})");
+
+        if !positions.is_null() {
+            let component_count: i32 = jvm.get_field(&positions, "componentCount", "I").await?;
+            if component_count != 3 {
+                return Err(jvm
+                    .exception("java/lang/IllegalArgumentException", "positions must have 3 components")
+                    .await);
+            }
+        }
+
+        Self::check_vertex_count(jvm, &this, &positions, "positions").await?;
+
+        let bias = Self::load_bias(jvm, &bias, 3).await?;
+        let mut scale_bias = jvm.get_field(&this, "positionScaleBias", "[F").await?;
+        jvm.store_array(&mut scale_bias, 0, vec![scale, bias[0], bias[1], bias[2]]).await?;
+
+        jvm.put_field(&mut this, "positions", "Ljavax/microedition/m3g/VertexArray;", positions)
+            .await
+    }
+
+    async fn get_positions(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        mut scale_bias: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<ClassInstanceRef<VertexArray>> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::getPositions({this:?}, {scale_bias:?})");
+
+        let positions: ClassInstanceRef<VertexArray> = jvm.get_field(&this, "positions", "Ljavax/microedition/m3g/VertexArray;").await?;
+        if !positions.is_null() && !scale_bias.is_null() {
+            let values = jvm.get_field(&this, "positionScaleBias", "[F").await?;
+            let values: Vec<f32> = jvm.load_array(&values, 0, 4).await?;
+            jvm.store_array(&mut scale_bias, 0, values).await?;
+        }
+
+        Ok(positions)
+    }
+
+    async fn set_normals(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        normals: ClassInstanceRef<VertexArray>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::setNormals({this:?}, {normals:?})");
+
+        if !normals.is_null() {
+            let component_count: i32 = jvm.get_field(&normals, "componentCount", "I").await?;
+            if component_count != 3 {
+                return Err(jvm
+                    .exception("java/lang/IllegalArgumentException", "normals must have 3 components")
+                    .await);
+            }
+        }
+
+        Self::check_vertex_count(jvm, &this, &normals, "normals").await?;
+
+        jvm.put_field(&mut this, "normals", "Ljavax/microedition/m3g/VertexArray;", normals).await
+    }
+
+    async fn get_normals(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<VertexArray>> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::getNormals({this:?})");
+
+        jvm.get_field(&this, "normals", "Ljavax/microedition/m3g/VertexArray;").await
+    }
+
+    async fn set_colors(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        colors: ClassInstanceRef<VertexArray>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::setColors({this:?}, {colors:?})");
+
+        if !colors.is_null() {
+            let component_count: i32 = jvm.get_field(&colors, "componentCount", "I").await?;
+            let component_size: i32 = jvm.get_field(&colors, "componentSize", "I").await?;
+            if component_size != 1 || component_count < 3 {
+                return Err(jvm
+                    .exception("java/lang/IllegalArgumentException", "colors must be 3 or 4 byte components")
+                    .await);
+            }
+        }
+
+        Self::check_vertex_count(jvm, &this, &colors, "colors").await?;
+
+        jvm.put_field(&mut this, "colors", "Ljavax/microedition/m3g/VertexArray;", colors).await
+    }
+
+    async fn get_colors(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<VertexArray>> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::getColors({this:?})");
+
+        jvm.get_field(&this, "colors", "Ljavax/microedition/m3g/VertexArray;").await
+    }
+
+    async fn set_tex_coords(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        index: i32,
+        tex_coords: ClassInstanceRef<VertexArray>,
+        scale: f32,
+        bias: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::setTexCoords({this:?}, {index}, {tex_coords:?}, {scale}, {bias:?})");
+
+        if !(0..MAX_TEXTURE_UNITS).contains(&index) {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid texture unit").await);
+        }
+
+        if !tex_coords.is_null() {
+            let component_count: i32 = jvm.get_field(&tex_coords, "componentCount", "I").await?;
+            if component_count == 4 {
+                return Err(jvm
+                    .exception("java/lang/IllegalArgumentException", "texture coordinates must have 2 or 3 components")
+                    .await);
+            }
+        }
+
+        Self::check_vertex_count(jvm, &this, &tex_coords, "texture coordinates").await?;
+
+        let bias = Self::load_bias(jvm, &bias, 3).await?;
+        let mut scale_bias = jvm.get_field(&this, &format!("texCoordScaleBias{index}"), "[F").await?;
+        jvm.store_array(&mut scale_bias, 0, vec![scale, bias[0], bias[1], bias[2]]).await?;
+
+        jvm.put_field(
+            &mut this,
+            &format!("texCoords{index}"),
+            "Ljavax/microedition/m3g/VertexArray;",
+            tex_coords,
+        )
+        .await
+    }
+
+    async fn get_tex_coords(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        index: i32,
+        mut scale_bias: ClassInstanceRef<Array<f32>>,
+    ) -> JvmResult<ClassInstanceRef<VertexArray>> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::getTexCoords({this:?}, {index}, {scale_bias:?})");
+
+        if !(0..MAX_TEXTURE_UNITS).contains(&index) {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid texture unit").await);
+        }
+
+        let tex_coords: ClassInstanceRef<VertexArray> = jvm
+            .get_field(&this, &format!("texCoords{index}"), "Ljavax/microedition/m3g/VertexArray;")
+            .await?;
+        if !tex_coords.is_null() && !scale_bias.is_null() {
+            let component_count: i32 = jvm.get_field(&tex_coords, "componentCount", "I").await?;
+            let values = jvm.get_field(&this, &format!("texCoordScaleBias{index}"), "[F").await?;
+            let values: Vec<f32> = jvm.load_array(&values, 0, 1 + component_count as usize).await?;
+            jvm.store_array(&mut scale_bias, 0, values).await?;
+        }
+
+        Ok(tex_coords)
+    }
+
+    async fn set_default_color(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, argb: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::setDefaultColor({this:?}, {argb:#x})");
+
+        jvm.put_field(&mut this, "defaultColor", "I", argb).await
+    }
+
+    async fn get_default_color(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.m3g.VertexBuffer::getDefaultColor({this:?})");
+
+        jvm.get_field(&this, "defaultColor", "I").await
+    }
+
+    async fn load_bias(jvm: &Jvm, bias: &ClassInstanceRef<Array<f32>>, length: usize) -> JvmResult<Vec<f32>> {
+        if bias.is_null() {
+            return Ok(vec![0.0; length]);
+        }
+
+        let available = jvm.array_length(bias).await?.min(length);
+        let mut result: Vec<f32> = jvm.load_array(bias, 0, available).await?;
+        result.resize(length, 0.0);
+
+        Ok(result)
+    }
+
+    async fn vertex_count(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<i32> {
+        for name in ["positions", "normals", "colors", "texCoords0", "texCoords1"] {
+            let array: ClassInstanceRef<VertexArray> = jvm.get_field(this, name, "Ljavax/microedition/m3g/VertexArray;").await?;
+            if !array.is_null() {
+                return jvm.get_field(&array, "vertexCount", "I").await;
+            }
+        }
+
+        Ok(0)
+    }
+
+    /// All vertex arrays of a buffer must have the same vertex count.
+    async fn check_vertex_count(jvm: &Jvm, this: &ClassInstanceRef<Self>, array: &ClassInstanceRef<VertexArray>, name: &str) -> JvmResult<()> {
+        if array.is_null() {
+            return Ok(());
+        }
+
+        let current = Self::vertex_count(jvm, this).await?;
+        let count: i32 = jvm.get_field(array, "vertexCount", "I").await?;
+        if current != 0 && current != count {
+            return Err(jvm
+                .exception("java/lang/IllegalArgumentException", &format!("{name} vertex count doesn't match"))
+                .await);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn vertex_data(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<VertexData> {
+        let default_color: i32 = jvm.get_field(this, "defaultColor", "I").await?;
+        let mut result = VertexData {
+            default_color: from_argb(default_color as u32),
+            ..Default::default()
+        };
+
+        let positions: ClassInstanceRef<VertexArray> = jvm.get_field(this, "positions", "Ljavax/microedition/m3g/VertexArray;").await?;
+        if !positions.is_null() {
+            let scale_bias = jvm.get_field(this, "positionScaleBias", "[F").await?;
+            let scale_bias: Vec<f32> = jvm.load_array(&scale_bias, 0, 4).await?;
+
+            let (_, values) = VertexArray::values(jvm, &positions).await?;
+            result.positions = values
+                .chunks_exact(3)
+                .map(|v| core::array::from_fn(|i| v[i] as f32 * scale_bias[0] + scale_bias[1 + i]))
+                .collect();
+        }
+
+        let normals: ClassInstanceRef<VertexArray> = jvm.get_field(this, "normals", "Ljavax/microedition/m3g/VertexArray;").await?;
+        if !normals.is_null() {
+            let component_size: i32 = jvm.get_field(&normals, "componentSize", "I").await?;
+            let max = if component_size == 1 { 255.0 } else { 65535.0 };
+
+            let (_, values) = VertexArray::values(jvm, &normals).await?;
+            result.normals = Some(
+                values
+                    .chunks_exact(3)
+                    .map(|v| core::array::from_fn(|i| (2.0 * v[i] as f32 + 1.0) / max))
+                    .collect(),
+            );
+        }
+
+        let colors: ClassInstanceRef<VertexArray> = jvm.get_field(this, "colors", "Ljavax/microedition/m3g/VertexArray;").await?;
+        if !colors.is_null() {
+            let (components, values) = VertexArray::values(jvm, &colors).await?;
+            result.colors = Some(
+                values
+                    .chunks_exact(components)
+                    .map(|v| {
+                        let channel = |i: usize| v.get(i).map(|&x| (x as u8) as f32 / 255.0).unwrap_or(1.0);
+                        [channel(0), channel(1), channel(2), channel(3)]
+                    })
+                    .collect(),
+            );
+        }
+
+        for index in 0..MAX_TEXTURE_UNITS {
+            let tex_coords: ClassInstanceRef<VertexArray> = jvm
+                .get_field(this, &format!("texCoords{index}"), "Ljavax/microedition/m3g/VertexArray;")
+                .await?;
+            if tex_coords.is_null() {
+                result.tex_coords.push(None);
+                continue;
+            }
+
+            let scale_bias = jvm.get_field(this, &format!("texCoordScaleBias{index}"), "[F").await?;
+            let scale_bias: Vec<f32> = jvm.load_array(&scale_bias, 0, 4).await?;
+
+            let (components, values) = VertexArray::values(jvm, &tex_coords).await?;
+            result.tex_coords.push(Some(
+                values
+                    .chunks_exact(components)
+                    .map(|v| [v[0] as f32 * scale_bias[0] + scale_bias[1], v[1] as f32 * scale_bias[0] + scale_bias[2]])
+                    .collect(),
+            ));
+        }
+
+        Ok(result)
+    }
+}
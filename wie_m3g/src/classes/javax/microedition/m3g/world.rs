@@ -0,0 +1,99 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::javax::microedition::m3g::{Background, Camera};
+
+// class javax.microedition.m3g.World
+pub struct World;
+
+impl World {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/m3g/World",
+            parent_class: Some("javax/microedition/m3g/Group"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "setActiveCamera",
+                    "(Ljavax/microedition/m3g/Camera;)V",
+                    Self::set_active_camera,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getActiveCamera",
+                    "()Ljavax/microedition/m3g/Camera;",
+                    Self::get_active_camera,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "setBackground",
+                    "(Ljavax/microedition/m3g/Background;)V",
+                    Self::set_background,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getBackground",
+                    "()Ljavax/microedition/m3g/Background;",
+                    Self::get_background,
+                    MethodAccessFlags::PUBLIC,
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("activeCamera", "Ljavax/microedition/m3g/Camera;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("background", "Ljavax/microedition/m3g/Background;", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.World::<init>({this:?})");
+
+        jvm.invoke_special(&this, "javax/microedition/m3g/Group", "<init>", "()V", ()).await
+    }
+
+    async fn set_active_camera(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        camera: ClassInstanceRef<Camera>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.World::setActiveCamera({this:?}, {camera:?})");
+
+        if camera.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "camera is null").await);
+        }
+
+        jvm.put_field(&mut this, "activeCamera", "Ljavax/microedition/m3g/Camera;", camera).await
+    }
+
+    async fn get_active_camera(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Camera>> {
+        tracing::debug!("javax.microedition.m3g.World::getActiveCamera({this:?})");
+
+        jvm.get_field(&this, "activeCamera", "Ljavax/microedition/m3g/Camera;").await
+    }
+
+    async fn set_background(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        background: ClassInstanceRef<Background>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.m3g.World::setBackground({this:?}, {background:?})");
+
+        jvm.put_field(&mut this, "background", "Ljavax/microedition/m3g/Background;", background)
+            .await
+    }
+
+    async fn get_background(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Background>> {
+        tracing::debug!("javax.microedition.m3g.World::getBackground({this:?})");
+
+        jvm.get_field(&this, "background", "Ljavax/microedition/m3g/Background;").await
+    }
+}
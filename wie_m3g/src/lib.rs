@@ -0,0 +1,37 @@
+#![no_std]
+extern crate alloc;
+
+pub mod classes;
+mod loader;
+mod renderer;
+
+use wie_jvm_support::WieJavaClassProto;
+
+pub fn get_protos() -> [WieJavaClassProto; 24] {
+    [
+        classes::javax::microedition::m3g::Appearance::as_proto(),
+        classes::javax::microedition::m3g::Background::as_proto(),
+        classes::javax::microedition::m3g::Camera::as_proto(),
+        classes::javax::microedition::m3g::CompositingMode::as_proto(),
+        classes::javax::microedition::m3g::Fog::as_proto(),
+        classes::javax::microedition::m3g::Graphics3D::as_proto(),
+        classes::javax::microedition::m3g::Group::as_proto(),
+        classes::javax::microedition::m3g::Image2D::as_proto(),
+        classes::javax::microedition::m3g::IndexBuffer::as_proto(),
+        classes::javax::microedition::m3g::Light::as_proto(),
+        classes::javax::microedition::m3g::Loader::as_proto(),
+        classes::javax::microedition::m3g::Material::as_proto(),
+        classes::javax::microedition::m3g::Mesh::as_proto(),
+        classes::javax::microedition::m3g::Node::as_proto(),
+        classes::javax::microedition::m3g::Object3D::as_proto(),
+        classes::javax::microedition::m3g::PolygonMode::as_proto(),
+        classes::javax::microedition::m3g::Sprite3D::as_proto(),
+        classes::javax::microedition::m3g::Texture2D::as_proto(),
+        classes::javax::microedition::m3g::Transform::as_proto(),
+        classes::javax::microedition::m3g::Transformable::as_proto(),
+        classes::javax::microedition::m3g::TriangleStripArray::as_proto(),
+        classes::javax::microedition::m3g::VertexArray::as_proto(),
+        classes::javax::microedition::m3g::VertexBuffer::as_proto(),
+        classes::javax::microedition::m3g::World::as_proto(),
+    ]
+}
@@ -0,0 +1,864 @@
+//! Parser for the JSR-184 `.m3g` file format.
+//!
+//! Objects are parsed into plain data, [`crate::classes::javax::microedition::m3g::Loader`] turns them into java objects.
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use wie_util::{Result, WieError};
+
+pub const FILE_IDENTIFIER: [u8; 12] = [0xab, 0x4a, 0x53, 0x52, 0x31, 0x38, 0x34, 0xbb, 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// Index into the list of loaded objects, 0 is null and the header is 1.
+pub type ObjectIndex = u32;
+
+pub struct Object3DData {
+    pub user_id: i32,
+    pub user_parameters: Vec<(i32, Vec<u8>)>,
+}
+
+pub struct ComponentTransform {
+    pub translation: [f32; 3],
+    pub scale: [f32; 3],
+    pub orientation_angle: f32,
+    pub orientation_axis: [f32; 3],
+}
+
+pub struct TransformableData {
+    pub object: Object3DData,
+    pub component_transform: Option<ComponentTransform>,
+    pub general_transform: Option<[f32; 16]>,
+}
+
+pub struct NodeData {
+    pub transformable: TransformableData,
+    pub rendering_enabled: bool,
+    pub picking_enabled: bool,
+    pub alpha_factor: u8,
+    pub scope: i32,
+}
+
+pub enum Projection {
+    Generic([f32; 16]),
+    Parallel {
+        kind: u8,
+        fovy: f32,
+        aspect_ratio: f32,
+        near: f32,
+        far: f32,
+    },
+}
+
+pub enum Indices {
+    Implicit(u32),
+    Explicit(Vec<u32>),
+}
+
+pub struct TexCoords {
+    pub array: ObjectIndex,
+    pub bias: [f32; 3],
+    pub scale: f32,
+}
+
+pub enum Object {
+    Header,
+    /// Objects we don't create, like animation data. References to them resolve to null.
+    Unsupported(u8),
+    Appearance {
+        object: Object3DData,
+        layer: i8,
+        compositing_mode: ObjectIndex,
+        fog: ObjectIndex,
+        polygon_mode: ObjectIndex,
+        material: ObjectIndex,
+        textures: Vec<ObjectIndex>,
+    },
+    Background {
+        object: Object3DData,
+        color: u32,
+        image: ObjectIndex,
+        image_mode_x: u8,
+        image_mode_y: u8,
+        crop: [i32; 4],
+        depth_clear_enabled: bool,
+        color_clear_enabled: bool,
+    },
+    Camera {
+        node: NodeData,
+        projection: Projection,
+    },
+    CompositingMode {
+        object: Object3DData,
+        depth_test_enabled: bool,
+        depth_write_enabled: bool,
+        color_write_enabled: bool,
+        alpha_write_enabled: bool,
+        blending: u8,
+        alpha_threshold: u8,
+        depth_offset_factor: f32,
+        depth_offset_units: f32,
+    },
+    Fog {
+        object: Object3DData,
+        color: u32,
+        mode: u8,
+        density: f32,
+        near: f32,
+        far: f32,
+    },
+    PolygonMode {
+        object: Object3DData,
+        culling: u8,
+        shading: u8,
+        winding: u8,
+        two_sided_lighting_enabled: bool,
+        local_camera_lighting_enabled: bool,
+        perspective_correction_enabled: bool,
+    },
+    Group {
+        node: NodeData,
+        children: Vec<ObjectIndex>,
+    },
+    Image2D {
+        object: Object3DData,
+        format: u8,
+        mutable: bool,
+        width: u32,
+        height: u32,
+        palette: Vec<u8>,
+        pixels: Vec<u8>,
+    },
+    TriangleStripArray {
+        object: Object3DData,
+        indices: Indices,
+        strip_lengths: Vec<u32>,
+    },
+    Light {
+        node: NodeData,
+        attenuation: [f32; 3],
+        color: u32,
+        mode: u8,
+        intensity: f32,
+        spot_angle: f32,
+        spot_exponent: f32,
+    },
+    Material {
+        object: Object3DData,
+        ambient_color: u32,
+        diffuse_color: u32,
+        emissive_color: u32,
+        specular_color: u32,
+        shininess: f32,
+        vertex_color_tracking_enabled: bool,
+    },
+    /// Also used for morphing and skinned meshes, without their deformation data.
+    Mesh {
+        node: NodeData,
+        vertex_buffer: ObjectIndex,
+        submeshes: Vec<(ObjectIndex, ObjectIndex)>,
+    },
+    Texture2D {
+        transformable: TransformableData,
+        image: ObjectIndex,
+        blend_color: u32,
+        blending: u8,
+        wrapping_s: u8,
+        wrapping_t: u8,
+        level_filter: u8,
+        image_filter: u8,
+    },
+    Sprite3D {
+        node: NodeData,
+        image: ObjectIndex,
+        appearance: ObjectIndex,
+        scaled: bool,
+        crop: [i32; 4],
+    },
+    VertexArray {
+        object: Object3DData,
+        component_size: u8,
+        component_count: u8,
+        vertex_count: u16,
+        values: Vec<i16>,
+    },
+    VertexBuffer {
+        object: Object3DData,
+        default_color: u32,
+        positions: ObjectIndex,
+        position_bias: [f32; 3],
+        position_scale: f32,
+        normals: ObjectIndex,
+        colors: ObjectIndex,
+        tex_coords: Vec<TexCoords>,
+    },
+    World {
+        node: NodeData,
+        children: Vec<ObjectIndex>,
+        active_camera: ObjectIndex,
+        background: ObjectIndex,
+    },
+}
+
+impl Object {
+    /// Indices of all objects this one refers to.
+    pub fn references(&self) -> Vec<ObjectIndex> {
+        let references = match self {
+            Self::Header | Self::Unsupported(_) => vec![],
+            Self::Appearance {
+                compositing_mode,
+                fog,
+                polygon_mode,
+                material,
+                textures,
+                ..
+            } => [*compositing_mode, *fog, *polygon_mode, *material]
+                .into_iter()
+                .chain(textures.iter().copied())
+                .collect(),
+            Self::Background { image, .. } => vec![*image],
+            Self::Camera { .. } | Self::CompositingMode { .. } | Self::Fog { .. } | Self::PolygonMode { .. } => vec![],
+            Self::Group { children, .. } => children.clone(),
+            Self::Image2D { .. } | Self::TriangleStripArray { .. } | Self::Light { .. } | Self::Material { .. } => vec![],
+            Self::Mesh {
+                vertex_buffer, submeshes, ..
+            } => [*vertex_buffer]
+                .into_iter()
+                .chain(submeshes.iter().flat_map(|(index_buffer, appearance)| [*index_buffer, *appearance]))
+                .collect(),
+            Self::Texture2D { image, .. } => vec![*image],
+            Self::Sprite3D { image, appearance, .. } => vec![*image, *appearance],
+            Self::VertexArray { .. } => vec![],
+            Self::VertexBuffer {
+                positions,
+                normals,
+                colors,
+                tex_coords,
+                ..
+            } => [*positions, *normals, *colors]
+                .into_iter()
+                .chain(tex_coords.iter().map(|x| x.array))
+                .collect(),
+            Self::World {
+                children,
+                active_camera,
+                background,
+                ..
+            } => children.iter().copied().chain([*active_camera, *background]).collect(),
+        };
+
+        references.into_iter().filter(|&x| x != 0).collect()
+    }
+}
+
+pub fn is_m3g(data: &[u8]) -> bool {
+    data.starts_with(&FILE_IDENTIFIER)
+}
+
+/// Parses a whole file, the returned list starts with the header.
+pub fn parse(data: &[u8]) -> Result<Vec<Object>> {
+    if !is_m3g(data) {
+        return Err(error("invalid file identifier"));
+    }
+
+    let mut objects = Vec::new();
+    let mut offset = FILE_IDENTIFIER.len();
+    while offset < data.len() {
+        let mut reader = Reader::new(&data[offset..]);
+        let compression = reader.u8()?;
+        let total_length = reader.u32()? as usize;
+        let uncompressed_length = reader.u32()? as usize;
+
+        if total_length < 13 || offset + total_length > data.len() {
+            return Err(error("invalid section length"));
+        }
+        let section = &data[offset..offset + total_length];
+        let (body, checksum) = section.split_at(total_length - 4);
+        if adler32(body) != u32::from_le_bytes(checksum.try_into().unwrap()) {
+            return Err(error("section checksum mismatch"));
+        }
+
+        let body = match compression {
+            0 => body[9..].to_vec(),
+            1 => miniz_oxide::inflate::decompress_to_vec_zlib(&body[9..]).map_err(|_| error("invalid compressed section"))?,
+            _ => return Err(error("unknown compression scheme")),
+        };
+        if body.len() != uncompressed_length {
+            return Err(error("section length mismatch"));
+        }
+
+        let mut reader = Reader::new(&body);
+        while !reader.is_empty() {
+            let object_type = reader.u8()?;
+            let length = reader.u32()? as usize;
+            let mut object_reader = Reader::new(reader.bytes(length)?);
+
+            if objects.is_empty() && object_type != 0 {
+                return Err(error("file does not start with a header"));
+            }
+            objects.push(parse_object(object_type, &mut object_reader)?);
+        }
+
+        offset += total_length;
+    }
+
+    if objects.is_empty() {
+        return Err(error("file has no objects"));
+    }
+
+    Ok(objects)
+}
+
+/// Indices of objects not referenced by any other object, which is what `Loader.load` returns.
+pub fn roots(objects: &[Object]) -> Vec<ObjectIndex> {
+    let mut referenced = vec![false; objects.len() + 1];
+    for object in objects {
+        for reference in object.references() {
+            if let Some(x) = referenced.get_mut(reference as usize) {
+                *x = true;
+            }
+        }
+    }
+
+    objects
+        .iter()
+        .enumerate()
+        .filter(|(i, object)| !matches!(object, Object::Header | Object::Unsupported(_)) && !referenced[i + 1])
+        .map(|(i, _)| (i + 1) as ObjectIndex)
+        .collect()
+}
+
+fn parse_object(object_type: u8, reader: &mut Reader) -> Result<Object> {
+    Ok(match object_type {
+        0 => {
+            let version = [reader.u8()?, reader.u8()?];
+            if version != [1, 0] {
+                return Err(error("unsupported version"));
+            }
+            if reader.bool()? {
+                return Err(error("external references are not supported"));
+            }
+
+            Object::Header
+        }
+        3 => Object::Appearance {
+            object: reader.object_3d()?,
+            layer: reader.u8()? as i8,
+            compositing_mode: reader.u32()?,
+            fog: reader.u32()?,
+            polygon_mode: reader.u32()?,
+            material: reader.u32()?,
+            textures: reader.u32_array()?,
+        },
+        4 => Object::Background {
+            object: reader.object_3d()?,
+            color: reader.color_rgba()?,
+            image: reader.u32()?,
+            image_mode_x: reader.u8()?,
+            image_mode_y: reader.u8()?,
+            crop: [reader.i32()?, reader.i32()?, reader.i32()?, reader.i32()?],
+            depth_clear_enabled: reader.bool()?,
+            color_clear_enabled: reader.bool()?,
+        },
+        5 => {
+            let node = reader.node()?;
+            let kind = reader.u8()?;
+            let projection = if kind == 48 {
+                Projection::Generic(reader.matrix()?)
+            } else {
+                Projection::Parallel {
+                    kind,
+                    fovy: reader.f32()?,
+                    aspect_ratio: reader.f32()?,
+                    near: reader.f32()?,
+                    far: reader.f32()?,
+                }
+            };
+
+            Object::Camera { node, projection }
+        }
+        6 => Object::CompositingMode {
+            object: reader.object_3d()?,
+            depth_test_enabled: reader.bool()?,
+            depth_write_enabled: reader.bool()?,
+            color_write_enabled: reader.bool()?,
+            alpha_write_enabled: reader.bool()?,
+            blending: reader.u8()?,
+            alpha_threshold: reader.u8()?,
+            depth_offset_factor: reader.f32()?,
+            depth_offset_units: reader.f32()?,
+        },
+        7 => {
+            let object = reader.object_3d()?;
+            let color = reader.color_rgb()?;
+            let mode = reader.u8()?;
+            let (density, near, far) = if mode == 80 {
+                (reader.f32()?, 0.0, 1.0)
+            } else {
+                (1.0, reader.f32()?, reader.f32()?)
+            };
+
+            Object::Fog {
+                object,
+                color,
+                mode,
+                density,
+                near,
+                far,
+            }
+        }
+        8 => Object::PolygonMode {
+            object: reader.object_3d()?,
+            culling: reader.u8()?,
+            shading: reader.u8()?,
+            winding: reader.u8()?,
+            two_sided_lighting_enabled: reader.bool()?,
+            local_camera_lighting_enabled: reader.bool()?,
+            perspective_correction_enabled: reader.bool()?,
+        },
+        9 => Object::Group {
+            node: reader.node()?,
+            children: reader.u32_array()?,
+        },
+        10 => {
+            let object = reader.object_3d()?;
+            let format = reader.u8()?;
+            let mutable = reader.bool()?;
+            let width = reader.u32()?;
+            let height = reader.u32()?;
+            let (palette, pixels) = if mutable {
+                (Vec::new(), Vec::new())
+            } else {
+                (reader.byte_array()?, reader.byte_array()?)
+            };
+
+            Object::Image2D {
+                object,
+                format,
+                mutable,
+                width,
+                height,
+                palette,
+                pixels,
+            }
+        }
+        11 => {
+            let object = reader.object_3d()?;
+            let indices = match reader.u8()? {
+                0 => Indices::Implicit(reader.u32()?),
+                1 => Indices::Implicit(reader.u8()? as _),
+                2 => Indices::Implicit(reader.u16()? as _),
+                128 => Indices::Explicit(reader.u32_array()?),
+                129 => {
+                    let count = reader.u32()?;
+                    Indices::Explicit((0..count).map(|_| reader.u8().map(|x| x as u32)).collect::<Result<_>>()?)
+                }
+                130 => {
+                    let count = reader.u32()?;
+                    Indices::Explicit((0..count).map(|_| reader.u16().map(|x| x as u32)).collect::<Result<_>>()?)
+                }
+                _ => return Err(error("unknown index encoding")),
+            };
+
+            Object::TriangleStripArray {
+                object,
+                indices,
+                strip_lengths: reader.u32_array()?,
+            }
+        }
+        12 => Object::Light {
+            node: reader.node()?,
+            attenuation: reader.vector3()?,
+            color: reader.color_rgb()?,
+            mode: reader.u8()?,
+            intensity: reader.f32()?,
+            spot_angle: reader.f32()?,
+            spot_exponent: reader.f32()?,
+        },
+        13 => Object::Material {
+            object: reader.object_3d()?,
+            ambient_color: reader.color_rgb()?,
+            diffuse_color: reader.color_rgba()?,
+            emissive_color: reader.color_rgb()?,
+            specular_color: reader.color_rgb()?,
+            shininess: reader.f32()?,
+            vertex_color_tracking_enabled: reader.bool()?,
+        },
+        // MorphingMesh and SkinnedMesh start out with the mesh data
+        14..=16 => {
+            let node = reader.node()?;
+            let vertex_buffer = reader.u32()?;
+            let count = reader.u32()?;
+            let submeshes = (0..count).map(|_| Ok((reader.u32()?, reader.u32()?))).collect::<Result<_>>()?;
+
+            if object_type != 14 {
+                tracing::warn!("Loading mesh type {object_type} without deformation");
+            }
+
+            Object::Mesh {
+                node,
+                vertex_buffer,
+                submeshes,
+            }
+        }
+        17 => Object::Texture2D {
+            transformable: reader.transformable()?,
+            image: reader.u32()?,
+            blend_color: reader.color_rgb()?,
+            blending: reader.u8()?,
+            wrapping_s: reader.u8()?,
+            wrapping_t: reader.u8()?,
+            level_filter: reader.u8()?,
+            image_filter: reader.u8()?,
+        },
+        18 => Object::Sprite3D {
+            node: reader.node()?,
+            image: reader.u32()?,
+            appearance: reader.u32()?,
+            scaled: reader.bool()?,
+            crop: [reader.i32()?, reader.i32()?, reader.i32()?, reader.i32()?],
+        },
+        20 => {
+            let object = reader.object_3d()?;
+            let component_size = reader.u8()?;
+            let component_count = reader.u8()?;
+            let encoding = reader.u8()?;
+            let vertex_count = reader.u16()?;
+
+            let mut values = Vec::with_capacity(vertex_count as usize * component_count as usize);
+            for _ in 0..vertex_count as usize * component_count as usize {
+                values.push(match component_size {
+                    1 => reader.u8()? as i8 as i16,
+                    2 => reader.u16()? as i16,
+                    _ => return Err(error("invalid component size")),
+                });
+            }
+
+            // delta encoded values are relative to the same component of the previous vertex
+            if encoding == 1 {
+                for i in component_count as usize..values.len() {
+                    let previous = values[i - component_count as usize];
+                    values[i] = if component_size == 1 {
+                        (previous as i8).wrapping_add(values[i] as i8) as i16
+                    } else {
+                        previous.wrapping_add(values[i])
+                    };
+                }
+            }
+
+            Object::VertexArray {
+                object,
+                component_size,
+                component_count,
+                vertex_count,
+                values,
+            }
+        }
+        21 => {
+            let object = reader.object_3d()?;
+            let default_color = reader.color_rgba()?;
+            let positions = reader.u32()?;
+            let position_bias = reader.vector3()?;
+            let position_scale = reader.f32()?;
+            let normals = reader.u32()?;
+            let colors = reader.u32()?;
+            let count = reader.u32()?;
+            let tex_coords = (0..count)
+                .map(|_| {
+                    Ok(TexCoords {
+                        array: reader.u32()?,
+                        bias: reader.vector3()?,
+                        scale: reader.f32()?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+
+            Object::VertexBuffer {
+                object,
+                default_color,
+                positions,
+                position_bias,
+                position_scale,
+                normals,
+                colors,
+                tex_coords,
+            }
+        }
+        22 => Object::World {
+            node: reader.node()?,
+            children: reader.u32_array()?,
+            active_camera: reader.u32()?,
+            background: reader.u32()?,
+        },
+        255 => return Err(error("external references are not supported")),
+        _ => {
+            tracing::warn!("Skipping unsupported m3g object type {object_type}");
+
+            Object::Unsupported(object_type)
+        }
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &x in chunk {
+            a += x as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn error(message: &str) -> WieError {
+    WieError::FatalError(format!("Invalid m3g file: {message}"))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        if self.offset + length > self.data.len() {
+            return Err(error("unexpected end of data"));
+        }
+
+        let result = &self.data[self.offset..self.offset + length];
+        self.offset += length;
+
+        Ok(result)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(error("invalid boolean")),
+        }
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(self.u32()? as i32)
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    fn vector3(&mut self) -> Result<[f32; 3]> {
+        Ok([self.f32()?, self.f32()?, self.f32()?])
+    }
+
+    fn matrix(&mut self) -> Result<[f32; 16]> {
+        let mut result = [0.0; 16];
+        for x in &mut result {
+            *x = self.f32()?;
+        }
+
+        Ok(result)
+    }
+
+    /// Returns `0x00RRGGBB`.
+    fn color_rgb(&mut self) -> Result<u32> {
+        let [r, g, b] = [self.u8()?, self.u8()?, self.u8()?];
+
+        Ok(u32::from_be_bytes([0, r, g, b]))
+    }
+
+    /// Returns `0xAARRGGBB`.
+    fn color_rgba(&mut self) -> Result<u32> {
+        let [r, g, b, a] = [self.u8()?, self.u8()?, self.u8()?, self.u8()?];
+
+        Ok(u32::from_be_bytes([a, r, g, b]))
+    }
+
+    fn byte_array(&mut self) -> Result<Vec<u8>> {
+        let length = self.u32()? as usize;
+
+        Ok(self.bytes(length)?.to_vec())
+    }
+
+    fn u32_array(&mut self) -> Result<Vec<u32>> {
+        let length = self.u32()?;
+
+        (0..length).map(|_| self.u32()).collect()
+    }
+
+    fn object_3d(&mut self) -> Result<Object3DData> {
+        let user_id = self.i32()?;
+
+        // animation tracks aren't supported, skip them
+        let _ = self.u32_array()?;
+
+        let count = self.u32()?;
+        let user_parameters = (0..count).map(|_| Ok((self.i32()?, self.byte_array()?))).collect::<Result<_>>()?;
+
+        Ok(Object3DData { user_id, user_parameters })
+    }
+
+    fn transformable(&mut self) -> Result<TransformableData> {
+        let object = self.object_3d()?;
+
+        let component_transform = if self.bool()? {
+            Some(ComponentTransform {
+                translation: self.vector3()?,
+                scale: self.vector3()?,
+                orientation_angle: self.f32()?,
+                orientation_axis: self.vector3()?,
+            })
+        } else {
+            None
+        };
+        let general_transform = if self.bool()? { Some(self.matrix()?) } else { None };
+
+        Ok(TransformableData {
+            object,
+            component_transform,
+            general_transform,
+        })
+    }
+
+    fn node(&mut self) -> Result<NodeData> {
+        let transformable = self.transformable()?;
+        let rendering_enabled = self.bool()?;
+        let picking_enabled = self.bool()?;
+        let alpha_factor = self.u8()?;
+        let scope = self.i32()?;
+
+        // alignment isn't supported, skip it
+        if self.bool()? {
+            self.bytes(2 + 4 + 4)?;
+        }
+
+        Ok(NodeData {
+            transformable,
+            rendering_enabled,
+            picking_enabled,
+            alpha_factor,
+            scope,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::{FILE_IDENTIFIER, Indices, Object, adler32, parse, roots};
+
+    pub(crate) fn section(compression: u8, objects: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (object_type, data) in objects {
+            body.push(*object_type);
+            body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            body.extend_from_slice(data);
+        }
+        let uncompressed_length = body.len() as u32;
+        if compression == 1 {
+            body = miniz_oxide::deflate::compress_to_vec_zlib(&body, 6);
+        }
+
+        let mut result = vec![compression];
+        result.extend_from_slice(&(body.len() as u32 + 13).to_le_bytes());
+        result.extend_from_slice(&uncompressed_length.to_le_bytes());
+        result.extend_from_slice(&body);
+        result.extend_from_slice(&adler32(&result).to_le_bytes());
+
+        result
+    }
+
+    pub(crate) fn header() -> (u8, Vec<u8>) {
+        let mut data = vec![1, 0, 0];
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.push(0);
+
+        (0, data)
+    }
+
+    fn object_3d() -> Vec<u8> {
+        [0u32, 0, 0].iter().flat_map(|x| x.to_le_bytes()).collect()
+    }
+
+    fn triangle_strip_array() -> (u8, Vec<u8>) {
+        let mut data = object_3d();
+        data.push(1);
+        data.push(5);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&3u32.to_le_bytes());
+
+        (11, data)
+    }
+
+    fn vertex_array() -> (u8, Vec<u8>) {
+        let mut data = object_3d();
+        data.extend_from_slice(&[1, 2, 1]);
+        data.extend_from_slice(&3u16.to_le_bytes());
+        data.extend_from_slice(&[10, 20, 1, 2, 0xff, 0xfe]);
+
+        (20, data)
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut file = FILE_IDENTIFIER.to_vec();
+        file.extend(section(0, &[header()]));
+        file.extend(section(1, &[vertex_array(), triangle_strip_array(), (1, vec![1, 2, 3])]));
+
+        let objects = parse(&file).unwrap();
+        assert_eq!(objects.len(), 4);
+        assert!(matches!(objects[0], Object::Header));
+
+        let Object::VertexArray { values, component_count, .. } = &objects[1] else {
+            panic!("expected vertex array");
+        };
+        assert_eq!(*component_count, 2);
+        assert_eq!(values, &[10, 20, 11, 22, 10, 20]);
+
+        let Object::TriangleStripArray {
+            indices: Indices::Implicit(5),
+            strip_lengths,
+            ..
+        } = &objects[2]
+        else {
+            panic!("expected implicit triangle strip array");
+        };
+        assert_eq!(strip_lengths, &[3]);
+
+        assert!(matches!(objects[3], Object::Unsupported(1)));
+        assert_eq!(roots(&objects), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_parse_checksum_mismatch() {
+        let mut file = FILE_IDENTIFIER.to_vec();
+        file.extend(section(0, &[header()]));
+        let last = file.len() - 1;
+        file[last] ^= 0xff;
+
+        assert!(parse(&file).is_err());
+    }
+}
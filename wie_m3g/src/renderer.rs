@@ -0,0 +1,1088 @@
+mod math;
+mod rasterizer;
+
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use wie_backend::canvas::{Canvas, Clip, Color};
+
+use self::rasterizer::{ClipVertex, FragmentState, rasterize_triangle, write_fragment};
+
+pub use self::math::{
+    Matrix4, Vector3, Vector4, multiply_quaternion, normalize_quaternion, quaternion_from_axis_angle, quaternion_to_axis_angle,
+};
+
+use self::math::{dot, length, normalize, sub};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Culling {
+    Back,
+    Front,
+    None,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shading {
+    Flat,
+    Smooth,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Winding {
+    Ccw,
+    Cw,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PolygonMode {
+    pub culling: Culling,
+    pub shading: Shading,
+    pub winding: Winding,
+    pub two_sided_lighting: bool,
+}
+
+impl Default for PolygonMode {
+    fn default() -> Self {
+        Self {
+            culling: Culling::Back,
+            shading: Shading::Smooth,
+            winding: Winding::Ccw,
+            two_sided_lighting: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Blending {
+    Alpha,
+    AlphaAdd,
+    Modulate,
+    ModulateX2,
+    Replace,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CompositingMode {
+    pub blending: Blending,
+    pub alpha_threshold: f32,
+    pub depth_test: bool,
+    pub depth_write: bool,
+    pub color_write: bool,
+}
+
+impl Default for CompositingMode {
+    fn default() -> Self {
+        Self {
+            blending: Blending::Replace,
+            alpha_threshold: 0.0,
+            depth_test: true,
+            depth_write: true,
+            color_write: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Material {
+    pub ambient: Vector4,
+    pub diffuse: Vector4,
+    pub emissive: Vector4,
+    pub specular: Vector4,
+    pub shininess: f32,
+    pub vertex_color_tracking: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum FogMode {
+    Linear { near: f32, far: f32 },
+    Exponential { density: f32 },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Fog {
+    pub mode: FogMode,
+    pub color: Vector4,
+}
+
+impl Fog {
+    fn factor(&self, distance: f32) -> f32 {
+        match self.mode {
+            FogMode::Linear { near, far } => {
+                if far == near {
+                    1.0
+                } else {
+                    ((far - distance) / (far - near)).clamp(0.0, 1.0)
+                }
+            }
+            FogMode::Exponential { density } => (-density * distance).exp().clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextureFunction {
+    Add,
+    Blend,
+    Decal,
+    Modulate,
+    Replace,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextureWrap {
+    Clamp,
+    Repeat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextureFilter {
+    Nearest,
+    Linear,
+}
+
+/// Decoded `Image2D` pixels. Texels are always stored as RGBA; `has_color`/`has_alpha`
+/// keep the original format around so ALPHA and RGB images combine like the reference implementation.
+#[derive(Clone, Debug)]
+pub struct TextureImage {
+    pub width: u32,
+    pub height: u32,
+    pub texels: Vec<Vector4>,
+    pub has_color: bool,
+    pub has_alpha: bool,
+}
+
+impl TextureImage {
+    fn texel(&self, x: i32, y: i32) -> Vector4 {
+        self.texels[(y as u32 * self.width + x as u32) as usize]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Texture {
+    pub image: TextureImage,
+    pub function: TextureFunction,
+    pub blend_color: Vector4,
+    pub wrap_s: TextureWrap,
+    pub wrap_t: TextureWrap,
+    pub filter: TextureFilter,
+    pub transform: Matrix4,
+}
+
+impl Texture {
+    fn wrap(coordinate: i32, size: u32, wrap: TextureWrap) -> i32 {
+        match wrap {
+            TextureWrap::Clamp => coordinate.clamp(0, size as i32 - 1),
+            TextureWrap::Repeat => coordinate.rem_euclid(size as i32),
+        }
+    }
+
+    fn sample(&self, s: f32, t: f32) -> Vector4 {
+        let width = self.image.width;
+        let height = self.image.height;
+        if width == 0 || height == 0 {
+            return [1.0; 4];
+        }
+
+        let u = s * width as f32;
+        let v = t * height as f32;
+
+        match self.filter {
+            TextureFilter::Nearest => self.image.texel(
+                Self::wrap(u.floor() as i32, width, self.wrap_s),
+                Self::wrap(v.floor() as i32, height, self.wrap_t),
+            ),
+            TextureFilter::Linear => {
+                let u = u - 0.5;
+                let v = v - 0.5;
+                let (x0, y0) = (u.floor(), v.floor());
+                let (fx, fy) = (u - x0, v - y0);
+
+                let x1 = Self::wrap(x0 as i32 + 1, width, self.wrap_s);
+                let y1 = Self::wrap(y0 as i32 + 1, height, self.wrap_t);
+                let x0 = Self::wrap(x0 as i32, width, self.wrap_s);
+                let y0 = Self::wrap(y0 as i32, height, self.wrap_t);
+
+                let top = lerp4(self.image.texel(x0, y0), self.image.texel(x1, y0), fx);
+                let bottom = lerp4(self.image.texel(x0, y1), self.image.texel(x1, y1), fx);
+
+                lerp4(top, bottom, fy)
+            }
+        }
+    }
+
+    fn apply(&self, fragment: Vector4, s: f32, t: f32) -> Vector4 {
+        let texel = self.sample(s, t);
+        let [fr, fg, fb, fa] = fragment;
+        let [tr, tg, tb, ta] = texel;
+
+        match self.function {
+            TextureFunction::Replace => {
+                let rgb = if self.image.has_color { [tr, tg, tb] } else { [fr, fg, fb] };
+                let alpha = if self.image.has_alpha { ta } else { fa };
+                [rgb[0], rgb[1], rgb[2], alpha]
+            }
+            TextureFunction::Modulate => [fr * tr, fg * tg, fb * tb, fa * ta],
+            TextureFunction::Decal => [fr + (tr - fr) * ta, fg + (tg - fg) * ta, fb + (tb - fb) * ta, fa],
+            TextureFunction::Blend => {
+                let [cr, cg, cb, _] = self.blend_color;
+                [fr + (cr - fr) * tr, fg + (cg - fg) * tg, fb + (cb - fb) * tb, fa * ta]
+            }
+            TextureFunction::Add => [(fr + tr).min(1.0), (fg + tg).min(1.0), (fb + tb).min(1.0), fa * ta],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Appearance {
+    pub layer: i32,
+    pub polygon_mode: PolygonMode,
+    pub compositing_mode: CompositingMode,
+    pub material: Option<Material>,
+    pub fog: Option<Fog>,
+    pub textures: Vec<Texture>,
+}
+
+/// Vertex attributes with the `VertexBuffer` scale and bias already applied.
+#[derive(Clone, Debug, Default)]
+pub struct VertexData {
+    pub positions: Vec<Vector3>,
+    pub normals: Option<Vec<Vector3>>,
+    pub colors: Option<Vec<Vector4>>,
+    pub tex_coords: Vec<Option<Vec<[f32; 2]>>>,
+    pub default_color: Vector4,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LightMode {
+    Ambient,
+    Directional,
+    Omni,
+    Spot,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Light {
+    pub mode: LightMode,
+    pub color: Vector3,
+    pub intensity: f32,
+    pub attenuation: Vector3,
+    pub spot_angle: f32,
+    pub spot_exponent: f32,
+    /// Light-to-world transform; lights shine along their local -Z axis.
+    pub transform: Matrix4,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BackgroundImageMode {
+    Border,
+    Repeat,
+}
+
+#[derive(Clone, Debug)]
+pub struct BackgroundImage {
+    pub image: TextureImage,
+    pub mode_x: BackgroundImageMode,
+    pub mode_y: BackgroundImageMode,
+    pub crop: (i32, i32, i32, i32),
+}
+
+#[derive(Clone, Debug)]
+pub struct Background {
+    pub color: Vector4,
+    pub color_clear: bool,
+    pub depth_clear: bool,
+    pub image: Option<BackgroundImage>,
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Self {
+            color: [0.0; 4],
+            color_clear: true,
+            depth_clear: true,
+            image: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Sprite {
+    pub image: TextureImage,
+    pub scaled: bool,
+    pub crop: (i32, i32, i32, i32),
+    pub compositing_mode: CompositingMode,
+    pub fog: Option<Fog>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Viewport {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Destination of a render pass: the bound 2D canvas plus its depth buffer.
+pub struct RenderTarget<'a> {
+    pub canvas: &'a mut dyn Canvas,
+    pub depth: &'a mut [f32],
+    pub width: u32,
+    pub height: u32,
+    pub clip: Clip,
+}
+
+impl RenderTarget<'_> {
+    fn bounds(&self, viewport: &Viewport) -> (i32, i32, i32, i32) {
+        let x0 = 0.max(self.clip.x).max(viewport.x);
+        let y0 = 0.max(self.clip.y).max(viewport.y);
+        let x1 = (self.width as i64)
+            .min(self.clip.x as i64 + self.clip.width as i64)
+            .min(viewport.x as i64 + viewport.width as i64) as i32;
+        let y1 = (self.height as i64)
+            .min(self.clip.y as i64 + self.clip.height as i64)
+            .min(viewport.y as i64 + viewport.height as i64) as i32;
+
+        (x0, y0, x1, y1)
+    }
+
+    fn read(&self, x: i32, y: i32) -> Vector4 {
+        self.canvas.get_pixel(x, y).map(from_color).unwrap_or([0.0, 0.0, 0.0, 1.0])
+    }
+
+    fn write(&mut self, x: i32, y: i32, color: Vector4) {
+        let clip = self.clip;
+        self.canvas.put_pixel(x, y, to_color([color[0], color[1], color[2], 1.0]), clip);
+    }
+}
+
+/// Per-frame state of `Graphics3D`: viewport, depth range, camera and lights.
+pub struct Renderer {
+    pub viewport: Viewport,
+    pub depth_range: (f32, f32),
+    pub projection: Matrix4,
+    /// World-to-camera transform, the inverse of the camera node transform.
+    pub view: Matrix4,
+    pub lights: Vec<Light>,
+}
+
+impl Renderer {
+    pub fn new(viewport: Viewport) -> Self {
+        Self {
+            viewport,
+            depth_range: (0.0, 1.0),
+            projection: Matrix4::IDENTITY,
+            view: Matrix4::IDENTITY,
+            lights: Vec::new(),
+        }
+    }
+
+    pub fn set_camera(&mut self, projection: Matrix4, camera_transform: &Matrix4) {
+        self.projection = projection;
+        self.view = camera_transform.invert().unwrap_or(Matrix4::IDENTITY);
+    }
+
+    pub fn clear(&self, target: &mut RenderTarget<'_>, background: &Background) {
+        let (x0, y0, x1, y1) = target.bounds(&self.viewport);
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                if background.color_clear {
+                    let color = background
+                        .image
+                        .as_ref()
+                        .and_then(|image| self.background_texel(image, x, y))
+                        .map(|texel| lerp4(background.color, texel, texel[3]))
+                        .unwrap_or(background.color);
+
+                    target.write(x, y, color);
+                }
+                if background.depth_clear {
+                    let index = (y as u32 * target.width + x as u32) as usize;
+                    target.depth[index] = 1.0;
+                }
+            }
+        }
+    }
+
+    fn background_texel(&self, image: &BackgroundImage, x: i32, y: i32) -> Option<Vector4> {
+        let (crop_x, crop_y, crop_width, crop_height) = image.crop;
+        if self.viewport.width <= 0 || self.viewport.height <= 0 {
+            return None;
+        }
+
+        let u = crop_x + ((x - self.viewport.x) as i64 * crop_width as i64 / self.viewport.width as i64) as i32;
+        let v = crop_y + ((y - self.viewport.y) as i64 * crop_height as i64 / self.viewport.height as i64) as i32;
+
+        let resolve = |coordinate: i32, size: u32, mode: BackgroundImageMode| match mode {
+            BackgroundImageMode::Border => (0..size as i32).contains(&coordinate).then_some(coordinate),
+            BackgroundImageMode::Repeat => (size > 0).then(|| coordinate.rem_euclid(size as i32)),
+        };
+
+        let u = resolve(u, image.image.width, image.mode_x)?;
+        let v = resolve(v, image.image.height, image.mode_y)?;
+
+        Some(image.image.texel(u, v))
+    }
+
+    /// Renders indexed triangles; `triangles` are already unpacked from strips with winding fixed up.
+    pub fn draw_mesh(
+        &self,
+        target: &mut RenderTarget<'_>,
+        model: &Matrix4,
+        vertices: &VertexData,
+        triangles: &[[u32; 3]],
+        appearance: &Appearance,
+        alpha_factor: f32,
+    ) {
+        let model_view = self.view.multiply(model);
+        let normal_matrix = model_view.invert().unwrap_or(Matrix4::IDENTITY).transpose();
+        let model_view_projection = self.projection.multiply(&model_view);
+        let lights = self.eye_space_lights();
+
+        let eye_positions = vertices
+            .positions
+            .iter()
+            .map(|&p| model_view.transform_point(p))
+            .collect::<Vec<_>>();
+        let clip_positions = vertices
+            .positions
+            .iter()
+            .map(|&p| model_view_projection.transform([p[0], p[1], p[2], 1.0]))
+            .collect::<Vec<_>>();
+        let normals = vertices
+            .normals
+            .as_ref()
+            .map(|normals| normals.iter().map(|&n| normalize(normal_matrix.transform_direction(n))).collect::<Vec<_>>());
+
+        let vertex_color = |index: usize| {
+            vertices
+                .colors
+                .as_ref()
+                .and_then(|colors| colors.get(index).copied())
+                .unwrap_or(vertices.default_color)
+        };
+
+        let shade = |index: usize, back: bool| {
+            let mut color = match (&appearance.material, &normals) {
+                (Some(material), Some(normals)) => {
+                    let normal = if back { normals[index].map(|x| -x) } else { normals[index] };
+                    light_vertex(material, &lights, eye_positions[index], normal, vertex_color(index))
+                }
+                (Some(material), None) => light_vertex(material, &lights, eye_positions[index], [0.0, 0.0, 0.0], vertex_color(index)),
+                (None, _) => vertex_color(index),
+            };
+            color[3] *= alpha_factor;
+            color
+        };
+
+        let front_colors = (0..vertices.positions.len()).map(|i| shade(i, false)).collect::<Vec<_>>();
+        let back_colors = (appearance.polygon_mode.two_sided_lighting && appearance.material.is_some())
+            .then(|| (0..vertices.positions.len()).map(|i| shade(i, true)).collect::<Vec<_>>());
+
+        let tex_coords = appearance
+            .textures
+            .iter()
+            .enumerate()
+            .map(|(unit, texture)| {
+                vertices.tex_coords.get(unit).and_then(|x| x.as_ref()).map(|coords| {
+                    coords
+                        .iter()
+                        .map(|&[s, t]| texture.transform.transform([s, t, 0.0, 1.0]))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let state = FragmentState {
+            compositing_mode: &appearance.compositing_mode,
+            textures: &appearance.textures,
+            fog: appearance.fog.as_ref(),
+        };
+
+        let vertex_count = vertices.positions.len() as u32;
+        for triangle in triangles {
+            if triangle.iter().any(|&index| index >= vertex_count) {
+                continue;
+            }
+
+            let [a, b, c] = triangle.map(|x| x as usize);
+            let front_facing = is_front_facing(clip_positions[a], clip_positions[b], clip_positions[c], appearance.polygon_mode.winding);
+            let culled = match appearance.polygon_mode.culling {
+                Culling::Back => !front_facing,
+                Culling::Front => front_facing,
+                Culling::None => false,
+            };
+            if culled {
+                continue;
+            }
+
+            let colors = match (&back_colors, front_facing) {
+                (Some(back_colors), false) => back_colors,
+                _ => &front_colors,
+            };
+
+            let mut triangle_vertices = [a, b, c].map(|index| ClipVertex {
+                position: clip_positions[index],
+                color: colors[index],
+                tex_coords: [0, 1].map(|unit| {
+                    tex_coords
+                        .get(unit)
+                        .and_then(|x| x.as_ref())
+                        .map(|coords| coords[index])
+                        .unwrap_or([0.0, 0.0, 0.0, 1.0])
+                }),
+                eye_depth: -eye_positions[index][2],
+            });
+
+            // M3G flat shading takes the color of the last vertex of each triangle
+            if appearance.polygon_mode.shading == Shading::Flat {
+                let color = triangle_vertices[2].color;
+                for vertex in &mut triangle_vertices {
+                    vertex.color = color;
+                }
+            }
+
+            rasterize_triangle(target, &self.viewport, self.depth_range, &triangle_vertices, &state);
+        }
+    }
+
+    pub fn draw_sprite(&self, target: &mut RenderTarget<'_>, model: &Matrix4, sprite: &Sprite, alpha_factor: f32) {
+        let model_view = self.view.multiply(model);
+        let origin = model_view.transform_point([0.0, 0.0, 0.0]);
+        let clip_origin = self.projection.transform([origin[0], origin[1], origin[2], 1.0]);
+
+        if clip_origin[3] <= 0.0 || clip_origin[2] < -clip_origin[3] || clip_origin[2] > clip_origin[3] {
+            return;
+        }
+
+        let (crop_x, crop_y, crop_width, crop_height) = sprite.crop;
+        if crop_width == 0 || crop_height == 0 {
+            return;
+        }
+
+        let (center_x, center_y) = self.to_window(clip_origin);
+        let (width, height) = if sprite.scaled {
+            let scale_x = length(sub(model_view.transform_point([1.0, 0.0, 0.0]), origin));
+            let scale_y = length(sub(model_view.transform_point([0.0, 1.0, 0.0]), origin));
+            let corner = self
+                .projection
+                .transform([origin[0] + scale_x / 2.0, origin[1] + scale_y / 2.0, origin[2], 1.0]);
+            let (corner_x, corner_y) = self.to_window(corner);
+
+            (2.0 * (corner_x - center_x).abs(), 2.0 * (corner_y - center_y).abs())
+        } else {
+            (crop_width.unsigned_abs() as f32, crop_height.unsigned_abs() as f32)
+        };
+
+        let depth = self.to_window_depth(clip_origin);
+        let left = center_x - width / 2.0;
+        let top = center_y - height / 2.0;
+
+        let (x0, y0, x1, y1) = target.bounds(&self.viewport);
+        let state = FragmentState {
+            compositing_mode: &sprite.compositing_mode,
+            textures: &[],
+            fog: sprite.fog.as_ref(),
+        };
+
+        for y in y0.max(top.floor() as i32)..y1.min((top + height).ceil() as i32) {
+            for x in x0.max(left.floor() as i32)..x1.min((left + width).ceil() as i32) {
+                let u = (x as f32 + 0.5 - left) / width;
+                let v = (y as f32 + 0.5 - top) / height;
+                if !(0.0..1.0).contains(&u) || !(0.0..1.0).contains(&v) {
+                    continue;
+                }
+
+                // negative crop dimensions flip the image
+                let texel_x = crop_x + (u * crop_width.unsigned_abs() as f32) as i32 * crop_width.signum() - (crop_width < 0) as i32;
+                let texel_y = crop_y + (v * crop_height.unsigned_abs() as f32) as i32 * crop_height.signum() - (crop_height < 0) as i32;
+                if texel_x < 0 || texel_y < 0 || texel_x as u32 >= sprite.image.width || texel_y as u32 >= sprite.image.height {
+                    continue;
+                }
+
+                let mut color = sprite.image.texel(texel_x, texel_y);
+                color[3] *= alpha_factor;
+
+                write_fragment(target, x, y, depth, color, -origin[2], &state);
+            }
+        }
+    }
+
+    fn eye_space_lights(&self) -> Vec<Light> {
+        self.lights
+            .iter()
+            .map(|light| Light {
+                transform: self.view.multiply(&light.transform),
+                ..*light
+            })
+            .collect()
+    }
+
+    fn to_window(&self, clip: Vector4) -> (f32, f32) {
+        let x = clip[0] / clip[3];
+        let y = clip[1] / clip[3];
+
+        (
+            self.viewport.x as f32 + (x + 1.0) * self.viewport.width as f32 / 2.0,
+            self.viewport.y as f32 + (1.0 - y) * self.viewport.height as f32 / 2.0,
+        )
+    }
+
+    fn to_window_depth(&self, clip: Vector4) -> f32 {
+        let (near, far) = self.depth_range;
+        near + (clip[2] / clip[3] + 1.0) * (far - near) / 2.0
+    }
+}
+
+/// Homogeneous orientation test; valid even when some vertices have `w <= 0`.
+fn is_front_facing(a: Vector4, b: Vector4, c: Vector4, winding: Winding) -> bool {
+    let det = a[0] * (b[1] * c[3] - c[1] * b[3]) - a[1] * (b[0] * c[3] - c[0] * b[3]) + a[3] * (b[0] * c[1] - c[0] * b[1]);
+
+    match winding {
+        Winding::Ccw => det > 0.0,
+        Winding::Cw => det < 0.0,
+    }
+}
+
+fn light_vertex(material: &Material, lights: &[Light], position: Vector3, normal: Vector3, vertex_color: Vector4) -> Vector4 {
+    let (ambient, diffuse) = if material.vertex_color_tracking {
+        (vertex_color, vertex_color)
+    } else {
+        (material.ambient, material.diffuse)
+    };
+
+    let mut color = [material.emissive[0], material.emissive[1], material.emissive[2]];
+
+    for light in lights {
+        let light_color = light.color.map(|x| x * light.intensity);
+
+        if light.mode == LightMode::Ambient {
+            color = core::array::from_fn(|i| color[i] + ambient[i] * light_color[i]);
+            continue;
+        }
+
+        let light_position = light.transform.transform_point([0.0, 0.0, 0.0]);
+        let light_direction = normalize(light.transform.transform_direction([0.0, 0.0, -1.0]));
+
+        let (to_light, attenuation) = match light.mode {
+            LightMode::Directional => (light_direction.map(|x| -x), 1.0),
+            _ => {
+                let delta = sub(light_position, position);
+                let distance = length(delta);
+                let [constant, linear, quadratic] = light.attenuation;
+                let divisor = constant + linear * distance + quadratic * distance * distance;
+
+                (normalize(delta), if divisor > 0.0 { 1.0 / divisor } else { 1.0 })
+            }
+        };
+
+        let spot = if light.mode == LightMode::Spot {
+            let cos_angle = dot(to_light.map(|x| -x), light_direction);
+            if cos_angle < light.spot_angle.to_radians().cos() {
+                0.0
+            } else {
+                cos_angle.max(0.0).powf(light.spot_exponent)
+            }
+        } else {
+            1.0
+        };
+
+        let factor = attenuation * spot;
+        let n_dot_l = dot(normal, to_light);
+        if factor <= 0.0 || n_dot_l <= 0.0 {
+            continue;
+        }
+
+        let half = normalize([to_light[0], to_light[1], to_light[2] + 1.0]);
+        let specular = dot(normal, half).max(0.0).powf(material.shininess);
+
+        color = core::array::from_fn(|i| color[i] + factor * light_color[i] * (diffuse[i] * n_dot_l + material.specular[i] * specular));
+    }
+
+    [color[0].min(1.0), color[1].min(1.0), color[2].min(1.0), diffuse[3]]
+}
+
+fn lerp4(a: Vector4, b: Vector4, t: f32) -> Vector4 {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+pub fn from_argb(argb: u32) -> Vector4 {
+    [
+        ((argb >> 16) & 0xff) as f32 / 255.0,
+        ((argb >> 8) & 0xff) as f32 / 255.0,
+        (argb & 0xff) as f32 / 255.0,
+        ((argb >> 24) & 0xff) as f32 / 255.0,
+    ]
+}
+
+fn from_color(color: Color) -> Vector4 {
+    [
+        color.r as f32 / 255.0,
+        color.g as f32 / 255.0,
+        color.b as f32 / 255.0,
+        color.a as f32 / 255.0,
+    ]
+}
+
+fn to_color(color: Vector4) -> Color {
+    let convert = |x: f32| (x.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+
+    Color {
+        r: convert(color[0]),
+        g: convert(color[1]),
+        b: convert(color[2]),
+        a: convert(color[3]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use wie_backend::canvas::{ArgbPixel, Clip, Image, ImageBufferCanvas, VecImageBuffer};
+
+    use super::{
+        Appearance, Background, Culling, Light, LightMode, Material, Matrix4, PolygonMode, RenderTarget, Renderer, Shading, Texture,
+        TextureFilter, TextureFunction, TextureImage, TextureWrap, VertexData, Viewport, Winding, from_argb,
+    };
+
+    const SIZE: u32 = 8;
+
+    fn render<F>(draw: F) -> VecImageBuffer<ArgbPixel>
+    where
+        F: FnOnce(&mut Renderer, &mut RenderTarget<'_>),
+    {
+        let mut canvas = ImageBufferCanvas::new(VecImageBuffer::<ArgbPixel>::new(SIZE, SIZE));
+        let mut depth = vec![1.0; (SIZE * SIZE) as usize];
+        {
+            let mut target = RenderTarget {
+                canvas: &mut canvas,
+                depth: &mut depth,
+                width: SIZE,
+                height: SIZE,
+                clip: Clip {
+                    x: 0,
+                    y: 0,
+                    width: SIZE,
+                    height: SIZE,
+                },
+            };
+            let mut renderer = Renderer::new(Viewport {
+                x: 0,
+                y: 0,
+                width: SIZE as _,
+                height: SIZE as _,
+            });
+            renderer.set_camera(Matrix4::parallel(2.0, 1.0, 0.1, 10.0), &Matrix4::IDENTITY);
+            renderer.clear(
+                &mut target,
+                &Background {
+                    color: from_argb(0xff000000),
+                    ..Default::default()
+                },
+            );
+
+            draw(&mut renderer, &mut target);
+        }
+
+        canvas.into_inner()
+    }
+
+    /// Compares the rendered image to an ASCII golden image where each character maps to a color.
+    fn assert_golden(image: &dyn Image, golden: &[&str], palette: &[(char, u32)]) {
+        let mut actual = Vec::new();
+        for y in 0..image.height() as i32 {
+            let row = (0..image.width() as i32)
+                .map(|x| {
+                    let color = image.get_pixel(x, y);
+                    let rgb = ((color.r as u32) << 16) | ((color.g as u32) << 8) | color.b as u32;
+                    palette.iter().find(|(_, value)| value & 0xffffff == rgb).map(|(c, _)| *c).unwrap_or('?')
+                })
+                .collect::<alloc::string::String>();
+            actual.push(row);
+        }
+
+        assert_eq!(actual, golden, "rendered image does not match golden image");
+    }
+
+    fn quad(x0: f32, y0: f32, x1: f32, y1: f32, z: f32) -> Vec<[f32; 3]> {
+        vec![[x0, y0, z], [x1, y0, z], [x1, y1, z], [x0, y1, z]]
+    }
+
+    const QUAD: [[u32; 3]; 2] = [[0, 1, 2], [0, 2, 3]];
+
+    fn flat(color: u32, positions: Vec<[f32; 3]>) -> VertexData {
+        VertexData {
+            positions,
+            default_color: from_argb(color),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_clear_fills_viewport() {
+        let image = render(|renderer, target| {
+            renderer.viewport = Viewport {
+                x: 2,
+                y: 2,
+                width: 4,
+                height: 4,
+            };
+            renderer.clear(
+                target,
+                &Background {
+                    color: from_argb(0xff0000ff),
+                    ..Default::default()
+                },
+            );
+        });
+
+        assert_golden(
+            &image,
+            &[
+                "........", "........", "..####..", "..####..", "..####..", "..####..", "........", "........",
+            ],
+            &[('.', 0x000000), ('#', 0x0000ff)],
+        );
+    }
+
+    #[test]
+    fn test_parallel_quad_covers_left_half() {
+        let image = render(|renderer, target| {
+            renderer.draw_mesh(
+                target,
+                &Matrix4::IDENTITY,
+                &flat(0xffff0000, quad(-1.0, -1.0, 0.0, 1.0, -1.0)),
+                &QUAD,
+                &Appearance::default(),
+                1.0,
+            );
+        });
+
+        assert_golden(
+            &image,
+            &[
+                "####....", "####....", "####....", "####....", "####....", "####....", "####....", "####....",
+            ],
+            &[('.', 0x000000), ('#', 0xff0000)],
+        );
+    }
+
+    #[test]
+    fn test_depth_test_keeps_nearest_surface() {
+        let image = render(|renderer, target| {
+            renderer.draw_mesh(
+                target,
+                &Matrix4::IDENTITY,
+                &flat(0xffff0000, quad(0.0, -1.0, 1.0, 1.0, -1.0)),
+                &QUAD,
+                &Appearance::default(),
+                1.0,
+            );
+            renderer.draw_mesh(
+                target,
+                &Matrix4::IDENTITY,
+                &flat(0xff0000ff, quad(-1.0, -1.0, 1.0, 1.0, -5.0)),
+                &QUAD,
+                &Appearance::default(),
+                1.0,
+            );
+        });
+
+        assert_golden(
+            &image,
+            &[
+                "bbbbrrrr", "bbbbrrrr", "bbbbrrrr", "bbbbrrrr", "bbbbrrrr", "bbbbrrrr", "bbbbrrrr", "bbbbrrrr",
+            ],
+            &[('r', 0xff0000), ('b', 0x0000ff)],
+        );
+    }
+
+    #[test]
+    fn test_triangle_edges_and_culling() {
+        let triangle = flat(0xff00ff00, vec![[-1.0, -1.0, -1.0], [1.0, -1.0, -1.0], [-1.0, 1.25, -1.0]]);
+        let golden = [
+            "#.......", "##......", "###.....", "####....", "#####...", "######..", "#######.", "########",
+        ];
+        let empty = ["........"; 8];
+        let palette = [('.', 0x000000), ('#', 0x00ff00)];
+
+        let draw = |indices: [u32; 3], culling: Culling| {
+            render(|renderer, target| {
+                let appearance = Appearance {
+                    polygon_mode: PolygonMode {
+                        culling,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                renderer.draw_mesh(target, &Matrix4::IDENTITY, &triangle, &[indices], &appearance, 1.0);
+            })
+        };
+
+        assert_golden(&draw([0, 1, 2], Culling::Back), &golden, &palette);
+        assert_golden(&draw([0, 2, 1], Culling::Back), &empty, &palette);
+        assert_golden(&draw([0, 2, 1], Culling::None), &golden, &palette);
+        assert_golden(&draw([0, 1, 2], Culling::Front), &empty, &palette);
+    }
+
+    #[test]
+    fn test_perspective_projection_shrinks_distant_quad() {
+        let image = render(|renderer, target| {
+            renderer.set_camera(Matrix4::perspective(90.0, 1.0, 1.0, 100.0), &Matrix4::IDENTITY);
+            renderer.draw_mesh(
+                target,
+                &Matrix4::IDENTITY,
+                &flat(0xffffffff, quad(-1.0, -1.0, 1.0, 1.0, -2.0)),
+                &QUAD,
+                &Appearance::default(),
+                1.0,
+            );
+        });
+
+        assert_golden(
+            &image,
+            &[
+                "........", "........", "..####..", "..####..", "..####..", "..####..", "........", "........",
+            ],
+            &[('.', 0x000000), ('#', 0xffffff)],
+        );
+    }
+
+    #[test]
+    fn test_camera_transform_moves_scene() {
+        let image = render(|renderer, target| {
+            renderer.set_camera(Matrix4::parallel(2.0, 1.0, 0.1, 10.0), &Matrix4::translation(1.0, 0.0, 0.0));
+            renderer.draw_mesh(
+                target,
+                &Matrix4::IDENTITY,
+                &flat(0xffff0000, quad(-1.0, -1.0, 0.0, 1.0, -1.0)),
+                &QUAD,
+                &Appearance::default(),
+                1.0,
+            );
+        });
+
+        assert_golden(
+            &image,
+            &[
+                "........", "........", "........", "........", "........", "........", "........", "........",
+            ],
+            &[('.', 0x000000)],
+        );
+    }
+
+    #[test]
+    fn test_directional_light_and_flat_shading() {
+        let mut vertices = flat(0xffffffff, quad(-1.0, -1.0, 1.0, 1.0, -1.0));
+        vertices.normals = Some(vec![[0.0, 0.0, 1.0]; 4]);
+
+        let material = Material {
+            ambient: from_argb(0xff000000),
+            diffuse: from_argb(0xff00ff00),
+            emissive: from_argb(0xff000080),
+            specular: from_argb(0xff000000),
+            shininess: 0.0,
+            vertex_color_tracking: false,
+        };
+        let light = |transform: Matrix4| Light {
+            mode: LightMode::Directional,
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+            attenuation: [1.0, 0.0, 0.0],
+            spot_angle: 45.0,
+            spot_exponent: 0.0,
+            transform,
+        };
+        let appearance = Appearance {
+            material: Some(material),
+            polygon_mode: PolygonMode {
+                shading: Shading::Flat,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // a light shining along -Z hits the +Z facing quad head-on
+        let lit = render(|renderer, target| {
+            renderer.lights.push(light(Matrix4::IDENTITY));
+            renderer.draw_mesh(target, &Matrix4::IDENTITY, &vertices, &QUAD, &appearance, 1.0);
+        });
+        assert_golden(&lit, &["########"; 8], &[('#', 0x00ff80)]);
+
+        // flipped light only leaves the emissive term
+        let unlit = render(|renderer, target| {
+            renderer.lights.push(light(Matrix4::rotation(180.0, 0.0, 1.0, 0.0)));
+            renderer.draw_mesh(target, &Matrix4::IDENTITY, &vertices, &QUAD, &appearance, 1.0);
+        });
+        assert_golden(&unlit, &["########"; 8], &[('#', 0x000080)]);
+    }
+
+    #[test]
+    fn test_texture_replace_nearest() {
+        let mut vertices = flat(0xffffffff, quad(-1.0, -1.0, 1.0, 1.0, -1.0));
+        vertices.tex_coords = vec![Some(vec![[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]])];
+
+        let texture = Texture {
+            image: TextureImage {
+                width: 2,
+                height: 2,
+                texels: [0xffff0000, 0xff00ff00, 0xff0000ff, 0xffffffff].into_iter().map(from_argb).collect(),
+                has_color: true,
+                has_alpha: false,
+            },
+            function: TextureFunction::Replace,
+            blend_color: [0.0; 4],
+            wrap_s: TextureWrap::Repeat,
+            wrap_t: TextureWrap::Repeat,
+            filter: TextureFilter::Nearest,
+            transform: Matrix4::IDENTITY,
+        };
+        let appearance = Appearance {
+            textures: vec![texture],
+            ..Default::default()
+        };
+
+        let image = render(|renderer, target| {
+            renderer.draw_mesh(target, &Matrix4::IDENTITY, &vertices, &QUAD, &appearance, 1.0);
+        });
+
+        assert_golden(
+            &image,
+            &[
+                "rrrrgggg", "rrrrgggg", "rrrrgggg", "rrrrgggg", "bbbbwwww", "bbbbwwww", "bbbbwwww", "bbbbwwww",
+            ],
+            &[('r', 0xff0000), ('g', 0x00ff00), ('b', 0x0000ff), ('w', 0xffffff)],
+        );
+    }
+
+    #[test]
+    fn test_winding_cw_flips_front_face() {
+        let triangle = flat(0xff00ff00, vec![[-1.0, -1.0, -1.0], [-1.0, 1.25, -1.0], [1.0, -1.0, -1.0]]);
+        let appearance = Appearance {
+            polygon_mode: PolygonMode {
+                winding: Winding::Cw,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let image = render(|renderer, target| {
+            renderer.draw_mesh(target, &Matrix4::IDENTITY, &triangle, &[[0, 1, 2]], &appearance, 1.0);
+        });
+
+        assert_golden(
+            &image,
+            &[
+                "#.......", "##......", "###.....", "####....", "#####...", "######..", "#######.", "########",
+            ],
+            &[('.', 0x000000), ('#', 0x00ff00)],
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use num_traits::Float;
+
+pub type Vector3 = [f32; 3];
+pub type Vector4 = [f32; 4];
+
+/// 4x4 matrix stored in row-major order, matching `javax.microedition.m3g.Transform::get`.
+/// Vectors are treated as columns, so `a.multiply(&b)` applies `b` first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix4(pub [f32; 16]);
+
+impl Matrix4 {
+    pub const IDENTITY: Matrix4 = Matrix4([1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+
+    pub fn translation(x: f32, y: f32, z: f32) -> Self {
+        Matrix4([1.0, 0.0, 0.0, x, 0.0, 1.0, 0.0, y, 0.0, 0.0, 1.0, z, 0.0, 0.0, 0.0, 1.0])
+    }
+
+    pub fn scale(x: f32, y: f32, z: f32) -> Self {
+        Matrix4([x, 0.0, 0.0, 0.0, 0.0, y, 0.0, 0.0, 0.0, 0.0, z, 0.0, 0.0, 0.0, 0.0, 1.0])
+    }
+
+    /// Rotation of `angle` degrees around the axis `(ax, ay, az)`. A zero angle yields identity even when the axis is zero.
+    pub fn rotation(angle: f32, ax: f32, ay: f32, az: f32) -> Self {
+        Self::from_quaternion(quaternion_from_axis_angle(angle, ax, ay, az))
+    }
+
+    pub fn from_quaternion(q: Vector4) -> Self {
+        let [x, y, z, w] = q;
+
+        Matrix4([
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - z * w),
+            2.0 * (x * z + y * w),
+            0.0,
+            2.0 * (x * y + z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - x * w),
+            0.0,
+            2.0 * (x * z - y * w),
+            2.0 * (y * z + x * w),
+            1.0 - 2.0 * (x * x + y * y),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ])
+    }
+
+    /// `glFrustum`-style projection used by `Camera::setPerspective`.
+    pub fn perspective(fovy: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
+        let h = (fovy.to_radians() / 2.0).tan();
+        let w = aspect_ratio * h;
+        let d = far - near;
+
+        Matrix4([
+            1.0 / w,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0 / h,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -(near + far) / d,
+            -2.0 * near * far / d,
+            0.0,
+            0.0,
+            -1.0,
+            0.0,
+        ])
+    }
+
+    /// `glOrtho`-style projection used by `Camera::setParallel`; `height` is the full height of the view volume.
+    pub fn parallel(height: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
+        let w = aspect_ratio * height;
+        let d = far - near;
+
+        Matrix4([
+            2.0 / w,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            2.0 / height,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -2.0 / d,
+            -(near + far) / d,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ])
+    }
+
+    pub fn multiply(&self, other: &Matrix4) -> Self {
+        let mut result = [0.0; 16];
+        for row in 0..4 {
+            for column in 0..4 {
+                result[row * 4 + column] = (0..4).map(|i| self.0[row * 4 + i] * other.0[i * 4 + column]).sum();
+            }
+        }
+
+        Matrix4(result)
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut result = [0.0; 16];
+        for row in 0..4 {
+            for column in 0..4 {
+                result[column * 4 + row] = self.0[row * 4 + column];
+            }
+        }
+
+        Matrix4(result)
+    }
+
+    pub fn invert(&self) -> Option<Self> {
+        let m = &self.0;
+        let mut inv = [0.0f32; 16];
+
+        inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15] + m[9] * m[7] * m[14] + m[13] * m[6] * m[11]
+            - m[13] * m[7] * m[10];
+        inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15] - m[8] * m[7] * m[14] - m[12] * m[6] * m[11]
+            + m[12] * m[7] * m[10];
+        inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15] + m[8] * m[7] * m[13] + m[12] * m[5] * m[11]
+            - m[12] * m[7] * m[9];
+        inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14] - m[8] * m[6] * m[13] - m[12] * m[5] * m[10]
+            + m[12] * m[6] * m[9];
+        inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15] - m[9] * m[3] * m[14] - m[13] * m[2] * m[11]
+            + m[13] * m[3] * m[10];
+        inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15] + m[8] * m[3] * m[14] + m[12] * m[2] * m[11]
+            - m[12] * m[3] * m[10];
+        inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15] - m[8] * m[3] * m[13] - m[12] * m[1] * m[11]
+            + m[12] * m[3] * m[9];
+        inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14] + m[8] * m[2] * m[13] + m[12] * m[1] * m[10]
+            - m[12] * m[2] * m[9];
+        inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15] + m[5] * m[3] * m[14] + m[13] * m[2] * m[7]
+            - m[13] * m[3] * m[6];
+        inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15] - m[4] * m[3] * m[14] - m[12] * m[2] * m[7]
+            + m[12] * m[3] * m[6];
+        inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15] + m[4] * m[3] * m[13] + m[12] * m[1] * m[7]
+            - m[12] * m[3] * m[5];
+        inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14] - m[4] * m[2] * m[13] - m[12] * m[1] * m[6]
+            + m[12] * m[2] * m[5];
+        inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11] - m[5] * m[3] * m[10] - m[9] * m[2] * m[7]
+            + m[9] * m[3] * m[6];
+        inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11] + m[4] * m[3] * m[10] + m[8] * m[2] * m[7]
+            - m[8] * m[3] * m[6];
+        inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11] - m[4] * m[3] * m[9] - m[8] * m[1] * m[7]
+            + m[8] * m[3] * m[5];
+        inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10] + m[4] * m[2] * m[9] + m[8] * m[1] * m[6]
+            - m[8] * m[2] * m[5];
+
+        let det = m[0] * inv[0] + m[1] * inv[4] + m[2] * inv[8] + m[3] * inv[12];
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        Some(Matrix4(inv.map(|x| x / det)))
+    }
+
+    pub fn transform(&self, v: Vector4) -> Vector4 {
+        let m = &self.0;
+
+        [
+            m[0] * v[0] + m[1] * v[1] + m[2] * v[2] + m[3] * v[3],
+            m[4] * v[0] + m[5] * v[1] + m[6] * v[2] + m[7] * v[3],
+            m[8] * v[0] + m[9] * v[1] + m[10] * v[2] + m[11] * v[3],
+            m[12] * v[0] + m[13] * v[1] + m[14] * v[2] + m[15] * v[3],
+        ]
+    }
+
+    pub fn transform_point(&self, p: Vector3) -> Vector3 {
+        let [x, y, z, w] = self.transform([p[0], p[1], p[2], 1.0]);
+        if w != 0.0 && w != 1.0 { [x / w, y / w, z / w] } else { [x, y, z] }
+    }
+
+    pub fn transform_direction(&self, d: Vector3) -> Vector3 {
+        let [x, y, z, _] = self.transform([d[0], d[1], d[2], 0.0]);
+        [x, y, z]
+    }
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Quaternion `[x, y, z, w]` for a rotation of `angle` degrees around an axis.
+pub fn quaternion_from_axis_angle(angle: f32, ax: f32, ay: f32, az: f32) -> Vector4 {
+    let length = (ax * ax + ay * ay + az * az).sqrt();
+    if angle == 0.0 || length == 0.0 {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+
+    let half = angle.to_radians() / 2.0;
+    let s = half.sin() / length;
+
+    [ax * s, ay * s, az * s, half.cos()]
+}
+
+/// Inverse of [`quaternion_from_axis_angle`], returning `[angle, ax, ay, az]`.
+pub fn quaternion_to_axis_angle(q: Vector4) -> Vector4 {
+    let [x, y, z, w] = normalize_quaternion(q);
+    let s = (1.0 - w * w).max(0.0).sqrt();
+    if s < 1e-6 {
+        return [0.0, 0.0, 0.0, 0.0];
+    }
+
+    [(2.0 * w.clamp(-1.0, 1.0).acos()).to_degrees(), x / s, y / s, z / s]
+}
+
+pub fn multiply_quaternion(a: Vector4, b: Vector4) -> Vector4 {
+    [
+        a[3] * b[0] + a[0] * b[3] + a[1] * b[2] - a[2] * b[1],
+        a[3] * b[1] - a[0] * b[2] + a[1] * b[3] + a[2] * b[0],
+        a[3] * b[2] + a[0] * b[1] - a[1] * b[0] + a[2] * b[3],
+        a[3] * b[3] - a[0] * b[0] - a[1] * b[1] - a[2] * b[2],
+    ]
+}
+
+pub fn normalize_quaternion(q: Vector4) -> Vector4 {
+    let length = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+    if length == 0.0 {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+
+    q.map(|x| x / length)
+}
+
+pub fn dot(a: Vector3, b: Vector3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn sub(a: Vector3, b: Vector3) -> Vector3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub fn length(v: Vector3) -> f32 {
+    dot(v, v).sqrt()
+}
+
+pub fn normalize(v: Vector3) -> Vector3 {
+    let length = length(v);
+    if length == 0.0 { v } else { v.map(|x| x / length) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Matrix4, quaternion_from_axis_angle, quaternion_to_axis_angle};
+
+    fn assert_near(actual: &[f32], expected: &[f32]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-4, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_invert_round_trips() {
+        let matrix = Matrix4::translation(1.0, 2.0, 3.0)
+            .multiply(&Matrix4::rotation(30.0, 0.0, 1.0, 0.0))
+            .multiply(&Matrix4::scale(2.0, 3.0, 4.0));
+        let inverse = matrix.invert().unwrap();
+
+        assert_near(&matrix.multiply(&inverse).0, &Matrix4::IDENTITY.0);
+        assert!(Matrix4::scale(0.0, 1.0, 1.0).invert().is_none());
+    }
+
+    #[test]
+    fn test_rotation_follows_right_hand_rule() {
+        let rotated = Matrix4::rotation(90.0, 0.0, 0.0, 1.0).transform_point([1.0, 0.0, 0.0]);
+
+        assert_near(&rotated, &[0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_axis_angle_round_trip() {
+        let q = quaternion_from_axis_angle(60.0, 0.0, 2.0, 0.0);
+
+        assert_near(&quaternion_to_axis_angle(q), &[60.0, 0.0, 1.0, 0.0]);
+        assert_near(&quaternion_to_axis_angle(quaternion_from_axis_angle(0.0, 0.0, 0.0, 0.0)), &[0.0; 4]);
+    }
+
+    #[test]
+    fn test_projection_maps_clip_planes() {
+        let perspective = Matrix4::perspective(90.0, 1.0, 1.0, 10.0);
+        let near = perspective.transform([0.0, 0.0, -1.0, 1.0]);
+        let far = perspective.transform([0.0, 0.0, -10.0, 1.0]);
+        assert_near(&[near[2] / near[3], far[2] / far[3]], &[-1.0, 1.0]);
+
+        let parallel = Matrix4::parallel(2.0, 1.0, 1.0, 10.0);
+        assert_near(&parallel.transform_point([1.0, 1.0, -1.0]), &[1.0, 1.0, -1.0]);
+    }
+}
@@ -0,0 +1,222 @@
+use alloc::vec::Vec;
+
+use super::{Blending, CompositingMode, Fog, RenderTarget, Texture, Vector4, Viewport};
+
+const W_EPSILON: f32 = 1e-5;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ClipVertex {
+    pub position: Vector4,
+    pub color: Vector4,
+    pub tex_coords: [Vector4; 2],
+    /// Distance from the camera plane, used for fog.
+    pub eye_depth: f32,
+}
+
+impl ClipVertex {
+    fn lerp(&self, other: &ClipVertex, t: f32) -> ClipVertex {
+        let lerp = |a: Vector4, b: Vector4| core::array::from_fn(|i| a[i] + (b[i] - a[i]) * t);
+
+        ClipVertex {
+            position: lerp(self.position, other.position),
+            color: lerp(self.color, other.color),
+            tex_coords: [
+                lerp(self.tex_coords[0], other.tex_coords[0]),
+                lerp(self.tex_coords[1], other.tex_coords[1]),
+            ],
+            eye_depth: self.eye_depth + (other.eye_depth - self.eye_depth) * t,
+        }
+    }
+}
+
+pub struct FragmentState<'a> {
+    pub compositing_mode: &'a CompositingMode,
+    pub textures: &'a [Texture],
+    pub fog: Option<&'a Fog>,
+}
+
+struct WindowVertex {
+    x: f32,
+    y: f32,
+    z: f32,
+    inv_w: f32,
+    vertex: ClipVertex,
+}
+
+/// Clips the polygon against `distance(v) >= 0` (Sutherland-Hodgman).
+fn clip_polygon(polygon: Vec<ClipVertex>, distance: impl Fn(&Vector4) -> f32) -> Vec<ClipVertex> {
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+        let current_distance = distance(&current.position);
+        let next_distance = distance(&next.position);
+
+        if current_distance >= 0.0 {
+            result.push(*current);
+        }
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            result.push(current.lerp(next, t));
+        }
+    }
+
+    result
+}
+
+pub fn rasterize_triangle(
+    target: &mut RenderTarget<'_>,
+    viewport: &Viewport,
+    depth_range: (f32, f32),
+    vertices: &[ClipVertex; 3],
+    state: &FragmentState<'_>,
+) {
+    let mut polygon = vertices.to_vec();
+    polygon = clip_polygon(polygon, |p| p[3] - W_EPSILON);
+    polygon = clip_polygon(polygon, |p| p[2] + p[3]);
+    polygon = clip_polygon(polygon, |p| p[3] - p[2]);
+    if polygon.len() < 3 {
+        return;
+    }
+
+    let (near, far) = depth_range;
+    let window = polygon
+        .into_iter()
+        .map(|vertex| {
+            let [x, y, z, w] = vertex.position;
+            let inv_w = 1.0 / w;
+
+            WindowVertex {
+                x: viewport.x as f32 + (x * inv_w + 1.0) * viewport.width as f32 / 2.0,
+                y: viewport.y as f32 + (1.0 - y * inv_w) * viewport.height as f32 / 2.0,
+                z: near + (z * inv_w + 1.0) * (far - near) / 2.0,
+                inv_w,
+                vertex,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for i in 1..window.len() - 1 {
+        fill_triangle(target, viewport, [&window[0], &window[i], &window[i + 1]], state);
+    }
+}
+
+fn fill_triangle(target: &mut RenderTarget<'_>, viewport: &Viewport, vertices: [&WindowVertex; 3], state: &FragmentState<'_>) {
+    let [a, mut b, mut c] = vertices;
+
+    let mut area = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    if area < 0.0 {
+        core::mem::swap(&mut b, &mut c);
+        area = -area;
+    }
+
+    let (x0, y0, x1, y1) = target.bounds(viewport);
+    let min_x = x0.max(a.x.min(b.x).min(c.x) as i32);
+    let min_y = y0.max(a.y.min(b.y).min(c.y) as i32);
+    let max_x = x1.min(a.x.max(b.x).max(c.x) as i32 + 1);
+    let max_y = y1.min(a.y.max(b.y).max(c.y) as i32 + 1);
+
+    // edge from p to q, including pixels exactly on top and left edges only
+    let edge = |p: &WindowVertex, q: &WindowVertex, x: f32, y: f32| {
+        let dx = q.x - p.x;
+        let dy = q.y - p.y;
+        let value = dx * (y - p.y) - dy * (x - p.x);
+
+        if value == 0.0 {
+            let top_left = (dy == 0.0 && dx > 0.0) || dy < 0.0;
+            top_left.then_some(0.0)
+        } else {
+            (value > 0.0).then_some(value)
+        }
+    };
+
+    for y in min_y..max_y {
+        let py = y as f32 + 0.5;
+        for x in min_x..max_x {
+            let px = x as f32 + 0.5;
+
+            let (Some(wa), Some(wb), Some(wc)) = (edge(b, c, px, py), edge(c, a, px, py), edge(a, b, px, py)) else {
+                continue;
+            };
+            let (la, lb, lc) = (wa / area, wb / area, wc / area);
+
+            let depth = la * a.z + lb * b.z + lc * c.z;
+            let inv_w = la * a.inv_w + lb * b.inv_w + lc * c.inv_w;
+            let (pa, pb, pc) = (la * a.inv_w / inv_w, lb * b.inv_w / inv_w, lc * c.inv_w / inv_w);
+            let interpolate = |f: fn(&ClipVertex) -> Vector4| {
+                let (va, vb, vc) = (f(&a.vertex), f(&b.vertex), f(&c.vertex));
+                core::array::from_fn::<f32, 4, _>(|i| pa * va[i] + pb * vb[i] + pc * vc[i])
+            };
+
+            let mut color = interpolate(|v| v.color);
+            for (unit, texture) in state.textures.iter().enumerate() {
+                let coordinate = if unit == 0 {
+                    interpolate(|v| v.tex_coords[0])
+                } else {
+                    interpolate(|v| v.tex_coords[1])
+                };
+                let q = if coordinate[3] != 0.0 { coordinate[3] } else { 1.0 };
+                color = texture.apply(color, coordinate[0] / q, coordinate[1] / q);
+            }
+
+            let eye_depth = pa * a.vertex.eye_depth + pb * b.vertex.eye_depth + pc * c.vertex.eye_depth;
+
+            write_fragment(target, x, y, depth, color, eye_depth, state);
+        }
+    }
+}
+
+/// Runs the per-fragment pipeline: fog, alpha test, depth test and blending.
+pub fn write_fragment(target: &mut RenderTarget<'_>, x: i32, y: i32, depth: f32, color: Vector4, eye_depth: f32, state: &FragmentState<'_>) {
+    let mode = state.compositing_mode;
+    let mut color = color.map(|x| x.clamp(0.0, 1.0));
+
+    if let Some(fog) = state.fog {
+        let factor = fog.factor(eye_depth);
+        for (channel, fog_channel) in color.iter_mut().zip(fog.color).take(3) {
+            *channel = fog_channel + (*channel - fog_channel) * factor;
+        }
+    }
+
+    if color[3] < mode.alpha_threshold {
+        return;
+    }
+
+    let index = (y as u32 * target.width + x as u32) as usize;
+    if mode.depth_test {
+        if depth > target.depth[index] {
+            return;
+        }
+        if mode.depth_write {
+            target.depth[index] = depth;
+        }
+    }
+
+    if !mode.color_write {
+        return;
+    }
+
+    let result = match mode.blending {
+        Blending::Replace => color,
+        blending => {
+            let destination = target.read(x, y);
+            let alpha = color[3];
+
+            core::array::from_fn(|i| {
+                let value = match blending {
+                    Blending::Alpha => color[i] * alpha + destination[i] * (1.0 - alpha),
+                    Blending::AlphaAdd => destination[i] + color[i] * alpha,
+                    Blending::Modulate => color[i] * destination[i],
+                    Blending::ModulateX2 => 2.0 * color[i] * destination[i],
+                    Blending::Replace => unreachable!(),
+                };
+                value.min(1.0)
+            })
+        }
+    };
+
+    target.write(x, y, result);
+}
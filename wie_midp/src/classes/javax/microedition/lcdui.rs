@@ -14,9 +14,11 @@ mod image;
 mod item;
 mod screen;
 mod text_box;
+mod text_field;
+mod text_input;
 
 pub use {
     alert::Alert, alert_type::AlertType, canvas::Canvas, choice_group::ChoiceGroup, command::Command, command_listener::CommandListener,
     display::Display, displayable::Displayable, font::Font, form::Form, graphics::Graphics, image::Image, item::Item, screen::Screen,
-    text_box::TextBox,
+    text_box::TextBox, text_field::TextField,
 };
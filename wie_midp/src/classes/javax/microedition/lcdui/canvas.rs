@@ -1,14 +1,14 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
 use crate::classes::{
     javax::microedition::lcdui::{Display, Graphics},
-    net::wie::{KeyboardEventType, MIDPKeyCode},
+    net::wie::{KeyboardEventType, MIDPKeyCode, TextInputListener},
 };
 
 // abstract class javax.microedition.lcdui.Canvas
@@ -44,8 +44,19 @@ impl Canvas {
                     Self::handle_paint_event,
                     MethodAccessFlags::empty(),
                 ),
+                JavaMethodProto::new("keyTyped", "(I)V", Self::key_typed, MethodAccessFlags::empty()),
+                JavaMethodProto::new(
+                    "setTextInputListener",
+                    "(Lnet/wie/TextInputListener;)V",
+                    Self::set_text_input_listener,
+                    MethodAccessFlags::empty(),
+                ),
             ],
-            fields: vec![],
+            fields: vec![JavaFieldProto::new(
+                "textInputListener",
+                "Lnet/wie/TextInputListener;",
+                FieldAccessFlags::PRIVATE,
+            )],
             access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
         }
     }
@@ -136,6 +147,32 @@ impl Canvas {
         Ok(())
     }
 
+    // host keyboard text goes to the focused text widget on this canvas, if any
+    async fn key_typed(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, chr: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::keyTyped({this:?}, {chr})");
+
+        let listener: ClassInstanceRef<TextInputListener> = jvm.get_field(&this, "textInputListener", "Lnet/wie/TextInputListener;").await?;
+        if !listener.is_null() {
+            let _: () = jvm
+                .invoke_virtual(&listener, "net/wie/TextInputListener", "keyTyped", "(I)V", (chr,))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn set_text_input_listener(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        listener: ClassInstanceRef<TextInputListener>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::setTextInputListener({this:?}, {listener:?})");
+
+        jvm.put_field(&mut this, "textInputListener", "Lnet/wie/TextInputListener;", listener)
+            .await
+    }
+
     async fn set_full_screen_mode(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: bool) -> JvmResult<()> {
         tracing::debug!("javax.microedition.lcdui.Canvas::setFullScreenMode({this:?}, {mode})");
 
@@ -184,7 +221,10 @@ impl Canvas {
                 jvm.invoke_virtual(&this, "javax/microedition/lcdui/Canvas", "keyRepeated", "(I)V", (code,))
                     .await
             }
-            _ => unimplemented!(),
+            KeyboardEventType::KeyTyped => {
+                jvm.invoke_virtual(&this, "javax/microedition/lcdui/Canvas", "keyTyped", "(I)V", (code,))
+                    .await
+            }
         }?;
 
         Ok(())
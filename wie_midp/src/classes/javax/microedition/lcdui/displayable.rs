@@ -163,4 +163,21 @@ impl Displayable {
 
         Ok(())
     }
+
+    // requests a repaint of the whole screen if shown
+    pub(crate) async fn repaint<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<()> {
+        let display: ClassInstanceRef<Display> = jvm.get_field(this, "currentDisplay", "Ljavax/microedition/lcdui/Display;").await?;
+        if display.is_null() {
+            return Ok(());
+        }
+
+        let width: i32 = jvm
+            .invoke_virtual(&display, "javax/microedition/lcdui/Display", "getWidth", "()I", ())
+            .await?;
+        let height: i32 = jvm
+            .invoke_virtual(&display, "javax/microedition/lcdui/Display", "getHeight", "()I", ())
+            .await?;
+        jvm.invoke_virtual(&display, "javax/microedition/lcdui/Display", "repaint", "(IIII)V", (0, 0, width, height))
+            .await
+    }
 }
@@ -123,10 +123,10 @@ impl Font {
         Ok(())
     }
 
-    async fn get_height(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<i32> {
-        tracing::warn!("stub javax.microedition.lcdui.Font::getHeight");
+    async fn get_height(_: &Jvm, context: &mut WieJvmContext) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::getHeight");
 
-        Ok(12) // TODO: hardcoded
+        Ok(context.system().device_profile().fonts.medium as _)
     }
 
     async fn get_default_font(jvm: &Jvm, _: &mut WieJvmContext) -> JvmResult<ClassInstanceRef<Self>> {
@@ -1,18 +1,21 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
-use java_runtime::classes::java::lang::String;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::{lang::String, util::Vector};
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
-use crate::classes::javax::microedition::lcdui::Item;
+use crate::classes::javax::microedition::lcdui::{Displayable, Graphics, Item};
 
 // class javax.microedition.lcdui.Form
 pub struct Form;
 
 impl Form {
+    const PADDING: i32 = 2;
+    const BACKGROUND_COLOR: i32 = 0xffffff;
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "javax/microedition/lcdui/Form",
@@ -22,24 +25,47 @@ impl Form {
                 JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("append", "(Ljavax/microedition/lcdui/Item;)I", Self::append, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("append", "(Ljava/lang/String;)I", Self::append_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("get", "(I)Ljavax/microedition/lcdui/Item;", Self::get, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("size", "()I", Self::size, MethodAccessFlags::PUBLIC),
+                // wie private methods...
+                JavaMethodProto::new("handleKeyEvent", "(II)V", Self::handle_key_event, MethodAccessFlags::empty()),
+                JavaMethodProto::new(
+                    "handlePaintEvent",
+                    "(Ljavax/microedition/lcdui/Graphics;)V",
+                    Self::handle_paint_event,
+                    MethodAccessFlags::empty(),
+                ),
             ],
-            fields: vec![],
+            fields: vec![JavaFieldProto::new("items", "Ljava/util/Vector;", FieldAccessFlags::PRIVATE)],
             access_flags: ClassAccessFlags::PUBLIC,
         }
     }
 
-    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, title: ClassInstanceRef<String>) -> JvmResult<()> {
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, title: ClassInstanceRef<String>) -> JvmResult<()> {
         tracing::debug!("javax.microedition.lcdui.Form::<init>({this:?}, {title:?})");
 
         let _: () = jvm.invoke_special(&this, "javax/microedition/lcdui/Screen", "<init>", "()V", ()).await?;
 
+        let items = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(&mut this, "items", "Ljava/util/Vector;", items).await?;
+
         Ok(())
     }
 
-    async fn append(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, item: ClassInstanceRef<Item>) -> JvmResult<i32> {
-        tracing::warn!("stub javax.microedition.lcdui.Form::append({this:?}, {item:?})");
+    async fn append(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, item: ClassInstanceRef<Item>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Form::append({this:?}, {item:?})");
 
-        Ok(0)
+        if item.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "item is null").await);
+        }
+
+        let items: ClassInstanceRef<Vector> = jvm.get_field(&this, "items", "Ljava/util/Vector;").await?;
+        let _: () = jvm
+            .invoke_virtual(&items, "java/util/Vector", "addElement", "(Ljava/lang/Object;)V", (item,))
+            .await?;
+        let size: i32 = jvm.invoke_virtual(&items, "java/util/Vector", "size", "()I", ()).await?;
+
+        Ok(size - 1)
     }
 
     async fn append_string(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, str: ClassInstanceRef<String>) -> JvmResult<i32> {
@@ -47,4 +73,161 @@ impl Form {
 
         Ok(0)
     }
+
+    async fn get(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, index: i32) -> JvmResult<ClassInstanceRef<Item>> {
+        tracing::debug!("javax.microedition.lcdui.Form::get({this:?}, {index})");
+
+        let items: ClassInstanceRef<Vector> = jvm.get_field(&this, "items", "Ljava/util/Vector;").await?;
+        jvm.invoke_virtual(&items, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+            .await
+    }
+
+    async fn size(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Form::size({this:?})");
+
+        let items: ClassInstanceRef<Vector> = jvm.get_field(&this, "items", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&items, "java/util/Vector", "size", "()I", ()).await
+    }
+
+    // offers the key to each item in order until one uses it
+    async fn handle_key_event(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, event_type: i32, code: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Form::handleKeyEvent({this:?}, {event_type}, {code})");
+
+        let size = Self::size(jvm, context, this.clone()).await?;
+        for index in 0..size {
+            let item = Self::get(jvm, context, this.clone(), index).await?;
+            let handled: bool = jvm
+                .invoke_virtual(&item, "javax/microedition/lcdui/Item", "handleKeyEvent", "(II)Z", (event_type, code))
+                .await?;
+            if handled {
+                return Displayable::repaint(jvm, &this).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    // stacks the items from the top of the screen
+    async fn handle_paint_event(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Form::handlePaintEvent({this:?}, {graphics:?})");
+
+        let width: i32 = jvm
+            .invoke_virtual(&this, "javax/microedition/lcdui/Displayable", "getWidth", "()I", ())
+            .await?;
+        let height: i32 = jvm
+            .invoke_virtual(&this, "javax/microedition/lcdui/Displayable", "getHeight", "()I", ())
+            .await?;
+
+        let _: () = jvm
+            .invoke_virtual(
+                &graphics,
+                "javax/microedition/lcdui/Graphics",
+                "setColor",
+                "(I)V",
+                (Self::BACKGROUND_COLOR,),
+            )
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                &graphics,
+                "javax/microedition/lcdui/Graphics",
+                "fillRect",
+                "(IIII)V",
+                (0, 0, width, height),
+            )
+            .await?;
+
+        let mut y = Self::PADDING;
+        let size = Self::size(jvm, context, this.clone()).await?;
+        for index in 0..size {
+            let item = Self::get(jvm, context, this.clone(), index).await?;
+            let item_height: i32 = jvm
+                .invoke_virtual(
+                    &item,
+                    "javax/microedition/lcdui/Item",
+                    "paintItem",
+                    "(Ljavax/microedition/lcdui/Graphics;II)I",
+                    (graphics.clone(), Self::PADDING, y),
+                )
+                .await?;
+            y += item_height + Self::PADDING;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use java_runtime::classes::java::lang::String;
+    use jvm::{ClassInstanceRef, runtime::JavaLangString};
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{
+        classes::{
+            javax::microedition::lcdui::{Form, Item},
+            net::wie::{KeyboardEventType, MIDPKeyCode},
+        },
+        get_protos,
+    };
+
+    #[test]
+    fn keys_go_to_text_field_items() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let title = JavaLangString::from_rust_string(&jvm, "form").await?;
+            let form: ClassInstanceRef<Form> = jvm
+                .new_class("javax/microedition/lcdui/Form", "(Ljava/lang/String;)V", (title,))
+                .await?
+                .into();
+            let label = JavaLangString::from_rust_string(&jvm, "number").await?;
+            let text = JavaLangString::from_rust_string(&jvm, "").await?;
+            let field: ClassInstanceRef<Item> = jvm
+                .new_class(
+                    "javax/microedition/lcdui/TextField",
+                    "(Ljava/lang/String;Ljava/lang/String;II)V",
+                    (label, text, 4, 2),
+                )
+                .await?
+                .into();
+            let index: i32 = jvm
+                .invoke_virtual(
+                    &form,
+                    "javax/microedition/lcdui/Form",
+                    "append",
+                    "(Ljavax/microedition/lcdui/Item;)I",
+                    (field.clone(),),
+                )
+                .await?;
+            assert_eq!(index, 0);
+
+            // numeric constraint enters digits as is, clear removes the last one
+            for key in [MIDPKeyCode::KEY_NUM4, MIDPKeyCode::KEY_NUM2, MIDPKeyCode::KEY_STAR, MIDPKeyCode::CLEAR] {
+                let _: () = jvm
+                    .invoke_virtual(
+                        &form,
+                        "javax/microedition/lcdui/Displayable",
+                        "handleKeyEvent",
+                        "(II)V",
+                        (KeyboardEventType::KeyPressed as i32, key as i32),
+                    )
+                    .await?;
+            }
+
+            let string: ClassInstanceRef<String> = jvm
+                .invoke_virtual(&field, "javax/microedition/lcdui/TextField", "getString", "()Ljava/lang/String;", ())
+                .await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &string).await?, "42");
+
+            Ok(())
+        })
+    }
 }
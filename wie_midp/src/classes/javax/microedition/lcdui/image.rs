@@ -146,6 +146,22 @@ impl Image {
     ) -> JvmResult<ClassInstanceRef<Image>> {
         tracing::debug!("javax.microedition.lcdui.Image::createImage({data:?}, {image_offset}, {image_length})");
 
+        if data.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
+        }
+
+        let array_length = jvm.array_length(&data).await?;
+        if image_offset < 0
+            || image_length < 0
+            || (image_offset as usize)
+                .checked_add(image_length as usize)
+                .is_none_or(|end| end > array_length)
+        {
+            return Err(jvm
+                .exception("java/lang/ArrayIndexOutOfBoundsException", "invalid offset or length")
+                .await);
+        }
+
         let mut image_data = vec![0; image_length as usize];
         jvm.array_raw_buffer(&data).await?.read(image_offset as _, &mut image_data)?;
 
@@ -226,6 +242,10 @@ impl Image {
         })
     }
 
+    pub async fn from_image(jvm: &Jvm, image: Box<dyn BackendImage>) -> JvmResult<ClassInstanceRef<Image>> {
+        Self::create_image_instance(jvm, image.width(), image.height(), &image.raw(), image.bytes_per_pixel()).await
+    }
+
     async fn create_image_instance(jvm: &Jvm, width: u32, height: u32, data: &[u8], bytes_per_pixel: u32) -> JvmResult<ClassInstanceRef<Image>> {
         let mut data_array = jvm.instantiate_array("B", data.len() as _).await?;
         jvm.array_raw_buffer_mut(&mut data_array).await?.write(0, data)?;
@@ -359,3 +379,40 @@ where
         self.raw_buffer.write(offset as _, bytemuck::bytes_of(&raw)).unwrap();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use jvm::{Array, ClassInstanceRef, JavaError, Result as JvmResult};
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::get_protos;
+
+    use super::Image;
+
+    #[test]
+    fn create_image_rejects_invalid_ranges() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let data: ClassInstanceRef<Array<i8>> = jvm.instantiate_array("B", 4).await?.into();
+
+            for (offset, length) in [(-1, 2), (0, -1), (2, 3), (i32::MAX, i32::MAX)] {
+                let result: JvmResult<ClassInstanceRef<Image>> = jvm
+                    .invoke_static(
+                        "javax/microedition/lcdui/Image",
+                        "createImage",
+                        "([BII)Ljavax/microedition/lcdui/Image;",
+                        (data.clone(), offset, length),
+                    )
+                    .await;
+                let Err(JavaError::JavaException(exception)) = result else {
+                    panic!("createImage({offset}, {length}) succeeded");
+                };
+                assert!(jvm.is_instance(&*exception, "java/lang/ArrayIndexOutOfBoundsException"));
+            }
+
+            Ok(())
+        })
+    }
+}
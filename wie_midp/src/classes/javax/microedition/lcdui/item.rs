@@ -1,11 +1,14 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::lang::String;
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::javax::microedition::lcdui::Graphics;
+
 // class javax.microedition.lcdui.Item
 pub struct Item;
 
@@ -15,8 +18,20 @@ impl Item {
             name: "javax/microedition/lcdui/Item",
             parent_class: Some("java/lang/Object"),
             interfaces: vec![],
-            methods: vec![JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PROTECTED)],
-            fields: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("getLabel", "()Ljava/lang/String;", Self::get_label, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setLabel", "(Ljava/lang/String;)V", Self::set_label, MethodAccessFlags::PUBLIC),
+                // wie private methods...
+                JavaMethodProto::new("handleKeyEvent", "(II)Z", Self::handle_key_event, MethodAccessFlags::empty()),
+                JavaMethodProto::new(
+                    "paintItem",
+                    "(Ljavax/microedition/lcdui/Graphics;II)I",
+                    Self::paint_item,
+                    MethodAccessFlags::empty(),
+                ),
+            ],
+            fields: vec![JavaFieldProto::new("label", "Ljava/lang/String;", FieldAccessFlags::PRIVATE)],
             access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
         }
     }
@@ -28,4 +43,37 @@ impl Item {
 
         Ok(())
     }
+
+    async fn get_label(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("javax.microedition.lcdui.Item::getLabel({this:?})");
+
+        jvm.get_field(&this, "label", "Ljava/lang/String;").await
+    }
+
+    async fn set_label(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, label: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Item::setLabel({this:?}, {label:?})");
+
+        jvm.put_field(&mut this, "label", "Ljava/lang/String;", label).await
+    }
+
+    // returns whether the item used the key, called by the owning form
+    async fn handle_key_event(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, event_type: i32, code: i32) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Item::handleKeyEvent({this:?}, {event_type}, {code})");
+
+        Ok(false)
+    }
+
+    // paints the item at the given position, returning the height it used
+    async fn paint_item(
+        _jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        graphics: ClassInstanceRef<Graphics>,
+        x: i32,
+        y: i32,
+    ) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Item::paintItem({this:?}, {graphics:?}, {x}, {y})");
+
+        Ok(0)
+    }
 }
@@ -1,36 +1,64 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::String;
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::javax::microedition::lcdui::{Displayable, Graphics, text_input::TextInput};
+
 // class javax.microedition.lcdui.TextBox
 pub struct TextBox;
 
 impl TextBox {
+    const PADDING: i32 = 2;
+    const BACKGROUND_COLOR: i32 = 0xffffff;
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "javax/microedition/lcdui/TextBox",
             parent_class: Some("javax/microedition/lcdui/Screen"),
             interfaces: vec![],
-            methods: vec![JavaMethodProto::new(
-                "<init>",
-                "(Ljava/lang/String;Ljava/lang/String;II)V",
-                Self::init,
-                MethodAccessFlags::PUBLIC,
-            )],
-            fields: vec![],
+            methods: vec![
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/lang/String;Ljava/lang/String;II)V",
+                    Self::init,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getString", "()Ljava/lang/String;", Self::get_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setString", "(Ljava/lang/String;)V", Self::set_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMaxSize", "()I", Self::get_max_size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setMaxSize", "(I)I", Self::set_max_size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("size", "()I", Self::size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCaretPosition", "()I", Self::get_caret_position, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getConstraints", "()I", Self::get_constraints, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setConstraints", "(I)V", Self::set_constraints, MethodAccessFlags::PUBLIC),
+                // wie private methods...
+                JavaMethodProto::new("handleKeyEvent", "(II)V", Self::handle_key_event, MethodAccessFlags::empty()),
+                JavaMethodProto::new(
+                    "handlePaintEvent",
+                    "(Ljavax/microedition/lcdui/Graphics;)V",
+                    Self::handle_paint_event,
+                    MethodAccessFlags::empty(),
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("text", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("maxSize", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("constraints", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("imState", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
+            ],
             access_flags: ClassAccessFlags::PUBLIC,
         }
     }
 
     async fn init(
         jvm: &Jvm,
-        _context: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
         title: ClassInstanceRef<String>,
         text: ClassInstanceRef<String>,
         max_size: i32,
@@ -40,6 +68,183 @@ impl TextBox {
 
         let _: () = jvm.invoke_special(&this, "javax/microedition/lcdui/Screen", "<init>", "()V", ()).await?;
 
+        TextInput::init(jvm, context, &mut this, text, max_size, constraints).await
+    }
+
+    async fn get_string(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getString({this:?})");
+
+        TextInput::get_string(jvm, &this).await
+    }
+
+    async fn set_string(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, text: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::setString({this:?}, {text:?})");
+
+        TextInput::set_string(jvm, &mut this, text).await?;
+        Displayable::repaint(jvm, &this).await
+    }
+
+    async fn get_max_size(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getMaxSize({this:?})");
+
+        jvm.get_field(&this, "maxSize", "I").await
+    }
+
+    async fn set_max_size(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, max_size: i32) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::setMaxSize({this:?}, {max_size})");
+
+        TextInput::set_max_size(jvm, &mut this, max_size).await
+    }
+
+    async fn size(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::size({this:?})");
+
+        TextInput::size(jvm, &this).await
+    }
+
+    // text is only appended, so the caret is always at the end
+    async fn get_caret_position(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getCaretPosition({this:?})");
+
+        TextInput::size(jvm, &this).await
+    }
+
+    async fn get_constraints(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getConstraints({this:?})");
+
+        jvm.get_field(&this, "constraints", "I").await
+    }
+
+    async fn set_constraints(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, constraints: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::setConstraints({this:?}, {constraints})");
+
+        TextInput::set_constraints(jvm, &mut this, constraints).await
+    }
+
+    async fn handle_key_event(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, event_type: i32, code: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::handleKeyEvent({this:?}, {event_type}, {code})");
+
+        TextInput::handle_key_event(jvm, context, &mut this, event_type, code).await?;
+        Displayable::repaint(jvm, &this).await
+    }
+
+    async fn handle_paint_event(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::handlePaintEvent({this:?}, {graphics:?})");
+
+        let width: i32 = jvm
+            .invoke_virtual(&this, "javax/microedition/lcdui/Displayable", "getWidth", "()I", ())
+            .await?;
+        let height: i32 = jvm
+            .invoke_virtual(&this, "javax/microedition/lcdui/Displayable", "getHeight", "()I", ())
+            .await?;
+
+        let _: () = jvm
+            .invoke_virtual(
+                &graphics,
+                "javax/microedition/lcdui/Graphics",
+                "setColor",
+                "(I)V",
+                (Self::BACKGROUND_COLOR,),
+            )
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                &graphics,
+                "javax/microedition/lcdui/Graphics",
+                "fillRect",
+                "(IIII)V",
+                (0, 0, width, height),
+            )
+            .await?;
+
+        TextInput::paint(jvm, &this, &graphics, Self::PADDING, Self::PADDING).await?;
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use java_runtime::classes::java::lang::String;
+    use jvm::{ClassInstanceRef, Result as JvmResult, runtime::JavaLangString};
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{
+        classes::net::wie::{KeyboardEventType, MIDPKeyCode},
+        get_protos,
+    };
+
+    use super::TextBox;
+
+    #[test]
+    fn keypad_entry_respects_max_size() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let title = JavaLangString::from_rust_string(&jvm, "name").await?;
+            let text = JavaLangString::from_rust_string(&jvm, "").await?;
+            let text_box: ClassInstanceRef<TextBox> = jvm
+                .new_class(
+                    "javax/microedition/lcdui/TextBox",
+                    "(Ljava/lang/String;Ljava/lang/String;II)V",
+                    (title, text, 2, 0),
+                )
+                .await?
+                .into();
+
+            // 천지인: ㅎ(88) ㅏ(12) ㄴ(5) ㄱ(4) ㅡ(3) ㄹ(55)
+            let keys = [
+                MIDPKeyCode::KEY_NUM8,
+                MIDPKeyCode::KEY_NUM8,
+                MIDPKeyCode::KEY_NUM1,
+                MIDPKeyCode::KEY_NUM2,
+                MIDPKeyCode::KEY_NUM5,
+                MIDPKeyCode::KEY_NUM4,
+                MIDPKeyCode::KEY_NUM3,
+                MIDPKeyCode::KEY_NUM5,
+                MIDPKeyCode::KEY_NUM5,
+            ];
+            for key in keys {
+                let _: () = jvm
+                    .invoke_virtual(
+                        &text_box,
+                        "javax/microedition/lcdui/Displayable",
+                        "handleKeyEvent",
+                        "(II)V",
+                        (KeyboardEventType::KeyPressed as i32, key as i32),
+                    )
+                    .await?;
+            }
+
+            let string: ClassInstanceRef<String> = jvm
+                .invoke_virtual(&text_box, "javax/microedition/lcdui/TextBox", "getString", "()Ljava/lang/String;", ())
+                .await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &string).await?, "한글");
+            assert_eq!(
+                jvm.invoke_virtual::<_, i32>(&text_box, "javax/microedition/lcdui/TextBox", "size", "()I", ())
+                    .await?,
+                2
+            );
+
+            let long: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "abc").await?.into();
+            let result: JvmResult<()> = jvm
+                .invoke_virtual(
+                    &text_box,
+                    "javax/microedition/lcdui/TextBox",
+                    "setString",
+                    "(Ljava/lang/String;)V",
+                    (long,),
+                )
+                .await;
+            assert!(result.is_err(), "text longer than maxSize should be rejected");
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,178 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::lang::String;
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::javax::microedition::lcdui::{Font, Graphics, text_input::TextInput};
+
+// class javax.microedition.lcdui.TextField
+pub struct TextField;
+
+impl TextField {
+    const ANCHOR_TOP_LEFT: i32 = 20;
+
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/lcdui/TextField",
+            parent_class: Some("javax/microedition/lcdui/Item"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/lang/String;Ljava/lang/String;II)V",
+                    Self::init,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getString", "()Ljava/lang/String;", Self::get_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setString", "(Ljava/lang/String;)V", Self::set_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMaxSize", "()I", Self::get_max_size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setMaxSize", "(I)I", Self::set_max_size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("size", "()I", Self::size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCaretPosition", "()I", Self::get_caret_position, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getConstraints", "()I", Self::get_constraints, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setConstraints", "(I)V", Self::set_constraints, MethodAccessFlags::PUBLIC),
+                // wie private methods...
+                JavaMethodProto::new("handleKeyEvent", "(II)Z", Self::handle_key_event, MethodAccessFlags::empty()),
+                JavaMethodProto::new(
+                    "paintItem",
+                    "(Ljavax/microedition/lcdui/Graphics;II)I",
+                    Self::paint_item,
+                    MethodAccessFlags::empty(),
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("text", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("maxSize", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("constraints", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("imState", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        label: ClassInstanceRef<String>,
+        text: ClassInstanceRef<String>,
+        max_size: i32,
+        constraints: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextField::<init>({this:?}, {label:?}, {text:?}, {max_size}, {constraints})");
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/lcdui/Item", "<init>", "()V", ()).await?;
+        let _: () = jvm
+            .invoke_virtual(&this, "javax/microedition/lcdui/Item", "setLabel", "(Ljava/lang/String;)V", (label,))
+            .await?;
+
+        TextInput::init(jvm, context, &mut this, text, max_size, constraints).await
+    }
+
+    async fn get_string(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("javax.microedition.lcdui.TextField::getString({this:?})");
+
+        TextInput::get_string(jvm, &this).await
+    }
+
+    async fn set_string(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, text: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextField::setString({this:?}, {text:?})");
+
+        TextInput::set_string(jvm, &mut this, text).await
+    }
+
+    async fn get_max_size(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextField::getMaxSize({this:?})");
+
+        jvm.get_field(&this, "maxSize", "I").await
+    }
+
+    async fn set_max_size(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, max_size: i32) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextField::setMaxSize({this:?}, {max_size})");
+
+        TextInput::set_max_size(jvm, &mut this, max_size).await
+    }
+
+    async fn size(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextField::size({this:?})");
+
+        TextInput::size(jvm, &this).await
+    }
+
+    // text is only appended, so the caret is always at the end
+    async fn get_caret_position(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextField::getCaretPosition({this:?})");
+
+        TextInput::size(jvm, &this).await
+    }
+
+    async fn get_constraints(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextField::getConstraints({this:?})");
+
+        jvm.get_field(&this, "constraints", "I").await
+    }
+
+    async fn set_constraints(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, constraints: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextField::setConstraints({this:?}, {constraints})");
+
+        TextInput::set_constraints(jvm, &mut this, constraints).await
+    }
+
+    async fn handle_key_event(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        event_type: i32,
+        code: i32,
+    ) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.TextField::handleKeyEvent({this:?}, {event_type}, {code})");
+
+        TextInput::handle_key_event(jvm, context, &mut this, event_type, code).await
+    }
+
+    // draws the label on its own line above the text
+    async fn paint_item(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        graphics: ClassInstanceRef<Graphics>,
+        x: i32,
+        y: i32,
+    ) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextField::paintItem({this:?}, {graphics:?}, {x}, {y})");
+
+        let label: ClassInstanceRef<String> = jvm
+            .invoke_virtual(&this, "javax/microedition/lcdui/Item", "getLabel", "()Ljava/lang/String;", ())
+            .await?;
+
+        let mut height = 0;
+        if !label.is_null() {
+            let font: ClassInstanceRef<Font> = jvm
+                .invoke_virtual(
+                    &graphics,
+                    "javax/microedition/lcdui/Graphics",
+                    "getFont",
+                    "()Ljavax/microedition/lcdui/Font;",
+                    (),
+                )
+                .await?;
+            height = jvm.invoke_virtual(&font, "javax/microedition/lcdui/Font", "getHeight", "()I", ()).await?;
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics,
+                    "javax/microedition/lcdui/Graphics",
+                    "drawString",
+                    "(Ljava/lang/String;III)V",
+                    (label, x, y, Self::ANCHOR_TOP_LEFT),
+                )
+                .await?;
+        }
+
+        Ok(height + TextInput::paint(jvm, &this, &graphics, x, y + height).await?)
+    }
+}
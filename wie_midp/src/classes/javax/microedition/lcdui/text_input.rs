@@ -0,0 +1,258 @@
+use alloc::{format, string::String as RustString};
+
+use java_runtime::classes::java::lang::String;
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
+
+use wie_backend::{InputMethod, InputMode, KeyPhase};
+use wie_jvm_support::WieJvmContext;
+
+use crate::classes::{
+    javax::microedition::lcdui::{Font, Graphics},
+    net::wie::{KeyboardEventType, MIDPKeyCode},
+};
+
+/// Text editing shared by `TextBox` and `TextField`, which both keep `text`, `maxSize`,
+/// `constraints` and `imState` fields. Text is only ever appended, so the caret is at its end.
+pub(crate) struct TextInput;
+
+impl TextInput {
+    // constraints that only accept digits
+    const CONSTRAINT_MASK: i32 = 0xffff;
+    const CONSTRAINT_NUMERIC: i32 = 2;
+    const CONSTRAINT_PHONENUMBER: i32 = 3;
+    const CONSTRAINT_DECIMAL: i32 = 5;
+
+    const TEXT_COLOR: i32 = 0x000000;
+    const ANCHOR_TOP_LEFT: i32 = 20;
+
+    pub async fn init<T>(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: &mut ClassInstanceRef<T>,
+        text: ClassInstanceRef<String>,
+        max_size: i32,
+        constraints: i32,
+    ) -> JvmResult<()> {
+        if max_size <= 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "maxSize must be positive").await);
+        }
+
+        jvm.put_field(this, "maxSize", "I", max_size).await?;
+        jvm.put_field(this, "constraints", "I", constraints).await?;
+        let input_method = InputMethod::new(Self::mode(constraints), context.system().device_profile().keypad);
+        Self::store_input_method(jvm, this, &input_method).await?;
+
+        Self::set_string(jvm, this, text).await
+    }
+
+    pub async fn get_string<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<ClassInstanceRef<String>> {
+        let text = Self::text(jvm, this).await?;
+        let composing = Self::input_method(jvm, this).await?.composing();
+
+        Ok(JavaLangString::from_rust_string(jvm, &format!("{text}{composing}")).await?.into())
+    }
+
+    pub async fn set_string<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, text: ClassInstanceRef<String>) -> JvmResult<()> {
+        let text = if text.is_null() {
+            RustString::new()
+        } else {
+            JavaLangString::to_rust_string(jvm, &text).await?
+        };
+
+        let max_size: i32 = jvm.get_field(this, "maxSize", "I").await?;
+        if text.chars().count() > max_size as usize {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "text is longer than maxSize").await);
+        }
+
+        // text still being composed is dropped along with the old text
+        let mut input_method = Self::input_method(jvm, this).await?;
+        input_method.commit();
+        Self::store_input_method(jvm, this, &input_method).await?;
+
+        Self::set_text(jvm, this, &text).await
+    }
+
+    pub async fn set_max_size<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, max_size: i32) -> JvmResult<i32> {
+        if max_size <= 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "maxSize must be positive").await);
+        }
+
+        let text = Self::text(jvm, this).await?;
+        if text.chars().count() > max_size as usize {
+            let truncated: RustString = text.chars().take(max_size as usize).collect();
+            Self::set_text(jvm, this, &truncated).await?;
+        }
+        jvm.put_field(this, "maxSize", "I", max_size).await?;
+
+        Ok(max_size)
+    }
+
+    pub async fn size<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<i32> {
+        let text = Self::text(jvm, this).await?;
+        let composing = Self::input_method(jvm, this).await?.composing();
+
+        Ok((text.chars().count() + composing.chars().count()) as i32)
+    }
+
+    pub async fn set_constraints<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, constraints: i32) -> JvmResult<()> {
+        let mut input_method = Self::input_method(jvm, this).await?;
+        let committed = input_method.set_mode(Self::mode(constraints));
+        Self::store_input_method(jvm, this, &input_method).await?;
+        Self::append(jvm, this, &committed).await?;
+
+        jvm.put_field(this, "constraints", "I", constraints).await
+    }
+
+    // returns whether the key was used for editing. keypad keys go through the input method, clear deletes the last character
+    // and characters typed on the host keyboard are appended as is
+    pub async fn handle_key_event<T>(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: &mut ClassInstanceRef<T>,
+        event_type: i32,
+        code: i32,
+    ) -> JvmResult<bool> {
+        if event_type == KeyboardEventType::KeyTyped as i32 {
+            return Self::type_char(jvm, this, code).await;
+        }
+
+        let phase = match KeyboardEventType::from_raw(event_type) {
+            Some(KeyboardEventType::KeyPressed) => KeyPhase::Pressed,
+            Some(KeyboardEventType::KeyRepeated) => KeyPhase::Repeated,
+            Some(KeyboardEventType::KeyReleased) => KeyPhase::Released,
+            _ => return Ok(false),
+        };
+        let Some(key) = MIDPKeyCode::from_raw(code) else {
+            return Ok(false);
+        };
+        let now = context.system().platform().now();
+
+        let mut input_method = Self::input_method(jvm, this).await?;
+        let mut text = Self::text(jvm, this).await?;
+        let max_size: i32 = jvm.get_field(this, "maxSize", "I").await?;
+        let room = (max_size as usize).saturating_sub(text.chars().count());
+
+        let consumed = match input_method.key_event(phase, key.to_key_code(), now, room) {
+            Some(result) => {
+                text.push_str(&result.committed);
+                result.consumed || (phase != KeyPhase::Released && code == MIDPKeyCode::CLEAR as i32 && text.pop().is_some())
+            }
+            None => false,
+        };
+
+        Self::store_input_method(jvm, this, &input_method).await?;
+        Self::set_text(jvm, this, &text).await?;
+
+        Ok(consumed)
+    }
+
+    // finishes the composition and appends the character if it fits
+    async fn type_char<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, chr: i32) -> JvmResult<bool> {
+        let Some(chr) = char::from_u32(chr as u32) else {
+            return Ok(false);
+        };
+
+        let mut input_method = Self::input_method(jvm, this).await?;
+        let mut text = Self::text(jvm, this).await?;
+        text.push_str(&input_method.commit());
+        Self::store_input_method(jvm, this, &input_method).await?;
+
+        let max_size: i32 = jvm.get_field(this, "maxSize", "I").await?;
+        if text.chars().count() < max_size as usize {
+            text.push(chr);
+        }
+        Self::set_text(jvm, this, &text).await?;
+
+        Ok(true)
+    }
+
+    // draws the text with the composition and a caret after it, returning the height used
+    pub async fn paint<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, graphics: &ClassInstanceRef<Graphics>, x: i32, y: i32) -> JvmResult<i32> {
+        let string = Self::get_string(jvm, this).await?;
+
+        let font: ClassInstanceRef<Font> = jvm
+            .invoke_virtual(
+                graphics,
+                "javax/microedition/lcdui/Graphics",
+                "getFont",
+                "()Ljavax/microedition/lcdui/Font;",
+                (),
+            )
+            .await?;
+        let font_height: i32 = jvm.invoke_virtual(&font, "javax/microedition/lcdui/Font", "getHeight", "()I", ()).await?;
+        let width: i32 = jvm
+            .invoke_virtual(
+                &font,
+                "javax/microedition/lcdui/Font",
+                "stringWidth",
+                "(Ljava/lang/String;)I",
+                (string.clone(),),
+            )
+            .await?;
+
+        let _: () = jvm
+            .invoke_virtual(graphics, "javax/microedition/lcdui/Graphics", "setColor", "(I)V", (Self::TEXT_COLOR,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                graphics,
+                "javax/microedition/lcdui/Graphics",
+                "drawString",
+                "(Ljava/lang/String;III)V",
+                (string, x, y, Self::ANCHOR_TOP_LEFT),
+            )
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                graphics,
+                "javax/microedition/lcdui/Graphics",
+                "drawLine",
+                "(IIII)V",
+                (x + width, y, x + width, y + font_height - 1),
+            )
+            .await?;
+
+        Ok(font_height)
+    }
+
+    fn mode(constraints: i32) -> InputMode {
+        match constraints & Self::CONSTRAINT_MASK {
+            Self::CONSTRAINT_NUMERIC | Self::CONSTRAINT_PHONENUMBER | Self::CONSTRAINT_DECIMAL => InputMode::Numeric,
+            _ => InputMode::Korean,
+        }
+    }
+
+    async fn append<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, committed: &str) -> JvmResult<()> {
+        if committed.is_empty() {
+            return Ok(());
+        }
+
+        let text = Self::text(jvm, this).await?;
+        Self::set_text(jvm, this, &format!("{text}{committed}")).await
+    }
+
+    async fn text<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<RustString> {
+        let text: ClassInstanceRef<String> = jvm.get_field(this, "text", "Ljava/lang/String;").await?;
+
+        JavaLangString::to_rust_string(jvm, &text).await
+    }
+
+    async fn set_text<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, text: &str) -> JvmResult<()> {
+        let string = JavaLangString::from_rust_string(jvm, text).await?;
+
+        jvm.put_field(this, "text", "Ljava/lang/String;", string).await
+    }
+
+    async fn input_method<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<InputMethod> {
+        let state: ClassInstanceRef<String> = jvm.get_field(this, "imState", "Ljava/lang/String;").await?;
+        let state = JavaLangString::to_rust_string(jvm, &state).await?;
+
+        Ok(InputMethod::restore(&state).unwrap())
+    }
+
+    async fn store_input_method<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, input_method: &InputMethod) -> JvmResult<()> {
+        let state = JavaLangString::from_rust_string(jvm, &input_method.save()).await?;
+
+        jvm.put_field(this, "imState", "Ljava/lang/String;", state).await
+    }
+}
@@ -1,5 +1,9 @@
 mod invalid_record_id_exception;
 mod record_store;
 mod record_store_exception;
+mod record_store_full_exception;
 
-pub use self::{invalid_record_id_exception::InvalidRecordIDException, record_store::RecordStore, record_store_exception::RecordStoreException};
+pub use self::{
+    invalid_record_id_exception::InvalidRecordIDException, record_store::RecordStore, record_store_exception::RecordStoreException,
+    record_store_full_exception::RecordStoreFullException,
+};
@@ -78,6 +78,9 @@ impl RecordStore {
         let mut database = Self::get_database(jvm, context, &this).await?;
 
         let data: Vec<i8> = jvm.load_array(&data, offset as _, length as _).await?;
+        if !context.system().storage().fits(data.len() as _).await {
+            return Err(jvm.exception("javax/microedition/rms/RecordStoreFullException", "Storage is full").await);
+        }
 
         let id = database.add(&cast_vec(data)).await;
 
@@ -95,10 +98,12 @@ impl RecordStore {
         Ok(())
     }
 
-    async fn get_size_available(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub javax.microedition.rms.RecordStore::getSizeAvailable({this:?})");
+    async fn get_size_available(_jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.rms.RecordStore::getSizeAvailable({this:?})");
+
+        let available = context.system().storage().available().await;
 
-        Ok(1000000 as _)
+        Ok(available.min(i32::MAX as u64) as _)
     }
 
     async fn get_next_record_id(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
@@ -187,6 +192,10 @@ impl RecordStore {
         let data: Vec<i8> = jvm.load_array(&data, offset as _, length as _).await?;
 
         let mut database = Self::get_database(jvm, context, &this).await?;
+        let old_len = database.get(record_id as _).await.map(|x| x.len()).unwrap_or(0);
+        if !context.system().storage().fits(data.len().saturating_sub(old_len) as _).await {
+            return Err(jvm.exception("javax/microedition/rms/RecordStoreFullException", "Storage is full").await);
+        }
         database.set(record_id as _, &cast_vec(data)).await;
 
         Ok(())
@@ -244,7 +253,7 @@ impl RecordStore {
         let system = context.system();
         let pid = system.pid().to_owned();
 
-        Ok(system.platform().database_repository().open(&db_name_str, &pid).await)
+        Ok(system.database_repository().open(&db_name_str, &pid).await)
     }
 }
 
@@ -255,6 +264,7 @@ mod test {
     use java_runtime::classes::java::lang::String;
     use jvm::{Array, ClassInstanceRef, JavaError, Result as JvmResult, runtime::JavaLangString};
     use test_utils::run_jvm_test;
+    use wie_backend::DEFAULT_STORAGE_QUOTA;
     use wie_util::Result;
 
     use crate::get_protos;
@@ -313,4 +323,50 @@ mod test {
             Ok(())
         })
     }
+
+    #[test]
+    fn add_record_beyond_quota_throws_record_store_full() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let name: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "full").await?.into();
+            let store: ClassInstanceRef<RecordStore> = jvm
+                .invoke_static(
+                    "javax/microedition/rms/RecordStore",
+                    "openRecordStore",
+                    "(Ljava/lang/String;Z)Ljavax/microedition/rms/RecordStore;",
+                    (name, true),
+                )
+                .await?;
+
+            let available: i32 = jvm
+                .invoke_virtual(&store, "javax/microedition/rms/RecordStore", "getSizeAvailable", "()I", ())
+                .await?;
+            assert_eq!(available, DEFAULT_STORAGE_QUOTA as i32);
+
+            let data = jvm.instantiate_array("B", 16).await?;
+            let _: i32 = jvm
+                .invoke_virtual(&store, "javax/microedition/rms/RecordStore", "addRecord", "([BII)I", (data, 0, 16))
+                .await?;
+            let available: i32 = jvm
+                .invoke_virtual(&store, "javax/microedition/rms/RecordStore", "getSizeAvailable", "()I", ())
+                .await?;
+            assert_eq!(available, DEFAULT_STORAGE_QUOTA as i32 - 16);
+
+            let data = jvm.instantiate_array("B", available + 1).await?;
+            let full: JvmResult<i32> = jvm
+                .invoke_virtual(
+                    &store,
+                    "javax/microedition/rms/RecordStore",
+                    "addRecord",
+                    "([BII)I",
+                    (data, 0, available + 1),
+                )
+                .await;
+            let Err(JavaError::JavaException(exception)) = full else {
+                panic!("record beyond quota was added");
+            };
+            assert!(jvm.is_instance(&*exception, "javax/microedition/rms/RecordStoreFullException"));
+
+            Ok(())
+        })
+    }
 }
@@ -1,11 +1,13 @@
 mod event_queue;
 mod launcher;
 mod smaf_player;
+mod text_input_listener;
 mod wie_error;
 
 pub use self::{
     event_queue::{EventQueue, KeyboardEventType, MIDPKeyCode},
     launcher::Launcher,
     smaf_player::SmafPlayer,
+    text_input_listener::TextInputListener,
     wie_error::WieError,
 };
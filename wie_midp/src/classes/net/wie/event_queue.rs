@@ -144,6 +144,35 @@ impl MIDPKeyCode {
             KeyCode::STAR => Self::KEY_STAR,
         }
     }
+
+    pub fn to_key_code(&self) -> KeyCode {
+        match self {
+            Self::UP => KeyCode::UP,
+            Self::DOWN => KeyCode::DOWN,
+            Self::LEFT => KeyCode::LEFT,
+            Self::RIGHT => KeyCode::RIGHT,
+            Self::FIRE => KeyCode::OK,
+            Self::LEFT_SOFT_KEY => KeyCode::LEFT_SOFT_KEY,
+            Self::RIGHT_SOFT_KEY => KeyCode::RIGHT_SOFT_KEY,
+            Self::CLEAR => KeyCode::CLEAR,
+            Self::CALL => KeyCode::CALL,
+            Self::HANGUP => KeyCode::HANGUP,
+            Self::VOLUME_UP => KeyCode::VOLUME_UP,
+            Self::VOLUME_DOWN => KeyCode::VOLUME_DOWN,
+            Self::KEY_NUM0 => KeyCode::NUM0,
+            Self::KEY_NUM1 => KeyCode::NUM1,
+            Self::KEY_NUM2 => KeyCode::NUM2,
+            Self::KEY_NUM3 => KeyCode::NUM3,
+            Self::KEY_NUM4 => KeyCode::NUM4,
+            Self::KEY_NUM5 => KeyCode::NUM5,
+            Self::KEY_NUM6 => KeyCode::NUM6,
+            Self::KEY_NUM7 => KeyCode::NUM7,
+            Self::KEY_NUM8 => KeyCode::NUM8,
+            Self::KEY_NUM9 => KeyCode::NUM9,
+            Self::KEY_POUND => KeyCode::HASH,
+            Self::KEY_STAR => KeyCode::STAR,
+        }
+    }
 }
 
 // class net.wie.EventQueue
@@ -222,6 +251,18 @@ impl EventQueue {
                         MIDPKeyCode::from_key_code(x) as _,
                         0,
                     ],
+                    // one character per event, the rest goes back to the front of the queue
+                    Event::Text(text) => {
+                        let mut chars = text.chars();
+                        let Some(first) = chars.next() else {
+                            continue;
+                        };
+                        if !chars.as_str().is_empty() {
+                            context.system().event_queue().push_front(Event::Text(chars.as_str().into()));
+                        }
+
+                        vec![EventQueueEvent::KeyEvent as _, KeyboardEventType::KeyTyped as _, first as _, 0]
+                    }
                     Event::Timer { due, callback } => {
                         // TODO we should wait for timer more efficiently
                         if due < now {
@@ -0,0 +1,27 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+
+use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use wie_jvm_support::WieJavaClassProto;
+
+// interface net.wie.TextInputListener
+// implemented by text widgets drawn on a canvas, which receive host keyboard text while they have focus
+pub struct TextInputListener;
+
+impl TextInputListener {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/TextInputListener",
+            parent_class: None,
+            interfaces: vec![],
+            methods: vec![JavaMethodProto::new_abstract(
+                "keyTyped",
+                "(I)V",
+                MethodAccessFlags::PUBLIC | MethodAccessFlags::ABSTRACT,
+            )],
+            fields: vec![],
+            access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::INTERFACE | ClassAccessFlags::ABSTRACT,
+        }
+    }
+}
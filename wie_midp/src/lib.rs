@@ -5,7 +5,7 @@ pub mod classes;
 
 use wie_jvm_support::WieJavaClassProto;
 
-pub fn get_protos() -> [WieJavaClassProto; 26] {
+pub fn get_protos() -> [WieJavaClassProto; 29] {
     [
         classes::javax::microedition::lcdui::Alert::as_proto(),
         classes::javax::microedition::lcdui::AlertType::as_proto(),
@@ -21,6 +21,7 @@ pub fn get_protos() -> [WieJavaClassProto; 26] {
         classes::javax::microedition::lcdui::Item::as_proto(),
         classes::javax::microedition::lcdui::Screen::as_proto(),
         classes::javax::microedition::lcdui::TextBox::as_proto(),
+        classes::javax::microedition::lcdui::TextField::as_proto(),
         classes::javax::microedition::lcdui::game::GameCanvas::as_proto(),
         classes::javax::microedition::media::Manager::as_proto(),
         classes::javax::microedition::media::MediaException::as_proto(),
@@ -29,9 +30,11 @@ pub fn get_protos() -> [WieJavaClassProto; 26] {
         classes::javax::microedition::rms::InvalidRecordIDException::as_proto(),
         classes::javax::microedition::rms::RecordStore::as_proto(),
         classes::javax::microedition::rms::RecordStoreException::as_proto(),
+        classes::javax::microedition::rms::RecordStoreFullException::as_proto(),
         classes::net::wie::EventQueue::as_proto(),
         classes::net::wie::Launcher::as_proto(),
         classes::net::wie::SmafPlayer::as_proto(),
+        classes::net::wie::TextInputListener::as_proto(),
         classes::net::wie::WieError::as_proto(),
     ]
 }
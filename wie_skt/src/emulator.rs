@@ -73,6 +73,7 @@ impl SktEmulator {
         properties: BTreeMap<String, String>,
         main_class_name: Option<String>,
     ) -> Result<()> {
+        let platform_version = system.device_profile().platform_version.clone();
         let system_properties = [
             ("MIN", "01000000000"),
             ("m.MIN", "01000000000"),
@@ -80,7 +81,7 @@ impl SktEmulator {
             ("m.VENDER", "vender"),
             ("m.CARRIER", "SKT"),
             ("m.SK_VM", "10"),
-            ("com.xce.wipi.version", ""),
+            ("com.xce.wipi.version", platform_version.as_str()),
         ];
         let properties = properties
             .into_iter()
@@ -130,6 +131,10 @@ impl Emulator for SktEmulator {
         self.system.event_queue().push(event)
     }
 
+    fn aid(&self) -> &str {
+        self.system.aid()
+    }
+
     fn tick(&mut self) -> Result<()> {
         self.system.tick()
     }
@@ -375,7 +375,7 @@ impl MathFP {
         }
     }
 
-    fn sin_fixed(angle: i128) -> i64 {
+    pub(crate) fn sin_fixed(angle: i128) -> i64 {
         let mut x = angle % TAU as i128;
         if x > PI as i128 {
             x -= TAU as i128;
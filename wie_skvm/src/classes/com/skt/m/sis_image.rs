@@ -1,10 +1,16 @@
-use alloc::vec;
+use alloc::{format, vec, vec::Vec};
+
+use bytemuck::cast_vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::String;
-use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{
+    Array, ClassInstanceRef, Jvm, Result as JvmResult,
+    runtime::{JavaIoInputStream, JavaLangClassLoader, JavaLangString},
+};
 
+use wie_backend::canvas::{SisImage, decode_sis};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 use wie_midp::classes::javax::microedition::lcdui::{Graphics, Image};
 
@@ -85,6 +91,17 @@ impl SISImage {
             fields: ["IMG_LEVEL_BW", "IMG_LEVEL_4G", "IMG_LEVEL_256C"]
                 .into_iter()
                 .map(|name| JavaFieldProto::new(name, "I", FieldAccessFlags::PUBLIC | FieldAccessFlags::STATIC | FieldAccessFlags::FINAL))
+                .chain([
+                    JavaFieldProto::new("objectBufferSize", "I", FieldAccessFlags::PRIVATE | FieldAccessFlags::STATIC),
+                    JavaFieldProto::new("otherBufferSize", "I", FieldAccessFlags::PRIVATE | FieldAccessFlags::STATIC),
+                    JavaFieldProto::new("width", "I", FieldAccessFlags::PRIVATE),
+                    JavaFieldProto::new("height", "I", FieldAccessFlags::PRIVATE),
+                    JavaFieldProto::new("level", "I", FieldAccessFlags::PRIVATE),
+                    JavaFieldProto::new("delays", "[I", FieldAccessFlags::PRIVATE),
+                    JavaFieldProto::new("frames", "[Ljavax/microedition/lcdui/Image;", FieldAccessFlags::PRIVATE),
+                    JavaFieldProto::new("objects", "[Ljavax/microedition/lcdui/Image;", FieldAccessFlags::PRIVATE),
+                    JavaFieldProto::new("opaqueObjects", "[Ljavax/microedition/lcdui/Image;", FieldAccessFlags::PRIVATE),
+                ])
                 .collect(),
             access_flags: ClassAccessFlags::PUBLIC,
         }
@@ -106,13 +123,17 @@ impl SISImage {
     }
 
     async fn create_buffer(jvm: &Jvm, _context: &mut WieJvmContext, object_buffer_size: i32, other_buffer_size: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.SISImage::createBuffer({object_buffer_size}, {other_buffer_size})");
+        tracing::debug!("com.skt.m.SISImage::createBuffer({object_buffer_size}, {other_buffer_size})");
 
         if object_buffer_size <= 0 || other_buffer_size <= 0 {
             return Err(jvm.exception("java/lang/IllegalArgumentException", "buffer sizes must be positive").await);
         }
 
-        Ok(())
+        // decoded images live in the java heap, so the sizes are only recorded
+        jvm.put_static_field("com/skt/m/SISImage", "objectBufferSize", "I", object_buffer_size)
+            .await?;
+        jvm.put_static_field("com/skt/m/SISImage", "otherBufferSize", "I", other_buffer_size)
+            .await
     }
 
     async fn create_sis_image_from_data(
@@ -122,20 +143,11 @@ impl SISImage {
         offset: i32,
         length: i32,
     ) -> JvmResult<ClassInstanceRef<Self>> {
-        tracing::warn!("stub com.skt.m.SISImage::createSISImage({data:?}, {offset}, {length})");
-
-        if data.is_null() {
-            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
-        }
+        tracing::debug!("com.skt.m.SISImage::createSISImage({data:?}, {offset}, {length})");
 
-        let array_length = jvm.array_length(&data).await?;
-        if offset < 0 || length < 0 || (offset as usize).checked_add(length as usize).is_none_or(|end| end > array_length) {
-            return Err(jvm
-                .exception("java/lang/ArrayIndexOutOfBoundsException", "invalid offset or length")
-                .await);
-        }
+        let data = Self::read_data(jvm, &data, offset, length).await?;
 
-        Ok(jvm.new_class("com/skt/m/SISImage", "()V", ()).await?.into())
+        Self::create(jvm, &data).await
     }
 
     async fn create_sis_image_from_name(
@@ -143,18 +155,30 @@ impl SISImage {
         _context: &mut WieJvmContext,
         name: ClassInstanceRef<String>,
     ) -> JvmResult<ClassInstanceRef<Self>> {
-        tracing::warn!("stub com.skt.m.SISImage::createSISImage({name:?})");
+        tracing::debug!("com.skt.m.SISImage::createSISImage({name:?})");
 
         if name.is_null() {
             return Err(jvm.exception("java/lang/NullPointerException", "name is null").await);
         }
 
-        Ok(jvm.new_class("com/skt/m/SISImage", "()V", ()).await?.into())
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
+
+        let class_loader = JavaLangClassLoader::get_system_class_loader(jvm).await?;
+        let stream = JavaLangClassLoader::get_resource_as_stream(jvm, &class_loader, &name).await?;
+        let Some(stream) = stream else {
+            return Err(jvm.exception("java/io/IOException", &format!("Resource not found: {name}")).await);
+        };
+
+        let data = JavaIoInputStream::read_until_end(jvm, &stream).await?;
+
+        Self::create(jvm, &data).await
     }
 
-    async fn free_buffer(_jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.SISImage::freeBuffer()");
-        Ok(())
+    async fn free_buffer(jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.SISImage::freeBuffer()");
+
+        jvm.put_static_field("com/skt/m/SISImage", "objectBufferSize", "I", 0).await?;
+        jvm.put_static_field("com/skt/m/SISImage", "otherBufferSize", "I", 0).await
     }
 
     async fn get_required_buffer_size(
@@ -164,20 +188,12 @@ impl SISImage {
         offset: i32,
         length: i32,
     ) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.SISImage::getRequiredBufferSize({data:?}, {offset}, {length})");
+        tracing::debug!("com.skt.m.SISImage::getRequiredBufferSize({data:?}, {offset}, {length})");
 
-        if data.is_null() {
-            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
-        }
+        let data = Self::read_data(jvm, &data, offset, length).await?;
+        let image = Self::decode(jvm, &data).await?;
 
-        let array_length = jvm.array_length(&data).await?;
-        if offset < 0 || length < 0 || (offset as usize).checked_add(length as usize).is_none_or(|end| end > array_length) {
-            return Err(jvm
-                .exception("java/lang/ArrayIndexOutOfBoundsException", "invalid offset or length")
-                .await);
-        }
-
-        Ok(0)
+        Ok(image.buffer_size().min(i32::MAX as _) as _)
     }
 
     async fn get_best_id(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
@@ -185,39 +201,49 @@ impl SISImage {
         Ok(0)
     }
 
-    async fn get_delay(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, frame_id: i32) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.SISImage::getDelay({this:?}, {frame_id})");
-        Ok(0)
+    async fn get_delay(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, frame_id: i32) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.SISImage::getDelay({this:?}, {frame_id})");
+
+        let delays: ClassInstanceRef<Array<i32>> = jvm.get_field(&this, "delays", "[I").await?;
+        let index = Self::check_id(jvm, &delays, frame_id, "invalid frame ID").await?;
+
+        Ok(jvm.load_array(&delays, index, 1).await?[0])
     }
 
     async fn get_frame(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, frame_id: i32) -> JvmResult<ClassInstanceRef<Image>> {
-        tracing::warn!("stub com.skt.m.SISImage::getFrame({this:?}, {frame_id})");
+        tracing::debug!("com.skt.m.SISImage::getFrame({this:?}, {frame_id})");
 
-        if frame_id != 0 {
-            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid frame ID").await);
-        }
+        let frames = jvm.get_field(&this, "frames", "[Ljavax/microedition/lcdui/Image;").await?;
 
-        Ok(ClassInstanceRef::new(None))
+        Self::image_at(jvm, &frames, frame_id, "invalid frame ID").await
     }
 
-    async fn get_height(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.SISImage::getHeight({this:?})");
-        Ok(0)
+    async fn get_height(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.SISImage::getHeight({this:?})");
+
+        jvm.get_field(&this, "height", "I").await
     }
 
-    async fn get_image_level(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.SISImage::getImageLevel({this:?})");
-        Ok(0)
+    async fn get_image_level(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.SISImage::getImageLevel({this:?})");
+
+        jvm.get_field(&this, "level", "I").await
     }
 
-    async fn get_max_frame_id(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.SISImage::getMaxFrameID({this:?})");
-        Ok(0)
+    async fn get_max_frame_id(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.SISImage::getMaxFrameID({this:?})");
+
+        let frames: ClassInstanceRef<Array<ClassInstanceRef<Image>>> = jvm.get_field(&this, "frames", "[Ljavax/microedition/lcdui/Image;").await?;
+
+        Ok(jvm.array_length(&frames).await? as i32 - 1)
     }
 
-    async fn get_max_object_id(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.SISImage::getMaxObjectID({this:?})");
-        Ok(0)
+    async fn get_max_object_id(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.SISImage::getMaxObjectID({this:?})");
+
+        let objects: ClassInstanceRef<Array<ClassInstanceRef<Image>>> = jvm.get_field(&this, "objects", "[Ljavax/microedition/lcdui/Image;").await?;
+
+        Ok(jvm.array_length(&objects).await? as i32 - 1)
     }
 
     async fn get_object(
@@ -227,18 +253,18 @@ impl SISImage {
         object_id: i32,
         use_transparency: bool,
     ) -> JvmResult<ClassInstanceRef<Image>> {
-        tracing::warn!("stub com.skt.m.SISImage::getObject({this:?}, {object_id}, {use_transparency})");
+        tracing::debug!("com.skt.m.SISImage::getObject({this:?}, {object_id}, {use_transparency})");
 
-        if object_id != 0 {
-            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid object ID").await);
-        }
+        let field = if use_transparency { "objects" } else { "opaqueObjects" };
+        let objects = jvm.get_field(&this, field, "[Ljavax/microedition/lcdui/Image;").await?;
 
-        Ok(ClassInstanceRef::new(None))
+        Self::image_at(jvm, &objects, object_id, "invalid object ID").await
     }
 
-    async fn get_width(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.SISImage::getWidth({this:?})");
-        Ok(0)
+    async fn get_width(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.SISImage::getWidth({this:?})");
+
+        jvm.get_field(&this, "width", "I").await
     }
 
     async fn paint_frame(
@@ -250,17 +276,16 @@ impl SISImage {
         x: i32,
         y: i32,
     ) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.SISImage::paintFrame({this:?}, {graphics:?}, {frame_id}, {x}, {y})");
+        tracing::debug!("com.skt.m.SISImage::paintFrame({this:?}, {graphics:?}, {frame_id}, {x}, {y})");
 
         if graphics.is_null() {
             return Err(jvm.exception("java/lang/NullPointerException", "graphics is null").await);
         }
 
-        if frame_id != 0 {
-            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid frame ID").await);
-        }
+        let frames = jvm.get_field(&this, "frames", "[Ljavax/microedition/lcdui/Image;").await?;
+        let frame = Self::image_at(jvm, &frames, frame_id, "invalid frame ID").await?;
 
-        Ok(())
+        Self::draw_image(jvm, &graphics, frame, x, y).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -274,16 +299,193 @@ impl SISImage {
         y: i32,
         use_transparency: bool,
     ) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.SISImage::paintObject({this:?}, {graphics:?}, {object_id}, {x}, {y}, {use_transparency})");
+        tracing::debug!("com.skt.m.SISImage::paintObject({this:?}, {graphics:?}, {object_id}, {x}, {y}, {use_transparency})");
 
         if graphics.is_null() {
             return Err(jvm.exception("java/lang/NullPointerException", "graphics is null").await);
         }
 
-        if object_id != 0 {
-            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid object ID").await);
+        let field = if use_transparency { "objects" } else { "opaqueObjects" };
+        let objects = jvm.get_field(&this, field, "[Ljavax/microedition/lcdui/Image;").await?;
+        let object = Self::image_at(jvm, &objects, object_id, "invalid object ID").await?;
+
+        Self::draw_image(jvm, &graphics, object, x, y).await
+    }
+
+    async fn create(jvm: &Jvm, data: &[u8]) -> JvmResult<ClassInstanceRef<Self>> {
+        let image = Self::decode(jvm, data).await?;
+
+        let mut instance: ClassInstanceRef<Self> = jvm.new_class("com/skt/m/SISImage", "()V", ()).await?.into();
+        jvm.put_field(&mut instance, "width", "I", image.width as i32).await?;
+        jvm.put_field(&mut instance, "height", "I", image.height as i32).await?;
+        jvm.put_field(&mut instance, "level", "I", image.level as i32).await?;
+
+        let delays = image.frames.iter().map(|x| x.delay as i32).collect::<Vec<_>>();
+        let mut delays_array = jvm.instantiate_array("I", delays.len()).await?;
+        jvm.store_array(&mut delays_array, 0, delays).await?;
+        jvm.put_field(&mut instance, "delays", "[I", delays_array).await?;
+
+        let mut frames = Vec::with_capacity(image.frames.len());
+        for index in 0..image.frames.len() {
+            let Ok(frame) = image.frame(index) else {
+                return Err(jvm.exception("com/skt/m/UnsupportedFormatException", "invalid SIS image").await);
+            };
+            frames.push(Image::from_image(jvm, frame).await?);
+        }
+        let mut objects = Vec::with_capacity(image.objects.len());
+        let mut opaque_objects = Vec::with_capacity(image.objects.len());
+        for object in &image.objects {
+            objects.push(Image::from_image(jvm, object.image(true)).await?);
+            opaque_objects.push(Image::from_image(jvm, object.image(false)).await?);
         }
 
-        Ok(())
+        for (field, images) in [("frames", frames), ("objects", objects), ("opaqueObjects", opaque_objects)] {
+            let mut array = jvm.instantiate_array("Ljavax/microedition/lcdui/Image;", images.len()).await?;
+            jvm.store_array(&mut array, 0, images).await?;
+            jvm.put_field(&mut instance, field, "[Ljavax/microedition/lcdui/Image;", array).await?;
+        }
+
+        Ok(instance)
+    }
+
+    async fn decode(jvm: &Jvm, data: &[u8]) -> JvmResult<SisImage> {
+        match decode_sis(data) {
+            Ok(x) => Ok(x),
+            Err(x) => {
+                tracing::error!("Failed to decode SIS image: {x:?}, header {:02x?}", &data[..data.len().min(16)]);
+                Err(jvm.exception("com/skt/m/UnsupportedFormatException", "invalid SIS image").await)
+            }
+        }
+    }
+
+    async fn read_data(jvm: &Jvm, data: &ClassInstanceRef<Array<i8>>, offset: i32, length: i32) -> JvmResult<Vec<u8>> {
+        if data.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
+        }
+
+        let array_length = jvm.array_length(data).await?;
+        if offset < 0 || length < 0 || (offset as usize).checked_add(length as usize).is_none_or(|end| end > array_length) {
+            return Err(jvm
+                .exception("java/lang/ArrayIndexOutOfBoundsException", "invalid offset or length")
+                .await);
+        }
+
+        let mut result = vec![0; length as usize];
+        jvm.array_raw_buffer(data).await?.read(offset as _, &mut result)?;
+
+        Ok(cast_vec(result))
+    }
+
+    async fn check_id<T>(jvm: &Jvm, array: &ClassInstanceRef<Array<T>>, id: i32, message: &str) -> JvmResult<usize> {
+        let length = jvm.array_length(array).await?;
+        if id < 0 || id as usize >= length {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", message).await);
+        }
+
+        Ok(id as usize)
+    }
+
+    async fn image_at(
+        jvm: &Jvm,
+        images: &ClassInstanceRef<Array<ClassInstanceRef<Image>>>,
+        id: i32,
+        message: &str,
+    ) -> JvmResult<ClassInstanceRef<Image>> {
+        let index = Self::check_id(jvm, images, id, message).await?;
+        let image: Vec<ClassInstanceRef<Image>> = jvm.load_array(images, index, 1).await?;
+
+        Ok(image.into_iter().next().unwrap())
+    }
+
+    async fn draw_image(jvm: &Jvm, graphics: &ClassInstanceRef<Graphics>, image: ClassInstanceRef<Image>, x: i32, y: i32) -> JvmResult<()> {
+        // anchor TOP | LEFT
+        jvm.invoke_virtual(
+            graphics,
+            "javax/microedition/lcdui/Graphics",
+            "drawImage",
+            "(Ljavax/microedition/lcdui/Image;III)V",
+            (image, x, y, 20),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec, vec::Vec};
+
+    use bytemuck::pod_collect_to_vec;
+    use jvm::ClassInstanceRef;
+    use test_utils::run_jvm_test;
+    use wie_midp::classes::javax::microedition::lcdui::{Graphics, Image};
+
+    use super::SISImage;
+
+    #[test]
+    fn test_sis_image() {
+        let result = run_jvm_test(
+            Box::new([wie_midp::get_protos().into(), Box::new([SISImage::as_proto()])]),
+            |jvm| async move {
+                let mut data: Vec<u8> = vec![b'S', b'I', b'S', 1, 2, 0, 1, 0, 8, 1, 2, 0];
+                data.extend([2, 0, 1, 0, 1, 0xe0, 0x03, 0b1000_0000]);
+                data.extend([100, 0, 1, 0, 0, 0, 0, 0]);
+                data.extend([50, 0, 0]);
+
+                let mut array = jvm.instantiate_array("B", data.len()).await?;
+                jvm.array_raw_buffer_mut(&mut array).await?.write(0, &data)?;
+                let _: () = jvm.invoke_static("com/skt/m/SISImage", "createBuffer", "(II)V", (1024, 1024)).await?;
+
+                let image: ClassInstanceRef<SISImage> = jvm
+                    .invoke_static(
+                        "com/skt/m/SISImage",
+                        "createSISImage",
+                        "([BII)Lcom/skt/m/SISImage;",
+                        (array, 0, data.len() as i32),
+                    )
+                    .await?;
+
+                let width: i32 = jvm.invoke_virtual(&image, "com/skt/m/SISImage", "getWidth", "()I", ()).await?;
+                let max_frame_id: i32 = jvm.invoke_virtual(&image, "com/skt/m/SISImage", "getMaxFrameID", "()I", ()).await?;
+                let delay: i32 = jvm.invoke_virtual(&image, "com/skt/m/SISImage", "getDelay", "(I)I", (1,)).await?;
+                assert_eq!((width, max_frame_id, delay), (2, 1, 50));
+
+                let canvas: ClassInstanceRef<Image> = jvm
+                    .invoke_static(
+                        "javax/microedition/lcdui/Image",
+                        "createImage",
+                        "(II)Ljavax/microedition/lcdui/Image;",
+                        (2, 1),
+                    )
+                    .await?;
+                let graphics: ClassInstanceRef<Graphics> = jvm
+                    .invoke_virtual(
+                        &canvas,
+                        "javax/microedition/lcdui/Image",
+                        "getGraphics",
+                        "()Ljavax/microedition/lcdui/Graphics;",
+                        (),
+                    )
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &image,
+                        "com/skt/m/SISImage",
+                        "paintObject",
+                        "(Ljavax/microedition/lcdui/Graphics;IIIZ)V",
+                        (graphics, 0, 0, 0, true),
+                    )
+                    .await?;
+
+                let pixels: Vec<u32> = pod_collect_to_vec(&Image::image(&jvm, &canvas).await?.raw());
+                assert_eq!(pixels, [0xffff0000, 0]);
+
+                let result: jvm::Result<i32> = jvm.invoke_virtual(&image, "com/skt/m/SISImage", "getDelay", "(I)I", (2,)).await;
+                assert!(result.is_err());
+
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok(), "JVM test failed: {result:?}");
     }
 }
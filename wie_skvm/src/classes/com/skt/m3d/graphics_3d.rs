@@ -1,14 +1,26 @@
-use alloc::vec;
+use alloc::{boxed::Box, vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
+use wie_backend::canvas::{Canvas, Clip, Color, PixelType, Rgb8Pixel};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
-use wie_midp::classes::javax::microedition::lcdui::Graphics;
+use wie_midp::classes::javax::microedition::lcdui::{Graphics, Image};
 
 use super::Object3D;
 
+// vertices closer to the camera than this are not drawn
+const NEAR: i64 = 1;
+const AMBIENT: i64 = 64;
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+    position: [i64; 3],
+}
+
 // class com.skt.m3d.Graphics3D
 pub struct Graphics3D;
 
@@ -73,6 +85,7 @@ impl Graphics3D {
             fields: vec![
                 JavaFieldProto::new("backfaceCulled", "Z", FieldAccessFlags::PRIVATE | FieldAccessFlags::STATIC),
                 JavaFieldProto::new("zBufferEnabled", "Z", FieldAccessFlags::PRIVATE | FieldAccessFlags::STATIC),
+                JavaFieldProto::new("zBuffer", "[I", FieldAccessFlags::PRIVATE | FieldAccessFlags::STATIC),
             ],
             access_flags: ClassAccessFlags::PUBLIC,
         }
@@ -88,23 +101,31 @@ impl Graphics3D {
         jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await
     }
 
-    async fn clear_z_buffer(_jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Graphics3D::clearZBuffer()");
-        Ok(())
+    async fn clear_z_buffer(jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("com.skt.m3d.Graphics3D::clearZBuffer()");
+
+        let mut z_buffer: ClassInstanceRef<Array<i32>> = jvm.get_static_field("com/skt/m3d/Graphics3D", "zBuffer", "[I").await?;
+        if z_buffer.is_null() {
+            return Ok(());
+        }
+
+        let length = jvm.array_length(&z_buffer).await?;
+        jvm.store_array(&mut z_buffer, 0, vec![i32::MAX; length]).await
     }
 
-    async fn destroy_z_buffer(_jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Graphics3D::destroyZBuffer()");
-        Ok(())
+    async fn destroy_z_buffer(jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("com.skt.m3d.Graphics3D::destroyZBuffer()");
+
+        jvm.put_static_field("com/skt/m3d/Graphics3D", "zBuffer", "[I", None).await
     }
 
     async fn draw_wireframe(
         jvm: &Jvm,
         _context: &mut WieJvmContext,
-        graphics: ClassInstanceRef<Graphics>,
+        mut graphics: ClassInstanceRef<Graphics>,
         object: ClassInstanceRef<Object3D>,
     ) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Graphics3D::drawWireframe({graphics:?}, {object:?})");
+        tracing::debug!("com.skt.m3d.Graphics3D::drawWireframe({graphics:?}, {object:?})");
 
         if graphics.is_null() || object.is_null() {
             return Err(jvm
@@ -112,6 +133,25 @@ impl Graphics3D {
                 .await);
         }
 
+        let backface_culled: bool = jvm.get_static_field("com/skt/m3d/Graphics3D", "backfaceCulled", "Z").await?;
+        let color: i32 = jvm.get_field(&graphics, "color", "I").await?;
+        let color = Rgb8Pixel::to_color(color as _);
+
+        let (mut canvas, clip, points, triangles) = Self::prepare(jvm, &mut graphics, &object).await?;
+
+        for [a, b, c, _] in triangles {
+            let (Some(a), Some(b), Some(c)) = (Self::point(&points, a), Self::point(&points, b), Self::point(&points, c)) else {
+                continue;
+            };
+            if backface_culled && Self::area(a, b, c) <= 0 {
+                continue;
+            }
+
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                canvas.draw_line(from.x, from.y, to.x, to.y, color, clip);
+            }
+        }
+
         Ok(())
     }
 
@@ -126,10 +166,10 @@ impl Graphics3D {
     async fn render(
         jvm: &Jvm,
         _context: &mut WieJvmContext,
-        graphics: ClassInstanceRef<Graphics>,
+        mut graphics: ClassInstanceRef<Graphics>,
         object: ClassInstanceRef<Object3D>,
     ) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Graphics3D::render({graphics:?}, {object:?})");
+        tracing::debug!("com.skt.m3d.Graphics3D::render({graphics:?}, {object:?})");
 
         if graphics.is_null() || object.is_null() {
             return Err(jvm
@@ -137,18 +177,194 @@ impl Graphics3D {
                 .await);
         }
 
+        let backface_culled: bool = jvm.get_static_field("com/skt/m3d/Graphics3D", "backfaceCulled", "Z").await?;
+        let z_buffer_enabled: bool = jvm.get_static_field("com/skt/m3d/Graphics3D", "zBufferEnabled", "Z").await?;
+
+        let (mut canvas, clip, points, mut triangles) = Self::prepare(jvm, &mut graphics, &object).await?;
+        let width = canvas.image().width();
+        let height = canvas.image().height();
+
+        let mut z_buffer = if z_buffer_enabled {
+            Some(Self::load_z_buffer(jvm, (width * height) as _).await?)
+        } else {
+            // without a z-buffer, draw back to front
+            triangles.sort_by_key(|&[a, b, c, _]| {
+                core::cmp::Reverse(
+                    [a, b, c]
+                        .iter()
+                        .filter_map(|&x| Self::point(&points, x))
+                        .map(|x| x.position[2])
+                        .sum::<i64>(),
+                )
+            });
+            None
+        };
+
+        for [a, b, c, color] in triangles {
+            let (Some(mut pa), Some(mut pb), Some(mut pc)) = (Self::point(&points, a), Self::point(&points, b), Self::point(&points, c)) else {
+                continue;
+            };
+
+            let area = Self::area(pa, pb, pc);
+            if area == 0 || (backface_culled && area < 0) {
+                continue;
+            }
+            if area < 0 {
+                core::mem::swap(&mut pb, &mut pc);
+            }
+
+            let color = Self::shade(Rgb8Pixel::to_color(color as _), pa.position, pb.position, pc.position);
+            Self::fill_triangle(&mut *canvas, z_buffer.as_deref_mut(), [pa, pb, pc], color, clip);
+        }
+
+        if let Some(z_buffer) = z_buffer {
+            let mut array: ClassInstanceRef<Array<i32>> = jvm.get_static_field("com/skt/m3d/Graphics3D", "zBuffer", "[I").await?;
+            jvm.store_array(&mut array, 0, z_buffer).await?;
+        }
+
         Ok(())
     }
 
     async fn set_backface_culled(jvm: &Jvm, _context: &mut WieJvmContext, enabled: bool) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Graphics3D::setBackfaceCulled({enabled})");
+        tracing::debug!("com.skt.m3d.Graphics3D::setBackfaceCulled({enabled})");
         jvm.put_static_field("com/skt/m3d/Graphics3D", "backfaceCulled", "Z", enabled).await
     }
 
     async fn set_z_buffer_enabled(jvm: &Jvm, _context: &mut WieJvmContext, enabled: bool) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Graphics3D::setZBufferEnabled({enabled})");
+        tracing::debug!("com.skt.m3d.Graphics3D::setZBufferEnabled({enabled})");
         jvm.put_static_field("com/skt/m3d/Graphics3D", "zBufferEnabled", "Z", enabled).await
     }
+
+    #[allow(clippy::type_complexity)]
+    async fn prepare(
+        jvm: &Jvm,
+        graphics: &mut ClassInstanceRef<Graphics>,
+        object: &ClassInstanceRef<Object3D>,
+    ) -> JvmResult<(Box<dyn Canvas>, Clip, Vec<Option<Point>>, Vec<[i32; 4]>)> {
+        let image = Graphics::image(jvm, graphics).await?;
+        let canvas = Image::canvas(jvm, &image).await?;
+        let clip = Graphics::clip(jvm, graphics).await?;
+
+        let translate_x: i32 = jvm.get_field(graphics, "translateX", "I").await?;
+        let translate_y: i32 = jvm.get_field(graphics, "translateY", "I").await?;
+        let width: i32 = jvm.get_field(graphics, "width", "I").await?;
+        let height: i32 = jvm.get_field(graphics, "height", "I").await?;
+
+        // camera sits at the origin looking down +z, with y pointing down the screen and a 90 degree horizontal field of view
+        let center_x = (translate_x + width / 2) as i64;
+        let center_y = (translate_y + height / 2) as i64;
+        let focal = (width / 2).max(1) as i64;
+
+        let points = Object3D::transformed_vertices(jvm, object)
+            .await?
+            .into_iter()
+            .map(|position| {
+                let [x, y, z] = position;
+                if z < NEAR {
+                    return None;
+                }
+
+                let screen_x = center_x + x * focal / z;
+                let screen_y = center_y + y * focal / z;
+
+                Some(Point {
+                    x: screen_x.clamp(i32::MIN as _, i32::MAX as _) as _,
+                    y: screen_y.clamp(i32::MIN as _, i32::MAX as _) as _,
+                    position,
+                })
+            })
+            .collect();
+        let triangles = Object3D::triangles(jvm, object).await?;
+
+        Ok((canvas, clip, points, triangles))
+    }
+
+    async fn load_z_buffer(jvm: &Jvm, length: usize) -> JvmResult<Vec<i32>> {
+        let z_buffer: ClassInstanceRef<Array<i32>> = jvm.get_static_field("com/skt/m3d/Graphics3D", "zBuffer", "[I").await?;
+
+        if !z_buffer.is_null() && jvm.array_length(&z_buffer).await? == length {
+            return jvm.load_array(&z_buffer, 0, length).await;
+        }
+
+        let mut z_buffer = jvm.instantiate_array("I", length).await?;
+        jvm.store_array(&mut z_buffer, 0, vec![i32::MAX; length]).await?;
+        jvm.put_static_field("com/skt/m3d/Graphics3D", "zBuffer", "[I", z_buffer).await?;
+
+        Ok(vec![i32::MAX; length])
+    }
+
+    fn point(points: &[Option<Point>], index: i32) -> Option<Point> {
+        usize::try_from(index).ok().and_then(|x| points.get(x).copied().flatten())
+    }
+
+    // positive for triangles wound clockwise on screen, which are the front faces
+    fn area(a: Point, b: Point, c: Point) -> i64 {
+        Self::edge((a.x, a.y), (b.x, b.y), (c.x, c.y))
+    }
+
+    fn edge(a: (i32, i32), b: (i32, i32), p: (i32, i32)) -> i64 {
+        (b.0 as i64 - a.0 as i64) * (p.1 as i64 - a.1 as i64) - (b.1 as i64 - a.1 as i64) * (p.0 as i64 - a.0 as i64)
+    }
+
+    // light comes from the camera, faces turned away from it get darker down to the ambient level
+    fn shade(color: Color, a: [i64; 3], b: [i64; 3], c: [i64; 3]) -> Color {
+        let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]].map(|x| x as i128);
+        let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]].map(|x| x as i128);
+        let normal = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+
+        let length = normal.iter().map(|x| (x * x) as u128).sum::<u128>().isqrt();
+        if length == 0 {
+            return color;
+        }
+
+        let intensity = AMBIENT + ((256 - AMBIENT) as u128 * normal[2].unsigned_abs() / length) as i64;
+        let apply = |x: u8| ((x as i64 * intensity) >> 8) as u8;
+
+        Color {
+            a: 0xff,
+            r: apply(color.r),
+            g: apply(color.g),
+            b: apply(color.b),
+        }
+    }
+
+    fn fill_triangle(canvas: &mut dyn Canvas, mut z_buffer: Option<&mut [i32]>, points: [Point; 3], color: Color, clip: Clip) {
+        let [a, b, c] = points;
+        let area = Self::area(a, b, c);
+
+        let width = canvas.image().width() as i32;
+        let height = canvas.image().height() as i32;
+
+        let min_x = a.x.min(b.x).min(c.x).max(clip.x).max(0);
+        let min_y = a.y.min(b.y).min(c.y).max(clip.y).max(0);
+        let max_x = a.x.max(b.x).max(c.x).min(clip.x + clip.width as i32 - 1).min(width - 1);
+        let max_y = a.y.max(b.y).max(c.y).min(clip.y + clip.height as i32 - 1).min(height - 1);
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let w_a = Self::edge((b.x, b.y), (c.x, c.y), (x, y));
+                let w_b = Self::edge((c.x, c.y), (a.x, a.y), (x, y));
+                let w_c = Self::edge((a.x, a.y), (b.x, b.y), (x, y));
+                if w_a < 0 || w_b < 0 || w_c < 0 {
+                    continue;
+                }
+
+                if let Some(z_buffer) = z_buffer.as_deref_mut() {
+                    let z = (w_a as i128 * a.position[2] as i128 + w_b as i128 * b.position[2] as i128 + w_c as i128 * c.position[2] as i128)
+                        / area as i128;
+                    let z = z.min(i32::MAX as i128 - 1) as i32;
+
+                    let index = (y * width + x) as usize;
+                    if z >= z_buffer[index] {
+                        continue;
+                    }
+                    z_buffer[index] = z;
+                }
+
+                canvas.put_pixel(x, y, color, clip);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -156,14 +372,53 @@ mod tests {
     use alloc::boxed::Box;
 
     use java_runtime::classes::java::lang::String;
-    use jvm::{ClassInstanceRef, runtime::JavaLangString};
+    use jvm::{ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
     use test_utils::run_jvm_test;
     use wie_midp::classes::javax::microedition::lcdui::{Graphics, Image};
 
     use super::{Graphics3D, Object3D};
 
+    async fn create_object(jvm: &Jvm, vertices: &[[i32; 3]], triangles: &[[i32; 4]]) -> JvmResult<ClassInstanceRef<Object3D>> {
+        let name: ClassInstanceRef<String> = JavaLangString::from_rust_string(jvm, "object").await?.into();
+        let object: ClassInstanceRef<Object3D> = jvm.new_class("com/skt/m3d/Object3D", "(Ljava/lang/String;)V", (name,)).await?.into();
+
+        for &[x, y, z] in vertices {
+            let _: () = jvm
+                .invoke_virtual(&object, "com/skt/m3d/Object3D", "addVertex", "(III)V", (x, y, z))
+                .await?;
+        }
+        for &[a, b, c, color] in triangles {
+            let _: () = jvm
+                .invoke_virtual(&object, "com/skt/m3d/Object3D", "addTriangle", "(IIII)V", (a, b, c, color))
+                .await?;
+        }
+
+        Ok(object)
+    }
+
+    // triangle covering the whole 16x16 target at the given depth
+    fn covering_triangle(z: i32) -> [[i32; 3]; 3] {
+        [[-z, -z, z], [3 * z, -z, z], [-z, 3 * z, z]]
+    }
+
+    async fn render(jvm: &Jvm, graphics: &ClassInstanceRef<Graphics>, object: &ClassInstanceRef<Object3D>) -> JvmResult<()> {
+        jvm.invoke_static(
+            "com/skt/m3d/Graphics3D",
+            "render",
+            "(Ljavax/microedition/lcdui/Graphics;Lcom/skt/m3d/Object3D;)V",
+            (graphics.clone(), object.clone()),
+        )
+        .await
+    }
+
+    async fn pixel(jvm: &Jvm, image: &ClassInstanceRef<Image>, x: i32, y: i32) -> JvmResult<(u8, u8, u8)> {
+        let color = Image::image(jvm, image).await?.get_pixel(x, y);
+
+        Ok((color.r, color.g, color.b))
+    }
+
     #[test]
-    fn flags_round_trip_and_render_operations_are_no_op() {
+    fn flags_round_trip() {
         let result = run_jvm_test(
             Box::new([wie_midp::get_protos().into(), Box::new([Graphics3D::as_proto(), Object3D::as_proto()])]),
             |jvm| async move {
@@ -192,12 +447,24 @@ mod tests {
                         .await?
                 );
 
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok(), "JVM test failed: {result:?}");
+    }
+
+    #[test]
+    fn render_fills_triangles_with_depth_test_and_culling() {
+        let result = run_jvm_test(
+            Box::new([wie_midp::get_protos().into(), Box::new([Graphics3D::as_proto(), Object3D::as_proto()])]),
+            |jvm| async move {
                 let image: ClassInstanceRef<Image> = jvm
                     .invoke_static(
                         "javax/microedition/lcdui/Image",
                         "createImage",
                         "(II)Ljavax/microedition/lcdui/Image;",
-                        (2, 2),
+                        (16, 16),
                     )
                     .await?;
                 let graphics: ClassInstanceRef<Graphics> = jvm
@@ -209,17 +476,66 @@ mod tests {
                         (),
                     )
                     .await?;
-                let name: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "triangle").await?.into();
-                let object: ClassInstanceRef<Object3D> = jvm.new_class("com/skt/m3d/Object3D", "(Ljava/lang/String;)V", (name,)).await?.into();
 
-                let _: () = jvm
+                // upper left half of the target, facing the camera
+                let object = create_object(&jvm, &[[-10, -10, 10], [10, -10, 10], [-10, 10, 10]], &[[0, 1, 2, 0xff0000]]).await?;
+                render(&jvm, &graphics, &object).await?;
+                assert_eq!(pixel(&jvm, &image, 1, 1).await?, (0xff, 0, 0));
+                assert_eq!(pixel(&jvm, &image, 15, 15).await?, (0, 0, 0));
+
+                // the far triangle is drawn after the near one but must stay hidden
+                let _: () = jvm.invoke_static("com/skt/m3d/Graphics3D", "setZBufferEnabled", "(Z)V", (true,)).await?;
+                let near = create_object(&jvm, &covering_triangle(5), &[[0, 1, 2, 0x00ff00]]).await?;
+                let far = create_object(&jvm, &covering_triangle(20), &[[0, 1, 2, 0x0000ff]]).await?;
+                render(&jvm, &graphics, &near).await?;
+                render(&jvm, &graphics, &far).await?;
+                assert_eq!(pixel(&jvm, &image, 8, 8).await?, (0, 0xff, 0));
+
+                let _: () = jvm.invoke_static("com/skt/m3d/Graphics3D", "clearZBuffer", "()V", ()).await?;
+                render(&jvm, &graphics, &far).await?;
+                assert_eq!(pixel(&jvm, &image, 8, 8).await?, (0, 0, 0xff));
+
+                // back facing triangles are skipped once culling is enabled
+                let _: () = jvm.invoke_static("com/skt/m3d/Graphics3D", "setBackfaceCulled", "(Z)V", (true,)).await?;
+                let _: () = jvm.invoke_static("com/skt/m3d/Graphics3D", "destroyZBuffer", "()V", ()).await?;
+                let back = create_object(&jvm, &covering_triangle(5), &[[0, 2, 1, 0xffffff]]).await?;
+                render(&jvm, &graphics, &back).await?;
+                assert_eq!(pixel(&jvm, &image, 8, 8).await?, (0, 0, 0xff));
+
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok(), "JVM test failed: {result:?}");
+    }
+
+    #[test]
+    fn draw_wireframe_uses_graphics_color() {
+        let result = run_jvm_test(
+            Box::new([wie_midp::get_protos().into(), Box::new([Graphics3D::as_proto(), Object3D::as_proto()])]),
+            |jvm| async move {
+                let image: ClassInstanceRef<Image> = jvm
                     .invoke_static(
-                        "com/skt/m3d/Graphics3D",
-                        "render",
-                        "(Ljavax/microedition/lcdui/Graphics;Lcom/skt/m3d/Object3D;)V",
-                        (graphics.clone(), object.clone()),
+                        "javax/microedition/lcdui/Image",
+                        "createImage",
+                        "(II)Ljavax/microedition/lcdui/Image;",
+                        (16, 16),
+                    )
+                    .await?;
+                let graphics: ClassInstanceRef<Graphics> = jvm
+                    .invoke_virtual(
+                        &image,
+                        "javax/microedition/lcdui/Image",
+                        "getGraphics",
+                        "()Ljavax/microedition/lcdui/Graphics;",
+                        (),
                     )
                     .await?;
+                let _: () = jvm
+                    .invoke_virtual(&graphics, "javax/microedition/lcdui/Graphics", "setColor", "(I)V", (0x00ff00,))
+                    .await?;
+
+                let object = create_object(&jvm, &[[-10, -10, 10], [10, -10, 10], [-10, 10, 10]], &[[0, 1, 2, 0xff0000]]).await?;
                 let _: () = jvm
                     .invoke_static(
                         "com/skt/m3d/Graphics3D",
@@ -229,6 +545,9 @@ mod tests {
                     )
                     .await?;
 
+                assert_eq!(pixel(&jvm, &image, 4, 0).await?, (0, 0xff, 0));
+                assert_eq!(pixel(&jvm, &image, 4, 4).await?, (0, 0, 0));
+
                 Ok(())
             },
         );
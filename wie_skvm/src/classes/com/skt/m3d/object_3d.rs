@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
@@ -7,7 +7,12 @@ use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::com::skt::m::MathFP;
+
+// the rotation/scale part of the matrix is fixed point, the translation column is in model units
 const MATRIX_SCALE: i32 = 1_000_000_000;
+const DEGREE: i64 = 17_453_293;
+const IDENTITY: [i64; 12] = [MATRIX_SCALE as _, 0, 0, 0, 0, MATRIX_SCALE as _, 0, 0, 0, 0, MATRIX_SCALE as _, 0];
 
 // class com.skt.m3d.Object3D
 pub struct Object3D;
@@ -39,7 +44,12 @@ impl Object3D {
                 JavaMethodProto::new("setVertices", "([I[I[I)V", Self::set_vertices, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("translate", "(III)V", Self::translate, MethodAccessFlags::PUBLIC),
             ],
-            fields: vec![JavaFieldProto::new("name", "Ljava/lang/String;", FieldAccessFlags::PRIVATE)],
+            fields: vec![
+                JavaFieldProto::new("name", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("vertices", "[I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("triangles", "[I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("matrix", "[J", FieldAccessFlags::PRIVATE),
+            ],
             access_flags: ClassAccessFlags::PUBLIC,
         }
     }
@@ -48,14 +58,18 @@ impl Object3D {
         tracing::debug!("com.skt.m3d.Object3D::<init>({this:?}, {name:?})");
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
-        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await
+        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await?;
+
+        Self::store_ints(jvm, &mut this, "vertices", &[]).await?;
+        Self::store_ints(jvm, &mut this, "triangles", &[]).await?;
+        Self::store_matrix(jvm, &mut this, &IDENTITY).await
     }
 
     #[allow(clippy::too_many_arguments)]
     async fn init_with_geometry(
         jvm: &Jvm,
         _context: &mut WieJvmContext,
-        mut this: ClassInstanceRef<Self>,
+        this: ClassInstanceRef<Self>,
         name: ClassInstanceRef<String>,
         vertices_x: ClassInstanceRef<Array<i32>>,
         vertices_y: ClassInstanceRef<Array<i32>>,
@@ -65,68 +79,109 @@ impl Object3D {
         triangles_c: ClassInstanceRef<Array<i32>>,
         triangle_colors: ClassInstanceRef<Array<i32>>,
     ) -> JvmResult<()> {
-        tracing::warn!(
-            "stub com.skt.m3d.Object3D::<init>({this:?}, {name:?}, {vertices_x:?}, {vertices_y:?}, {vertices_z:?}, {triangles_a:?}, {triangles_b:?}, {triangles_c:?}, {triangle_colors:?})"
+        tracing::debug!(
+            "com.skt.m3d.Object3D::<init>({this:?}, {name:?}, {vertices_x:?}, {vertices_y:?}, {vertices_z:?}, {triangles_a:?}, {triangles_b:?}, {triangles_c:?}, {triangle_colors:?})"
         );
 
-        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
-        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await
+        let _: () = jvm
+            .invoke_special(&this, "com/skt/m3d/Object3D", "<init>", "(Ljava/lang/String;)V", (name,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                &this,
+                "com/skt/m3d/Object3D",
+                "setVertices",
+                "([I[I[I)V",
+                (vertices_x, vertices_y, vertices_z),
+            )
+            .await?;
+        jvm.invoke_virtual(
+            &this,
+            "com/skt/m3d/Object3D",
+            "setTriangles",
+            "([I[I[I[I)V",
+            (triangles_a, triangles_b, triangles_c, triangle_colors),
+        )
+        .await
     }
 
     async fn add_triangle(
-        _jvm: &Jvm,
+        jvm: &Jvm,
         _context: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        mut this: ClassInstanceRef<Self>,
         vertex_a: i32,
         vertex_b: i32,
         vertex_c: i32,
         color: i32,
     ) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Object3D::addTriangle({this:?}, {vertex_a}, {vertex_b}, {vertex_c}, {color})");
-        Ok(())
+        tracing::debug!("com.skt.m3d.Object3D::addTriangle({this:?}, {vertex_a}, {vertex_b}, {vertex_c}, {color})");
+
+        let mut triangles = Self::load_ints(jvm, &this, "triangles").await?;
+        triangles.extend([vertex_a, vertex_b, vertex_c, color]);
+
+        Self::store_ints(jvm, &mut this, "triangles", &triangles).await
     }
 
-    async fn add_vertex(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Object3D::addVertex({this:?}, {x}, {y}, {z})");
-        Ok(())
+    async fn add_vertex(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m3d.Object3D::addVertex({this:?}, {x}, {y}, {z})");
+
+        let mut vertices = Self::load_ints(jvm, &this, "vertices").await?;
+        vertices.extend([x, y, z]);
+
+        Self::store_ints(jvm, &mut this, "vertices", &vertices).await
     }
 
     async fn get_matrix_row_0(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Array<i32>>> {
-        tracing::warn!("stub com.skt.m3d.Object3D::getMatrixRow0({this:?})");
+        tracing::debug!("com.skt.m3d.Object3D::getMatrixRow0({this:?})");
 
-        let mut row = jvm.instantiate_array("I", 4).await?;
-        jvm.store_array(&mut row, 0, [MATRIX_SCALE, 0, 0, 0]).await?;
-        Ok(row.into())
+        Self::matrix_row(jvm, &this, 0).await
     }
 
     async fn get_matrix_row_1(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Array<i32>>> {
-        tracing::warn!("stub com.skt.m3d.Object3D::getMatrixRow1({this:?})");
+        tracing::debug!("com.skt.m3d.Object3D::getMatrixRow1({this:?})");
 
-        let mut row = jvm.instantiate_array("I", 4).await?;
-        jvm.store_array(&mut row, 0, [0, MATRIX_SCALE, 0, 0]).await?;
-        Ok(row.into())
+        Self::matrix_row(jvm, &this, 1).await
     }
 
     async fn get_matrix_row_2(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Array<i32>>> {
-        tracing::warn!("stub com.skt.m3d.Object3D::getMatrixRow2({this:?})");
+        tracing::debug!("com.skt.m3d.Object3D::getMatrixRow2({this:?})");
 
-        let mut row = jvm.instantiate_array("I", 4).await?;
-        jvm.store_array(&mut row, 0, [0, 0, MATRIX_SCALE, 0]).await?;
-        Ok(row.into())
+        Self::matrix_row(jvm, &this, 2).await
     }
 
     async fn get_name(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
         jvm.get_field(&this, "name", "Ljava/lang/String;").await
     }
 
-    async fn rotate(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Object3D::rotate({this:?}, {x}, {y}, {z})");
-        Ok(())
+    async fn rotate(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m3d.Object3D::rotate({this:?}, {x}, {y}, {z})");
+
+        // angles are in degrees, applied around x, then y, then z
+        let (sin_x, cos_x) = Self::sin_cos(x);
+        let (sin_y, cos_y) = Self::sin_cos(y);
+        let (sin_z, cos_z) = Self::sin_cos(z);
+        let scale = MATRIX_SCALE as i64;
+
+        let rotation_x = [scale, 0, 0, 0, 0, cos_x, -sin_x, 0, 0, sin_x, cos_x, 0];
+        let rotation_y = [cos_y, 0, sin_y, 0, 0, scale, 0, 0, -sin_y, 0, cos_y, 0];
+        let rotation_z = [cos_z, -sin_z, 0, 0, sin_z, cos_z, 0, 0, 0, 0, scale, 0];
+
+        let matrix = Self::load_matrix(jvm, &this).await?;
+        let matrix = Self::multiply(&rotation_z, &Self::multiply(&rotation_y, &Self::multiply(&rotation_x, &matrix)));
+
+        Self::store_matrix(jvm, &mut this, &matrix).await
     }
 
-    async fn scale(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Object3D::scale({this:?}, {x}, {y}, {z})");
-        Ok(())
+    async fn scale(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m3d.Object3D::scale({this:?}, {x}, {y}, {z})");
+
+        let scale = MATRIX_SCALE as i64;
+        let scaling = [x as i64 * scale, 0, 0, 0, 0, y as i64 * scale, 0, 0, 0, 0, z as i64 * scale, 0];
+
+        let matrix = Self::load_matrix(jvm, &this).await?;
+        let matrix = Self::multiply(&scaling, &matrix);
+
+        Self::store_matrix(jvm, &mut this, &matrix).await
     }
 
     async fn set_name(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> JvmResult<()> {
@@ -134,33 +189,168 @@ impl Object3D {
     }
 
     async fn set_triangles(
-        _jvm: &Jvm,
+        jvm: &Jvm,
         _context: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        mut this: ClassInstanceRef<Self>,
         vertices_a: ClassInstanceRef<Array<i32>>,
         vertices_b: ClassInstanceRef<Array<i32>>,
         vertices_c: ClassInstanceRef<Array<i32>>,
         colors: ClassInstanceRef<Array<i32>>,
     ) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Object3D::setTriangles({this:?}, {vertices_a:?}, {vertices_b:?}, {vertices_c:?}, {colors:?})");
-        Ok(())
+        tracing::debug!("com.skt.m3d.Object3D::setTriangles({this:?}, {vertices_a:?}, {vertices_b:?}, {vertices_c:?}, {colors:?})");
+
+        let columns = Self::load_columns(jvm, [vertices_a, vertices_b, vertices_c, colors]).await?;
+        let triangles = (0..columns[0].len())
+            .flat_map(|i| columns.iter().map(move |column| column[i]))
+            .collect::<Vec<_>>();
+
+        Self::store_ints(jvm, &mut this, "triangles", &triangles).await
     }
 
     async fn set_vertices(
-        _jvm: &Jvm,
+        jvm: &Jvm,
         _context: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        mut this: ClassInstanceRef<Self>,
         vertices_x: ClassInstanceRef<Array<i32>>,
         vertices_y: ClassInstanceRef<Array<i32>>,
         vertices_z: ClassInstanceRef<Array<i32>>,
     ) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Object3D::setVertices({this:?}, {vertices_x:?}, {vertices_y:?}, {vertices_z:?})");
-        Ok(())
+        tracing::debug!("com.skt.m3d.Object3D::setVertices({this:?}, {vertices_x:?}, {vertices_y:?}, {vertices_z:?})");
+
+        let columns = Self::load_columns(jvm, [vertices_x, vertices_y, vertices_z]).await?;
+        let vertices = (0..columns[0].len())
+            .flat_map(|i| columns.iter().map(move |column| column[i]))
+            .collect::<Vec<_>>();
+
+        Self::store_ints(jvm, &mut this, "vertices", &vertices).await
+    }
+
+    async fn translate(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m3d.Object3D::translate({this:?}, {x}, {y}, {z})");
+
+        let mut matrix = Self::load_matrix(jvm, &this).await?;
+        matrix[3] += x as i64;
+        matrix[7] += y as i64;
+        matrix[11] += z as i64;
+
+        Self::store_matrix(jvm, &mut this, &matrix).await
+    }
+
+    /// Vertices of this object with the object transform applied.
+    pub(crate) async fn transformed_vertices(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Vec<[i64; 3]>> {
+        let matrix = Self::load_matrix(jvm, this).await?;
+        let vertices = Self::load_ints(jvm, this, "vertices").await?;
+
+        Ok(vertices
+            .chunks_exact(3)
+            .map(|vertex| {
+                let mut result = [0; 3];
+                for (row, value) in result.iter_mut().enumerate() {
+                    let linear = (0..3)
+                        .map(|column| matrix[row * 4 + column] as i128 * vertex[column] as i128)
+                        .sum::<i128>();
+                    *value = Self::round_div(linear, MATRIX_SCALE as i128) as i64 + matrix[row * 4 + 3];
+                }
+                result
+            })
+            .collect())
+    }
+
+    /// Triangles of this object as `[vertex_a, vertex_b, vertex_c, color]`.
+    pub(crate) async fn triangles(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Vec<[i32; 4]>> {
+        let triangles = Self::load_ints(jvm, this, "triangles").await?;
+
+        Ok(triangles.chunks_exact(4).map(|x| [x[0], x[1], x[2], x[3]]).collect())
+    }
+
+    async fn matrix_row(jvm: &Jvm, this: &ClassInstanceRef<Self>, row: usize) -> JvmResult<ClassInstanceRef<Array<i32>>> {
+        let matrix = Self::load_matrix(jvm, this).await?;
+        let values = matrix[row * 4..row * 4 + 4]
+            .iter()
+            .map(|&x| x.clamp(i32::MIN as _, i32::MAX as _) as i32)
+            .collect::<Vec<_>>();
+
+        let mut result = jvm.instantiate_array("I", 4).await?;
+        jvm.store_array(&mut result, 0, values).await?;
+        Ok(result.into())
+    }
+
+    async fn load_columns<const N: usize>(jvm: &Jvm, arrays: [ClassInstanceRef<Array<i32>>; N]) -> JvmResult<[Vec<i32>; N]> {
+        let mut columns = [const { Vec::new() }; N];
+        for (column, array) in columns.iter_mut().zip(arrays) {
+            if array.is_null() {
+                return Err(jvm.exception("java/lang/NullPointerException", "geometry array must not be null").await);
+            }
+            let length = jvm.array_length(&array).await?;
+            *column = jvm.load_array(&array, 0, length).await?;
+        }
+
+        if columns.iter().any(|column| column.len() != columns[0].len()) {
+            return Err(jvm
+                .exception("java/lang/IllegalArgumentException", "geometry arrays must have the same length")
+                .await);
+        }
+
+        Ok(columns)
     }
 
-    async fn translate(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32, z: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m3d.Object3D::translate({this:?}, {x}, {y}, {z})");
-        Ok(())
+    async fn load_ints(jvm: &Jvm, this: &ClassInstanceRef<Self>, field: &str) -> JvmResult<Vec<i32>> {
+        let array: ClassInstanceRef<Array<i32>> = jvm.get_field(this, field, "[I").await?;
+        let length = jvm.array_length(&array).await?;
+
+        jvm.load_array(&array, 0, length).await
+    }
+
+    async fn store_ints(jvm: &Jvm, this: &mut ClassInstanceRef<Self>, field: &str, values: &[i32]) -> JvmResult<()> {
+        let mut array = jvm.instantiate_array("I", values.len()).await?;
+        jvm.store_array(&mut array, 0, values.to_vec()).await?;
+
+        jvm.put_field(this, field, "[I", array).await
+    }
+
+    async fn load_matrix(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<[i64; 12]> {
+        let array: ClassInstanceRef<Array<i64>> = jvm.get_field(this, "matrix", "[J").await?;
+        let values: Vec<i64> = jvm.load_array(&array, 0, 12).await?;
+
+        let mut matrix = [0; 12];
+        matrix.copy_from_slice(&values);
+        Ok(matrix)
+    }
+
+    async fn store_matrix(jvm: &Jvm, this: &mut ClassInstanceRef<Self>, matrix: &[i64; 12]) -> JvmResult<()> {
+        let mut array = jvm.instantiate_array("J", 12).await?;
+        jvm.store_array(&mut array, 0, matrix.to_vec()).await?;
+
+        jvm.put_field(this, "matrix", "[J", array).await
+    }
+
+    // composes two affine transforms, `a` is applied after `b`
+    fn multiply(a: &[i64; 12], b: &[i64; 12]) -> [i64; 12] {
+        let scale = MATRIX_SCALE as i128;
+        let mut result = [0; 12];
+
+        for row in 0..3 {
+            for column in 0..4 {
+                let value = (0..3).map(|k| a[row * 4 + k] as i128 * b[k * 4 + column] as i128).sum::<i128>() / scale;
+                result[row * 4 + column] = if column == 3 { value + a[row * 4 + 3] as i128 } else { value } as i64;
+            }
+        }
+
+        result
+    }
+
+    fn round_div(value: i128, divisor: i128) -> i128 {
+        if value < 0 {
+            (value - divisor / 2) / divisor
+        } else {
+            (value + divisor / 2) / divisor
+        }
+    }
+
+    fn sin_cos(degrees: i32) -> (i64, i64) {
+        let angle = degrees as i128 * DEGREE as i128;
+
+        (MathFP::sin_fixed(angle), MathFP::sin_fixed(angle + 90 * DEGREE as i128))
     }
 }
 
@@ -209,13 +399,6 @@ mod tests {
             let _: () = jvm
                 .invoke_virtual(&object, "com/skt/m3d/Object3D", "addTriangle", "(IIII)V", (0, 1, 2, 0xff00ff))
                 .await?;
-            let _: () = jvm
-                .invoke_virtual(&object, "com/skt/m3d/Object3D", "rotate", "(III)V", (10, 20, 30))
-                .await?;
-            let _: () = jvm
-                .invoke_virtual(&object, "com/skt/m3d/Object3D", "translate", "(III)V", (40, 50, 60))
-                .await?;
-            let _: () = jvm.invoke_virtual(&object, "com/skt/m3d/Object3D", "scale", "(III)V", (2, 3, 4)).await?;
 
             let row_0: ClassInstanceRef<Array<i32>> = jvm.invoke_virtual(&object, "com/skt/m3d/Object3D", "getMatrixRow0", "()[I", ()).await?;
             let row_1: ClassInstanceRef<Array<i32>> = jvm.invoke_virtual(&object, "com/skt/m3d/Object3D", "getMatrixRow1", "()[I", ()).await?;
@@ -231,6 +414,42 @@ mod tests {
                 row_0.instance.as_ref().map(|instance| instance.identity())
             );
 
+            assert_eq!(Object3D::transformed_vertices(&jvm, &object).await?, [[1, 2, 3]]);
+            assert_eq!(Object3D::triangles(&jvm, &object).await?, [[0, 1, 2, 0xff00ff]]);
+
+            Ok(())
+        });
+
+        assert!(result.is_ok(), "JVM test failed: {result:?}");
+    }
+
+    #[test]
+    fn transforms_accumulate_into_matrix() {
+        let result = run_jvm_test(Box::new([[Object3D::as_proto()].into()]), |jvm| async move {
+            let name: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "cube").await?.into();
+            let object: ClassInstanceRef<Object3D> = jvm.new_class("com/skt/m3d/Object3D", "(Ljava/lang/String;)V", (name,)).await?.into();
+
+            let _: () = jvm
+                .invoke_virtual(&object, "com/skt/m3d/Object3D", "addVertex", "(III)V", (10, 0, 0))
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&object, "com/skt/m3d/Object3D", "rotate", "(III)V", (0, 0, 90))
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&object, "com/skt/m3d/Object3D", "translate", "(III)V", (40, 50, 60))
+                .await?;
+
+            let row_0: ClassInstanceRef<Array<i32>> = jvm.invoke_virtual(&object, "com/skt/m3d/Object3D", "getMatrixRow0", "()[I", ()).await?;
+            let row_0 = jvm.load_array::<i32>(&row_0, 0, 4).await?;
+            assert!(row_0[0].abs() < 1000);
+            assert!((row_0[1] + MATRIX_SCALE).abs() < 1000);
+            assert_eq!(row_0[3], 40);
+
+            assert_eq!(Object3D::transformed_vertices(&jvm, &object).await?, [[40, 60, 60]]);
+
+            let _: () = jvm.invoke_virtual(&object, "com/skt/m3d/Object3D", "scale", "(III)V", (2, 3, 4)).await?;
+            assert_eq!(Object3D::transformed_vertices(&jvm, &object).await?, [[80, 180, 240]]);
+
             Ok(())
         });
 
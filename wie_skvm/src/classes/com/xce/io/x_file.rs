@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{format, string::String as RustString, vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
@@ -179,6 +179,7 @@ impl XFile {
                 JavaFieldProto::new("is", "Ljava/io/InputStream;", FieldAccessFlags::PRIVATE),
                 JavaFieldProto::new("os", "Ljava/io/OutputStream;", FieldAccessFlags::PRIVATE),
                 JavaFieldProto::new("raf", "Ljava/io/RandomAccessFile;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("entries", "[Ljava/lang/String;", FieldAccessFlags::PRIVATE),
             ],
             access_flags: ClassAccessFlags::PUBLIC,
         }
@@ -280,7 +281,23 @@ impl XFile {
             jvm.put_field(&mut this, "type", "I", FILE_JAR).await?;
         } else {
             if mode == READ_DIRECTORY {
-                return Err(jvm.exception("java/io/IOException", "Directory reads are not supported").await);
+                let guest_path = JavaLangString::to_rust_string(jvm, &name).await?;
+                let Some(entries) = context.system().filesystem().list(&guest_path).await else {
+                    return Err(jvm.exception("java/io/IOException", "Directory not found").await);
+                };
+
+                let mut names = Vec::with_capacity(entries.len());
+                for entry in entries {
+                    // directories are reported with a trailing separator
+                    let entry_name = if entry.is_dir { format!("{}/", entry.name) } else { entry.name };
+                    names.push(JavaLangString::from_rust_string(jvm, &entry_name).await?);
+                }
+                let mut array = jvm.instantiate_array("Ljava/lang/String;", names.len()).await?;
+                jvm.store_array(&mut array, 0, names).await?;
+
+                jvm.put_field(&mut this, "type", "I", DIRECTORY).await?;
+                jvm.put_field(&mut this, "entries", "[Ljava/lang/String;", array).await?;
+                return Ok(());
             }
             if mode != READ && mode != WRITE && mode != READ_WRITE {
                 return Err(jvm.exception("java/lang/IllegalArgumentException", "Invalid file mode").await);
@@ -513,13 +530,15 @@ impl XFile {
         Ok(length)
     }
 
-    async fn close(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+    async fn close(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
         tracing::debug!("com.xce.io.XFile::close({this:?})");
 
         let file_type: i32 = jvm.get_field(&this, "type", "I").await?;
         let mode: i32 = jvm.get_field(&this, "mode", "I").await?;
         if file_type == STDSTREAM {
             return Ok(());
+        } else if file_type == DIRECTORY {
+            jvm.put_field(&mut this, "entries", "[Ljava/lang/String;", None).await?;
         } else if mode == READ_RESOURCE {
             let is: ClassInstanceRef<InputStream> = jvm.get_field(&this, "is", "Ljava/io/InputStream;").await?;
             let _: () = jvm.invoke_virtual(&is, "java/io/InputStream", "close", "()V", ()).await?;
@@ -551,7 +570,7 @@ impl XFile {
 
         let file_type: i32 = jvm.get_field(&this, "type", "I").await?;
         let mode: i32 = jvm.get_field(&this, "mode", "I").await?;
-        if file_type == STDSTREAM || mode == READ_RESOURCE {
+        if file_type == STDSTREAM || file_type == DIRECTORY || mode == READ_RESOURCE {
             return Err(jvm.exception("java/io/IOException", "File is not seekable").await);
         }
 
@@ -581,49 +600,106 @@ impl XFile {
         Ok(new_pos as i32)
     }
 
-    async fn read_dir(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
-        tracing::warn!("unsupported com.xce.io.XFile::readdir({this:?})");
+    async fn read_dir(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("com.xce.io.XFile::readdir({this:?})");
+
+        let file_type: i32 = jvm.get_field(&this, "type", "I").await?;
+        let entries: ClassInstanceRef<Array<ClassInstanceRef<String>>> = jvm.get_field(&this, "entries", "[Ljava/lang/String;").await?;
+        if file_type != DIRECTORY || entries.is_null() {
+            return Err(jvm.exception("java/io/IOException", "File is not open for directory reading").await);
+        }
+
+        let index: i32 = jvm.get_field(&this, "offset", "I").await?;
+        if index as usize >= jvm.array_length(&entries).await? {
+            return Ok(ClassInstanceRef::new(None));
+        }
+        let entry: Vec<ClassInstanceRef<String>> = jvm.load_array(&entries, index as usize, 1).await?;
+        jvm.put_field(&mut this, "offset", "I", index + 1).await?;
 
-        Err(jvm.exception("java/io/IOException", "Directory reads are not supported").await)
+        Ok(entry.into_iter().next().unwrap())
     }
 
-    async fn make_dir(jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("unsupported com.xce.io.XFile::mkdir({name:?})");
+    async fn make_dir(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("com.xce.io.XFile::mkdir({name:?})");
 
-        if name.is_null() {
-            return Err(jvm.exception("java/lang/NullPointerException", "dirname is null").await);
+        let guest_path = Self::dir_path(jvm, context, name).await?;
+        if !context.system().filesystem().create_dir(&guest_path).await {
+            return Err(jvm.exception("java/io/IOException", "Unable to create directory").await);
         }
-        Err(jvm.exception("java/io/IOException", "Directory creation is not supported").await)
+
+        Ok(())
     }
 
-    async fn remove_dir(jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("unsupported com.xce.io.XFile::rmdir({name:?})");
+    async fn remove_dir(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("com.xce.io.XFile::rmdir({name:?})");
 
-        if name.is_null() {
-            return Err(jvm.exception("java/lang/NullPointerException", "dirname is null").await);
+        let guest_path = Self::dir_path(jvm, context, name).await?;
+        if !context.system().filesystem().remove_dir(&guest_path).await {
+            return Err(jvm.exception("java/io/IOException", "Unable to remove directory").await);
         }
-        Err(jvm.exception("java/io/IOException", "Directory removal is not supported").await)
+
+        Ok(())
     }
 
-    async fn remove_dir_recursive(jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("unsupported com.xce.io.XFile::rmrdir({name:?})");
+    async fn remove_dir_recursive(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("com.xce.io.XFile::rmrdir({name:?})");
+
+        let guest_path = Self::dir_path(jvm, context, name).await?;
+        let filesystem = context.system().filesystem();
+
+        // collect the tree breadth first, then remove it deepest first
+        let mut directories = vec![guest_path];
+        let mut index = 0;
+        while index < directories.len() {
+            let Some(entries) = filesystem.list(&directories[index]).await else {
+                return Err(jvm.exception("java/io/IOException", "Directory not found").await);
+            };
+            for entry in entries {
+                let path = format!("{}/{}", directories[index], entry.name);
+                if entry.is_dir {
+                    directories.push(path);
+                } else if !filesystem.remove(&path).await {
+                    return Err(jvm.exception("java/io/IOException", "Unable to remove file").await);
+                }
+            }
+            index += 1;
+        }
 
-        if name.is_null() {
-            return Err(jvm.exception("java/lang/NullPointerException", "dirname is null").await);
+        for directory in directories.iter().rev() {
+            if !filesystem.remove_dir(directory).await {
+                return Err(jvm.exception("java/io/IOException", "Unable to remove directory").await);
+            }
         }
-        Err(jvm.exception("java/io/IOException", "Recursive directory removal is not supported").await)
+
+        Ok(())
     }
 
-    async fn fs_used(_jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<i32> {
-        tracing::warn!("stub com.xce.io.XFile::fsused()");
+    async fn fs_used(_jvm: &Jvm, context: &mut WieJvmContext) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::fsused()");
 
-        Ok(0)
+        let used = context.system().storage().used().await;
+
+        Ok(used.min(i32::MAX as u64) as _)
     }
 
-    async fn fs_available(_jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<i32> {
-        tracing::warn!("stub com.xce.io.XFile::fsavail()");
+    async fn fs_available(_jvm: &Jvm, context: &mut WieJvmContext) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::fsavail()");
 
-        Ok(0)
+        let available = context.system().storage().available().await;
+
+        Ok(available.min(i32::MAX as u64) as _)
+    }
+
+    async fn dir_path(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<RustString> {
+        if name.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "dirname is null").await);
+        }
+        let guest_path = JavaLangString::to_rust_string(jvm, &name).await?;
+        if !context.system().filesystem().is_valid_path(guest_path.trim_end_matches('/')) {
+            return Err(jvm.exception("java/io/IOException", "Invalid directory path").await);
+        }
+
+        Ok(guest_path.trim_end_matches('/').into())
     }
 
     pub async fn raf(jvm: &Jvm, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<RandomAccessFile>> {
@@ -974,4 +1050,57 @@ mod tests {
 
         assert!(result.is_ok(), "JVM test failed: {result:?}");
     }
+
+    #[test]
+    fn directory_read_and_recursive_removal() {
+        let result = run_jvm_test(
+            Box::new([Box::new([XFile::as_proto(), FileInputStream::as_proto(), FileOutputStream::as_proto()])]),
+            |jvm| async move {
+                let write: i32 = jvm.get_static_field("com/xce/io/XFile", "WRITE", "I").await?;
+                let read_directory: i32 = jvm.get_static_field("com/xce/io/XFile", "READ_DIRECTORY", "I").await?;
+
+                let dirname = JavaLangString::from_rust_string(&jvm, "data/sub").await?;
+                let _: () = jvm
+                    .invoke_static("com/xce/io/XFile", "mkdir", "(Ljava/lang/String;)V", (dirname,))
+                    .await?;
+                let name = JavaLangString::from_rust_string(&jvm, "data/file.dat").await?;
+                let file: ClassInstanceRef<XFile> = jvm.new_class("com/xce/io/XFile", "(Ljava/lang/String;I)V", (name, write)).await?.into();
+                let _: () = jvm.invoke_virtual(&file, "com/xce/io/XFile", "close", "()V", ()).await?;
+
+                let dirname = JavaLangString::from_rust_string(&jvm, "data").await?;
+                let directory: ClassInstanceRef<XFile> = jvm
+                    .new_class("com/xce/io/XFile", "(Ljava/lang/String;I)V", (dirname, read_directory))
+                    .await?
+                    .into();
+                let mut entries = alloc::vec::Vec::new();
+                loop {
+                    let entry: ClassInstanceRef<String> = jvm
+                        .invoke_virtual(&directory, "com/xce/io/XFile", "readdir", "()Ljava/lang/String;", ())
+                        .await?;
+                    if entry.is_null() {
+                        break;
+                    }
+                    entries.push(JavaLangString::to_rust_string(&jvm, &entry).await?);
+                }
+                assert_eq!(entries, ["file.dat", "sub/"]);
+                let _: () = jvm.invoke_virtual(&directory, "com/xce/io/XFile", "close", "()V", ()).await?;
+
+                let dirname = JavaLangString::from_rust_string(&jvm, "data").await?;
+                let not_empty: JvmResult<()> = jvm.invoke_static("com/xce/io/XFile", "rmdir", "(Ljava/lang/String;)V", (dirname,)).await;
+                assert!(matches!(not_empty, Err(JavaError::JavaException(_))));
+
+                let dirname = JavaLangString::from_rust_string(&jvm, "data").await?;
+                let _: () = jvm
+                    .invoke_static("com/xce/io/XFile", "rmrdir", "(Ljava/lang/String;)V", (dirname,))
+                    .await?;
+                let name = JavaLangString::from_rust_string(&jvm, "data/file.dat").await?;
+                let exists: bool = jvm.invoke_static("com/xce/io/XFile", "exists", "(Ljava/lang/String;)Z", (name,)).await?;
+                assert!(!exists);
+
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok(), "JVM test failed: {result:?}");
+    }
 }
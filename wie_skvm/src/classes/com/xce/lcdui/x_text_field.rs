@@ -1,22 +1,32 @@
-use alloc::vec;
+use alloc::{format, vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::String;
-use jvm::{ClassInstanceRef, JavaChar, Jvm, Result as JvmResult};
+use jvm::{ClassInstanceRef, JavaChar, Jvm, Result as JvmResult, runtime::JavaLangString};
 
+use wie_backend::{InputMethod, InputMode, KeyPhase};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
-use wie_midp::classes::javax::microedition::lcdui::{Canvas, Graphics};
+use wie_midp::classes::{
+    javax::microedition::lcdui::{Canvas, Graphics},
+    net::wie::{MIDPKeyCode, TextInputListener},
+};
 
 // class com.xce.lcdui.XTextField
 pub struct XTextField;
 
 impl XTextField {
+    // constraints that only accept digits
+    const CONSTRAINT_MASK: i32 = 0xffff;
+    const CONSTRAINT_NUMERIC: i32 = 2;
+    const CONSTRAINT_PHONENUMBER: i32 = 3;
+    const CONSTRAINT_DECIMAL: i32 = 5;
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "com/xce/lcdui/XTextField",
             parent_class: Some("java/lang/Object"),
-            interfaces: vec![],
+            interfaces: vec!["net/wie/TextInputListener"],
             methods: vec![
                 JavaMethodProto::new(
                     "<init>",
@@ -37,6 +47,8 @@ impl XTextField {
                 JavaMethodProto::new("setFocus", "(Z)V", Self::set_focus, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("setMaxSize", "(I)V", Self::set_max_size, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("setText", "(Ljava/lang/String;)V", Self::set_text, MethodAccessFlags::PUBLIC),
+                // net.wie.TextInputListener
+                JavaMethodProto::new("keyTyped", "(I)V", Self::key_typed, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![
                 JavaFieldProto::new("text", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
@@ -48,6 +60,7 @@ impl XTextField {
                 JavaFieldProto::new("width", "I", FieldAccessFlags::PRIVATE),
                 JavaFieldProto::new("height", "I", FieldAccessFlags::PRIVATE),
                 JavaFieldProto::new("canvas", "Ljavax/microedition/lcdui/Canvas;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("imState", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
             ],
             access_flags: ClassAccessFlags::PUBLIC,
         }
@@ -55,7 +68,7 @@ impl XTextField {
 
     async fn init(
         jvm: &Jvm,
-        _context: &mut WieJvmContext,
+        context: &mut WieJvmContext,
         mut this: ClassInstanceRef<Self>,
         text: ClassInstanceRef<String>,
         max_size: i32,
@@ -79,6 +92,13 @@ impl XTextField {
         jvm.put_field(&mut this, "constraints", "I", constraints).await?;
         jvm.put_field(&mut this, "canvas", "Ljavax/microedition/lcdui/Canvas;", canvas).await?;
 
+        let mode = match constraints & Self::CONSTRAINT_MASK {
+            Self::CONSTRAINT_NUMERIC | Self::CONSTRAINT_PHONENUMBER | Self::CONSTRAINT_DECIMAL => InputMode::Numeric,
+            _ => InputMode::Korean,
+        };
+        let input_method = InputMethod::new(mode, context.system().device_profile().keypad);
+        Self::store_input_method(jvm, &mut this, &input_method).await?;
+
         Ok(())
     }
 
@@ -98,7 +118,7 @@ impl XTextField {
 
     async fn get_text(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
         tracing::debug!("com.xce.lcdui.XTextField::getText({this:?})");
-        jvm.get_field(&this, "text", "Ljava/lang/String;").await
+        Self::text_with_composing(jvm, &this).await
     }
 
     async fn has_focus(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
@@ -128,8 +148,22 @@ impl XTextField {
         jvm.put_field(&mut this, "text", "Ljava/lang/String;", text).await
     }
 
+    // the focused field receives host keyboard text typed on its canvas
     async fn set_focus(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, focus: bool) -> JvmResult<()> {
         tracing::debug!("com.xce.lcdui.XTextField::setFocus({this:?}, {focus})");
+
+        let canvas: ClassInstanceRef<Canvas> = jvm.get_field(&this, "canvas", "Ljavax/microedition/lcdui/Canvas;").await?;
+        let listener: ClassInstanceRef<TextInputListener> = if focus { this.clone().instance.into() } else { None.into() };
+        let _: () = jvm
+            .invoke_virtual(
+                &canvas,
+                "javax/microedition/lcdui/Canvas",
+                "setTextInputListener",
+                "(Lnet/wie/TextInputListener;)V",
+                (listener,),
+            )
+            .await?;
+
         jvm.put_field(&mut this, "focus", "Z", focus).await
     }
 
@@ -159,26 +193,87 @@ impl XTextField {
     async fn key_pressed(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
         tracing::debug!("com.xce.lcdui.XTextField::keyPressed({this:?}, {key_code})");
 
+        Self::handle_key(jvm, context, this, KeyPhase::Pressed, key_code).await
+    }
+
+    async fn key_repeated(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
+        tracing::debug!("com.xce.lcdui.XTextField::keyRepeated({this:?}, {key_code})");
+
+        Self::handle_key(jvm, context, this, KeyPhase::Repeated, key_code).await
+    }
+
+    async fn key_released(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
+        tracing::debug!("com.xce.lcdui.XTextField::keyReleased({this:?}, {key_code})");
+
+        Self::handle_key(jvm, context, this, KeyPhase::Released, key_code).await
+    }
+
+    // keypad keys go through the input method, other printable keys are entered as is
+    async fn handle_key(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, phase: KeyPhase, key_code: i32) -> JvmResult<()> {
         let focus: bool = jvm.get_field(&this, "focus", "Z").await?;
         if !focus {
             return Ok(());
         }
 
+        let text: ClassInstanceRef<String> = jvm.get_field(&this, "text", "Ljava/lang/String;").await?;
+        let mut text = JavaLangString::to_rust_string(jvm, &text).await?;
+        let max_size: i32 = jvm.get_field(&this, "maxSize", "I").await?;
+
+        let mut input_method = Self::input_method(jvm, &this).await?;
+        let room = (max_size as usize).saturating_sub(text.chars().count());
+        let result = match MIDPKeyCode::from_raw(key_code) {
+            Some(key) => input_method.key_event(phase, key.to_key_code(), context.system().platform().now(), room),
+            None if phase == KeyPhase::Released => None,
+            None => Some(Default::default()),
+        };
+        let Some(result) = result else {
+            return Ok(());
+        };
+
+        text.push_str(&result.committed);
+        if !result.consumed && phase != KeyPhase::Released {
+            text.push_str(&input_method.commit());
+        }
+        Self::store_input_method(jvm, &mut this, &input_method).await?;
+
+        let string = JavaLangString::from_rust_string(jvm, &text).await?;
+        jvm.put_field(&mut this, "text", "Ljava/lang/String;", string).await?;
+
+        if result.consumed || phase == KeyPhase::Released {
+            return Ok(());
+        }
+        if key_code == MIDPKeyCode::CLEAR as i32 {
+            text.pop();
+            let string = JavaLangString::from_rust_string(jvm, &text).await?;
+            return jvm.put_field(&mut this, "text", "Ljava/lang/String;", string).await;
+        }
         if (32..=126).contains(&key_code) {
             return Self::input_char(jvm, context, this, key_code as JavaChar).await;
         }
         Ok(())
     }
 
-    async fn key_repeated(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
-        tracing::debug!("com.xce.lcdui.XTextField::keyRepeated({this:?}, {key_code})");
-        Self::key_pressed(jvm, context, this, key_code).await
-    }
+    // host keyboard text finishes the composition and is appended as is
+    async fn key_typed(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, chr: i32) -> JvmResult<()> {
+        tracing::debug!("com.xce.lcdui.XTextField::keyTyped({this:?}, {chr})");
+
+        let Some(chr) = char::from_u32(chr as u32) else {
+            return Ok(());
+        };
 
-    async fn key_released(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.xce.lcdui.XTextField::keyReleased({this:?}, {key_code})");
+        let mut input_method = Self::input_method(jvm, &this).await?;
+        let committed = input_method.commit();
+        Self::store_input_method(jvm, &mut this, &input_method).await?;
 
-        Ok(())
+        let text: ClassInstanceRef<String> = jvm.get_field(&this, "text", "Ljava/lang/String;").await?;
+        let mut text = format!("{}{committed}", JavaLangString::to_rust_string(jvm, &text).await?);
+        let max_size: i32 = jvm.get_field(&this, "maxSize", "I").await?;
+        if text.chars().count() < max_size as usize {
+            text.push(chr);
+        }
+
+        let string = JavaLangString::from_rust_string(jvm, &text).await?;
+        jvm.put_field(&mut this, "text", "Ljava/lang/String;", string).await
     }
 
     async fn paint(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
@@ -188,7 +283,7 @@ impl XTextField {
             return Err(jvm.exception("java/lang/NullPointerException", "graphics is null").await);
         }
 
-        let text: ClassInstanceRef<String> = jvm.get_field(&this, "text", "Ljava/lang/String;").await?;
+        let text = Self::text_with_composing(jvm, &this).await?;
         let x: i32 = jvm.get_field(&this, "x", "I").await?;
         let y: i32 = jvm.get_field(&this, "y", "I").await?;
         let width: i32 = jvm.get_field(&this, "width", "I").await?;
@@ -269,10 +364,39 @@ impl XTextField {
             return Err(jvm.exception("java/lang/NullPointerException", "text is null").await);
         }
 
+        // text still being composed is dropped along with the old text
+        let mut input_method = Self::input_method(jvm, &this).await?;
+        input_method.commit();
+        Self::store_input_method(jvm, &mut this, &input_method).await?;
+
         let max_size: i32 = jvm.get_field(&this, "maxSize", "I").await?;
         let text = Self::truncate_text(jvm, text, max_size).await?;
         jvm.put_field(&mut this, "text", "Ljava/lang/String;", text).await
     }
+
+    async fn text_with_composing(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        let text: ClassInstanceRef<String> = jvm.get_field(this, "text", "Ljava/lang/String;").await?;
+        let composing = Self::input_method(jvm, this).await?.composing();
+        if composing.is_empty() {
+            return Ok(text);
+        }
+
+        let text = JavaLangString::to_rust_string(jvm, &text).await?;
+        Ok(JavaLangString::from_rust_string(jvm, &format!("{text}{composing}")).await?.into())
+    }
+
+    async fn input_method(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<InputMethod> {
+        let state: ClassInstanceRef<String> = jvm.get_field(this, "imState", "Ljava/lang/String;").await?;
+        let state = JavaLangString::to_rust_string(jvm, &state).await?;
+
+        Ok(InputMethod::restore(&state).unwrap())
+    }
+
+    async fn store_input_method(jvm: &Jvm, this: &mut ClassInstanceRef<Self>, input_method: &InputMethod) -> JvmResult<()> {
+        let state = JavaLangString::from_rust_string(jvm, &input_method.save()).await?;
+
+        jvm.put_field(this, "imState", "Ljava/lang/String;", state).await
+    }
 }
 
 #[cfg(test)]
@@ -522,6 +646,100 @@ mod tests {
         assert!(result.is_ok(), "JVM test failed: {result:?}");
     }
 
+    #[test]
+    fn keypad_keys_compose_hangul() {
+        let result = run_jvm_test(
+            Box::new([
+                wie_midp::get_protos().into(),
+                [XTextField::as_proto(), TrackingCanvas::as_proto(), TrackingGraphics::as_proto()].into(),
+            ]),
+            |jvm| async move {
+                let canvas: ClassInstanceRef<Canvas> = jvm.new_class("test/TrackingCanvas", "()V", ()).await?.into();
+                let initial: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "").await?.into();
+                let field: ClassInstanceRef<XTextField> = jvm
+                    .new_class(
+                        "com/xce/lcdui/XTextField",
+                        "(Ljava/lang/String;IILjavax/microedition/lcdui/Canvas;)V",
+                        (initial, 8, 0, canvas),
+                    )
+                    .await?
+                    .into();
+                let _: () = jvm
+                    .invoke_virtual(&field, "com/xce/lcdui/XTextField", "setFocus", "(Z)V", (true,))
+                    .await?;
+
+                // 천지인: ㅎ(88) ㅏ(12) ㄴ(5), then clear removes the ㄴ still being composed
+                for key in "88125".chars() {
+                    let _: () = jvm
+                        .invoke_virtual(&field, "com/xce/lcdui/XTextField", "keyPressed", "(I)V", (key as i32,))
+                        .await?;
+                }
+                let text: ClassInstanceRef<String> = jvm
+                    .invoke_virtual(&field, "com/xce/lcdui/XTextField", "getText", "()Ljava/lang/String;", ())
+                    .await?;
+                assert_eq!(JavaLangString::to_rust_string(&jvm, &text).await?, "한");
+
+                let _: () = jvm.invoke_virtual(&field, "com/xce/lcdui/XTextField", "keyPressed", "(I)V", (8,)).await?;
+                let _: () = jvm
+                    .invoke_virtual(&field, "com/xce/lcdui/XTextField", "keyPressed", "(I)V", ('!' as i32,))
+                    .await?;
+                let text: ClassInstanceRef<String> = jvm
+                    .invoke_virtual(&field, "com/xce/lcdui/XTextField", "getText", "()Ljava/lang/String;", ())
+                    .await?;
+                assert_eq!(JavaLangString::to_rust_string(&jvm, &text).await?, "하!");
+
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok(), "JVM test failed: {result:?}");
+    }
+
+    #[test]
+    fn host_text_typed_on_the_canvas_goes_to_the_focused_field() {
+        let result = run_jvm_test(
+            Box::new([
+                wie_midp::get_protos().into(),
+                [XTextField::as_proto(), TrackingCanvas::as_proto(), TrackingGraphics::as_proto()].into(),
+            ]),
+            |jvm| async move {
+                let canvas: ClassInstanceRef<Canvas> = jvm.new_class("test/TrackingCanvas", "()V", ()).await?.into();
+                let initial: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "").await?.into();
+                let field: ClassInstanceRef<XTextField> = jvm
+                    .new_class(
+                        "com/xce/lcdui/XTextField",
+                        "(Ljava/lang/String;IILjavax/microedition/lcdui/Canvas;)V",
+                        (initial, 3, 0, canvas.clone()),
+                    )
+                    .await?
+                    .into();
+                let _: () = jvm
+                    .invoke_virtual(&field, "com/xce/lcdui/XTextField", "setFocus", "(Z)V", (true,))
+                    .await?;
+
+                // ㅎ(88) is still being composed when the host text arrives, and only three characters fit
+                for key in "88".chars() {
+                    let _: () = jvm
+                        .invoke_virtual(&field, "com/xce/lcdui/XTextField", "keyPressed", "(I)V", (key as i32,))
+                        .await?;
+                }
+                for chr in "가나다".chars() {
+                    let _: () = jvm
+                        .invoke_virtual(&canvas, "javax/microedition/lcdui/Canvas", "handleKeyEvent", "(II)V", (4, chr as i32))
+                        .await?;
+                }
+                let text: ClassInstanceRef<String> = jvm
+                    .invoke_virtual(&field, "com/xce/lcdui/XTextField", "getText", "()Ljava/lang/String;", ())
+                    .await?;
+                assert_eq!(JavaLangString::to_rust_string(&jvm, &text).await?, "ㅎ가나");
+
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok(), "JVM test failed: {result:?}");
+    }
+
     #[test]
     fn paint_uses_the_bounds_clip_and_restores_the_original_clip() {
         let result = run_jvm_test(
@@ -1,7 +1,7 @@
 #![no_std]
 extern crate alloc;
 
-use alloc::{format, string::String, vec::Vec};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 use core::{
     any::Any,
     error::Error,
@@ -13,9 +13,41 @@ use core::{
 
 use bytemuck::{AnyBitPattern, NoUninit, bytes_of};
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MemoryAccessKind {
+    Read,
+    Write,
+    Execute,
+}
+
+/// Guest memory access the mapping doesn't permit, caught at the faulting instruction.
+#[derive(Debug)]
+pub struct GuestFault {
+    pub address: u32,
+    pub access: MemoryAccessKind,
+    pub pc: u32,
+    pub thread_id: Option<usize>,
+    pub call_stack: String,
+}
+
+impl Display for GuestFault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} fault; address: {:#x}, pc: {:#x}", self.access, self.address, self.pc)?;
+        if let Some(thread_id) = self.thread_id {
+            write!(f, ", thread: {thread_id}")?;
+        }
+        if !self.call_stack.is_empty() {
+            write!(f, "\nCall stack:\n{}", self.call_stack)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum WieError {
     InvalidMemoryAccess(u32),
+    GuestFault(Box<GuestFault>),
     AllocationFailure,
     JavaException(u32), // to pass java exception down to rust
     JavaExceptionUnwind { context_base: u32, target: u32, next_pc: u32 },
@@ -27,6 +59,7 @@ impl Display for WieError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             WieError::InvalidMemoryAccess(address) => write!(f, "Invalid memory access; address: {address}"),
+            WieError::GuestFault(fault) => write!(f, "Guest fault: {fault}"),
             WieError::AllocationFailure => write!(f, "Allocation failure"),
             WieError::JavaException(exception) => write!(f, "Java exception: {exception:#x}"),
             WieError::JavaExceptionUnwind {
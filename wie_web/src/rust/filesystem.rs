@@ -1,20 +1,41 @@
-use alloc::{boxed::Box, rc::Rc, string::ToString, vec};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use core::cell::RefCell;
 use core::cmp::{max, min};
 
-use wie_backend::Filesystem;
+use wie_backend::{DirEntry, FileMetadata, Filesystem, Instant};
 
 use crate::indexed_db_store::{Store, StoreKey};
 
 const DB_NAME: &str = "wie_filesystem";
 const STORE_NAME: &str = "files";
+const META_DB_NAME: &str = "wie_filesystem_meta";
+const META_STORE_NAME: &str = "meta";
 
 fn make_key(aid: &str, path: &str) -> StoreKey {
     StoreKey::Pair(aid.to_string(), path.to_string())
 }
 
+// directories are stored as empty values under `<path>/`, which guest file
+// paths can never collide with.
+fn make_dir_key(aid: &str, path: &str) -> StoreKey {
+    StoreKey::Pair(aid.to_string(), format!("{path}/"))
+}
+
+fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
 pub struct WebFilesystem {
     store: Rc<RefCell<Option<Store>>>,
+    meta_store: Rc<RefCell<Option<Store>>>,
 }
 
 // single threaded wasm; RefCell + Rc are only touched sequentially.
@@ -25,17 +46,85 @@ impl WebFilesystem {
     pub fn new() -> Self {
         Self {
             store: Rc::new(RefCell::new(None)),
+            meta_store: Rc::new(RefCell::new(None)),
         }
     }
 
     async fn store(&self) -> Store {
-        if let Some(store) = self.store.borrow().as_ref() {
+        Self::open_cached(&self.store, DB_NAME, STORE_NAME).await
+    }
+
+    async fn meta_store(&self) -> Store {
+        Self::open_cached(&self.meta_store, META_DB_NAME, META_STORE_NAME).await
+    }
+
+    async fn open_cached(cache: &RefCell<Option<Store>>, db_name: &str, store_name: &str) -> Store {
+        if let Some(store) = cache.borrow().as_ref() {
             return store.clone();
         }
-        let store = Store::open(DB_NAME, STORE_NAME).await;
-        *self.store.borrow_mut() = Some(store.clone());
+        let store = Store::open(db_name, store_name).await;
+        *cache.borrow_mut() = Some(store.clone());
         store
     }
+
+    /// Timestamps are stored as `created, modified` little-endian epoch millis.
+    async fn timestamps(&self, aid: &str, path: &str) -> Option<(u64, u64)> {
+        let data = self.meta_store().await.get(make_key(aid, path)).await?;
+        if data.len() != 16 {
+            return None;
+        }
+
+        Some((
+            u64::from_le_bytes(data[..8].try_into().unwrap()),
+            u64::from_le_bytes(data[8..].try_into().unwrap()),
+        ))
+    }
+
+    async fn set_timestamps(&self, aid: &str, path: &str, created: u64, modified: u64) {
+        let mut data = [0u8; 16];
+        data[..8].copy_from_slice(&created.to_le_bytes());
+        data[8..].copy_from_slice(&modified.to_le_bytes());
+
+        self.meta_store().await.set(make_key(aid, path), &data).await;
+    }
+
+    async fn touch(&self, aid: &str, path: &str) {
+        let now = now();
+        let created = self.timestamps(aid, path).await.map(|(created, _)| created).unwrap_or(now);
+
+        self.set_timestamps(aid, path, created, now).await;
+    }
+
+    /// All file and directory paths of `aid`, directories with a trailing `/`.
+    async fn paths(&self, aid: &str) -> Vec<String> {
+        self.store()
+            .await
+            .get_all_pair_keys()
+            .await
+            .into_iter()
+            .filter(|(key_aid, _)| key_aid == aid)
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    async fn is_dir(&self, aid: &str, path: &str) -> bool {
+        if path.is_empty() || self.store().await.get(make_dir_key(aid, path)).await.is_some() {
+            return true;
+        }
+
+        let prefix = format!("{path}/");
+        self.paths(aid).await.iter().any(|x| x.starts_with(&prefix))
+    }
+
+    async fn create_parents(&self, aid: &str, path: &str) {
+        let store = self.store().await;
+        for (index, _) in path.match_indices('/') {
+            let key = make_dir_key(aid, &path[..index]);
+            if store.get(key.clone()).await.is_none() {
+                store.set(key, &[]).await;
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,7 +159,9 @@ impl Filesystem for WebFilesystem {
         next[..existing.len()].copy_from_slice(&existing);
         next[offset..offset + data.len()].copy_from_slice(data);
 
+        self.create_parents(aid, path).await;
         store.set(key, &next).await;
+        self.touch(aid, path).await;
         data.len()
     }
 
@@ -83,6 +174,109 @@ impl Filesystem for WebFilesystem {
         let copy_len = min(existing.len(), len);
         next[..copy_len].copy_from_slice(&existing[..copy_len]);
 
+        self.create_parents(aid, path).await;
         store.set(key, &next).await;
+        self.touch(aid, path).await;
+    }
+
+    async fn metadata(&self, aid: &str, path: &str) -> Option<FileMetadata> {
+        let (is_dir, size) = if let Some(size) = self.size(aid, path).await {
+            (false, size)
+        } else if self.is_dir(aid, path).await {
+            (true, 0)
+        } else {
+            return None;
+        };
+
+        let timestamps = self.timestamps(aid, path).await;
+        Some(FileMetadata {
+            is_dir,
+            size,
+            created: timestamps.map(|(created, _)| Instant::from_epoch_millis(created)),
+            modified: timestamps.map(|(_, modified)| Instant::from_epoch_millis(modified)),
+        })
+    }
+
+    async fn list(&self, aid: &str, path: &str) -> Option<Vec<DirEntry>> {
+        if !self.is_dir(aid, path).await {
+            return None;
+        }
+
+        let prefix = if path.is_empty() { String::new() } else { format!("{path}/") };
+        let mut result: Vec<DirEntry> = Vec::new();
+        for entry_path in self.paths(aid).await {
+            let Some(rest) = entry_path.strip_prefix(&prefix) else {
+                continue;
+            };
+            let (name, is_dir) = match rest.split_once('/') {
+                Some((name, _)) => (name, true),
+                None => (rest, false),
+            };
+            if !name.is_empty() && !result.iter().any(|e| e.name == name) {
+                result.push(DirEntry {
+                    name: name.to_owned(),
+                    is_dir,
+                });
+            }
+        }
+
+        Some(result)
+    }
+
+    async fn remove(&self, aid: &str, path: &str) -> bool {
+        if !self.exists(aid, path).await {
+            return false;
+        }
+
+        self.store().await.delete(make_key(aid, path)).await;
+        self.meta_store().await.delete(make_key(aid, path)).await;
+        true
+    }
+
+    async fn rename(&self, aid: &str, from: &str, to: &str) -> bool {
+        let store = self.store().await;
+        let Some(data) = store.get(make_key(aid, from)).await else {
+            return false;
+        };
+        if self.is_dir(aid, to).await {
+            return false;
+        }
+
+        self.create_parents(aid, to).await;
+        store.set(make_key(aid, to), &data).await;
+        store.delete(make_key(aid, from)).await;
+
+        let meta_store = self.meta_store().await;
+        match meta_store.get(make_key(aid, from)).await {
+            Some(meta) => {
+                meta_store.set(make_key(aid, to), &meta).await;
+                meta_store.delete(make_key(aid, from)).await;
+            }
+            None => meta_store.delete(make_key(aid, to)).await,
+        }
+
+        true
+    }
+
+    async fn create_dir(&self, aid: &str, path: &str) -> bool {
+        let ancestors = path.match_indices('/').map(|(index, _)| &path[..index]).chain(core::iter::once(path));
+        for ancestor in ancestors {
+            if self.exists(aid, ancestor).await {
+                return false;
+            }
+        }
+
+        self.create_parents(aid, path).await;
+        self.store().await.set(make_dir_key(aid, path), &[]).await;
+        true
+    }
+
+    async fn remove_dir(&self, aid: &str, path: &str) -> bool {
+        if path.is_empty() || !matches!(self.list(aid, path).await, Some(entries) if entries.is_empty()) {
+            return false;
+        }
+
+        self.store().await.delete(make_dir_key(aid, path)).await;
+        true
     }
 }
@@ -66,6 +66,22 @@ impl Store {
         run_js_future(async move { js.get_all_keys().await.iter().filter_map(|key| key.as_string()).collect() }).await
     }
 
+    pub async fn get_all_pair_keys(&self) -> Vec<(String, String)> {
+        let js: IndexedDBStore = self.js.clone().into();
+        run_js_future(async move {
+            js.get_all_keys()
+                .await
+                .iter()
+                .filter(|key| Array::is_array(key))
+                .filter_map(|key| {
+                    let key = Array::from(&key);
+                    Some((key.get(0).as_string()?, key.get(1).as_string()?))
+                })
+                .collect()
+        })
+        .await
+    }
+
     pub async fn get(&self, key: StoreKey) -> Option<Vec<u8>> {
         let js: IndexedDBStore = self.js.clone().into();
         run_js_future(async move {
@@ -13,6 +13,7 @@ use alloc::{
     boxed::Box,
     string::{String, ToString},
     sync::Arc,
+    vec::Vec,
 };
 use core::{
     str,
@@ -122,6 +123,9 @@ impl WieWeb {
             let options = Options {
                 enable_gdbserver: false,
                 profile: None,
+                trace: None,
+                heap_debug: false,
+                binary_patches: Vec::new(),
             };
 
             let emulator: Box<dyn Emulator> = if filename.ends_with("zip") {
@@ -223,6 +227,12 @@ impl WieWeb {
         Ok(())
     }
 
+    pub fn text_input(&mut self, text: String) -> Result<(), JsError> {
+        self.emulator.handle_event(Event::Text(text));
+
+        Ok(())
+    }
+
     pub fn set_pcm_volume(&self, volume: f32) {
         audio_sink::set_pcm_volume(volume);
     }
@@ -128,6 +128,18 @@ const main = () => {
             button.addEventListener("touchstart", handleKeyDown);
             button.addEventListener("touchend", handleKeyUp);
           }
+          // text typed here, including korean composed by the OS input method, goes to the focused text field as is
+          const textInput = document.getElementById("text-input") as HTMLInputElement;
+          textInput.addEventListener("keydown", (e) => {
+            e.stopPropagation();
+            if (e.key === "Enter" && !e.isComposing && textInput.value) {
+              e.preventDefault();
+              wie_web.text_input(textInput.value);
+              textInput.value = "";
+            }
+          });
+          textInput.addEventListener("keyup", (e) => e.stopPropagation());
+
           document.addEventListener("keydown", (e) => {
             if (key_map[e.code]) {
               e.preventDefault();
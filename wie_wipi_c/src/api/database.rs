@@ -42,7 +42,6 @@ struct DatabaseHandle {
 }
 
 const MIN_BUFFER_CAPACITY: u32 = 64;
-const KTF_DATABASE_STORAGE_LIMIT: u64 = 1024 * 1024;
 // "MCDB" — sentinel at the start of the handle struct so we can distinguish
 // a real DB handle pointer from an unrelated guest pointer (e.g. a C-string
 // name pointer that KTF's slot 6 passes through the same SVC argument slot).
@@ -73,7 +72,7 @@ pub async fn open_database(context: &mut dyn WIPICContext, ptr_name: WIPICWord,
 
     let system = context.system();
     let pid = system.pid().to_owned();
-    let exists = system.platform().database_repository().exists(&name, &pid).await;
+    let exists = system.database_repository().exists(&name, &pid).await;
 
     if !exists && packaged.is_none() && mode == 1 {
         return Ok(-12); // M_E_NOENT
@@ -84,7 +83,7 @@ pub async fn open_database(context: &mut dyn WIPICContext, ptr_name: WIPICWord,
     // buffer with the existing record or packaged data so seek+overlay writes
     // preserve unrelated bytes (multi-slot saves at fixed byte offsets).
     let initial: Vec<u8> = if exists {
-        let mut db = system.platform().database_repository().open(&name, &pid).await;
+        let mut db = system.database_repository().open(&name, &pid).await;
         if mode == 4 && packaged.is_none() {
             db.delete(1).await;
             Vec::new()
@@ -97,11 +96,11 @@ pub async fn open_database(context: &mut dyn WIPICContext, ptr_name: WIPICWord,
             Vec::new()
         }
     } else if let Some(data) = packaged {
-        let mut db = system.platform().database_repository().open(&name, &pid).await;
+        let mut db = system.database_repository().open(&name, &pid).await;
         db.set(1, &data).await;
         data
     } else if mode == 4 {
-        system.platform().database_repository().open(&name, &pid).await;
+        system.database_repository().open(&name, &pid).await;
         Vec::new()
     } else {
         Vec::new()
@@ -177,12 +176,12 @@ pub async fn list_record(context: &mut dyn WIPICContext, db_id: i32, buf_ptr: WI
 /// KTF titles use its no-argument return value as an available-storage byte count.
 /// Known callers reject values below 0x100 and 0x1200 respectively.
 pub async fn list_databases(context: &mut dyn WIPICContext) -> Result<i32> {
-    let system = context.system();
-    let pid = system.pid().to_owned();
-    let usage = system.platform().database_repository().usage(&pid).await;
-    let available = KTF_DATABASE_STORAGE_LIMIT.saturating_sub(usage).min(i32::MAX as u64) as i32;
+    let storage = context.system().storage();
+    let used = storage.used().await;
+    let quota = storage.quota();
+    let available = quota.saturating_sub(used).min(i32::MAX as u64) as i32;
 
-    tracing::debug!("MC_dbListDataBase() = {available} (used={usage}, limit={KTF_DATABASE_STORAGE_LIMIT})");
+    tracing::debug!("MC_dbListDataBase() = {available} (used={used}, quota={quota})");
     Ok(available)
 }
 
@@ -216,7 +215,7 @@ pub async fn list_record_info(context: &mut dyn WIPICContext, ptr_name: WIPICWor
     let system = context.system();
     let pid = system.pid().to_owned();
 
-    if !system.platform().database_repository().exists(&name, &pid).await {
+    if !system.database_repository().exists(&name, &pid).await {
         if let Some(data) = read_packaged_database(context, &name).await? {
             if capacity > 0 {
                 write_generic(context, buf_ptr, 1u32)?;
@@ -228,7 +227,7 @@ pub async fn list_record_info(context: &mut dyn WIPICContext, ptr_name: WIPICWor
         return Ok(-12); // M_E_NOENT
     }
 
-    let db = system.platform().database_repository().open(&name, &pid).await;
+    let db = system.database_repository().open(&name, &pid).await;
     let ids = db.get_record_ids().await;
 
     let mut written = 0;
@@ -263,7 +262,7 @@ pub async fn exists_database(context: &mut dyn WIPICContext, ptr_name: WIPICWord
 
     let system = context.system();
     let pid = system.pid().to_owned();
-    if system.platform().database_repository().exists(&name, &pid).await {
+    if system.database_repository().exists(&name, &pid).await {
         Ok(0)
     } else {
         Ok(-12) // M_E_NOENT
@@ -286,6 +285,13 @@ pub async fn stream_write(context: &mut dyn WIPICContext, db_id: i32, buf_ptr: W
 
     let old_len = handle.buffer_len;
 
+    // The record is flushed on every write, so anything past its current
+    // end has to fit the application's storage quota.
+    if !context.system().storage().fits(new_end.saturating_sub(old_len) as u64).await {
+        tracing::warn!("db.stream_write: storage quota exceeded");
+        return Ok(-1); // M_E_ERROR
+    }
+
     // Grow the guest-heap buffer if the next write would land past its
     // end. Doubling-on-demand starting from MIN_BUFFER_CAPACITY keeps the
     // realloc count amortized; alloc/free is a guest-side `WIPICContext`
@@ -394,8 +400,8 @@ pub async fn delete_database(context: &mut dyn WIPICContext, ptr_name: WIPICWord
     let system = context.system();
     let pid = system.pid().to_owned();
 
-    let deleted = system.platform().database_repository().delete(&name, &pid).await;
-    if deleted || !system.platform().database_repository().exists(&name, &pid).await {
+    let deleted = system.database_repository().delete(&name, &pid).await;
+    if deleted || !system.database_repository().exists(&name, &pid).await {
         Ok(0)
     } else {
         Ok(-12) // M_E_NOENT
@@ -415,8 +421,12 @@ pub async fn update_record(context: &mut dyn WIPICContext, db_id: i32, rec_id: i
         return Ok(-22);
     }
     let rec_id = rec_id as u32;
-    if db.get(rec_id).await.is_none() {
+    let Some(old) = db.get(rec_id).await else {
         return Ok(-22);
+    };
+    if !context.system().storage().fits(buf_len.saturating_sub(old.len() as u32) as u64).await {
+        tracing::warn!("MC_dbUpdateRecord: storage quota exceeded");
+        return Ok(-1); // M_E_ERROR
     }
 
     let mut buf = vec![0; buf_len as usize];
@@ -536,7 +546,7 @@ pub async fn stat_by_name_ktf(context: &mut dyn WIPICContext, name_ptr: WIPICWor
 
     let system = context.system();
     let pid = system.pid().to_owned();
-    let exists = system.platform().database_repository().exists(&name, &pid).await;
+    let exists = system.database_repository().exists(&name, &pid).await;
     if !exists {
         tracing::debug!("db.stat_by_name({name:?}, mode={mode}) -> -22 (not found)");
         return Ok(-22);
@@ -544,7 +554,7 @@ pub async fn stat_by_name_ktf(context: &mut dyn WIPICContext, name_ptr: WIPICWor
 
     // Pull record 1's size as the "valid save" indicator the game checks
     // against 0xC7 in v2[2].
-    let db = system.platform().database_repository().open(&name, &pid).await;
+    let db = system.database_repository().open(&name, &pid).await;
     let record_size = db.get(1).await.map(|x| x.len() as u32).unwrap_or(0);
 
     if out_buf != 0 {
@@ -580,7 +590,7 @@ pub async fn exists_database_ktf(context: &mut dyn WIPICContext, name_ptr: WIPIC
 
     let system = context.system();
     let pid = system.pid().to_owned();
-    let exists = system.platform().database_repository().exists(&name, &pid).await;
+    let exists = system.database_repository().exists(&name, &pid).await;
 
     let result = if exists { 1 } else { 0 };
     tracing::debug!("MC_dbExists({name:?}) -> {result}");
@@ -610,7 +620,7 @@ async fn open_db_for_handle(context: &mut dyn WIPICContext, handle: &DatabaseHan
     let system = context.system();
     let pid = system.pid().to_owned();
 
-    Some(system.platform().database_repository().open(db_name, &pid).await)
+    Some(system.database_repository().open(db_name, &pid).await)
 }
 
 async fn get_database_from_db_id(context: &mut dyn WIPICContext, db_id: i32) -> Result<Option<Box<dyn Database>>> {
@@ -633,26 +643,47 @@ mod tests {
     use alloc::boxed::Box;
 
     use test_utils::TestPlatform;
-    use wie_backend::{DefaultTaskRunner, System};
+    use wie_backend::{DEFAULT_STORAGE_QUOTA, DefaultTaskRunner, System};
     use wie_util::{ByteRead, ByteWrite};
 
     use crate::context::test::TestContext;
 
     use super::{
-        KTF_DATABASE_STORAGE_LIMIT, delete_database, exists_database, list_databases, list_record_info, open_database, select_record, stream_read,
+        delete_database, exists_database, list_databases, list_record_info, open_database, seek_record_single, select_record, stream_read,
         stream_write, update_record,
     };
 
     #[futures_test::test]
     async fn ktf_available_database_storage_tracks_app_usage() {
         let mut context = database_test_context();
-        assert_eq!(list_databases(&mut context).await.unwrap(), KTF_DATABASE_STORAGE_LIMIT as i32);
+        assert_eq!(list_databases(&mut context).await.unwrap(), DEFAULT_STORAGE_QUOTA as i32);
+
+        let db_id = open_test_database(&mut context).await;
+        context.write_bytes(0x2000, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(stream_write(&mut context, db_id, 0x2000, 4).await.unwrap(), 4);
+
+        assert_eq!(list_databases(&mut context).await.unwrap(), DEFAULT_STORAGE_QUOTA as i32 - 4);
+    }
+
+    #[futures_test::test]
+    async fn ktf_stream_write_fails_when_storage_is_full() {
+        let system = System::new(
+            Box::new(TestPlatform::new().with_storage_quota(6)),
+            "test-pid",
+            "test-aid",
+            DefaultTaskRunner,
+        );
+        let mut context = TestContext::with_system(system);
 
         let db_id = open_test_database(&mut context).await;
         context.write_bytes(0x2000, &[1, 2, 3, 4]).unwrap();
         assert_eq!(stream_write(&mut context, db_id, 0x2000, 4).await.unwrap(), 4);
+        assert_eq!(stream_write(&mut context, db_id, 0x2000, 4).await.unwrap(), -1);
+        assert_eq!(list_databases(&mut context).await.unwrap(), 2);
 
-        assert_eq!(list_databases(&mut context).await.unwrap(), KTF_DATABASE_STORAGE_LIMIT as i32 - 4);
+        // rewriting stored bytes does not need more space
+        assert_eq!(seek_record_single(&mut context, db_id, 0, 0).await.unwrap(), 0);
+        assert_eq!(stream_write(&mut context, db_id, 0x2000, 4).await.unwrap(), 4);
     }
 
     #[futures_test::test]
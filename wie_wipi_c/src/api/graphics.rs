@@ -1,4 +1,4 @@
-mod framebuffer;
+pub(crate) mod framebuffer;
 mod grp_context;
 mod image;
 
@@ -459,10 +459,10 @@ pub async fn get_font(_: &mut dyn WIPICContext, face: i32, size: i32, style: i32
     Ok(0)
 }
 
-pub async fn get_font_height(_: &mut dyn WIPICContext, font: i32) -> Result<i32> {
-    tracing::warn!("stub MC_grpGetFontHeight({font})");
+pub async fn get_font_height(context: &mut dyn WIPICContext, font: i32) -> Result<i32> {
+    tracing::debug!("MC_grpGetFontHeight({font})");
 
-    Ok(12)
+    Ok(context.system().device_profile().fonts.medium as _)
 }
 
 pub async fn get_font_ascent(_: &mut dyn WIPICContext, font: i32) -> Result<i32> {
@@ -0,0 +1,180 @@
+use alloc::{string::String, vec::Vec};
+
+use wie_backend::{InputMode, KeyCode};
+use wie_util::{Result, read_null_terminated_string_bytes, write_generic};
+
+use wipi_types::wipic::WIPICWord;
+
+use crate::context::WIPICContext;
+
+const MC_IM_MODE_KOREAN: i32 = 1;
+const MC_IM_MODE_UPPERCASE: i32 = 2;
+const MC_IM_MODE_LOWERCASE: i32 = 3;
+const MC_IM_MODE_NUMERIC: i32 = 4;
+const SUPPORTED_MODES: [i32; 4] = [MC_IM_MODE_KOREAN, MC_IM_MODE_UPPERCASE, MC_IM_MODE_LOWERCASE, MC_IM_MODE_NUMERIC];
+
+const MV_KEY_PRESS_EVENT: i32 = 1;
+const MV_KEY_RELEASE_EVENT: i32 = 2;
+
+const MH_KEY_CLEAR: i32 = -16;
+
+// the handset-wide input method keeps the composition, so the buffer holds it at its end between calls
+pub async fn handle_input(context: &mut dyn WIPICContext, r#type: i32, key: i32, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    tracing::debug!("MC_imHandleInput({type}, {key}, {buf:#x}, {buf_size})");
+
+    let Some(key_code) = key_code(key) else {
+        return Ok(-9); // M_E_INVALID
+    };
+
+    let bytes = read_null_terminated_string_bytes(context, buf)?;
+    let mut text = String::from(encoding_rs::EUC_KR.decode(&bytes).0);
+
+    let now = context.system().platform().now();
+    let consumed = {
+        let mut input_method = context.system().input_method();
+        if let Some(stripped) = text.strip_suffix(input_method.composing().as_str()) {
+            text.truncate(stripped.len());
+        }
+
+        let consumed = match r#type {
+            MV_KEY_PRESS_EVENT => {
+                // hangul syllables take two bytes in EUC-KR
+                let room = (buf_size as usize).saturating_sub(1 + encoding_rs::EUC_KR.encode(&text).0.len()) / 2;
+
+                let result = input_method.key(key_code, now, room);
+                text.push_str(&result.committed);
+                result.consumed || (key == MH_KEY_CLEAR && text.pop().is_some())
+            }
+            MV_KEY_RELEASE_EVENT => {
+                text.push_str(&input_method.tick(now));
+                true
+            }
+            _ => false,
+        };
+        text.push_str(&input_method.composing());
+
+        consumed
+    };
+
+    if !consumed {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let mut result: Vec<u8> = encoding_rs::EUC_KR.encode(&text).0.into_owned();
+    result.truncate((buf_size as usize).saturating_sub(1));
+    context.write_bytes(buf, &result)?;
+    write_generic(context, buf + result.len() as WIPICWord, 0u8)?;
+
+    Ok(result.len() as _)
+}
+
+pub async fn set_current_mode(context: &mut dyn WIPICContext, mode: i32) -> Result<i32> {
+    tracing::debug!("MC_imSetCurrentMode({mode})");
+
+    let Some(mode) = input_mode(mode) else {
+        return Ok(-9); // M_E_INVALID
+    };
+
+    let mut input_method = context.system().input_method();
+    let previous = raw_mode(input_method.mode());
+    input_method.set_mode(mode);
+
+    Ok(previous)
+}
+
+pub async fn get_current_mode(context: &mut dyn WIPICContext) -> Result<i32> {
+    tracing::debug!("MC_imGetCurrentMode()");
+
+    Ok(raw_mode(context.system().input_method().mode()))
+}
+
+pub async fn get_support_mode_count(_context: &mut dyn WIPICContext) -> Result<i32> {
+    tracing::debug!("MC_imGetSupportModeCount()");
+
+    Ok(SUPPORTED_MODES.len() as _)
+}
+
+pub async fn get_supported_modes(context: &mut dyn WIPICContext, modes: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_imGetSupportedModes({modes:#x})");
+
+    for (i, mode) in SUPPORTED_MODES.into_iter().enumerate() {
+        write_generic(context, modes + (i * 4) as WIPICWord, mode)?;
+    }
+
+    Ok(SUPPORTED_MODES.len() as _)
+}
+
+fn input_mode(raw: i32) -> Option<InputMode> {
+    Some(match raw {
+        MC_IM_MODE_KOREAN => InputMode::Korean,
+        MC_IM_MODE_UPPERCASE => InputMode::Uppercase,
+        MC_IM_MODE_LOWERCASE => InputMode::Lowercase,
+        MC_IM_MODE_NUMERIC => InputMode::Numeric,
+        _ => return None,
+    })
+}
+
+fn raw_mode(mode: InputMode) -> i32 {
+    match mode {
+        InputMode::Korean => MC_IM_MODE_KOREAN,
+        InputMode::Uppercase => MC_IM_MODE_UPPERCASE,
+        InputMode::Lowercase => MC_IM_MODE_LOWERCASE,
+        InputMode::Numeric => MC_IM_MODE_NUMERIC,
+    }
+}
+
+// MH_KEY_* values that take part in text entry
+pub(crate) fn key_code(raw: i32) -> Option<KeyCode> {
+    Some(match raw {
+        0x30 => KeyCode::NUM0,
+        0x31 => KeyCode::NUM1,
+        0x32 => KeyCode::NUM2,
+        0x33 => KeyCode::NUM3,
+        0x34 => KeyCode::NUM4,
+        0x35 => KeyCode::NUM5,
+        0x36 => KeyCode::NUM6,
+        0x37 => KeyCode::NUM7,
+        0x38 => KeyCode::NUM8,
+        0x39 => KeyCode::NUM9,
+        0x23 => KeyCode::HASH,
+        0x2a => KeyCode::STAR,
+        MH_KEY_CLEAR => KeyCode::CLEAR,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use test_utils::TestPlatform;
+    use wie_backend::{DefaultTaskRunner, System};
+    use wie_util::{ByteWrite, read_null_terminated_string_bytes};
+
+    use crate::context::test::TestContext;
+
+    use super::{MH_KEY_CLEAR, MV_KEY_PRESS_EVENT, get_current_mode, handle_input, set_current_mode};
+
+    #[futures_test::test]
+    async fn handle_input_composes_hangul_in_buffer() {
+        let system = System::new(Box::new(TestPlatform::new()), "test-pid", "test-aid", DefaultTaskRunner);
+        let mut context = TestContext::with_system(system);
+        context.write_bytes(0x1000, b"a\0").unwrap();
+
+        // 천지인: ㅎ(88) ㅏ(12)
+        let mut length = 0;
+        for key in b"8812" {
+            length = handle_input(&mut context, MV_KEY_PRESS_EVENT, *key as i32, 0x1000, 16).await.unwrap();
+        }
+        assert_eq!(length, 3);
+        let bytes = read_null_terminated_string_bytes(&mut context, 0x1000).unwrap();
+        assert_eq!(encoding_rs::EUC_KR.decode(&bytes).0, "a하");
+
+        assert_eq!(handle_input(&mut context, MV_KEY_PRESS_EVENT, MH_KEY_CLEAR, 0x1000, 16).await.unwrap(), 3);
+        assert_eq!(handle_input(&mut context, MV_KEY_PRESS_EVENT, -1, 0x1000, 16).await.unwrap(), -9);
+
+        assert_eq!(set_current_mode(&mut context, 4).await.unwrap(), 1);
+        assert_eq!(get_current_mode(&mut context).await.unwrap(), 4);
+        assert_eq!(set_current_mode(&mut context, 9).await.unwrap(), -9);
+    }
+}
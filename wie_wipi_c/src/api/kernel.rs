@@ -35,33 +35,38 @@ pub async fn get_system_property(context: &mut dyn WIPICContext, ptr_id: WIPICWo
     let id_bytes = read_null_terminated_string_bytes(context, ptr_id)?;
     let id = encoding_rs::EUC_KR.decode(&id_bytes).0;
 
-    let value = match id.as_ref() {
-        "RSSILEVEL" => "30",
-        "BATTERYLEVEL" => "100",
-        "PHONEMODEL" => "Emulator",
-        "PHONENUMBER" => "", // putting this cause some game to fail authentication
-        "MIN" => "01000000000",
-        "ANNUN_CALL" => "0",
-        "ANNUN_SMS" => "0",
-        "ANNUN_SILENT" => "0",
-        "ANNUN_ALARM" => "0",
-        "ANNUN_SECURITY" => "0",
-        "CURRENTCH" => "0",
-        "AIRPLANE_MODE" => "0",
-        "ROAMING_AREA" => "0",
-        "DS_LOCK" => "0",
-        _ => {
-            tracing::warn!("unknown system property id: {id}");
-            return Ok(-9); // M_E_INVALID
+    let profile = context.system().device_profile();
+    let value = if let Some(value) = profile.property(&id) {
+        value
+    } else {
+        match id.as_ref() {
+            "RSSILEVEL" => "30",
+            "BATTERYLEVEL" => "100",
+            "PHONEMODEL" => profile.name.as_str(),
+            "PHONENUMBER" => "", // putting this cause some game to fail authentication
+            "MIN" => "01000000000",
+            "ANNUN_CALL" => "0",
+            "ANNUN_SMS" => "0",
+            "ANNUN_SILENT" => "0",
+            "ANNUN_ALARM" => "0",
+            "ANNUN_SECURITY" => "0",
+            "CURRENTCH" => "0",
+            "AIRPLANE_MODE" => "0",
+            "ROAMING_AREA" => "0",
+            "DS_LOCK" => "0",
+            _ => {
+                tracing::warn!("unknown system property id: {id}");
+                return Ok(-9); // M_E_INVALID
+            }
         }
     };
 
-    let bytes = value.as_bytes();
+    let bytes = value.as_bytes().to_vec();
     if bytes.len() + 1 > buf_size as usize {
         return Ok(-18); // M_E_SHORTBUF
     }
 
-    write_null_terminated_string_bytes(context, p_out, value.as_bytes())?;
+    write_null_terminated_string_bytes(context, p_out, &bytes)?;
 
     Ok(0)
 }
@@ -135,6 +140,10 @@ pub async fn alloc(context: &mut dyn WIPICContext, size: WIPICWord) -> Result<WI
     if size == 0 {
         return Ok(WIPICIndirectPtr(0));
     }
+    if size > context.system().device_profile().heap_size {
+        tracing::warn!("MC_knlAlloc({size:#x}) exceeds the handset heap");
+        return Ok(WIPICIndirectPtr(0));
+    }
 
     context.alloc(size)
 }
@@ -145,6 +154,10 @@ pub async fn calloc(context: &mut dyn WIPICContext, size: WIPICWord) -> Result<W
     if size == 0 {
         return Ok(WIPICIndirectPtr(0));
     }
+    if size > context.system().device_profile().heap_size {
+        tracing::warn!("MC_knlCalloc({size:#x}) exceeds the handset heap");
+        return Ok(WIPICIndirectPtr(0));
+    }
 
     let memory = context.alloc(size)?;
 
@@ -257,16 +270,18 @@ pub async fn sprintk(
     Ok(result.len() as _)
 }
 
-pub async fn get_total_memory(_context: &mut dyn WIPICContext) -> Result<i32> {
-    tracing::warn!("stub MC_knlGetTotalMemory()");
+pub async fn get_total_memory(context: &mut dyn WIPICContext) -> Result<i32> {
+    let total = context.total_memory()?.min(i32::MAX as u32) as i32;
+    tracing::debug!("MC_knlGetTotalMemory() = {total}");
 
-    Ok(0x100000) // TODO hardcoded
+    Ok(total)
 }
 
-pub async fn get_free_memory(_context: &mut dyn WIPICContext) -> Result<i32> {
-    tracing::warn!("stub MC_knlGetFreeMemory()");
+pub async fn get_free_memory(context: &mut dyn WIPICContext) -> Result<i32> {
+    let free = context.free_memory()?.min(i32::MAX as u32) as i32;
+    tracing::debug!("MC_knlGetFreeMemory() = {free}");
 
-    Ok(0x100000) // TODO hardcoded
+    Ok(free)
 }
 
 pub async fn exit(context: &mut dyn WIPICContext, code: i32) -> Result<()> {
@@ -303,12 +318,20 @@ pub async fn get_program_name(context: &mut dyn WIPICContext, name_buf: WIPICWor
 mod test {
     use alloc::{boxed::Box, string::String};
 
+    use test_utils::TestPlatform;
+    use wie_backend::{DefaultTaskRunner, DeviceProfile, System};
     use wie_util::{ByteRead, ByteWrite, Result, read_null_terminated_string_bytes, write_null_terminated_string_bytes};
 
     use crate::{WIPICContext, context::test::TestContext, method::MethodImpl};
 
     use super::{alloc, calloc, free, get_resource, get_resource_id, get_system_property, sprintk};
 
+    fn context_with_profile(profile: DeviceProfile) -> TestContext {
+        let platform = TestPlatform::new().with_device_profile(profile);
+
+        TestContext::with_system(System::new(Box::new(platform), "test-pid", "test-aid", DefaultTaskRunner))
+    }
+
     #[futures_test::test]
     async fn test_sprintk() -> Result<()> {
         let mut context = TestContext::new();
@@ -339,7 +362,7 @@ mod test {
 
     #[futures_test::test]
     async fn test_get_system_property_min() -> Result<()> {
-        let mut context = TestContext::new();
+        let mut context = context_with_profile(DeviceProfile::default());
         let id = context.alloc_raw(16).unwrap();
         let out = context.alloc_raw(16).unwrap();
 
@@ -352,6 +375,23 @@ mod test {
         Ok(())
     }
 
+    #[futures_test::test]
+    async fn test_get_system_property_from_device_profile() -> Result<()> {
+        let mut profile = DeviceProfile::default();
+        profile.properties.insert("MIN".into(), "01012345678".into());
+        let mut context = context_with_profile(profile);
+        let id = context.alloc_raw(16).unwrap();
+        let out = context.alloc_raw(16).unwrap();
+
+        write_null_terminated_string_bytes(&mut context, id, b"MIN").unwrap();
+
+        assert_eq!(get_system_property(&mut context, id, out, 16).await.unwrap(), 0);
+        let result = read_null_terminated_string_bytes(&context, out).unwrap();
+        assert_eq!(String::from_utf8(result).unwrap(), "01012345678");
+
+        Ok(())
+    }
+
     #[futures_test::test]
     async fn test_zero_size_memory_returns_null() -> Result<()> {
         let mut context = TestContext::new();
@@ -363,9 +403,39 @@ mod test {
         Ok(())
     }
 
+    #[futures_test::test]
+    async fn test_free_memory_is_part_of_total_memory() -> Result<()> {
+        let mut context = context_with_profile(DeviceProfile::default());
+
+        let total = get_total_memory(&mut context).await?;
+        assert_eq!(get_free_memory(&mut context).await?, total);
+
+        alloc(&mut context, 0x100).await?;
+        let free = get_free_memory(&mut context).await?;
+        assert!(free < total);
+        assert_eq!(get_total_memory(&mut context).await?, total);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn test_allocation_larger_than_heap_returns_null() -> Result<()> {
+        let heap_size = 0x1000;
+        let mut context = context_with_profile(DeviceProfile {
+            heap_size,
+            ..Default::default()
+        });
+
+        assert_eq!(alloc(&mut context, heap_size + 1).await.unwrap().0, 0);
+        assert_eq!(calloc(&mut context, heap_size + 1).await.unwrap().0, 0);
+        assert_ne!(alloc(&mut context, 16).await.unwrap().0, 0);
+
+        Ok(())
+    }
+
     #[futures_test::test]
     async fn test_get_system_property_non_utf8() -> Result<()> {
-        let mut context = TestContext::new();
+        let mut context = context_with_profile(DeviceProfile::default());
         let id = context.alloc_raw(16).unwrap();
         let out = context.alloc_raw(16).unwrap();
 
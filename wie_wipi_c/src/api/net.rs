@@ -1,44 +1,731 @@
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 use wipi_types::wipic::WIPICWord;
 
-use wie_util::{Result, WieError};
+use wie_backend::{HttpConnection, HttpResponse, NetworkError};
+use wie_util::{Result, WieError, read_null_terminated_string_bytes, write_generic, write_null_terminated_string_bytes};
 
 use crate::{WIPICResult, context::WIPICContext, method::MethodBody};
 
-pub async fn connect(context: &mut dyn WIPICContext, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
-    tracing::warn!("stub MC_netConnect({cb:#x}, {param:#x})");
+const M_E_SUCCESS: i32 = 0;
+const M_E_ERROR: i32 = -1;
+const M_E_INVALID: i32 = -9;
+const M_E_NOENT: i32 = -12;
+const M_E_WOULDBLOCK: i32 = -19;
+
+const MC_SOCKET_STREAM: i32 = 1;
+const MC_SOCKET_DGRAM: i32 = 2;
+const MAX_PACKET_LENGTH: i32 = 1500;
+// socket reads and writes move at most this many bytes per call, in packet sized chunks
+const MAX_TRANSFER_LENGTH: usize = 0x10000;
+
+// how often pending operations are polled, in milliseconds
+const POLL_INTERVAL: u64 = 10;
+
+/// Network operation a callback waits for.
+enum Pending {
+    /// `cb(result, param)` once the network is up.
+    NetworkConnect,
+    /// `cb(result, address, param)`, address in network byte order.
+    Resolve(String),
+    /// `cb(fd, result, param)` for the remaining variants.
+    SocketConnect(i32),
+    Readable(i32),
+    Writable(i32),
+    Http(i32),
+}
+
+struct PendingCallback {
+    pending: Pending,
+    cb: WIPICWord,
+    param: WIPICWord,
+}
 
-    struct ConnectCallback {
-        cb: WIPICWord,
-        param: WIPICWord,
+impl PendingCallback {
+    // arguments to call back with, or `None` while the operation is still in progress
+    fn poll(&self, context: &mut dyn WIPICContext) -> Option<Vec<WIPICWord>> {
+        let mut connections = context.system().connections();
+
+        let (fd, result) = match &self.pending {
+            Pending::NetworkConnect => {
+                let result = if connections.is_online() { M_E_SUCCESS } else { M_E_ERROR };
+
+                return Some(vec![result as _, self.param]);
+            }
+            Pending::Resolve(host) => {
+                return match connections.resolve(host) {
+                    Ok(address) => Some(vec![M_E_SUCCESS as _, u32::from_le_bytes(address), self.param]),
+                    Err(NetworkError::WouldBlock) => None,
+                    Err(err) => Some(vec![error_code(err) as _, 0, self.param]),
+                };
+            }
+            Pending::SocketConnect(fd) => (*fd, connections.socket(*fd as _).map(|x| x.poll_connect())),
+            Pending::Readable(fd) => (*fd, connections.socket(*fd as _).map(|x| x.poll_readable())),
+            Pending::Writable(fd) => (*fd, connections.socket(*fd as _).map(|x| x.poll_connect())),
+            Pending::Http(fd) => (*fd, Some(connections.poll_http(*fd as _))),
+        };
+
+        let result = match result {
+            None => M_E_INVALID, // closed while waiting
+            Some(Ok(())) => M_E_SUCCESS,
+            Some(Err(NetworkError::WouldBlock)) => return None,
+            Some(Err(err)) => error_code(err),
+        };
+
+        Some(vec![fd as _, result as _, self.param])
     }
+}
 
-    #[async_trait::async_trait]
-    impl MethodBody<WieError> for ConnectCallback {
-        #[tracing::instrument(name = "timer", skip_all)]
-        async fn call(&self, context: &mut dyn WIPICContext, _: Box<[WIPICWord]>) -> Result<WIPICResult> {
-            context.system().sleep(1).await; // simulate some delay
+#[async_trait::async_trait]
+impl MethodBody<WieError> for PendingCallback {
+    #[tracing::instrument(name = "net", skip_all)]
+    async fn call(&self, context: &mut dyn WIPICContext, _: Box<[WIPICWord]>) -> Result<WIPICResult> {
+        loop {
+            if let Some(args) = self.poll(context) {
+                context.call_function(self.cb, &args).await?;
 
-            context.call_function(self.cb, &[u32::MAX, self.param]).await?; // callback with M_E_ERROR
+                break;
+            }
 
-            Ok(WIPICResult { results: Vec::new() })
+            context.system().sleep(POLL_INTERVAL).await;
         }
+
+        Ok(WIPICResult { results: Vec::new() })
     }
+}
 
-    context.spawn(Box::new(ConnectCallback { cb, param }))?;
+pub async fn connect(context: &mut dyn WIPICContext, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_netConnect({cb:#x}, {param:#x})");
 
-    Ok(0)
+    schedule(context, Pending::NetworkConnect, cb, param)
 }
 
 pub async fn close(_context: &mut dyn WIPICContext) -> Result<()> {
-    tracing::warn!("stub MC_netClose()");
+    tracing::debug!("MC_netClose()");
 
     Ok(())
 }
 
-pub async fn socket_close(_context: &mut dyn WIPICContext, fd: i32) -> Result<i32> {
-    tracing::warn!("stub MC_netSocketClose({fd})");
+pub async fn socket(context: &mut dyn WIPICContext, domain: i32, r#type: i32) -> Result<i32> {
+    tracing::debug!("MC_netSocket({domain}, {type})");
+
+    let mut connections = context.system().connections();
+    match r#type {
+        MC_SOCKET_STREAM => Ok(connections.create_socket() as _),
+        MC_SOCKET_DGRAM => Ok(connections.open_datagram_socket().map_or_else(error_code, |x| x as _)),
+        _ => {
+            tracing::warn!("MC_netSocket: unsupported socket type {type}");
+            Ok(M_E_INVALID)
+        }
+    }
+}
+
+// addr is in network byte order, port in host byte order
+pub async fn socket_connect(context: &mut dyn WIPICContext, fd: i32, addr: WIPICWord, port: i32, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_netSocketConnect({fd}, {addr:#x}, {port}, {cb:#x}, {param:#x})");
+
+    let result = context.system().connections().connect(fd as _, addr.to_le_bytes(), port as _);
+    if let Err(err) = result {
+        return Ok(error_code(err));
+    }
+
+    schedule(context, Pending::SocketConnect(fd), cb, param)
+}
+
+pub async fn socket_write(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, len: i32) -> Result<i32> {
+    tracing::debug!("MC_netSocketWrite({fd}, {buf:#x}, {len})");
+
+    let length = (len.max(0) as usize).min(MAX_TRANSFER_LENGTH);
+    let mut chunk = vec![0; length.min(MAX_PACKET_LENGTH as _)];
+    let mut total = 0;
+    while total < length {
+        let data = &mut chunk[..(length - total).min(MAX_PACKET_LENGTH as _)];
+        context.read_bytes(buf + total as WIPICWord, data)?;
+
+        let mut connections = context.system().connections();
+        let result = if let Some(http) = connections.http(fd as _) {
+            if http.write(data) { Ok(data.len()) } else { Err(M_E_ERROR) }
+        } else if let Some(socket) = connections.socket(fd as _) {
+            socket.write(data).map_err(error_code)
+        } else {
+            Err(M_E_INVALID)
+        };
+
+        match result {
+            Ok(written) => {
+                total += written;
+                if written < data.len() {
+                    break;
+                }
+            }
+            // bytes already sent are reported, the error shows up on the next call
+            Err(_) if total > 0 => break,
+            Err(code) => return Ok(code),
+        }
+    }
+
+    Ok(total as _)
+}
+
+pub async fn socket_read(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, len: i32) -> Result<i32> {
+    tracing::debug!("MC_netSocketRead({fd}, {buf:#x}, {len})");
+
+    let length = (len.max(0) as usize).min(MAX_TRANSFER_LENGTH);
+    let mut chunk = vec![0; length.min(MAX_PACKET_LENGTH as _)];
+    let mut total = 0;
+    while total < length {
+        let data = &mut chunk[..(length - total).min(MAX_PACKET_LENGTH as _)];
+        let result = {
+            let mut connections = context.system().connections();
+            if let Some(http) = connections.http(fd as _) {
+                Ok(http.read(data))
+            } else if let Some(socket) = connections.socket(fd as _) {
+                socket.read(data).map_err(error_code)
+            } else {
+                Err(M_E_INVALID)
+            }
+        };
+
+        match result {
+            Ok(read) => {
+                context.write_bytes(buf + total as WIPICWord, &data[..read])?;
+                total += read;
+                if read < data.len() {
+                    break;
+                }
+            }
+            Err(_) if total > 0 => break,
+            Err(code) => return Ok(code),
+        }
+    }
+
+    Ok(total as _)
+}
+
+pub async fn socket_close(context: &mut dyn WIPICContext, fd: i32) -> Result<i32> {
+    tracing::debug!("MC_netSocketClose({fd})");
+
+    Ok(if context.system().connections().close(fd as _) {
+        M_E_SUCCESS
+    } else {
+        M_E_INVALID
+    })
+}
+
+// binding and accepting are out of scope: datagram sockets send from an ephemeral port picked by the platform and
+// there are no listening sockets, so both fail
+pub async fn socket_bind(_context: &mut dyn WIPICContext, fd: i32, addr: WIPICWord, port: i32) -> Result<i32> {
+    tracing::warn!("MC_netSocketBind({fd}, {addr:#x}, {port}): listening sockets are not supported");
+
+    Ok(M_E_ERROR)
+}
+
+pub async fn get_max_packet_length(_context: &mut dyn WIPICContext) -> Result<i32> {
+    tracing::debug!("MC_netGetMaxPacketLength()");
+
+    Ok(MAX_PACKET_LENGTH)
+}
+
+// addr is in network byte order, port in host byte order. datagrams can't be split, so larger ones are rejected
+pub async fn socket_send_to(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, len: i32, addr: WIPICWord, port: i32) -> Result<i32> {
+    tracing::debug!("MC_netSocketSendTo({fd}, {buf:#x}, {len}, {addr:#x}, {port})");
+
+    if !(0..=MAX_PACKET_LENGTH).contains(&len) {
+        return Ok(M_E_INVALID);
+    }
+    let mut data = vec![0; len as usize];
+    context.read_bytes(buf, &mut data)?;
+
+    let mut connections = context.system().connections();
+    let Some(socket) = connections.datagram_socket(fd as _) else {
+        return Ok(M_E_INVALID);
+    };
+
+    Ok(match socket.send_to(addr.to_le_bytes(), port as _, &data) {
+        Ok(sent) => sent as _,
+        Err(err) => error_code(err),
+    })
+}
+
+// receives one datagram, truncated to `len` bytes, storing the sender like MC_netSocketSendTo takes it
+pub async fn socket_rcv_from(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, len: i32, p_addr: WIPICWord, p_port: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_netSocketRcvFrom({fd}, {buf:#x}, {len}, {p_addr:#x}, {p_port:#x})");
+
+    let mut data = vec![0; len.clamp(0, MAX_PACKET_LENGTH) as usize];
+    let result = {
+        let mut connections = context.system().connections();
+        let Some(socket) = connections.datagram_socket(fd as _) else {
+            return Ok(M_E_INVALID);
+        };
+        socket.recv_from(&mut data)
+    };
+
+    let (length, address, port) = match result {
+        Ok(x) => x,
+        Err(err) => return Ok(error_code(err)),
+    };
+    context.write_bytes(buf, &data[..length])?;
+    if p_addr != 0 {
+        write_generic(context, p_addr, u32::from_le_bytes(address))?;
+    }
+    if p_port != 0 {
+        write_generic(context, p_port, port as i32)?;
+    }
+
+    Ok(length as _)
+}
+
+pub async fn get_host_addr(context: &mut dyn WIPICContext, name: WIPICWord, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
+    let host = read_string(context, name)?;
+    tracing::debug!("MC_netGetHostAddr({host}, {cb:#x}, {param:#x})");
+
+    schedule(context, Pending::Resolve(host), cb, param)
+}
+
+pub async fn socket_accept(_context: &mut dyn WIPICContext, fd: i32, p_addr: WIPICWord, p_port: WIPICWord) -> Result<i32> {
+    tracing::warn!("MC_netSocketAccept({fd}, {p_addr:#x}, {p_port:#x}): listening sockets are not supported");
+
+    Ok(M_E_ERROR)
+}
+
+// read and write callbacks fire once; applications register again to be notified again
+pub async fn set_read_cb(context: &mut dyn WIPICContext, fd: i32, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_netSetReadCB({fd}, {cb:#x}, {param:#x})");
+
+    if cb == 0 {
+        return Ok(M_E_SUCCESS);
+    }
+
+    schedule(context, Pending::Readable(fd), cb, param)
+}
+
+pub async fn set_write_cb(context: &mut dyn WIPICContext, fd: i32, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_netSetWriteCB({fd}, {cb:#x}, {param:#x})");
+
+    if cb == 0 {
+        return Ok(M_E_SUCCESS);
+    }
+
+    schedule(context, Pending::Writable(fd), cb, param)
+}
+
+pub async fn http_open(context: &mut dyn WIPICContext, url: WIPICWord) -> Result<i32> {
+    let url = read_string(context, url)?;
+    tracing::debug!("MC_netHttpOpen({url})");
+
+    Ok(context.system().connections().open_http(&url).map(|x| x as _).unwrap_or(M_E_INVALID))
+}
+
+pub async fn http_connect(context: &mut dyn WIPICContext, fd: i32, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_netHttpConnect({fd}, {cb:#x}, {param:#x})");
+
+    if context.system().connections().http(fd as _).is_none() {
+        return Ok(M_E_INVALID);
+    }
+
+    schedule(context, Pending::Http(fd), cb, param)
+}
+
+pub async fn http_set_request_method(context: &mut dyn WIPICContext, fd: i32, method: WIPICWord) -> Result<i32> {
+    let method = read_string(context, method)?;
+    tracing::debug!("MC_netHttpSetRequestMethod({fd}, {method})");
+
+    with_http(context, fd, |http| if http.set_method(&method) { M_E_SUCCESS } else { M_E_INVALID })
+}
+
+pub async fn http_get_request_method(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    tracing::debug!("MC_netHttpGetRequestMethod({fd}, {buf:#x}, {buf_size})");
+
+    let method = query_http(context, fd, |http| Ok(String::from(http.method())));
+
+    copy_string_out(context, method, buf, buf_size)
+}
+
+pub async fn http_set_request_property(context: &mut dyn WIPICContext, fd: i32, key: WIPICWord, value: WIPICWord) -> Result<i32> {
+    let key = read_string(context, key)?;
+    let value = read_string(context, value)?;
+    tracing::debug!("MC_netHttpSetRequestProperty({fd}, {key}, {value})");
+
+    with_http(context, fd, |http| {
+        if http.set_request_property(&key, &value) {
+            M_E_SUCCESS
+        } else {
+            M_E_ERROR
+        }
+    })
+}
+
+pub async fn http_get_request_property(context: &mut dyn WIPICContext, fd: i32, key: WIPICWord, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    let key = read_string(context, key)?;
+    tracing::debug!("MC_netHttpGetRequestProperty({fd}, {key}, {buf:#x}, {buf_size})");
+
+    let value = query_http(context, fd, |http| http.request_property(&key).map(String::from).ok_or(M_E_NOENT));
+
+    copy_string_out(context, value, buf, buf_size)
+}
+
+pub async fn http_set_proxy(context: &mut dyn WIPICContext, fd: i32, host: WIPICWord, port: i32) -> Result<i32> {
+    let host = read_string(context, host)?;
+    tracing::debug!("MC_netHttpSetProxy({fd}, {host}, {port})");
+
+    with_http(context, fd, |http| if http.set_proxy(&host, port as _) { M_E_SUCCESS } else { M_E_ERROR })
+}
+
+pub async fn http_get_proxy(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, buf_size: i32, p_port: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_netHttpGetProxy({fd}, {buf:#x}, {buf_size}, {p_port:#x})");
+
+    let proxy = query_http(context, fd, |http| {
+        http.proxy().map(|(host, port)| (String::from(host), port)).ok_or(M_E_NOENT)
+    });
+    if let (Ok((_, port)), true) = (&proxy, p_port != 0) {
+        write_generic(context, p_port, *port as u32)?;
+    }
+
+    copy_string_out(context, proxy.map(|(host, _)| host), buf, buf_size)
+}
+
+pub async fn http_get_response_code(context: &mut dyn WIPICContext, fd: i32) -> Result<i32> {
+    tracing::debug!("MC_netHttpGetResponseCode({fd})");
+
+    Ok(query_http(context, fd, |http| Ok(response(http)?.code as i32)).unwrap_or_else(|x| x))
+}
+
+pub async fn http_get_response_message(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    tracing::debug!("MC_netHttpGetResponseMessage({fd}, {buf:#x}, {buf_size})");
+
+    let message = query_http(context, fd, |http| Ok(response(http)?.message.clone()));
+
+    copy_string_out(context, message, buf, buf_size)
+}
+
+pub async fn http_get_header_field(context: &mut dyn WIPICContext, fd: i32, key: WIPICWord, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    let key = read_string(context, key)?;
+    tracing::debug!("MC_netHttpGetHeaderField({fd}, {key}, {buf:#x}, {buf_size})");
+
+    let value = query_http(context, fd, |http| response_header(http, &key));
+
+    copy_string_out(context, value, buf, buf_size)
+}
+
+pub async fn http_get_length(context: &mut dyn WIPICContext, fd: i32) -> Result<i32> {
+    tracing::debug!("MC_netHttpGetLength({fd})");
+
+    // -1 when the response announced no length
+    let length = query_http(context, fd, |http| Ok(response(http)?.content_length().map(|x| x as i32).unwrap_or(-1)));
+
+    Ok(length.unwrap_or_else(|x| x))
+}
+
+pub async fn http_get_type(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    tracing::debug!("MC_netHttpGetType({fd}, {buf:#x}, {buf_size})");
+
+    let value = query_http(context, fd, |http| response_header(http, "Content-Type"));
+
+    copy_string_out(context, value, buf, buf_size)
+}
+
+pub async fn http_get_encoding(context: &mut dyn WIPICContext, fd: i32, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    tracing::debug!("MC_netHttpGetEncoding({fd}, {buf:#x}, {buf_size})");
+
+    let value = query_http(context, fd, |http| response_header(http, "Content-Encoding"));
+
+    copy_string_out(context, value, buf, buf_size)
+}
+
+pub async fn http_close(context: &mut dyn WIPICContext, fd: i32) -> Result<i32> {
+    tracing::debug!("MC_netHttpClose({fd})");
+
+    let mut connections = context.system().connections();
+    if connections.http(fd as _).is_none() {
+        return Ok(M_E_INVALID);
+    }
+    connections.close(fd as _);
+
+    Ok(M_E_SUCCESS)
+}
+
+fn schedule(context: &mut dyn WIPICContext, pending: Pending, cb: WIPICWord, param: WIPICWord) -> Result<i32> {
+    context.spawn(Box::new(PendingCallback { pending, cb, param }))?;
+
+    Ok(M_E_SUCCESS)
+}
+
+fn error_code(err: NetworkError) -> i32 {
+    match err {
+        NetworkError::WouldBlock => M_E_WOULDBLOCK,
+        NetworkError::Invalid => M_E_INVALID,
+        NetworkError::HostNotFound => M_E_NOENT,
+        NetworkError::ConnectionRefused | NetworkError::ConnectionLost | NetworkError::Offline => M_E_ERROR,
+    }
+}
+
+fn read_string(context: &dyn WIPICContext, ptr: WIPICWord) -> Result<String> {
+    let bytes = read_null_terminated_string_bytes(context, ptr)?;
+
+    Ok(encoding_rs::EUC_KR.decode(&bytes).0.into_owned())
+}
+
+fn with_http<F>(context: &mut dyn WIPICContext, fd: i32, f: F) -> Result<i32>
+where
+    F: FnOnce(&mut HttpConnection) -> i32,
+{
+    Ok(context.system().connections().http(fd as _).map(f).unwrap_or(M_E_INVALID))
+}
+
+// value from the http request `fd`, or the error code to return
+fn query_http<T, F>(context: &mut dyn WIPICContext, fd: i32, f: F) -> core::result::Result<T, i32>
+where
+    F: FnOnce(&HttpConnection) -> core::result::Result<T, i32>,
+{
+    context.system().connections().http(fd as _).map(|x| f(x)).unwrap_or(Err(M_E_INVALID))
+}
+
+fn response(http: &HttpConnection) -> core::result::Result<&HttpResponse, i32> {
+    http.response().ok_or(M_E_ERROR)
+}
+
+fn response_header(http: &HttpConnection, name: &str) -> core::result::Result<String, i32> {
+    response(http)?.header(name).map(String::from).ok_or(M_E_NOENT)
+}
+
+// copies `value` into a buffer of `buf_size` bytes, returning its full length in bytes
+fn copy_string_out(context: &mut dyn WIPICContext, value: core::result::Result<String, i32>, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    let value = match value {
+        Ok(value) => value,
+        Err(code) => return Ok(code),
+    };
+
+    let bytes = encoding_rs::EUC_KR.encode(&value).0;
+    if buf != 0 && buf_size > 0 {
+        let length = bytes.len().min(buf_size as usize - 1);
+        write_null_terminated_string_bytes(context, buf, &bytes[..length])?;
+    }
+
+    Ok(bytes.len() as _)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{boxed::Box, vec, vec::Vec};
+
+    use test_utils::{LoopbackNetwork, TestPlatform};
+    use wie_backend::{DefaultTaskRunner, Network, NetworkError, Socket, System};
+    use wie_util::{ByteRead, ByteWrite, Result, read_generic};
+
+    use crate::context::test::TestContext;
+
+    use super::{
+        M_E_ERROR, M_E_INVALID, M_E_NOENT, M_E_WOULDBLOCK, MAX_PACKET_LENGTH, MC_SOCKET_DGRAM, MC_SOCKET_STREAM, connect, get_host_addr,
+        http_connect, http_get_header_field, http_get_length, http_get_response_code, http_open, set_read_cb, socket, socket_close, socket_connect,
+        socket_rcv_from, socket_read, socket_send_to, socket_write,
+    };
+
+    const HOST_ADDRESS: [u8; 4] = [10, 0, 0, 1];
+
+    // echoes writes back, or serves `response` if there is one
+    struct StubSocket {
+        incoming: Vec<u8>,
+        echo: bool,
+    }
+
+    impl Socket for StubSocket {
+        fn poll_connect(&mut self) -> core::result::Result<(), NetworkError> {
+            Ok(())
+        }
 
-    Ok(-1) // M_E_ERROR
+        fn poll_readable(&mut self) -> core::result::Result<(), NetworkError> {
+            Ok(())
+        }
+
+        fn read(&mut self, buf: &mut [u8]) -> core::result::Result<usize, NetworkError> {
+            let length = self.incoming.len().min(buf.len());
+            buf[..length].copy_from_slice(&self.incoming[..length]);
+            self.incoming.drain(..length);
+
+            Ok(length)
+        }
+
+        fn write(&mut self, data: &[u8]) -> core::result::Result<usize, NetworkError> {
+            if self.echo {
+                self.incoming.extend_from_slice(data);
+            }
+
+            Ok(data.len())
+        }
+    }
+
+    struct StubNetwork {
+        response: Option<&'static [u8]>,
+    }
+
+    impl Network for StubNetwork {
+        fn resolve(&self, host: &str) -> core::result::Result<[u8; 4], NetworkError> {
+            if host == "rank.example.com" {
+                Ok(HOST_ADDRESS)
+            } else {
+                Err(NetworkError::HostNotFound)
+            }
+        }
+
+        fn connect(&self, address: [u8; 4], port: u16) -> core::result::Result<Box<dyn Socket>, NetworkError> {
+            assert_eq!((address, port), (HOST_ADDRESS, 80));
+
+            Ok(Box::new(StubSocket {
+                incoming: self.response.map(|x| x.to_vec()).unwrap_or_default(),
+                echo: self.response.is_none(),
+            }))
+        }
+    }
+
+    fn test_context(platform: TestPlatform) -> TestContext {
+        TestContext::with_system(System::new(Box::new(platform), "test-pid", "test-aid", DefaultTaskRunner))
+    }
+
+    #[futures_test::test]
+    async fn connect_fails_when_offline() -> Result<()> {
+        let mut context = test_context(TestPlatform::new());
+
+        assert_eq!(connect(&mut context, 0x1000, 0x55).await?, 0);
+        context.run_spawned().await?;
+        assert_eq!(context.calls(), [(0x1000, vec![M_E_ERROR as u32, 0x55])]);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn resolves_host_names() -> Result<()> {
+        let mut context = test_context(TestPlatform::new().with_network(StubNetwork { response: None }));
+
+        context.write_bytes(0x100, b"rank.example.com\0")?;
+        assert_eq!(get_host_addr(&mut context, 0x100, 0x1000, 0x55).await?, 0);
+        context.write_bytes(0x100, b"unknown.example.com\0")?;
+        assert_eq!(get_host_addr(&mut context, 0x100, 0x1000, 0x66).await?, 0);
+        context.run_spawned().await?;
+
+        assert_eq!(
+            context.calls(),
+            [
+                (0x1000, vec![0, u32::from_le_bytes(HOST_ADDRESS), 0x55]),
+                (0x1000, vec![M_E_NOENT as u32, 0, 0x66])
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn socket_round_trip() -> Result<()> {
+        let mut context = test_context(TestPlatform::new().with_network(StubNetwork { response: None }));
+
+        let fd = socket(&mut context, 0, MC_SOCKET_STREAM).await?;
+        assert!(fd > 0);
+        assert_eq!(
+            socket_connect(&mut context, fd, u32::from_le_bytes(HOST_ADDRESS), 80, 0x1000, 0x55).await?,
+            0
+        );
+        context.run_spawned().await?;
+        assert_eq!(context.calls(), [(0x1000, vec![fd as u32, 0, 0x55])]);
+
+        context.write_bytes(0x100, b"ping")?;
+        assert_eq!(socket_write(&mut context, fd, 0x100, 4).await?, 4);
+        assert_eq!(set_read_cb(&mut context, fd, 0x2000, 0x66).await?, 0);
+        context.run_spawned().await?;
+        assert_eq!(context.calls()[1], (0x2000, vec![fd as u32, 0, 0x66]));
+
+        assert_eq!(socket_read(&mut context, fd, 0x200, 16).await?, 4);
+        let mut buf = [0; 4];
+        context.read_bytes(0x200, &mut buf)?;
+        assert_eq!(&buf, b"ping");
+
+        assert_eq!(socket_close(&mut context, fd).await?, 0);
+        assert_eq!(socket_close(&mut context, fd).await?, M_E_INVALID);
+        assert_eq!(socket_read(&mut context, fd, 0x200, 16).await?, M_E_INVALID);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn large_transfers_are_chunked() -> Result<()> {
+        let mut context = test_context(TestPlatform::new().with_network(StubNetwork { response: None }));
+
+        let fd = socket(&mut context, 0, MC_SOCKET_STREAM).await?;
+        socket_connect(&mut context, fd, u32::from_le_bytes(HOST_ADDRESS), 80, 0x1000, 0x55).await?;
+        context.run_spawned().await?;
+
+        let data = (0..4000).map(|x| x as u8).collect::<Vec<_>>();
+        context.write_bytes(0x1000, &data)?;
+        assert_eq!(socket_write(&mut context, fd, 0x1000, data.len() as _).await?, 4000);
+
+        // a huge length reads what is there instead of allocating it
+        assert_eq!(socket_read(&mut context, fd, 0x4000, i32::MAX).await?, 4000);
+        let mut buf = vec![0; 4000];
+        context.read_bytes(0x4000, &mut buf)?;
+        assert_eq!(buf, data);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn datagram_round_trip() -> Result<()> {
+        let network = LoopbackNetwork::new().with_service(9000, |data| data.iter().rev().copied().collect());
+        let mut context = test_context(TestPlatform::new().with_network(network));
+        let localhost = u32::from_le_bytes([127, 0, 0, 1]);
+
+        let fd = socket(&mut context, 0, MC_SOCKET_DGRAM).await?;
+        assert!(fd > 0);
+        assert_eq!(socket_rcv_from(&mut context, fd, 0x200, 16, 0x300, 0x304).await?, M_E_WOULDBLOCK);
+
+        context.write_bytes(0x100, b"ping")?;
+        assert_eq!(socket_send_to(&mut context, fd, 0x100, 4, localhost, 9000).await?, 4);
+        assert_eq!(
+            socket_send_to(&mut context, fd, 0x100, MAX_PACKET_LENGTH + 1, localhost, 9000).await?,
+            M_E_INVALID
+        );
+
+        assert_eq!(socket_rcv_from(&mut context, fd, 0x200, 16, 0x300, 0x304).await?, 4);
+        let mut buf = [0; 4];
+        context.read_bytes(0x200, &mut buf)?;
+        assert_eq!(&buf, b"gnip");
+        assert_eq!(read_generic::<u32, _>(&context, 0x300)?, localhost);
+        assert_eq!(read_generic::<i32, _>(&context, 0x304)?, 9000);
+
+        // stream operations don't apply to datagram sockets
+        assert_eq!(socket_write(&mut context, fd, 0x100, 4).await?, M_E_INVALID);
+        assert_eq!(socket_close(&mut context, fd).await?, 0);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn http_get() -> Result<()> {
+        let network = StubNetwork {
+            response: Some(b"HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 5\r\n\r\nhello"),
+        };
+        let mut context = test_context(TestPlatform::new().with_network(network));
+
+        context.write_bytes(0x100, b"http://rank.example.com/top\0")?;
+        let fd = http_open(&mut context, 0x100).await?;
+        assert!(fd > 0);
+        assert_eq!(http_get_response_code(&mut context, fd).await?, M_E_ERROR);
+
+        assert_eq!(http_connect(&mut context, fd, 0x1000, 0x55).await?, 0);
+        context.run_spawned().await?;
+        assert_eq!(context.calls(), [(0x1000, vec![fd as u32, 0, 0x55])]);
+
+        assert_eq!(http_get_response_code(&mut context, fd).await?, 200);
+        assert_eq!(http_get_length(&mut context, fd).await?, 5);
+
+        context.write_bytes(0x100, b"content-type\0")?;
+        assert_eq!(http_get_header_field(&mut context, fd, 0x100, 0x200, 32).await?, 10);
+        let mut buf = [0; 11];
+        context.read_bytes(0x200, &mut buf)?;
+        assert_eq!(&buf, b"text/plain\0");
+
+        assert_eq!(socket_read(&mut context, fd, 0x300, 16).await?, 5);
+        let mut buf = [0; 5];
+        context.read_bytes(0x300, &mut buf)?;
+        assert_eq!(&buf, b"hello");
+
+        Ok(())
+    }
 }
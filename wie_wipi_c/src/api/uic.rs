@@ -1,37 +1,952 @@
-use wie_util::{Result, read_null_terminated_string_bytes};
+mod component;
+mod paint;
+mod time;
+
+use alloc::{string::String, vec, vec::Vec};
+
+use bytemuck::{Pod, Zeroable};
 
 use wipi_types::wipic::{WIPICIndirectPtr, WIPICWord};
 
-use crate::context::WIPICContext;
+use wie_util::{Result, read_generic, read_null_terminated_string_bytes, write_generic};
+
+use crate::{
+    api::{
+        graphics::{self, framebuffer::FrameBuffer},
+        input_method::key_code,
+    },
+    context::WIPICContext,
+};
+
+use self::{
+    component::{UicClass, UicComponent, UicItem, alloc_application_context, copy_string_out, is_application_context, read_string, replace_string},
+    time::{DateTime, TimeField},
+};
+
+const M_E_SUCCESS: i32 = 0;
+const M_E_INVALID: i32 = -9;
+const M_E_SHORTBUF: i32 = -18;
 
-pub async fn create_application_context(_context: &mut dyn WIPICContext) -> Result<WIPICIndirectPtr> {
-    tracing::warn!("stub MC_uicCreateApplicationContext");
+const MV_KEY_PRESS_EVENT: i32 = 1;
+const MV_KEY_RELEASE_EVENT: i32 = 2;
+
+const MH_KEY_UP: i32 = -1;
+const MH_KEY_DOWN: i32 = -2;
+const MH_KEY_LEFT: i32 = -3;
+const MH_KEY_RIGHT: i32 = -4;
+const MH_KEY_SELECT: i32 = -5;
+const MH_KEY_CLEAR: i32 = -16;
+
+// events passed to the callback registered with MC_uicSetCallback
+const MC_UIC_EVENT_SELECTED: i32 = 1;
+const MC_UIC_EVENT_CHANGED: i32 = 2;
+
+#[derive(Pod, Zeroable, Copy, Clone)]
+#[repr(C)]
+struct UicRect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
 
-    Ok(WIPICIndirectPtr(0))
+enum KeyOutcome {
+    Ignored,
+    Consumed,
+    Notify(i32),
 }
 
-pub async fn get_class(context: &mut dyn WIPICContext, psz: WIPICWord) -> Result<WIPICIndirectPtr> {
-    let name_bytes = read_null_terminated_string_bytes(context, psz)?;
-    let name = encoding_rs::EUC_KR.decode(&name_bytes).0;
-    tracing::warn!("stub MC_uicGetClass({name})");
+pub async fn create_application_context(context: &mut dyn WIPICContext) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicCreateApplicationContext()");
+
+    alloc_application_context(context)
+}
+
+pub async fn get_class(context: &mut dyn WIPICContext, psz: WIPICWord) -> Result<WIPICWord> {
+    let name = read_string(context, psz)?;
+    tracing::debug!("MC_uicGetClass({name})");
+
+    match UicClass::from_name(&name) {
+        Some(class) => Ok(class as _),
+        None => {
+            tracing::warn!("MC_uicGetClass: unknown class {name}");
+            Ok(0)
+        }
+    }
+}
+
+pub async fn create(context: &mut dyn WIPICContext, pac: WIPICWord, cls: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicCreate({pac:#x}, {cls:#x})");
+
+    if !is_application_context(context, pac)? {
+        tracing::warn!("MC_uicCreate: unknown application context {pac:#x}");
+    }
+
+    let Some(class) = UicClass::from_raw(cls) else {
+        return Ok(0);
+    };
 
-    Ok(WIPICIndirectPtr(0))
+    UicComponent::alloc(context, class)
 }
 
-pub async fn create(_context: &mut dyn WIPICContext, pac: WIPICWord, cls: WIPICWord) -> Result<WIPICIndirectPtr> {
-    tracing::warn!("stub MC_uicCreate({pac:#x}, {cls:#x})");
+pub async fn destroy(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicDestroy({cc:#x})");
 
-    Ok(WIPICIndirectPtr(0))
+    let Some(component) = read_component(context, cc)? else {
+        return Ok(());
+    };
+
+    component.free(context, cc)
 }
 
-pub async fn destroy(_context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<()> {
-    tracing::warn!("stub MC_uicDestroy({cc:#x})");
+pub async fn repaint(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicRepaint({cc:#x})");
+
+    paint_component(context, cc, WIPICIndirectPtr(0)).await?;
+
+    let platform = context.system().platform();
+    let screen = platform.screen();
+    screen.request_redraw()?;
 
     Ok(())
 }
 
-pub async fn get_menu_item(_context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32, psz: WIPICWord, buflen: i32, img: WIPICWord) -> Result<i32> {
-    tracing::warn!("stub MC_uicGetMenuItem({cc:#x}, {idx}, {psz:#x}, {buflen}, {img:#x})");
+pub async fn paint(context: &mut dyn WIPICContext, cc: WIPICWord, framebuffer: WIPICIndirectPtr) -> Result<()> {
+    tracing::debug!("MC_uicPaint({cc:#x}, {:#x})", framebuffer.0);
+
+    paint_component(context, cc, framebuffer).await
+}
+
+pub async fn get_class_name(context: &mut dyn WIPICContext, cc: WIPICWord, buf: WIPICWord, buf_len: i32) -> Result<i32> {
+    tracing::debug!("MC_uicGetClassName({cc:#x}, {buf:#x}, {buf_len})");
+
+    let Some(component) = read_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    copy_string_out(context, component.class().name(), buf, buf_len)
+}
+
+pub async fn is_instance(context: &mut dyn WIPICContext, cc: WIPICWord, cls: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicIsInstance({cc:#x}, {cls:#x})");
+
+    let Some(component) = read_component(context, cc)? else {
+        return Ok(0);
+    };
+
+    Ok((component.class == cls) as _)
+}
+
+pub async fn handle_event(context: &mut dyn WIPICContext, cc: WIPICWord, r#type: i32, param1: i32, param2: i32) -> Result<i32> {
+    tracing::debug!("MC_uicHandleEvent({cc:#x}, {type}, {param1}, {param2})");
+
+    let Some(component) = read_component(context, cc)? else {
+        return Ok(0);
+    };
+
+    if component.event_handler != 0 {
+        let handled = context
+            .call_function(component.event_handler, &[cc, r#type as _, param1 as _, param2 as _])
+            .await?;
+        if handled != 0 {
+            return Ok(1);
+        }
+    }
+
+    // the application's handler may have changed the component
+    let Some(mut component) = read_component(context, cc)? else {
+        return Ok(0);
+    };
+    if component.enabled == 0 {
+        return Ok(0);
+    }
+
+    let outcome = match component.class() {
+        UicClass::Label => KeyOutcome::Ignored,
+        UicClass::Button => match (r#type, param1) {
+            (MV_KEY_PRESS_EVENT, MH_KEY_SELECT) => KeyOutcome::Notify(MC_UIC_EVENT_SELECTED),
+            _ => KeyOutcome::Ignored,
+        },
+        UicClass::Menu | UicClass::List => handle_item_key(&mut component, r#type, param1),
+        UicClass::TextEdit => handle_text_key(context, &mut component, r#type, param1)?,
+        UicClass::Time => handle_time_key(&mut component, r#type, param1),
+    };
+    component.write(context, cc)?;
+
+    match outcome {
+        KeyOutcome::Ignored => Ok(0),
+        KeyOutcome::Consumed => Ok(1),
+        KeyOutcome::Notify(event) => {
+            if component.callback != 0 {
+                context
+                    .call_function(component.callback, &[cc, event as _, component.callback_param])
+                    .await?;
+            }
+
+            Ok(1)
+        }
+    }
+}
+
+pub async fn configure(context: &mut dyn WIPICContext, cc: WIPICWord, x: i32, y: i32, width: i32, height: i32) -> Result<()> {
+    tracing::debug!("MC_uicConfigure({cc:#x}, {x}, {y}, {width}, {height})");
+
+    update_component(context, cc, |component| {
+        component.x = x;
+        component.y = y;
+        component.width = width;
+        component.height = height;
+    })
+}
+
+pub async fn get_geometry(context: &mut dyn WIPICContext, cc: WIPICWord, p_rect: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicGetGeometry({cc:#x}, {p_rect:#x})");
+
+    let Some(component) = read_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    let rect = UicRect {
+        x: component.x,
+        y: component.y,
+        width: component.width,
+        height: component.height,
+    };
+    write_generic(context, p_rect, rect)?;
+
+    Ok(M_E_SUCCESS)
+}
+
+pub async fn set_enable(context: &mut dyn WIPICContext, cc: WIPICWord, enable: i32) -> Result<()> {
+    tracing::debug!("MC_uicSetEnable({cc:#x}, {enable})");
+
+    update_component(context, cc, |component| component.enabled = (enable != 0) as _)
+}
+
+pub async fn set_callback(context: &mut dyn WIPICContext, cc: WIPICWord, callback: WIPICWord, param: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetCallback({cc:#x}, {callback:#x}, {param:#x})");
+
+    update_component(context, cc, |component| {
+        component.callback = callback;
+        component.callback_param = param;
+    })
+}
+
+pub async fn set_event_handler(context: &mut dyn WIPICContext, cc: WIPICWord, handler: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetEventHandler({cc:#x}, {handler:#x})");
+
+    update_component(context, cc, |component| component.event_handler = handler)
+}
+
+pub async fn set_font(context: &mut dyn WIPICContext, cc: WIPICWord, font: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetFont({cc:#x}, {font:#x})");
+
+    update_component(context, cc, |component| component.font = font)
+}
+
+pub async fn get_font(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicGetFont({cc:#x})");
+
+    Ok(read_component(context, cc)?.map(|x| x.font).unwrap_or(0))
+}
+
+pub async fn set_fg_color(context: &mut dyn WIPICContext, cc: WIPICWord, pixel: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetFgColor({cc:#x}, {pixel:#x})");
+
+    update_component(context, cc, |component| component.fg_color = pixel)
+}
+
+pub async fn set_bg_color(context: &mut dyn WIPICContext, cc: WIPICWord, pixel: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetBgColor({cc:#x}, {pixel:#x})");
+
+    update_component(context, cc, |component| component.bg_color = pixel)
+}
+
+pub async fn set_label(context: &mut dyn WIPICContext, cc: WIPICWord, psz: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetLabel({cc:#x}, {psz:#x})");
+
+    let Some(mut component) = read_component(context, cc)? else {
+        return Ok(());
+    };
+
+    let label = read_string(context, psz)?;
+    component.label = replace_string(context, component.label, &label)?;
+
+    component.write(context, cc)
+}
+
+pub async fn get_label(context: &mut dyn WIPICContext, cc: WIPICWord, buf: WIPICWord, buf_len: i32) -> Result<i32> {
+    tracing::debug!("MC_uicGetLabel({cc:#x}, {buf:#x}, {buf_len})");
+
+    let Some(component) = read_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    let label = read_string(context, component.label)?;
+
+    copy_string_out(context, &label, buf, buf_len)
+}
+
+pub async fn set_label_alignment(context: &mut dyn WIPICContext, cc: WIPICWord, alignment: i32) -> Result<()> {
+    tracing::debug!("MC_uicSetLabelAlignment({cc:#x}, {alignment})");
+
+    update_component(context, cc, |component| component.label_alignment = alignment)
+}
+
+pub async fn set_time_mask(context: &mut dyn WIPICContext, cc: WIPICWord, mask: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetTimeMask({cc:#x}, {mask:#x})");
+
+    update_component(context, cc, |component| {
+        component.time_mask = mask;
+        component.cursor = 0;
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn set_time(
+    context: &mut dyn WIPICContext,
+    cc: WIPICWord,
+    year: i32,
+    month: i32,
+    day: i32,
+    hour: i32,
+    minute: i32,
+    second: i32,
+) -> Result<i32> {
+    tracing::debug!("MC_uicSetTime({cc:#x}, {year}, {month}, {day}, {hour}, {minute}, {second})");
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..60).contains(&second)
+    {
+        return Ok(M_E_INVALID);
+    }
+
+    let time = DateTime {
+        year,
+        month: month as _,
+        day: day as _,
+        hour: hour as _,
+        minute: minute as _,
+        second: second as _,
+    };
+
+    let Some(mut component) = read_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+    component.time = time.to_millis();
+    component.write(context, cc)?;
+
+    Ok(M_E_SUCCESS)
+}
+
+pub async fn set_time_long(context: &mut dyn WIPICContext, cc: WIPICWord, time_low: WIPICWord, time_high: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetTimeLong({cc:#x}, {time_low:#x}, {time_high:#x})");
+
+    update_component(context, cc, |component| component.time = ((time_high as u64) << 32) | time_low as u64)
+}
+
+pub async fn get_time(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<u64> {
+    tracing::debug!("MC_uicGetTime({cc:#x})");
+
+    Ok(read_component(context, cc)?.map(|x| x.time).unwrap_or(0))
+}
+
+pub async fn add_menu_item(context: &mut dyn WIPICContext, cc: WIPICWord, psz: WIPICWord, img: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicAddMenuItem({cc:#x}, {psz:#x}, {img:#x})");
+
+    add_item(context, cc, psz, img)
+}
+
+pub async fn get_menu_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32, psz: WIPICWord, buflen: i32, img: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicGetMenuItem({cc:#x}, {idx}, {psz:#x}, {buflen}, {img:#x})");
+
+    get_item(context, cc, idx, psz, buflen, img)
+}
+
+pub async fn remove_menu_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32) -> Result<i32> {
+    tracing::debug!("MC_uicRemoveMenuItem({cc:#x}, {idx})");
+
+    remove_item(context, cc, idx)
+}
+
+pub async fn set_active_menu_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32) -> Result<i32> {
+    tracing::debug!("MC_uicSetActiveMenuItem({cc:#x}, {idx})");
+
+    set_active_item(context, cc, idx)
+}
+
+pub async fn get_active_menu_item(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicGetActiveMenuItem({cc:#x})");
+
+    get_active_item(context, cc)
+}
+
+pub async fn insert_text(context: &mut dyn WIPICContext, cc: WIPICWord, pos: i32, psz: WIPICWord, len: i32) -> Result<i32> {
+    tracing::debug!("MC_uicInsertText({cc:#x}, {pos}, {psz:#x}, {len})");
+
+    let Some(mut component) = read_text_edit(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    let bytes = if len < 0 {
+        read_null_terminated_string_bytes(context, psz)?
+    } else {
+        let mut bytes = vec![0; len as usize];
+        context.read_bytes(psz, &mut bytes)?;
+
+        bytes
+    };
+    let value = encoding_rs::EUC_KR.decode(&bytes).0;
+
+    finish_composition(context, &mut component);
+    let mut chars = read_string(context, component.text)?.chars().collect::<Vec<_>>();
+
+    let mut room = (component.max_text_size as usize).saturating_sub(1 + chars.iter().map(|&x| char_size(x)).sum::<usize>());
+    let inserted = value
+        .chars()
+        .take_while(|&x| {
+            let fits = char_size(x) <= room;
+            room = room.saturating_sub(char_size(x));
+            fits
+        })
+        .collect::<Vec<_>>();
+
+    let pos = (pos.max(0) as usize).min(chars.len());
+    chars.splice(pos..pos, inserted.iter().copied());
+    if component.cursor as usize >= pos {
+        component.cursor += inserted.len() as u32;
+    }
+
+    component.text = replace_string(context, component.text, &chars.into_iter().collect::<String>())?;
+    component.write(context, cc)?;
+
+    Ok(if inserted.len() < value.chars().count() {
+        M_E_SHORTBUF
+    } else {
+        M_E_SUCCESS
+    })
+}
+
+pub async fn delete_text(context: &mut dyn WIPICContext, cc: WIPICWord, pos: i32, len: i32) -> Result<i32> {
+    tracing::debug!("MC_uicDeleteText({cc:#x}, {pos}, {len})");
+
+    let Some(mut component) = read_text_edit(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    finish_composition(context, &mut component);
+    let mut chars = read_string(context, component.text)?.chars().collect::<Vec<_>>();
+
+    let start = (pos.max(0) as usize).min(chars.len());
+    let end = (start + len.max(0) as usize).min(chars.len());
+    chars.drain(start..end);
+
+    let cursor = component.cursor as usize;
+    if cursor > start {
+        component.cursor = (cursor - (cursor.min(end) - start)) as _;
+    }
+
+    component.text = replace_string(context, component.text, &chars.into_iter().collect::<String>())?;
+    component.write(context, cc)?;
+
+    Ok(M_E_SUCCESS)
+}
+
+pub async fn get_max_text_size(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicGetMaxTextSize({cc:#x})");
+
+    Ok(read_text_edit(context, cc)?.map(|x| x.max_text_size as _).unwrap_or(M_E_INVALID))
+}
+
+pub async fn set_max_text_size(context: &mut dyn WIPICContext, cc: WIPICWord, size: i32) -> Result<i32> {
+    tracing::debug!("MC_uicSetMaxTextSize({cc:#x}, {size})");
+
+    let Some(mut component) = read_text_edit(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+    if size <= 0 {
+        return Ok(M_E_INVALID);
+    }
+
+    component.max_text_size = size as _;
+    component.write(context, cc)?;
+
+    Ok(M_E_SUCCESS)
+}
+
+pub async fn get_text_size(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicGetTextSize({cc:#x})");
+
+    let Some(component) = read_text_edit(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    let text = read_string(context, component.text)?;
+
+    Ok(encoding_rs::EUC_KR.encode(&text).0.len() as _)
+}
+
+pub async fn get_text(context: &mut dyn WIPICContext, cc: WIPICWord, buf: WIPICWord, buf_len: i32) -> Result<i32> {
+    tracing::debug!("MC_uicGetText({cc:#x}, {buf:#x}, {buf_len})");
+
+    let Some(component) = read_text_edit(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    let text = read_string(context, component.text)?;
+
+    copy_string_out(context, &text, buf, buf_len)
+}
+
+pub async fn add_list_item(context: &mut dyn WIPICContext, cc: WIPICWord, psz: WIPICWord, img: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicAddListItem({cc:#x}, {psz:#x}, {img:#x})");
+
+    add_item(context, cc, psz, img)
+}
+
+pub async fn get_list_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32, psz: WIPICWord, buflen: i32, img: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicGetListItem({cc:#x}, {idx}, {psz:#x}, {buflen}, {img:#x})");
+
+    get_item(context, cc, idx, psz, buflen, img)
+}
+
+pub async fn remove_list_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32) -> Result<i32> {
+    tracing::debug!("MC_uicRemoveListItem({cc:#x}, {idx})");
+
+    remove_item(context, cc, idx)
+}
+
+pub async fn set_active_list_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32) -> Result<i32> {
+    tracing::debug!("MC_uicSetActiveListItem({cc:#x}, {idx})");
+
+    set_active_item(context, cc, idx)
+}
+
+pub async fn get_active_list_item(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicGetActiveListItem({cc:#x})");
+
+    get_active_item(context, cc)
+}
+
+pub async fn get_cursor_position(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<i32> {
+    tracing::debug!("OEMC_uicGetCursorPosition({cc:#x})");
+
+    Ok(read_text_edit(context, cc)?.map(|x| x.cursor as _).unwrap_or(M_E_INVALID))
+}
+
+pub async fn set_cursor_position(context: &mut dyn WIPICContext, cc: WIPICWord, pos: i32) -> Result<i32> {
+    tracing::debug!("OEMC_uicSetCursorPosition({cc:#x}, {pos})");
+
+    let Some(mut component) = read_text_edit(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    finish_composition(context, &mut component);
+    let length = read_string(context, component.text)?.chars().count();
+    component.cursor = (pos.max(0) as usize).min(length) as _;
+    component.write(context, cc)?;
+
+    Ok(M_E_SUCCESS)
+}
+
+pub async fn set_line_gap(context: &mut dyn WIPICContext, cc: WIPICWord, gap: i32) -> Result<()> {
+    tracing::debug!("OEMC_uicSetLineGap({cc:#x}, {gap})");
+
+    update_component(context, cc, |component| component.line_gap = gap)
+}
+
+pub async fn get_line_gap(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<i32> {
+    tracing::debug!("OEMC_uicGetLineGap({cc:#x})");
+
+    Ok(read_component(context, cc)?.map(|x| x.line_gap).unwrap_or(M_E_INVALID))
+}
+
+fn read_component(context: &dyn WIPICContext, cc: WIPICWord) -> Result<Option<UicComponent>> {
+    let component = UicComponent::read(context, cc)?;
+    if component.is_none() {
+        tracing::warn!("Invalid UIC component {cc:#x}");
+    }
+
+    Ok(component)
+}
+
+fn read_text_edit(context: &dyn WIPICContext, cc: WIPICWord) -> Result<Option<UicComponent>> {
+    Ok(read_component(context, cc)?.filter(|x| x.class() == UicClass::TextEdit))
+}
+
+fn read_item_component(context: &dyn WIPICContext, cc: WIPICWord) -> Result<Option<UicComponent>> {
+    Ok(read_component(context, cc)?.filter(|x| x.class().has_items()))
+}
+
+fn update_component(context: &mut dyn WIPICContext, cc: WIPICWord, update: impl FnOnce(&mut UicComponent)) -> Result<()> {
+    let Some(mut component) = read_component(context, cc)? else {
+        return Ok(());
+    };
+
+    update(&mut component);
+
+    component.write(context, cc)
+}
+
+async fn paint_component(context: &mut dyn WIPICContext, cc: WIPICWord, framebuffer: WIPICIndirectPtr) -> Result<()> {
+    let Some(component) = read_component(context, cc)? else {
+        return Ok(());
+    };
+
+    let framebuffer = if framebuffer.0 == 0 {
+        graphics::get_screen_framebuffer(context, 0).await?
+    } else {
+        framebuffer
+    };
+    let framebuffer = FrameBuffer(read_generic(context, context.data_ptr(framebuffer)?)?);
+
+    paint::paint(context, &component, &framebuffer)
+}
+
+fn add_item(context: &mut dyn WIPICContext, cc: WIPICWord, psz: WIPICWord, img: WIPICWord) -> Result<i32> {
+    let Some(mut component) = read_item_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    let label = read_string(context, psz)?;
+    let mut items = component.items(context)?;
+    items.push(UicItem {
+        label: replace_string(context, 0, &label)?,
+        image: img,
+    });
+    component.set_items(context, &items)?;
+    if component.active_item < 0 {
+        component.active_item = 0;
+    }
+    component.write(context, cc)?;
+
+    Ok(items.len() as i32 - 1)
+}
+
+fn get_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32, psz: WIPICWord, buf_len: i32, img: WIPICWord) -> Result<i32> {
+    let Some(component) = read_item_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+    let Some(item) = component.items(context)?.get(idx as usize).copied() else {
+        return Ok(M_E_INVALID);
+    };
+
+    let label = read_string(context, item.label)?;
+    copy_string_out(context, &label, psz, buf_len)?;
+    if img != 0 {
+        write_generic(context, img, item.image)?;
+    }
+
+    Ok(M_E_SUCCESS)
+}
+
+fn remove_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32) -> Result<i32> {
+    let Some(mut component) = read_item_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+
+    let mut items = component.items(context)?;
+    if idx as usize >= items.len() {
+        return Ok(M_E_INVALID);
+    }
+
+    let item = items.remove(idx as usize);
+    replace_string(context, item.label, "")?;
+    component.set_items(context, &items)?;
+
+    if (idx as i32) < component.active_item || component.active_item >= items.len() as i32 {
+        component.active_item -= 1;
+    }
+    component.write(context, cc)?;
+
+    Ok(M_E_SUCCESS)
+}
+
+fn set_active_item(context: &mut dyn WIPICContext, cc: WIPICWord, idx: u32) -> Result<i32> {
+    let Some(mut component) = read_item_component(context, cc)? else {
+        return Ok(M_E_INVALID);
+    };
+    if idx >= component.item_count {
+        return Ok(M_E_INVALID);
+    }
+
+    component.active_item = idx as _;
+    component.write(context, cc)?;
+
+    Ok(M_E_SUCCESS)
+}
+
+fn get_active_item(context: &mut dyn WIPICContext, cc: WIPICWord) -> Result<i32> {
+    Ok(read_item_component(context, cc)?.map(|x| x.active_item).unwrap_or(M_E_INVALID))
+}
+
+fn handle_item_key(component: &mut UicComponent, r#type: i32, key: i32) -> KeyOutcome {
+    let count = component.item_count as i32;
+    if r#type != MV_KEY_PRESS_EVENT || count == 0 {
+        return KeyOutcome::Ignored;
+    }
+
+    match key {
+        MH_KEY_UP => {
+            component.active_item = (component.active_item - 1).rem_euclid(count);
+            KeyOutcome::Consumed
+        }
+        MH_KEY_DOWN => {
+            component.active_item = (component.active_item + 1).rem_euclid(count);
+            KeyOutcome::Consumed
+        }
+        MH_KEY_SELECT if component.active_item >= 0 => KeyOutcome::Notify(MC_UIC_EVENT_SELECTED),
+        _ => KeyOutcome::Ignored,
+    }
+}
+
+fn handle_time_key(component: &mut UicComponent, r#type: i32, key: i32) -> KeyOutcome {
+    let fields = TimeField::enabled(component.time_mask);
+    if r#type != MV_KEY_PRESS_EVENT || fields.is_empty() {
+        return KeyOutcome::Ignored;
+    }
+
+    let selected = (component.cursor as usize).min(fields.len() - 1);
+    let delta = match key {
+        MH_KEY_LEFT => {
+            component.cursor = selected.saturating_sub(1) as _;
+            return KeyOutcome::Consumed;
+        }
+        MH_KEY_RIGHT => {
+            component.cursor = (selected + 1).min(fields.len() - 1) as _;
+            return KeyOutcome::Consumed;
+        }
+        MH_KEY_UP => 1,
+        MH_KEY_DOWN => -1,
+        _ => return KeyOutcome::Ignored,
+    };
+
+    component.time = DateTime::from_millis(component.time).step(fields[selected], delta).to_millis();
+
+    KeyOutcome::Notify(MC_UIC_EVENT_CHANGED)
+}
+
+// the composition is kept in the text at the cursor, as the handset-wide input method renders it
+fn handle_text_key(context: &mut dyn WIPICContext, component: &mut UicComponent, r#type: i32, key: i32) -> Result<KeyOutcome> {
+    let mut chars = read_string(context, component.text)?.chars().collect::<Vec<_>>();
+    let mut cursor = (component.cursor as usize).min(chars.len());
+    let start = cursor.saturating_sub(component.composing as usize);
+    chars.drain(start..cursor);
+    cursor = start;
+
+    let now = context.system().platform().now();
+    let outcome = {
+        let mut input_method = context.system().input_method();
+
+        let outcome = match r#type {
+            MV_KEY_PRESS_EVENT if key == MH_KEY_LEFT || key == MH_KEY_RIGHT => {
+                insert_chars(&mut chars, &mut cursor, &input_method.commit());
+                cursor = if key == MH_KEY_LEFT {
+                    cursor.saturating_sub(1)
+                } else {
+                    (cursor + 1).min(chars.len())
+                };
+
+                KeyOutcome::Consumed
+            }
+            MV_KEY_PRESS_EVENT => match key_code(key) {
+                Some(code) => {
+                    // hangul syllables take two bytes in EUC-KR
+                    let used = chars.iter().map(|&x| char_size(x)).sum::<usize>();
+                    let room = (component.max_text_size as usize).saturating_sub(1 + used) / 2;
+
+                    let result = input_method.key(code, now, room);
+                    insert_chars(&mut chars, &mut cursor, &result.committed);
+
+                    if result.consumed {
+                        KeyOutcome::Notify(MC_UIC_EVENT_CHANGED)
+                    } else if key == MH_KEY_CLEAR && cursor > 0 {
+                        cursor -= 1;
+                        chars.remove(cursor);
+
+                        KeyOutcome::Notify(MC_UIC_EVENT_CHANGED)
+                    } else {
+                        KeyOutcome::Ignored
+                    }
+                }
+                None => KeyOutcome::Ignored,
+            },
+            MV_KEY_RELEASE_EVENT => {
+                insert_chars(&mut chars, &mut cursor, &input_method.tick(now));
+
+                if key_code(key).is_some() {
+                    KeyOutcome::Consumed
+                } else {
+                    KeyOutcome::Ignored
+                }
+            }
+            _ => KeyOutcome::Ignored,
+        };
+
+        let composing = input_method.composing();
+        insert_chars(&mut chars, &mut cursor, &composing);
+        component.composing = composing.chars().count() as _;
+
+        outcome
+    };
+
+    component.cursor = cursor as _;
+    component.text = replace_string(context, component.text, &chars.into_iter().collect::<String>())?;
+
+    Ok(outcome)
+}
+
+// text set through the API replaces whatever the input method was composing
+fn finish_composition(context: &mut dyn WIPICContext, component: &mut UicComponent) {
+    if component.composing != 0 {
+        context.system().input_method().commit();
+        component.composing = 0;
+    }
+}
+
+fn insert_chars(chars: &mut Vec<char>, cursor: &mut usize, value: &str) {
+    let length = chars.len();
+    chars.splice(*cursor..*cursor, value.chars());
+    *cursor += chars.len() - length;
+}
+
+fn char_size(c: char) -> usize {
+    if c.is_ascii() { 1 } else { 2 }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{boxed::Box, vec};
+
+    use test_utils::TestPlatform;
+    use wie_backend::{DefaultTaskRunner, System};
+    use wie_util::{ByteWrite, Result, read_generic, read_null_terminated_string_bytes};
+
+    use crate::context::test::TestContext;
+
+    use super::{
+        M_E_INVALID, MC_UIC_EVENT_SELECTED, MH_KEY_CLEAR, MH_KEY_DOWN, MH_KEY_SELECT, MV_KEY_PRESS_EVENT, add_menu_item, create,
+        create_application_context, delete_text, destroy, get_active_menu_item, get_class, get_class_name, get_cursor_position, get_menu_item,
+        get_text, handle_event, insert_text, is_instance, remove_menu_item, set_callback, set_enable, set_event_handler,
+    };
+
+    fn test_context() -> TestContext {
+        TestContext::with_system(System::new(Box::new(TestPlatform::new()), "test-pid", "test-aid", DefaultTaskRunner))
+    }
+
+    async fn create_component(context: &mut TestContext, class: &[u8]) -> Result<u32> {
+        context.write_bytes(0x100, class)?;
+        let class = get_class(context, 0x100).await?;
+        assert_ne!(class, 0);
+
+        let pac = create_application_context(context).await?;
+        create(context, pac, class).await
+    }
+
+    #[futures_test::test]
+    async fn menu_selects_active_item() -> Result<()> {
+        let mut context = test_context();
+        let menu = create_component(&mut context, b"Menu\0").await?;
+
+        context.write_bytes(0x200, b"first\0")?;
+        assert_eq!(add_menu_item(&mut context, menu, 0x200, 0x1234).await?, 0);
+        context.write_bytes(0x200, b"second\0")?;
+        assert_eq!(add_menu_item(&mut context, menu, 0x200, 0).await?, 1);
+        assert_eq!(get_active_menu_item(&mut context, menu).await?, 0);
+
+        assert_eq!(get_menu_item(&mut context, menu, 0, 0x300, 16, 0x400).await?, 0);
+        assert_eq!(read_null_terminated_string_bytes(&context, 0x300)?, b"first");
+        assert_eq!(read_null_terminated_string_bytes(&context, 0x400)?[0], 0x34);
+        assert_eq!(get_menu_item(&mut context, menu, 2, 0x300, 16, 0).await?, -9);
+
+        set_callback(&mut context, menu, 0x5000, 0x77).await?;
+        assert_eq!(handle_event(&mut context, menu, MV_KEY_PRESS_EVENT, MH_KEY_DOWN, 0).await?, 1);
+        assert_eq!(handle_event(&mut context, menu, MV_KEY_PRESS_EVENT, MH_KEY_SELECT, 0).await?, 1);
+        assert_eq!(context.calls(), [(0x5000, vec![menu, MC_UIC_EVENT_SELECTED as _, 0x77])]);
+
+        assert_eq!(remove_menu_item(&mut context, menu, 1).await?, 0);
+        assert_eq!(get_active_menu_item(&mut context, menu).await?, 0);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn text_edit_composes_at_cursor() -> Result<()> {
+        let mut context = test_context();
+        let edit = create_component(&mut context, b"TextEdit\0").await?;
+
+        context.write_bytes(0x200, b"ab\0")?;
+        assert_eq!(insert_text(&mut context, edit, 0, 0x200, -1).await?, 0);
+        assert_eq!(get_cursor_position(&mut context, edit).await?, 2);
+
+        // 천지인: ㅎ(88) ㅏ(12)
+        for key in b"8812" {
+            assert_eq!(handle_event(&mut context, edit, MV_KEY_PRESS_EVENT, *key as _, 0).await?, 1);
+        }
+        assert_eq!(get_text(&mut context, edit, 0x300, 16).await?, 4);
+        assert_eq!(encoding_rs::EUC_KR.decode(&read_null_terminated_string_bytes(&context, 0x300)?).0, "ab하");
+
+        assert_eq!(delete_text(&mut context, edit, 0, 1).await?, 0);
+        assert_eq!(get_cursor_position(&mut context, edit).await?, 2);
+        assert_eq!(handle_event(&mut context, edit, MV_KEY_PRESS_EVENT, MH_KEY_CLEAR, 0).await?, 1);
+        assert_eq!(get_text(&mut context, edit, 0x300, 16).await?, 1);
+        assert_eq!(read_null_terminated_string_bytes(&context, 0x300)?, b"b");
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn event_handler_runs_before_builtin_handling() -> Result<()> {
+        let mut context = test_context();
+        let button = create_component(&mut context, b"Button\0").await?;
+        set_callback(&mut context, button, 0x5000, 0x77).await?;
+        set_event_handler(&mut context, button, 0x6000).await?;
+        let handler_call = (0x6000, vec![button, MV_KEY_PRESS_EVENT as _, MH_KEY_SELECT as _, 0]);
+
+        // a handler returning nonzero takes the event
+        context.set_call_result(0x6000, 1);
+        assert_eq!(handle_event(&mut context, button, MV_KEY_PRESS_EVENT, MH_KEY_SELECT, 0).await?, 1);
+        assert_eq!(context.calls(), [handler_call.clone()]);
+
+        context.set_call_result(0x6000, 0);
+        assert_eq!(handle_event(&mut context, button, MV_KEY_PRESS_EVENT, MH_KEY_SELECT, 0).await?, 1);
+        assert_eq!(
+            context.calls()[1..],
+            [handler_call.clone(), (0x5000, vec![button, MC_UIC_EVENT_SELECTED as _, 0x77])]
+        );
+
+        set_enable(&mut context, button, 0).await?;
+        assert_eq!(handle_event(&mut context, button, MV_KEY_PRESS_EVENT, MH_KEY_SELECT, 0).await?, 0);
+        assert_eq!(context.calls()[3..], [handler_call]);
+
+        let label = create_component(&mut context, b"Label\0").await?;
+        assert_eq!(handle_event(&mut context, label, MV_KEY_PRESS_EVENT, MH_KEY_SELECT, 0).await?, 0);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn destroyed_component_is_rejected() -> Result<()> {
+        let mut context = test_context();
+        let menu = create_component(&mut context, b"Menu\0").await?;
+        context.write_bytes(0x200, b"item\0")?;
+        add_menu_item(&mut context, menu, 0x200, 0).await?;
+        let class = get_class(&mut context, 0x100).await?;
+
+        destroy(&mut context, menu).await?;
+        assert_eq!(read_generic::<u32, _>(&context, menu)?, 0);
+        assert_eq!(get_class_name(&mut context, menu, 0x300, 16).await?, M_E_INVALID);
+        assert_eq!(is_instance(&mut context, menu, class).await?, 0);
+        assert_eq!(handle_event(&mut context, menu, MV_KEY_PRESS_EVENT, MH_KEY_DOWN, 0).await?, 0);
+        destroy(&mut context, menu).await?;
+
+        // the heap handing the memory out again does not revive the handle
+        context.write_bytes(menu, &[0xaa; 64])?;
+        assert_eq!(get_class_name(&mut context, menu, 0x300, 16).await?, M_E_INVALID);
+
+        let menu = create_component(&mut context, b"Menu\0").await?;
+        assert_eq!(add_menu_item(&mut context, menu, 0x200, 0).await?, 0);
+        assert_eq!(get_active_menu_item(&mut context, menu).await?, 0);
 
-    Ok(0)
+        Ok(())
+    }
 }
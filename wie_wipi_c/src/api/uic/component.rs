@@ -0,0 +1,265 @@
+use alloc::{string::String, vec::Vec};
+use core::mem::size_of;
+
+use bytemuck::{Pod, Zeroable};
+
+use wipi_types::wipic::WIPICWord;
+
+use wie_util::{Result, read_generic, read_null_terminated_string_bytes, write_generic, write_null_terminated_string_bytes};
+
+use crate::context::WIPICContext;
+
+// "MCUI" — distinguishes a component from an unrelated guest pointer
+const COMPONENT_MAGIC: u32 = 0x4D435549;
+// "MCUA"
+const APPLICATION_CONTEXT_MAGIC: u32 = 0x4D435541;
+
+const MIN_ITEM_CAPACITY: u32 = 8;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum UicClass {
+    Label = 1,
+    Button = 2,
+    Menu = 3,
+    List = 4,
+    TextEdit = 5,
+    Time = 6,
+}
+
+impl UicClass {
+    const ALL: [Self; 6] = [Self::Label, Self::Button, Self::Menu, Self::List, Self::TextEdit, Self::Time];
+
+    pub fn from_raw(raw: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| *x as u32 == raw)
+    }
+
+    // no title calling MC_uicGetClass has been examined yet, so the names follow the widgets of the MC_uic* functions
+    // (MC_uicAddMenuItem -> "Menu", MC_uicSetTime -> "Time", ...) and must match exactly; unknown names are logged by the caller
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Label => "Label",
+            Self::Button => "Button",
+            Self::Menu => "Menu",
+            Self::List => "List",
+            Self::TextEdit => "TextEdit",
+            Self::Time => "Time",
+        }
+    }
+
+    pub fn has_items(&self) -> bool {
+        matches!(self, Self::Menu | Self::List)
+    }
+}
+
+#[derive(Pod, Zeroable, Copy, Clone)]
+#[repr(C)]
+struct UicApplicationContext {
+    magic: u32,
+    reserved: u32,
+}
+
+/// Component state, kept entirely in emulated memory at the pointer returned from `MC_uicCreate`.
+///
+/// Strings are EUC-KR, null terminated guest allocations; `0` means unset.
+#[derive(Pod, Zeroable, Copy, Clone)]
+#[repr(C)]
+pub struct UicComponent {
+    magic: u32,
+    pub class: u32,
+    /// Milliseconds since the epoch, for [`UicClass::Time`].
+    pub time: u64,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub enabled: u32,
+    pub fg_color: u32,
+    pub bg_color: u32,
+    pub font: u32,
+    pub callback: WIPICWord,
+    pub callback_param: WIPICWord,
+    pub event_handler: WIPICWord,
+    pub label: WIPICWord,
+    pub label_alignment: i32,
+    pub items: WIPICWord, // UicItem[item_capacity]
+    pub item_count: u32,
+    pub item_capacity: u32,
+    pub active_item: i32,
+    pub text: WIPICWord,
+    pub max_text_size: u32,
+    /// Cursor position in characters; the selected field for [`UicClass::Time`].
+    pub cursor: u32,
+    /// Characters before the cursor that are still being composed by the input method.
+    pub composing: u32,
+    pub line_gap: i32,
+    pub time_mask: u32,
+    _padding: u32,
+}
+
+#[derive(Pod, Zeroable, Copy, Clone)]
+#[repr(C)]
+pub struct UicItem {
+    pub label: WIPICWord,
+    pub image: WIPICWord,
+}
+
+impl UicComponent {
+    pub fn new(class: UicClass) -> Self {
+        Self {
+            magic: COMPONENT_MAGIC,
+            class: class as _,
+            enabled: 1,
+            fg_color: 0,
+            bg_color: 0xffff,
+            active_item: -1,
+            max_text_size: 256,
+            line_gap: 2,
+            time_mask: 0x3f,
+            ..Zeroable::zeroed()
+        }
+    }
+
+    pub fn class(&self) -> UicClass {
+        UicClass::from_raw(self.class).unwrap()
+    }
+
+    pub fn read(context: &dyn WIPICContext, ptr: WIPICWord) -> Result<Option<Self>> {
+        if ptr == 0 {
+            return Ok(None);
+        }
+
+        let component: Self = read_generic(context, ptr)?;
+        if component.magic != COMPONENT_MAGIC || UicClass::from_raw(component.class).is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(component))
+    }
+
+    pub fn write(&self, context: &mut dyn WIPICContext, ptr: WIPICWord) -> Result<()> {
+        write_generic(context, ptr, *self)
+    }
+
+    pub fn alloc(context: &mut dyn WIPICContext, class: UicClass) -> Result<WIPICWord> {
+        let ptr = context.alloc_raw(size_of::<Self>() as _)?;
+        Self::new(class).write(context, ptr)?;
+
+        Ok(ptr)
+    }
+
+    pub fn free(self, context: &mut dyn WIPICContext, ptr: WIPICWord) -> Result<()> {
+        for item in self.items(context)? {
+            free_string(context, item.label)?;
+        }
+        if self.items != 0 {
+            context.free_raw(self.items, self.item_capacity * size_of::<UicItem>() as u32)?;
+        }
+        free_string(context, self.label)?;
+        free_string(context, self.text)?;
+
+        // zero the "MCUI" magic before the memory goes back to the heap, so stale pointers are rejected
+        write_generic(context, ptr, Self::zeroed())?;
+        context.free_raw(ptr, size_of::<Self>() as _)
+    }
+
+    pub fn items(&self, context: &dyn WIPICContext) -> Result<Vec<UicItem>> {
+        (0..self.item_count)
+            .map(|i| read_generic(context, self.items + i * size_of::<UicItem>() as u32))
+            .collect()
+    }
+
+    pub fn set_items(&mut self, context: &mut dyn WIPICContext, items: &[UicItem]) -> Result<()> {
+        let count = items.len() as u32;
+        if count > self.item_capacity {
+            let capacity = count.next_power_of_two().max(MIN_ITEM_CAPACITY);
+            let new_items = context.alloc_raw(capacity * size_of::<UicItem>() as u32)?;
+            if self.items != 0 {
+                context.free_raw(self.items, self.item_capacity * size_of::<UicItem>() as u32)?;
+            }
+
+            self.items = new_items;
+            self.item_capacity = capacity;
+        }
+
+        for (i, item) in items.iter().enumerate() {
+            write_generic(context, self.items + (i * size_of::<UicItem>()) as u32, *item)?;
+        }
+        self.item_count = count;
+
+        Ok(())
+    }
+}
+
+pub fn alloc_application_context(context: &mut dyn WIPICContext) -> Result<WIPICWord> {
+    let ptr = context.alloc_raw(size_of::<UicApplicationContext>() as _)?;
+    write_generic(
+        context,
+        ptr,
+        UicApplicationContext {
+            magic: APPLICATION_CONTEXT_MAGIC,
+            reserved: 0,
+        },
+    )?;
+
+    Ok(ptr)
+}
+
+pub fn is_application_context(context: &dyn WIPICContext, ptr: WIPICWord) -> Result<bool> {
+    if ptr == 0 {
+        return Ok(false);
+    }
+
+    let application_context: UicApplicationContext = read_generic(context, ptr)?;
+
+    Ok(application_context.magic == APPLICATION_CONTEXT_MAGIC)
+}
+
+pub fn read_string(context: &dyn WIPICContext, ptr: WIPICWord) -> Result<String> {
+    if ptr == 0 {
+        return Ok(String::new());
+    }
+
+    let bytes = read_null_terminated_string_bytes(context, ptr)?;
+
+    Ok(encoding_rs::EUC_KR.decode(&bytes).0.into_owned())
+}
+
+/// Replaces the string at `old`, returning the new allocation; empty strings are stored as `0`.
+pub fn replace_string(context: &mut dyn WIPICContext, old: WIPICWord, value: &str) -> Result<WIPICWord> {
+    free_string(context, old)?;
+    if value.is_empty() {
+        return Ok(0);
+    }
+
+    let bytes = encoding_rs::EUC_KR.encode(value).0;
+    let ptr = context.alloc_raw(bytes.len() as WIPICWord + 1)?;
+    write_null_terminated_string_bytes(context, ptr, &bytes)?;
+
+    Ok(ptr)
+}
+
+fn free_string(context: &mut dyn WIPICContext, ptr: WIPICWord) -> Result<()> {
+    if ptr == 0 {
+        return Ok(());
+    }
+
+    let length = read_null_terminated_string_bytes(context, ptr)?.len();
+
+    context.free_raw(ptr, length as WIPICWord + 1)
+}
+
+/// Copies `value` into a caller buffer of `buf_size` bytes, truncating it; returns the full length in bytes.
+pub fn copy_string_out(context: &mut dyn WIPICContext, value: &str, buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    let bytes = encoding_rs::EUC_KR.encode(value).0;
+    if buf != 0 && buf_size > 0 {
+        let length = bytes.len().min(buf_size as usize - 1);
+        write_null_terminated_string_bytes(context, buf, &bytes[..length])?;
+    }
+
+    Ok(bytes.len() as _)
+}
@@ -0,0 +1,96 @@
+use alloc::{string::String, vec::Vec};
+
+use wie_backend::canvas::{Clip, TextAlignment, string_width};
+use wie_util::Result;
+
+use crate::{api::graphics::framebuffer::FrameBuffer, context::WIPICContext};
+
+use super::{
+    component::{UicClass, UicComponent, read_string},
+    time::DateTime,
+};
+
+const LABEL_ALIGN_CENTER: i32 = 1;
+const LABEL_ALIGN_RIGHT: i32 = 2;
+
+const PADDING: i32 = 2;
+
+/// Draws `component` into `framebuffer`, clipped to the component's geometry.
+pub fn paint(context: &mut dyn WIPICContext, component: &UicComponent, framebuffer: &FrameBuffer) -> Result<()> {
+    if component.width <= 0 || component.height <= 0 {
+        return Ok(());
+    }
+
+    // gather everything from guest memory up front, the canvas holds the context until flushed
+    let label = read_string(context, component.label)?;
+    let items = component
+        .items(context)?
+        .into_iter()
+        .map(|x| read_string(context, x.label))
+        .collect::<Result<Vec<_>>>()?;
+    let text = match component.class() {
+        UicClass::Time => DateTime::from_millis(component.time).format(component.time_mask),
+        _ => read_string(context, component.text)?,
+    };
+    let line_height = context.system().device_profile().fonts.medium as i32 + component.line_gap;
+
+    let (x, y, width, height) = (component.x, component.y, component.width as u32, component.height as u32);
+    let clip = Clip { x, y, width, height };
+    let fg = framebuffer.pixel_to_color(component.fg_color);
+    let bg = framebuffer.pixel_to_color(component.bg_color);
+
+    let mut canvas = framebuffer.canvas(context)?;
+    canvas.fill_rect(x, y, width, height, bg, clip);
+
+    let (text_x, alignment) = match component.label_alignment {
+        LABEL_ALIGN_CENTER => (x + component.width / 2, TextAlignment::Center),
+        LABEL_ALIGN_RIGHT => (x + component.width - PADDING, TextAlignment::Right),
+        _ => (x + PADDING, TextAlignment::Left),
+    };
+
+    match component.class() {
+        UicClass::Label => {
+            canvas.draw_text(&label, text_x, y + PADDING, alignment, fg, clip);
+        }
+        UicClass::Button => {
+            canvas.draw_rect(x, y, width, height, fg, clip);
+            canvas.draw_text(&label, x + component.width / 2, y + PADDING, TextAlignment::Center, fg, clip);
+        }
+        UicClass::Menu | UicClass::List => {
+            let mut line_y = y + PADDING;
+            if !label.is_empty() {
+                canvas.draw_text(&label, text_x, line_y, alignment, fg, clip);
+                line_y += line_height;
+                canvas.draw_line(x, line_y - 1, x + component.width, line_y - 1, fg, clip);
+            }
+
+            // keep the active item on screen
+            let visible = ((y + component.height - line_y) / line_height).max(1) as usize;
+            let first = (component.active_item.max(0) as usize + 1).saturating_sub(visible);
+
+            for (i, item) in items.iter().enumerate().skip(first).take(visible) {
+                if i as i32 == component.active_item {
+                    canvas.fill_rect(x, line_y, width, line_height as _, fg, clip);
+                    canvas.draw_text(item, x + PADDING, line_y, TextAlignment::Left, bg, clip);
+                } else {
+                    canvas.draw_text(item, x + PADDING, line_y, TextAlignment::Left, fg, clip);
+                }
+                line_y += line_height;
+            }
+        }
+        UicClass::TextEdit => {
+            canvas.draw_rect(x, y, width, height, fg, clip);
+            canvas.draw_text(&text, x + PADDING, y + PADDING, TextAlignment::Left, fg, clip);
+
+            let before_cursor = text.chars().take(component.cursor as usize).collect::<String>();
+            let cursor_x = x + PADDING + string_width(&before_cursor, 10.0) as i32;
+            canvas.draw_line(cursor_x, y + PADDING, cursor_x, y + line_height, fg, clip);
+        }
+        UicClass::Time => {
+            canvas.draw_rect(x, y, width, height, fg, clip);
+            canvas.draw_text(&text, x + PADDING, y + PADDING, TextAlignment::Left, fg, clip);
+        }
+    }
+
+    canvas.flush()
+}
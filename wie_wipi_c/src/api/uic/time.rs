@@ -0,0 +1,189 @@
+use alloc::{format, string::String, vec::Vec};
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// Fields of a time control, in display order; bit `i` of the time mask enables field `i`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl TimeField {
+    const ALL: [Self; 6] = [Self::Year, Self::Month, Self::Day, Self::Hour, Self::Minute, Self::Second];
+
+    pub fn enabled(mask: u32) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, x)| x)
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DateTime {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    pub fn from_millis(millis: u64) -> Self {
+        let millis = millis as i64;
+        let days = millis.div_euclid(MS_PER_DAY);
+        let seconds = millis.rem_euclid(MS_PER_DAY) / 1000;
+        let (year, month, day) = civil_from_days(days);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: (seconds / 3600) as _,
+            minute: (seconds / 60 % 60) as _,
+            second: (seconds % 60) as _,
+        }
+    }
+
+    pub fn to_millis(self) -> u64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        let seconds = days * 86_400 + (self.hour * 3600 + self.minute * 60 + self.second) as i64;
+
+        (seconds.max(0) * 1000) as u64
+    }
+
+    /// Steps `field` by `delta`, wrapping within the field like a handset time picker.
+    pub fn step(mut self, field: TimeField, delta: i32) -> Self {
+        match field {
+            TimeField::Year => self.year = (self.year + delta).clamp(1970, 9999),
+            TimeField::Month => self.month = wrap(self.month, delta, 1, 12),
+            TimeField::Day => self.day = wrap(self.day, delta, 1, days_in_month(self.year, self.month)),
+            TimeField::Hour => self.hour = wrap(self.hour, delta, 0, 23),
+            TimeField::Minute => self.minute = wrap(self.minute, delta, 0, 59),
+            TimeField::Second => self.second = wrap(self.second, delta, 0, 59),
+        }
+        self.day = self.day.min(days_in_month(self.year, self.month));
+
+        self
+    }
+
+    pub fn format(&self, mask: u32) -> String {
+        let date = TimeField::enabled(mask)
+            .into_iter()
+            .filter(|x| matches!(x, TimeField::Year | TimeField::Month | TimeField::Day))
+            .map(|x| self.format_field(x))
+            .collect::<Vec<_>>()
+            .join("-");
+        let time = TimeField::enabled(mask)
+            .into_iter()
+            .filter(|x| matches!(x, TimeField::Hour | TimeField::Minute | TimeField::Second))
+            .map(|x| self.format_field(x))
+            .collect::<Vec<_>>()
+            .join(":");
+
+        match (date.is_empty(), time.is_empty()) {
+            (false, false) => format!("{date} {time}"),
+            (true, _) => time,
+            (_, true) => date,
+        }
+    }
+
+    fn format_field(&self, field: TimeField) -> String {
+        match field {
+            TimeField::Year => format!("{:04}", self.year),
+            TimeField::Month => format!("{:02}", self.month),
+            TimeField::Day => format!("{:02}", self.day),
+            TimeField::Hour => format!("{:02}", self.hour),
+            TimeField::Minute => format!("{:02}", self.minute),
+            TimeField::Second => format!("{:02}", self.second),
+        }
+    }
+}
+
+fn wrap(value: u32, delta: i32, min: u32, max: u32) -> u32 {
+    let range = (max - min + 1) as i32;
+
+    min + (value as i32 - min as i32 + delta).rem_euclid(range) as u32
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = (if month <= 2 { year - 1 } else { year }) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DateTime, TimeField};
+
+    #[test]
+    fn converts_between_millis_and_fields() {
+        // 2004-02-29 23:59:58 UTC
+        let time = DateTime::from_millis(1_078_099_198_000);
+        assert_eq!(
+            time,
+            DateTime {
+                year: 2004,
+                month: 2,
+                day: 29,
+                hour: 23,
+                minute: 59,
+                second: 58
+            }
+        );
+        assert_eq!(time.to_millis(), 1_078_099_198_000);
+        assert_eq!(time.format(0x3f), "2004-02-29 23:59:58");
+        assert_eq!(time.format(0x18), "23:59");
+    }
+
+    #[test]
+    fn step_wraps_and_clamps_day() {
+        let time = DateTime::from_millis(1_078_099_198_000);
+
+        assert_eq!(time.step(TimeField::Second, 3).second, 1);
+        assert_eq!(time.step(TimeField::Hour, 1).hour, 0);
+        assert_eq!(time.step(TimeField::Year, 1).day, 28);
+        assert_eq!(time.step(TimeField::Month, -2).month, 12);
+    }
+}
@@ -16,6 +16,10 @@ pub trait WIPICContext: ByteRead + ByteWrite + Send + Sync {
     fn alloc(&mut self, size: WIPICWord) -> Result<WIPICIndirectPtr>;
     fn free(&mut self, memory: WIPICIndirectPtr) -> Result<()>;
     fn free_raw(&mut self, address: WIPICWord, size: WIPICWord) -> Result<()>;
+    /// Heap bytes available to the application in total.
+    fn total_memory(&self) -> Result<WIPICWord>;
+    /// Bytes of [`Self::total_memory`] still available.
+    fn free_memory(&self) -> Result<WIPICWord>;
     fn data_ptr(&self, memory: WIPICIndirectPtr) -> Result<WIPICWord>;
     async fn call_function(&mut self, address: WIPICWord, args: &[WIPICWord]) -> Result<WIPICWord>;
     fn system(&mut self) -> &mut System;
@@ -88,6 +92,8 @@ pub mod test {
     use wie_backend::{Instant, System};
     use wie_util::{ByteRead, ByteWrite, Result, WieError};
 
+    use crate::method::MethodBody;
+
     use super::{WIPICContext, WIPICMethodBody};
 
     const TEST_MEMORY_SIZE: usize = 0x20000;
@@ -98,6 +104,9 @@ pub mod test {
         last_alloc: usize,
         system: Option<System>,
         resources: Vec<(String, Vec<u8>)>,
+        calls: Vec<(WIPICWord, Vec<WIPICWord>)>,
+        call_results: Vec<(WIPICWord, WIPICWord)>,
+        spawned: Vec<WIPICMethodBody>,
     }
 
     impl TestContext {
@@ -108,6 +117,9 @@ pub mod test {
                 last_alloc: TEST_ALLOC_START,
                 system: None,
                 resources: Vec::new(),
+                calls: Vec::new(),
+                call_results: Vec::new(),
+                spawned: Vec::new(),
             }
         }
 
@@ -117,6 +129,9 @@ pub mod test {
                 last_alloc: TEST_ALLOC_START,
                 system: Some(system),
                 resources: Vec::new(),
+                calls: Vec::new(),
+                call_results: Vec::new(),
+                spawned: Vec::new(),
             }
         }
 
@@ -124,6 +139,26 @@ pub mod test {
             self.resources.push((String::from(name), data.to_vec()));
             self
         }
+
+        /// Guest functions invoked through [`WIPICContext::call_function`], with their arguments.
+        pub fn calls(&self) -> &[(WIPICWord, Vec<WIPICWord>)] {
+            &self.calls
+        }
+
+        /// Makes calls to the guest function at `address` return `result` instead of `0`.
+        pub fn set_call_result(&mut self, address: WIPICWord, result: WIPICWord) {
+            self.call_results.retain(|(x, _)| *x != address);
+            self.call_results.push((address, result));
+        }
+
+        /// Runs the callbacks passed to [`WIPICContext::spawn`] so far, in order.
+        pub async fn run_spawned(&mut self) -> Result<()> {
+            for callback in core::mem::take(&mut self.spawned) {
+                callback.call(self, Box::new([])).await?;
+            }
+
+            Ok(())
+        }
     }
 
     #[async_trait::async_trait]
@@ -147,20 +182,32 @@ pub mod test {
             Ok(())
         }
 
+        fn total_memory(&self) -> Result<WIPICWord> {
+            Ok((TEST_MEMORY_SIZE - TEST_ALLOC_START) as WIPICWord)
+        }
+
+        fn free_memory(&self) -> Result<WIPICWord> {
+            Ok((TEST_MEMORY_SIZE - self.last_alloc) as WIPICWord)
+        }
+
         fn data_ptr(&self, memory: WIPICIndirectPtr) -> Result<WIPICWord> {
             Ok(memory.0)
         }
 
-        async fn call_function(&mut self, _address: WIPICWord, _args: &[WIPICWord]) -> Result<WIPICWord> {
-            todo!()
+        async fn call_function(&mut self, address: WIPICWord, args: &[WIPICWord]) -> Result<WIPICWord> {
+            self.calls.push((address, args.to_vec()));
+
+            Ok(self.call_results.iter().find(|(x, _)| *x == address).map_or(0, |(_, result)| *result))
         }
 
         fn system(&mut self) -> &mut System {
             self.system.as_mut().unwrap()
         }
 
-        fn spawn(&mut self, _callback: WIPICMethodBody) -> Result<()> {
-            todo!()
+        fn spawn(&mut self, callback: WIPICMethodBody) -> Result<()> {
+            self.spawned.push(callback);
+
+            Ok(())
         }
 
         async fn get_resource_size(&self, name: &str) -> Result<Option<usize>> {
@@ -1,9 +1,17 @@
 mod card_canvas;
+mod shell_card;
 mod wipi_file_output_stream;
 mod wipi_midlet;
+pub(crate) mod wipi_socket;
+mod wipi_socket_input_stream;
+mod wipi_socket_output_stream;
 
 pub use self::{
     card_canvas::{CardCanvas, WIPIKeyCode},
+    shell_card::ShellCard,
     wipi_file_output_stream::WIPIFileOutputStream,
     wipi_midlet::WIPIMIDlet,
+    wipi_socket::WIPISocket,
+    wipi_socket_input_stream::WIPISocketInputStream,
+    wipi_socket_output_stream::WIPISocketOutputStream,
 };
@@ -5,13 +5,17 @@ use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::{Class, String};
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
 
+use wie_backend::KeyCode;
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 use wie_midp::classes::{
     javax::microedition::lcdui::{Canvas as MidpCanvas, Display as MidpDisplay, Graphics as MidpGraphics},
     net::wie::MIDPKeyCode,
 };
 
-use crate::classes::org::kwis::msp::lcdui::{Card, Display};
+use crate::classes::{
+    net::wie::ShellCard,
+    org::kwis::msp::lcdui::{Card, Display},
+};
 
 #[repr(i32)]
 #[allow(clippy::upper_case_acronyms, non_camel_case_types)]
@@ -104,6 +108,35 @@ impl WIPIKeyCode {
             None => keycode,
         }
     }
+
+    pub fn to_key_code(&self) -> KeyCode {
+        match self {
+            Self::UP => KeyCode::UP,
+            Self::DOWN => KeyCode::DOWN,
+            Self::LEFT => KeyCode::LEFT,
+            Self::RIGHT => KeyCode::RIGHT,
+            Self::FIRE => KeyCode::OK,
+            Self::LEFT_SOFT_KEY => KeyCode::LEFT_SOFT_KEY,
+            Self::RIGHT_SOFT_KEY => KeyCode::RIGHT_SOFT_KEY,
+            Self::CLEAR => KeyCode::CLEAR,
+            Self::CALL => KeyCode::CALL,
+            Self::HANGUP => KeyCode::HANGUP,
+            Self::VOLUME_UP => KeyCode::VOLUME_UP,
+            Self::VOLUME_DOWN => KeyCode::VOLUME_DOWN,
+            Self::NUM0 => KeyCode::NUM0,
+            Self::NUM1 => KeyCode::NUM1,
+            Self::NUM2 => KeyCode::NUM2,
+            Self::NUM3 => KeyCode::NUM3,
+            Self::NUM4 => KeyCode::NUM4,
+            Self::NUM5 => KeyCode::NUM5,
+            Self::NUM6 => KeyCode::NUM6,
+            Self::NUM7 => KeyCode::NUM7,
+            Self::NUM8 => KeyCode::NUM8,
+            Self::NUM9 => KeyCode::NUM9,
+            Self::HASH => KeyCode::HASH,
+            Self::STAR => KeyCode::STAR,
+        }
+    }
 }
 
 // class net.wie.CardCanvas
@@ -133,6 +166,7 @@ impl CardCanvas {
                 JavaMethodProto::new("removeAllCards", "()V", Self::remove_all_cards, MethodAccessFlags::PUBLIC),
                 // wie private
                 JavaMethodProto::new("handleNotifyEvent", "(III)V", Self::handle_notify_event, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("keyTyped", "(I)V", Self::key_typed, MethodAccessFlags::empty()),
             ],
             fields: vec![JavaFieldProto::new("cards", "Ljava/util/Vector;", FieldAccessFlags::PRIVATE)],
             access_flags: ClassAccessFlags::PUBLIC,
@@ -265,6 +299,27 @@ impl CardCanvas {
         Ok(())
     }
 
+    // host keyboard text only goes to the built-in text widgets of a shell on top; guest cards get key presses only
+    async fn key_typed(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, chr: i32) -> JvmResult<()> {
+        tracing::debug!("net.wie.CardCanvas::keyTyped({this:?}, {chr})");
+
+        let cards = jvm.get_field(&this, "cards", "Ljava/util/Vector;").await?;
+        let length: i32 = jvm.invoke_virtual(&cards, "java/util/Vector", "size", "()I", ()).await?;
+        if length == 0 {
+            return Ok(());
+        }
+
+        let card: ClassInstanceRef<ShellCard> = jvm
+            .invoke_virtual(&cards, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (length - 1,))
+            .await?;
+        let is_shell_card = card.instance.as_ref().is_some_and(|x| jvm.is_instance(&**x, "net/wie/ShellCard"));
+        if is_shell_card {
+            ShellCard::key_typed(jvm, &card, chr).await?;
+        }
+
+        Ok(())
+    }
+
     async fn push_card(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, c: ClassInstanceRef<Card>) -> JvmResult<()> {
         tracing::debug!("net.wie.CardCanvas::pushCard({this:?}, {c:?})");
 
@@ -0,0 +1,135 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::org::kwis::msp::{
+    lcdui::{Display, Graphics},
+    lwc::{Component, ShellComponent},
+};
+
+// class net.wie.ShellCard
+// card hosting a lwc shell component on the display card stack
+pub struct ShellCard;
+
+impl ShellCard {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/ShellCard",
+            parent_class: Some("org/kwis/msp/lcdui/Card"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Lorg/kwis/msp/lwc/ShellComponent;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("showNotify", "(Z)V", Self::show_notify, MethodAccessFlags::PROTECTED),
+            ],
+            fields: vec![JavaFieldProto::new(
+                "shell",
+                "Lorg/kwis/msp/lwc/ShellComponent;",
+                FieldAccessFlags::PRIVATE,
+            )],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, shell: ClassInstanceRef<ShellComponent>) -> JvmResult<()> {
+        tracing::debug!("net.wie.ShellCard::<init>({this:?}, {shell:?})");
+
+        let display: ClassInstanceRef<Display> = jvm
+            .invoke_static("org/kwis/msp/lcdui/Display", "getDefaultDisplay", "()Lorg/kwis/msp/lcdui/Display;", [])
+            .await?;
+
+        let x: i32 = jvm.get_field(&shell, "x", "I").await?;
+        let y: i32 = jvm.get_field(&shell, "y", "I").await?;
+        let width: i32 = jvm.get_field(&shell, "w", "I").await?;
+        let height: i32 = jvm.get_field(&shell, "h", "I").await?;
+
+        let _: () = jvm
+            .invoke_special(
+                &this,
+                "org/kwis/msp/lcdui/Card",
+                "<init>",
+                "(Lorg/kwis/msp/lcdui/Display;IIII)V",
+                (display, x, y, width, height),
+            )
+            .await?;
+        jvm.put_field(&mut this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;", shell).await?;
+
+        Ok(())
+    }
+
+    async fn paint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("net.wie.ShellCard::paint({this:?}, {graphics:?})");
+
+        let shell: ClassInstanceRef<ShellComponent> = jvm.get_field(&this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;").await?;
+        jvm.invoke_virtual(
+            &shell,
+            "org/kwis/msp/lwc/Component",
+            "paint",
+            "(Lorg/kwis/msp/lcdui/Graphics;)V",
+            (graphics,),
+        )
+        .await
+    }
+
+    async fn key_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: i32, key: i32) -> JvmResult<bool> {
+        tracing::debug!("net.wie.ShellCard::keyNotify({this:?}, {type}, {key})");
+
+        let shell: ClassInstanceRef<ShellComponent> = jvm.get_field(&this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;").await?;
+        jvm.invoke_virtual(&shell, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (r#type, key))
+            .await
+    }
+
+    // follows the focus down from the shell; only a built-in text component takes the character
+    pub(crate) async fn key_typed(jvm: &Jvm, this: &ClassInstanceRef<Self>, chr: i32) -> JvmResult<()> {
+        let mut component: ClassInstanceRef<Component> = jvm.get_field(this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;").await?;
+        loop {
+            let Some(instance) = &component.instance else {
+                return Ok(());
+            };
+            if jvm.is_instance(&**instance, "org/kwis/msp/lwc/TextComponent") {
+                break;
+            }
+            if !jvm.is_instance(&**instance, "org/kwis/msp/lwc/ContainerComponent") {
+                return Ok(());
+            }
+
+            component = jvm.get_field(&component, "focusedComponent", "Lorg/kwis/msp/lwc/Component;").await?;
+        }
+
+        // the built-in handler, so that a keyNotify override never sees the host-only event type
+        let _: bool = jvm
+            .invoke_special(
+                &component,
+                "org/kwis/msp/lwc/TextComponent",
+                "keyNotify",
+                "(II)Z",
+                (Component::KEY_TYPED, chr),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn show_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, show: bool) -> JvmResult<()> {
+        tracing::debug!("net.wie.ShellCard::showNotify({this:?}, {show})");
+
+        let _: () = jvm
+            .invoke_special(&this, "org/kwis/msp/lcdui/Card", "showNotify", "(Z)V", (show,))
+            .await?;
+
+        let shell: ClassInstanceRef<ShellComponent> = jvm.get_field(&this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;").await?;
+        let _: () = jvm
+            .invoke_virtual(&shell, "org/kwis/msp/lwc/Component", "showNotify", "(Z)V", (show,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&shell, "org/kwis/msp/lwc/Component", "focusNotify", "(Z)V", (show,))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use alloc::{format, vec, vec::Vec};
+
+use bytemuck::cast_vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::io::{InputStream, OutputStream};
+use jvm::{Array, ClassInstanceRef, JavaError, Jvm, Result as JvmResult};
+
+use wie_backend::{ConnectionId, Connections, NetworkError};
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::org::kwis::msf::io::Message;
+
+// how often pending network operations are polled, in milliseconds
+const POLL_INTERVAL: u64 = 10;
+const MAX_MESSAGE_LENGTH: i32 = 1500;
+
+// Socket returned from org.kwis.msf.io.URL.find, backed by a host connection.
+pub struct WIPISocket;
+
+impl WIPISocket {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/WIPISocket",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec!["org/kwis/msf/io/Socket"],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(IZ)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("accept", "()Lorg/kwis/msf/io/Socket;", Self::accept, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getInputStream",
+                    "()Ljava/io/InputStream;",
+                    Self::get_input_stream,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getMessageCount", "()I", Self::get_message_count, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMessageMaxLength", "()I", Self::get_message_max_length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getOutputStream",
+                    "()Ljava/io/OutputStream;",
+                    Self::get_output_stream,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("isStream", "()Z", Self::is_stream, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("recv", "(Lorg/kwis/msf/io/Message;)V", Self::recv, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("send", "(Lorg/kwis/msf/io/Message;)V", Self::send, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("id", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("stream", "Z", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, id: i32, stream: bool) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocket::<init>({this:?}, {id}, {stream})");
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+        jvm.put_field(&mut this, "id", "I", id).await?;
+        jvm.put_field(&mut this, "stream", "Z", stream).await?;
+
+        Ok(())
+    }
+
+    async fn accept(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Self>> {
+        tracing::warn!("net.wie.WIPISocket::accept({this:?}): listening sockets are not supported");
+
+        Err(jvm.exception("java/io/IOException", "accept is not supported").await)
+    }
+
+    async fn close(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocket::close({this:?})");
+
+        let id: i32 = jvm.get_field(&this, "id", "I").await?;
+        context.system().connections().close(id as _);
+
+        Ok(())
+    }
+
+    async fn get_input_stream(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<InputStream>> {
+        tracing::debug!("net.wie.WIPISocket::getInputStream({this:?})");
+
+        let id = Self::stream_id(jvm, &this).await?;
+
+        Ok(jvm.new_class("net/wie/WIPISocketInputStream", "(I)V", (id,)).await?.into())
+    }
+
+    async fn get_output_stream(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<OutputStream>> {
+        tracing::debug!("net.wie.WIPISocket::getOutputStream({this:?})");
+
+        let id = Self::stream_id(jvm, &this).await?;
+
+        Ok(jvm.new_class("net/wie/WIPISocketOutputStream", "(I)V", (id,)).await?.into())
+    }
+
+    async fn get_message_count(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("net.wie.WIPISocket::getMessageCount({this:?})");
+
+        let stream: bool = jvm.get_field(&this, "stream", "Z").await?;
+        if stream {
+            return Ok(0);
+        }
+
+        let id: i32 = jvm.get_field(&this, "id", "I").await?;
+        let count = context
+            .system()
+            .connections()
+            .datagram(id as _)
+            .ok_or(NetworkError::Invalid)
+            .and_then(|x| x.pending());
+
+        match count {
+            Ok(count) => Ok(count as _),
+            Err(err) => Err(io_exception(jvm, err).await),
+        }
+    }
+
+    async fn get_message_max_length(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("net.wie.WIPISocket::getMessageMaxLength({this:?})");
+
+        let stream: bool = jvm.get_field(&this, "stream", "Z").await?;
+
+        Ok(if stream { 0 } else { MAX_MESSAGE_LENGTH })
+    }
+
+    async fn is_stream(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("net.wie.WIPISocket::isStream({this:?})");
+
+        jvm.get_field(&this, "stream", "Z").await
+    }
+
+    async fn recv(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, mut message: ClassInstanceRef<Message>) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocket::recv({this:?}, {message:?})");
+
+        if message.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "message is null").await);
+        }
+        let id = Self::datagram_id(jvm, &this).await?;
+
+        let data = match wait(context, |connections| {
+            connections.datagram(id as _).ok_or(NetworkError::Invalid)?.receive()
+        })
+        .await
+        {
+            Ok(data) => data,
+            Err(err) => return Err(io_exception(jvm, err).await),
+        };
+
+        // fill the message's buffer, replacing it only when the datagram does not fit
+        let mut buffer: ClassInstanceRef<Array<i8>> = jvm.get_field(&message, "data", "[B").await?;
+        if buffer.is_null() || jvm.array_length(&buffer).await? < data.len() {
+            buffer = jvm.instantiate_array("B", data.len()).await?.into();
+            jvm.put_field(&mut message, "data", "[B", buffer.clone()).await?;
+        }
+        jvm.put_field(&mut message, "length", "I", data.len() as i32).await?;
+        jvm.store_array(&mut buffer, 0, cast_vec::<u8, i8>(data)).await?;
+
+        Ok(())
+    }
+
+    async fn send(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, message: ClassInstanceRef<Message>) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocket::send({this:?}, {message:?})");
+
+        if message.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "message is null").await);
+        }
+        let id = Self::datagram_id(jvm, &this).await?;
+
+        let buffer: ClassInstanceRef<Array<i8>> = jvm.get_field(&message, "data", "[B").await?;
+        let length: i32 = jvm.get_field(&message, "length", "I").await?;
+        let data: Vec<i8> = if buffer.is_null() {
+            Vec::new()
+        } else {
+            jvm.load_array(&buffer, 0, length as _).await?
+        };
+
+        let result = context
+            .system()
+            .connections()
+            .datagram(id as _)
+            .ok_or(NetworkError::Invalid)
+            .and_then(|x| x.send(&cast_vec::<i8, u8>(data)));
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(err) => Err(io_exception(jvm, err).await),
+        }
+    }
+
+    async fn stream_id(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<i32> {
+        let stream: bool = jvm.get_field(this, "stream", "Z").await?;
+        if !stream {
+            return Err(jvm.exception("java/io/IOException", "Not a stream socket").await);
+        }
+
+        jvm.get_field(this, "id", "I").await
+    }
+
+    async fn datagram_id(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<i32> {
+        let stream: bool = jvm.get_field(this, "stream", "Z").await?;
+        if stream {
+            return Err(jvm.exception("java/io/IOException", "Not a datagram socket").await);
+        }
+
+        jvm.get_field(this, "id", "I").await
+    }
+}
+
+/// Retries `f` until it stops returning `WouldBlock`, letting other tasks run in between.
+pub(crate) async fn wait<T, F>(context: &mut WieJvmContext, mut f: F) -> Result<T, NetworkError>
+where
+    F: FnMut(&mut Connections) -> Result<T, NetworkError>,
+{
+    loop {
+        let result = f(&mut context.system().connections());
+        match result {
+            Err(NetworkError::WouldBlock) => context.system().sleep(POLL_INTERVAL).await,
+            result => return result,
+        }
+    }
+}
+
+/// Reads from the stream socket or http request `id`; `Ok(0)` at the end of the stream.
+pub(crate) async fn read(context: &mut WieJvmContext, id: ConnectionId, buf: &mut [u8]) -> Result<usize, NetworkError> {
+    wait(context, |connections| {
+        // http responses are read once complete
+        if connections.http(id).is_some() {
+            connections.poll_http(id)?;
+        }
+        if let Some(http) = connections.http(id) {
+            return Ok(http.read(buf));
+        }
+
+        let socket = connections.socket(id).ok_or(NetworkError::Invalid)?;
+        socket.poll_readable()?;
+        socket.read(buf)
+    })
+    .await
+}
+
+/// Writes all of `data` to the stream socket or the body of the http request `id`.
+pub(crate) async fn write(context: &mut WieJvmContext, id: ConnectionId, data: &[u8]) -> Result<(), NetworkError> {
+    let mut offset = 0;
+    while offset < data.len() {
+        let remaining = &data[offset..];
+        offset += wait(context, |connections| {
+            if let Some(http) = connections.http(id) {
+                // the body can't be extended once the request is sent
+                return if http.write(remaining) {
+                    Ok(remaining.len())
+                } else {
+                    Err(NetworkError::Invalid)
+                };
+            }
+
+            connections.socket(id).ok_or(NetworkError::Invalid)?.write(remaining)
+        })
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn io_exception(jvm: &Jvm, err: NetworkError) -> JavaError {
+    jvm.exception("java/io/IOException", &format!("Network error: {err:?}")).await
+}
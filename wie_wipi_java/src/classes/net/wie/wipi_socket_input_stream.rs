@@ -0,0 +1,94 @@
+use alloc::vec;
+
+use bytemuck::cast_vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use super::wipi_socket::{io_exception, read};
+
+// InputStream of a net.wie.WIPISocket.
+pub struct WIPISocketInputStream;
+
+impl WIPISocketInputStream {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/WIPISocketInputStream",
+            parent_class: Some("java/io/InputStream"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(I)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "()I", Self::read_byte, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "([BII)I", Self::read_array, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![JavaFieldProto::new("id", "I", FieldAccessFlags::PRIVATE)],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, id: i32) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocketInputStream::<init>({this:?}, {id})");
+
+        let _: () = jvm.invoke_special(&this, "java/io/InputStream", "<init>", "()V", ()).await?;
+        jvm.put_field(&mut this, "id", "I", id).await?;
+
+        Ok(())
+    }
+
+    async fn read_byte(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("net.wie.WIPISocketInputStream::read({this:?})");
+
+        let id: i32 = jvm.get_field(&this, "id", "I").await?;
+        let mut buf = [0; 1];
+        match read(context, id as _, &mut buf).await {
+            Ok(0) => Ok(-1),
+            Ok(_) => Ok(buf[0] as i32),
+            Err(err) => Err(io_exception(jvm, err).await),
+        }
+    }
+
+    async fn read_array(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        mut buf: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+        length: i32,
+    ) -> JvmResult<i32> {
+        tracing::debug!("net.wie.WIPISocketInputStream::read({this:?}, {buf:?}, {offset}, {length})");
+
+        if buf.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "buffer is null").await);
+        }
+        let array_length = jvm.array_length(&buf).await? as i32;
+        if offset < 0 || length < 0 || offset > array_length - length {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "Invalid offset or length").await);
+        }
+        if length == 0 {
+            return Ok(0);
+        }
+
+        let id: i32 = jvm.get_field(&this, "id", "I").await?;
+        let mut data = vec![0; length as usize];
+        let read = match read(context, id as _, &mut data).await {
+            Ok(0) => return Ok(-1),
+            Ok(read) => read,
+            Err(err) => return Err(io_exception(jvm, err).await),
+        };
+        data.truncate(read);
+        jvm.store_array(&mut buf, offset as _, cast_vec::<u8, i8>(data)).await?;
+
+        Ok(read as _)
+    }
+
+    async fn close(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocketInputStream::close({this:?})");
+
+        // the connection stays open until the socket is closed
+        Ok(())
+    }
+}
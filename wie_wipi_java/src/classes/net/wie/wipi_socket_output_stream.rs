@@ -0,0 +1,86 @@
+use alloc::{vec, vec::Vec};
+
+use bytemuck::cast_vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use super::wipi_socket::{io_exception, write};
+
+// OutputStream of a net.wie.WIPISocket.
+pub struct WIPISocketOutputStream;
+
+impl WIPISocketOutputStream {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/WIPISocketOutputStream",
+            parent_class: Some("java/io/OutputStream"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(I)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "(I)V", Self::write_byte, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "([BII)V", Self::write_array, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![JavaFieldProto::new("id", "I", FieldAccessFlags::PRIVATE)],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, id: i32) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocketOutputStream::<init>({this:?}, {id})");
+
+        let _: () = jvm.invoke_special(&this, "java/io/OutputStream", "<init>", "()V", ()).await?;
+        jvm.put_field(&mut this, "id", "I", id).await?;
+
+        Ok(())
+    }
+
+    async fn write_byte(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, byte: i32) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocketOutputStream::write({this:?}, {byte})");
+
+        let id: i32 = jvm.get_field(&this, "id", "I").await?;
+        if let Err(err) = write(context, id as _, &[byte as u8]).await {
+            return Err(io_exception(jvm, err).await);
+        }
+
+        Ok(())
+    }
+
+    async fn write_array(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        buf: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+        length: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocketOutputStream::write({this:?}, {buf:?}, {offset}, {length})");
+
+        if buf.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "buffer is null").await);
+        }
+        let array_length = jvm.array_length(&buf).await? as i32;
+        if offset < 0 || length < 0 || offset > array_length - length {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "Invalid offset or length").await);
+        }
+
+        let id: i32 = jvm.get_field(&this, "id", "I").await?;
+        let data: Vec<i8> = jvm.load_array(&buf, offset as _, length as _).await?;
+        if let Err(err) = write(context, id as _, &cast_vec::<i8, u8>(data)).await {
+            return Err(io_exception(jvm, err).await);
+        }
+
+        Ok(())
+    }
+
+    async fn close(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.WIPISocketOutputStream::close({this:?})");
+
+        // the connection stays open until the socket is closed
+        Ok(())
+    }
+}
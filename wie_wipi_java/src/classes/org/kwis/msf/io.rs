@@ -1,6 +1,7 @@
+mod message;
 mod network;
 mod scheme_not_found_exception;
 mod socket;
 mod url;
 
-pub use {network::Network, scheme_not_found_exception::SchemeNotFoundException, socket::Socket, url::URL};
+pub use {message::Message, network::Network, scheme_not_found_exception::SchemeNotFoundException, socket::Socket, url::URL};
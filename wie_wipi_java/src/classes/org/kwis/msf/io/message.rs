@@ -0,0 +1,101 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+// class org.kwis.msf.io.Message
+pub struct Message;
+
+impl Message {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "org/kwis/msf/io/Message",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(I)V", Self::init_with_size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "([B)V", Self::init_with_data, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getData", "()[B", Self::get_data, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setData", "([B)V", Self::set_data, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getLength", "()I", Self::get_length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setLength", "(I)V", Self::set_length, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("data", "[B", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("length", "I", FieldAccessFlags::PRIVATE),
+            ],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msf.io.Message::<init>({this:?})");
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msf/io/Message", "<init>", "(I)V", (0,)).await?;
+
+        Ok(())
+    }
+
+    async fn init_with_size(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, size: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msf.io.Message::<init>({this:?}, {size})");
+
+        if size < 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "size is negative").await);
+        }
+        let data = jvm.instantiate_array("B", size as _).await?;
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msf/io/Message", "<init>", "([B)V", (data,)).await?;
+
+        Ok(())
+    }
+
+    async fn init_with_data(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, data: ClassInstanceRef<Array<i8>>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msf.io.Message::<init>({this:?}, {data:?})");
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+        let _: () = jvm.invoke_virtual(&this, "org/kwis/msf/io/Message", "setData", "([B)V", (data,)).await?;
+
+        Ok(())
+    }
+
+    async fn get_data(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Array<i8>>> {
+        tracing::debug!("org.kwis.msf.io.Message::getData({this:?})");
+
+        jvm.get_field(&this, "data", "[B").await
+    }
+
+    async fn set_data(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, data: ClassInstanceRef<Array<i8>>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msf.io.Message::setData({this:?}, {data:?})");
+
+        if data.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
+        }
+        let length = jvm.array_length(&data).await? as i32;
+        jvm.put_field(&mut this, "data", "[B", data).await?;
+        jvm.put_field(&mut this, "length", "I", length).await?;
+
+        Ok(())
+    }
+
+    async fn get_length(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msf.io.Message::getLength({this:?})");
+
+        jvm.get_field(&this, "length", "I").await
+    }
+
+    async fn set_length(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, length: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msf.io.Message::setLength({this:?}, {length})");
+
+        let data: ClassInstanceRef<Array<i8>> = jvm.get_field(&this, "data", "[B").await?;
+        if length < 0 || length as usize > jvm.array_length(&data).await? {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "length is out of range").await);
+        }
+        jvm.put_field(&mut this, "length", "I", length).await?;
+
+        Ok(())
+    }
+}
@@ -34,14 +34,15 @@ impl Network {
         }
     }
 
-    async fn connect(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msf.io.Network::connect()");
+    async fn connect(_: &Jvm, context: &mut WieJvmContext) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msf.io.Network::connect()");
 
-        Ok(-1)
+        // there is no data session to bring up; report whether the host is reachable at all
+        Ok(if context.system().connections().is_online() { 0 } else { -1 })
     }
 
     async fn disconnect(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msf.io.Network::disconnect()");
+        tracing::debug!("org.kwis.msf.io.Network::disconnect()");
 
         Ok(())
     }
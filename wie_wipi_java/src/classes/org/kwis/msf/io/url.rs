@@ -1,12 +1,17 @@
-use alloc::vec;
+use alloc::{format, vec};
 
 use java_class_proto::JavaMethodProto;
 use java_constants::{ClassAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::String;
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
 
+use wie_backend::{ConnectionId, NetworkError};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::net::wie::wipi_socket::{io_exception, wait};
+
+use super::Socket;
+
 // class org.kwis.msf.io.URL
 pub struct URL;
 
@@ -36,33 +41,136 @@ impl URL {
         Ok(())
     }
 
-    async fn find(jvm: &Jvm, _: &mut WieJvmContext, _: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<Self>> {
-        Err(jvm.exception("org/kwis/msf/io/SchemeNotFoundException", "Network is not supported").await)
+    async fn find(jvm: &Jvm, context: &mut WieJvmContext, url: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<Socket>> {
+        if url.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "url is null").await);
+        }
+        let url = JavaLangString::to_rust_string(jvm, &url).await?;
+        tracing::debug!("org.kwis.msf.io.URL::find({url})");
+
+        let (scheme, rest) = url.split_once("://").unwrap_or(("", &url));
+        let scheme = scheme.to_ascii_lowercase();
+        let result = match scheme.as_str() {
+            "http" => {
+                let Some(id) = context.system().connections().open_http(&url) else {
+                    return Err(jvm.exception("java/lang/IllegalArgumentException", &format!("Invalid url {url}")).await);
+                };
+
+                Ok((id, true))
+            }
+            "tcp" | "udp" => {
+                let Some((host, port)) = parse_address(rest) else {
+                    return Err(jvm.exception("java/lang/IllegalArgumentException", &format!("Invalid url {url}")).await);
+                };
+
+                Self::open(context, host, port, scheme == "tcp").await
+            }
+            _ => {
+                return Err(jvm
+                    .exception("org/kwis/msf/io/SchemeNotFoundException", &format!("Unsupported scheme {scheme}"))
+                    .await);
+            }
+        };
+
+        match result {
+            Ok((id, stream)) => Ok(jvm.new_class("net/wie/WIPISocket", "(IZ)V", (id as i32, stream)).await?.into()),
+            Err(err) => Err(io_exception(jvm, err).await),
+        }
+    }
+
+    // connects a tcp or udp socket, returning its connection and whether it is a stream
+    async fn open(context: &mut WieJvmContext, host: &str, port: u16, stream: bool) -> Result<(ConnectionId, bool), NetworkError> {
+        let address = wait(context, |connections| connections.resolve(host)).await?;
+        if !stream {
+            return Ok((context.system().connections().open_datagram(address, port)?, false));
+        }
+
+        let id = context.system().connections().create_socket();
+        let result = context.system().connections().connect(id, address, port);
+        let result = match result {
+            Ok(()) => wait(context, |connections| connections.socket(id).ok_or(NetworkError::Invalid)?.poll_connect()).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            context.system().connections().close(id);
+
+            return Err(err);
+        }
+
+        Ok((id, true))
+    }
+}
+
+// host and port of an authority like `example.com:1234/path`
+fn parse_address(authority: &str) -> Option<(&str, u16)> {
+    let authority = authority.split('/').next()?;
+    let (host, port) = authority.rsplit_once(':')?;
+    if host.is_empty() {
+        return None;
     }
+
+    Some((host, port.parse().ok()?))
 }
 
 #[cfg(test)]
 mod tests {
-    use alloc::boxed::Box;
+    use alloc::{boxed::Box, vec::Vec};
 
-    use java_runtime::classes::java::lang::String;
-    use jvm::{ClassInstanceRef, JavaError, Result as JvmResult, runtime::JavaLangString};
+    use java_runtime::classes::java::{
+        io::{InputStream, OutputStream},
+        lang::String,
+    };
+    use jvm::{Array, ClassInstanceRef, JavaError, Jvm, Result as JvmResult, runtime::JavaLangString};
 
-    use test_utils::run_jvm_test;
+    use test_utils::{LoopbackNetwork, TestPlatform, run_jvm_test, run_jvm_test_with_platform};
     use wie_util::Result;
 
-    use crate::get_protos;
+    use crate::{classes::org::kwis::msf::io::Message, get_protos};
+
+    use super::{Socket, URL, parse_address};
+
+    async fn find(jvm: &Jvm, url: &str) -> JvmResult<ClassInstanceRef<Socket>> {
+        let url: ClassInstanceRef<String> = JavaLangString::from_rust_string(jvm, url).await?.into();
+
+        jvm.invoke_static("org/kwis/msf/io/URL", "find", "(Ljava/lang/String;)Lorg/kwis/msf/io/Socket;", (url,))
+            .await
+    }
+
+    async fn byte_array(jvm: &Jvm, data: &[u8]) -> JvmResult<ClassInstanceRef<Array<i8>>> {
+        let mut array = jvm.instantiate_array("B", data.len()).await?;
+        jvm.store_array(&mut array, 0, data.iter().map(|&x| x as i8)).await?;
+
+        Ok(array.into())
+    }
+
+    async fn write_stream(jvm: &Jvm, socket: &ClassInstanceRef<Socket>, data: &[u8]) -> JvmResult<()> {
+        let stream: ClassInstanceRef<OutputStream> = jvm
+            .invoke_virtual(socket, "org/kwis/msf/io/Socket", "getOutputStream", "()Ljava/io/OutputStream;", ())
+            .await?;
+        let array = byte_array(jvm, data).await?;
 
-    use super::URL;
+        jvm.invoke_virtual(&stream, "java/io/OutputStream", "write", "([BII)V", (array, 0, data.len() as i32))
+            .await
+    }
+
+    async fn read_stream(jvm: &Jvm, socket: &ClassInstanceRef<Socket>, length: usize) -> JvmResult<Vec<u8>> {
+        let stream: ClassInstanceRef<InputStream> = jvm
+            .invoke_virtual(socket, "org/kwis/msf/io/Socket", "getInputStream", "()Ljava/io/InputStream;", ())
+            .await?;
+        let array: ClassInstanceRef<Array<i8>> = jvm.instantiate_array("B", length).await?.into();
+        let read: i32 = jvm
+            .invoke_virtual(&stream, "java/io/InputStream", "read", "([BII)I", (array.clone(), 0, length as i32))
+            .await?;
+
+        let data: Vec<i8> = jvm.load_array(&array, 0, read as _).await?;
+        Ok(data.into_iter().map(|x| x as u8).collect())
+    }
 
     #[test]
     fn find_throws_scheme_not_found_exception() -> Result<()> {
         run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
             let _: ClassInstanceRef<URL> = jvm.new_class("org/kwis/msf/io/URL", "()V", ()).await?.into();
-            let url: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "http://example.com").await?.into();
-            let result: JvmResult<ClassInstanceRef<URL>> = jvm
-                .invoke_static("org/kwis/msf/io/URL", "find", "(Ljava/lang/String;)Lorg/kwis/msf/io/Socket;", (url,))
-                .await;
+            let result = find(&jvm, "ftp://example.com").await;
 
             let Err(JavaError::JavaException(exception)) = result else {
                 panic!("URL.find returned without throwing SchemeNotFoundException");
@@ -73,4 +181,103 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn find_throws_io_exception_when_offline() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let result = find(&jvm, "tcp://localhost:7").await;
+
+            let Err(JavaError::JavaException(exception)) = result else {
+                panic!("URL.find connected without a network");
+            };
+            assert!(jvm.is_instance(&*exception, "java/io/IOException"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn tcp_socket_round_trip() -> Result<()> {
+        let platform = TestPlatform::new().with_network(LoopbackNetwork::new().with_echo(7));
+
+        run_jvm_test_with_platform(platform, Box::new([get_protos().into()]), |jvm| async move {
+            let socket = find(&jvm, "tcp://localhost:7").await?;
+            let stream: bool = jvm.invoke_virtual(&socket, "org/kwis/msf/io/Socket", "isStream", "()Z", ()).await?;
+            assert!(stream);
+
+            write_stream(&jvm, &socket, b"ping").await?;
+            assert_eq!(read_stream(&jvm, &socket, 16).await?, b"ping");
+
+            let _: () = jvm.invoke_virtual(&socket, "org/kwis/msf/io/Socket", "close", "()V", ()).await?;
+            assert!(matches!(read_stream(&jvm, &socket, 16).await, Err(JavaError::JavaException(_))));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn udp_socket_exchanges_messages() -> Result<()> {
+        let network = LoopbackNetwork::new().with_service(9, |data| data.iter().rev().copied().collect());
+        let platform = TestPlatform::new().with_network(network);
+
+        run_jvm_test_with_platform(platform, Box::new([get_protos().into()]), |jvm| async move {
+            let socket = find(&jvm, "udp://localhost:9").await?;
+            let stream: bool = jvm.invoke_virtual(&socket, "org/kwis/msf/io/Socket", "isStream", "()Z", ()).await?;
+            assert!(!stream);
+
+            let data = byte_array(&jvm, b"abc").await?;
+            let message: ClassInstanceRef<Message> = jvm.new_class("org/kwis/msf/io/Message", "([B)V", (data,)).await?.into();
+            let _: () = jvm
+                .invoke_virtual(&socket, "org/kwis/msf/io/Socket", "send", "(Lorg/kwis/msf/io/Message;)V", (message,))
+                .await?;
+            let count: i32 = jvm
+                .invoke_virtual(&socket, "org/kwis/msf/io/Socket", "getMessageCount", "()I", ())
+                .await?;
+            assert_eq!(count, 1);
+
+            let message: ClassInstanceRef<Message> = jvm.new_class("org/kwis/msf/io/Message", "(I)V", (16,)).await?.into();
+            let _: () = jvm
+                .invoke_virtual(
+                    &socket,
+                    "org/kwis/msf/io/Socket",
+                    "recv",
+                    "(Lorg/kwis/msf/io/Message;)V",
+                    (message.clone(),),
+                )
+                .await?;
+            let length: i32 = jvm.invoke_virtual(&message, "org/kwis/msf/io/Message", "getLength", "()I", ()).await?;
+            let data: ClassInstanceRef<Array<i8>> = jvm.invoke_virtual(&message, "org/kwis/msf/io/Message", "getData", "()[B", ()).await?;
+            assert_eq!(jvm.load_array::<i8>(&data, 0, length as _).await?, [b'c' as i8, b'b' as i8, b'a' as i8]);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn http_socket_sends_body_and_reads_response() -> Result<()> {
+        let network = LoopbackNetwork::new().with_service(80, |request| {
+            assert!(request.starts_with(b"GET /rank HTTP/1.0\r\n"));
+            assert!(request.ends_with(b"\r\n\r\nscore=10"));
+
+            b"HTTP/1.0 200 OK\r\nContent-Length: 2\r\n\r\nok".to_vec()
+        });
+        let platform = TestPlatform::new().with_network(network);
+
+        run_jvm_test_with_platform(platform, Box::new([get_protos().into()]), |jvm| async move {
+            let socket = find(&jvm, "http://localhost/rank").await?;
+
+            write_stream(&jvm, &socket, b"score=10").await?;
+            assert_eq!(read_stream(&jvm, &socket, 16).await?, b"ok");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn parses_addresses() {
+        assert_eq!(parse_address("example.com:1234"), Some(("example.com", 1234)));
+        assert_eq!(parse_address("10.0.0.1:80/path"), Some(("10.0.0.1", 80)));
+        assert_eq!(parse_address("example.com"), None);
+        assert_eq!(parse_address(":80"), None);
+    }
 }
@@ -35,14 +35,15 @@ impl HandsetProperty {
         }
     }
 
-    async fn get_system_property(jvm: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<String>> {
+    async fn get_system_property(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<String>> {
         let name = JavaLangString::to_rust_string(jvm, &name).await?;
-        tracing::warn!("stub org.kwis.msp.handset.HandsetProperty::getSystemProperty({name})");
+        tracing::debug!("org.kwis.msp.handset.HandsetProperty::getSystemProperty({name})");
 
-        let value = match name.as_ref() {
+        let profile = context.system().device_profile();
+        let value = profile.property(&name).unwrap_or(match name.as_ref() {
             "VIBRATORLEVEL" => "0",
             _ => "",
-        };
+        });
 
         let result = JavaLangString::from_rust_string(jvm, value).await?;
         Ok(result.into())
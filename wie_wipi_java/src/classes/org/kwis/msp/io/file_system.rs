@@ -1,9 +1,9 @@
-use alloc::vec;
+use alloc::{format, string::String as RustString, vec};
 
 use java_class_proto::JavaMethodProto;
 use java_constants::{ClassAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::{io::File as JavaFile, lang::String, util::Vector};
-use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
@@ -185,16 +185,23 @@ impl FileSystem {
         Ok(exists)
     }
 
-    async fn mkdir(_jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>, flag: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::mkdir({name:?}, {flag:?})");
+    async fn mkdir(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>, flag: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::mkdir({name:?}, {flag:?})");
+
+        let path = Self::path(jvm, &name).await?;
+        if !context.system().filesystem().create_dir(&path).await {
+            return Err(jvm.exception("java/io/IOException", &format!("Cannot create directory {path}")).await);
+        }
 
         Ok(())
     }
 
-    async fn available(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::available()");
+    async fn available(_: &Jvm, context: &mut WieJvmContext) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::available()");
+
+        let available = context.system().storage().available().await;
 
-        Ok(0x1000000) // TODO temp
+        Ok(available.min(i32::MAX as u64) as _)
     }
 
     async fn get_max_filename_length(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<i32> {
@@ -203,44 +210,83 @@ impl FileSystem {
         Ok(0)
     }
 
-    async fn list(_: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<Vector>> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::list({dirname:?})");
+    async fn list(jvm: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<Vector>> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::list({dirname:?})");
 
-        Ok(ClassInstanceRef::new(None))
+        jvm.invoke_static(
+            "org/kwis/msp/io/FileSystem",
+            "list",
+            "(Ljava/lang/String;I)Ljava/util/Vector;",
+            (dirname, 0),
+        )
+        .await
     }
 
-    async fn list_with_flag(_: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>, flag: i32) -> JvmResult<ClassInstanceRef<Vector>> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::list({dirname:?}, {flag})");
+    async fn list_with_flag(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        dirname: ClassInstanceRef<String>,
+        flag: i32,
+    ) -> JvmResult<ClassInstanceRef<Vector>> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::list({dirname:?}, {flag})");
+
+        let path = Self::path(jvm, &dirname).await?;
+        let Some(entries) = context.system().filesystem().list(&path).await else {
+            return Ok(ClassInstanceRef::new(None));
+        };
+
+        let vector: ClassInstanceRef<Vector> = jvm.new_class("java/util/Vector", "()V", ()).await?.into();
+        for entry in entries {
+            // directories are reported with a trailing separator
+            let name = if entry.is_dir { format!("{}/", entry.name) } else { entry.name };
+            let name = JavaLangString::from_rust_string(jvm, &name).await?;
+            let _: () = jvm
+                .invoke_virtual(&vector, "java/util/Vector", "addElement", "(Ljava/lang/Object;)V", (name,))
+                .await?;
+        }
 
-        Ok(ClassInstanceRef::new(None))
+        Ok(vector)
     }
 
-    async fn remove(_: &Jvm, _: &mut WieJvmContext, filename: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::remove({filename:?})");
+    async fn remove(jvm: &Jvm, _: &mut WieJvmContext, filename: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::remove({filename:?})");
 
-        Ok(())
+        jvm.invoke_static("org/kwis/msp/io/FileSystem", "remove", "(Ljava/lang/String;I)V", (filename, 0))
+            .await
     }
 
-    async fn remove_with_flag(_: &Jvm, _: &mut WieJvmContext, filename: ClassInstanceRef<String>, flag: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::remove({filename:?}, {flag})");
+    async fn remove_with_flag(jvm: &Jvm, context: &mut WieJvmContext, filename: ClassInstanceRef<String>, flag: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::remove({filename:?}, {flag})");
+
+        let path = Self::path(jvm, &filename).await?;
+        if !context.system().filesystem().remove(&path).await {
+            return Err(jvm.exception("java/io/IOException", &format!("Cannot remove {path}")).await);
+        }
 
         Ok(())
     }
 
-    async fn mkdir_without_flag(_: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::mkdir({dirname:?})");
+    async fn mkdir_without_flag(jvm: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::mkdir({dirname:?})");
 
-        Ok(())
+        jvm.invoke_static("org/kwis/msp/io/FileSystem", "mkdir", "(Ljava/lang/String;I)V", (dirname, 0))
+            .await
     }
 
-    async fn rmdir(_: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::rmdir({dirname:?})");
+    async fn rmdir(jvm: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::rmdir({dirname:?})");
 
-        Ok(())
+        jvm.invoke_static("org/kwis/msp/io/FileSystem", "rmdir", "(Ljava/lang/String;I)V", (dirname, 0))
+            .await
     }
 
-    async fn rmdir_with_flag(_: &Jvm, _: &mut WieJvmContext, dirname: ClassInstanceRef<String>, flag: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::rmdir({dirname:?}, {flag})");
+    async fn rmdir_with_flag(jvm: &Jvm, context: &mut WieJvmContext, dirname: ClassInstanceRef<String>, flag: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::rmdir({dirname:?}, {flag})");
+
+        let path = Self::path(jvm, &dirname).await?;
+        if !context.system().filesystem().remove_dir(&path).await {
+            return Err(jvm.exception("java/io/IOException", &format!("Cannot remove directory {path}")).await);
+        }
 
         Ok(())
     }
@@ -251,10 +297,11 @@ impl FileSystem {
         Ok(ClassInstanceRef::new(None))
     }
 
-    async fn is_file_with_flag(_: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>, flag: i32) -> JvmResult<bool> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::isFile({name:?}, {flag})");
+    async fn is_file_with_flag(jvm: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>, flag: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::isFile({name:?}, {flag})");
 
-        Ok(false)
+        jvm.invoke_static("org/kwis/msp/io/FileSystem", "isFile", "(Ljava/lang/String;)Z", (name,))
+            .await
     }
 
     async fn is_directory_without_flag(jvm: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<bool> {
@@ -264,52 +311,107 @@ impl FileSystem {
         jvm.invoke_virtual(&file, "java/io/File", "isDirectory", "()Z", ()).await
     }
 
-    async fn get_creation_time(_: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::getCreationTime({name:?})");
+    async fn get_creation_time(jvm: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::getCreationTime({name:?})");
 
-        Ok(0)
+        jvm.invoke_static("org/kwis/msp/io/FileSystem", "getCreationTime", "(Ljava/lang/String;I)I", (name, 0))
+            .await
     }
 
-    async fn get_creation_time_with_flag(_: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>, flag: i32) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::getCreationTime({name:?}, {flag})");
+    async fn get_creation_time_with_flag(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>, flag: i32) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::getCreationTime({name:?}, {flag})");
 
-        Ok(0)
+        let path = Self::path(jvm, &name).await?;
+        let Some(metadata) = context.system().filesystem().metadata(&path).await else {
+            return Err(jvm.exception("java/io/IOException", &format!("{path} not found")).await);
+        };
+
+        // seconds since epoch; 0 when the backend has no timestamps
+        Ok(metadata.created.map(|x| (x.raw() / 1000) as i32).unwrap_or(0))
     }
 
-    async fn rename(_: &Jvm, _: &mut WieJvmContext, old_name: ClassInstanceRef<String>, new_name: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::rename({old_name:?}, {new_name:?})");
+    async fn rename(jvm: &Jvm, _: &mut WieJvmContext, old_name: ClassInstanceRef<String>, new_name: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.io.FileSystem::rename({old_name:?}, {new_name:?})");
 
-        Ok(())
+        jvm.invoke_static(
+            "org/kwis/msp/io/FileSystem",
+            "rename",
+            "(Ljava/lang/String;Ljava/lang/String;I)V",
+            (old_name, new_name, 0),
+        )
+        .await
     }
 
     async fn rename_with_flag(
-        _: &Jvm,
-        _: &mut WieJvmContext,
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
         old_name: ClassInstanceRef<String>,
         new_name: ClassInstanceRef<String>,
         flag: i32,
     ) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.io.FileSystem::rename({old_name:?}, {new_name:?}, {flag})");
+        tracing::debug!("org.kwis.msp.io.FileSystem::rename({old_name:?}, {new_name:?}, {flag})");
+
+        let from = Self::path(jvm, &old_name).await?;
+        let to = Self::path(jvm, &new_name).await?;
+        if !context.system().filesystem().rename(&from, &to).await {
+            return Err(jvm.exception("java/io/IOException", &format!("Cannot rename {from} to {to}")).await);
+        }
 
         Ok(())
     }
+
+    async fn path(jvm: &Jvm, name: &ClassInstanceRef<String>) -> JvmResult<RustString> {
+        if name.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "name is null").await);
+        }
+
+        JavaLangString::to_rust_string(jvm, name).await
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use alloc::boxed::Box;
+    use alloc::{boxed::Box, string::String as RustString, vec::Vec};
 
     use java_runtime::classes::java::{lang::String, util::Vector};
-    use jvm::{Array, ClassInstanceRef, runtime::JavaLangString};
+    use jvm::{Array, ClassInstanceRef, JavaError, Jvm, Result as JvmResult, runtime::JavaLangString};
     use test_utils::run_jvm_test;
     use wie_util::Result;
 
-    use crate::get_protos;
+    use crate::{classes::org::kwis::msp::io::File, get_protos};
 
     use super::FileSystem;
 
+    // org.kwis.msp.io.File.WRITE_TRUNC
+    const WRITE_TRUNC: i32 = 3;
+
+    fn assert_io_exception(jvm: &Jvm, result: JvmResult<()>) {
+        let Err(JavaError::JavaException(exception)) = result else {
+            panic!("expected IOException");
+        };
+        assert!(jvm.is_instance(&*exception, "java/io/IOException"));
+    }
+
+    async fn list(jvm: &Jvm, dirname: &str) -> JvmResult<Vec<RustString>> {
+        let dirname = JavaLangString::from_rust_string(jvm, dirname).await?;
+        let listed: ClassInstanceRef<Vector> = jvm
+            .invoke_static("org/kwis/msp/io/FileSystem", "list", "(Ljava/lang/String;)Ljava/util/Vector;", (dirname,))
+            .await?;
+        let size: i32 = jvm.invoke_virtual(&listed, "java/util/Vector", "size", "()I", ()).await?;
+
+        let mut result = Vec::new();
+        for i in 0..size {
+            let name: ClassInstanceRef<String> = jvm
+                .invoke_virtual(&listed, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (i,))
+                .await?;
+            result.push(JavaLangString::to_rust_string(jvm, &name).await?);
+        }
+
+        Ok(result)
+    }
+
     #[test]
-    fn test_filesystem_overloads_and_neutral_stubs() -> Result<()> {
+    fn test_filesystem_missing_paths() -> Result<()> {
         run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
             let _: ClassInstanceRef<FileSystem> = jvm.new_class("org/kwis/msp/io/FileSystem", "()V", ()).await?.into();
             let name: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "missing").await?.into();
@@ -318,14 +420,6 @@ mod test {
             let max_length: i32 = jvm.invoke_static("org/kwis/msp/io/FileSystem", "getMaxFilenameLength", "()I", ()).await?;
             assert_eq!(max_length, 0);
 
-            let listed: ClassInstanceRef<Vector> = jvm
-                .invoke_static(
-                    "org/kwis/msp/io/FileSystem",
-                    "list",
-                    "(Ljava/lang/String;)Ljava/util/Vector;",
-                    (name.clone(),),
-                )
-                .await?;
             let listed_with_flag: ClassInstanceRef<Vector> = jvm
                 .invoke_static(
                     "org/kwis/msp/io/FileSystem",
@@ -334,7 +428,6 @@ mod test {
                     (name.clone(), 1),
                 )
                 .await?;
-            assert!(listed.is_null());
             assert!(listed_with_flag.is_null());
 
             let c_string: ClassInstanceRef<Array<i8>> = jvm
@@ -348,53 +441,85 @@ mod test {
             let is_directory: bool = jvm
                 .invoke_static("org/kwis/msp/io/FileSystem", "isDirectory", "(Ljava/lang/String;)Z", (name.clone(),))
                 .await?;
-            let creation_time: i32 = jvm
-                .invoke_static("org/kwis/msp/io/FileSystem", "getCreationTime", "(Ljava/lang/String;)I", (name.clone(),))
-                .await?;
-            let creation_time_with_flag: i32 = jvm
-                .invoke_static(
-                    "org/kwis/msp/io/FileSystem",
-                    "getCreationTime",
-                    "(Ljava/lang/String;I)I",
-                    (name.clone(), 1),
-                )
-                .await?;
             assert!(!is_file);
             assert!(!is_directory);
-            assert_eq!(creation_time, 0);
-            assert_eq!(creation_time_with_flag, 0);
 
-            let _: () = jvm
-                .invoke_static("org/kwis/msp/io/FileSystem", "remove", "(Ljava/lang/String;)V", (name.clone(),))
-                .await?;
-            let _: () = jvm
+            let creation_time: JvmResult<i32> = jvm
+                .invoke_static("org/kwis/msp/io/FileSystem", "getCreationTime", "(Ljava/lang/String;)I", (name.clone(),))
+                .await;
+            assert_io_exception(&jvm, creation_time.map(|_| ()));
+            let removed = jvm
                 .invoke_static("org/kwis/msp/io/FileSystem", "remove", "(Ljava/lang/String;I)V", (name.clone(), 1))
-                .await?;
-            let _: () = jvm
-                .invoke_static("org/kwis/msp/io/FileSystem", "mkdir", "(Ljava/lang/String;)V", (name.clone(),))
-                .await?;
-            let _: () = jvm
+                .await;
+            assert_io_exception(&jvm, removed);
+            let removed_dir = jvm
                 .invoke_static("org/kwis/msp/io/FileSystem", "rmdir", "(Ljava/lang/String;)V", (name.clone(),))
-                .await?;
-            let _: () = jvm
-                .invoke_static("org/kwis/msp/io/FileSystem", "rmdir", "(Ljava/lang/String;I)V", (name.clone(), 1))
-                .await?;
-            let _: () = jvm
+                .await;
+            assert_io_exception(&jvm, removed_dir);
+            let renamed = jvm
                 .invoke_static(
                     "org/kwis/msp/io/FileSystem",
                     "rename",
                     "(Ljava/lang/String;Ljava/lang/String;)V",
-                    (name.clone(), new_name.clone()),
+                    (name, new_name),
                 )
+                .await;
+            assert_io_exception(&jvm, renamed);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_filesystem_directory_operations() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let dirname = JavaLangString::from_rust_string(&jvm, "save").await?;
+            let _: () = jvm
+                .invoke_static("org/kwis/msp/io/FileSystem", "mkdir", "(Ljava/lang/String;)V", (dirname,))
                 .await?;
+
+            let filename = JavaLangString::from_rust_string(&jvm, "save/slot.dat").await?;
+            let file: ClassInstanceRef<File> = jvm
+                .new_class("org/kwis/msp/io/File", "(Ljava/lang/String;I)V", (filename, WRITE_TRUNC))
+                .await?
+                .into();
+            let _: i32 = jvm.invoke_virtual(&file, "org/kwis/msp/io/File", "write", "(I)I", (1,)).await?;
+            let _: () = jvm.invoke_virtual(&file, "org/kwis/msp/io/File", "close", "()V", ()).await?;
+
+            assert_eq!(list(&jvm, "/").await?, ["save/"]);
+            assert_eq!(list(&jvm, "save").await?, ["slot.dat"]);
+
+            let from = JavaLangString::from_rust_string(&jvm, "save/slot.dat").await?;
+            let to = JavaLangString::from_rust_string(&jvm, "save/backup.dat").await?;
             let _: () = jvm
                 .invoke_static(
                     "org/kwis/msp/io/FileSystem",
                     "rename",
-                    "(Ljava/lang/String;Ljava/lang/String;I)V",
-                    (name, new_name, 1),
+                    "(Ljava/lang/String;Ljava/lang/String;)V",
+                    (from, to.clone()),
                 )
                 .await?;
+            assert_eq!(list(&jvm, "save").await?, ["backup.dat"]);
+
+            let is_file: bool = jvm
+                .invoke_static("org/kwis/msp/io/FileSystem", "isFile", "(Ljava/lang/String;I)Z", (to.clone(), 0))
+                .await?;
+            assert!(is_file);
+
+            let dirname = JavaLangString::from_rust_string(&jvm, "save").await?;
+            let not_empty = jvm
+                .invoke_static("org/kwis/msp/io/FileSystem", "rmdir", "(Ljava/lang/String;)V", (dirname,))
+                .await;
+            assert_io_exception(&jvm, not_empty);
+
+            let _: () = jvm
+                .invoke_static("org/kwis/msp/io/FileSystem", "remove", "(Ljava/lang/String;)V", (to,))
+                .await?;
+            let dirname = JavaLangString::from_rust_string(&jvm, "save").await?;
+            let _: () = jvm
+                .invoke_static("org/kwis/msp/io/FileSystem", "rmdir", "(Ljava/lang/String;)V", (dirname,))
+                .await?;
+            assert!(list(&jvm, "/").await?.is_empty());
 
             Ok(())
         })
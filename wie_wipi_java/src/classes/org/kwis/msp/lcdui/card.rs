@@ -856,6 +856,13 @@ mod test {
                 assert_eq!(jvm.get_field::<i32>(&first, "notifyCount", "I").await?, 0);
                 assert_eq!(jvm.get_field::<i32>(&second, "keyCount", "I").await?, 1);
                 assert_eq!(jvm.get_field::<i32>(&second, "notifyCount", "I").await?, 1);
+
+                // host keyboard text is for the built-in text widgets, guest cards don't see it
+                let _: () = jvm
+                    .invoke_virtual(&canvas, "net/wie/CardCanvas", "keyTyped", "(I)V", ('a' as i32,))
+                    .await?;
+                assert_eq!(jvm.get_field::<i32>(&second, "keyCount", "I").await?, 1);
+
                 let popped: ClassInstanceRef<TestCard> = jvm
                     .invoke_virtual(&canvas, "net/wie/CardCanvas", "popCard", "()Lorg/kwis/msp/lcdui/Card;", ())
                     .await?;
@@ -359,16 +359,16 @@ impl Display {
         Ok(())
     }
 
-    async fn is_color(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Display::isColor({this:?})");
+    async fn is_color(_: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lcdui.Display::isColor({this:?})");
 
-        Ok(false)
+        Ok(context.system().device_profile().color_depth > 1)
     }
 
-    async fn num_colors(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Display::numColors({this:?})");
+    async fn num_colors(_: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lcdui.Display::numColors({this:?})");
 
-        Ok(0)
+        Ok(context.system().device_profile().num_colors())
     }
 
     async fn has_pointer_events(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
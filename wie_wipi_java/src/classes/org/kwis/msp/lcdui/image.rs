@@ -1,15 +1,24 @@
-use alloc::vec;
+use alloc::{boxed::Box, format, vec, vec::Vec};
 
-use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use bytemuck::cast_vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto, MethodBody};
 use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
-use java_runtime::classes::java::lang::String;
-use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+use java_runtime::classes::java::{lang::String, util::Vector};
+use jvm::{
+    Array, ClassInstanceRef, JavaError, JavaValue, Jvm, Result as JvmResult,
+    runtime::{JavaIoInputStream, JavaLangClassLoader, JavaLangString},
+};
 
+use wie_backend::canvas::{ArgbPixel, Image as BackendImage, PixelType, VecImageBuffer, decode_image_frames};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 use wie_midp::classes::javax::microedition::lcdui::{Graphics as MidpGraphics, Image as MidpImage};
 
 use crate::classes::org::kwis::msp::lcdui::{Graphics, ImageObserver};
 
+// gif frames without a delay are shown for this many milliseconds, like most browsers do
+const DEFAULT_FRAME_DELAY: i32 = 100;
+
 // class org.kwis.msp.lcdui.Image
 pub struct Image;
 
@@ -20,6 +29,7 @@ impl Image {
             parent_class: Some("java/lang/Object"),
             interfaces: vec![],
             methods: vec![
+                JavaMethodProto::new("<clinit>", "()V", Self::cl_init, MethodAccessFlags::STATIC),
                 JavaMethodProto::new("<init>", "()V", Self::init_empty, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("<init>", "(Ljavax/microedition/lcdui/Image;)V", Self::init, MethodAccessFlags::PRIVATE),
                 JavaMethodProto::new(
@@ -87,11 +97,29 @@ impl Image {
             fields: vec![
                 JavaFieldProto::new("midpImage", "Ljavax/microedition/lcdui/Image;", FieldAccessFlags::PRIVATE),
                 JavaFieldProto::new("mutable", "Z", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("frames", "[Ljavax/microedition/lcdui/Image;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("delays", "[I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("currentFrame", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("observer", "Lorg/kwis/msp/lcdui/ImageObserver;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("animation", "I", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new(
+                    "playingImages",
+                    "Ljava/util/Vector;",
+                    FieldAccessFlags::PRIVATE | FieldAccessFlags::STATIC,
+                ),
             ],
             access_flags: ClassAccessFlags::PUBLIC,
         }
     }
 
+    async fn cl_init(jvm: &Jvm, _: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::<clinit>()");
+
+        let playing_images: ClassInstanceRef<Vector> = jvm.new_class("java/util/Vector", "()V", ()).await?.into();
+        jvm.put_static_field("org/kwis/msp/lcdui/Image", "playingImages", "Ljava/util/Vector;", playing_images)
+            .await
+    }
+
     async fn init_empty(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Image>) -> JvmResult<()> {
         tracing::debug!("org.kwis.msp.lcdui.Image::<init>({this:?})");
 
@@ -104,21 +132,44 @@ impl Image {
         tracing::debug!("org.kwis.msp.lcdui.Image::<init>({this:?})");
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
-        jvm.put_field(&mut this, "midpImage", "Ljavax/microedition/lcdui/Image;", image).await?;
+        jvm.put_field(&mut this, "midpImage", "Ljavax/microedition/lcdui/Image;", image.clone())
+            .await?;
         jvm.put_field(&mut this, "mutable", "Z", false).await?;
 
-        Ok(())
+        Self::set_frames(jvm, &mut this, vec![image], vec![0]).await
     }
 
     async fn load_image(
-        _: &Jvm,
+        jvm: &Jvm,
         _: &mut WieJvmContext,
         name: ClassInstanceRef<String>,
         observer: ClassInstanceRef<ImageObserver>,
     ) -> JvmResult<ClassInstanceRef<Image>> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Image::loadImage({name:?}, {observer:?})");
+        tracing::debug!("org.kwis.msp.lcdui.Image::loadImage({name:?}, {observer:?})");
+
+        let image: ClassInstanceRef<Image> = jvm
+            .invoke_static(
+                "org/kwis/msp/lcdui/Image",
+                "createImage",
+                "(Ljava/lang/String;)Lorg/kwis/msp/lcdui/Image;",
+                (name,),
+            )
+            .await?;
 
-        Ok(None.into())
+        // images are decoded right away, so the observer only has to follow the animation
+        if !observer.is_null() {
+            let _: () = jvm
+                .invoke_virtual(
+                    &image,
+                    "org/kwis/msp/lcdui/Image",
+                    "play",
+                    "(Lorg/kwis/msp/lcdui/ImageObserver;)V",
+                    (observer,),
+                )
+                .await?;
+        }
+
+        Ok(image)
     }
 
     async fn create_image(jvm: &Jvm, _: &mut WieJvmContext, width: i32, height: i32) -> JvmResult<ClassInstanceRef<Image>> {
@@ -145,20 +196,19 @@ impl Image {
     async fn create_image_from_name(jvm: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<Image>> {
         tracing::debug!("org.kwis.msp.lcdui.Image::createImage({name:?})");
 
-        let midp_image: ClassInstanceRef<MidpImage> = jvm
-            .invoke_static(
-                "javax/microedition/lcdui/Image",
-                "createImage",
-                "(Ljava/lang/String;)Ljavax/microedition/lcdui/Image;",
-                (name,),
-            )
-            .await?;
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
 
-        let instance = jvm
-            .new_class("org/kwis/msp/lcdui/Image", "(Ljavax/microedition/lcdui/Image;)V", (midp_image,))
-            .await?;
+        let class_loader = JavaLangClassLoader::get_system_class_loader(jvm).await?;
+        let stream = JavaLangClassLoader::get_resource_as_stream(jvm, &class_loader, &name).await?;
+        let Some(stream) = stream else {
+            return Err(jvm
+                .exception("java/io/FileNotFoundException", &format!("Resource not found: {name}"))
+                .await);
+        };
 
-        Ok(instance.into())
+        let data = JavaIoInputStream::read_until_end(jvm, &stream).await?;
+
+        Self::decode(jvm, &data).await
     }
 
     async fn create_image_from_data(
@@ -170,20 +220,26 @@ impl Image {
     ) -> JvmResult<ClassInstanceRef<Image>> {
         tracing::debug!("org.kwis.msp.lcdui.Image::createImage({data:?}, {image_offset}, {image_length})");
 
-        let midp_image: ClassInstanceRef<MidpImage> = jvm
-            .invoke_static(
-                "javax/microedition/lcdui/Image",
-                "createImage",
-                "([BII)Ljavax/microedition/lcdui/Image;",
-                (data, image_offset, image_length),
-            )
-            .await?;
+        if data.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
+        }
 
-        let instance = jvm
-            .new_class("org/kwis/msp/lcdui/Image", "(Ljavax/microedition/lcdui/Image;)V", (midp_image,))
-            .await?;
+        let array_length = jvm.array_length(&data).await?;
+        if image_offset < 0
+            || image_length < 0
+            || (image_offset as usize)
+                .checked_add(image_length as usize)
+                .is_none_or(|end| end > array_length)
+        {
+            return Err(jvm
+                .exception("java/lang/ArrayIndexOutOfBoundsException", "invalid offset or length")
+                .await);
+        }
 
-        Ok(instance.into())
+        let mut image_data = vec![0; image_length as usize];
+        jvm.array_raw_buffer(&data).await?.read(image_offset as _, &mut image_data)?;
+
+        Self::decode(jvm, &cast_vec(image_data)).await
     }
 
     async fn create_image_from_image(jvm: &Jvm, _: &mut WieJvmContext, image: ClassInstanceRef<Image>) -> JvmResult<ClassInstanceRef<Image>> {
@@ -252,26 +308,91 @@ impl Image {
         jvm.get_field(&this, "mutable", "Z").await
     }
 
-    async fn is_animated(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>) -> JvmResult<bool> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Image::isAnimated({this:?})");
+    async fn is_animated(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::isAnimated({this:?})");
 
-        Ok(false)
+        Ok(Self::frames(jvm, &this).await?.len() > 1)
     }
 
-    async fn play(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>, observer: ClassInstanceRef<ImageObserver>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Image::play({this:?}, {observer:?})");
+    async fn play(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Image>,
+        observer: ClassInstanceRef<ImageObserver>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::play({this:?}, {observer:?})");
 
-        Ok(())
+        jvm.put_field(&mut this, "observer", "Lorg/kwis/msp/lcdui/ImageObserver;", observer)
+            .await?;
+
+        if Self::frames(jvm, &this).await?.len() <= 1 {
+            return Ok(());
+        }
+
+        // bumping the generation makes an animation task from a previous play() exit
+        let generation: i32 = jvm.get_field(&this, "animation", "I").await?;
+        jvm.put_field(&mut this, "animation", "I", generation.wrapping_add(1)).await?;
+
+        let playing_images = Self::playing_images(jvm).await?;
+        let index: i32 = jvm
+            .invoke_virtual(&playing_images, "java/util/Vector", "indexOf", "(Ljava/lang/Object;)I", (this.clone(),))
+            .await?;
+        if index < 0 {
+            let _: () = jvm
+                .invoke_virtual(
+                    &playing_images,
+                    "java/util/Vector",
+                    "addElement",
+                    "(Ljava/lang/Object;)V",
+                    (this.clone(),),
+                )
+                .await?;
+        }
+
+        context.spawn(
+            jvm,
+            Box::new(Animation {
+                image: this,
+                generation: generation.wrapping_add(1),
+            }),
+        )
     }
 
-    async fn stop(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Image::stop({this:?})");
+    async fn stop(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Image>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::stop({this:?})");
+
+        let generation: i32 = jvm.get_field(&this, "animation", "I").await?;
+        jvm.put_field(&mut this, "animation", "I", generation.wrapping_add(1)).await?;
+
+        let playing_images = Self::playing_images(jvm).await?;
+        let _: bool = jvm
+            .invoke_virtual(&playing_images, "java/util/Vector", "removeElement", "(Ljava/lang/Object;)Z", (this,))
+            .await?;
 
         Ok(())
     }
 
-    async fn stop_image(_: &Jvm, _: &mut WieJvmContext, observer: ClassInstanceRef<ImageObserver>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Image::stopImage({observer:?})");
+    async fn stop_image(jvm: &Jvm, _: &mut WieJvmContext, observer: ClassInstanceRef<ImageObserver>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::stopImage({observer:?})");
+
+        let playing_images = Self::playing_images(jvm).await?;
+        let size: i32 = jvm.invoke_virtual(&playing_images, "java/util/Vector", "size", "()I", ()).await?;
+
+        // walk backwards as stop() removes the image from the list
+        for index in (0..size).rev() {
+            let image: ClassInstanceRef<Image> = jvm
+                .invoke_virtual(&playing_images, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+                .await?;
+            let image_observer: ClassInstanceRef<ImageObserver> = jvm.get_field(&image, "observer", "Lorg/kwis/msp/lcdui/ImageObserver;").await?;
+
+            let matches = match (&image_observer.instance, &observer.instance) {
+                (Some(x), Some(y)) => x.equals(&**y)?,
+                _ => false,
+            };
+            if matches {
+                let _: () = jvm.invoke_virtual(&image, "org/kwis/msp/lcdui/Image", "stop", "()V", ()).await?;
+            }
+        }
 
         Ok(())
     }
@@ -332,7 +453,7 @@ impl Image {
 
     #[allow(clippy::too_many_arguments)]
     async fn create_sub_image(
-        _: &Jvm,
+        jvm: &Jvm,
         _: &mut WieJvmContext,
         this: ClassInstanceRef<Image>,
         x: i32,
@@ -341,15 +462,147 @@ impl Image {
         height: i32,
         mutable: bool,
     ) -> JvmResult<ClassInstanceRef<Image>> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Image::createSubImage({this:?}, {x}, {y}, {width}, {height}, {mutable})");
+        tracing::debug!("org.kwis.msp.lcdui.Image::createSubImage({this:?}, {x}, {y}, {width}, {height}, {mutable})");
+
+        let image_width: i32 = jvm.invoke_virtual(&this, "org/kwis/msp/lcdui/Image", "getWidth", "()I", ()).await?;
+        let image_height: i32 = jvm.invoke_virtual(&this, "org/kwis/msp/lcdui/Image", "getHeight", "()I", ()).await?;
+        if x < 0
+            || y < 0
+            || width <= 0
+            || height <= 0
+            || x as i64 + width as i64 > image_width as i64
+            || y as i64 + height as i64 > image_height as i64
+        {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid sub image region").await);
+        }
 
-        Ok(None.into())
+        let frames = Self::map_frames(jvm, &this, |image| {
+            let pixels = (0..height)
+                .flat_map(|py| (0..width).map(move |px| ArgbPixel::from_color(image.get_pixel(x + px, y + py))))
+                .collect();
+
+            Box::new(VecImageBuffer::<ArgbPixel>::from_raw(width as _, height as _, pixels))
+        })
+        .await?;
+        let delays = Self::delays(jvm, &this).await?;
+        let Some(first_frame) = frames.first().cloned() else {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "image has no frames").await);
+        };
+
+        let mut instance: ClassInstanceRef<Image> = jvm
+            .new_class("org/kwis/msp/lcdui/Image", "(Ljavax/microedition/lcdui/Image;)V", (first_frame,))
+            .await?
+            .into();
+        jvm.put_field(&mut instance, "mutable", "Z", mutable).await?;
+        Self::set_frames(jvm, &mut instance, frames, delays).await?;
+
+        Ok(instance)
     }
 
-    async fn set_transparent_color(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>, rgb: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Image::setTransparentColor({this:?}, {rgb})");
+    async fn set_transparent_color(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Image>, rgb: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::setTransparentColor({this:?}, {rgb})");
+
+        let key = rgb as u32 & 0xffffff;
+        let frames = Self::map_frames(jvm, &this, |image| {
+            let pixels = image
+                .colors()
+                .into_iter()
+                .map(|color| {
+                    let argb = ArgbPixel::from_color(color);
+                    if argb & 0xffffff == key { argb & 0xffffff } else { argb }
+                })
+                .collect();
+
+            Box::new(VecImageBuffer::<ArgbPixel>::from_raw(image.width(), image.height(), pixels))
+        })
+        .await?;
+        let delays = Self::delays(jvm, &this).await?;
 
-        Ok(())
+        Self::set_frames(jvm, &mut this, frames, delays).await
+    }
+
+    async fn decode(jvm: &Jvm, data: &[u8]) -> JvmResult<ClassInstanceRef<Image>> {
+        let frames = match decode_image_frames(data) {
+            Ok(x) => x,
+            Err(x) => {
+                tracing::error!("Failed to decode image: {x:?}");
+                return Err(jvm.exception("java/lang/IllegalArgumentException", "Failed to decode image").await);
+            }
+        };
+
+        let delays = frames.iter().map(|x| x.delay as i32).collect();
+        let mut images = Vec::with_capacity(frames.len());
+        for frame in frames {
+            images.push(MidpImage::from_image(jvm, frame.image).await?);
+        }
+        let Some(first_frame) = images.first().cloned() else {
+            tracing::error!("Decoded image has no frames");
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "Failed to decode image").await);
+        };
+
+        let mut instance: ClassInstanceRef<Image> = jvm
+            .new_class("org/kwis/msp/lcdui/Image", "(Ljavax/microedition/lcdui/Image;)V", (first_frame,))
+            .await?
+            .into();
+        Self::set_frames(jvm, &mut instance, images, delays).await?;
+
+        Ok(instance)
+    }
+
+    async fn set_frames(jvm: &Jvm, this: &mut ClassInstanceRef<Image>, frames: Vec<ClassInstanceRef<MidpImage>>, delays: Vec<i32>) -> JvmResult<()> {
+        let current_frame: i32 = jvm.get_field(this, "currentFrame", "I").await?;
+        let current_frame = if current_frame as usize >= frames.len() { 0 } else { current_frame };
+        let Some(frame) = frames.get(current_frame as usize).cloned() else {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "image has no frames").await);
+        };
+
+        jvm.put_field(this, "midpImage", "Ljavax/microedition/lcdui/Image;", frame).await?;
+        jvm.put_field(this, "currentFrame", "I", current_frame).await?;
+
+        let mut frames_array = jvm.instantiate_array("Ljavax/microedition/lcdui/Image;", frames.len()).await?;
+        jvm.store_array(&mut frames_array, 0, frames).await?;
+        jvm.put_field(this, "frames", "[Ljavax/microedition/lcdui/Image;", frames_array).await?;
+
+        let mut delays_array = jvm.instantiate_array("I", delays.len()).await?;
+        jvm.store_array(&mut delays_array, 0, delays).await?;
+        jvm.put_field(this, "delays", "[I", delays_array).await
+    }
+
+    async fn frames(jvm: &Jvm, this: &ClassInstanceRef<Image>) -> JvmResult<Vec<ClassInstanceRef<MidpImage>>> {
+        let frames: ClassInstanceRef<Array<ClassInstanceRef<MidpImage>>> = jvm.get_field(this, "frames", "[Ljavax/microedition/lcdui/Image;").await?;
+        if frames.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let length = jvm.array_length(&frames).await?;
+        jvm.load_array(&frames, 0, length).await
+    }
+
+    async fn delays(jvm: &Jvm, this: &ClassInstanceRef<Image>) -> JvmResult<Vec<i32>> {
+        let delays: ClassInstanceRef<Array<i32>> = jvm.get_field(this, "delays", "[I").await?;
+
+        let length = jvm.array_length(&delays).await?;
+        jvm.load_array(&delays, 0, length).await
+    }
+
+    async fn map_frames<F>(jvm: &Jvm, this: &ClassInstanceRef<Image>, f: F) -> JvmResult<Vec<ClassInstanceRef<MidpImage>>>
+    where
+        F: Fn(&dyn BackendImage) -> Box<dyn BackendImage> + Send + Sync,
+    {
+        let mut result = Vec::new();
+        for frame in Self::frames(jvm, this).await? {
+            let image = MidpImage::image(jvm, &frame).await?;
+            let image = f(&*image);
+
+            result.push(MidpImage::from_image(jvm, image).await?);
+        }
+
+        Ok(result)
+    }
+
+    async fn playing_images(jvm: &Jvm) -> JvmResult<ClassInstanceRef<Vector>> {
+        jvm.get_static_field("org/kwis/msp/lcdui/Image", "playingImages", "Ljava/util/Vector;")
+            .await
     }
 
     pub async fn midp_image(jvm: &Jvm, this: &ClassInstanceRef<Image>) -> JvmResult<ClassInstanceRef<MidpImage>> {
@@ -357,11 +610,61 @@ impl Image {
     }
 }
 
+struct Animation {
+    image: ClassInstanceRef<Image>,
+    generation: i32,
+}
+
+#[async_trait::async_trait]
+impl MethodBody<JavaError, WieJvmContext> for Animation {
+    async fn call(&self, jvm: &Jvm, context: &mut WieJvmContext, _args: Box<[JavaValue]>) -> Result<JavaValue, JavaError> {
+        jvm.attach_thread(None).await?;
+
+        let mut image = self.image.clone();
+        loop {
+            let current_frame: i32 = jvm.get_field(&image, "currentFrame", "I").await?;
+            let delays = Image::delays(jvm, &image).await?;
+            let delay = delays.get(current_frame as usize).copied().unwrap_or_default();
+
+            let delay = if delay > 0 { delay } else { DEFAULT_FRAME_DELAY };
+            context.system().sleep(delay as _).await;
+
+            let generation: i32 = jvm.get_field(&image, "animation", "I").await?;
+            if generation != self.generation {
+                break;
+            }
+
+            let frames = Image::frames(jvm, &image).await?;
+            let next_frame = (current_frame as usize + 1) % frames.len();
+            jvm.put_field(&mut image, "currentFrame", "I", next_frame as i32).await?;
+            jvm.put_field(&mut image, "midpImage", "Ljavax/microedition/lcdui/Image;", frames[next_frame].clone())
+                .await?;
+
+            let observer: ClassInstanceRef<ImageObserver> = jvm.get_field(&image, "observer", "Lorg/kwis/msp/lcdui/ImageObserver;").await?;
+            if !observer.is_null() {
+                let _: () = jvm
+                    .invoke_virtual(
+                        &observer,
+                        "org/kwis/msp/lcdui/ImageObserver",
+                        "notify",
+                        "(Lorg/kwis/msp/lcdui/Image;I)V",
+                        (image.clone(), next_frame as i32),
+                    )
+                    .await?;
+            }
+        }
+
+        jvm.detach_thread()?;
+
+        Ok(JavaValue::Void)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use alloc::boxed::Box;
+    use alloc::{boxed::Box, vec::Vec};
 
-    use jvm::ClassInstanceRef;
+    use jvm::{Array, ClassInstanceRef, JavaError, Result as JvmResult};
     use test_utils::run_jvm_test;
     use wie_midp::classes::javax::microedition::lcdui::Image as MidpImage;
     use wie_util::Result;
@@ -439,4 +742,105 @@ mod test {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_sub_image_and_transparent_color() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let source: ClassInstanceRef<Image> = jvm
+                .invoke_static("org/kwis/msp/lcdui/Image", "createImage", "(II)Lorg/kwis/msp/lcdui/Image;", (2, 1))
+                .await?;
+            let graphics: ClassInstanceRef<Graphics> = jvm
+                .invoke_virtual(&source, "org/kwis/msp/lcdui/Image", "getGraphics", "()Lorg/kwis/msp/lcdui/Graphics;", ())
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "setColor", "(I)V", (0x00ff00,))
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "fillRect", "(IIII)V", (1, 0, 1, 1))
+                .await?;
+
+            let sub_image: ClassInstanceRef<Image> = jvm
+                .invoke_virtual(
+                    &source,
+                    "org/kwis/msp/lcdui/Image",
+                    "createSubImage",
+                    "(IIIIZ)Lorg/kwis/msp/lcdui/Image;",
+                    (1, 0, 1, 1, false),
+                )
+                .await?;
+            let width: i32 = jvm.invoke_virtual(&sub_image, "org/kwis/msp/lcdui/Image", "getWidth", "()I", ()).await?;
+            assert_eq!(width, 1);
+
+            let sub_image_midp = Image::midp_image(&jvm, &sub_image).await?;
+            let pixel = MidpImage::image(&jvm, &sub_image_midp).await?.get_pixel(0, 0);
+            assert_eq!((pixel.a, pixel.g), (0xff, 0xff));
+
+            let _: () = jvm
+                .invoke_virtual(&sub_image, "org/kwis/msp/lcdui/Image", "setTransparentColor", "(I)V", (0x00ff00,))
+                .await?;
+            let sub_image_midp = Image::midp_image(&jvm, &sub_image).await?;
+            let pixel = MidpImage::image(&jvm, &sub_image_midp).await?.get_pixel(0, 0);
+            assert_eq!(pixel.a, 0);
+
+            let result: jvm::Result<ClassInstanceRef<Image>> = jvm
+                .invoke_virtual(
+                    &source,
+                    "org/kwis/msp/lcdui/Image",
+                    "createSubImage",
+                    "(IIIIZ)Lorg/kwis/msp/lcdui/Image;",
+                    (1, 0, 2, 1, false),
+                )
+                .await;
+            assert!(result.is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_create_image_rejects_invalid_data() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let mut data: ClassInstanceRef<Array<i8>> = jvm.instantiate_array("B", 4).await?.into();
+
+            for (data, offset, length, exception_class) in [
+                (None.into(), 0, 0, "java/lang/NullPointerException"),
+                (data.clone(), -1, 2, "java/lang/ArrayIndexOutOfBoundsException"),
+                (data.clone(), 0, -1, "java/lang/ArrayIndexOutOfBoundsException"),
+                (data.clone(), 2, 3, "java/lang/ArrayIndexOutOfBoundsException"),
+            ] {
+                let result: JvmResult<ClassInstanceRef<Image>> = jvm
+                    .invoke_static(
+                        "org/kwis/msp/lcdui/Image",
+                        "createImage",
+                        "([BII)Lorg/kwis/msp/lcdui/Image;",
+                        (data, offset, length),
+                    )
+                    .await;
+                let Err(JavaError::JavaException(exception)) = result else {
+                    panic!("createImage({offset}, {length}) succeeded");
+                };
+                assert!(jvm.is_instance(&*exception, exception_class));
+            }
+
+            // a gif that ends right after its header has no frames
+            let gif = b"GIF89a\x01\x00\x01\x00\x00\x00\x00\x3b".iter().map(|x| *x as i8).collect::<Vec<_>>();
+            let gif_length = gif.len();
+            data = jvm.instantiate_array("B", gif_length).await?.into();
+            jvm.store_array(&mut data, 0, gif).await?;
+            let result: JvmResult<ClassInstanceRef<Image>> = jvm
+                .invoke_static(
+                    "org/kwis/msp/lcdui/Image",
+                    "createImage",
+                    "([BII)Lorg/kwis/msp/lcdui/Image;",
+                    (data, 0, gif_length as i32),
+                )
+                .await;
+            let Err(JavaError::JavaException(exception)) = result else {
+                panic!("createImage decoded a gif without frames");
+            };
+            assert!(jvm.is_instance(&*exception, "java/lang/IllegalArgumentException"));
+
+            Ok(())
+        })
+    }
 }
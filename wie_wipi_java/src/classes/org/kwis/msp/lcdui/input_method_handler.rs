@@ -1,15 +1,28 @@
-use alloc::vec;
+use alloc::{string::String as RustString, vec};
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::lang::String;
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
 
+use wie_backend::{InputMethod, InputMode, InputResult, KeyCode, KeyPhase};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
 // class org.kwis.msp.lcdui.InputMethodHandler
 pub struct InputMethodHandler;
 
 impl InputMethodHandler {
+    pub const MODE_KOREAN: i32 = 1;
+    pub const MODE_UPPERCASE: i32 = 2;
+    pub const MODE_LOWERCASE: i32 = 3;
+    pub const MODE_NUMERIC: i32 = 4;
+
+    // TextField constraints that only accept digits
+    const CONSTRAINT_MASK: i32 = 0xffff;
+    const CONSTRAINT_NUMERIC: i32 = 2;
+    const CONSTRAINT_PHONENUMBER: i32 = 3;
+    const CONSTRAINT_DECIMAL: i32 = 5;
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "org/kwis/msp/lcdui/InputMethodHandler",
@@ -18,23 +31,120 @@ impl InputMethodHandler {
             methods: vec![
                 JavaMethodProto::new("<init>", "(I)V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("setCurrentMode", "(I)Z", Self::set_current_mode, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCurrentMode", "()I", Self::get_current_mode, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getSupportedModes", "()[I", Self::get_supported_modes, MethodAccessFlags::PUBLIC),
             ],
-            fields: vec![],
+            fields: vec![JavaFieldProto::new("state", "Ljava/lang/String;", FieldAccessFlags::PRIVATE)],
             access_flags: ClassAccessFlags::PUBLIC,
         }
     }
 
-    async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, constraint: i32) -> JvmResult<()> {
-        tracing::debug!("stub org.kwis.msp.lcdui.InputMethodHandler::<init>({this:?}, {constraint})");
+    async fn init(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, constraint: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.InputMethodHandler::<init>({this:?}, {constraint})");
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
 
+        let mode = match constraint & Self::CONSTRAINT_MASK {
+            Self::CONSTRAINT_NUMERIC | Self::CONSTRAINT_PHONENUMBER | Self::CONSTRAINT_DECIMAL => InputMode::Numeric,
+            _ => InputMode::Korean,
+        };
+        let layout = context.system().device_profile().keypad;
+        Self::store(jvm, &mut this, &InputMethod::new(mode, layout)).await?;
+
         Ok(())
     }
 
-    async fn set_current_mode(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<bool> {
-        tracing::warn!("stub org.kwis.msp.lcdui.InputMethodHandler::setCurrentMode({this:?}, {mode})");
+    async fn set_current_mode(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mode: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lcdui.InputMethodHandler::setCurrentMode({this:?}, {mode})");
+
+        let Some(mode) = Self::mode_from_raw(mode) else {
+            return Ok(false);
+        };
+
+        // text still being composed is dropped, as the owning component is not notified of mode changes
+        let mut input_method = Self::load(jvm, &this).await?;
+        input_method.set_mode(mode);
+        Self::store(jvm, &mut this, &input_method).await?;
 
         Ok(true)
     }
+
+    async fn get_current_mode(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lcdui.InputMethodHandler::getCurrentMode({this:?})");
+
+        let input_method = Self::load(jvm, &this).await?;
+
+        Ok(Self::mode_to_raw(input_method.mode()))
+    }
+
+    async fn get_supported_modes(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Array<i32>>> {
+        tracing::debug!("org.kwis.msp.lcdui.InputMethodHandler::getSupportedModes({this:?})");
+
+        let modes = [Self::MODE_KOREAN, Self::MODE_UPPERCASE, Self::MODE_LOWERCASE, Self::MODE_NUMERIC];
+        let mut array = jvm.instantiate_array("I", modes.len()).await?;
+        jvm.store_array(&mut array, 0, modes).await?;
+
+        Ok(array.into())
+    }
+
+    // feeds a key event to the input method, `room` being the number of characters the owning component can still take
+    pub(crate) async fn handle_key(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: &mut ClassInstanceRef<Self>,
+        phase: KeyPhase,
+        key: KeyCode,
+        room: usize,
+    ) -> JvmResult<Option<InputResult>> {
+        let mut input_method = Self::load(jvm, this).await?;
+        let result = input_method.key_event(phase, key, context.system().platform().now(), room);
+        Self::store(jvm, this, &input_method).await?;
+
+        Ok(result)
+    }
+
+    // ends the composition, returning the text that was being composed
+    pub(crate) async fn commit(jvm: &Jvm, this: &mut ClassInstanceRef<Self>) -> JvmResult<RustString> {
+        let mut input_method = Self::load(jvm, this).await?;
+        let committed = input_method.commit();
+        Self::store(jvm, this, &input_method).await?;
+
+        Ok(committed)
+    }
+
+    pub(crate) async fn composing(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<RustString> {
+        Ok(Self::load(jvm, this).await?.composing())
+    }
+
+    fn mode_from_raw(mode: i32) -> Option<InputMode> {
+        Some(match mode {
+            Self::MODE_KOREAN => InputMode::Korean,
+            Self::MODE_UPPERCASE => InputMode::Uppercase,
+            Self::MODE_LOWERCASE => InputMode::Lowercase,
+            Self::MODE_NUMERIC => InputMode::Numeric,
+            _ => return None,
+        })
+    }
+
+    fn mode_to_raw(mode: InputMode) -> i32 {
+        match mode {
+            InputMode::Korean => Self::MODE_KOREAN,
+            InputMode::Uppercase => Self::MODE_UPPERCASE,
+            InputMode::Lowercase => Self::MODE_LOWERCASE,
+            InputMode::Numeric => Self::MODE_NUMERIC,
+        }
+    }
+
+    async fn load(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<InputMethod> {
+        let state: ClassInstanceRef<String> = jvm.get_field(this, "state", "Ljava/lang/String;").await?;
+        let state = JavaLangString::to_rust_string(jvm, &state).await?;
+
+        Ok(InputMethod::restore(&state).unwrap())
+    }
+
+    async fn store(jvm: &Jvm, this: &mut ClassInstanceRef<Self>, input_method: &InputMethod) -> JvmResult<()> {
+        let state = JavaLangString::from_rust_string(jvm, &input_method.save()).await?;
+
+        jvm.put_field(this, "state", "Ljava/lang/String;", state).await
+    }
 }
@@ -6,10 +6,17 @@ use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::lcdui::{Display, Graphics};
+
 // class org.kwis.msp.lwc.AnnunciatorComponent
+// status bar shell at the top of the screen
 pub struct AnnunciatorComponent;
 
 impl AnnunciatorComponent {
+    const HEIGHT: i32 = 16;
+    const BACKGROUND_COLOR: i32 = 0xe0e0e0;
+    const LINE_COLOR: i32 = 0x808080;
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "org/kwis/msp/lwc/AnnunciatorComponent",
@@ -18,6 +25,7 @@ impl AnnunciatorComponent {
             methods: vec![
                 JavaMethodProto::new("<init>", "(Z)V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("show", "()V", Self::show, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, MethodAccessFlags::PROTECTED),
             ],
             fields: vec![],
             access_flags: ClassAccessFlags::PUBLIC,
@@ -25,16 +33,152 @@ impl AnnunciatorComponent {
     }
 
     async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<AnnunciatorComponent>, a0: bool) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.AnnunciatorComponent::<init>({this:?}, {a0})");
+        tracing::debug!("org.kwis.msp.lwc.AnnunciatorComponent::<init>({this:?}, {a0})");
 
-        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/ShellComponent", "<init>", "()V", ()).await?;
+        let display: ClassInstanceRef<Display> = jvm
+            .invoke_static("org/kwis/msp/lcdui/Display", "getDefaultDisplay", "()Lorg/kwis/msp/lcdui/Display;", [])
+            .await?;
+        let width: i32 = jvm.invoke_virtual(&display, "org/kwis/msp/lcdui/Display", "getWidth", "()I", []).await?;
+
+        let _: () = jvm
+            .invoke_special(&this, "org/kwis/msp/lwc/ShellComponent", "<init>", "(IIII)V", (0, 0, width, Self::HEIGHT))
+            .await?;
 
         Ok(())
     }
 
-    async fn show(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.AnnunciatorComponent::show()");
+    async fn show(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<AnnunciatorComponent>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.AnnunciatorComponent::show({this:?})");
 
-        Ok(())
+        jvm.invoke_special(&this, "org/kwis/msp/lwc/ShellComponent", "show", "()V", ()).await
+    }
+
+    async fn paint(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<AnnunciatorComponent>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.AnnunciatorComponent::paint({this:?}, {graphics:?})");
+
+        let width: i32 = jvm.get_field(&this, "w", "I").await?;
+        let height: i32 = jvm.get_field(&this, "h", "I").await?;
+
+        let _: () = jvm
+            .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "setColor", "(I)V", (Self::BACKGROUND_COLOR,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "fillRect", "(IIII)V", (0, 0, width, height))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "setColor", "(I)V", (Self::LINE_COLOR,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                &graphics,
+                "org/kwis/msp/lcdui/Graphics",
+                "drawLine",
+                "(IIII)V",
+                (0, height - 1, width - 1, height - 1),
+            )
+            .await?;
+
+        // children are painted over the bar, skipping the shell background
+        jvm.invoke_special(
+            &this,
+            "org/kwis/msp/lwc/ContainerComponent",
+            "paint",
+            "(Lorg/kwis/msp/lcdui/Graphics;)V",
+            (graphics,),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{boxed::Box, vec, vec::Vec};
+
+    use java_constants::ClassAccessFlags;
+    use jvm::ClassInstanceRef;
+    use test_utils::run_jvm_test;
+    use wie_jvm_support::WieJavaClassProto;
+    use wie_midp::classes::javax::microedition::lcdui::Image as MidpImage;
+    use wie_util::Result;
+
+    use crate::{
+        classes::org::kwis::msp::lcdui::{Display, Graphics, Image, Jlet},
+        get_protos,
+    };
+
+    use super::AnnunciatorComponent;
+
+    fn test_jlet_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "test/TestJlet",
+            parent_class: Some("org/kwis/msp/lcdui/Jlet"),
+            interfaces: vec![],
+            methods: vec![],
+            fields: vec![],
+            access_flags: ClassAccessFlags::PUBLIC,
+        }
+    }
+
+    #[test]
+    fn test_annunciator_spans_the_screen_top() -> Result<()> {
+        let fixture: Box<[WieJavaClassProto]> = Vec::from([test_jlet_proto()]).into_boxed_slice();
+        run_jvm_test(
+            Box::new([wie_midp::get_protos().into(), get_protos().into(), fixture]),
+            |jvm| async move {
+                // the annunciator sizes itself from the active jlet's display
+                let midp_display = jvm.new_class("javax/microedition/lcdui/Display", "()V", ()).await?;
+                let mut display: ClassInstanceRef<Display> = jvm.instantiate_class("org/kwis/msp/lcdui/Display").await?.into();
+                jvm.put_field(&mut display, "midpDisplay", "Ljavax/microedition/lcdui/Display;", midp_display)
+                    .await?;
+                let mut jlet: ClassInstanceRef<Jlet> = jvm.instantiate_class("test/TestJlet").await?.into();
+                jvm.put_field(&mut jlet, "dis", "Lorg/kwis/msp/lcdui/Display;", display).await?;
+                jvm.put_static_field("org/kwis/msp/lcdui/Jlet", "currentJlet", "Lorg/kwis/msp/lcdui/Jlet;", jlet)
+                    .await?;
+
+                let annunciator: ClassInstanceRef<AnnunciatorComponent> =
+                    jvm.new_class("org/kwis/msp/lwc/AnnunciatorComponent", "(Z)V", (true,)).await?.into();
+                let bounds = [("getX", 0), ("getY", 0), ("getWidth", 320), ("getHeight", AnnunciatorComponent::HEIGHT)];
+                for (method, expected) in bounds {
+                    let value: i32 = jvm.invoke_virtual(&annunciator, "org/kwis/msp/lwc/Component", method, "()I", ()).await?;
+                    assert_eq!(value, expected, "{method}");
+                }
+
+                // a light bar with a separator line at the bottom
+                let image: ClassInstanceRef<Image> = jvm
+                    .invoke_static(
+                        "org/kwis/msp/lcdui/Image",
+                        "createImage",
+                        "(II)Lorg/kwis/msp/lcdui/Image;",
+                        (320, AnnunciatorComponent::HEIGHT),
+                    )
+                    .await?;
+                let graphics: ClassInstanceRef<Graphics> = jvm
+                    .invoke_virtual(&image, "org/kwis/msp/lcdui/Image", "getGraphics", "()Lorg/kwis/msp/lcdui/Graphics;", ())
+                    .await?;
+                let _: () = jvm
+                    .invoke_virtual(
+                        &annunciator,
+                        "org/kwis/msp/lwc/Component",
+                        "paint",
+                        "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                        (graphics,),
+                    )
+                    .await?;
+
+                let midp_image = Image::midp_image(&jvm, &image).await?;
+                let backend_image = MidpImage::image(&jvm, &midp_image).await?;
+                let background = backend_image.get_pixel(10, 5);
+                let line = backend_image.get_pixel(10, AnnunciatorComponent::HEIGHT as u32 - 1);
+                assert_eq!((background.r, background.g, background.b), (0xe0, 0xe0, 0xe0));
+                assert_eq!((line.r, line.g, line.b), (0x80, 0x80, 0x80));
+
+                Ok(())
+            },
+        )
     }
 }
@@ -1,15 +1,23 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::{lcdui::Graphics, lwc::ContainerComponent};
+
 // class org.kwis.msp.lwc.Component
 pub struct Component;
 
 impl Component {
+    // keyNotify event types, as dispatched by the card canvas
+    pub(crate) const KEY_PRESSED: i32 = 1;
+    pub(crate) const KEY_RELEASED: i32 = 2;
+    pub(crate) const KEY_REPEATED: i32 = 3;
+    pub(crate) const KEY_TYPED: i32 = 4; // chr is a character typed on the host keyboard
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "org/kwis/msp/lwc/Component",
@@ -22,15 +30,36 @@ impl Component {
                 JavaMethodProto::new("showNotify", "(Z)V", Self::show_notify, MethodAccessFlags::PROTECTED),
                 JavaMethodProto::new("configure", "(IIIII)V", Self::configure, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("setFocus", "()V", Self::set_focus, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("hasFocus", "()Z", Self::has_focus, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isShown", "()Z", Self::is_shown, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getX", "()I", Self::get_x, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getY", "()I", Self::get_y, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getWidth", "()I", Self::get_width, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("getHeight", "()I", Self::get_height, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getParent",
+                    "()Lorg/kwis/msp/lwc/ContainerComponent;",
+                    Self::get_parent,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("repaint", "()V", Self::repaint, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("x", "I", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("y", "I", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("w", "I", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("h", "I", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("focused", "Z", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("shown", "Z", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("parent", "Lorg/kwis/msp/lwc/ContainerComponent;", FieldAccessFlags::PRIVATE),
             ],
-            fields: vec![],
             access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
         }
     }
 
     async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
-        tracing::debug!("stub org.kwis.msp.lwc.Component::<init>({this:?})");
+        tracing::debug!("org.kwis.msp.lwc.Component::<init>({this:?})");
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
 
@@ -38,39 +67,131 @@ impl Component {
     }
 
     async fn key_notify(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: i32, chr: i32) -> JvmResult<bool> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::keyNotify({this:?}, {type:?}, {chr:?})");
+        tracing::debug!("org.kwis.msp.lwc.Component::keyNotify({this:?}, {type:?}, {chr:?})");
 
-        Ok(true)
+        Ok(false)
     }
 
-    async fn focus_notify(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, focus: bool) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::focusNotify({this:?}, {focus:?})");
+    async fn focus_notify(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, focus: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::focusNotify({this:?}, {focus:?})");
+
+        jvm.put_field(&mut this, "focused", "Z", focus).await?;
+
+        let _: () = jvm.invoke_virtual(&this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
 
         Ok(())
     }
 
-    async fn show_notify(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, show: bool) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::showNotify({this:?}, {show:?})");
+    async fn show_notify(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, show: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::showNotify({this:?}, {show:?})");
 
-        Ok(())
+        jvm.put_field(&mut this, "shown", "Z", show).await
     }
 
+    // mask is a layout hint for the native toolkit, bounds are always applied as given
     #[allow(clippy::too_many_arguments)]
-    async fn configure(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32, w: i32, h: i32, mask: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::configure({this:?}, {x}, {y}, {w}, {h}, {mask})");
+    async fn configure(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        mask: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::configure({this:?}, {x}, {y}, {w}, {h}, {mask})");
+
+        jvm.put_field(&mut this, "x", "I", x).await?;
+        jvm.put_field(&mut this, "y", "I", y).await?;
+        jvm.put_field(&mut this, "w", "I", w.max(0)).await?;
+        jvm.put_field(&mut this, "h", "I", h.max(0)).await?;
+
+        let _: () = jvm.invoke_virtual(&this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
 
         Ok(())
     }
 
-    async fn set_focus(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::setFocus({this:?})");
+    async fn set_focus(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setFocus({this:?})");
+
+        let parent: ClassInstanceRef<ContainerComponent> = jvm.get_field(&this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await?;
+        if parent.is_null() {
+            let focused: bool = jvm.get_field(&this, "focused", "Z").await?;
+            if !focused {
+                let _: () = jvm
+                    .invoke_virtual(&this, "org/kwis/msp/lwc/Component", "focusNotify", "(Z)V", (true,))
+                    .await?;
+            }
+
+            return Ok(());
+        }
+
+        ContainerComponent::focus_component(jvm, &parent, this).await?;
+
+        // focus travels up so every container on the path forwards keys to this component
+        let _: () = jvm.invoke_virtual(&parent, "org/kwis/msp/lwc/Component", "setFocus", "()V", ()).await?;
 
         Ok(())
     }
 
-    async fn get_height(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::getHeight({this:?})");
+    async fn has_focus(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.Component::hasFocus({this:?})");
+
+        jvm.get_field(&this, "focused", "Z").await
+    }
+
+    async fn is_shown(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.Component::isShown({this:?})");
+
+        jvm.get_field(&this, "shown", "Z").await
+    }
+
+    async fn get_x(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getX({this:?})");
+
+        jvm.get_field(&this, "x", "I").await
+    }
+
+    async fn get_y(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getY({this:?})");
 
-        Ok(0)
+        jvm.get_field(&this, "y", "I").await
+    }
+
+    async fn get_width(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getWidth({this:?})");
+
+        jvm.get_field(&this, "w", "I").await
+    }
+
+    async fn get_height(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getHeight({this:?})");
+
+        jvm.get_field(&this, "h", "I").await
+    }
+
+    async fn get_parent(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<ContainerComponent>> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getParent({this:?})");
+
+        jvm.get_field(&this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await
+    }
+
+    async fn paint(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::paint({this:?}, {graphics:?})");
+
+        Ok(())
+    }
+
+    // repaints are forwarded up to the shell, which owns the card on the display
+    async fn repaint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::repaint({this:?})");
+
+        let parent: ClassInstanceRef<ContainerComponent> = jvm.get_field(&this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await?;
+        if !parent.is_null() {
+            let _: () = jvm.invoke_virtual(&parent, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
+        }
+
+        Ok(())
     }
 }
@@ -1,12 +1,16 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use java_runtime::classes::java::util::Vector;
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
-use crate::classes::org::kwis::msp::lwc::Component;
+use crate::classes::{
+    net::wie::WIPIKeyCode,
+    org::kwis::msp::{lcdui::Graphics, lwc::Component},
+};
 
 // class org.kwis.msp.lwc.ContainerComponent
 pub struct ContainerComponent;
@@ -32,35 +36,602 @@ impl ContainerComponent {
                     Self::remove_component,
                     MethodAccessFlags::PUBLIC,
                 ),
+                JavaMethodProto::new(
+                    "getComponent",
+                    "(I)Lorg/kwis/msp/lwc/Component;",
+                    Self::get_component,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getComponentCount", "()I", Self::get_component_count, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getFocusedComponent",
+                    "()Lorg/kwis/msp/lwc/Component;",
+                    Self::get_focused_component,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("focusNotify", "(Z)V", Self::focus_notify, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("showNotify", "(Z)V", Self::show_notify, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, MethodAccessFlags::PROTECTED),
+            ],
+            fields: vec![
+                JavaFieldProto::new("components", "Ljava/util/Vector;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("focusedComponent", "Lorg/kwis/msp/lwc/Component;", FieldAccessFlags::PRIVATE),
             ],
-            fields: vec![],
             access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
         }
     }
 
-    async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
-        tracing::debug!("stub org.kwis.msp.lwc.ContainerComponent::<init>({this:?})");
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::<init>({this:?})");
 
         let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "<init>", "()V", ()).await?;
 
+        let components = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(&mut this, "components", "Ljava/util/Vector;", components).await?;
+
+        Ok(())
+    }
+
+    async fn add_component(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        mut component: ClassInstanceRef<Component>,
+    ) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::addComponent({this:?}, {component:?})");
+
+        if component.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "component is null").await);
+        }
+
+        let components = Self::components(jvm, &this).await?;
+        let index: i32 = jvm
+            .invoke_virtual(&components, "java/util/Vector", "indexOf", "(Ljava/lang/Object;)I", (component.clone(),))
+            .await?;
+        if index >= 0 {
+            return Ok(index);
+        }
+
+        // a component lives in one container at a time, so adding it elsewhere moves it
+        let parent: ClassInstanceRef<Self> = jvm.get_field(&component, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await?;
+        if !parent.is_null() {
+            let _: () = jvm
+                .invoke_virtual(
+                    &parent,
+                    "org/kwis/msp/lwc/ContainerComponent",
+                    "removeComponent",
+                    "(Lorg/kwis/msp/lwc/Component;)V",
+                    (component.clone(),),
+                )
+                .await?;
+        }
+
+        let _: () = jvm
+            .invoke_virtual(
+                &components,
+                "java/util/Vector",
+                "addElement",
+                "(Ljava/lang/Object;)V",
+                (component.clone(),),
+            )
+            .await?;
+        jvm.put_field(&mut component, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;", this.clone())
+            .await?;
+
+        let shown: bool = jvm.get_field(&this, "shown", "Z").await?;
+        if shown {
+            let _: () = jvm
+                .invoke_virtual(&component, "org/kwis/msp/lwc/Component", "showNotify", "(Z)V", (true,))
+                .await?;
+        }
+
+        let focused_component: ClassInstanceRef<Component> = jvm.get_field(&this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;").await?;
+        if focused_component.is_null() {
+            Self::focus_component(jvm, &this, component.clone()).await?;
+        }
+
+        let _: () = jvm.invoke_virtual(&this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
+
+        jvm.invoke_virtual(&components, "java/util/Vector", "size", "()I", ())
+            .await
+            .map(|x: i32| x - 1)
+    }
+
+    async fn remove_component_index(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, index: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::removeComponent({this:?}, {index})");
+
+        let components = Self::components(jvm, &this).await?;
+        let size: i32 = jvm.invoke_virtual(&components, "java/util/Vector", "size", "()I", ()).await?;
+        if index < 0 || index >= size {
+            return Err(jvm.exception("java/lang/ArrayIndexOutOfBoundsException", "invalid component index").await);
+        }
+
+        let mut component: ClassInstanceRef<Component> = jvm
+            .invoke_virtual(&components, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&components, "java/util/Vector", "removeElementAt", "(I)V", (index,))
+            .await?;
+        jvm.put_field(&mut component, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;", None)
+            .await?;
+
+        let shown: bool = jvm.get_field(&component, "shown", "Z").await?;
+        if shown {
+            let _: () = jvm
+                .invoke_virtual(&component, "org/kwis/msp/lwc/Component", "showNotify", "(Z)V", (false,))
+                .await?;
+        }
+
+        // focus moves to the component that took the removed one's place
+        let focused_component: ClassInstanceRef<Component> = jvm.get_field(&this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;").await?;
+        if Self::is_same(&focused_component, &component)? {
+            let mut this = this.clone();
+            jvm.put_field(&mut this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;", None).await?;
+
+            let focused: bool = jvm.get_field(&component, "focused", "Z").await?;
+            if focused {
+                let _: () = jvm
+                    .invoke_virtual(&component, "org/kwis/msp/lwc/Component", "focusNotify", "(Z)V", (false,))
+                    .await?;
+            }
+
+            if size > 1 {
+                let next: ClassInstanceRef<Component> = jvm
+                    .invoke_virtual(
+                        &components,
+                        "java/util/Vector",
+                        "elementAt",
+                        "(I)Ljava/lang/Object;",
+                        (index.min(size - 2),),
+                    )
+                    .await?;
+                Self::focus_component(jvm, &this, next).await?;
+            }
+        }
+
+        let _: () = jvm.invoke_virtual(&this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
+
         Ok(())
     }
 
-    async fn add_component(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, component: ClassInstanceRef<Component>) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::addComponent({this:?}, {component:?})");
+    async fn remove_component(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        component: ClassInstanceRef<Component>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::removeComponent({this:?}, {component:?})");
+
+        let components = Self::components(jvm, &this).await?;
+        let index: i32 = jvm
+            .invoke_virtual(&components, "java/util/Vector", "indexOf", "(Ljava/lang/Object;)I", (component,))
+            .await?;
+        if index < 0 {
+            return Ok(());
+        }
+
+        jvm.invoke_virtual(&this, "org/kwis/msp/lwc/ContainerComponent", "removeComponent", "(I)V", (index,))
+            .await
+    }
+
+    async fn get_component(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, index: i32) -> JvmResult<ClassInstanceRef<Component>> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::getComponent({this:?}, {index})");
+
+        let components = Self::components(jvm, &this).await?;
+        jvm.invoke_virtual(&components, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (index,))
+            .await
+    }
+
+    async fn get_component_count(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::getComponentCount({this:?})");
+
+        let components = Self::components(jvm, &this).await?;
+        jvm.invoke_virtual(&components, "java/util/Vector", "size", "()I", ()).await
+    }
+
+    async fn get_focused_component(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Component>> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::getFocusedComponent({this:?})");
+
+        jvm.get_field(&this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;").await
+    }
+
+    async fn key_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: i32, chr: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::keyNotify({this:?}, {type}, {chr})");
+
+        let focused_component: ClassInstanceRef<Component> = jvm.get_field(&this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;").await?;
+        if focused_component.is_null() {
+            return Ok(false);
+        }
+
+        let handled: bool = jvm
+            .invoke_virtual(&focused_component, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (r#type, chr))
+            .await?;
+        if handled {
+            return Ok(true);
+        }
+
+        // unhandled up and down keys move focus between children
+        if r#type != Component::KEY_PRESSED && r#type != Component::KEY_REPEATED {
+            return Ok(false);
+        }
+        let step = match WIPIKeyCode::from_raw(chr) {
+            Some(WIPIKeyCode::UP) => -1,
+            Some(WIPIKeyCode::DOWN) => 1,
+            _ => return Ok(false),
+        };
+
+        let components = Self::components(jvm, &this).await?;
+        let size: i32 = jvm.invoke_virtual(&components, "java/util/Vector", "size", "()I", ()).await?;
+        let index: i32 = jvm
+            .invoke_virtual(&components, "java/util/Vector", "indexOf", "(Ljava/lang/Object;)I", (focused_component,))
+            .await?;
+
+        let next = index + step;
+        if next < 0 || next >= size {
+            return Ok(false);
+        }
+
+        let next: ClassInstanceRef<Component> = jvm
+            .invoke_virtual(&components, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (next,))
+            .await?;
+        Self::focus_component(jvm, &this, next).await?;
 
-        Ok(0)
+        Ok(true)
     }
 
-    async fn remove_component_index(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, index: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::removeComponent({this:?}, {index})");
+    async fn focus_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, focus: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::focusNotify({this:?}, {focus})");
+
+        let _: () = jvm
+            .invoke_special(&this, "org/kwis/msp/lwc/Component", "focusNotify", "(Z)V", (focus,))
+            .await?;
+
+        let focused_component: ClassInstanceRef<Component> = jvm.get_field(&this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;").await?;
+        if !focused_component.is_null() {
+            let _: () = jvm
+                .invoke_virtual(&focused_component, "org/kwis/msp/lwc/Component", "focusNotify", "(Z)V", (focus,))
+                .await?;
+        }
 
         Ok(())
     }
 
-    async fn remove_component(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, component: ClassInstanceRef<Component>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::removeComponent({this:?}, {component:?})");
+    async fn show_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, show: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::showNotify({this:?}, {show})");
+
+        let _: () = jvm
+            .invoke_special(&this, "org/kwis/msp/lwc/Component", "showNotify", "(Z)V", (show,))
+            .await?;
+
+        let components = Self::components(jvm, &this).await?;
+        let size: i32 = jvm.invoke_virtual(&components, "java/util/Vector", "size", "()I", ()).await?;
+        for i in 0..size {
+            let component: ClassInstanceRef<Component> = jvm
+                .invoke_virtual(&components, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (i,))
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&component, "org/kwis/msp/lwc/Component", "showNotify", "(Z)V", (show,))
+                .await?;
+        }
 
         Ok(())
     }
+
+    // children are painted in insertion order, each translated and clipped to its own bounds
+    async fn paint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::paint({this:?}, {graphics:?})");
+
+        let components = Self::components(jvm, &this).await?;
+        let size: i32 = jvm.invoke_virtual(&components, "java/util/Vector", "size", "()I", ()).await?;
+        for i in 0..size {
+            let component: ClassInstanceRef<Component> = jvm
+                .invoke_virtual(&components, "java/util/Vector", "elementAt", "(I)Ljava/lang/Object;", (i,))
+                .await?;
+
+            let x: i32 = jvm.get_field(&component, "x", "I").await?;
+            let y: i32 = jvm.get_field(&component, "y", "I").await?;
+            let width: i32 = jvm.get_field(&component, "w", "I").await?;
+            let height: i32 = jvm.get_field(&component, "h", "I").await?;
+            if width <= 0 || height <= 0 {
+                continue;
+            }
+
+            let clip_x: i32 = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "getClipX", "()I", ())
+                .await?;
+            let clip_y: i32 = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "getClipY", "()I", ())
+                .await?;
+            let clip_width: i32 = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "getClipWidth", "()I", ())
+                .await?;
+            let clip_height: i32 = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "getClipHeight", "()I", ())
+                .await?;
+
+            let _: () = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "translate", "(II)V", (x, y))
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "clipRect", "(IIII)V", (0, 0, width, height))
+                .await?;
+
+            let result: JvmResult<()> = jvm
+                .invoke_virtual(
+                    &component,
+                    "org/kwis/msp/lwc/Component",
+                    "paint",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    (graphics.clone(),),
+                )
+                .await;
+
+            let _: () = jvm
+                .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "translate", "(II)V", (-x, -y))
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics,
+                    "org/kwis/msp/lcdui/Graphics",
+                    "setClip",
+                    "(IIII)V",
+                    (clip_x, clip_y, clip_width, clip_height),
+                )
+                .await?;
+
+            result?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn focus_component(jvm: &Jvm, this: &ClassInstanceRef<Self>, component: ClassInstanceRef<Component>) -> JvmResult<()> {
+        let focused_component: ClassInstanceRef<Component> = jvm.get_field(this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;").await?;
+        if Self::is_same(&focused_component, &component)? {
+            return Ok(());
+        }
+
+        if !focused_component.is_null() {
+            let _: () = jvm
+                .invoke_virtual(&focused_component, "org/kwis/msp/lwc/Component", "focusNotify", "(Z)V", (false,))
+                .await?;
+        }
+
+        let mut this = this.clone();
+        jvm.put_field(&mut this, "focusedComponent", "Lorg/kwis/msp/lwc/Component;", component.clone())
+            .await?;
+
+        // the child only has focus while every container above it has
+        let focused: bool = jvm.get_field(&this, "focused", "Z").await?;
+        let _: () = jvm
+            .invoke_virtual(&component, "org/kwis/msp/lwc/Component", "focusNotify", "(Z)V", (focused,))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn components(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Vector>> {
+        jvm.get_field(this, "components", "Ljava/util/Vector;").await
+    }
+
+    fn is_same(a: &ClassInstanceRef<Component>, b: &ClassInstanceRef<Component>) -> JvmResult<bool> {
+        Ok(match (&a.instance, &b.instance) {
+            (Some(x), Some(y)) => x.equals(&**y)?,
+            _ => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use jvm::{ClassInstanceRef, JavaError, Jvm, Result as JvmResult};
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{
+        classes::{net::wie::WIPIKeyCode, org::kwis::msp::lwc::Component},
+        get_protos,
+    };
+
+    async fn new_shell(jvm: &Jvm) -> JvmResult<ClassInstanceRef<Component>> {
+        Ok(jvm.new_class("org/kwis/msp/lwc/ShellComponent", "(IIII)V", (0, 0, 100, 80)).await?.into())
+    }
+
+    async fn new_field(jvm: &Jvm) -> JvmResult<ClassInstanceRef<Component>> {
+        Ok(jvm
+            .new_class("org/kwis/msp/lwc/TextFieldComponent", "(Ljava/lang/String;I)V", [None.into(), 2.into()])
+            .await?
+            .into())
+    }
+
+    async fn add(jvm: &Jvm, container: &ClassInstanceRef<Component>, component: &ClassInstanceRef<Component>) -> JvmResult<i32> {
+        jvm.invoke_virtual(
+            container,
+            "org/kwis/msp/lwc/ContainerComponent",
+            "addComponent",
+            "(Lorg/kwis/msp/lwc/Component;)I",
+            (component.clone(),),
+        )
+        .await
+    }
+
+    async fn count(jvm: &Jvm, container: &ClassInstanceRef<Component>) -> JvmResult<i32> {
+        jvm.invoke_virtual(container, "org/kwis/msp/lwc/ContainerComponent", "getComponentCount", "()I", ())
+            .await
+    }
+
+    async fn has_focus(jvm: &Jvm, component: &ClassInstanceRef<Component>) -> JvmResult<bool> {
+        jvm.invoke_virtual(component, "org/kwis/msp/lwc/Component", "hasFocus", "()Z", ()).await
+    }
+
+    async fn press(jvm: &Jvm, component: &ClassInstanceRef<Component>, key: WIPIKeyCode) -> JvmResult<bool> {
+        jvm.invoke_virtual(component, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (1, key as i32))
+            .await
+    }
+
+    async fn is_parent(jvm: &Jvm, component: &ClassInstanceRef<Component>, container: &ClassInstanceRef<Component>) -> JvmResult<bool> {
+        let parent: ClassInstanceRef<Component> = jvm
+            .invoke_virtual(
+                component,
+                "org/kwis/msp/lwc/Component",
+                "getParent",
+                "()Lorg/kwis/msp/lwc/ContainerComponent;",
+                (),
+            )
+            .await?;
+
+        jvm.invoke_virtual(container, "java/lang/Object", "equals", "(Ljava/lang/Object;)Z", (parent,))
+            .await
+    }
+
+    #[test]
+    fn test_remove_component() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let shell = new_shell(&jvm).await?;
+            let _: () = jvm.invoke_virtual(&shell, "org/kwis/msp/lwc/Component", "setFocus", "()V", ()).await?;
+
+            let first = new_field(&jvm).await?;
+            let second = new_field(&jvm).await?;
+            let third = new_field(&jvm).await?;
+            for component in [&first, &second, &third] {
+                add(&jvm, &shell, component).await?;
+            }
+
+            // removing an unfocused component leaves the focus alone and detaches it
+            let _: () = jvm
+                .invoke_virtual(
+                    &shell,
+                    "org/kwis/msp/lwc/ContainerComponent",
+                    "removeComponent",
+                    "(Lorg/kwis/msp/lwc/Component;)V",
+                    (second.clone(),),
+                )
+                .await?;
+            assert_eq!(count(&jvm, &shell).await?, 2);
+            assert!(!is_parent(&jvm, &second, &shell).await?);
+            assert!(has_focus(&jvm, &first).await?);
+
+            // components that are not children are ignored
+            let _: () = jvm
+                .invoke_virtual(
+                    &shell,
+                    "org/kwis/msp/lwc/ContainerComponent",
+                    "removeComponent",
+                    "(Lorg/kwis/msp/lwc/Component;)V",
+                    (second.clone(),),
+                )
+                .await?;
+            assert_eq!(count(&jvm, &shell).await?, 2);
+
+            let invalid: JvmResult<()> = jvm
+                .invoke_virtual(&shell, "org/kwis/msp/lwc/ContainerComponent", "removeComponent", "(I)V", (5,))
+                .await;
+            let Err(JavaError::JavaException(exception)) = invalid else {
+                panic!("removing an invalid index succeeded");
+            };
+            assert!(jvm.is_instance(&*exception, "java/lang/ArrayIndexOutOfBoundsException"));
+
+            // removing the focused component loses its focus and hands it to the next one
+            let _: () = jvm
+                .invoke_virtual(
+                    &shell,
+                    "org/kwis/msp/lwc/ContainerComponent",
+                    "removeComponent",
+                    "(Lorg/kwis/msp/lwc/Component;)V",
+                    (first.clone(),),
+                )
+                .await?;
+            assert_eq!(count(&jvm, &shell).await?, 1);
+            assert!(!has_focus(&jvm, &first).await?);
+            assert!(has_focus(&jvm, &third).await?);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_component_moves_between_containers() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let old_shell = new_shell(&jvm).await?;
+            let new_shell = new_shell(&jvm).await?;
+            let field = new_field(&jvm).await?;
+
+            add(&jvm, &old_shell, &field).await?;
+            assert_eq!(add(&jvm, &new_shell, &field).await?, 0);
+
+            assert_eq!(count(&jvm, &old_shell).await?, 0);
+            assert_eq!(count(&jvm, &new_shell).await?, 1);
+            assert!(is_parent(&jvm, &field, &new_shell).await?);
+
+            let focused: ClassInstanceRef<Component> = jvm
+                .invoke_virtual(
+                    &old_shell,
+                    "org/kwis/msp/lwc/ContainerComponent",
+                    "getFocusedComponent",
+                    "()Lorg/kwis/msp/lwc/Component;",
+                    (),
+                )
+                .await?;
+            assert!(focused.is_null());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_focus_traversal_across_nested_containers() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let outer = new_shell(&jvm).await?;
+            let _: () = jvm.invoke_virtual(&outer, "org/kwis/msp/lwc/Component", "setFocus", "()V", ()).await?;
+
+            let top = new_field(&jvm).await?;
+            let inner = new_shell(&jvm).await?;
+            let inner_first = new_field(&jvm).await?;
+            let inner_second = new_field(&jvm).await?;
+            let bottom = new_field(&jvm).await?;
+
+            add(&jvm, &inner, &inner_first).await?;
+            add(&jvm, &inner, &inner_second).await?;
+            for component in [&top, &inner, &bottom] {
+                add(&jvm, &outer, component).await?;
+            }
+            assert!(has_focus(&jvm, &top).await?);
+            assert!(!has_focus(&jvm, &inner_first).await?);
+
+            // entering the inner container focuses its own focused child
+            assert!(press(&jvm, &outer, WIPIKeyCode::DOWN).await?);
+            assert!(!has_focus(&jvm, &top).await?);
+            assert!(has_focus(&jvm, &inner_first).await?);
+
+            // the inner container moves within itself first
+            assert!(press(&jvm, &outer, WIPIKeyCode::DOWN).await?);
+            assert!(!has_focus(&jvm, &inner_first).await?);
+            assert!(has_focus(&jvm, &inner_second).await?);
+
+            // and hands the key back to the outer one at its end
+            assert!(press(&jvm, &outer, WIPIKeyCode::DOWN).await?);
+            assert!(!has_focus(&jvm, &inner_second).await?);
+            assert!(has_focus(&jvm, &bottom).await?);
+            assert!(!press(&jvm, &outer, WIPIKeyCode::DOWN).await?);
+
+            // coming back up restores the inner container's last focused child
+            assert!(press(&jvm, &outer, WIPIKeyCode::UP).await?);
+            assert!(!has_focus(&jvm, &bottom).await?);
+            assert!(has_focus(&jvm, &inner_second).await?);
+
+            // setFocus on a nested component focuses the whole path to it
+            let _: () = jvm.invoke_virtual(&top, "org/kwis/msp/lwc/Component", "setFocus", "()V", ()).await?;
+            let _: () = jvm
+                .invoke_virtual(&inner_first, "org/kwis/msp/lwc/Component", "setFocus", "()V", ())
+                .await?;
+            assert!(!has_focus(&jvm, &top).await?);
+            assert!(has_focus(&jvm, &inner_first).await?);
+            assert!(!has_focus(&jvm, &inner_second).await?);
+
+            Ok(())
+        })
+    }
 }
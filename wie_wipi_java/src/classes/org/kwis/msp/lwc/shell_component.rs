@@ -1,16 +1,24 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::{ClassAccessFlags, MethodAccessFlags};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
-use crate::classes::org::kwis::msp::lwc::Component;
+use crate::classes::{
+    net::wie::ShellCard,
+    org::kwis::msp::{
+        lcdui::{Display, Graphics},
+        lwc::Component,
+    },
+};
 
 // class org.kwis.msp.lwc.ShellComponent
 pub struct ShellComponent;
 
 impl ShellComponent {
+    const BACKGROUND_COLOR: i32 = 0xffffff;
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "org/kwis/msp/lwc/ShellComponent",
@@ -25,18 +33,37 @@ impl ShellComponent {
                     Self::set_work_component,
                     MethodAccessFlags::PUBLIC,
                 ),
+                JavaMethodProto::new(
+                    "getWorkComponent",
+                    "()Lorg/kwis/msp/lwc/Component;",
+                    Self::get_work_component,
+                    MethodAccessFlags::PUBLIC,
+                ),
                 JavaMethodProto::new("show", "()V", Self::show, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("hide", "()V", Self::hide, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("repaint", "()V", Self::repaint, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, MethodAccessFlags::PROTECTED),
+            ],
+            fields: vec![
+                JavaFieldProto::new("card", "Lorg/kwis/msp/lcdui/Card;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("workComponent", "Lorg/kwis/msp/lwc/Component;", FieldAccessFlags::PRIVATE),
             ],
-            fields: vec![],
             access_flags: ClassAccessFlags::PUBLIC,
         }
     }
 
     async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
-        tracing::debug!("stub org.kwis.msp.lwc.ShellComponent::<init>({this:?})");
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::<init>({this:?})");
 
-        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "<init>", "()V", ()).await?;
+        let display: ClassInstanceRef<Display> = jvm
+            .invoke_static("org/kwis/msp/lcdui/Display", "getDefaultDisplay", "()Lorg/kwis/msp/lcdui/Display;", [])
+            .await?;
+        let width: i32 = jvm.invoke_virtual(&display, "org/kwis/msp/lcdui/Display", "getWidth", "()I", []).await?;
+        let height: i32 = jvm.invoke_virtual(&display, "org/kwis/msp/lcdui/Display", "getHeight", "()I", []).await?;
+
+        let _: () = jvm
+            .invoke_special(&this, "org/kwis/msp/lwc/ShellComponent", "<init>", "(IIII)V", (0, 0, width, height))
+            .await?;
 
         Ok(())
     }
@@ -50,35 +77,374 @@ impl ShellComponent {
         width: i32,
         height: i32,
     ) -> JvmResult<()> {
-        tracing::debug!("stub org.kwis.msp.lwc.ShellComponent::<init>({this:?}, {x}, {y}, {width}, {height})");
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::<init>({this:?}, {x}, {y}, {width}, {height})");
 
         let _: () = jvm
             .invoke_special(&this, "org/kwis/msp/lwc/ContainerComponent", "<init>", "()V", ())
             .await?;
+        let _: () = jvm
+            .invoke_virtual(&this, "org/kwis/msp/lwc/Component", "configure", "(IIIII)V", (x, y, width, height, 0))
+            .await?;
 
         Ok(())
     }
 
+    // the work component fills the whole shell and takes the focus
     async fn set_work_component(
-        _: &Jvm,
+        jvm: &Jvm,
         _: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        mut this: ClassInstanceRef<Self>,
         component: ClassInstanceRef<Component>,
     ) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.ShellComponent::setWorkComponent({this:?}, {component:?})");
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::setWorkComponent({this:?}, {component:?})");
+
+        let work_component: ClassInstanceRef<Component> = jvm.get_field(&this, "workComponent", "Lorg/kwis/msp/lwc/Component;").await?;
+        if !work_component.is_null() {
+            let _: () = jvm
+                .invoke_virtual(
+                    &this,
+                    "org/kwis/msp/lwc/ContainerComponent",
+                    "removeComponent",
+                    "(Lorg/kwis/msp/lwc/Component;)V",
+                    (work_component,),
+                )
+                .await?;
+        }
+
+        jvm.put_field(&mut this, "workComponent", "Lorg/kwis/msp/lwc/Component;", component.clone())
+            .await?;
+        if component.is_null() {
+            return Ok(());
+        }
+
+        let width: i32 = jvm.get_field(&this, "w", "I").await?;
+        let height: i32 = jvm.get_field(&this, "h", "I").await?;
+
+        let _: i32 = jvm
+            .invoke_virtual(
+                &this,
+                "org/kwis/msp/lwc/ContainerComponent",
+                "addComponent",
+                "(Lorg/kwis/msp/lwc/Component;)I",
+                (component.clone(),),
+            )
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                &component,
+                "org/kwis/msp/lwc/Component",
+                "configure",
+                "(IIIII)V",
+                (0, 0, width, height, 0),
+            )
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&component, "org/kwis/msp/lwc/Component", "setFocus", "()V", ())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_work_component(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Component>> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::getWorkComponent({this:?})");
+
+        jvm.get_field(&this, "workComponent", "Lorg/kwis/msp/lwc/Component;").await
+    }
+
+    async fn show(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::show({this:?})");
+
+        let card: ClassInstanceRef<ShellCard> = jvm.get_field(&this, "card", "Lorg/kwis/msp/lcdui/Card;").await?;
+        if !card.is_null() {
+            return Ok(());
+        }
+
+        let card: ClassInstanceRef<ShellCard> = jvm
+            .new_class("net/wie/ShellCard", "(Lorg/kwis/msp/lwc/ShellComponent;)V", (this.clone(),))
+            .await?
+            .into();
+        jvm.put_field(&mut this, "card", "Lorg/kwis/msp/lcdui/Card;", card.clone()).await?;
+
+        let display: ClassInstanceRef<Display> = jvm
+            .invoke_static("org/kwis/msp/lcdui/Display", "getDefaultDisplay", "()Lorg/kwis/msp/lcdui/Display;", [])
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                &display,
+                "org/kwis/msp/lcdui/Display",
+                "pushCard",
+                "(Lorg/kwis/msp/lcdui/Card;)V",
+                (card,),
+            )
+            .await?;
 
         Ok(())
     }
 
-    async fn show(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.ShellComponent::show({this:?})");
+    async fn hide(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::hide({this:?})");
+
+        let card: ClassInstanceRef<ShellCard> = jvm.get_field(&this, "card", "Lorg/kwis/msp/lcdui/Card;").await?;
+        if card.is_null() {
+            return Ok(());
+        }
+
+        jvm.put_field(&mut this, "card", "Lorg/kwis/msp/lcdui/Card;", None).await?;
+
+        let display: ClassInstanceRef<Display> = jvm
+            .invoke_static("org/kwis/msp/lcdui/Display", "getDefaultDisplay", "()Lorg/kwis/msp/lcdui/Display;", [])
+            .await?;
+        let _: bool = jvm
+            .invoke_virtual(
+                &display,
+                "org/kwis/msp/lcdui/Display",
+                "removeCard",
+                "(Lorg/kwis/msp/lcdui/Card;)Z",
+                (card,),
+            )
+            .await?;
 
         Ok(())
     }
 
-    async fn hide(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.ShellComponent::hide({this:?})");
+    async fn repaint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::repaint({this:?})");
+
+        let card: ClassInstanceRef<ShellCard> = jvm.get_field(&this, "card", "Lorg/kwis/msp/lcdui/Card;").await?;
+        if !card.is_null() {
+            let _: () = jvm.invoke_virtual(&card, "org/kwis/msp/lcdui/Card", "repaint", "()V", ()).await?;
+        }
 
         Ok(())
     }
+
+    async fn paint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::paint({this:?}, {graphics:?})");
+
+        let width: i32 = jvm.get_field(&this, "w", "I").await?;
+        let height: i32 = jvm.get_field(&this, "h", "I").await?;
+
+        let _: () = jvm
+            .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "setColor", "(I)V", (Self::BACKGROUND_COLOR,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&graphics, "org/kwis/msp/lcdui/Graphics", "fillRect", "(IIII)V", (0, 0, width, height))
+            .await?;
+
+        jvm.invoke_special(
+            &this,
+            "org/kwis/msp/lwc/ContainerComponent",
+            "paint",
+            "(Lorg/kwis/msp/lcdui/Graphics;)V",
+            (graphics,),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use java_runtime::classes::java::lang::String;
+    use jvm::{ClassInstanceRef, runtime::JavaLangString};
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{
+        classes::{
+            net::wie::WIPIKeyCode,
+            org::kwis::msp::lwc::{Component, ShellComponent},
+        },
+        get_protos,
+    };
+
+    #[test]
+    fn test_shell_focus_and_text_entry() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let shell: ClassInstanceRef<ShellComponent> = jvm.new_class("org/kwis/msp/lwc/ShellComponent", "(IIII)V", (0, 0, 100, 80)).await?.into();
+            let _: () = jvm.invoke_virtual(&shell, "org/kwis/msp/lwc/Component", "setFocus", "()V", ()).await?;
+
+            // numeric fields, so that keypad keys are entered as is
+            let text: ClassInstanceRef<String> = JavaLangString::from_rust_string(&jvm, "ab").await?.into();
+            let first: ClassInstanceRef<Component> = jvm
+                .new_class("org/kwis/msp/lwc/TextFieldComponent", "(Ljava/lang/String;I)V", (text, 2))
+                .await?
+                .into();
+            let second: ClassInstanceRef<Component> = jvm
+                .new_class("org/kwis/msp/lwc/TextFieldComponent", "(Ljava/lang/String;I)V", [None.into(), 2.into()])
+                .await?
+                .into();
+
+            for component in [&first, &second] {
+                let _: i32 = jvm
+                    .invoke_virtual(
+                        &shell,
+                        "org/kwis/msp/lwc/ContainerComponent",
+                        "addComponent",
+                        "(Lorg/kwis/msp/lwc/Component;)I",
+                        (component.clone(),),
+                    )
+                    .await?;
+            }
+            assert!(
+                jvm.invoke_virtual::<_, bool>(&first, "org/kwis/msp/lwc/Component", "hasFocus", "()Z", ())
+                    .await?
+            );
+            assert!(
+                !jvm.invoke_virtual::<_, bool>(&second, "org/kwis/msp/lwc/Component", "hasFocus", "()Z", ())
+                    .await?
+            );
+
+            // keypad input goes to the focused field at the caret
+            for key in [WIPIKeyCode::LEFT, WIPIKeyCode::NUM1, WIPIKeyCode::RIGHT, WIPIKeyCode::NUM2] {
+                assert!(
+                    jvm.invoke_virtual::<_, bool>(&shell, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (1, key as i32))
+                        .await?
+                );
+            }
+            let string: ClassInstanceRef<String> = jvm
+                .invoke_virtual(&first, "org/kwis/msp/lwc/TextComponent", "getString", "()Ljava/lang/String;", ())
+                .await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &string).await?, "a1b2");
+
+            // unhandled down key moves focus to the next component, up at the top is left unhandled
+            assert!(
+                jvm.invoke_virtual::<_, bool>(&shell, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (1, WIPIKeyCode::DOWN as i32))
+                    .await?
+            );
+            assert!(
+                !jvm.invoke_virtual::<_, bool>(&first, "org/kwis/msp/lwc/Component", "hasFocus", "()Z", ())
+                    .await?
+            );
+            assert!(
+                jvm.invoke_virtual::<_, bool>(&second, "org/kwis/msp/lwc/Component", "hasFocus", "()Z", ())
+                    .await?
+            );
+
+            let _: () = jvm
+                .invoke_virtual(&second, "org/kwis/msp/lwc/TextComponent", "setMaxLength", "(I)V", (1,))
+                .await?;
+            for key in [WIPIKeyCode::NUM3, WIPIKeyCode::NUM4] {
+                let _: bool = jvm
+                    .invoke_virtual(&shell, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (1, key as i32))
+                    .await?;
+            }
+            let string: ClassInstanceRef<String> = jvm
+                .invoke_virtual(&second, "org/kwis/msp/lwc/TextComponent", "getString", "()Ljava/lang/String;", ())
+                .await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &string).await?, "3");
+
+            // removing the focused component hands focus to its neighbour
+            let _: () = jvm
+                .invoke_virtual(&shell, "org/kwis/msp/lwc/ContainerComponent", "removeComponent", "(I)V", (1,))
+                .await?;
+            assert!(
+                jvm.invoke_virtual::<_, bool>(&first, "org/kwis/msp/lwc/Component", "hasFocus", "()Z", ())
+                    .await?
+            );
+            assert_eq!(
+                jvm.invoke_virtual::<_, i32>(&shell, "org/kwis/msp/lwc/ContainerComponent", "getComponentCount", "()I", ())
+                    .await?,
+                1
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_text_field_hangul_entry() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let field: ClassInstanceRef<Component> = jvm
+                .new_class("org/kwis/msp/lwc/TextFieldComponent", "(Ljava/lang/String;I)V", [None.into(), 0.into()])
+                .await?
+                .into();
+
+            // 천지인: ㅎ(88) ㅏ(12) ㄴ(5) ㄱ(4) ㅡ(3) ㄹ(55)
+            let keys = [
+                WIPIKeyCode::NUM8,
+                WIPIKeyCode::NUM8,
+                WIPIKeyCode::NUM1,
+                WIPIKeyCode::NUM2,
+                WIPIKeyCode::NUM5,
+                WIPIKeyCode::NUM4,
+                WIPIKeyCode::NUM3,
+                WIPIKeyCode::NUM5,
+                WIPIKeyCode::NUM5,
+            ];
+            for key in keys {
+                assert!(
+                    jvm.invoke_virtual::<_, bool>(&field, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (1, key as i32))
+                        .await?
+                );
+            }
+
+            // the syllable being composed is part of the string, but the caret only moves once it is committed
+            let string: ClassInstanceRef<String> = jvm
+                .invoke_virtual(&field, "org/kwis/msp/lwc/TextComponent", "getString", "()Ljava/lang/String;", ())
+                .await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &string).await?, "한글");
+            assert_eq!(
+                jvm.invoke_virtual::<_, i32>(&field, "org/kwis/msp/lwc/TextComponent", "getCaretPosition", "()I", ())
+                    .await?,
+                1
+            );
+
+            // other keys commit the composition and are left to the application
+            assert!(
+                !jvm.invoke_virtual::<_, bool>(&field, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (1, WIPIKeyCode::FIRE as i32))
+                    .await?
+            );
+            assert_eq!(
+                jvm.invoke_virtual::<_, i32>(&field, "org/kwis/msp/lwc/TextComponent", "getCaretPosition", "()I", ())
+                    .await?,
+                2
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_text_field_host_text_entry() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let field: ClassInstanceRef<Component> = jvm
+                .new_class("org/kwis/msp/lwc/TextFieldComponent", "(Ljava/lang/String;I)V", [None.into(), 0.into()])
+                .await?
+                .into();
+
+            for key in [WIPIKeyCode::NUM8, WIPIKeyCode::NUM8] {
+                let _: bool = jvm
+                    .invoke_virtual(&field, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (1, key as i32))
+                    .await?;
+            }
+
+            // typed text commits the ㅎ being composed and is inserted as is
+            for chr in "A나".chars() {
+                assert!(
+                    jvm.invoke_virtual::<_, bool>(
+                        &field,
+                        "org/kwis/msp/lwc/Component",
+                        "keyNotify",
+                        "(II)Z",
+                        (Component::KEY_TYPED, chr as i32)
+                    )
+                    .await?
+                );
+            }
+
+            let string: ClassInstanceRef<String> = jvm
+                .invoke_virtual(&field, "org/kwis/msp/lwc/TextComponent", "getString", "()Ljava/lang/String;", ())
+                .await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &string).await?, "ㅎA나");
+            assert_eq!(
+                jvm.invoke_virtual::<_, i32>(&field, "org/kwis/msp/lwc/TextComponent", "getCaretPosition", "()I", ())
+                    .await?,
+                3
+            );
+
+            Ok(())
+        })
+    }
 }
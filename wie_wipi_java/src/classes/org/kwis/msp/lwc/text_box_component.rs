@@ -7,6 +7,8 @@ use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::{lcdui::Graphics, lwc::TextComponent};
+
 // class org.kwis.msp.lwc.TextBoxComponent
 pub struct TextBoxComponent;
 
@@ -16,12 +18,10 @@ impl TextBoxComponent {
             name: "org/kwis/msp/lwc/TextBoxComponent",
             parent_class: Some("org/kwis/msp/lwc/TextComponent"),
             interfaces: vec![],
-            methods: vec![JavaMethodProto::new(
-                "<init>",
-                "(Ljava/lang/String;I)V",
-                Self::init,
-                MethodAccessFlags::PUBLIC,
-            )],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;I)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, MethodAccessFlags::PROTECTED),
+            ],
             fields: vec![],
             access_flags: ClassAccessFlags::PUBLIC,
         }
@@ -30,14 +30,134 @@ impl TextBoxComponent {
     async fn init(
         jvm: &Jvm,
         _: &mut WieJvmContext,
-        this: ClassInstanceRef<TextBoxComponent>,
+        mut this: ClassInstanceRef<TextBoxComponent>,
         data: ClassInstanceRef<String>,
         constraint: i32,
     ) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextBoxComponent::<init>({this:?}, {data:?}, {constraint:?})");
+        tracing::debug!("org.kwis.msp.lwc.TextBoxComponent::<init>({this:?}, {data:?}, {constraint:?})");
 
         let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/TextComponent", "<init>", "()V", ()).await?;
 
+        let im_handler = jvm.new_class("org/kwis/msp/lcdui/InputMethodHandler", "(I)V", (constraint,)).await?;
+        jvm.put_field(&mut this, "imHandler", "Lorg/kwis/msp/lcdui/InputMethodHandler;", im_handler)
+            .await?;
+
+        let _: () = jvm
+            .invoke_virtual(&this, "org/kwis/msp/lwc/TextComponent", "setString", "(Ljava/lang/String;)V", (data,))
+            .await?;
+
         Ok(())
     }
+
+    async fn paint(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<TextBoxComponent>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextBoxComponent::paint({this:?}, {graphics:?})");
+
+        TextComponent::paint_text(jvm, &this, &graphics, true).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{boxed::Box, string::String as RustString};
+
+    use java_runtime::classes::java::lang::String;
+    use jvm::{ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
+    use test_utils::run_jvm_test;
+    use wie_midp::classes::javax::microedition::lcdui::Image as MidpImage;
+    use wie_util::Result;
+
+    use crate::{
+        classes::{
+            net::wie::WIPIKeyCode,
+            org::kwis::msp::lcdui::{Graphics, Image},
+        },
+        get_protos,
+    };
+
+    use super::TextBoxComponent;
+
+    async fn text(jvm: &Jvm, text_box: &ClassInstanceRef<TextBoxComponent>) -> JvmResult<(RustString, i32)> {
+        let string: ClassInstanceRef<String> = jvm
+            .invoke_virtual(text_box, "org/kwis/msp/lwc/TextComponent", "getString", "()Ljava/lang/String;", ())
+            .await?;
+        let caret: i32 = jvm
+            .invoke_virtual(text_box, "org/kwis/msp/lwc/TextComponent", "getCaretPosition", "()I", ())
+            .await?;
+
+        Ok((JavaLangString::to_rust_string(jvm, &string).await?, caret))
+    }
+
+    async fn key(jvm: &Jvm, text_box: &ClassInstanceRef<TextBoxComponent>, r#type: i32, chr: i32) -> JvmResult<bool> {
+        jvm.invoke_virtual(text_box, "org/kwis/msp/lwc/Component", "keyNotify", "(II)Z", (r#type, chr))
+            .await
+    }
+
+    #[test]
+    fn test_text_box_editing() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            // numeric, so that keypad keys are entered as is
+            let initial = JavaLangString::from_rust_string(&jvm, "ab").await?;
+            let text_box: ClassInstanceRef<TextBoxComponent> = jvm
+                .new_class("org/kwis/msp/lwc/TextBoxComponent", "(Ljava/lang/String;I)V", (initial, 2))
+                .await?
+                .into();
+            assert_eq!(text(&jvm, &text_box).await?, ("ab".into(), 2));
+
+            // insertion and deletion happen at the caret
+            assert!(key(&jvm, &text_box, 1, WIPIKeyCode::LEFT as i32).await?);
+            assert!(key(&jvm, &text_box, 1, WIPIKeyCode::NUM5 as i32).await?);
+            assert_eq!(text(&jvm, &text_box).await?, ("a5b".into(), 2));
+            assert!(key(&jvm, &text_box, 1, WIPIKeyCode::CLEAR as i32).await?);
+            assert!(key(&jvm, &text_box, 1, WIPIKeyCode::CLEAR as i32).await?);
+            assert_eq!(text(&jvm, &text_box).await?, ("b".into(), 0));
+            assert!(!key(&jvm, &text_box, 1, WIPIKeyCode::CLEAR as i32).await?);
+            assert!(!key(&jvm, &text_box, 1, WIPIKeyCode::LEFT as i32).await?);
+
+            // text boxes take typed line breaks
+            assert!(key(&jvm, &text_box, 1, WIPIKeyCode::RIGHT as i32).await?);
+            for chr in "\nxy".chars() {
+                assert!(key(&jvm, &text_box, 4, chr as i32).await?);
+            }
+            assert_eq!(text(&jvm, &text_box).await?, ("b\nxy".into(), 4));
+
+            // a shorter max length truncates, and input beyond it is dropped
+            let _: () = jvm
+                .invoke_virtual(&text_box, "org/kwis/msp/lwc/TextComponent", "setMaxLength", "(I)V", (3,))
+                .await?;
+            assert_eq!(text(&jvm, &text_box).await?, ("b\nx".into(), 3));
+            let _ = key(&jvm, &text_box, 1, WIPIKeyCode::NUM7 as i32).await?;
+            assert_eq!(text(&jvm, &text_box).await?, ("b\nx".into(), 3));
+
+            // unfocused text boxes paint a gray border
+            let _: () = jvm
+                .invoke_virtual(&text_box, "org/kwis/msp/lwc/Component", "configure", "(IIIII)V", (0, 0, 40, 30, 0))
+                .await?;
+            let image: ClassInstanceRef<Image> = jvm
+                .invoke_static("org/kwis/msp/lcdui/Image", "createImage", "(II)Lorg/kwis/msp/lcdui/Image;", (40, 30))
+                .await?;
+            let graphics: ClassInstanceRef<Graphics> = jvm
+                .invoke_virtual(&image, "org/kwis/msp/lcdui/Image", "getGraphics", "()Lorg/kwis/msp/lcdui/Graphics;", ())
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &text_box,
+                    "org/kwis/msp/lwc/Component",
+                    "paint",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    (graphics,),
+                )
+                .await?;
+
+            let midp_image = Image::midp_image(&jvm, &image).await?;
+            let pixel = MidpImage::image(&jvm, &midp_image).await?.get_pixel(39, 29);
+            assert_eq!((pixel.r, pixel.g, pixel.b), (0x80, 0x80, 0x80));
+
+            Ok(())
+        })
+    }
 }
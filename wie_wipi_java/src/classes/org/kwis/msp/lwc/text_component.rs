@@ -1,16 +1,31 @@
-use alloc::vec;
+use alloc::{string::String as RustString, vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::String;
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult, runtime::JavaLangString};
 
+use wie_backend::KeyPhase;
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::{
+    net::wie::WIPIKeyCode,
+    org::kwis::msp::{
+        lcdui::{Font, Graphics, InputMethodHandler},
+        lwc::Component,
+    },
+};
+
 // class org.kwis.msp.lwc.TextComponent
 pub struct TextComponent;
 
 impl TextComponent {
+    const PADDING: i32 = 2;
+    const TEXT_COLOR: i32 = 0x000000;
+    const BORDER_COLOR: i32 = 0x808080;
+    const FOCUSED_BORDER_COLOR: i32 = 0x0000ff;
+    const ANCHOR_TOP_LEFT: i32 = 20;
+
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "org/kwis/msp/lwc/TextComponent",
@@ -20,18 +35,23 @@ impl TextComponent {
                 JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PROTECTED),
                 JavaMethodProto::new("setString", "(Ljava/lang/String;)V", Self::set_string, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("setMaxLength", "(I)V", Self::set_max_length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMaxLength", "()I", Self::get_max_length, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("getString", "()Ljava/lang/String;", Self::get_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getCaretPosition", "()I", Self::get_caret_position, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, MethodAccessFlags::PROTECTED),
             ],
             fields: vec![
                 JavaFieldProto::new("m_cPos", "I", FieldAccessFlags::PROTECTED),
                 JavaFieldProto::new("imHandler", "Lorg/kwis/msp/lcdui/InputMethodHandler;", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("text", "Ljava/lang/String;", FieldAccessFlags::PRIVATE),
+                JavaFieldProto::new("maxLength", "I", FieldAccessFlags::PRIVATE),
             ],
             access_flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::ABSTRACT,
         }
     }
 
     async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<TextComponent>) -> JvmResult<()> {
-        tracing::debug!("stub org.kwis.msp.lwc.TextComponent::<init>({this:?})");
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::<init>({this:?})");
 
         let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "<init>", "()V", ()).await?;
 
@@ -40,27 +60,291 @@ impl TextComponent {
 
         jvm.put_field(&mut this, "imHandler", "Lorg/kwis/msp/lcdui/InputMethodHandler;", im_handler)
             .await?;
+        jvm.put_field(&mut this, "maxLength", "I", i32::MAX).await?;
+        Self::set_text(jvm, &mut this, &[]).await?;
 
         Ok(())
     }
 
-    async fn set_max_length(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>, max_length: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextComponent::<init>({this:?}, {max_length})");
+    async fn set_max_length(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<TextComponent>, max_length: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::setMaxLength({this:?}, {max_length})");
+
+        if max_length < 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "max length is negative").await);
+        }
+
+        jvm.put_field(&mut this, "maxLength", "I", max_length).await?;
+
+        let text = Self::text(jvm, &this).await?;
+        if text.len() > max_length as usize {
+            Self::set_text(jvm, &mut this, &text[..max_length as usize]).await?;
+        }
 
         Ok(())
     }
 
-    async fn set_string(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>, data: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextComponent::setString({this:?}, {data:?})");
+    async fn get_max_length(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::getMaxLength({this:?})");
 
-        Ok(())
+        jvm.get_field(&this, "maxLength", "I").await
+    }
+
+    async fn set_string(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<TextComponent>,
+        data: ClassInstanceRef<String>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::setString({this:?}, {data:?})");
+
+        let text: Vec<char> = if data.is_null() {
+            Vec::new()
+        } else {
+            JavaLangString::to_rust_string(jvm, &data).await?.chars().collect()
+        };
+        let max_length: i32 = jvm.get_field(&this, "maxLength", "I").await?;
+
+        Self::set_text(jvm, &mut this, &text[..text.len().min(max_length as usize)]).await
     }
 
     async fn get_string(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>) -> JvmResult<ClassInstanceRef<String>> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextComponent::<init>({this:?})");
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::getString({this:?})");
+
+        let (text, _) = Self::text_with_composing(jvm, &this).await?;
+
+        Ok(JavaLangString::from_rust_string(jvm, &text.iter().collect::<RustString>()).await?.into())
+    }
+
+    async fn get_caret_position(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::getCaretPosition({this:?})");
+
+        jvm.get_field(&this, "m_cPos", "I").await
+    }
+
+    // keypad keys go through the input method, left and right move the caret and clear deletes the character before it.
+    // characters typed on the host keyboard are inserted as is
+    async fn key_notify(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<TextComponent>, r#type: i32, chr: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::keyNotify({this:?}, {type}, {chr})");
+
+        let phase = match r#type {
+            Component::KEY_TYPED => return Self::type_char(jvm, &mut this, chr).await,
+            Component::KEY_PRESSED => KeyPhase::Pressed,
+            Component::KEY_REPEATED => KeyPhase::Repeated,
+            Component::KEY_RELEASED => KeyPhase::Released,
+            _ => return Ok(false),
+        };
+        let Some(key) = WIPIKeyCode::from_raw(chr) else {
+            return Ok(false);
+        };
+
+        let text = Self::text(jvm, &this).await?;
+        let max_length: i32 = jvm.get_field(&this, "maxLength", "I").await?;
+
+        let mut im_handler: ClassInstanceRef<InputMethodHandler> =
+            jvm.get_field(&this, "imHandler", "Lorg/kwis/msp/lcdui/InputMethodHandler;").await?;
+        let room = (max_length as usize).saturating_sub(text.len());
+        let Some(result) = InputMethodHandler::handle_key(jvm, context, &mut im_handler, phase, key.to_key_code(), room).await? else {
+            return Ok(false);
+        };
+
+        if !result.committed.is_empty() {
+            let cursor = Self::cursor(jvm, &this, text.len()).await?;
+            Self::insert_text(jvm, &mut this, cursor, &result.committed.chars().collect::<Vec<_>>()).await?;
+        }
+        if result.consumed || !result.committed.is_empty() {
+            let _: () = jvm.invoke_virtual(&this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
+        }
+        if result.consumed || phase == KeyPhase::Released {
+            return Ok(result.consumed);
+        }
+
+        let mut text = Self::text(jvm, &this).await?;
+        let cursor = Self::cursor(jvm, &this, text.len()).await?;
+
+        match key {
+            WIPIKeyCode::LEFT if cursor > 0 => Self::set_cursor(jvm, &mut this, cursor - 1).await?,
+            WIPIKeyCode::RIGHT if cursor < text.len() => Self::set_cursor(jvm, &mut this, cursor + 1).await?,
+            WIPIKeyCode::CLEAR if cursor > 0 => {
+                text.remove(cursor - 1);
+                Self::update_text(jvm, &mut this, &text, cursor - 1).await?;
+            }
+            _ => return Ok(false),
+        }
+
+        let _: () = jvm.invoke_virtual(&this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
+
+        Ok(true)
+    }
+
+    // finishes the composition and inserts the character at the caret
+    async fn type_char(jvm: &Jvm, this: &mut ClassInstanceRef<TextComponent>, chr: i32) -> JvmResult<bool> {
+        let Some(chr) = char::from_u32(chr as u32) else {
+            return Ok(false);
+        };
+
+        let mut im_handler: ClassInstanceRef<InputMethodHandler> =
+            jvm.get_field(this, "imHandler", "Lorg/kwis/msp/lcdui/InputMethodHandler;").await?;
+        let mut chars: Vec<char> = InputMethodHandler::commit(jvm, &mut im_handler).await?.chars().collect();
+        chars.push(chr);
+
+        let text = Self::text(jvm, this).await?;
+        let cursor = Self::cursor(jvm, this, text.len()).await?;
+        Self::insert_text(jvm, this, cursor, &chars).await?;
+        let _: () = jvm.invoke_virtual(this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
+
+        Ok(true)
+    }
+
+    // inserts as much of the given text as max length allows, leaving the caret after the inserted part
+    pub(crate) async fn insert_text<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, index: usize, chars: &[char]) -> JvmResult<()> {
+        let mut text = Self::text(jvm, this).await?;
+        let max_length: i32 = jvm.get_field(this, "maxLength", "I").await?;
+
+        let index = index.min(text.len());
+        let count = chars.len().min((max_length as usize).saturating_sub(text.len()));
+        text.splice(index..index, chars[..count].iter().copied());
+
+        Self::update_text(jvm, this, &text, index + count).await
+    }
+
+    pub(crate) async fn text<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<Vec<char>> {
+        let text: ClassInstanceRef<String> = jvm.get_field(this, "text", "Ljava/lang/String;").await?;
+
+        Ok(JavaLangString::to_rust_string(jvm, &text).await?.chars().collect())
+    }
+
+    // draws the border, the text and the caret if focused. single line text scrolls to keep the caret visible
+    pub(crate) async fn paint_text<T>(
+        jvm: &Jvm,
+        this: &ClassInstanceRef<T>,
+        graphics: &ClassInstanceRef<Graphics>,
+        multiline: bool,
+    ) -> JvmResult<()> {
+        let width: i32 = jvm.get_field(this, "w", "I").await?;
+        let height: i32 = jvm.get_field(this, "h", "I").await?;
+        let focused: bool = jvm.get_field(this, "focused", "Z").await?;
+
+        let (text, cursor) = Self::text_with_composing(jvm, this).await?;
+
+        let border_color = if focused { Self::FOCUSED_BORDER_COLOR } else { Self::BORDER_COLOR };
+        let _: () = jvm
+            .invoke_virtual(graphics, "org/kwis/msp/lcdui/Graphics", "setColor", "(I)V", (border_color,))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(
+                graphics,
+                "org/kwis/msp/lcdui/Graphics",
+                "drawRect",
+                "(IIII)V",
+                (0, 0, width - 1, height - 1),
+            )
+            .await?;
+
+        let font: ClassInstanceRef<Font> = jvm
+            .invoke_virtual(graphics, "org/kwis/msp/lcdui/Graphics", "getFont", "()Lorg/kwis/msp/lcdui/Font;", ())
+            .await?;
+        let font_height: i32 = jvm.invoke_virtual(&font, "org/kwis/msp/lcdui/Font", "getHeight", "()I", ()).await?;
+
+        let lines: Vec<&[char]> = if multiline {
+            text.split(|&x| x == '\n').collect()
+        } else {
+            vec![&text[..]]
+        };
+
+        // line and column of the caret
+        let mut cursor_line = 0;
+        let mut cursor_column = cursor;
+        for line in &lines {
+            if cursor_column <= line.len() {
+                break;
+            }
+            cursor_column -= line.len() + 1;
+            cursor_line += 1;
+        }
+
+        let cursor_x = Self::string_width(jvm, &font, &lines[cursor_line][..cursor_column]).await?;
+        let scroll = if multiline {
+            0
+        } else {
+            (cursor_x - (width - Self::PADDING * 2 - 1)).max(0)
+        };
+        let top = if multiline { Self::PADDING } else { (height - font_height) / 2 };
+
+        let _: () = jvm
+            .invoke_virtual(graphics, "org/kwis/msp/lcdui/Graphics", "setColor", "(I)V", (Self::TEXT_COLOR,))
+            .await?;
+        for (i, line) in lines.iter().enumerate() {
+            let string = JavaLangString::from_rust_string(jvm, &line.iter().collect::<RustString>()).await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    graphics,
+                    "org/kwis/msp/lcdui/Graphics",
+                    "drawString",
+                    "(Ljava/lang/String;III)V",
+                    (string, Self::PADDING - scroll, top + i as i32 * font_height, Self::ANCHOR_TOP_LEFT),
+                )
+                .await?;
+        }
+
+        if focused {
+            let x = Self::PADDING - scroll + cursor_x;
+            let y = top + cursor_line as i32 * font_height;
+            let _: () = jvm
+                .invoke_virtual(
+                    graphics,
+                    "org/kwis/msp/lcdui/Graphics",
+                    "drawLine",
+                    "(IIII)V",
+                    (x, y, x, y + font_height - 1),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // text as displayed, with the input method's composition at the caret, and the caret after it
+    async fn text_with_composing<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<(Vec<char>, usize)> {
+        let mut text = Self::text(jvm, this).await?;
+        let cursor = Self::cursor(jvm, this, text.len()).await?;
+
+        let im_handler: ClassInstanceRef<InputMethodHandler> = jvm.get_field(this, "imHandler", "Lorg/kwis/msp/lcdui/InputMethodHandler;").await?;
+        let composing = InputMethodHandler::composing(jvm, &im_handler).await?;
+        let count = composing.chars().count();
+        text.splice(cursor..cursor, composing.chars());
+
+        Ok((text, cursor + count))
+    }
+
+    async fn string_width(jvm: &Jvm, font: &ClassInstanceRef<Font>, chars: &[char]) -> JvmResult<i32> {
+        let string = JavaLangString::from_rust_string(jvm, &chars.iter().collect::<RustString>()).await?;
+
+        jvm.invoke_virtual(font, "org/kwis/msp/lcdui/Font", "stringWidth", "(Ljava/lang/String;)I", (string,))
+            .await
+    }
+
+    async fn cursor<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, length: usize) -> JvmResult<usize> {
+        let cursor: i32 = jvm.get_field(this, "m_cPos", "I").await?;
+
+        Ok((cursor.max(0) as usize).min(length))
+    }
+
+    async fn set_cursor<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, cursor: usize) -> JvmResult<()> {
+        jvm.put_field(this, "m_cPos", "I", cursor as i32).await
+    }
+
+    async fn set_text<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, text: &[char]) -> JvmResult<()> {
+        Self::update_text(jvm, this, text, text.len()).await?;
+
+        let _: () = jvm.invoke_virtual(this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await?;
+
+        Ok(())
+    }
 
-        let result = JavaLangString::from_rust_string(jvm, "temp").await?;
+    async fn update_text<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, text: &[char], cursor: usize) -> JvmResult<()> {
+        let string = JavaLangString::from_rust_string(jvm, &text.iter().collect::<RustString>()).await?;
 
-        Ok(result.into())
+        jvm.put_field(this, "text", "Ljava/lang/String;", string).await?;
+        Self::set_cursor(jvm, this, cursor).await
     }
 }
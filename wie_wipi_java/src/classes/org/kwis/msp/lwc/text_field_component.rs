@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{string::String as RustString, vec, vec::Vec};
 
 use java_class_proto::JavaMethodProto;
 use java_constants::{ClassAccessFlags, MethodAccessFlags};
@@ -7,6 +7,8 @@ use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::{lcdui::Graphics, lwc::TextComponent};
+
 // class org.kwis.msp.lwc.TextFieldComponent
 pub struct TextFieldComponent;
 
@@ -20,6 +22,7 @@ impl TextFieldComponent {
                 JavaMethodProto::new("<init>", "(Ljava/lang/String;I)V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("insert", "([CIII)V", Self::insert, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new("setString", "(Ljava/lang/String;)V", Self::set_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, MethodAccessFlags::PROTECTED),
             ],
             fields: vec![],
             access_flags: ClassAccessFlags::PUBLIC,
@@ -29,29 +32,55 @@ impl TextFieldComponent {
     async fn init(
         jvm: &Jvm,
         _: &mut WieJvmContext,
-        this: ClassInstanceRef<TextFieldComponent>,
+        mut this: ClassInstanceRef<TextFieldComponent>,
         data: ClassInstanceRef<String>,
         constraint: i32,
     ) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextFieldComponent::<init>({this:?}, {data:?}, {constraint:?})");
+        tracing::debug!("org.kwis.msp.lwc.TextFieldComponent::<init>({this:?}, {data:?}, {constraint:?})");
 
         let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/TextComponent", "<init>", "()V", ()).await?;
 
+        let im_handler = jvm.new_class("org/kwis/msp/lcdui/InputMethodHandler", "(I)V", (constraint,)).await?;
+        jvm.put_field(&mut this, "imHandler", "Lorg/kwis/msp/lcdui/InputMethodHandler;", im_handler)
+            .await?;
+
+        let _: () = jvm
+            .invoke_virtual(&this, "org/kwis/msp/lwc/TextComponent", "setString", "(Ljava/lang/String;)V", (data,))
+            .await?;
+
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn insert(
-        _: &Jvm,
+        jvm: &Jvm,
         _: &mut WieJvmContext,
-        this: ClassInstanceRef<TextFieldComponent>,
+        mut this: ClassInstanceRef<TextFieldComponent>,
         data: ClassInstanceRef<Array<u16>>,
         offset: i32,
         length: i32,
         index: i32,
     ) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextFieldComponent::insert({this:?}, {data:?}, {offset}, {length}, {index})");
+        tracing::debug!("org.kwis.msp.lwc.TextFieldComponent::insert({this:?}, {data:?}, {offset}, {length}, {index})");
 
-        Ok(())
+        let data_length = jvm.array_length(&data).await? as i32;
+        if offset < 0 || length < 0 || offset > data_length - length {
+            return Err(jvm
+                .exception("java/lang/ArrayIndexOutOfBoundsException", "invalid offset or length")
+                .await);
+        }
+
+        let text = TextComponent::text(jvm, &this).await?;
+        if index < 0 || index as usize > text.len() {
+            return Err(jvm.exception("java/lang/StringIndexOutOfBoundsException", "invalid index").await);
+        }
+
+        let chars: Vec<u16> = jvm.load_array(&data, offset as _, length as _).await?;
+        let chars = RustString::from_utf16_lossy(&chars).chars().collect::<Vec<_>>();
+
+        TextComponent::insert_text(jvm, &mut this, index as _, &chars).await?;
+
+        jvm.invoke_virtual(&this, "org/kwis/msp/lwc/Component", "repaint", "()V", ()).await
     }
 
     async fn set_string(
@@ -63,4 +92,15 @@ impl TextFieldComponent {
         jvm.invoke_special(&this, "org/kwis/msp/lwc/TextComponent", "setString", "(Ljava/lang/String;)V", (data,))
             .await
     }
+
+    async fn paint(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<TextFieldComponent>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextFieldComponent::paint({this:?}, {graphics:?})");
+
+        TextComponent::paint_text(jvm, &this, &graphics, false).await
+    }
 }
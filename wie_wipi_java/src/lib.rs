@@ -6,8 +6,9 @@ pub mod classes;
 
 use wie_jvm_support::WieJavaClassProto;
 
-pub fn get_protos() -> [WieJavaClassProto; 42] {
+pub fn get_protos() -> [WieJavaClassProto; 47] {
     [
+        crate::classes::org::kwis::msf::io::Message::as_proto(),
         crate::classes::org::kwis::msf::io::Network::as_proto(),
         crate::classes::org::kwis::msf::io::SchemeNotFoundException::as_proto(),
         crate::classes::org::kwis::msf::io::Socket::as_proto(),
@@ -48,7 +49,11 @@ pub fn get_protos() -> [WieJavaClassProto; 42] {
         crate::classes::org::kwis::msp::media::Vibrator::as_proto(),
         crate::classes::org::kwis::msp::media::Volume::as_proto(),
         crate::classes::net::wie::CardCanvas::as_proto(),
+        crate::classes::net::wie::ShellCard::as_proto(),
         crate::classes::net::wie::WIPIFileOutputStream::as_proto(),
         crate::classes::net::wie::WIPIMIDlet::as_proto(),
+        crate::classes::net::wie::WIPISocket::as_proto(),
+        crate::classes::net::wie::WIPISocketInputStream::as_proto(),
+        crate::classes::net::wie::WIPISocketOutputStream::as_proto(),
     ]
 }